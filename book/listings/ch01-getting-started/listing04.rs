@@ -1,5 +1,5 @@
 use mun_runtime::{invoke_fn, RuntimeBuilder};
-use std::{cell::RefCell, env, rc::Rc};
+use std::env;
 
 fn main() {
     let lib_path = env::args().nth(1).expect("Expected path to a Mun library.");
@@ -10,11 +10,11 @@ fn main() {
 
     loop {
         {
-            let runtime_ref = runtime.borrow();
+            let runtime_ref = runtime.read();
             let arg: i64 = invoke_fn!(runtime_ref, "arg").unwrap();
             let result: i64 = invoke_fn!(runtime_ref, "fibonacci", arg).unwrap();
             println!("fibonacci({}) = {}", arg, result);
         }
-        runtime.borrow_mut().update();
+        runtime.write().update();
     }
 }
@@ -1,5 +1,4 @@
 use mun_runtime::{invoke_fn, RuntimeBuilder};
-use std::{cell::RefCell, rc::Rc};
 
 extern "C" fn random() -> i64 {
     let result = std::time::Instant::now().elapsed().subsec_nanos() as i64;
@@ -13,7 +12,7 @@ fn main() {
         .spawn()
         .expect("Failed to spawn Runtime");
 
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
     let result: bool = invoke_fn!(runtime_ref, "random_bool").unwrap();
     println!("random_bool: {}", result);
 }
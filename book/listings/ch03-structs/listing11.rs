@@ -1,6 +1,6 @@
 # extern crate mun_runtime;
 use mun_runtime::{invoke_fn, RuntimeBuilder, StructRef};
-use std::{cell::RefCell, env, rc::Rc};
+use std::env;
 
 fn main() {
     let lib_path = env::args().nth(1).expect("Expected path to a Mun library.");
@@ -9,7 +9,7 @@ fn main() {
         .spawn()
         .expect("Failed to spawn Runtime");
 
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
     let a: StructRef = invoke_fn!(runtime_ref, "vector2_new", -1.0f32, 1.0f32).unwrap();
     let b: StructRef = invoke_fn!(runtime_ref, "vector2_new", 1.0f32, -1.0f32).unwrap();
     let added: StructRef = invoke_fn!(runtime_ref, "vector2_add", a, b).unwrap();
@@ -1,6 +1,6 @@
 # extern crate mun_runtime;
 # use mun_runtime::{invoke_fn, RuntimeBuilder, StructRef};
-# use std::{cell::RefCell, env, rc::Rc};
+# use std::env;
 #
 # fn main() {
 #     let lib_path = env::args().nth(1).expect("Expected path to a Mun library.");
@@ -10,7 +10,7 @@
 #             .spawn()
 #             .expect("Failed to spawn Runtime");
 #
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
     let mut xy: StructRef = invoke_fn!(runtime_ref, "vector2_new", -1.0f32, 1.0f32).unwrap();
     let x: f32 = xy.get("x").unwrap();
     xy.set("x", x * x).unwrap();
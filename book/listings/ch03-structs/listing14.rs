@@ -15,7 +15,7 @@ fn main() {
         .expect("Failed to spawn Runtime");
 
     let ctx = {
-        let runtime_ref = runtime.borrow();
+        let runtime_ref = runtime.read();
         let ctx: StructRef = invoke_fn!(runtime_ref, "new_sim").unwrap();
         ctx.root(runtime.clone())
     };
@@ -34,11 +34,11 @@ fn main() {
         };
 
         {
-            let runtime_ref = runtime.borrow();
+            let runtime_ref = runtime.read();
             let _: () = invoke_fn!(runtime_ref, "sim_update", unsafe { ctx.as_ref(&runtime_ref) }, elapsed_secs).unwrap();
         }
         previous = now;
 
-        runtime.borrow_mut().update();
+        runtime.write().update();
     }
 }
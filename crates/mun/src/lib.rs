@@ -71,6 +71,14 @@ where
                         .long("emit-ir")
                         .help("emits IR instead of a *.munlib"),
                 )
+                .arg(
+                    Arg::with_name("registry")
+                        .long("registry")
+                        .takes_value(true)
+                        .help(
+                            "directory of the package registry from which dependencies are loaded",
+                        ),
+                )
                 .about("Compiles a local Mun file into a module"),
         )
         .subcommand(
@@ -86,6 +94,11 @@ where
                         .long("entry")
                         .takes_value(true)
                         .help("the function entry point to call on startup"),
+                )
+                .arg(
+                    Arg::with_name("print-result")
+                        .long("print-result")
+                        .help("prints the value returned by the entry point"),
                 ),
         )
         .subcommand(
@@ -95,6 +95,12 @@ fn extract_build_options(matches: &ArgMatches) -> Result<BuildOptions, anyhow::E
 
     let manifest_path = matches.value_of("manifest-path").map(ToOwned::to_owned);
 
+    let registry = matches
+        .value_of("registry")
+        .map(ToOwned::to_owned)
+        .or_else(|| env::var("MUN_REGISTRY").ok())
+        .map(PathBuf::from);
+
     Ok(BuildOptions {
         manifest_path,
         display_colors,
@@ -106,6 +112,7 @@ fn extract_build_options(matches: &ArgMatches) -> Result<BuildOptions, anyhow::E
             out_dir: None,
 
             emit_ir,
+            registry,
         },
     })
 }
@@ -1,8 +1,9 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use clap::ArgMatches;
-use mun_runtime::{invoke_fn, ReturnTypeReflection, Runtime, RuntimeBuilder};
+use mun_runtime::{Runtime, RuntimeBuilder, Value};
+use parking_lot::RwLock;
 
 use crate::ExitStatus;
 
@@ -10,47 +11,26 @@ use crate::ExitStatus;
 pub fn start(matches: &ArgMatches) -> Result<ExitStatus, anyhow::Error> {
     let runtime = runtime(matches)?;
 
-    let borrowed = runtime.borrow();
+    let borrowed = runtime.read();
     let entry_point = matches.value_of("entry").unwrap_or("main");
-    let fn_definition = borrowed
-        .get_function_definition(entry_point)
-        .ok_or_else(|| {
-            std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                format!("Failed to obtain entry point '{}'", entry_point),
-            )
-        })?;
-
-    if let Some(ret_type) = fn_definition.prototype.signature.return_type() {
-        let type_guid = &ret_type.guid;
-        if *type_guid == bool::type_guid() {
-            let result: bool = invoke_fn!(borrowed, entry_point).map_err(|e| anyhow!("{}", e))?;
-
-            println!("{}", result)
-        } else if *type_guid == f64::type_guid() {
-            let result: f64 = invoke_fn!(borrowed, entry_point).map_err(|e| anyhow!("{}", e))?;
-
-            println!("{}", result)
-        } else if *type_guid == i64::type_guid() {
-            let result: i64 = invoke_fn!(borrowed, entry_point).map_err(|e| anyhow!("{}", e))?;
+    if borrowed.get_function_definition(entry_point).is_none() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Failed to obtain entry point '{}'", entry_point),
+        )
+        .into());
+    }
 
-            println!("{}", result)
-        } else {
-            return Err(anyhow!(
-                "Only native Mun return types are supported for entry points. Found: {}",
-                ret_type.name()
-            ));
-        };
-        Ok(ExitStatus::Success)
-    } else {
-        #[allow(clippy::unit_arg)]
-        invoke_fn!(borrowed, entry_point)
-            .map(|_: ()| ExitStatus::Success)
-            .map_err(|e| anyhow!("{}", e))
+    let result = borrowed
+        .invoke(entry_point, &[])
+        .map_err(|e| anyhow!("{}", e))?;
+    if matches.is_present("print-result") && !matches!(result, Value::Unit) {
+        println!("{}", result)
     }
+    Ok(ExitStatus::Success)
 }
 
-fn runtime(matches: &ArgMatches) -> Result<Rc<RefCell<Runtime>>, anyhow::Error> {
+fn runtime(matches: &ArgMatches) -> Result<Arc<RwLock<Runtime>>, anyhow::Error> {
     let builder = RuntimeBuilder::new(
         matches.value_of("LIBRARY").unwrap(), // Safe because its a required arg
     );
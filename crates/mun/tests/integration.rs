@@ -1,5 +1,6 @@
 use mun::run_with_args;
 use mun_runtime::{invoke_fn, RuntimeBuilder};
+use mun_runtime_wasm::{Runtime as WasmRuntime, RuntimeValue};
 use std::ffi::OsString;
 use std::path::Path;
 
@@ -53,6 +54,232 @@ fn mun_emit_ir() {
     assert!(ir_path.is_file());
 }
 
+/// Verifies that a newly created project can be cross-compiled for AArch64 Linux.
+#[test]
+fn mun_build_aarch64() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &["--target", "aarch64-unknown-linux-gnu"]);
+
+    // The assembly is a 64-bit little-endian ELF file for AArch64 (EM_AARCH64)
+    let header = std::fs::read(project_path.join("target/mod.munlib")).unwrap();
+    assert_eq!(&header[0..6], b"\x7fELF\x02\x01");
+    assert_eq!(u16::from_le_bytes([header[18], header[19]]), 183);
+}
+
+/// Verifies that a newly created project can be compiled to a wasm module that runs in the wasm
+/// runtime.
+#[test]
+fn mun_build_wasm32() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &["--target", "wasm32-unknown-unknown"]);
+
+    let runtime = WasmRuntime::from_file(project_path.join("target/mod.munlib")).unwrap();
+    let result: Option<f64> = runtime
+        .invoke("main", &[])
+        .unwrap()
+        .and_then(|value| value.try_into());
+    assert_eq!(result, Some(3.14159));
+}
+
+/// Verifies that a wasm module can allocate and assign structs, arrays, and strings in the wasm
+/// runtime.
+#[test]
+fn mun_build_wasm32_allocating() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+    std::fs::create_dir_all(project_path.join("src")).unwrap();
+    std::fs::write(
+        project_path.join("mun.toml"),
+        format!("[package]\nname=\"{}\"\nversion=\"0.1.0\"\n", PROJECT_NAME),
+    )
+    .unwrap();
+    std::fs::write(
+        project_path.join("src/mod.mun"),
+        r#"
+    pub struct Vec2 { x: i32, y: i32 }
+    pub struct(value) Pair { a: i64, b: i64 }
+
+    fn vec2(x: i32, y: i32) -> Vec2 { Vec2 { x: x, y: y } }
+
+    pub fn struct_sum() -> i32 {
+        let v = vec2(3, 4);
+        let w = vec2(v.y, v.x);
+        v.x + w.x
+    }
+
+    pub struct Line { from: Vec2, to: Vec2 }
+
+    pub fn line_sum() -> i32 {
+        let line = Line { from: vec2(1, 2), to: vec2(0, 0) };
+        line.to = vec2(3, 4);
+        line.from.x + line.to.y
+    }
+
+    pub fn array_sum() -> i64 {
+        let pairs = [Pair { a: 1, b: 2 }, Pair { a: 3, b: 4 }];
+        pairs[0].a + pairs[1].b
+    }
+
+    pub fn num_greetings() -> usize {
+        let greetings = ["hello", "world", "!"];
+        len(greetings)
+    }
+
+    pub fn get(index: usize) -> i32 {
+        let a = [1, 2, 3];
+        a[index]
+    }
+    "#,
+    )
+    .unwrap();
+
+    build(&project_path, &["--target", "wasm32-unknown-unknown"]);
+
+    let runtime = WasmRuntime::from_file(project_path.join("target/mod.munlib")).unwrap();
+    let invoke = |fn_name, args: &[RuntimeValue]| runtime.invoke(fn_name, args).unwrap();
+    assert_eq!(invoke("struct_sum", &[]), Some(RuntimeValue::I32(7)));
+    assert_eq!(invoke("line_sum", &[]), Some(RuntimeValue::I32(5)));
+    assert_eq!(invoke("array_sum", &[]), Some(RuntimeValue::I64(5)));
+    assert_eq!(invoke("num_greetings", &[]), Some(RuntimeValue::I32(3)));
+    assert_eq!(
+        invoke("get", &[RuntimeValue::I32(2)]),
+        Some(RuntimeValue::I32(3))
+    );
+    assert_eq!(
+        runtime
+            .invoke("get", &[RuntimeValue::I32(3)])
+            .unwrap_err()
+            .to_string(),
+        "failed to invoke `fn get(core::u32):core::i32`: index out of bounds: the len is 3 but the index is 3"
+    );
+}
+
+/// Verifies that a project can use the public items of a package it depends on.
+#[test]
+fn mun_dependency() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let math_path = project_dir.path().join("math");
+    std::fs::create_dir_all(math_path.join("src")).unwrap();
+    std::fs::write(
+        math_path.join("mun.toml"),
+        "[package]\nname=\"math\"\nversion=\"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        math_path.join("src/mod.mun"),
+        r#"
+    pub fn add(a: f64, b: f64) -> f64 { a + b }
+    pub(package) fn sub(a: f64, b: f64) -> f64 { a - b }
+    "#,
+    )
+    .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+    std::fs::create_dir_all(project_path.join("src")).unwrap();
+    std::fs::write(
+        project_path.join("mun.toml"),
+        format!(
+            "[package]\nname=\"{}\"\nversion=\"0.1.0\"\n\n[dependencies]\nmath = {{ path = \"../math\" }}\n",
+            PROJECT_NAME
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        project_path.join("src/mod.mun"),
+        r#"
+    use math::add;
+
+    pub fn main() -> f64 { add(3.0, 0.14159) }
+    "#,
+    )
+    .unwrap();
+
+    build(&project_path, &[]);
+
+    // The dependency is compiled to a separate assembly, which is loaded by the runtime
+    assert!(project_path.join("target/math.munlib").is_file());
+    run_main(&project_path);
+}
+
+#[test]
+fn mun_workspace() {
+    let workspace_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+    let workspace_path = workspace_dir.path();
+    std::fs::write(
+        workspace_path.join("mun.toml"),
+        "[workspace]\nmembers = [\"game\", \"math\"]\n",
+    )
+    .unwrap();
+
+    let math_path = workspace_path.join("math");
+    std::fs::create_dir_all(math_path.join("src")).unwrap();
+    std::fs::write(
+        math_path.join("mun.toml"),
+        "[package]\nname=\"math\"\nversion=\"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        math_path.join("src/mod.mun"),
+        "pub fn add(a: f64, b: f64) -> f64 { a + b }",
+    )
+    .unwrap();
+
+    let game_path = workspace_path.join("game");
+    std::fs::create_dir_all(game_path.join("src")).unwrap();
+    std::fs::write(
+        game_path.join("mun.toml"),
+        "[package]\nname=\"game\"\nversion=\"0.1.0\"\n\n[dependencies]\nmath = { path = \"../math\" }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        game_path.join("src/mod.mun"),
+        "pub fn main() -> f64 { math::add(3.0, 0.14159) }",
+    )
+    .unwrap();
+
+    build(workspace_path, &[]);
+
+    // All members are compiled to the output directory of the workspace
+    assert!(workspace_path.join("target/math.munlib").is_file());
+    let library_path = workspace_path.join("target/game.munlib");
+    assert!(library_path.is_file());
+    assert!(!game_path.join("target").exists());
+
+    let runtime = RuntimeBuilder::new(&library_path).spawn().unwrap();
+    let runtime_ref = runtime.read();
+    let result: f64 = invoke_fn!(runtime_ref, "game::main").unwrap();
+    assert_eq!(result, 3.14159);
+}
+
 fn build(project: &Path, args: &[&str]) {
     let args: Vec<OsString> = vec![
         OsString::from("mun"),
@@ -69,12 +296,16 @@ fn build(project: &Path, args: &[&str]) {
 /// Builds and runs an newly generated mun project
 fn build_and_run(project: &Path) {
     build(project.as_ref(), &[]);
+    run_main(project);
+}
 
+/// Runs the `main` function of an already built mun project
+fn run_main(project: &Path) {
     let library_path = project.join("target/mod.munlib");
     assert!(library_path.is_file());
 
     let runtime = RuntimeBuilder::new(&library_path).spawn().unwrap();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
     let result: f64 = invoke_fn!(runtime_ref, "main").unwrap();
     assert_eq!(result, 3.14159);
 }
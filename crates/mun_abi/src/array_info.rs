@@ -0,0 +1,38 @@
+use crate::TypeInfo;
+
+/// Represents a dynamically sized array type.
+///
+/// Arrays are always allocated on the heap. In memory an array is represented by an object pointer
+/// to an array header that stores the length of the array, followed by its elements.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrayInfo {
+    /// The type of the elements of the array
+    pub(crate) element_type: *const TypeInfo,
+}
+
+impl ArrayInfo {
+    /// Returns the type of the elements of the array.
+    pub fn element_type(&self) -> &TypeInfo {
+        // Safety: `element_type` is never `ptr::null()`.
+        unsafe { &*self.element_type }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{fake_array_info, fake_type_info, FAKE_TYPE_NAME},
+        TypeInfoData,
+    };
+    use std::ffi::CString;
+
+    #[test]
+    fn test_array_info_element_type() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, 1, 1, TypeInfoData::Primitive);
+
+        let array_info = fake_array_info(&type_info);
+        assert_eq!(array_info.element_type(), &type_info);
+    }
+}
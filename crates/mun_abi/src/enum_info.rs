@@ -0,0 +1,103 @@
+use crate::StructInfo;
+use std::{ffi::CStr, os::raw::c_char, slice, str};
+
+/// Represents an enum declaration.
+///
+/// Enums are value types. In memory an enum is represented by a `u16` tag at offset zero that
+/// identifies the active variant, followed by the fields of that variant. The tag of a variant is
+/// its index in the declaration.
+#[repr(C)]
+#[derive(Debug)]
+pub struct EnumInfo {
+    /// Enum variants' names
+    pub(crate) variant_names: *const *const c_char,
+    /// Enum variants' fields. The field offsets are relative to the start of the enum value.
+    pub(crate) variant_infos: *const StructInfo,
+    /// Number of variants
+    pub(crate) num_variants: u16,
+}
+
+impl EnumInfo {
+    /// Returns the enum's variant names.
+    pub fn variant_names(&self) -> impl Iterator<Item = &str> {
+        let variant_names = if self.num_variants == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.variant_names, self.num_variants as usize) }
+        };
+
+        variant_names
+            .iter()
+            .map(|n| unsafe { str::from_utf8_unchecked(CStr::from_ptr(*n).to_bytes()) })
+    }
+
+    /// Returns the fields of the enum's variants.
+    pub fn variant_infos(&self) -> &[StructInfo] {
+        if self.num_variants == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.variant_infos, self.num_variants as usize) }
+        }
+    }
+
+    /// Returns the number of enum variants.
+    pub fn num_variants(&self) -> usize {
+        self.num_variants.into()
+    }
+
+    /// Returns the tag of the variant matching the specified `variant_name`.
+    pub fn find_variant_by_name<S: AsRef<str>>(&self, variant_name: S) -> Option<u16> {
+        let variant_name = variant_name.as_ref();
+        self.variant_names()
+            .enumerate()
+            .find(|(_, name)| *name == variant_name)
+            .map(|(idx, _)| idx as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{
+        fake_enum_info, fake_struct_info, fake_type_info, FAKE_FIELD_NAME, FAKE_TYPE_NAME,
+        FAKE_VARIANT_NAME,
+    };
+    use crate::TypeInfoData;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_enum_info_variant_names_none() {
+        let variant_names = &[];
+        let variant_infos = &[];
+        let enum_info = fake_enum_info(variant_names, variant_infos);
+
+        assert_eq!(enum_info.variant_names().count(), 0);
+        assert!(enum_info.variant_infos().is_empty());
+        assert_eq!(enum_info.num_variants(), 0);
+    }
+
+    #[test]
+    fn test_enum_info_variants_some() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, 1, 1, TypeInfoData::Primitive);
+
+        let field_name = CString::new(FAKE_FIELD_NAME).expect("Invalid fake field name.");
+        let field_names = &[field_name.as_ptr()];
+        let field_types = &[&type_info];
+        let field_offsets = &[2];
+        let variant_info =
+            fake_struct_info(field_names, field_types, field_offsets, Default::default());
+
+        let variant_name = CString::new(FAKE_VARIANT_NAME).expect("Invalid fake variant name.");
+        let variant_names = &[variant_name.as_ptr()];
+        let variant_infos = &[variant_info];
+        let enum_info = fake_enum_info(variant_names, variant_infos);
+
+        for (lhs, rhs) in enum_info.variant_names().zip([FAKE_VARIANT_NAME].iter()) {
+            assert_eq!(lhs, *rhs)
+        }
+        assert_eq!(enum_info.num_variants(), 1);
+        assert_eq!(enum_info.variant_infos()[0].field_offsets(), field_offsets);
+        assert_eq!(enum_info.find_variant_by_name(FAKE_VARIANT_NAME), Some(0));
+        assert_eq!(enum_info.find_variant_by_name("Unknown"), None);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::TypeInfo;
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+    str,
+};
+
+/// Represents a module-level `const` or `static` item. A global contains the name, type, and a
+/// pointer to the memory that stores its value.
+#[repr(C)]
+pub struct GlobalInfo {
+    /// Global name
+    pub(crate) name: *const c_char,
+    /// Global type
+    pub(crate) type_info: *const TypeInfo,
+    /// Pointer to the value of the global
+    pub(crate) value: *mut c_void,
+    /// Whether the global is a constant or a variable
+    pub kind: GlobalKind,
+}
+
+/// Represents the kind of a global.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalKind {
+    /// A `const` item, which can only be read.
+    Const,
+
+    /// A `static` item, which can be read and written.
+    Static,
+}
+
+impl GlobalInfo {
+    /// Returns the global's name.
+    pub fn name(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.name).to_bytes()) }
+    }
+
+    /// Returns the global's type.
+    pub fn type_info(&self) -> &TypeInfo {
+        // Safety: the type of a global is never `null`.
+        unsafe { &*self.type_info }
+    }
+
+    /// Returns a pointer to the value of the global.
+    pub fn value_ptr(&self) -> *mut c_void {
+        self.value
+    }
+
+    /// Returns true if the global can be written.
+    pub fn is_mutable(&self) -> bool {
+        self.kind == GlobalKind::Static
+    }
+}
+
+unsafe impl Send for GlobalInfo {}
+unsafe impl Sync for GlobalInfo {}
+
+#[cfg(test)]
+mod tests {
+    use super::GlobalKind;
+    use crate::{
+        test_utils::{fake_global_info, fake_type_info, FAKE_GLOBAL_NAME, FAKE_TYPE_NAME},
+        TypeInfoData,
+    };
+    use std::ffi::{c_void, CString};
+
+    #[test]
+    fn test_global_info_name() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, 32, 4, TypeInfoData::Primitive);
+
+        let global_name = CString::new(FAKE_GLOBAL_NAME).expect("Invalid fake global name.");
+        let global_info = fake_global_info(
+            &global_name,
+            &type_info,
+            std::ptr::null_mut(),
+            GlobalKind::Const,
+        );
+
+        assert_eq!(global_info.name(), FAKE_GLOBAL_NAME);
+    }
+
+    #[test]
+    fn test_global_info_type_info() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, 32, 4, TypeInfoData::Primitive);
+
+        let global_name = CString::new(FAKE_GLOBAL_NAME).expect("Invalid fake global name.");
+        let global_info = fake_global_info(
+            &global_name,
+            &type_info,
+            std::ptr::null_mut(),
+            GlobalKind::Const,
+        );
+
+        assert_eq!(global_info.type_info(), &type_info);
+    }
+
+    #[test]
+    fn test_global_info_value() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, 32, 4, TypeInfoData::Primitive);
+
+        let mut value = 5i32;
+        let value_ptr = (&mut value as *mut i32).cast::<c_void>();
+
+        let global_name = CString::new(FAKE_GLOBAL_NAME).expect("Invalid fake global name.");
+        let global_info = fake_global_info(&global_name, &type_info, value_ptr, GlobalKind::Static);
+
+        assert_eq!(global_info.value_ptr(), value_ptr);
+        assert_eq!(unsafe { *global_info.value_ptr().cast::<i32>() }, 5);
+    }
+
+    #[test]
+    fn test_global_info_is_mutable() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, 32, 4, TypeInfoData::Primitive);
+
+        let global_name = CString::new(FAKE_GLOBAL_NAME).expect("Invalid fake global name.");
+        let const_info = fake_global_info(
+            &global_name,
+            &type_info,
+            std::ptr::null_mut(),
+            GlobalKind::Const,
+        );
+        let static_info = fake_global_info(
+            &global_name,
+            &type_info,
+            std::ptr::null_mut(),
+            GlobalKind::Static,
+        );
+
+        assert!(!const_info.is_mutable());
+        assert!(static_info.is_mutable());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{FunctionDefinition, TypeInfo};
+use crate::{FunctionDefinition, GlobalInfo, TypeInfo};
 use std::{ffi::CStr, os::raw::c_char, slice, str};
 
 /// Represents a module declaration.
@@ -10,10 +10,14 @@ pub struct ModuleInfo {
     pub(crate) functions: *const FunctionDefinition,
     /// Module types
     pub(crate) types: *const *const TypeInfo,
+    /// Module globals
+    pub(crate) globals: *const GlobalInfo,
     /// Number of module functions
     pub num_functions: u32,
     /// Number of module types
     pub num_types: u32,
+    /// Number of module globals
+    pub num_globals: u32,
 }
 
 impl ModuleInfo {
@@ -56,6 +60,20 @@ impl ModuleInfo {
             }
         }
     }
+
+    /// Returns the module's globals.
+    pub fn globals(&self) -> &[GlobalInfo] {
+        if self.num_globals == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.globals, self.num_globals as usize) }
+        }
+    }
+
+    /// Retrieves the global with the specified `name`, if it exists.
+    pub fn get_global(&self, name: &str) -> Option<&GlobalInfo> {
+        self.globals().iter().find(|g| g.name() == name)
+    }
 }
 
 unsafe impl Send for ModuleInfo {}
@@ -65,17 +83,18 @@ unsafe impl Sync for ModuleInfo {}
 mod tests {
     use crate::{
         test_utils::{
-            fake_fn_prototype, fake_module_info, fake_struct_info, fake_type_info, FAKE_FN_NAME,
-            FAKE_MODULE_PATH, FAKE_STRUCT_NAME, FAKE_TYPE_NAME,
+            fake_fn_prototype, fake_global_info, fake_module_info, fake_struct_info,
+            fake_type_info, FAKE_FN_NAME, FAKE_GLOBAL_NAME, FAKE_MODULE_PATH, FAKE_STRUCT_NAME,
+            FAKE_TYPE_NAME,
         },
-        FunctionDefinition, TypeInfo, TypeInfoData,
+        FunctionDefinition, GlobalKind, TypeInfo, TypeInfoData,
     };
     use std::{ffi::CString, ptr};
 
     #[test]
     fn test_module_info_path() {
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, &[], &[]);
+        let module = fake_module_info(&module_path, &[], &[], &[]);
 
         assert_eq!(module.path(), FAKE_MODULE_PATH);
     }
@@ -85,7 +104,7 @@ mod tests {
         let functions = &[];
         let types = &[];
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, functions, types);
+        let module = fake_module_info(&module_path, functions, types, &[]);
 
         assert_eq!(module.functions().len(), functions.len());
         assert_eq!(module.types().len(), types.len());
@@ -112,7 +131,7 @@ mod tests {
         let types = &[&type_info];
 
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, functions, types);
+        let module = fake_module_info(&module_path, functions, types, &[]);
 
         let result_functions = module.functions();
         assert_eq!(result_functions.len(), functions.len());
@@ -145,4 +164,33 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_module_info_globals() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, 32, 4, TypeInfoData::Primitive);
+
+        let global_name = CString::new(FAKE_GLOBAL_NAME).expect("Invalid fake global name.");
+        let global_info = fake_global_info(
+            &global_name,
+            &type_info,
+            ptr::null_mut(),
+            GlobalKind::Static,
+        );
+        let globals = &[global_info];
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], globals);
+
+        let result_globals = module.globals();
+        assert_eq!(result_globals.len(), globals.len());
+        for (lhs, rhs) in result_globals.iter().zip(globals.iter()) {
+            assert_eq!(lhs.name(), rhs.name());
+            assert_eq!(lhs.type_info(), rhs.type_info());
+            assert_eq!(lhs.kind, rhs.kind);
+        }
+
+        assert!(module.get_global(FAKE_GLOBAL_NAME).is_some());
+        assert!(module.get_global("NOT_A_GLOBAL").is_none());
+    }
 }
@@ -1,6 +1,7 @@
 use crate::{
-    AssemblyInfo, DispatchTable, FunctionDefinition, FunctionPrototype, FunctionSignature, Guid,
-    ModuleInfo, StructInfo, StructMemoryKind, TypeInfo, TypeInfoData,
+    ArrayInfo, AssemblyInfo, DispatchTable, EnumInfo, FunctionDefinition, FunctionPrototype,
+    FunctionSignature, GlobalInfo, GlobalKind, Guid, ModuleInfo, StructInfo, StructMemoryKind,
+    TypeInfo, TypeInfoData,
 };
 use std::{
     ffi::{c_void, CStr},
@@ -13,9 +14,11 @@ pub(crate) const FAKE_TYPE_GUID: Guid =
 pub(crate) const FAKE_DEPENDENCY: &str = "path/to/dependency.munlib";
 pub(crate) const FAKE_FIELD_NAME: &str = "field_name";
 pub(crate) const FAKE_FN_NAME: &str = "fn_name";
+pub(crate) const FAKE_GLOBAL_NAME: &str = "GLOBAL_NAME";
 pub(crate) const FAKE_MODULE_PATH: &str = "path::to::module";
 pub(crate) const FAKE_STRUCT_NAME: &str = "StructName";
 pub(crate) const FAKE_TYPE_NAME: &str = "TypeName";
+pub(crate) const FAKE_VARIANT_NAME: &str = "VariantName";
 
 pub(crate) fn fake_assembly_info(
     symbols: ModuleInfo,
@@ -65,10 +68,25 @@ pub(crate) fn fake_fn_prototype(
     }
 }
 
+pub(crate) fn fake_global_info(
+    name: &CStr,
+    type_info: &TypeInfo,
+    value: *mut c_void,
+    kind: GlobalKind,
+) -> GlobalInfo {
+    GlobalInfo {
+        name: name.as_ptr(),
+        type_info: type_info as *const TypeInfo,
+        value,
+        kind,
+    }
+}
+
 pub(crate) fn fake_module_info(
     path: &CStr,
     functions: &[FunctionDefinition],
     types: &[&TypeInfo],
+    globals: &[GlobalInfo],
 ) -> ModuleInfo {
     ModuleInfo {
         path: path.as_ptr(),
@@ -76,6 +94,8 @@ pub(crate) fn fake_module_info(
         num_functions: functions.len() as u32,
         types: types.as_ptr().cast::<*const TypeInfo>(),
         num_types: types.len() as u32,
+        globals: globals.as_ptr(),
+        num_globals: globals.len() as u32,
     }
 }
 
@@ -97,6 +117,25 @@ pub(crate) fn fake_struct_info(
     }
 }
 
+pub(crate) fn fake_enum_info(
+    variant_names: &[*const c_char],
+    variant_infos: &[StructInfo],
+) -> EnumInfo {
+    assert!(variant_names.len() == variant_infos.len());
+
+    EnumInfo {
+        variant_names: variant_names.as_ptr(),
+        variant_infos: variant_infos.as_ptr(),
+        num_variants: variant_names.len() as u16,
+    }
+}
+
+pub(crate) fn fake_array_info(element_type: &TypeInfo) -> ArrayInfo {
+    ArrayInfo {
+        element_type: element_type as *const TypeInfo,
+    }
+}
+
 pub(crate) fn fake_type_info(
     name: &CStr,
     size: u32,
@@ -1,4 +1,4 @@
-use crate::{static_type_map::StaticTypeMap, Guid, StructInfo};
+use crate::{static_type_map::StaticTypeMap, ArrayInfo, EnumInfo, Guid, StructInfo};
 use once_cell::sync::OnceCell;
 use std::{
     convert::TryInto,
@@ -11,8 +11,8 @@ use std::{
 
 /// Represents the type declaration for a value type.
 ///
-/// TODO: add support for polymorphism, enumerations, type parameters, generic type definitions, and
-/// constructed generic types.
+/// TODO: add support for polymorphism, type parameters, generic type definitions, and constructed
+/// generic types.
 #[repr(C)]
 #[derive(Debug)]
 pub struct TypeInfo {
@@ -36,6 +36,10 @@ pub enum TypeInfoData {
     Primitive,
     /// Struct types (i.e. record, tuple, or unit structs)
     Struct(StructInfo),
+    /// Dynamically sized array types (i.e. `[T]`)
+    Array(ArrayInfo),
+    /// Enum types (i.e. a tag followed by the fields of the active variant)
+    Enum(EnumInfo),
 }
 
 impl TypeInfo {
@@ -53,6 +57,24 @@ impl TypeInfo {
         }
     }
 
+    /// Retrieves the type's array information, if available.
+    pub fn as_array(&self) -> Option<&ArrayInfo> {
+        if let TypeInfoData::Array(a) = &self.data {
+            Some(a)
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the type's enum information, if available.
+    pub fn as_enum(&self) -> Option<&EnumInfo> {
+        if let TypeInfoData::Enum(e) = &self.data {
+            Some(e)
+        } else {
+            None
+        }
+    }
+
     /// Returns the size of the type in bits
     pub fn size_in_bits(&self) -> usize {
         self.size_in_bits
@@ -106,6 +128,16 @@ impl TypeInfoData {
     pub fn is_struct(&self) -> bool {
         matches!(self, TypeInfoData::Struct(_))
     }
+
+    /// Returns whether this is an array type.
+    pub fn is_array(&self) -> bool {
+        matches!(self, TypeInfoData::Array(_))
+    }
+
+    /// Returns whether this is an enum type.
+    pub fn is_enum(&self) -> bool {
+        matches!(self, TypeInfoData::Enum(_))
+    }
 }
 
 /// A trait that defines that for a type we can statically return a `TypeInfo`.
@@ -257,6 +289,37 @@ impl_has_type_info_name!(
     TypeInfo => "TypeInfo"
 );
 
+/// Strings are heap-allocated by the runtime. In memory, a string is represented by an object
+/// pointer, similar to how garbage collected structs are represented.
+impl HasStaticTypeInfo for str {
+    fn type_info() -> &'static TypeInfo {
+        static TYPE_INFO: OnceCell<TypeInfo> = OnceCell::new();
+        TYPE_INFO.get_or_init(|| {
+            static TYPE_INFO_NAME: OnceCell<CString> = OnceCell::new();
+            let type_info_name: &'static CString =
+                TYPE_INFO_NAME.get_or_init(|| CString::new("core::string").unwrap());
+
+            TypeInfo {
+                guid: Guid(md5::compute(&type_info_name.as_bytes()).0),
+                name: type_info_name.as_ptr(),
+                size_in_bits: (std::mem::size_of::<*const *mut std::ffi::c_void>() * 8)
+                    .try_into()
+                    .expect("size of T is larger than the maximum allowed ABI size. Please file a bug."),
+                alignment: (std::mem::align_of::<*const *mut std::ffi::c_void>())
+                    .try_into()
+                    .expect("alignment of T is larger than the maximum allowed ABI size. Please file a bug."),
+                data: TypeInfoData::Primitive,
+            }
+        })
+    }
+}
+
+impl HasStaticTypeInfo for String {
+    fn type_info() -> &'static TypeInfo {
+        str::type_info()
+    }
+}
+
 #[cfg(target_pointer_width = "64")]
 impl HasStaticTypeInfo for usize {
     fn type_info() -> &'static TypeInfo {
@@ -288,7 +351,9 @@ impl HasStaticTypeInfo for isize {
 #[cfg(test)]
 mod tests {
     use super::{HasStaticTypeInfoName, TypeInfoData};
-    use crate::test_utils::{fake_struct_info, fake_type_info, FAKE_TYPE_NAME};
+    use crate::test_utils::{
+        fake_array_info, fake_enum_info, fake_struct_info, fake_type_info, FAKE_TYPE_NAME,
+    };
     use std::ffi::CString;
 
     #[test]
@@ -334,6 +399,39 @@ mod tests {
         assert!(!type_info.data.is_primitive());
     }
 
+    #[test]
+    fn test_type_info_group_array() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let element_type_info = fake_type_info(&type_name, 1, 1, TypeInfoData::Primitive);
+
+        let array_info = fake_array_info(&element_type_info);
+        let type_info = fake_type_info(&type_name, 1, 1, TypeInfoData::Array(array_info));
+
+        assert!(type_info.data.is_array());
+        assert!(!type_info.data.is_struct());
+        assert!(!type_info.data.is_primitive());
+        assert_eq!(
+            type_info.as_array().unwrap().element_type(),
+            &element_type_info
+        );
+    }
+
+    #[test]
+    fn test_type_info_group_enum() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+
+        let variant_names = &[];
+        let variant_infos = &[];
+        let enum_info = fake_enum_info(variant_names, variant_infos);
+
+        let type_info = fake_type_info(&type_name, 16, 2, TypeInfoData::Enum(enum_info));
+
+        assert!(type_info.data.is_enum());
+        assert!(!type_info.data.is_struct());
+        assert!(!type_info.data.is_primitive());
+        assert!(type_info.as_enum().is_some());
+    }
+
     #[test]
     fn test_type_info_eq() {
         let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
@@ -342,6 +440,20 @@ mod tests {
         assert_eq!(type_info, type_info);
     }
 
+    #[test]
+    fn test_string() {
+        use super::HasStaticTypeInfo;
+
+        let ty = <str>::type_info();
+        assert_eq!(ty.name(), "core::string");
+        assert!(ty.data.is_primitive());
+        assert_eq!(
+            ty.size_in_bytes(),
+            std::mem::size_of::<*const *mut std::ffi::c_void>()
+        );
+        assert_eq!(ty, String::type_info());
+    }
+
     #[test]
     fn test_ptr() {
         let ty = <*const std::ffi::c_void>::type_name();
@@ -92,6 +92,7 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
             self.code_gen.db,
             &value_context,
             &file.api,
+            &group_ir.globals,
             &group_ir.dispatch_table,
             &group_ir.type_table,
             &self.code_gen.hir_types,
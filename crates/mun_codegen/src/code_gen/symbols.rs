@@ -4,6 +4,7 @@ use crate::{
     ir::{
         dispatch_table::{DispatchTable, DispatchableFunction},
         function,
+        global::GlobalItem,
         type_table::TypeTable,
     },
     type_info::TypeInfo,
@@ -167,6 +168,44 @@ fn get_function_definition_array<'ink, 'a>(
         .into_const_private_global("fn.get_info.functions", context)
 }
 
+/// Construct a global that holds a reference to all `const` and `static` items. e.g.:
+/// MunGlobalInfo[] globals = { ... }
+fn get_global_info_array<'ink>(
+    db: &dyn HirDatabase,
+    context: &IrValueContext<'ink, '_, '_>,
+    globals: &[GlobalItem],
+    hir_types: &HirTypeCache,
+) -> Value<'ink, *const ir::GlobalInfo<'ink>> {
+    let module = context.module;
+    globals
+        .iter()
+        .map(|item| {
+            let name = item.full_name(db);
+            let name_str = CString::new(name.clone())
+                .expect("global name is not a valid CString")
+                .intern(format!("global::<{}>::name", &name), context);
+
+            let type_info = TypeTable::get(module, &hir_types.type_info(&item.ty(db)), context)
+                .expect("expected a TypeInfo for a global but it was not found");
+
+            // Get the global from the linked module
+            let value = module
+                .get_global(&item.symbol_name(db))
+                .expect("could not find the value of a global");
+
+            ir::GlobalInfo {
+                name: name_str.as_value(context),
+                type_info,
+                value: Value::<*mut u8>::with_cast(value.as_pointer_value(), context),
+                kind: match item {
+                    GlobalItem::Const(_) => abi::GlobalKind::Const,
+                    GlobalItem::Static(_) => abi::GlobalKind::Static,
+                },
+            }
+        })
+        .into_const_private_pointer_or_null("fn.get_info.globals", context)
+}
+
 /// Generate the dispatch table information. e.g.:
 /// ```c
 /// MunDispatchTable dispatchTable = { ... }
@@ -210,6 +249,7 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
     db: &'db dyn HirDatabase,
     context: &IrValueContext<'ink, '_, '_>,
     api: &HashSet<hir::Function>,
+    globals: &[GlobalItem],
     dispatch_table: &DispatchTable<'ink>,
     type_table: &TypeTable<'ink>,
     hir_types: &HirTypeCache<'db, 'ink>,
@@ -236,6 +276,8 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         num_functions,
         types,
         num_types: type_table.num_types() as u32,
+        globals: get_global_info_array(db, context, globals, hir_types),
+        num_globals: globals.len() as u32,
     };
 
     // Construct the dispatch table struct
@@ -264,11 +306,12 @@ fn gen_get_info_fn<'ink>(
     dependencies: Vec<String>,
 ) {
     let target = db.target();
+    let returns_indirectly = target.options.is_like_windows || target.options.is_like_wasm;
 
     // Construct the return type of the `get_info` method. Depending on the C ABI this is either the
-    // `MunAssemblyInfo` struct or void. On windows the return argument is passed back to the caller
-    // through a pointer to the return type as the first argument. e.g.:
-    // On Windows:
+    // `MunAssemblyInfo` struct or void. On windows and WebAssembly the return argument is passed
+    // back to the caller through a pointer to the return type as the first argument. e.g.:
+    // On Windows and WebAssembly:
     // ```c
     // void get_info(MunModuleInfo* result) {...}
     // ```
@@ -276,7 +319,7 @@ fn gen_get_info_fn<'ink>(
     // ```c
     // MunModuleInfo get_info() { ... }
     // ```
-    let get_symbols_type = if target.options.is_like_windows {
+    let get_symbols_type = if returns_indirectly {
         Value::<'ink, fn(*mut ir::AssemblyInfo<'ink>)>::get_ir_type(context.type_context)
     } else {
         Value::<'ink, fn() -> ir::AssemblyInfo<'ink>>::get_ir_type(context.type_context)
@@ -287,7 +330,7 @@ fn gen_get_info_fn<'ink>(
             .module
             .add_function("get_info", get_symbols_type, Some(Linkage::DLLExport));
 
-    if target.options.is_like_windows {
+    if returns_indirectly {
         get_symbols_fn.add_attribute(
             inkwell::attributes::AttributeLoc::Param(0),
             context
@@ -302,7 +345,7 @@ fn gen_get_info_fn<'ink>(
 
     // Get a pointer to the IR value that will hold the return value. Again this differs depending
     // on the C ABI.
-    let result_ptr = if target.options.is_like_windows {
+    let result_ptr = if returns_indirectly {
         get_symbols_fn
             .get_nth_param(0)
             .unwrap()
@@ -354,7 +397,7 @@ fn gen_get_info_fn<'ink>(
     );
 
     // Construct the return statement of the function.
-    if target.options.is_like_windows {
+    if returns_indirectly {
         builder.build_return(None);
     } else {
         builder.build_return(Some(&builder.build_load(result_ptr, "")));
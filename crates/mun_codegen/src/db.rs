@@ -38,14 +38,26 @@ fn target_machine(db: &dyn CodeGenDatabase) -> ByAddress<Arc<inkwell::targets::T
     // Get the HIR target
     let target = db.target();
 
-    // Initialize the x86 target
-    Target::initialize_x86(&InitializationConfig::default());
+    // Initialize the LLVM target for the architecture
+    match target.arch.as_str() {
+        "aarch64" => Target::initialize_aarch64(&InitializationConfig::default()),
+        "wasm32" => Target::initialize_webassembly(&InitializationConfig::default()),
+        _ => Target::initialize_x86(&InitializationConfig::default()),
+    }
 
     // Retrieve the LLVM target using the specified target.
     let target_triple = TargetTriple::create(&db.target().llvm_target);
     let llvm_target = Target::from_triple(&target_triple)
         .expect("could not find llvm target tripple for Mun target");
 
+    // WebAssembly modules are not relocated when they are loaded; position independent code would
+    // require the loader to provide the base addresses of the memory and table.
+    let reloc_mode = if target.options.is_like_wasm {
+        RelocMode::Static
+    } else {
+        RelocMode::PIC
+    };
+
     // Construct target machine for machine code generation
     let target_machine = llvm_target
         .create_target_machine(
@@ -53,7 +65,7 @@ fn target_machine(db: &dyn CodeGenDatabase) -> ByAddress<Arc<inkwell::targets::T
             &target.options.cpu,
             &target.options.features,
             db.optimization_level(),
-            RelocMode::PIC,
+            reloc_mode,
             CodeModel::Default,
         )
         .expect("could not create llvm target machine");
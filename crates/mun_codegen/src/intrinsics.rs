@@ -21,4 +21,16 @@ pub trait Intrinsic: Sync {
 intrinsics! {
     /// Allocates memory for the specified `type` in the allocator referred to by `alloc_handle`.
     pub fn new(type: *const TypeInfo, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+    /// Allocates a string in the allocator referred to by `alloc_handle` and initializes it with
+    /// the `length` UTF-8 encoded bytes pointed to by `bytes`.
+    pub fn new_string(bytes: *const u8, length: usize, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+    /// Allocates an array of the array `type` in the allocator referred to by `alloc_handle`, that
+    /// can hold `length` zero-initialized elements.
+    pub fn new_array(type: *const TypeInfo, length: usize, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+    /// Reports that `index` is out of bounds for an array with `length` elements. This function
+    /// never returns.
+    pub fn panic_index_out_of_bounds(index: usize, length: usize) -> ();
+    /// Notifies the allocator referred to by `alloc_handle` that a value that can reference other
+    /// objects was stored in the object referred to by `obj`.
+    pub fn write_barrier(obj: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) -> ();
 }
@@ -8,11 +8,14 @@ use inkwell::AddressSpace;
 use crate::type_info::TypeInfo;
 
 pub mod body;
+pub(crate) mod closure;
 #[macro_use]
 pub(crate) mod dispatch_table;
 pub mod file;
 pub(crate) mod file_group;
 pub mod function;
+pub(crate) mod global;
+pub(crate) mod instance;
 mod intrinsics;
 pub mod ty;
 pub(crate) mod type_table;
@@ -3,27 +3,37 @@ use crate::{
     intrinsics,
     ir::ty::HirTypeCache,
     ir::types as ir,
-    ir::{dispatch_table::DispatchTable, type_table::TypeTable},
+    ir::{
+        closure,
+        dispatch_table::DispatchTable,
+        global,
+        instance::{self, FunctionInstance},
+        type_table::TypeTable,
+    },
+    type_info::TypeInfo,
     value::Global,
 };
 use hir::{
     ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, HirDatabase, HirDisplay, InferenceResult,
-    Literal, LogicOp, Name, Ordering, Pat, PatId, Path, ResolveBitness, Resolver, Statement,
-    TyKind, UnaryOp, ValueNs,
+    Literal, LogicOp, MatchArm, Name, Ordering, Pat, PatId, Path, RangeOp, ResolveBitness,
+    Resolver, Statement, Substitution, Ty, TyKind, UnaryOp, ValueNs, VariantDef,
 };
 use inkwell::{
     basic_block::BasicBlock,
     builder::Builder,
     context::Context,
-    values::{AggregateValueEnum, GlobalValue, PointerValue},
+    module::{Linkage, Module},
+    types::{BasicType, StructType},
+    values::{AggregateValueEnum, BasicValue, GlobalValue, PointerValue, UnnamedAddress},
     values::{BasicValueEnum, CallSiteValue, FloatValue, FunctionValue, IntValue, StructValue},
     AddressSpace, FloatPredicate, IntPredicate,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, iter::once, sync::Arc};
 
 struct LoopInfo<'ink> {
     break_values: Vec<(BasicValueEnum<'ink>, BasicBlock<'ink>)>,
     exit_block: BasicBlock<'ink>,
+    continue_block: BasicBlock<'ink>,
 }
 
 #[derive(Clone)]
@@ -31,10 +41,12 @@ pub(crate) struct ExternalGlobals<'ink> {
     pub alloc_handle: Option<GlobalValue<'ink>>,
     pub dispatch_table: Option<GlobalValue<'ink>>,
     pub type_table: Option<Global<'ink, [*const ir::TypeInfo<'ink>]>>,
+    pub statics: HashMap<hir::Static, GlobalValue<'ink>>,
 }
 
 pub(crate) struct BodyIrGenerator<'db, 'ink, 't> {
     context: &'ink Context,
+    module: &'t Module<'ink>,
     db: &'db dyn HirDatabase,
     body: Arc<Body>,
     infer: Arc<InferenceResult>,
@@ -44,11 +56,13 @@ pub(crate) struct BodyIrGenerator<'db, 'ink, 't> {
     pat_to_local: HashMap<PatId, inkwell::values::PointerValue<'ink>>,
     pat_to_name: HashMap<PatId, String>,
     function_map: &'t HashMap<hir::Function, FunctionValue<'ink>>,
+    instance_map: &'t HashMap<FunctionInstance, FunctionValue<'ink>>,
     dispatch_table: &'t DispatchTable<'ink>,
     type_table: &'t TypeTable<'ink>,
     hir_types: &'t HirTypeCache<'db, 'ink>,
     active_loop: Option<LoopInfo<'ink>>,
     hir_function: hir::Function,
+    substs: Substitution,
     external_globals: ExternalGlobals<'ink>,
     module_group: &'t ModuleGroup,
 }
@@ -57,20 +71,24 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         context: &'ink Context,
+        module: &'t Module<'ink>,
         db: &'db dyn HirDatabase,
-        function: (hir::Function, FunctionValue<'ink>),
+        function: (FunctionInstance, FunctionValue<'ink>),
         function_map: &'t HashMap<hir::Function, FunctionValue<'ink>>,
+        instance_map: &'t HashMap<FunctionInstance, FunctionValue<'ink>>,
         dispatch_table: &'t DispatchTable<'ink>,
         type_table: &'t TypeTable<'ink>,
         external_globals: ExternalGlobals<'ink>,
         hir_types: &'t HirTypeCache<'db, 'ink>,
         module_group: &'t ModuleGroup,
     ) -> Self {
-        let (hir_function, ir_function) = function;
+        let (instance, ir_function) = function;
+        let hir_function = instance.function;
 
-        // Get the type information from the `hir::Function`
+        // Get the type information from the `hir::Function`. For instances of generic functions
+        // the type arguments are substituted.
         let body = hir_function.body(db);
-        let infer = hir_function.infer(db);
+        let infer = instance.infer(db);
 
         // Construct a builder for the IR function
         let builder = context.create_builder();
@@ -79,6 +97,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         BodyIrGenerator {
             context,
+            module,
             db,
             body,
             infer,
@@ -88,27 +107,139 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             pat_to_local: HashMap::default(),
             pat_to_name: HashMap::default(),
             function_map,
+            instance_map,
             dispatch_table,
             type_table,
             active_loop: None,
             hir_function,
+            substs: instance.substs,
             external_globals,
             hir_types,
             module_group,
         }
     }
 
+    /// Constructs a generator for a function that is generated as part of the body of this
+    /// function, e.g. the function of a lambda. The generator shares the body and the type
+    /// information of this function.
+    fn new_nested_generator(
+        &self,
+        fn_value: FunctionValue<'ink>,
+    ) -> BodyIrGenerator<'db, 'ink, 't> {
+        let builder = self.context.create_builder();
+        let body_ir = self.context.append_basic_block(fn_value, "body");
+        builder.position_at_end(body_ir);
+
+        BodyIrGenerator {
+            context: self.context,
+            module: self.module,
+            db: self.db,
+            body: self.body.clone(),
+            infer: self.infer.clone(),
+            builder,
+            fn_value,
+            pat_to_param: HashMap::default(),
+            pat_to_local: HashMap::default(),
+            pat_to_name: HashMap::default(),
+            function_map: self.function_map,
+            instance_map: self.instance_map,
+            dispatch_table: self.dispatch_table,
+            type_table: self.type_table,
+            active_loop: None,
+            hir_function: self.hir_function,
+            substs: self.substs.clone(),
+            external_globals: self.external_globals.clone(),
+            hir_types: self.hir_types,
+            module_group: self.module_group,
+        }
+    }
+
     /// Generates IR for the body of the function.
     pub fn gen_fn_body(&mut self) {
         // Iterate over all parameters and their type and store them so we can reference them
         // later in code.
-        for (i, (pat, _ty)) in self.body.params().iter().enumerate() {
-            let body = self.body.clone(); // Avoid borrow issues
+        let params: Vec<PatId> = self.body.params().iter().map(|(pat, _)| *pat).collect();
+        self.gen_param_bindings(&params, 0);
+
+        // Generate code for the body of the function
+        let fn_ret_type = self
+            .hir_function
+            .ty(self.db)
+            .callable_sig(self.db)
+            .unwrap()
+            .ret()
+            .clone()
+            .subst(&self.substs);
+        self.gen_body_return(self.body.body_expr(), &fn_ret_type);
+    }
 
+    /// Generates IR for the body of the lambda `expr`. The object of the lambda is passed as the
+    /// first parameter; its memory stores the captured variables, which are laid out as
+    /// `closure_ir_ty`.
+    fn gen_lambda_body(
+        &mut self,
+        expr: ExprId,
+        captures: &[PatId],
+        closure_ir_ty: StructType<'ink>,
+    ) {
+        let body = self.body.clone();
+        let (args, lambda_body) = match &body[expr] {
+            Expr::Lambda { args, body, .. } => (args, *body),
+            _ => unreachable!("expected a lambda expression"),
+        };
+
+        // Captured variables are accessed through the memory of the object, so assignments to
+        // them persist between calls.
+        let object_ptr = self.fn_value.get_nth_param(0).unwrap();
+        let closure_ptr_ptr = self
+            .builder
+            .build_bitcast(
+                object_ptr,
+                closure_ir_ty
+                    .ptr_type(AddressSpace::Generic)
+                    .ptr_type(AddressSpace::Generic),
+                "closure_ptr_ptr",
+            )
+            .into_pointer_value();
+        let mem_ptr = self
+            .builder
+            .build_load(closure_ptr_ptr, "closure_mem_ptr")
+            .into_pointer_value();
+        for (idx, pat) in captures.iter().enumerate() {
+            let name = match &body[*pat] {
+                Pat::Bind { name } => name.to_string(),
+                _ => unreachable!("only bindings can be captured"),
+            };
+            let ptr = self
+                .builder
+                .build_struct_gep(mem_ptr, idx as u32 + 1, &format!("{}_ptr", name))
+                .expect("could not get pointer to a captured variable");
+            self.pat_to_local.insert(*pat, ptr);
+            self.pat_to_name.insert(*pat, name);
+        }
+
+        // The parameters of the lambda follow the object
+        let params: Vec<PatId> = args.iter().map(|(pat, _)| *pat).collect();
+        self.gen_param_bindings(&params, 1);
+
+        let ret_type = self.infer[expr]
+            .as_fn_ptr()
+            .expect("expected a function pointer type")
+            .ret()
+            .clone();
+        self.gen_body_return(lambda_body, &ret_type);
+    }
+
+    /// Stores the parameters of the function, starting at `first_param`, so they can be
+    /// referenced by the patterns in `params`.
+    fn gen_param_bindings(&mut self, params: &[PatId], first_param: u32) {
+        let body = self.body.clone(); // Avoid borrow issues
+        for (i, pat) in params.iter().enumerate() {
+            let param_idx = first_param + i as u32;
             match &body[*pat] {
                 Pat::Bind { name } => {
                     let name = name.to_string();
-                    let param = self.fn_value.get_nth_param(i as u32).unwrap();
+                    let param = self.fn_value.get_nth_param(param_idx).unwrap();
                     let builder = self.new_alloca_builder();
                     let param_ptr = builder.build_alloca(param.get_type(), &name);
                     builder.build_store(param_ptr, param);
@@ -118,31 +249,28 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 Pat::Wild => {
                     // Wildcard patterns cannot be referenced from code. So nothing to do.
                 }
-                Pat::Path(_) => unreachable!(
-                    "Path patterns are not supported as parameters, are we missing a diagnostic?"
-                ),
+                Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => {
+                    // The pattern is irrefutable, so the parameter can be destructured directly
+                    let param = self.fn_value.get_nth_param(param_idx).unwrap();
+                    self.gen_pat_bindings(*pat, Some(param));
+                }
                 Pat::Missing => unreachable!(
                     "found missing Pattern, should not be generating IR for incomplete code"
                 ),
             }
         }
+    }
 
-        // Generate code for the body of the function
-        let ret_value = self.gen_expr(self.body.body_expr());
+    /// Generates IR for `body_expr` and returns its value from the function.
+    fn gen_body_return(&mut self, body_expr: ExprId, ret_type: &Ty) {
+        let ret_value = self.gen_expr(body_expr);
 
         // Construct a return statement from the returned value of the body if a return is expected
         // in the first place. If the return type of the body is `never` there is no need to
         // generate a return statement.
-        let block_ret_type = &self.infer[self.body.body_expr()];
-        let fn_ret_type = self
-            .hir_function
-            .ty(self.db)
-            .callable_sig(self.db)
-            .unwrap()
-            .ret()
-            .clone();
+        let block_ret_type = &self.infer[body_expr];
         if !block_ret_type.is_never() {
-            if fn_ret_type.is_empty() {
+            if ret_type.is_empty() {
                 self.builder.build_return(None);
             } else if let Some(value) = ret_value {
                 self.builder.build_return(Some(&value));
@@ -164,6 +292,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     } else {
                         param
                     }
+                } else if ty.as_enum().is_some() {
+                    deref_heap_value(&self.builder, param)
                 } else {
                     param
                 }
@@ -191,10 +321,17 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 let ret_value = if let Some(hir_struct) = fn_ret_type.as_struct() {
                     if hir_struct.data(self.db.upcast()).memory_kind == hir::StructMemoryKind::Value
                     {
-                        self.gen_struct_alloc_on_heap(hir_struct, value.into_struct_value())
+                        self.gen_struct_alloc_on_heap(&fn_ret_type, value.into_struct_value())
                     } else {
                         value
                     }
+                } else if let Some(hir_enum) = fn_ret_type.as_enum() {
+                    self.gen_value_alloc_on_heap(
+                        &fn_ret_type,
+                        self.hir_types.get_enum_type(hir_enum),
+                        &hir_enum.name(self.db).to_string(),
+                        value.into_struct_value(),
+                    )
                 } else {
                     value
                 };
@@ -235,25 +372,42 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                             .map(|expr| self.gen_expr(*expr).expect("expected a value"))
                             .collect();
 
-                        self.gen_call(def, &args)
-                            .try_as_basic_value()
-                            .left()
-                            // If the called function is a void function it doesn't return anything.
-                            // If this method (`gen_expr`) returns None we assume the return value
-                            // is `never`. We return a const unit struct here to ensure that at
-                            // least something is returned. This matches with the hir where a
-                            // `nothing` is returned instead of a `never`.
-                            //
-                            // This unit value will also be optimized out.
-                            .or_else(|| match self.infer[expr].interned() {
-                                TyKind::Never => None,
-                                _ => Some(self.context.const_struct(&[], false).into()),
-                            })
+                        self.gen_fn_call(expr, def, &args)
                     }
                     Some(hir::CallableDef::Struct(_)) => Some(self.gen_named_tuple_lit(expr, args)),
-                    None => panic!("expected a callable expression"),
+                    Some(hir::CallableDef::EnumVariant(variant)) => {
+                        let args = args
+                            .iter()
+                            .map(|expr| self.gen_expr(*expr))
+                            .collect::<Option<Vec<BasicValueEnum>>>()?;
+                        Some(self.gen_enum_variant_alloc(variant, args))
+                    }
+                    Some(hir::CallableDef::Intrinsic(intrinsic)) => {
+                        // Get all the arguments
+                        let args: Vec<BasicValueEnum> = args
+                            .iter()
+                            .map(|expr| self.gen_expr(*expr).expect("expected a value"))
+                            .collect();
+
+                        Some(self.gen_intrinsic_call(intrinsic, &args))
+                    }
+                    None => self.gen_closure_call(expr, *callee, args),
                 }
             }
+            Expr::MethodCall { receiver, args, .. } => {
+                let def = self
+                    .infer
+                    .method_resolution(expr)
+                    .expect("expected a resolved method");
+
+                // The receiver is passed as the first argument
+                let args: Vec<BasicValueEnum> = once(*receiver)
+                    .chain(args.iter().copied())
+                    .map(|expr| self.gen_expr(expr).expect("expected a value"))
+                    .collect();
+
+                self.gen_fn_call(expr, def, &args)
+            }
             Expr::If {
                 condition,
                 then_branch,
@@ -262,11 +416,24 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             Expr::Return { expr: ret_expr } => self.gen_return(expr, *ret_expr),
             Expr::Loop { body } => self.gen_loop(expr, *body),
             Expr::While { condition, body } => self.gen_while(expr, *condition, *body),
+            Expr::For {
+                pat,
+                iterable,
+                body,
+            } => self.gen_for(expr, *pat, *iterable, *body),
             Expr::Break { expr: break_expr } => self.gen_break(expr, *break_expr),
+            Expr::Continue => self.gen_continue(expr),
             Expr::Field {
                 expr: receiver_expr,
                 name,
             } => self.gen_field(expr, *receiver_expr, name),
+            Expr::Array(elements) => self.gen_array_lit(expr, elements),
+            Expr::Index { base, index } => self.gen_index(expr, *base, *index),
+            Expr::Match {
+                expr: match_expr,
+                arms,
+            } => self.gen_match(expr, *match_expr, arms),
+            Expr::Lambda { .. } => Some(self.gen_lambda(expr)),
             _ => unimplemented!("unimplemented expr type {:?}", &body[expr]),
         }
     }
@@ -324,10 +491,63 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 }
             }
 
-            Literal::String(_) => unimplemented!("string literals are not implemented yet"),
+            Literal::String(value) => self.gen_string_alloc(value),
         }
     }
 
+    /// Allocates a string on the heap that is initialized with the specified `value`.
+    fn gen_string_alloc(&mut self, value: &str) -> BasicValueEnum<'ink> {
+        let new_string_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::new_string,
+        );
+
+        // Store the UTF-8 encoded bytes of the literal in a private constant
+        let byte_ty = self.context.i8_type();
+        let bytes: Vec<IntValue> = value
+            .bytes()
+            .map(|b| byte_ty.const_int(u64::from(b), false))
+            .collect();
+        let bytes = byte_ty.const_array(&bytes);
+        let bytes_global = self.module.add_global(bytes.get_type(), None, "str");
+        bytes_global.set_linkage(Linkage::Private);
+        bytes_global.set_constant(true);
+        bytes_global.set_unnamed_address(UnnamedAddress::Global);
+        bytes_global.set_initializer(&bytes);
+
+        let bytes_ptr = self.builder.build_bitcast(
+            bytes_global.as_pointer_value(),
+            byte_ty.ptr_type(AddressSpace::Generic),
+            "str_ptr",
+        );
+
+        let length = self
+            .hir_types
+            .get_int_type(hir::IntTy::usize())
+            .const_int(value.len() as u64, false);
+
+        let allocator_handle = self.builder.build_load(
+            self.external_globals
+                .alloc_handle
+                .expect("no allocator handle was specified, this is required for strings")
+                .as_pointer_value(),
+            "allocator_handle",
+        );
+
+        // Just like structs, the object pointer adds an extra layer of indirection to allow for
+        // moving of the string's memory.
+        self.builder
+            .build_call(
+                new_string_fn_ptr,
+                &[bytes_ptr, length.into(), allocator_handle],
+                "new_string",
+            )
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+    }
+
     /// Constructs an empty struct value e.g. `{}`
     fn gen_empty(&mut self) -> BasicValueEnum<'ink> {
         self.context.const_struct(&[], false).into()
@@ -336,12 +556,17 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     /// Allocate a struct literal either on the stack or the heap based on the type of the struct.
     fn gen_struct_alloc(
         &mut self,
-        hir_struct: hir::Struct,
+        struct_ty: &Ty,
         args: Vec<BasicValueEnum<'ink>>,
     ) -> BasicValueEnum<'ink> {
+        let (hir_struct, substs) = match struct_ty.interned() {
+            TyKind::Struct(hir_struct, substs) => (*hir_struct, substs),
+            _ => unreachable!("expected a struct type"),
+        };
+
         // Construct the struct literal
-        let struct_ty = self.hir_types.get_struct_type(hir_struct);
-        let mut value: AggregateValueEnum = struct_ty.get_undef().into();
+        let struct_ir_ty = self.hir_types.get_struct_type(hir_struct, substs);
+        let mut value: AggregateValueEnum = struct_ir_ty.get_undef().into();
         for (i, arg) in args.into_iter().enumerate() {
             value = self
                 .builder
@@ -354,17 +579,51 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             hir::StructMemoryKind::Value => struct_lit.into(),
             hir::StructMemoryKind::Gc => {
                 // TODO: Root memory in GC
-                self.gen_struct_alloc_on_heap(hir_struct, struct_lit)
+                self.gen_struct_alloc_on_heap(struct_ty, struct_lit)
             }
         }
     }
 
     fn gen_struct_alloc_on_heap(
         &mut self,
-        hir_struct: hir::Struct,
-        struct_lit: StructValue,
+        struct_ty: &Ty,
+        struct_lit: StructValue<'ink>,
+    ) -> BasicValueEnum<'ink> {
+        let (hir_struct, substs) = match struct_ty.interned() {
+            TyKind::Struct(hir_struct, substs) => (*hir_struct, substs),
+            _ => unreachable!("expected a struct type"),
+        };
+
+        self.gen_value_alloc_on_heap(
+            struct_ty,
+            self.hir_types.get_struct_type(hir_struct, substs),
+            &struct_ty.display(self.db).to_string(),
+            struct_lit,
+        )
+    }
+
+    /// Allocates an object of type `ty` on the heap and initializes it with `value`. Returns the
+    /// object pointer of the allocated memory.
+    fn gen_value_alloc_on_heap(
+        &mut self,
+        ty: &Ty,
+        ir_ty: StructType<'ink>,
+        name: &str,
+        value: StructValue<'ink>,
+    ) -> BasicValueEnum<'ink> {
+        let type_info = self.hir_types.type_info(ty);
+        self.gen_object_alloc(&type_info, ir_ty, name, value)
+    }
+
+    /// Allocates an object described by `type_info` on the heap and initializes it with `value`.
+    /// Returns the object pointer of the allocated memory.
+    fn gen_object_alloc(
+        &mut self,
+        type_info: &TypeInfo,
+        ir_ty: StructType<'ink>,
+        name: &str,
+        value: StructValue<'ink>,
     ) -> BasicValueEnum<'ink> {
-        let struct_ir_ty = self.hir_types.get_struct_type(hir_struct);
         let new_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
             self.external_globals.dispatch_table,
             &self.builder,
@@ -374,7 +633,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         let type_info_ptr = self.type_table.gen_type_info_lookup(
             self.context,
             &self.builder,
-            &self.hir_types.type_info(&hir_struct.ty(self.db)),
+            type_info,
             self.external_globals.type_table,
         );
 
@@ -408,61 +667,231 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .builder
             .build_bitcast(
                 object_ptr,
-                struct_ir_ty
+                ir_ty
                     .ptr_type(AddressSpace::Generic)
                     .ptr_type(AddressSpace::Generic),
-                &format!("{}_ptr_ptr", hir_struct.name(self.db).to_string()),
+                &format!("{}_ptr_ptr", name),
             )
             .into_pointer_value();
 
         // Load the actual memory location of the struct
         let mem_ptr = self
             .builder
-            .build_load(
-                struct_ptr_ptr,
-                &format!("{}_mem_ptr", hir_struct.name(self.db).to_string()),
-            )
+            .build_load(struct_ptr_ptr, &format!("{}_mem_ptr", name))
             .into_pointer_value();
 
         // Store the struct value
-        self.builder.build_store(mem_ptr, struct_lit);
+        self.builder.build_store(mem_ptr, value);
 
         struct_ptr_ptr.into()
     }
 
+    /// Constructs an enum value of the specified `variant`, initialized with the field values in
+    /// `args`.
+    fn gen_enum_variant_alloc(
+        &mut self,
+        variant: hir::EnumVariant,
+        args: Vec<BasicValueEnum<'ink>>,
+    ) -> BasicValueEnum<'ink> {
+        let enum_ir_ty = self.hir_types.get_enum_type(variant.parent_enum());
+        let variant_name = variant.name(self.db).to_string();
+
+        // Construct the enum in stack memory by writing the tag and fields of the variant
+        let enum_ptr = self
+            .new_alloca_builder()
+            .build_alloca(enum_ir_ty, &variant_name);
+        let variant_ptr = self.gen_enum_variant_ptr(variant, enum_ptr);
+        let tag_ptr = self
+            .builder
+            .build_struct_gep(variant_ptr, 0, "tag_ptr")
+            .expect("could not get pointer to the tag of an enum");
+        let tag = self
+            .hir_types
+            .get_enum_tag_type()
+            .const_int(u64::from(variant.index(self.db)), false);
+        self.builder.build_store(tag_ptr, tag);
+        for (idx, arg) in args.into_iter().enumerate() {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(variant_ptr, idx as u32 + 1, "field_ptr")
+                .expect("could not get pointer to the field of an enum variant");
+            self.builder.build_store(field_ptr, arg);
+        }
+
+        self.builder.build_load(enum_ptr, &variant_name)
+    }
+
+    /// Casts a pointer to the memory of an enum to a pointer to the memory of the specified
+    /// `variant`.
+    fn gen_enum_variant_ptr(
+        &self,
+        variant: hir::EnumVariant,
+        enum_ptr: PointerValue<'ink>,
+    ) -> PointerValue<'ink> {
+        self.builder
+            .build_bitcast(
+                enum_ptr,
+                self.hir_types
+                    .get_enum_variant_type(variant)
+                    .ptr_type(AddressSpace::Generic),
+                &format!("{}_ptr", variant.name(self.db)),
+            )
+            .into_pointer_value()
+    }
+
+    /// Stores the enum `value` in stack memory and returns a pointer to it.
+    fn gen_enum_spill(
+        &self,
+        hir_enum: hir::Enum,
+        value: BasicValueEnum<'ink>,
+    ) -> PointerValue<'ink> {
+        let enum_ptr = self
+            .new_alloca_builder()
+            .build_alloca(self.hir_types.get_enum_type(hir_enum), "enum_tmp");
+        self.builder.build_store(enum_ptr, value);
+        enum_ptr
+    }
+
     /// Generates IR for a record literal, e.g. `Foo { a: 1.23, b: 4 }`
     fn gen_record_lit(
         &mut self,
         type_expr: ExprId,
         fields: &[hir::RecordLitField],
     ) -> BasicValueEnum<'ink> {
+        if let Some(VariantDef::EnumVariant(variant)) =
+            self.infer.variant_resolution_for_expr(type_expr)
+        {
+            // Order the fields of the variant by their declaration
+            let mut values = vec![None; variant.fields(self.db).len()];
+            for field in fields.iter() {
+                let idx = variant
+                    .field(self.db, &field.name)
+                    .expect("expected an enum variant field")
+                    .index(self.db);
+                values[idx as usize] = self.gen_expr(field.expr);
+            }
+            let values = values
+                .into_iter()
+                .map(|value| value.expect("expected a field value"))
+                .collect();
+            return self.gen_enum_variant_alloc(variant, values);
+        }
+
         let struct_ty = self.infer[type_expr].clone();
-        let hir_struct = struct_ty.as_struct().unwrap(); // Can only really get here if the type is a struct
         let fields: Vec<BasicValueEnum> = fields
             .iter()
             .map(|field| self.gen_expr(field.expr).expect("expected a field value"))
             .collect();
 
-        self.gen_struct_alloc(hir_struct, fields)
+        self.gen_struct_alloc(&struct_ty, fields)
     }
 
     /// Generates IR for a named tuple literal, e.g. `Foo(1.23, 4)`
     fn gen_named_tuple_lit(&mut self, type_expr: ExprId, args: &[ExprId]) -> BasicValueEnum<'ink> {
         let struct_ty = self.infer[type_expr].clone();
-        let hir_struct = struct_ty.as_struct().unwrap(); // Can only really get here if the type is a struct
         let args: Vec<BasicValueEnum> = args
             .iter()
             .map(|expr| self.gen_expr(*expr).expect("expected a field value"))
             .collect();
 
-        self.gen_struct_alloc(hir_struct, args)
+        self.gen_struct_alloc(&struct_ty, args)
+    }
+
+    /// Generates IR for an array literal, e.g. `[1, 2, 3]`
+    fn gen_array_lit(
+        &mut self,
+        type_expr: ExprId,
+        elements: &[ExprId],
+    ) -> Option<BasicValueEnum<'ink>> {
+        let array_ty = self.infer[type_expr].clone();
+        let element_ty = array_ty.as_array().expect("expected an array type");
+
+        // Generate the values of all elements before allocating the array. If any of them never
+        // returns, the array is never constructed.
+        let elements = elements
+            .iter()
+            .map(|expr| self.gen_expr(*expr))
+            .collect::<Option<Vec<BasicValueEnum>>>()?;
+
+        let new_array_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::new_array,
+        );
+
+        let type_info_ptr = self.type_table.gen_type_info_lookup(
+            self.context,
+            &self.builder,
+            &self.hir_types.type_info(&array_ty),
+            self.external_globals.type_table,
+        );
+
+        // HACK: We should be able to use pointers for built-in struct types like `TypeInfo` in intrinsics
+        let type_info_ptr = self.builder.build_bitcast(
+            type_info_ptr,
+            self.context.i8_type().ptr_type(AddressSpace::Generic),
+            "type_info_ptr_to_i8_ptr",
+        );
+
+        let length = self
+            .hir_types
+            .get_int_type(hir::IntTy::usize())
+            .const_int(elements.len() as u64, false);
+
+        let allocator_handle = self.builder.build_load(
+            self.external_globals
+                .alloc_handle
+                .expect("no allocator handle was specified, this is required for arrays")
+                .as_pointer_value(),
+            "allocator_handle",
+        );
+
+        // Just like structs, the object pointer adds an extra layer of indirection to allow for
+        // moving of the array's memory.
+        let object_ptr = self
+            .builder
+            .build_call(
+                new_array_fn_ptr,
+                &[type_info_ptr, length.into(), allocator_handle],
+                "new_array",
+            )
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        // Cast the object pointer to the array type
+        let array_ptr_ptr = self
+            .builder
+            .build_bitcast(
+                object_ptr,
+                self.hir_types.get_array_reference_type(element_ty),
+                "array_ptr_ptr",
+            )
+            .into_pointer_value();
+
+        // Load the actual memory location of the array and store the elements
+        let mem_ptr = self
+            .builder
+            .build_load(array_ptr_ptr, "array_mem_ptr")
+            .into_pointer_value();
+        for (idx, element) in elements.into_iter().enumerate() {
+            let element_ptr = self.gen_array_element_gep(
+                mem_ptr,
+                self.hir_types
+                    .get_int_type(hir::IntTy::usize())
+                    .const_int(idx as u64, false),
+            );
+            self.builder.build_store(element_ptr, element);
+        }
+
+        Some(array_ptr_ptr.into())
     }
 
     /// Generates IR for a unit struct literal, e.g `Foo`
     fn gen_unit_struct_lit(&mut self, type_expr: ExprId) -> BasicValueEnum<'ink> {
         let struct_ty = self.infer[type_expr].clone();
-        let hir_struct = struct_ty.as_struct().unwrap(); // Can only really get here if the type is a struct
-        self.gen_struct_alloc(hir_struct, Vec::new())
+        self.gen_struct_alloc(&struct_ty, Vec::new())
     }
 
     /// Generates IR for the specified block expression.
@@ -526,7 +955,16 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             None => None,
         };
 
-        match &self.body[pat] {
+        // The pattern is irrefutable, so the initializer can be destructured directly
+        self.gen_pat_bindings(pat, initializer);
+        true
+    }
+
+    /// Generates IR that introduces the local variables bound by the pattern `pat` and initializes
+    /// them with the corresponding parts of `value`. The pattern must match `value`.
+    fn gen_pat_bindings(&mut self, pat: PatId, value: Option<BasicValueEnum<'ink>>) {
+        let body = self.body.clone();
+        match &body[pat] {
             Pat::Bind { name } => {
                 let builder = self.new_alloca_builder();
                 let pat_ty = self.infer[pat].clone();
@@ -538,15 +976,128 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 self.pat_to_local.insert(pat, ptr);
                 self.pat_to_name.insert(pat, name.to_string());
                 if !(pat_ty.is_empty() || pat_ty.is_never()) {
-                    if let Some(value) = initializer {
+                    if let Some(value) = value {
                         self.builder.build_store(ptr, value);
                     };
                 }
             }
+            Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => {
+                let variant = self
+                    .infer
+                    .variant_resolution_for_pat(pat)
+                    .expect("unresolved pattern");
+                for (field_pat, field_idx) in self.field_pats(pat, variant) {
+                    let field_value =
+                        value.map(|value| self.gen_variant_field(variant, value, field_idx));
+                    self.gen_pat_bindings(field_pat, field_value);
+                }
+            }
             Pat::Wild => {}
-            Pat::Missing | Pat::Path(_) => unreachable!(),
+            Pat::Missing => unreachable!(),
+        }
+    }
+
+    /// Generates IR that tests whether `value` matches the pattern `pat`. If it doesn't, execution
+    /// continues at `no_match_block`; otherwise the builder is positioned at the block where the
+    /// match succeeded.
+    fn gen_pat_match(
+        &mut self,
+        pat: PatId,
+        value: BasicValueEnum<'ink>,
+        no_match_block: BasicBlock<'ink>,
+    ) {
+        let variant = match &self.body[pat] {
+            Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => self
+                .infer
+                .variant_resolution_for_pat(pat)
+                .expect("unresolved pattern"),
+            Pat::Bind { .. } | Pat::Wild | Pat::Missing => return,
+        };
+
+        // Compare the tag of an enum to the tag of the variant
+        if let VariantDef::EnumVariant(variant) = variant {
+            let tag_ty = self.hir_types.get_enum_tag_type();
+            let enum_ptr = self.gen_enum_spill(variant.parent_enum(), value);
+            let tag_ptr = self
+                .builder
+                .build_bitcast(enum_ptr, tag_ty.ptr_type(AddressSpace::Generic), "tag_ptr")
+                .into_pointer_value();
+            let tag = self.builder.build_load(tag_ptr, "tag").into_int_value();
+            let is_variant = self.builder.build_int_compare(
+                IntPredicate::EQ,
+                tag,
+                tag_ty.const_int(u64::from(variant.index(self.db)), false),
+                "is_variant",
+            );
+
+            let match_block = self
+                .context
+                .append_basic_block(self.fn_value, "variant_match");
+            self.builder
+                .build_conditional_branch(is_variant, match_block, no_match_block);
+            self.builder.position_at_end(match_block);
+        }
+
+        // Match the fields of the variant
+        for (field_pat, field_idx) in self.field_pats(pat, variant) {
+            let field_value = self.gen_variant_field(variant, value, field_idx);
+            self.gen_pat_match(field_pat, field_value, no_match_block);
+        }
+    }
+
+    /// Returns the sub-patterns of the pattern `pat` that destructures `variant`, together with
+    /// the index of the field they match.
+    fn field_pats(&self, pat: PatId, variant: VariantDef) -> Vec<(PatId, u32)> {
+        match &self.body[pat] {
+            Pat::TupleStruct { args, .. } => args
+                .iter()
+                .enumerate()
+                .map(|(idx, arg)| (*arg, idx as u32))
+                .collect(),
+            Pat::Record { fields, .. } => {
+                let field_names = variant.field_names(self.db);
+                fields
+                    .iter()
+                    .filter_map(|field| {
+                        field_names
+                            .iter()
+                            .position(|name| *name == field.name)
+                            .map(|idx| (field.pat, idx as u32))
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Generates IR that loads the field with index `field_idx` from the `value` of a struct or
+    /// enum variant.
+    fn gen_variant_field(
+        &self,
+        variant: VariantDef,
+        value: BasicValueEnum<'ink>,
+        field_idx: u32,
+    ) -> BasicValueEnum<'ink> {
+        match variant {
+            VariantDef::Struct(hir_struct) => {
+                let value = match hir_struct.data(self.db.upcast()).memory_kind {
+                    hir::StructMemoryKind::Gc => deref_heap_value(&self.builder, value),
+                    hir::StructMemoryKind::Value => value,
+                };
+                self.builder
+                    .build_extract_value(value.into_struct_value(), field_idx, "field")
+                    .expect("could not extract struct field")
+            }
+            VariantDef::EnumVariant(variant) => {
+                let enum_ptr = self.gen_enum_spill(variant.parent_enum(), value);
+                let variant_ptr = self.gen_enum_variant_ptr(variant, enum_ptr);
+                let field_ptr = self
+                    .builder
+                    .build_struct_gep(variant_ptr, field_idx + 1, "field_ptr")
+                    .expect("could not get pointer to the field of an enum variant");
+                self.builder.build_load(field_ptr, "field")
+            }
         }
-        true
     }
 
     /// Generates IR for looking up a certain path expression.
@@ -561,21 +1112,228 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .expect("unknown path")
             .0
         {
-            ValueNs::LocalBinding(pat) => {
-                if let Some(param) = self.pat_to_param.get(&pat) {
-                    *param
-                } else if let Some(ptr) = self.pat_to_local.get(&pat) {
-                    let name = self.pat_to_name.get(&pat).expect("could not find pat name");
-                    self.builder.build_load(*ptr, &name)
-                } else {
-                    unreachable!("could not find the pattern..");
+            ValueNs::LocalBinding(pat) => self.gen_local_binding(pat),
+            ValueNs::StructId(_) => self.gen_unit_struct_lit(expr),
+            ValueNs::EnumVariantId(_) => match self.infer.variant_resolution_for_expr(expr) {
+                Some(VariantDef::EnumVariant(variant)) => {
+                    self.gen_enum_variant_alloc(variant, Vec::new())
                 }
+                _ => unreachable!("expected an enum variant"),
+            },
+            ValueNs::FunctionId(function) if self.infer[expr].as_fn_ptr().is_some() => {
+                self.gen_fn_ptr(expr, function.into())
             }
-            ValueNs::StructId(_) => self.gen_unit_struct_lit(expr),
-            ValueNs::FunctionId(_) => panic!("unable to generate path expression from a function"),
+            ValueNs::ConstId(c) => {
+                let c = hir::Const::from(c);
+                let value = c
+                    .value(self.db)
+                    .expect("the value of a constant must be known at compile time");
+                global::gen_const_value(self.hir_types, &self.infer[expr], value)
+            }
+            ValueNs::StaticId(s) => self.gen_static_load(s.into()),
+            ValueNs::FunctionId(_) | ValueNs::Intrinsic(_) => {
+                panic!("unable to generate path expression from a function")
+            }
+        }
+    }
+
+    /// Returns a pointer to the global that stores the value of the `static`.
+    fn gen_static_ptr(&self, s: hir::Static) -> PointerValue<'ink> {
+        self.external_globals
+            .statics
+            .get(&s)
+            .unwrap_or_else(|| {
+                panic!(
+                    "no support for accessing `{}` from another module group",
+                    s.full_name(self.db)
+                )
+            })
+            .as_pointer_value()
+    }
+
+    /// Generates IR that atomically loads the value of the `static`.
+    fn gen_static_load(&self, s: hir::Static) -> BasicValueEnum<'ink> {
+        let ty = s.ty(self.db);
+        let (access_type, alignment) = global::static_access_type(self.hir_types, &ty);
+        let ptr = self.builder.build_bitcast(
+            self.gen_static_ptr(s),
+            access_type.ptr_type(AddressSpace::Generic),
+            "static_ptr",
+        );
+        let value = self.builder.build_load(ptr.into_pointer_value(), "static");
+        global::set_atomic(
+            value
+                .as_instruction_value()
+                .expect("expected a load instruction"),
+            alignment,
+        );
+
+        if let TyKind::Bool = ty.interned() {
+            self.builder
+                .build_int_truncate(
+                    value.into_int_value(),
+                    self.hir_types.get_bool_type(),
+                    "static",
+                )
+                .into()
+        } else {
+            value
+        }
+    }
+
+    /// Generates IR that atomically stores `value` in the `static`.
+    fn gen_static_store(&self, s: hir::Static, value: BasicValueEnum<'ink>) {
+        let ty = s.ty(self.db);
+        let (access_type, alignment) = global::static_access_type(self.hir_types, &ty);
+        let ptr = self.builder.build_bitcast(
+            self.gen_static_ptr(s),
+            access_type.ptr_type(AddressSpace::Generic),
+            "static_ptr",
+        );
+        let value = if let TyKind::Bool = ty.interned() {
+            self.builder
+                .build_int_z_extend(
+                    value.into_int_value(),
+                    access_type.into_int_type(),
+                    "static",
+                )
+                .into()
+        } else {
+            value
+        };
+        let store = self.builder.build_store(ptr.into_pointer_value(), value);
+        global::set_atomic(store, alignment);
+    }
+
+    /// Generates IR that loads the value of the local variable bound by `pat`.
+    fn gen_local_binding(&self, pat: PatId) -> BasicValueEnum<'ink> {
+        if let Some(param) = self.pat_to_param.get(&pat) {
+            *param
+        } else if let Some(ptr) = self.pat_to_local.get(&pat) {
+            let name = self.pat_to_name.get(&pat).expect("could not find pat name");
+            self.builder.build_load(*ptr, &name)
+        } else {
+            unreachable!("could not find the pattern..");
         }
     }
 
+    /// Generates IR for a lambda expression. The body of the lambda is generated as a separate
+    /// function. The lambda itself is an object that stores a pointer to that function, followed
+    /// by the values of the captured variables.
+    fn gen_lambda(&mut self, expr: ExprId) -> BasicValueEnum<'ink> {
+        let instance = FunctionInstance::new(self.hir_function, self.substs.clone());
+        let sig = self.infer[expr]
+            .as_fn_ptr()
+            .expect("expected a function pointer type")
+            .clone();
+        let captures = self.infer.lambda_captures(expr).to_vec();
+        let capture_tys: Vec<Ty> = captures
+            .iter()
+            .map(|pat| self.infer[*pat].clone())
+            .collect();
+        let closure_ir_ty = self.hir_types.get_closure_type(&capture_tys);
+
+        // Generate the function of the lambda
+        let name = closure::lambda_name(self.db, &instance, expr);
+        let lambda_fn = self.module.add_function(
+            &name,
+            self.hir_types.get_closure_function_type(&sig),
+            Some(Linkage::Private),
+        );
+        self.new_nested_generator(lambda_fn)
+            .gen_lambda_body(expr, &captures, closure_ir_ty);
+
+        // Copy the values of the captured variables into the object
+        let fn_ptr = self.gen_fn_ptr_value(lambda_fn);
+        let mut value: AggregateValueEnum = closure_ir_ty.get_undef().into();
+        for (idx, value_ir) in once(fn_ptr)
+            .chain(captures.iter().map(|pat| self.gen_local_binding(*pat)))
+            .enumerate()
+        {
+            value = self
+                .builder
+                .build_insert_value(value, value_ir, idx as u32, "init")
+                .expect("Failed to initialize closure field.");
+        }
+
+        let type_info = closure::lambda_type_info(
+            self.db,
+            self.hir_types,
+            &instance,
+            &self.body,
+            &self.infer,
+            expr,
+        );
+        let object_ptr = self.gen_object_alloc(
+            &type_info,
+            closure_ir_ty,
+            "closure",
+            value.into_struct_value(),
+        );
+
+        // All function pointers share the same type, regardless of what they capture
+        self.builder.build_bitcast(
+            object_ptr,
+            self.hir_types.get_fn_ptr_reference_type(),
+            "fn_ptr_object",
+        )
+    }
+
+    /// Generates IR for a function that is used as a value of the function pointer type of
+    /// expression `expr`. The resulting object points to a thunk that calls the function.
+    fn gen_fn_ptr(&mut self, expr: ExprId, function: hir::Function) -> BasicValueEnum<'ink> {
+        let thunk = self.gen_fn_ptr_thunk(function);
+        let closure_ir_ty = self.hir_types.get_closure_type(&[]);
+        let fn_ptr = self.gen_fn_ptr_value(thunk);
+        let value = self
+            .builder
+            .build_insert_value(closure_ir_ty.get_undef(), fn_ptr, 0, "init")
+            .expect("Failed to initialize function pointer.")
+            .into_struct_value();
+
+        let type_info = self.hir_types.type_info(&self.infer[expr]);
+        self.gen_object_alloc(&type_info, closure_ir_ty, "fn_ptr", value)
+    }
+
+    /// Returns a function with the calling convention of a function pointer, that forwards its
+    /// arguments to `function`. The thunk is only generated once per module.
+    fn gen_fn_ptr_thunk(&self, function: hir::Function) -> FunctionValue<'ink> {
+        let name = format!("{}::thunk", function.full_name(self.db));
+        if let Some(thunk) = self.module.get_function(&name) {
+            return thunk;
+        }
+
+        let sig = function.ty(self.db).callable_sig(self.db).unwrap();
+        let thunk = self.module.add_function(
+            &name,
+            self.hir_types.get_closure_function_type(&sig),
+            Some(Linkage::Private),
+        );
+
+        // Skip the object pointer, a function doesn't capture any variables
+        let args: Vec<BasicValueEnum> = thunk.get_param_iter().skip(1).collect();
+        let mut generator = self.new_nested_generator(thunk);
+        let ret_value = generator
+            .gen_call(function, &args)
+            .try_as_basic_value()
+            .left();
+        match ret_value {
+            Some(value) => generator.builder.build_return(Some(&value)),
+            None => generator.builder.build_return(None),
+        };
+
+        thunk
+    }
+
+    /// Returns a type-agnostic pointer to `function`, which is stored in the object of a closure.
+    fn gen_fn_ptr_value(&self, function: FunctionValue<'ink>) -> BasicValueEnum<'ink> {
+        self.builder.build_bitcast(
+            function.as_global_value().as_pointer_value(),
+            self.context.i8_type().ptr_type(AddressSpace::Generic),
+            "fn_ptr",
+        )
+    }
+
     /// Given an expression and its value optionally dereference the value to get to the actual
     /// value. This is useful if we need to do an indirection to get to the actual value.
     fn opt_deref_value(
@@ -608,9 +1366,12 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 .pat_to_local
                 .get(&pat)
                 .expect("unresolved local binding"),
-            ValueNs::FunctionId(_) | ValueNs::StructId(_) => {
-                panic!("no support for module definitions")
-            }
+            ValueNs::StaticId(_) => unreachable!("statics are stored through `gen_static_store`"),
+            ValueNs::FunctionId(_)
+            | ValueNs::ConstId(_)
+            | ValueNs::StructId(_)
+            | ValueNs::EnumVariantId(_)
+            | ValueNs::Intrinsic(_) => panic!("no support for module definitions"),
         }
     }
 
@@ -627,13 +1388,17 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             TyKind::Bool => self.gen_binary_op_bool(lhs, rhs, op),
             TyKind::Float(_) => self.gen_binary_op_float(lhs, rhs, op),
             TyKind::Int(ty) => self.gen_binary_op_int(lhs, rhs, op, ty.signedness),
-            TyKind::Struct(s) => {
+            TyKind::Struct(s, _) => {
                 if s.data(self.db.upcast()).memory_kind == hir::StructMemoryKind::Value {
                     self.gen_binary_op_value_struct(lhs, rhs, op)
                 } else {
                     self.gen_binary_op_heap_struct(lhs, rhs, op)
                 }
             }
+            TyKind::FnPtr(_) => self.gen_binary_op_heap_struct(lhs, rhs, op),
+            // Strings and tuples can only be assigned to through a type parameter
+            TyKind::Array(_) | TyKind::String => self.gen_binary_op_heap_struct(lhs, rhs, op),
+            TyKind::Enum(_) | TyKind::Tuple(..) => self.gen_binary_op_value_struct(lhs, rhs, op),
             _ => {
                 let rhs_type = self.infer[rhs].clone();
                 unimplemented!(
@@ -729,8 +1494,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     Some(op) => self.gen_arith_bin_op_bool(lhs, rhs, op),
                     None => rhs,
                 };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+                self.gen_primitive_assignment(lhs_expr, rhs.into());
                 Some(self.gen_empty())
             }
             BinaryOp::LogicOp(op) => Some(self.gen_logic_bin_op(lhs, rhs, op).into()),
@@ -792,8 +1556,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     Some(op) => self.gen_arith_bin_op_float(lhs, rhs, op),
                     None => rhs,
                 };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+                self.gen_primitive_assignment(lhs_expr, rhs.into());
                 Some(self.gen_empty())
             }
             _ => unimplemented!("Operator {:?} is not implemented for float", op),
@@ -828,8 +1591,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     Some(op) => self.gen_arith_bin_op_int(lhs, rhs, op, signedness),
                     None => rhs,
                 };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+                self.gen_primitive_assignment(lhs_expr, rhs.into());
                 Some(self.gen_empty())
             }
             _ => unreachable!(format!("Operator {:?} is not implemented for integer", op)),
@@ -857,8 +1619,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     ),
                     None => rhs,
                 };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+                self.gen_assignment(lhs_expr, rhs.into());
                 Some(self.gen_empty())
             }
             _ => unimplemented!("Operator {:?} is not implemented for struct", op),
@@ -886,8 +1647,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     ),
                     None => rhs,
                 };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+                self.gen_assignment(lhs_expr, rhs.into());
                 Some(self.gen_empty())
             }
             _ => unimplemented!("Operator {:?} is not implemented for struct", op),
@@ -1033,20 +1793,95 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     /// Given an expression generate code that results in a memory address that can be used for
     /// other place operations.
     fn gen_place_expr(&mut self, expr: ExprId) -> PointerValue<'ink> {
+        self.gen_place_expr_with_owner(expr).0
+    }
+
+    /// Given an expression generate code that results in a memory address that can be used for
+    /// other place operations, together with the handle of the garbage collected object that
+    /// contains it, if any.
+    fn gen_place_expr_with_owner(
+        &mut self,
+        expr: ExprId,
+    ) -> (PointerValue<'ink>, Option<PointerValue<'ink>>) {
         let body = self.body.clone();
         match &body[expr] {
             Expr::Path(ref p) => {
                 let resolver = hir::resolver_for_expr(self.db.upcast(), self.body.owner(), expr);
-                self.gen_path_place_expr(p, expr, &resolver)
+                (self.gen_path_place_expr(p, expr, &resolver), None)
             }
             Expr::Field {
                 expr: receiver_expr,
                 name,
             } => self.gen_place_field(expr, *receiver_expr, name),
+            Expr::Index { base, index } => {
+                let (element_ptr, array_ptr) = self
+                    .gen_array_element_ptr(*base, *index)
+                    .expect("expected an array element");
+                (element_ptr, Some(array_ptr))
+            }
             _ => unreachable!("invalid place expression"),
         }
     }
 
+    /// Generates IR that stores the primitive `value` in the place `place_expr`.
+    fn gen_primitive_assignment(&mut self, place_expr: ExprId, value: BasicValueEnum<'ink>) {
+        if let Some(s) = self.resolve_static(place_expr) {
+            self.gen_static_store(s, value);
+        } else {
+            let place = self.gen_place_expr(place_expr);
+            self.builder.build_store(place, value);
+        }
+    }
+
+    /// Returns the `static` that the path expression `expr` refers to, if any.
+    fn resolve_static(&self, expr: ExprId) -> Option<hir::Static> {
+        let path = match &self.body[expr] {
+            Expr::Path(path) => path,
+            _ => return None,
+        };
+        let resolver = hir::resolver_for_expr(self.db.upcast(), self.body.owner(), expr);
+        match resolver
+            .resolve_path_as_value_fully(self.db.upcast(), path)?
+            .0
+        {
+            ValueNs::StaticId(s) => Some(s.into()),
+            _ => None,
+        }
+    }
+
+    /// Generates IR that stores `value`, which can reference other objects, in the place
+    /// `place_expr`. If the place is part of a garbage collected object, the garbage collector is
+    /// notified through a write barrier.
+    fn gen_assignment(&mut self, place_expr: ExprId, value: BasicValueEnum<'ink>) {
+        let (place, owner) = self.gen_place_expr_with_owner(place_expr);
+        self.builder.build_store(place, value);
+
+        if let Some(owner) = owner {
+            let write_barrier_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+                self.external_globals.dispatch_table,
+                &self.builder,
+                &intrinsics::write_barrier,
+            );
+            let owner = self.builder.build_bitcast(
+                owner,
+                self.context
+                    .i8_type()
+                    .ptr_type(AddressSpace::Generic)
+                    .ptr_type(AddressSpace::Generic),
+                "owner_ptr",
+            );
+            let allocator_handle = self.builder.build_load(
+                self.external_globals
+                    .alloc_handle
+                    .expect("no allocator handle was specified, this is required for assignments")
+                    .as_pointer_value(),
+                "allocator_handle",
+            );
+            self.builder
+                .build_call(write_barrier_fn_ptr, &[owner, allocator_handle], "");
+        }
+    }
+
     /// Returns true if the specified expression refers to an expression that results in a memory
     /// address that can be used for other place operations.
     fn is_place_expr(&self, expr: ExprId) -> bool {
@@ -1054,6 +1889,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         match &body[expr] {
             Expr::Path(..) => true,
             Expr::Field { expr, .. } => self.is_place_expr(*expr),
+            Expr::Index { .. } => true,
             _ => false,
         }
     }
@@ -1091,6 +1927,100 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Generates IR for a direct call to an instance of a generic function.
+    fn gen_instance_call(
+        &mut self,
+        instance: &FunctionInstance,
+        args: &[BasicValueEnum<'ink>],
+    ) -> CallSiteValue<'ink> {
+        let name = instance.name(self.db);
+        let llvm_function = self.instance_map.get(instance).unwrap_or_else(|| {
+            panic!(
+                "missing function value for instance of hir function: '{}'",
+                name
+            )
+        });
+        self.builder.build_call(*llvm_function, &args, &name)
+    }
+
+    /// Generates IR for a call to a function of which the result is the value of the expression
+    /// `expr`.
+    fn gen_fn_call(
+        &mut self,
+        expr: ExprId,
+        function: hir::Function,
+        args: &[BasicValueEnum<'ink>],
+    ) -> Option<BasicValueEnum<'ink>> {
+        let call = match instance::called_instance(&self.body, &self.infer, expr) {
+            Some(instance) => self.gen_instance_call(&instance, args),
+            None => self.gen_call(function, args),
+        };
+        call.try_as_basic_value()
+            .left()
+            // If the called function is a void function it doesn't return anything. If this method
+            // (`gen_expr`) returns None we assume the return value is `never`. We return a const
+            // unit struct here to ensure that at least something is returned. This matches with
+            // the hir where a `nothing` is returned instead of a `never`.
+            //
+            // This unit value will also be optimized out.
+            .or_else(|| match self.infer[expr].interned() {
+                TyKind::Never => None,
+                _ => Some(self.context.const_struct(&[], false).into()),
+            })
+    }
+
+    /// Generates IR for a call to a value of a function pointer type, e.g. a lambda. The function
+    /// is loaded from the memory of the object, which is passed as the first argument.
+    fn gen_closure_call(
+        &mut self,
+        expr: ExprId,
+        callee: ExprId,
+        args: &[ExprId],
+    ) -> Option<BasicValueEnum<'ink>> {
+        let sig = self.infer[callee]
+            .as_fn_ptr()
+            .expect("expected a callable expression")
+            .clone();
+
+        let object_ptr = self.gen_expr(callee)?;
+        let args: Vec<BasicValueEnum> = once(object_ptr)
+            .chain(
+                args.iter()
+                    .map(|expr| self.gen_expr(*expr).expect("expected a value")),
+            )
+            .collect();
+
+        // Load the function from the memory of the object
+        let mem_ptr = self
+            .builder
+            .build_load(object_ptr.into_pointer_value(), "closure_mem_ptr")
+            .into_pointer_value();
+        let fn_ptr_ptr = self
+            .builder
+            .build_struct_gep(mem_ptr, 0, "fn_ptr_ptr")
+            .expect("could not get pointer to the function of a closure");
+        let fn_ptr = self.builder.build_load(fn_ptr_ptr, "fn_ptr");
+        let fn_ptr = self
+            .builder
+            .build_bitcast(
+                fn_ptr,
+                self.hir_types
+                    .get_closure_function_type(&sig)
+                    .ptr_type(AddressSpace::Generic),
+                "closure_fn",
+            )
+            .into_pointer_value();
+
+        let call = self.builder.build_call(fn_ptr, &args, "closure_call");
+        call.try_as_basic_value()
+            .left()
+            // A void function returns a unit value, see `gen_fn_call`
+            .or_else(|| match self.infer[expr].interned() {
+                TyKind::Never => None,
+                _ => Some(self.context.const_struct(&[], false).into()),
+            })
+    }
+
     /// Generates IR for an if statement.
     fn gen_if(
         &mut self,
@@ -1165,6 +2095,61 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Generates IR for a match expression. The arms are tested in order and the expression of
+    /// the first arm whose pattern matches the value of `match_expr` is evaluated.
+    fn gen_match(
+        &mut self,
+        _expr: ExprId,
+        match_expr: ExprId,
+        arms: &[MatchArm],
+    ) -> Option<BasicValueEnum<'ink>> {
+        let value = self.gen_expr(match_expr)?;
+
+        let merge_block = self
+            .context
+            .append_basic_block(self.fn_value, "match_merge");
+        let mut incoming = Vec::with_capacity(arms.len());
+        for arm in arms.iter() {
+            // If the pattern doesn't match, the next arm is tested
+            let next_block = self.context.append_basic_block(self.fn_value, "match_next");
+            self.gen_pat_match(arm.pat, value, next_block);
+            self.gen_pat_bindings(arm.pat, Some(value));
+
+            let arm_value = self.gen_expr(arm.expr);
+            if !self.infer[arm.expr].is_never() {
+                if let Some(arm_value) = arm_value {
+                    incoming.push((arm_value, self.builder.get_insert_block().unwrap()));
+                }
+                self.builder.build_unconditional_branch(merge_block);
+            }
+
+            let current_block = self.builder.get_insert_block().unwrap();
+            next_block.move_after(current_block).unwrap();
+            self.builder.position_at_end(next_block);
+        }
+
+        // The arms are exhaustive, so if none of the patterns matched we can never get here
+        self.builder.build_unreachable();
+
+        let current_block = self.builder.get_insert_block().unwrap();
+        merge_block.move_after(current_block).unwrap();
+        self.builder.position_at_end(merge_block);
+
+        if incoming.is_empty() {
+            // None of the arms finishes, so neither does the match expression
+            self.builder.build_unreachable();
+            return None;
+        }
+
+        let phi = self
+            .builder
+            .build_phi(incoming[0].0.get_type(), "match_result");
+        for (value, block) in incoming.iter() {
+            phi.add_incoming(&[(value, *block)]);
+        }
+        Some(phi.as_basic_value())
+    }
+
     fn gen_return(
         &mut self,
         _expr: ExprId,
@@ -1199,10 +2184,20 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         None
     }
 
+    fn gen_continue(&mut self, _expr: ExprId) -> Option<BasicValueEnum<'ink>> {
+        let loop_info = self.active_loop.as_ref().unwrap();
+        self.builder
+            .build_unconditional_branch(loop_info.continue_block);
+        None
+    }
+
+    /// Generates the body of a loop. A `break` jumps to `exit_block`, while a `continue` jumps to
+    /// `continue_block`.
     fn gen_loop_block_expr(
         &mut self,
         block: ExprId,
         exit_block: BasicBlock<'ink>,
+        continue_block: BasicBlock<'ink>,
     ) -> (
         BasicBlock<'ink>,
         Vec<(BasicValueEnum<'ink>, BasicBlock<'ink>)>,
@@ -1211,6 +2206,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         // Build a new loop info struct
         let loop_info = LoopInfo {
             exit_block,
+            continue_block,
             break_values: Vec::new(),
         };
 
@@ -1223,6 +2219,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         let LoopInfo {
             exit_block,
             break_values,
+            ..
         } = std::mem::replace(&mut self.active_loop, prev_loop).unwrap();
 
         (exit_block, break_values, value)
@@ -1261,7 +2258,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         // Generate loop block
         self.builder.position_at_end(loop_block);
-        let (exit_block, _, value) = self.gen_loop_block_expr(body_expr, exit_block);
+        let (exit_block, _, value) = self.gen_loop_block_expr(body_expr, exit_block, cond_block);
         if value.is_some() {
             self.builder.build_unconditional_branch(cond_block);
         }
@@ -1272,6 +2269,97 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         Some(self.gen_empty())
     }
 
+    fn gen_for(
+        &mut self,
+        _expr: ExprId,
+        pat: PatId,
+        iterable_expr: ExprId,
+        body_expr: ExprId,
+    ) -> Option<BasicValueEnum<'ink>> {
+        let body = self.body.clone();
+        let (start_expr, end_expr, op) = match &body[iterable_expr] {
+            Expr::Range { lhs, rhs, op } => (*lhs, *rhs, *op),
+            _ => unreachable!("`for` loops can only iterate over ranges"),
+        };
+        let signedness = match self.infer[start_expr].interned() {
+            TyKind::Int(ty) => ty.signedness,
+            _ => unreachable!("the bounds of a range must be integers"),
+        };
+
+        // The bounds of the range are only evaluated once, before entering the loop
+        let start = self
+            .gen_expr(start_expr)
+            .map(|value| self.opt_deref_value(start_expr, value))?
+            .into_int_value();
+        let end = self
+            .gen_expr(end_expr)
+            .map(|value| self.opt_deref_value(end_expr, value))?
+            .into_int_value();
+
+        let counter = self
+            .new_alloca_builder()
+            .build_alloca(start.get_type(), "for.counter");
+        self.builder.build_store(counter, start);
+
+        let context = self.context;
+        let cond_block = context.append_basic_block(self.fn_value, "forcond");
+        let loop_block = context.append_basic_block(self.fn_value, "for");
+        let step_block = context.append_basic_block(self.fn_value, "forstep");
+        let exit_block = context.append_basic_block(self.fn_value, "afterfor");
+
+        // Insert an explicit fall through from the current block to the condition check
+        self.builder.build_unconditional_branch(cond_block);
+
+        // Generate condition block
+        self.builder.position_at_end(cond_block);
+        let index = self
+            .builder
+            .build_load(counter, "for.index")
+            .into_int_value();
+        let in_range = self.gen_cmp_bin_op_int(
+            index,
+            end,
+            CmpOp::Ord {
+                ordering: Ordering::Less,
+                strict: op == RangeOp::Exclusive,
+            },
+            signedness,
+        );
+        self.builder
+            .build_conditional_branch(in_range, loop_block, exit_block);
+
+        // Generate loop block
+        self.builder.position_at_end(loop_block);
+        self.gen_pat_bindings(pat, Some(index.into()));
+        let (exit_block, _, value) = self.gen_loop_block_expr(body_expr, exit_block, step_block);
+        if value.is_some() {
+            self.builder.build_unconditional_branch(step_block);
+        }
+
+        // Generate step block, which advances the counter to the next element of the range
+        self.builder.position_at_end(step_block);
+        if op == RangeOp::Inclusive {
+            // Exit before incrementing the counter, otherwise it would overflow if the range ends
+            // at the maximum value of its type
+            let increment_block = context.append_basic_block(self.fn_value, "forincrement");
+            let is_last =
+                self.gen_cmp_bin_op_int(index, end, CmpOp::Eq { negated: false }, signedness);
+            self.builder
+                .build_conditional_branch(is_last, exit_block, increment_block);
+            self.builder.position_at_end(increment_block);
+        }
+        let next =
+            self.builder
+                .build_int_add(index, index.get_type().const_int(1, false), "for.next");
+        self.builder.build_store(counter, next);
+        self.builder.build_unconditional_branch(cond_block);
+
+        // Generate exit block
+        self.builder.position_at_end(exit_block);
+
+        Some(self.gen_empty())
+    }
+
     fn gen_loop(&mut self, _expr: ExprId, body_expr: ExprId) -> Option<BasicValueEnum<'ink>> {
         let context = self.context;
         let loop_block = context.append_basic_block(self.fn_value, "loop");
@@ -1282,7 +2370,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         // Generate the body of the loop
         self.builder.position_at_end(loop_block);
-        let (exit_block, break_values, value) = self.gen_loop_block_expr(body_expr, exit_block);
+        let (exit_block, break_values, value) =
+            self.gen_loop_block_expr(body_expr, exit_block, loop_block);
         if value.is_some() {
             self.builder.build_unconditional_branch(loop_block);
         }
@@ -1357,12 +2446,14 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Generates IR that computes a pointer to the field `name` of the place `receiver_expr`,
+    /// together with the handle of the garbage collected object that contains the field, if any.
     fn gen_place_field(
         &mut self,
         _expr: ExprId,
         receiver_expr: ExprId,
         name: &Name,
-    ) -> PointerValue<'ink> {
+    ) -> (PointerValue<'ink>, Option<PointerValue<'ink>>) {
         let hir_struct = self.infer[receiver_expr]
             .as_struct()
             .expect("expected a struct");
@@ -1374,11 +2465,25 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .expect("expected a struct field")
             .index(self.db);
 
-        let receiver_ptr = self.gen_place_expr(receiver_expr);
-        let receiver_ptr = self
-            .opt_deref_value(receiver_expr, receiver_ptr.into())
-            .into_pointer_value();
-        self.builder
+        // The fields of a `struct(gc)` are stored in the memory of the object its handle refers to,
+        // whereas the fields of a `struct(value)` are part of the place that contains it.
+        let (receiver_ptr, owner) = self.gen_place_expr_with_owner(receiver_expr);
+        let (receiver_ptr, owner) =
+            if hir_struct.data(self.db.upcast()).memory_kind == hir::StructMemoryKind::Gc {
+                let handle = self
+                    .builder
+                    .build_load(receiver_ptr, "mem_ptr")
+                    .into_pointer_value();
+                let mem_ptr = self
+                    .builder
+                    .build_load(handle, "deref")
+                    .into_pointer_value();
+                (mem_ptr, Some(handle))
+            } else {
+                (receiver_ptr, owner)
+            };
+        let field_ptr = self
+            .builder
             .build_struct_gep(
                 receiver_ptr,
                 field_idx,
@@ -1389,7 +2494,108 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     "could not get pointer to field `{}::{}` at index {}",
                     hir_struct_name, name, field_idx
                 )
-            })
+            });
+        (field_ptr, owner)
+    }
+
+    /// Generates IR for indexing into an array, e.g. `a[1]`.
+    fn gen_index(
+        &mut self,
+        _expr: ExprId,
+        base_expr: ExprId,
+        index_expr: ExprId,
+    ) -> Option<BasicValueEnum<'ink>> {
+        let (element_ptr, _) = self.gen_array_element_ptr(base_expr, index_expr)?;
+        Some(self.builder.build_load(element_ptr, "element"))
+    }
+
+    /// Generates IR that computes a pointer to the element at `index_expr` of the array
+    /// `base_expr`, together with the handle of the array. If the index is out of bounds, the
+    /// `panic_index_out_of_bounds` intrinsic is called, which never returns.
+    fn gen_array_element_ptr(
+        &mut self,
+        base_expr: ExprId,
+        index_expr: ExprId,
+    ) -> Option<(PointerValue<'ink>, PointerValue<'ink>)> {
+        let array_ptr_ptr = self.gen_expr(base_expr)?.into_pointer_value();
+        let index = self.gen_expr(index_expr)?.into_int_value();
+
+        // Load the actual memory location of the array
+        let mem_ptr = self
+            .builder
+            .build_load(array_ptr_ptr, "array_mem_ptr")
+            .into_pointer_value();
+        let length = self.gen_array_length(mem_ptr);
+
+        // Check that the index is within the bounds of the array
+        let in_bounds =
+            self.builder
+                .build_int_compare(IntPredicate::ULT, index, length, "in_bounds");
+        let out_of_bounds_block = self
+            .context
+            .append_basic_block(self.fn_value, "index_out_of_bounds");
+        let in_bounds_block = self
+            .context
+            .append_basic_block(self.fn_value, "index_in_bounds");
+        self.builder
+            .build_conditional_branch(in_bounds, in_bounds_block, out_of_bounds_block);
+
+        // Panic if the index is out of bounds
+        self.builder.position_at_end(out_of_bounds_block);
+        let panic_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::panic_index_out_of_bounds,
+        );
+        self.builder
+            .build_call(panic_fn_ptr, &[index.into(), length.into()], "");
+        self.builder.build_unreachable();
+
+        self.builder.position_at_end(in_bounds_block);
+        Some((self.gen_array_element_gep(mem_ptr, index), array_ptr_ptr))
+    }
+
+    /// Generates IR that computes a pointer to the element at `index` of the array memory pointed
+    /// to by `mem_ptr`, without any bounds checking.
+    fn gen_array_element_gep(
+        &self,
+        mem_ptr: PointerValue<'ink>,
+        index: IntValue<'ink>,
+    ) -> PointerValue<'ink> {
+        let zero = self.context.i32_type().const_zero();
+        let elements_idx = self.context.i32_type().const_int(1, false);
+        unsafe {
+            self.builder
+                .build_in_bounds_gep(mem_ptr, &[zero, elements_idx, index], "element_ptr")
+        }
+    }
+
+    /// Generates IR that loads the length of the array memory pointed to by `mem_ptr`.
+    fn gen_array_length(&self, mem_ptr: PointerValue<'ink>) -> IntValue<'ink> {
+        let length_ptr = self
+            .builder
+            .build_struct_gep(mem_ptr, 0, "length_ptr")
+            .expect("could not get pointer to the length of an array");
+        self.builder
+            .build_load(length_ptr, "length")
+            .into_int_value()
+    }
+
+    /// Generates IR for a call to an intrinsic function.
+    fn gen_intrinsic_call(
+        &mut self,
+        intrinsic: hir::Intrinsic,
+        args: &[BasicValueEnum<'ink>],
+    ) -> BasicValueEnum<'ink> {
+        match intrinsic {
+            hir::Intrinsic::Len => {
+                let mem_ptr = self
+                    .builder
+                    .build_load(args[0].into_pointer_value(), "array_mem_ptr")
+                    .into_pointer_value();
+                self.gen_array_length(mem_ptr).into()
+            }
+        }
     }
 }
 
@@ -0,0 +1,89 @@
+//! Lambdas and functions can be used as values of a function pointer type, e.g. `fn(i32) -> i32`.
+//! Such a value is an object that is allocated on the heap. Its memory starts with a pointer to
+//! the function that is called, followed by the variables captured by a lambda. The object pointer
+//! is passed as the first argument of the function, which gives a lambda access to its captures.
+//!
+//! A closure keeps pointing to the code of the assembly that created it. When an assembly is
+//! reloaded, closures that are still alive keep running the code they were created with.
+
+use crate::{
+    ir::{instance::FunctionInstance, ty::HirTypeCache},
+    type_info::TypeInfo,
+};
+use hir::{Body, Expr, ExprId, HirDatabase, InferenceResult, Pat, ValueNs};
+
+/// The name of the field of a closure that stores the pointer to its function.
+pub(crate) const FN_PTR_FIELD_NAME: &str = "fn_ptr";
+
+/// Returns the name of the lambda `expr` in the function `instance`, e.g. `foo::bar::lambda#3`.
+pub(crate) fn lambda_name(
+    db: &dyn HirDatabase,
+    instance: &FunctionInstance,
+    expr: ExprId,
+) -> String {
+    format!(
+        "{}::lambda#{}",
+        instance.full_name(db),
+        u32::from(expr.into_raw())
+    )
+}
+
+/// Returns the names and types of the variables captured by the lambda `expr`, in the order in
+/// which they are stored in its memory.
+pub(crate) fn lambda_captures(
+    body: &Body,
+    infer: &InferenceResult,
+    expr: ExprId,
+) -> Vec<(String, hir::Ty)> {
+    infer
+        .lambda_captures(expr)
+        .iter()
+        .map(|pat| {
+            let name = match &body[*pat] {
+                Pat::Bind { name } => name.to_string(),
+                _ => unreachable!("only bindings can be captured"),
+            };
+            (name, infer[*pat].clone())
+        })
+        .collect()
+}
+
+/// Returns the `TypeInfo` of the object that is allocated for the lambda `expr`. A lambda that
+/// doesn't capture any variables has the same memory layout as its function pointer type, so it
+/// shares its `TypeInfo`.
+pub(crate) fn lambda_type_info(
+    db: &dyn HirDatabase,
+    hir_types: &HirTypeCache,
+    instance: &FunctionInstance,
+    body: &Body,
+    infer: &InferenceResult,
+    expr: ExprId,
+) -> TypeInfo {
+    let captures = lambda_captures(body, infer, expr);
+    if captures.is_empty() {
+        hir_types.type_info(&infer[expr])
+    } else {
+        hir_types.lambda_type_info(&infer[expr], &lambda_name(db, instance, expr), captures)
+    }
+}
+
+/// Returns the function that the path expression `expr` refers to, if the function is used as a
+/// value of a function pointer type.
+pub(crate) fn fn_ptr_function(
+    db: &dyn HirDatabase,
+    body: &Body,
+    infer: &InferenceResult,
+    expr: ExprId,
+) -> Option<hir::Function> {
+    let path = match &body[expr] {
+        Expr::Path(path) => path,
+        _ => return None,
+    };
+    infer[expr].as_fn_ptr()?;
+
+    let resolver = hir::resolver_for_expr(db.upcast(), body.owner(), expr);
+    match resolver.resolve_path_as_value_fully(db.upcast(), path)?.0 {
+        ValueNs::FunctionId(f) => Some(f.into()),
+        _ => None,
+    }
+}
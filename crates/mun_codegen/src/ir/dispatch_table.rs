@@ -1,5 +1,7 @@
 use crate::module_group::ModuleGroup;
-use crate::{intrinsics::Intrinsic, ir::function, ir::ty::HirTypeCache, type_info::TypeInfo};
+use crate::{
+    intrinsics::Intrinsic, ir::closure, ir::function, ir::ty::HirTypeCache, type_info::TypeInfo,
+};
 use hir::{Body, Expr, ExprId, HirDatabase, InferenceResult};
 use inkwell::{
     context::Context,
@@ -248,19 +250,32 @@ impl<'db, 'ink, 't> DispatchTableBuilder<'db, 'ink, 't> {
         let expr = &body[expr_id];
 
         // If this expression is a call, store it in the dispatch table
-        if let Expr::Call { callee, .. } = expr {
-            match infer[*callee].as_callable_def() {
-                Some(hir::CallableDef::Function(def)) => {
-                    if self.module_group.should_runtime_link_fn(self.db, def) {
-                        let fn_module = def.module(self.db);
-                        if !def.is_extern(self.db) && !self.module_group.contains(fn_module) {
-                            self.referenced_modules.insert(fn_module);
-                        }
-                        self.collect_fn_def(def);
-                    }
-                }
-                Some(hir::CallableDef::Struct(_)) => (),
+        let callee = match expr {
+            Expr::Call { callee, .. } => match infer[*callee].as_callable_def() {
+                Some(hir::CallableDef::Function(def)) => Some(def),
+                Some(hir::CallableDef::Struct(_))
+                | Some(hir::CallableDef::EnumVariant(_))
+                | Some(hir::CallableDef::Intrinsic(_)) => None,
+                // A call through a function pointer doesn't refer to a specific function
+                None if infer[*callee].as_fn_ptr().is_some() => None,
                 None => panic!("expected a callable expression"),
+            },
+            // A function that is used as a function pointer is called from a generated thunk
+            Expr::Path(_) => closure::fn_ptr_function(self.db, body, infer, expr_id),
+            Expr::MethodCall { .. } => Some(
+                infer
+                    .method_resolution(expr_id)
+                    .expect("expected a resolved method"),
+            ),
+            _ => None,
+        };
+        if let Some(def) = callee {
+            if self.module_group.should_runtime_link_fn(self.db, def) {
+                let fn_module = def.module(self.db);
+                if !def.is_extern(self.db) && !self.module_group.contains(fn_module) {
+                    self.referenced_modules.insert(fn_module);
+                }
+                self.collect_fn_def(def);
             }
         }
 
@@ -4,7 +4,8 @@ use crate::{
     code_gen::CodeGenContext,
     ir::body::BodyIrGenerator,
     ir::file_group::FileGroupIr,
-    ir::{function, type_table::TypeTable},
+    ir::global::GlobalItem,
+    ir::{function, instance::FunctionInstance, type_table::TypeTable},
     value::Global,
 };
 use hir::{HasVisibility, ModuleDef};
@@ -37,9 +38,14 @@ pub(crate) fn gen_file_ir<'db, 'ink>(
     for def in module_group
         .iter()
         .flat_map(|module| module.declarations(code_gen.db))
+        .chain(
+            module_group
+                .associated_functions(code_gen.db)
+                .map(ModuleDef::Function),
+        )
     {
         if let ModuleDef::Function(f) = def {
-            if !f.is_extern(code_gen.db) {
+            if !f.is_extern(code_gen.db) && !f.is_generic(code_gen.db) {
                 let fun = function::gen_prototype(code_gen.db, hir_types, f, &llvm_module);
                 functions.insert(f, fun);
 
@@ -59,6 +65,17 @@ pub(crate) fn gen_file_ir<'db, 'ink>(
         }
     }
 
+    // Generate the signatures of all instances of generic functions
+    let instances: HashMap<FunctionInstance, _> = group_ir
+        .instances
+        .iter()
+        .map(|instance| {
+            let fun =
+                function::gen_instance_prototype(code_gen.db, hir_types, instance, &llvm_module);
+            (instance.clone(), fun)
+        })
+        .collect();
+
     let external_globals = {
         let alloc_handle = group_ir
             .allocator_handle_type
@@ -72,10 +89,26 @@ pub(crate) fn gen_file_ir<'db, 'ink>(
         } else {
             Some(llvm_module.add_global(group_ir.type_table.ty(), None, TypeTable::NAME))
         };
+        let statics = group_ir
+            .globals
+            .iter()
+            .filter_map(|item| match item {
+                GlobalItem::Static(s) => {
+                    let ir_type = hir_types
+                        .get_basic_type(&s.ty(code_gen.db))
+                        .expect("expected a primitive type for a static");
+                    let global =
+                        llvm_module.add_global(ir_type, None, &item.symbol_name(code_gen.db));
+                    Some((*s, global))
+                }
+                GlobalItem::Const(_) => None,
+            })
+            .collect();
         ExternalGlobals {
             alloc_handle,
             dispatch_table,
             type_table: type_table.map(|g| unsafe { Global::from_raw(g) }),
+            statics,
         }
     };
 
@@ -86,9 +119,11 @@ pub(crate) fn gen_file_ir<'db, 'ink>(
     for (hir_function, llvm_function) in functions.iter() {
         let mut code_gen = BodyIrGenerator::new(
             code_gen.context,
+            &llvm_module,
             code_gen.db,
-            (*hir_function, *llvm_function),
+            (FunctionInstance::non_generic(*hir_function), *llvm_function),
             &functions,
+            &instances,
             &group_ir.dispatch_table,
             &group_ir.type_table,
             external_globals.clone(),
@@ -100,12 +135,35 @@ pub(crate) fn gen_file_ir<'db, 'ink>(
         fn_pass_manager.run_on(llvm_function);
     }
 
+    // Generate the bodies of the instances in a deterministic order
+    for instance in group_ir.instances.iter() {
+        let llvm_function = instances[instance];
+        let mut code_gen = BodyIrGenerator::new(
+            code_gen.context,
+            &llvm_module,
+            code_gen.db,
+            (instance.clone(), llvm_function),
+            &functions,
+            &instances,
+            &group_ir.dispatch_table,
+            &group_ir.type_table,
+            external_globals.clone(),
+            &code_gen.hir_types,
+            &module_group,
+        );
+
+        code_gen.gen_fn_body();
+        fn_pass_manager.run_on(&llvm_function);
+    }
+
     for (hir_function, llvm_function) in wrapper_functions.iter() {
         let mut code_gen = BodyIrGenerator::new(
             code_gen.context,
+            &llvm_module,
             code_gen.db,
-            (*hir_function, *llvm_function),
+            (FunctionInstance::non_generic(*hir_function), *llvm_function),
             &functions,
+            &instances,
             &group_ir.dispatch_table,
             &group_ir.type_table,
             external_globals.clone(),
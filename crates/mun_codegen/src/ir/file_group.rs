@@ -1,5 +1,7 @@
 use super::{
     dispatch_table::{DispatchTable, DispatchTableBuilder},
+    global::{self, GlobalItem},
+    instance::{self, FunctionInstance},
     intrinsics,
     type_table::{TypeTable, TypeTableBuilder},
 };
@@ -27,6 +29,10 @@ pub struct FileGroupIr<'ink> {
     pub(crate) allocator_handle_type: Option<PointerType<'ink>>,
     /// The modules that contain code that was referenced from this group of modules
     pub(crate) referenced_modules: FxHashSet<hir::Module>,
+    /// The instances of generic functions that are used by this group of modules
+    pub(crate) instances: Vec<FunctionInstance>,
+    /// The `const` and `static` items that are stored in the assembly
+    pub(crate) globals: Vec<GlobalItem>,
 }
 
 /// Generates IR that is shared among the group's files.
@@ -36,6 +42,9 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
 ) -> FileGroupIr<'ink> {
     let llvm_module = code_gen.context.create_module("group_name");
 
+    // Collect all instances of generic functions that are used by the group
+    let instances = instance::collect_instances(code_gen.db, module_group);
+
     // Use a `BTreeMap` to guarantee deterministically ordered output.
     let mut intrinsics_map = BTreeMap::new();
     let mut needs_alloc = false;
@@ -44,9 +53,14 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
     for def in module_group
         .iter()
         .flat_map(|module| module.declarations(code_gen.db))
+        .chain(
+            module_group
+                .associated_functions(code_gen.db)
+                .map(ModuleDef::Function),
+        )
     {
         match def {
-            ModuleDef::Function(f) if !f.is_extern(code_gen.db) => {
+            ModuleDef::Function(f) if !f.is_extern(code_gen.db) && !f.is_generic(code_gen.db) => {
                 intrinsics::collect_fn_body(
                     &code_gen.context,
                     code_gen.target_machine.get_target_data(),
@@ -72,10 +86,25 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
             ModuleDef::Module(_) => (),
             ModuleDef::Function(_) => (), // TODO: Extern types?
             ModuleDef::Struct(_) => (),
+            ModuleDef::Enum(_) => (),
             ModuleDef::PrimitiveType(_) => (),
             ModuleDef::TypeAlias(_) => (),
+            ModuleDef::Const(_) => (),
+            ModuleDef::Static(_) => (),
+            ModuleDef::Trait(_) => (),
         }
     }
+    for instance in instances.iter() {
+        intrinsics::collect_fn_body(
+            &code_gen.context,
+            code_gen.target_machine.get_target_data(),
+            code_gen.db,
+            &mut intrinsics_map,
+            &mut needs_alloc,
+            &instance.function.body(code_gen.db),
+            &instance.infer(code_gen.db),
+        );
+    }
 
     // Collect all exposed functions' bodies.
     let mut dispatch_table_builder = DispatchTableBuilder::new(
@@ -90,16 +119,26 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
     for def in module_group
         .iter()
         .flat_map(|module| module.declarations(code_gen.db))
+        .chain(
+            module_group
+                .associated_functions(code_gen.db)
+                .map(ModuleDef::Function),
+        )
     {
         if let ModuleDef::Function(f) = def {
             // Find all functions that must be present in the dispatch table
-            if !f.is_extern(code_gen.db) {
+            if !f.is_extern(code_gen.db) && !f.is_generic(code_gen.db) {
                 let body = f.body(code_gen.db);
                 let infer = f.infer(code_gen.db);
                 dispatch_table_builder.collect_body(&body, &infer);
             }
         }
     }
+    for instance in instances.iter() {
+        let body = instance.function.body(code_gen.db);
+        let infer = instance.infer(code_gen.db);
+        dispatch_table_builder.collect_body(&body, &infer);
+    }
 
     let (dispatch_table, referenced_modules) = dispatch_table_builder.build();
 
@@ -128,17 +167,42 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
     for def in module_group
         .iter()
         .flat_map(|module| module.declarations(code_gen.db))
+        .chain(
+            module_group
+                .associated_functions(code_gen.db)
+                .map(ModuleDef::Function),
+        )
     {
         match def {
-            ModuleDef::Struct(s) => {
-                type_table_builder.collect_struct(s);
+            // Generic structs are collected for each set of type arguments they are used with
+            ModuleDef::Struct(s) if !s.is_generic(code_gen.db) => {
+                type_table_builder.collect_struct(s, &hir::Substitution::empty());
             }
-            ModuleDef::Function(f) => {
+            ModuleDef::Enum(e) => {
+                type_table_builder.collect_enum(e);
+            }
+            ModuleDef::Function(f) if !f.is_generic(code_gen.db) => {
                 type_table_builder.collect_fn(f);
             }
-            ModuleDef::PrimitiveType(_) | ModuleDef::TypeAlias(_) | ModuleDef::Module(_) => (),
+            ModuleDef::Struct(_)
+            | ModuleDef::Function(_)
+            | ModuleDef::PrimitiveType(_)
+            | ModuleDef::TypeAlias(_)
+            | ModuleDef::Const(_)
+            | ModuleDef::Static(_)
+            | ModuleDef::Trait(_)
+            | ModuleDef::Module(_) => (),
         }
     }
+    for instance in instances.iter() {
+        type_table_builder.collect_instance(instance);
+    }
+
+    // Collect the types of all globals, they are exposed through the `ModuleInfo`
+    let globals = global::collect_globals(code_gen.db, module_group);
+    for global in globals.iter() {
+        type_table_builder.collect_global(&global.ty(code_gen.db));
+    }
 
     let type_table = type_table_builder.build();
 
@@ -153,11 +217,33 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
         None
     };
 
+    // Create the global values that store the `const` and `static` items
+    for item in globals.iter() {
+        let ty = item.ty(code_gen.db);
+        let ir_type = code_gen
+            .hir_types
+            .get_basic_type(&ty)
+            .expect("expected a primitive type for a global");
+        let global = llvm_module.add_global(ir_type, None, &item.symbol_name(code_gen.db));
+        global.set_initializer(&global::gen_const_value(
+            &code_gen.hir_types,
+            &ty,
+            item.value(code_gen.db),
+        ));
+        global.set_constant(!item.is_mutable());
+        if item.is_mutable() {
+            let (_, alignment) = global::static_access_type(&code_gen.hir_types, &ty);
+            global.set_alignment(alignment);
+        }
+    }
+
     FileGroupIr {
         llvm_module,
         dispatch_table,
         type_table,
         allocator_handle_type,
         referenced_modules,
+        instances,
+        globals,
     }
 }
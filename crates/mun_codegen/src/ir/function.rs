@@ -1,5 +1,9 @@
-use crate::{ir::ty::HirTypeCache, Module, OptimizationLevel};
+use crate::{
+    ir::{instance::FunctionInstance, ty::HirTypeCache},
+    Module, OptimizationLevel,
+};
 use inkwell::{
+    module::Linkage,
     passes::{PassManager, PassManagerBuilder},
     values::FunctionValue,
 };
@@ -36,6 +40,19 @@ pub(crate) fn gen_prototype<'db, 'ink>(
     module.add_function(&name, ir_ty, None)
 }
 
+/// Generates a `FunctionValue` for an instance of a generic `hir::Function`. Instances are only
+/// called from within the module, so they are never exposed through the public API.
+pub(crate) fn gen_instance_prototype<'db, 'ink>(
+    db: &'db dyn HirDatabase,
+    types: &HirTypeCache<'db, 'ink>,
+    instance: &FunctionInstance,
+    module: &Module<'ink>,
+) -> FunctionValue<'ink> {
+    let name = instance.name(db);
+    let ir_ty = types.get_function_instance_type(instance.function, &instance.substs);
+    module.add_function(&name, ir_ty, Some(Linkage::Private))
+}
+
 /// Generates a `FunctionValue` for a `hir::Function` that is usable from the public API. This
 /// function does not generate a body for the `hir::Function`. That task is left to the `gen_body`
 /// function. The reason this is split between two functions is that first all signatures are
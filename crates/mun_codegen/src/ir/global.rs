@@ -0,0 +1,145 @@
+//! Module-level `const` and `static` items. The value of a `const` is evaluated at compile time
+//! and inlined wherever the constant is used. A `static` is stored in a global variable of the
+//! assembly, which is initialized with the compile-time value of its initializer.
+//!
+//! All `static`s and all externally visible `const`s are exported through the `ModuleInfo` of the
+//! assembly, so the host can read them and write the `static`s.
+//!
+//! Functions that access the same `static` can be invoked from multiple threads at the same time,
+//! so all loads and stores of a `static` are atomic.
+
+use crate::{ir::ty::HirTypeCache, module_group::ModuleGroup};
+use hir::{ConstValue, HasVisibility, HirDatabase, IntTy, ModuleDef, Ty, TyKind};
+use inkwell::{
+    types::BasicTypeEnum,
+    values::{BasicValueEnum, InstructionValue},
+    AtomicOrdering,
+};
+
+/// A module-level item that is stored in the assembly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum GlobalItem {
+    Const(hir::Const),
+    Static(hir::Static),
+}
+
+impl GlobalItem {
+    /// Returns the fully qualified name of the item, e.g. `foo::BAR`.
+    pub fn full_name(self, db: &dyn HirDatabase) -> String {
+        match self {
+            GlobalItem::Const(c) => c.full_name(db),
+            GlobalItem::Static(s) => s.full_name(db),
+        }
+    }
+
+    /// Returns the name of the LLVM global that stores the value of the item.
+    pub fn symbol_name(self, db: &dyn HirDatabase) -> String {
+        match self {
+            GlobalItem::Const(c) => format!("const::{}", c.full_name(db)),
+            GlobalItem::Static(s) => static_symbol_name(db, s),
+        }
+    }
+
+    /// Returns the type of the item.
+    pub fn ty(self, db: &dyn HirDatabase) -> Ty {
+        match self {
+            GlobalItem::Const(c) => c.ty(db),
+            GlobalItem::Static(s) => s.ty(db),
+        }
+    }
+
+    /// Returns the compile-time value of the item.
+    pub fn value(self, db: &dyn HirDatabase) -> ConstValue {
+        match self {
+            GlobalItem::Const(c) => c.value(db),
+            GlobalItem::Static(s) => s.initial_value(db),
+        }
+        .expect("the value of a global must be known at compile time")
+    }
+
+    /// Returns true if the host is allowed to write to the item.
+    pub fn is_mutable(self) -> bool {
+        matches!(self, GlobalItem::Static(_))
+    }
+}
+
+/// Returns the name of the LLVM global that stores the value of the `static`.
+pub(crate) fn static_symbol_name(db: &dyn HirDatabase, s: hir::Static) -> String {
+    format!("static::{}", s.full_name(db))
+}
+
+/// Returns the type through which a `static` of type `ty` is loaded and stored, and the alignment
+/// of its global. Atomic accesses require a type of at least a byte that is aligned to its size,
+/// so a `bool` is accessed as an `i8`.
+pub(crate) fn static_access_type<'ink>(
+    hir_types: &HirTypeCache<'_, 'ink>,
+    ty: &Ty,
+) -> (BasicTypeEnum<'ink>, u32) {
+    let ir_ty: BasicTypeEnum = match ty.interned() {
+        TyKind::Bool => hir_types.get_int_type(IntTy::u8()).into(),
+        _ => hir_types
+            .get_basic_type(ty)
+            .expect("expected a primitive type for a static"),
+    };
+    (ir_ty, hir_types.get_store_size(&ir_ty))
+}
+
+/// Marks the load or store `instruction` of a `static` with the specified `alignment` as atomic.
+pub(crate) fn set_atomic(instruction: InstructionValue, alignment: u32) {
+    instruction
+        .set_alignment(alignment)
+        .expect("expected a load or store");
+    instruction
+        .set_atomic_ordering(AtomicOrdering::SequentiallyConsistent)
+        .expect("expected a load or store");
+}
+
+/// Collects all items of the module group that are stored in the assembly, in a deterministic
+/// order.
+pub(crate) fn collect_globals(db: &dyn HirDatabase, module_group: &ModuleGroup) -> Vec<GlobalItem> {
+    module_group
+        .iter()
+        .flat_map(|module| module.declarations(db))
+        .filter_map(|def| match def {
+            ModuleDef::Static(s) => Some(GlobalItem::Static(s)),
+            ModuleDef::Const(c) if c.visibility(db).is_externally_visible() => {
+                Some(GlobalItem::Const(c))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Constructs the IR constant of the compile-time `value` of type `ty`.
+pub(crate) fn gen_const_value<'ink>(
+    hir_types: &HirTypeCache<'_, 'ink>,
+    ty: &Ty,
+    value: ConstValue,
+) -> BasicValueEnum<'ink> {
+    match (ty.interned(), value) {
+        (TyKind::Int(int_ty), ConstValue::Int(v)) => {
+            let ir_ty = hir_types.get_int_type(*int_ty);
+            if ir_ty.get_bit_width() > 64 {
+                ir_ty
+                    .const_int_arbitrary_precision(&unsafe {
+                        std::mem::transmute::<u128, [u64; 2]>(v as u128)
+                    })
+                    .into()
+            } else {
+                ir_ty.const_int(v as u64, false).into()
+            }
+        }
+        (TyKind::Float(float_ty), ConstValue::Float(v)) => {
+            hir_types.get_float_type(*float_ty).const_float(v).into()
+        }
+        (TyKind::Bool, ConstValue::Bool(v)) => {
+            let ir_ty = hir_types.get_bool_type();
+            if v {
+                ir_ty.const_all_ones().into()
+            } else {
+                ir_ty.const_zero().into()
+            }
+        }
+        _ => unreachable!("the type of a global does not match its value"),
+    }
+}
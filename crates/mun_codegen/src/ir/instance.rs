@@ -0,0 +1,154 @@
+//! Generic functions are monomorphized: for every set of type arguments a generic function is
+//! called with, a separate copy of the function is generated. Such a copy is called an instance.
+
+use crate::module_group::ModuleGroup;
+use hir::{Body, Expr, ExprId, HirDatabase, HirDisplay, InferenceResult, ModuleDef, Substitution};
+use rustc_hash::FxHashSet;
+use std::sync::Arc;
+
+/// The maximum depth of nested instantiations, e.g. a generic function that calls itself with
+/// ever growing type arguments would otherwise never terminate.
+const RECURSION_LIMIT: usize = 64;
+
+/// A function together with the type arguments it is instantiated with. For non-generic functions
+/// the substitution is empty.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionInstance {
+    pub function: hir::Function,
+    pub substs: Substitution,
+}
+
+impl FunctionInstance {
+    /// Constructs a new instance of `function` with the type arguments in `substs`.
+    pub fn new(function: hir::Function, substs: Substitution) -> Self {
+        Self { function, substs }
+    }
+
+    /// Constructs the instance of a non-generic function.
+    pub fn non_generic(function: hir::Function) -> Self {
+        Self::new(function, Substitution::empty())
+    }
+
+    /// Returns the name of the instance, e.g. `max<i32>`.
+    pub fn name(&self, db: &dyn HirDatabase) -> String {
+        self.with_type_args(db, self.function.name(db).to_string())
+    }
+
+    /// Returns the fully qualified name of the instance, e.g. `foo::max<i32>`.
+    pub fn full_name(&self, db: &dyn HirDatabase) -> String {
+        self.with_type_args(db, self.function.full_name(db))
+    }
+
+    /// Appends the type arguments of the instance to `name`, if there are any.
+    fn with_type_args(&self, db: &dyn HirDatabase, name: String) -> String {
+        if self.substs.is_empty() {
+            name
+        } else {
+            let type_args: Vec<String> = self
+                .substs
+                .iter()
+                .map(|ty| ty.display(db).to_string())
+                .collect();
+            format!("{}<{}>", name, type_args.join(", "))
+        }
+    }
+
+    /// Returns the inference result of the function's body with the type arguments substituted.
+    pub fn infer(&self, db: &dyn HirDatabase) -> Arc<InferenceResult> {
+        let infer = self.function.infer(db);
+        if self.substs.is_empty() {
+            infer
+        } else {
+            Arc::new(infer.subst(db, &self.substs))
+        }
+    }
+}
+
+/// Returns the instance of a generic function that is called by the expression `expr_id`, if any.
+pub(crate) fn called_instance(
+    body: &Body,
+    infer: &InferenceResult,
+    expr_id: ExprId,
+) -> Option<FunctionInstance> {
+    match &body[expr_id] {
+        Expr::Call { callee, .. } => match infer[*callee].interned() {
+            hir::TyKind::FnDef(hir::CallableDef::Function(def), substs) if !substs.is_empty() => {
+                Some(FunctionInstance::new(*def, substs.clone()))
+            }
+            _ => None,
+        },
+        Expr::MethodCall { .. } => {
+            let def = infer
+                .method_resolution(expr_id)
+                .expect("expected a resolved method");
+            infer
+                .method_substitution(expr_id)
+                .filter(|substs| !substs.is_empty())
+                .map(|substs| FunctionInstance::new(def, substs.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Collects all instances of generic functions that are used, directly or indirectly, by the
+/// functions of the module group. The instances are returned in a deterministic order.
+pub(crate) fn collect_instances(
+    db: &dyn HirDatabase,
+    module_group: &ModuleGroup,
+) -> Vec<FunctionInstance> {
+    let mut instances = Vec::new();
+    let mut visited = FxHashSet::default();
+
+    // Start from all non-generic functions of the module group
+    let mut worklist: Vec<(FunctionInstance, usize)> = module_group
+        .iter()
+        .flat_map(|module| module.declarations(db))
+        .filter_map(|def| match def {
+            ModuleDef::Function(f) => Some(f),
+            _ => None,
+        })
+        .chain(module_group.associated_functions(db))
+        .filter(|f| !f.is_extern(db) && !f.is_generic(db))
+        .map(|f| (FunctionInstance::non_generic(f), 0))
+        .collect();
+    worklist.reverse();
+
+    while let Some((instance, depth)) = worklist.pop() {
+        let body = instance.function.body(db);
+        let infer = instance.infer(db);
+
+        let mut called = Vec::new();
+        collect_called_instances(&body, &infer, body.body_expr(), &mut called);
+
+        for callee in called {
+            if visited.insert(callee.clone()) {
+                if depth >= RECURSION_LIMIT {
+                    panic!(
+                        "reached the recursion limit while instantiating `{}`",
+                        callee.name(db)
+                    );
+                }
+                instances.push(callee.clone());
+                worklist.push((callee, depth + 1));
+            }
+        }
+    }
+
+    instances
+}
+
+/// Collects the instances of generic functions called from the expression `expr_id` and its
+/// sub-expressions.
+fn collect_called_instances(
+    body: &Body,
+    infer: &InferenceResult,
+    expr_id: ExprId,
+    instances: &mut Vec<FunctionInstance>,
+) {
+    if let Some(instance) = called_instance(body, infer, expr_id) {
+        instances.push(instance);
+    }
+
+    body[expr_id]
+        .walk_child_exprs(|expr_id| collect_called_instances(body, infer, expr_id, instances));
+}
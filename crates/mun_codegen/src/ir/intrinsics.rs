@@ -2,7 +2,9 @@ use crate::{
     intrinsics::{self, Intrinsic},
     ir::dispatch_table::FunctionPrototype,
 };
-use hir::{Body, Expr, ExprId, HirDatabase, InferenceResult, ValueNs};
+use hir::{
+    BinaryOp, Body, Expr, ExprId, HirDatabase, InferenceResult, Literal, Ty, TyKind, ValueNs,
+};
 use inkwell::{context::Context, targets::TargetData, types::FunctionType};
 use std::{collections::BTreeMap, sync::Arc};
 
@@ -44,28 +46,79 @@ fn collect_expr<'db, 'ink>(
                 // self.collect_intrinsic(module, entries, &intrinsics::drop);
                 *needs_alloc = true;
             }
-            Some(hir::CallableDef::Function(_)) => (),
+            Some(hir::CallableDef::Function(_))
+            | Some(hir::CallableDef::EnumVariant(_))
+            | Some(hir::CallableDef::Intrinsic(_)) => (),
+            // A call through a function pointer doesn't allocate
+            None if infer[*callee].as_fn_ptr().is_some() => (),
             None => panic!("expected a callable expression"),
         }
     }
 
+    // A lambda allocates an object that stores its function and captured variables
+    if let Expr::Lambda { .. } = expr {
+        collect_intrinsic(context, &target, &intrinsics::new, intrinsics);
+        *needs_alloc = true;
+    }
+
     if let Expr::RecordLit { .. } = expr {
         collect_intrinsic(context, &target, &intrinsics::new, intrinsics);
         // self.collect_intrinsic(module, entries, &intrinsics::drop);
         *needs_alloc = true;
     }
 
-    if let Expr::Path(path) = expr {
-        let resolver = hir::resolver_for_expr(db.upcast(), body.owner(), expr_id);
-        if let Some((ValueNs::StructId(_), _)) =
-            resolver.resolve_path_as_value_fully(db.upcast(), path)
+    if let Expr::Literal(Literal::String(_)) = expr {
+        collect_intrinsic(context, &target, &intrinsics::new_string, intrinsics);
+        *needs_alloc = true;
+    }
+
+    if let Expr::Array(_) = expr {
+        collect_intrinsic(context, &target, &intrinsics::new_array, intrinsics);
+        *needs_alloc = true;
+    }
+
+    // Storing a value that can reference other objects in a field or an array element requires a
+    // write barrier
+    if let Expr::BinaryOp {
+        lhs,
+        op: Some(BinaryOp::Assignment { .. }),
+        ..
+    } = expr
+    {
+        if matches!(body[*lhs], Expr::Field { .. } | Expr::Index { .. })
+            && needs_write_barrier(&infer[*lhs])
         {
-            collect_intrinsic(context, &target, &intrinsics::new, intrinsics);
-            // self.collect_intrinsic( module, entries, &intrinsics::drop);
+            collect_intrinsic(context, &target, &intrinsics::write_barrier, intrinsics);
             *needs_alloc = true;
         }
     }
 
+    if let Expr::Index { .. } = expr {
+        collect_intrinsic(
+            context,
+            &target,
+            &intrinsics::panic_index_out_of_bounds,
+            intrinsics,
+        );
+    }
+
+    if let Expr::Path(path) = expr {
+        let resolver = hir::resolver_for_expr(db.upcast(), body.owner(), expr_id);
+        match resolver.resolve_path_as_value_fully(db.upcast(), path) {
+            Some((ValueNs::StructId(_), _)) => {
+                collect_intrinsic(context, &target, &intrinsics::new, intrinsics);
+                // self.collect_intrinsic( module, entries, &intrinsics::drop);
+                *needs_alloc = true;
+            }
+            // A function that is used as a function pointer is allocated as an object
+            Some((ValueNs::FunctionId(_), _)) if infer[expr_id].as_fn_ptr().is_some() => {
+                collect_intrinsic(context, &target, &intrinsics::new, intrinsics);
+                *needs_alloc = true;
+            }
+            _ => (),
+        }
+    }
+
     // Recurse further
     expr.walk_child_exprs(|expr_id| {
         collect_expr(
@@ -81,6 +134,15 @@ fn collect_expr<'db, 'ink>(
     })
 }
 
+/// Returns whether storing a value of type `ty` in a garbage collected object requires a write
+/// barrier, because the value can contain references to other objects.
+pub(crate) fn needs_write_barrier(ty: &Ty) -> bool {
+    !matches!(
+        ty.interned(),
+        TyKind::Bool | TyKind::Float(_) | TyKind::Int(_)
+    )
+}
+
 /// Collects all intrinsics from the specified `body`.
 pub fn collect_fn_body<'db, 'ink>(
     context: &'ink Context,
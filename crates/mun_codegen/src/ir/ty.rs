@@ -1,13 +1,16 @@
 use crate::{
     ir::IsIrType,
-    type_info::{TypeInfo, TypeSize},
+    type_info::{HasStaticTypeInfo, TypeInfo, TypeSize},
+};
+use hir::{
+    FloatBitness, FnSig, HirDatabase, HirDisplay, IntBitness, ResolveBitness, Substitution, Ty,
+    TyKind,
 };
-use hir::{FloatBitness, HirDatabase, HirDisplay, IntBitness, ResolveBitness, Ty, TyKind};
 use inkwell::{
     context::Context,
     targets::TargetData,
     types::FunctionType,
-    types::{AnyTypeEnum, BasicType, BasicTypeEnum, FloatType, IntType, StructType},
+    types::{AnyTypeEnum, BasicType, BasicTypeEnum, FloatType, IntType, PointerType, StructType},
     AddressSpace,
 };
 use smallvec::SmallVec;
@@ -19,6 +22,7 @@ pub struct HirTypeCache<'db, 'ink> {
     db: &'db dyn HirDatabase,
     target_data: TargetData,
     types: RefCell<HashMap<hir::Ty, StructType<'ink>>>,
+    variant_types: RefCell<HashMap<hir::EnumVariant, StructType<'ink>>>,
 }
 
 impl<'db, 'ink> HirTypeCache<'db, 'ink> {
@@ -29,6 +33,7 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             db,
             target_data,
             types: RefCell::new(HashMap::default()),
+            variant_types: RefCell::new(HashMap::default()),
         }
     }
 
@@ -57,13 +62,54 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
         self.context.bool_type()
     }
 
-    /// Returns the type of the specified integer type
-    pub fn get_struct_type(&self, struct_ty: hir::Struct) -> StructType<'ink> {
+    /// Returns the type for strings. Strings are allocated on the heap, which means they are
+    /// referenced through an object pointer just like GC structs.
+    pub fn get_string_type(&self) -> PointerType<'ink> {
+        // string**
+        self.context
+            .i8_type()
+            .ptr_type(AddressSpace::Generic)
+            .ptr_type(AddressSpace::Generic)
+    }
+
+    /// Returns the type of the memory of an array with elements of type `element_ty`. The memory of
+    /// an array consists of a header that stores the length of the array, followed by its elements.
+    pub fn get_array_type(&self, element_ty: &hir::Ty) -> StructType<'ink> {
+        let element_ir_ty = self
+            .get_basic_type(element_ty)
+            .expect("could not convert array element to basic type");
+
+        // { usize, [0 x T] }
+        self.context.struct_type(
+            &[
+                usize::ir_type(self.context, &self.target_data).into(),
+                element_ir_ty.array_type(0).into(),
+            ],
+            false,
+        )
+    }
+
+    /// Returns the type of an array that should be used for variables. Arrays are allocated on the
+    /// heap, which means they are referenced through an object pointer just like GC structs.
+    pub fn get_array_reference_type(&self, element_ty: &hir::Ty) -> PointerType<'ink> {
+        // { usize, [0 x T] }**
+        self.get_array_type(element_ty)
+            .ptr_type(AddressSpace::Generic)
+            .ptr_type(AddressSpace::Generic)
+    }
+
+    /// Returns the type of the specified struct, instantiated with the type arguments in
+    /// `substs`. Every instantiation of a generic struct results in a distinct type.
+    pub fn get_struct_type(
+        &self,
+        struct_ty: hir::Struct,
+        substs: &Substitution,
+    ) -> StructType<'ink> {
         // TODO: This assumes the contents of the hir::Struct does not change. It definitely does
         //  between compilations. We have to have a way to uniquely identify the `hir::Struct` and
         //  its contents.
 
-        let ty = Ty::struct_ty(struct_ty);
+        let ty = Ty::struct_ty(struct_ty, substs.clone());
 
         // Get the type from the cache
         if let Some(ir_ty) = self.types.borrow().get(&ty) {
@@ -73,14 +119,14 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
         // Opaquely construct the struct type and store it in the cache
         let ir_ty = self
             .context
-            .opaque_struct_type(&struct_ty.name(self.db).to_string());
+            .opaque_struct_type(&ty.display(self.db).to_string());
         self.types.borrow_mut().insert(ty, ir_ty);
 
         // Fill the struct members
         let field_types: Vec<_> = struct_ty
             .fields(self.db)
             .into_iter()
-            .map(|field| field.ty(self.db))
+            .map(|field| field.ty(self.db).subst(substs))
             .map(|ty| {
                 self.get_basic_type(&ty)
                     .expect("could not convert struct field to basic type")
@@ -92,8 +138,12 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
     }
 
     /// Returns the type of the struct that should be used for variables.
-    pub fn get_struct_reference_type(&self, struct_ty: hir::Struct) -> BasicTypeEnum<'ink> {
-        let ir_ty = self.get_struct_type(struct_ty);
+    pub fn get_struct_reference_type(
+        &self,
+        struct_ty: hir::Struct,
+        substs: &Substitution,
+    ) -> BasicTypeEnum<'ink> {
+        let ir_ty = self.get_struct_type(struct_ty, substs);
         match struct_ty.data(self.db.upcast()).memory_kind {
             hir::StructMemoryKind::Gc => {
                 // GC values are pointers to pointers
@@ -113,8 +163,12 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
 
     /// Returns the type of the struct that should be used in the public API. In the public API we
     /// don't deal with value types, only with pointers.
-    pub fn get_public_struct_reference_type(&self, struct_ty: hir::Struct) -> BasicTypeEnum<'ink> {
-        let ir_ty = self.get_struct_type(struct_ty);
+    pub fn get_public_struct_reference_type(
+        &self,
+        struct_ty: hir::Struct,
+        substs: &Substitution,
+    ) -> BasicTypeEnum<'ink> {
+        let ir_ty = self.get_struct_type(struct_ty, substs);
 
         // GC values are pointers to pointers
         // struct Foo {}
@@ -127,9 +181,154 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             .into()
     }
 
+    /// Returns the type of the specified enum. An enum is stored as the tag of the active variant
+    /// followed by the fields of that variant, so its memory is large enough and sufficiently
+    /// aligned to store any of its variants. Use `get_enum_variant_type` to access the memory of
+    /// a specific variant.
+    pub fn get_enum_type(&self, enum_ty: hir::Enum) -> StructType<'ink> {
+        let ty = Ty::enum_ty(enum_ty);
+
+        // Get the type from the cache
+        if let Some(ir_ty) = self.types.borrow().get(&ty) {
+            return *ir_ty;
+        };
+
+        // Opaquely construct the enum type and store it in the cache
+        let ir_ty = self
+            .context
+            .opaque_struct_type(&enum_ty.name(self.db).to_string());
+        self.types.borrow_mut().insert(ty, ir_ty);
+
+        // Determine the size and alignment of the largest variant
+        let tag_ty = self.get_enum_tag_type();
+        let (size, alignment) = enum_ty.variants(self.db).into_iter().fold(
+            (
+                self.target_data.get_abi_size(&tag_ty),
+                self.target_data.get_abi_alignment(&tag_ty),
+            ),
+            |(size, alignment), variant| {
+                let variant_ir_ty = self.get_enum_variant_type(variant);
+                (
+                    size.max(self.target_data.get_abi_size(&variant_ir_ty)),
+                    alignment.max(self.target_data.get_abi_alignment(&variant_ir_ty)),
+                )
+            },
+        );
+
+        // { [n x iN] }
+        let chunk_ty = self.context.custom_width_int_type(alignment * 8);
+        let num_chunks = (size + u64::from(alignment) - 1) / u64::from(alignment);
+        ir_ty.set_body(&[chunk_ty.array_type(num_chunks as u32).into()], false);
+
+        ir_ty
+    }
+
+    /// Returns the type of the tag of an enum, which identifies the active variant.
+    pub fn get_enum_tag_type(&self) -> IntType<'ink> {
+        self.context.i16_type()
+    }
+
+    /// Returns the type of the memory of an enum when the specified `variant` is active. The tag
+    /// is stored as the first field, followed by the fields of the variant.
+    pub fn get_enum_variant_type(&self, variant: hir::EnumVariant) -> StructType<'ink> {
+        // Get the type from the cache
+        if let Some(ir_ty) = self.variant_types.borrow().get(&variant) {
+            return *ir_ty;
+        };
+
+        let ir_ty = self.context.opaque_struct_type(&format!(
+            "{}::{}",
+            variant.parent_enum().name(self.db),
+            variant.name(self.db)
+        ));
+        self.variant_types.borrow_mut().insert(variant, ir_ty);
+
+        // { i16, fields... }
+        let field_types: Vec<_> = std::iter::once(self.get_enum_tag_type().into())
+            .chain(variant.fields(self.db).into_iter().map(|field| {
+                self.get_basic_type(&field.ty(self.db))
+                    .expect("could not convert enum variant field to basic type")
+            }))
+            .collect();
+        ir_ty.set_body(&field_types, false);
+
+        ir_ty
+    }
+
+    /// Returns the type of the enum that should be used in the public API. In the public API we
+    /// don't deal with value types, only with pointers.
+    pub fn get_public_enum_reference_type(&self, enum_ty: hir::Enum) -> BasicTypeEnum<'ink> {
+        // Enums are converted to GC types in the public API.
+        // enum Foo {}
+        // Foo**
+        self.get_enum_type(enum_ty)
+            .ptr_type(AddressSpace::Generic)
+            .ptr_type(AddressSpace::Generic)
+            .into()
+    }
+
+    /// Returns the type of the memory of a closure that captures variables of types `captures`. The
+    /// memory starts with a pointer to the function of the closure, followed by the captured
+    /// variables. Function pointers that don't capture anything only store the function pointer.
+    pub fn get_closure_type(&self, captures: &[Ty]) -> StructType<'ink> {
+        // { i8*, captures... }
+        let field_types: Vec<_> = std::iter::once(
+            self.context
+                .i8_type()
+                .ptr_type(AddressSpace::Generic)
+                .into(),
+        )
+        .chain(captures.iter().map(|ty| {
+            self.get_basic_type(ty)
+                .expect("could not convert captured variable to basic type")
+        }))
+        .collect();
+        self.context.struct_type(&field_types, false)
+    }
+
+    /// Returns the type of a function pointer that should be used for variables. Function pointers
+    /// are allocated on the heap, which means they are referenced through an object pointer just
+    /// like GC structs.
+    pub fn get_fn_ptr_reference_type(&self) -> PointerType<'ink> {
+        // { i8* }**
+        self.get_closure_type(&[])
+            .ptr_type(AddressSpace::Generic)
+            .ptr_type(AddressSpace::Generic)
+    }
+
+    /// Returns the type of the function that is called through a function pointer with the
+    /// signature `sig`. The object pointer of the closure is passed as the first argument.
+    pub fn get_closure_function_type(&self, sig: &FnSig) -> FunctionType<'ink> {
+        let param_tys: Vec<_> = std::iter::once(self.get_fn_ptr_reference_type().into())
+            .chain(sig.params().iter().map(|p| {
+                self.get_basic_type(p)
+                    .expect("could not convert function argument to basic type")
+            }))
+            .collect();
+
+        let return_type = sig.ret();
+        match return_type.interned() {
+            TyKind::Tuple(0, _) => self.context.void_type().fn_type(&param_tys, false),
+            _ => self
+                .get_basic_type(return_type)
+                .expect("could not convert return value")
+                .fn_type(&param_tys, false),
+        }
+    }
+
     /// Returns the type of the specified function definition
     pub fn get_function_type(&self, ty: hir::Function) -> FunctionType<'ink> {
-        let ty = self.db.callable_sig(ty.into());
+        self.get_function_instance_type(ty, &Substitution::empty())
+    }
+
+    /// Returns the type of the instance of the specified function definition with the type
+    /// arguments in `substs`.
+    pub fn get_function_instance_type(
+        &self,
+        ty: hir::Function,
+        substs: &Substitution,
+    ) -> FunctionType<'ink> {
+        let ty = self.db.callable_sig(ty.into()).subst(substs);
         let param_tys: Vec<_> = ty
             .params()
             .iter()
@@ -172,6 +371,11 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
         }
     }
 
+    /// Returns the number of bytes that a value of the specified type occupies in memory.
+    pub fn get_store_size(&self, ty: &BasicTypeEnum<'ink>) -> u32 {
+        self.target_data.get_store_size(ty) as u32
+    }
+
     /// Returns the inkwell type of the specified HIR type as a basic value. If the type cannot be
     /// represented as a basic type enum, `None` is returned.
     pub fn get_basic_type(&self, ty: &hir::Ty) -> Option<BasicTypeEnum<'ink>> {
@@ -179,8 +383,14 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             TyKind::Tuple(_, substs) => Some(self.get_tuple_type(substs).into()),
             TyKind::Float(float_ty) => Some(self.get_float_type(*float_ty).into()),
             TyKind::Int(int_ty) => Some(self.get_int_type(*int_ty).into()),
-            TyKind::Struct(struct_ty) => Some(self.get_struct_reference_type(*struct_ty)),
+            TyKind::Struct(struct_ty, substs) => {
+                Some(self.get_struct_reference_type(*struct_ty, substs))
+            }
+            TyKind::Enum(enum_ty) => Some(self.get_enum_type(*enum_ty).into()),
             TyKind::Bool => Some(self.get_bool_type().into()),
+            TyKind::String => Some(self.get_string_type().into()),
+            TyKind::Array(element_ty) => Some(self.get_array_reference_type(element_ty).into()),
+            TyKind::FnPtr(_) => Some(self.get_fn_ptr_reference_type().into()),
             _ => None,
         }
     }
@@ -193,8 +403,14 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             TyKind::Tuple(_, substs) => Some(self.get_tuple_type(substs).into()),
             TyKind::Float(float_ty) => Some(self.get_float_type(*float_ty).into()),
             TyKind::Int(int_ty) => Some(self.get_int_type(*int_ty).into()),
-            TyKind::Struct(struct_ty) => Some(self.get_public_struct_reference_type(*struct_ty)),
+            TyKind::Struct(struct_ty, substs) => {
+                Some(self.get_public_struct_reference_type(*struct_ty, substs))
+            }
+            TyKind::Enum(enum_ty) => Some(self.get_public_enum_reference_type(*enum_ty)),
             TyKind::Bool => Some(self.get_bool_type().into()),
+            TyKind::String => Some(self.get_string_type().into()),
+            TyKind::Array(element_ty) => Some(self.get_array_reference_type(element_ty).into()),
+            TyKind::FnPtr(_) => Some(self.get_fn_ptr_reference_type().into()),
             _ => None,
         }
     }
@@ -206,14 +422,17 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             TyKind::Tuple(_, substs) => Some(self.get_tuple_type(substs).into()),
             TyKind::Float(float_ty) => Some(self.get_float_type(*float_ty).into()),
             TyKind::Int(int_ty) => Some(self.get_int_type(*int_ty).into()),
-            TyKind::Struct(struct_ty) => Some(self.get_struct_type(*struct_ty).into()),
+            TyKind::Struct(struct_ty, substs) => {
+                Some(self.get_struct_type(*struct_ty, substs).into())
+            }
+            TyKind::Enum(enum_ty) => Some(self.get_enum_type(*enum_ty).into()),
             TyKind::FnDef(hir::CallableDef::Function(fn_ty), type_params) => {
-                if !type_params.is_empty() {
-                    unimplemented!("cannot yet deal with type parameters in functions");
-                }
-                Some(self.get_function_type(*fn_ty).into())
+                Some(self.get_function_instance_type(*fn_ty, type_params).into())
             }
             TyKind::Bool => Some(self.get_bool_type().into()),
+            TyKind::String => Some(self.get_string_type().into()),
+            TyKind::Array(element_ty) => Some(self.get_array_reference_type(element_ty).into()),
+            TyKind::FnPtr(_) => Some(self.get_fn_ptr_reference_type().into()),
             _ => None,
         }
     }
@@ -260,12 +479,76 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
                 let type_size = TypeSize::from_ir_type(&ir_ty, &self.target_data);
                 TypeInfo::new_primitive("core::bool", type_size)
             }
-            &TyKind::Struct(s) => {
-                let ir_ty = self.get_struct_type(s);
+            TyKind::String => {
+                let ir_ty = self.get_string_type();
+                let type_size = TypeSize::from_ir_type(&ir_ty, &self.target_data);
+                TypeInfo::new_primitive("core::string", type_size)
+            }
+            TyKind::Struct(s, substs) => {
+                let ir_ty = self.get_struct_type(*s, substs);
                 let type_size = TypeSize::from_ir_type(&ir_ty, &self.target_data);
-                TypeInfo::new_struct(self.db, s, type_size)
+                let type_arg_infos: Vec<TypeInfo> =
+                    substs.iter().map(|ty| self.type_info(ty)).collect();
+                TypeInfo::new_struct(self.db, *s, substs.clone(), &type_arg_infos, type_size)
+            }
+            &TyKind::Enum(e) => {
+                let ir_ty = self.get_enum_type(e);
+                let type_size = TypeSize::from_ir_type(&ir_ty, &self.target_data);
+                TypeInfo::new_enum(self.db, e, type_size)
+            }
+            TyKind::Array(element_ty) => {
+                let ir_ty = self.get_array_reference_type(element_ty);
+                let type_size = TypeSize::from_ir_type(&ir_ty, &self.target_data);
+                TypeInfo::new_array(
+                    self.db,
+                    element_ty.clone(),
+                    &self.type_info(element_ty),
+                    type_size,
+                )
+            }
+            TyKind::FnPtr(sig) => {
+                let ir_ty = self.get_closure_type(&[]);
+                let type_size = TypeSize::from_ir_type(&ir_ty, &self.target_data);
+                let param_type_infos: Vec<TypeInfo> =
+                    sig.params().iter().map(|ty| self.type_info(ty)).collect();
+                let ret_type_info = if sig.ret().is_empty() {
+                    None
+                } else {
+                    Some(self.type_info(sig.ret()))
+                };
+                TypeInfo::new_fn_ptr(
+                    sig.clone(),
+                    &param_type_infos,
+                    ret_type_info.as_ref(),
+                    type_size,
+                )
             }
             _ => unimplemented!("{} unhandled", ty.display(self.db)),
         }
     }
+
+    /// Returns a `TypeInfo` for the object of the lambda `lambda_name` of type `fn_ptr_ty`, that
+    /// captures the variables in `captures`.
+    pub fn lambda_type_info(
+        &self,
+        fn_ptr_ty: &Ty,
+        lambda_name: &str,
+        captures: Vec<(String, Ty)>,
+    ) -> TypeInfo {
+        let capture_tys: Vec<Ty> = captures.iter().map(|(_, ty)| ty.clone()).collect();
+        let ir_ty = self.get_closure_type(&capture_tys);
+        let type_size = TypeSize::from_ir_type(&ir_ty, &self.target_data);
+        TypeInfo::new_lambda(
+            self.db,
+            &self.type_info(fn_ptr_ty),
+            lambda_name,
+            captures,
+            type_size,
+        )
+    }
+
+    /// Returns the `TypeInfo` of the field of a closure that stores the pointer to its function.
+    pub fn fn_ptr_field_type_info(&self) -> TypeInfo {
+        <*const std::ffi::c_void as HasStaticTypeInfo>::type_info(self.context, &self.target_data)
+    }
 }
@@ -1,12 +1,14 @@
 use super::types as ir;
 use crate::{
+    ir::closure,
     ir::dispatch_table::{DispatchTable, FunctionPrototype},
+    ir::instance::FunctionInstance,
     ir::ty::HirTypeCache,
     type_info::{TypeInfo, TypeInfoData},
     value::{AsValue, CanInternalize, Global, IrValueContext, IterAsIrValue, Value},
     ModuleGroup,
 };
-use hir::{Body, ExprId, HirDatabase, InferenceResult};
+use hir::{Body, ExprId, HirDatabase, InferenceResult, Substitution, Ty, TyKind};
 use inkwell::{
     context::Context, module::Linkage, module::Module, targets::TargetData, types::ArrayType,
     values::PointerValue,
@@ -147,15 +149,43 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
 
     /// Collects unique `TypeInfo` from the given `Ty`.
     fn collect_type(&mut self, type_info: TypeInfo) {
-        if let TypeInfoData::Struct(hir_struct) = type_info.data {
-            self.collect_struct(hir_struct);
-        } else {
-            self.entries.insert(type_info);
+        match &type_info.data {
+            TypeInfoData::Struct(hir_struct, substs) => self.collect_struct(*hir_struct, substs),
+            TypeInfoData::Array(element_ty) => {
+                self.collect_type(self.hir_types.type_info(element_ty));
+                self.entries.insert(type_info);
+            }
+            TypeInfoData::Enum(hir_enum) => self.collect_enum(*hir_enum),
+            TypeInfoData::Closure(sig, captures) => {
+                // The types of the signature are required to invoke the closure from the runtime
+                for ty in sig.params().iter() {
+                    self.collect_type(self.hir_types.type_info(ty));
+                }
+                if !sig.ret().is_empty() {
+                    self.collect_type(self.hir_types.type_info(sig.ret()));
+                }
+
+                self.collect_type(self.hir_types.fn_ptr_field_type_info());
+                for (_, ty) in captures.iter() {
+                    self.collect_type(self.hir_types.type_info(ty));
+                }
+                self.entries.insert(type_info);
+            }
+            TypeInfoData::Primitive => {
+                self.entries.insert(type_info);
+            }
         }
     }
 
-    /// Collects unique `TypeInfo` from the specified expression and its sub-expressions.
-    fn collect_expr(&mut self, expr_id: ExprId, body: &Arc<Body>, infer: &InferenceResult) {
+    /// Collects unique `TypeInfo` from the specified expression and its sub-expressions, which
+    /// are part of the body of `instance`.
+    fn collect_expr(
+        &mut self,
+        expr_id: ExprId,
+        body: &Arc<Body>,
+        infer: &InferenceResult,
+        instance: &FunctionInstance,
+    ) {
         let expr = &body[expr_id];
 
         // If this expression is a call, store it in the dispatch table
@@ -164,13 +194,60 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
                 Some(hir::CallableDef::Function(hir_fn)) => {
                     self.maybe_collect_fn_signature(hir_fn);
                 }
-                Some(hir::CallableDef::Struct(_)) => (),
+                Some(hir::CallableDef::Struct(_))
+                | Some(hir::CallableDef::EnumVariant(_))
+                | Some(hir::CallableDef::Intrinsic(_)) => (),
+                None if infer[*callee].as_fn_ptr().is_some() => (),
                 None => panic!("expected a callable expression"),
             }
         }
 
+        // If this expression refers to a function that is used as a function pointer, the function
+        // is called from a generated thunk
+        if let Some(hir_fn) = closure::fn_ptr_function(self.db, body, infer, expr_id) {
+            self.maybe_collect_fn_signature(hir_fn);
+        }
+
+        // If this expression is a lambda, its type is required to allocate the lambda
+        if let hir::Expr::Lambda { .. } = expr {
+            self.collect_type(closure::lambda_type_info(
+                self.db,
+                self.hir_types,
+                instance,
+                body,
+                infer,
+                expr_id,
+            ));
+        }
+
+        // Values of a function pointer type are allocated as objects
+        if let TyKind::FnPtr(_) = infer[expr_id].interned() {
+            self.collect_type(self.hir_types.type_info(&infer[expr_id]));
+        }
+
+        // If this expression is a method call, the signature of the method is required
+        if let hir::Expr::MethodCall { .. } = expr {
+            let hir_fn = infer
+                .method_resolution(expr_id)
+                .expect("expected a resolved method");
+            self.maybe_collect_fn_signature(hir_fn);
+        }
+
+        // If this expression is an array literal, its type is required to allocate the array
+        if let hir::Expr::Array(_) = expr {
+            self.collect_type(self.hir_types.type_info(&infer[expr_id]));
+        }
+
+        // Instances of generic structs are not declared anywhere, so collect them where they are
+        // used
+        if let TyKind::Struct(hir_struct, substs) = infer[expr_id].interned() {
+            if !substs.is_empty() {
+                self.collect_struct(*hir_struct, substs);
+            }
+        }
+
         // Recurse further
-        expr.walk_child_exprs(|expr_id| self.collect_expr(expr_id, body, infer))
+        expr.walk_child_exprs(|expr_id| self.collect_expr(expr_id, body, infer, instance))
     }
 
     /// Collects `TypeInfo` from types in the signature of a function
@@ -207,20 +284,54 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
         // Collect used types from body
         let body = hir_fn.body(self.db);
         let infer = hir_fn.infer(self.db);
-        self.collect_expr(body.body_expr(), &body, &infer);
+        self.collect_expr(
+            body.body_expr(),
+            &body,
+            &infer,
+            &FunctionInstance::non_generic(hir_fn),
+        );
     }
 
-    /// Collects unique `TypeInfo` from the specified struct type.
-    pub fn collect_struct(&mut self, hir_struct: hir::Struct) {
-        let type_info = self.hir_types.type_info(&hir_struct.ty(self.db));
-        self.entries.insert(type_info);
+    /// Collects unique `TypeInfo` from the body of the specified instance of a generic function.
+    pub fn collect_instance(&mut self, instance: &FunctionInstance) {
+        let body = instance.function.body(self.db);
+        let infer = instance.infer(self.db);
+        self.collect_expr(body.body_expr(), &body, &infer, instance);
+    }
+
+    /// Collects unique `TypeInfo` from the specified struct type, instantiated with the type
+    /// arguments in `substs`.
+    pub fn collect_struct(&mut self, hir_struct: hir::Struct, substs: &Substitution) {
+        let type_info = self
+            .hir_types
+            .type_info(&Ty::struct_ty(hir_struct, substs.clone()));
+        if !self.entries.insert(type_info) {
+            return;
+        }
 
         let fields = hir_struct.fields(self.db);
         for field in fields.into_iter() {
-            self.collect_type(self.hir_types.type_info(&field.ty(self.db)));
+            self.collect_type(self.hir_types.type_info(&field.ty(self.db).subst(substs)));
+        }
+    }
+
+    /// Collects unique `TypeInfo` from the specified enum type.
+    pub fn collect_enum(&mut self, hir_enum: hir::Enum) {
+        let type_info = self.hir_types.type_info(&hir_enum.ty(self.db));
+        self.entries.insert(type_info);
+
+        for variant in hir_enum.variants(self.db) {
+            for field in variant.fields(self.db) {
+                self.collect_type(self.hir_types.type_info(&field.ty(self.db)));
+            }
         }
     }
 
+    /// Collects `TypeInfo` from the type of a `const` or `static` item.
+    pub fn collect_global(&mut self, ty: &Ty) {
+        self.collect_type(self.hir_types.type_info(ty));
+    }
+
     fn gen_type_info(
         &self,
         type_info_to_ir: &mut HashMap<TypeInfo, Value<'ink, *const ir::TypeInfo<'ink>>>,
@@ -251,7 +362,7 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
                 .alignment
                 .try_into()
                 .expect("could not convert alignment to smaller size"),
-            data: self.gen_type_info_data(type_info_to_ir, &type_info.data),
+            data: self.gen_type_info_data(type_info_to_ir, type_info),
         }
         .as_value(self.value_context);
 
@@ -270,13 +381,25 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
     fn gen_type_info_data(
         &self,
         type_info_to_ir: &mut HashMap<TypeInfo, Value<'ink, *const ir::TypeInfo<'ink>>>,
-        data: &TypeInfoData,
+        type_info: &TypeInfo,
     ) -> ir::TypeInfoData<'ink> {
-        match data {
+        match &type_info.data {
             TypeInfoData::Primitive => ir::TypeInfoData::Primitive,
-            TypeInfoData::Struct(s) => {
-                ir::TypeInfoData::Struct(self.gen_struct_info(type_info_to_ir, *s))
+            TypeInfoData::Struct(s, substs) => {
+                ir::TypeInfoData::Struct(self.gen_struct_info(type_info_to_ir, *s, substs))
             }
+            TypeInfoData::Array(element_ty) => ir::TypeInfoData::Array(ir::ArrayInfo {
+                element_type: self
+                    .gen_type_info(type_info_to_ir, &self.hir_types.type_info(element_ty)),
+            }),
+            TypeInfoData::Enum(e) => {
+                ir::TypeInfoData::Enum(self.gen_enum_info(type_info_to_ir, *e))
+            }
+            TypeInfoData::Closure(_, captures) => ir::TypeInfoData::Struct(self.gen_closure_info(
+                type_info_to_ir,
+                &type_info.name,
+                captures,
+            )),
         }
     }
 
@@ -284,9 +407,13 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
         &self,
         type_info_to_ir: &mut HashMap<TypeInfo, Value<'ink, *const ir::TypeInfo<'ink>>>,
         hir_struct: hir::Struct,
+        substs: &Substitution,
     ) -> ir::StructInfo<'ink> {
-        let struct_ir = self.hir_types.get_struct_type(hir_struct);
-        let name = hir_struct.full_name(self.db);
+        let struct_ir = self.hir_types.get_struct_type(hir_struct, substs);
+        let name = self
+            .hir_types
+            .type_info(&Ty::struct_ty(hir_struct, substs.clone()))
+            .name;
         let fields = hir_struct.fields(self.db);
 
         // Construct an array of field names (or null if there are no fields)
@@ -311,7 +438,7 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
         let field_types = fields
             .iter()
             .map(|field| {
-                let field_type_info = self.hir_types.type_info(&field.ty(self.db));
+                let field_type_info = self.hir_types.type_info(&field.ty(self.db).subst(substs));
                 self.gen_type_info(type_info_to_ir, &field_type_info)
             })
             .into_const_private_pointer_or_null(
@@ -345,6 +472,182 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
         }
     }
 
+    fn gen_enum_info(
+        &self,
+        type_info_to_ir: &mut HashMap<TypeInfo, Value<'ink, *const ir::TypeInfo<'ink>>>,
+        hir_enum: hir::Enum,
+    ) -> ir::EnumInfo<'ink> {
+        let name = hir_enum.full_name(self.db);
+        let variants = hir_enum.variants(self.db);
+
+        // Construct an array of variant names (or null if there are no variants)
+        let variant_names = variants
+            .iter()
+            .enumerate()
+            .map(|(idx, variant)| {
+                CString::new(variant.name(self.db).to_string())
+                    .expect("variant name is not a valid CString")
+                    .intern(
+                        format!("enum_info::<{}>::variant_names.{}", name, idx),
+                        self.value_context,
+                    )
+                    .as_value(self.value_context)
+            })
+            .into_const_private_pointer_or_null(
+                format!("enum_info::<{}>::variant_names", name),
+                self.value_context,
+            );
+
+        // Construct an array of the fields of each variant (or null if there are no variants)
+        let variant_infos = variants
+            .iter()
+            .map(|variant| self.gen_enum_variant_info(type_info_to_ir, &name, *variant))
+            .into_const_private_pointer_or_null(
+                format!("enum_info::<{}>::variant_infos", name),
+                self.value_context,
+            );
+
+        ir::EnumInfo {
+            variant_names,
+            variant_infos,
+            num_variants: variants
+                .len()
+                .try_into()
+                .expect("could not convert num_variants to smaller bit size"),
+        }
+    }
+
+    /// Generates the `StructInfo` that describes the fields of an enum variant. The offsets of the
+    /// fields are relative to the start of the enum, i.e. they include the tag.
+    fn gen_enum_variant_info(
+        &self,
+        type_info_to_ir: &mut HashMap<TypeInfo, Value<'ink, *const ir::TypeInfo<'ink>>>,
+        enum_name: &str,
+        variant: hir::EnumVariant,
+    ) -> ir::StructInfo<'ink> {
+        let variant_ir = self.hir_types.get_enum_variant_type(variant);
+        let name = format!("{}::{}", enum_name, variant.name(self.db));
+        let fields = variant.fields(self.db);
+
+        // Construct an array of field names (or null if there are no fields)
+        let field_names = fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                CString::new(field.name(self.db).to_string())
+                    .expect("field name is not a valid CString")
+                    .intern(
+                        format!("struct_info::<{}>::field_names.{}", name, idx),
+                        self.value_context,
+                    )
+                    .as_value(self.value_context)
+            })
+            .into_const_private_pointer_or_null(
+                format!("struct_info::<{}>::field_names", name),
+                self.value_context,
+            );
+
+        // Construct an array of field types (or null if there are no fields)
+        let field_types = fields
+            .iter()
+            .map(|field| {
+                let field_type_info = self.hir_types.type_info(&field.ty(self.db));
+                self.gen_type_info(type_info_to_ir, &field_type_info)
+            })
+            .into_const_private_pointer_or_null(
+                format!("struct_info::<{}>::field_types", name),
+                self.value_context,
+            );
+
+        // Construct an array of field offsets (or null if there are no fields). The first element
+        // of the variant is the tag.
+        let field_offsets = fields
+            .iter()
+            .enumerate()
+            .map(|(idx, _)| {
+                self.target_data
+                    .offset_of_element(&variant_ir, idx as u32 + 1)
+                    .unwrap() as u16
+            })
+            .into_const_private_pointer_or_null(
+                format!("struct_info::<{}>::field_offsets", name),
+                self.value_context,
+            );
+
+        ir::StructInfo {
+            field_names,
+            field_types,
+            field_offsets,
+            num_fields: fields
+                .len()
+                .try_into()
+                .expect("could not convert num_fields to smaller bit size"),
+            memory_kind: abi::StructMemoryKind::Value,
+        }
+    }
+
+    /// Generates the `StructInfo` that describes the memory of a closure: a pointer to its function,
+    /// followed by the captured variables. Closures are always allocated on the heap.
+    fn gen_closure_info(
+        &self,
+        type_info_to_ir: &mut HashMap<TypeInfo, Value<'ink, *const ir::TypeInfo<'ink>>>,
+        name: &str,
+        captures: &[(String, Ty)],
+    ) -> ir::StructInfo<'ink> {
+        let capture_tys: Vec<Ty> = captures.iter().map(|(_, ty)| ty.clone()).collect();
+        let closure_ir = self.hir_types.get_closure_type(&capture_tys);
+        let num_fields = captures.len() + 1;
+
+        // Construct an array of field names
+        let field_names = std::iter::once(closure::FN_PTR_FIELD_NAME)
+            .chain(captures.iter().map(|(name, _)| name.as_str()))
+            .enumerate()
+            .map(|(idx, field_name)| {
+                CString::new(field_name)
+                    .expect("field name is not a valid CString")
+                    .intern(
+                        format!("struct_info::<{}>::field_names.{}", name, idx),
+                        self.value_context,
+                    )
+                    .as_value(self.value_context)
+            })
+            .into_const_private_pointer_or_null(
+                format!("struct_info::<{}>::field_names", name),
+                self.value_context,
+            );
+
+        // Construct an array of field types
+        let field_types = std::iter::once(self.hir_types.fn_ptr_field_type_info())
+            .chain(capture_tys.iter().map(|ty| self.hir_types.type_info(ty)))
+            .map(|field_type_info| self.gen_type_info(type_info_to_ir, &field_type_info))
+            .into_const_private_pointer_or_null(
+                format!("struct_info::<{}>::field_types", name),
+                self.value_context,
+            );
+
+        // Construct an array of field offsets
+        let field_offsets = (0..num_fields)
+            .map(|idx| {
+                self.target_data
+                    .offset_of_element(&closure_ir, idx as u32)
+                    .unwrap() as u16
+            })
+            .into_const_private_pointer_or_null(
+                format!("struct_info::<{}>::field_offsets", name),
+                self.value_context,
+            );
+
+        ir::StructInfo {
+            field_names,
+            field_types,
+            field_offsets,
+            num_fields: num_fields
+                .try_into()
+                .expect("could not convert num_fields to smaller bit size"),
+            memory_kind: abi::StructMemoryKind::Gc,
+        }
+    }
+
     /// Constructs a `TypeTable` from all *used* types.
     pub fn build(mut self) -> TypeTable<'ink> {
         let mut entries = BTreeSet::new();
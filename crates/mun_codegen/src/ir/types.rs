@@ -40,6 +40,18 @@ impl<'ink> TransparentValue<'ink> for abi::StructMemoryKind {
     }
 }
 
+impl<'ink> TransparentValue<'ink> for abi::GlobalKind {
+    type Target = u8;
+
+    fn as_target_value(&self, context: &IrValueContext<'ink, '_, '_>) -> Value<'ink, Self::Target> {
+        (*self as u8).as_value(context)
+    }
+
+    fn as_bytes_and_ptrs(&self, _: &IrTypeContext<'ink, '_>) -> Vec<BytesOrPtr<'ink>> {
+        vec![vec![*self as u8].into()]
+    }
+}
+
 #[derive(AsValue)]
 pub struct TypeInfo<'ink> {
     pub guid: abi::Guid,
@@ -54,6 +66,8 @@ pub struct TypeInfo<'ink> {
 pub enum TypeInfoData<'ink> {
     Primitive,
     Struct(StructInfo<'ink>),
+    Array(ArrayInfo<'ink>),
+    Enum(EnumInfo<'ink>),
 }
 
 #[derive(AsValue)]
@@ -84,13 +98,35 @@ pub struct StructInfo<'ink> {
     pub memory_kind: abi::StructMemoryKind,
 }
 
+#[derive(AsValue)]
+pub struct ArrayInfo<'ink> {
+    pub element_type: Value<'ink, *const TypeInfo<'ink>>,
+}
+
+#[derive(AsValue)]
+pub struct EnumInfo<'ink> {
+    pub variant_names: Value<'ink, *const *const u8>,
+    pub variant_infos: Value<'ink, *const StructInfo<'ink>>,
+    pub num_variants: u16,
+}
+
+#[derive(AsValue)]
+pub struct GlobalInfo<'ink> {
+    pub name: Value<'ink, *const u8>,
+    pub type_info: Value<'ink, *const TypeInfo<'ink>>,
+    pub value: Value<'ink, *mut u8>,
+    pub kind: abi::GlobalKind,
+}
+
 #[derive(AsValue)]
 pub struct ModuleInfo<'ink> {
     pub path: Value<'ink, *const u8>,
     pub functions: Value<'ink, *const FunctionDefinition<'ink>>,
     pub types: Value<'ink, *const *const TypeInfo<'ink>>,
+    pub globals: Value<'ink, *const GlobalInfo<'ink>>,
     pub num_functions: u32,
     pub num_types: u32,
+    pub num_globals: u32,
 }
 
 #[derive(AsValue)]
@@ -61,8 +61,10 @@ impl IsAbiCompatible<u32> for u32 {}
 impl IsAbiCompatible<abi::Guid> for abi::Guid {}
 impl IsAbiCompatible<abi::TypeGroup> for abi::TypeGroup {}
 impl IsAbiCompatible<abi::StructMemoryKind> for abi::StructMemoryKind {}
+impl IsAbiCompatible<abi::GlobalKind> for abi::GlobalKind {}
 impl IsAbiCompatible<*const ::std::os::raw::c_char> for *const u8 {}
 impl IsAbiCompatible<*const ::std::os::raw::c_void> for *const fn() {}
+impl IsAbiCompatible<*mut ::std::os::raw::c_void> for *mut u8 {}
 impl<S, T: IsAbiCompatible<S>> IsAbiCompatible<*const S> for *const T {}
 impl<S, T: IsAbiCompatible<S>> IsAbiCompatible<*mut S> for *mut T {}
 impl<'ink, S, T: ConcreteValueType<'ink>> IsAbiCompatible<S> for Value<'ink, T> where
@@ -145,6 +147,19 @@ fn test_struct_info_abi_compatible() {
     super::StructInfo::test(&abi_type);
 }
 
+#[test]
+#[cfg(test)]
+fn test_global_info_abi_compatible() {
+    let abi_type = abi::GlobalInfo {
+        name: std::ptr::null(),
+        type_info: std::ptr::null(),
+        value: std::ptr::null_mut(),
+        kind: abi::GlobalKind::Static,
+    };
+
+    super::GlobalInfo::test(&abi_type);
+}
+
 #[test]
 #[cfg(test)]
 fn test_module_info_abi_compatible() {
@@ -154,6 +169,8 @@ fn test_module_info_abi_compatible() {
         num_functions: 0,
         types: std::ptr::null(),
         num_types: 0,
+        globals: std::ptr::null(),
+        num_globals: 0,
     };
 
     super::ModuleInfo::test(&abi_type);
@@ -181,6 +198,8 @@ fn test_assembly_info_abi_compatible() {
             num_functions: 0,
             types: std::ptr::null(),
             num_types: 0,
+            globals: std::ptr::null(),
+            num_globals: 0,
         },
         dispatch_table: abi::DispatchTable {
             prototypes: std::ptr::null(),
@@ -31,6 +31,7 @@ pub fn create_with_target(target: &spec::Target) -> Box<dyn Linker> {
         LinkerFlavor::Ld => Box::new(LdLinker::new(target)),
         LinkerFlavor::Ld64 => Box::new(Ld64Linker::new(target)),
         LinkerFlavor::Msvc => Box::new(MsvcLinker::new(target)),
+        LinkerFlavor::Wasm => Box::new(WasmLinker::new(target)),
     }
 }
 
@@ -189,3 +190,58 @@ impl Linker for MsvcLinker {
             .map_err(LinkerError::LinkError)
     }
 }
+
+struct WasmLinker {
+    args: Vec<String>,
+}
+
+impl WasmLinker {
+    fn new(_target: &spec::Target) -> Self {
+        WasmLinker {
+            args: Vec::default(),
+        }
+    }
+}
+
+impl Linker for WasmLinker {
+    fn add_object(&mut self, path: &Path) -> Result<(), LinkerError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?
+            .to_owned();
+        self.args.push(path_str);
+        Ok(())
+    }
+
+    fn build_shared_object(&mut self, path: &Path) -> Result<(), LinkerError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?;
+
+        // A wasm module has no entry point; the loader calls the exported functions directly
+        self.args.push("--no-entry".to_owned());
+        self.args
+            .push(format!("--export={}", abi::GET_INFO_FN_NAME));
+        self.args
+            .push(format!("--export={}", abi::GET_VERSION_FN_NAME));
+        self.args
+            .push(format!("--export={}", abi::SET_ALLOCATOR_HANDLE_FN_NAME));
+
+        // Function pointers are indices into the indirect function table, which the loader needs
+        // to invoke functions and to fill the dispatch table. The loader provides the table, so it
+        // can append the intrinsics that it implements as host functions.
+        self.args.push("--import-table".to_owned());
+
+        // Specify output path
+        self.args.push("-o".to_owned());
+        self.args.push(path_str.to_owned());
+
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(), LinkerError> {
+        mun_lld::link(mun_lld::LldFlavor::Wasm, &self.args)
+            .ok()
+            .map_err(LinkerError::LinkError)
+    }
+}
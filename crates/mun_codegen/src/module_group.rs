@@ -74,6 +74,20 @@ impl ModuleGroup {
         self.ordered_modules.iter().copied()
     }
 
+    /// Returns an iterator over all functions that are defined in the `impl` blocks of the modules
+    /// in the group
+    pub fn associated_functions<'s>(
+        &'s self,
+        db: &'s dyn HirDatabase,
+    ) -> impl Iterator<Item = hir::Function> + 's {
+        self.iter()
+            .flat_map(move |module| module.impls(db))
+            .flat_map(move |hir_impl| hir_impl.items(db))
+            .map(|item| match item {
+                hir::AssocItem::Function(f) => f,
+            })
+    }
+
     /// Returns true if the specified function should be exported from the module group. This
     /// indicates that when queried the resulting assembly will expose this function.
     pub fn should_export_fn(&self, db: &dyn HirDatabase, function: hir::Function) -> bool {
@@ -82,6 +96,12 @@ impl ModuleGroup {
             return false;
         }
 
+        // Generic functions are only compiled for the type arguments they are called with, so
+        // there is nothing to export.
+        if function.is_generic(db) {
+            return false;
+        }
+
         let vis = function.visibility(db);
         match vis {
             // If the function is publicly accessible it must always be exported
@@ -103,8 +123,12 @@ impl ModuleGroup {
     }
 
     /// Returns true if the specified function should be included in the dispatch table of this
-    /// module group if it is used from within this module group.
+    /// module group if it is used from within this module group. Generic functions are never
+    /// linked at runtime; instead they are instantiated in every module group that uses them.
     pub fn should_runtime_link_fn(&self, db: &dyn HirDatabase, function: hir::Function) -> bool {
+        if function.is_generic(db) {
+            return false;
+        }
         function.is_extern(db) || !self.modules.contains(&function.module(db))
     }
 
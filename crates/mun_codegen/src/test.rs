@@ -2,7 +2,9 @@ use crate::{
     code_gen::{AssemblyBuilder, CodeGenContext},
     ir::file::gen_file_ir,
     ir::file_group::gen_file_group_ir,
+    ir::types::{AssemblyInfo, TypeInfo},
     mock::MockDatabase,
+    value::{IrTypeContext, Value},
     CodeGenDatabase,
 };
 use hir::{
@@ -870,6 +872,107 @@ fn incremental_compilation() {
     // TODO: Add support for multiple files in a group
 }
 
+#[test]
+fn cross_compile_aarch64() {
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        pub struct(value) Foo(i8, i128);
+
+        pub fn foo(foo: Foo) -> i128 {
+            foo.1
+        }
+        "#,
+    );
+    db.set_target(Target::search("aarch64-unknown-linux-gnu").unwrap());
+
+    // The layout of types is determined by the target, which aligns `i128` to 16 bytes
+    let foo = hir::Package::all(db.upcast())
+        .into_iter()
+        .flat_map(|package| package.modules(db.upcast()))
+        .flat_map(|module| module.declarations(db.upcast()))
+        .find_map(|def| match def {
+            hir::ModuleDef::Struct(s) => Some(s),
+            _ => None,
+        })
+        .expect("could not find struct");
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let type_info = code_gen.hir_types.type_info(&foo.ty(db.upcast()));
+    assert_eq!(type_info.size.alloc_size, 32);
+    assert_eq!(type_info.size.alignment, 16);
+
+    // The ABI types emitted for the target must match the layout of their Rust counterparts
+    let target_data = code_gen.target_machine.get_target_data();
+    let type_context = IrTypeContext {
+        context: &llvm_context,
+        target_data: &target_data,
+        struct_types: &code_gen.rust_types,
+    };
+    assert_eq!(
+        target_data.get_store_size(&Value::<TypeInfo>::get_ir_type(&type_context)) as usize,
+        std::mem::size_of::<abi::TypeInfo>()
+    );
+    assert_eq!(
+        target_data.get_store_size(&Value::<AssemblyInfo>::get_ir_type(&type_context)) as usize,
+        std::mem::size_of::<abi::AssemblyInfo>()
+    );
+
+    // The assembly is an ELF shared object for AArch64
+    let module_group_id = db
+        .module_partition()
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+    let assembly = db.target_assembly(module_group_id);
+    let header = std::fs::read(assembly.path()).unwrap();
+    assert_eq!(&header[0..4], b"\x7fELF");
+    assert_eq!(header[4], 2, "expected a 64-bit object");
+    assert_eq!(header[5], 1, "expected a little-endian object");
+    assert_eq!(
+        u16::from_le_bytes([header[16], header[17]]),
+        3,
+        "expected a shared object"
+    );
+    assert_eq!(
+        u16::from_le_bytes([header[18], header[19]]),
+        183,
+        "expected an AArch64 object"
+    );
+}
+
+#[test]
+fn cross_compile_wasm32() {
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        pub fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+        "#,
+    );
+    db.set_target(Target::search("wasm32-unknown-unknown").unwrap());
+
+    // The assembly is a wasm module that exports the functions required by the runtime
+    let module_group_id = db
+        .module_partition()
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+    let assembly = db.target_assembly(module_group_id);
+    let module = std::fs::read(assembly.path()).unwrap();
+    assert_eq!(&module[0..8], b"\0asm\x01\0\0\0", "expected a wasm module");
+    for export in &[
+        abi::GET_INFO_FN_NAME,
+        abi::GET_VERSION_FN_NAME,
+        abi::SET_ALLOCATOR_HANDLE_FN_NAME,
+    ] {
+        assert!(
+            module
+                .windows(export.len())
+                .any(|bytes| bytes == export.as_bytes()),
+            "expected `{}` to be exported",
+            export
+        );
+    }
+}
+
 #[test]
 fn nested_structs() {
     test_snapshot(
@@ -9,7 +9,12 @@ use std::hash::{Hash, Hasher};
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TypeInfoData {
     Primitive,
-    Struct(hir::Struct),
+    Struct(hir::Struct, hir::Substitution),
+    Array(hir::Ty),
+    Enum(hir::Enum),
+    /// A function pointer or a lambda. The memory of the object starts with a pointer to the
+    /// function, followed by the names and types of the variables captured by a lambda.
+    Closure(hir::FnSig, Vec<(String, hir::Ty)>),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -80,8 +85,25 @@ impl TypeInfo {
         }
     }
 
-    pub fn new_struct(db: &dyn HirDatabase, s: hir::Struct, type_size: TypeSize) -> TypeInfo {
-        let name = s.full_name(db);
+    /// Constructs a `TypeInfo` for the instance of struct `s` with the type arguments in
+    /// `substs`. Every instance of a generic struct has its own name and `Guid`, e.g. `Pair<i32>`
+    /// and `Pair<f64>` are distinct types.
+    pub fn new_struct(
+        db: &dyn HirDatabase,
+        s: hir::Struct,
+        substs: hir::Substitution,
+        type_arg_infos: &[TypeInfo],
+        type_size: TypeSize,
+    ) -> TypeInfo {
+        let name = if type_arg_infos.is_empty() {
+            s.full_name(db)
+        } else {
+            let type_args: Vec<&str> = type_arg_infos
+                .iter()
+                .map(|type_info| type_info.name.as_str())
+                .collect();
+            format!("{}<{}>", s.full_name(db), type_args.join(", "))
+        };
         let guid_string = {
             let fields: Vec<String> = s
                 .fields(db)
@@ -89,6 +111,7 @@ impl TypeInfo {
                 .map(|f| {
                     let ty_string = f
                         .ty(db)
+                        .subst(&substs)
                         .guid_string(db)
                         .expect("type should be convertible to a string");
                     format!("{}: {}", f.name(db).to_string(), ty_string)
@@ -105,7 +128,128 @@ impl TypeInfo {
             guid: Guid(md5::compute(&guid_string).0),
             name,
             size: type_size,
-            data: TypeInfoData::Struct(s),
+            data: TypeInfoData::Struct(s, substs),
+        }
+    }
+
+    pub fn new_enum(db: &dyn HirDatabase, e: hir::Enum, type_size: TypeSize) -> TypeInfo {
+        let name = e.full_name(db);
+        let guid_string = {
+            let variants: Vec<String> = e
+                .variants(db)
+                .into_iter()
+                .map(|v| {
+                    let fields: Vec<String> = v
+                        .fields(db)
+                        .into_iter()
+                        .map(|f| {
+                            let ty_string = f
+                                .ty(db)
+                                .guid_string(db)
+                                .expect("type should be convertible to a string");
+                            format!("{}: {}", f.name(db).to_string(), ty_string)
+                        })
+                        .collect();
+                    format!("{}{{{}}}", v.name(db).to_string(), fields.join(","))
+                })
+                .collect();
+
+            format!(
+                "enum {name}{{{variants}}}",
+                name = &name,
+                variants = variants.join(",")
+            )
+        };
+        Self {
+            guid: Guid(md5::compute(&guid_string).0),
+            name,
+            size: type_size,
+            data: TypeInfoData::Enum(e),
+        }
+    }
+
+    pub fn new_array(
+        db: &dyn HirDatabase,
+        element_ty: hir::Ty,
+        element_type_info: &TypeInfo,
+        type_size: TypeSize,
+    ) -> TypeInfo {
+        let guid_string = format!(
+            "[{}]",
+            element_ty
+                .guid_string(db)
+                .expect("type should be convertible to a string")
+        );
+        Self {
+            guid: Guid(md5::compute(&guid_string).0),
+            name: format!("[{}]", element_type_info.name),
+            size: type_size,
+            data: TypeInfoData::Array(element_ty),
+        }
+    }
+
+    /// Constructs a `TypeInfo` for the function pointer type with the signature `sig`, e.g.
+    /// `fn(core::i32) -> core::i32`. The representation of a function pointer does not depend on
+    /// its signature, so just like for primitives the `Guid` is derived from the name.
+    pub fn new_fn_ptr(
+        sig: hir::FnSig,
+        param_type_infos: &[TypeInfo],
+        ret_type_info: Option<&TypeInfo>,
+        type_size: TypeSize,
+    ) -> TypeInfo {
+        let params: Vec<&str> = param_type_infos
+            .iter()
+            .map(|type_info| type_info.name.as_str())
+            .collect();
+        let name = match ret_type_info {
+            Some(ret_type_info) => format!("fn({}) -> {}", params.join(", "), ret_type_info.name),
+            None => format!("fn({})", params.join(", ")),
+        };
+        Self {
+            guid: Guid(md5::compute(&name).0),
+            name,
+            size: type_size,
+            data: TypeInfoData::Closure(sig, Vec::new()),
+        }
+    }
+
+    /// Constructs a `TypeInfo` for the object of a lambda that captures variables. The name of
+    /// the type starts with the name of its function pointer type, e.g.
+    /// `fn(core::i32) -> core::i32 {foo::lambda#3}`.
+    pub fn new_lambda(
+        db: &dyn HirDatabase,
+        fn_ptr_type_info: &TypeInfo,
+        lambda_name: &str,
+        captures: Vec<(String, hir::Ty)>,
+        type_size: TypeSize,
+    ) -> TypeInfo {
+        let sig = match &fn_ptr_type_info.data {
+            TypeInfoData::Closure(sig, _) => sig.clone(),
+            _ => unreachable!("expected a function pointer type"),
+        };
+        let name = format!("{} {{{}}}", fn_ptr_type_info.name, lambda_name);
+        let guid_string = {
+            let fields: Vec<String> = captures
+                .iter()
+                .map(|(name, ty)| {
+                    let ty_string = ty
+                        .guid_string(db)
+                        .expect("type should be convertible to a string");
+                    format!("{}: {}", name, ty_string)
+                })
+                .collect();
+
+            format!(
+                "closure {name}{{{fields}}}",
+                name = &name,
+                fields = fields.join(",")
+            )
+        };
+        Self {
+            guid: Guid(md5::compute(&guid_string).0),
+            name,
+            size: type_size,
+            data: TypeInfoData::Closure(sig, captures),
         }
     }
 }
@@ -53,7 +53,7 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                     let ty = &f.ty;
                     quote! {{
                         let ir_type = <#ty>::get_ir_type(type_context);
-                        type_context.target_data.get_preferred_alignment(&ir_type) as usize
+                        type_context.target_data.get_abi_alignment(&ir_type) as usize
                     }}
                 });
 
@@ -412,7 +412,7 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                     let ty = &f.ty;
                     quote! {{
                         let ir_type = <#ty>::get_ir_type(type_context);
-                        type_context.target_data.get_preferred_alignment(&ir_type) as usize
+                        type_context.target_data.get_abi_alignment(&ir_type) as usize
                     }}
                 });
 
@@ -450,7 +450,7 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                     let ty = &f.ty;
                     quote! {{
                         let ir_type = <#ty>::get_ir_type(type_context);
-                        type_context.target_data.get_preferred_alignment(&ir_type) as usize
+                        type_context.target_data.get_abi_alignment(&ir_type) as usize
                     }}
                 });
 
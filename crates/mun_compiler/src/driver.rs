@@ -2,8 +2,8 @@
 //! from previous compilation.
 
 use crate::{
-    compute_source_relative_path, db::CompilerDatabase, ensure_package_output_dir, is_source_file,
-    PathOrInline, RelativePath,
+    compute_source_relative_path, db::CompilerDatabase, ensure_workspace_output_dir,
+    is_source_file, PathOrInline, RelativePath,
 };
 use hir::{
     AstDatabase, DiagnosticSink, FileId, Module, PackageSet, SourceDatabase, SourceRoot,
@@ -19,7 +19,7 @@ pub use self::config::Config;
 pub use self::display_color::DisplayColor;
 
 use crate::diagnostics_snippets::{emit_hir_diagnostic, emit_syntax_error};
-use mun_project::{Package, LOCKFILE_NAME};
+use mun_project::{Package, PackageGraph, Registry, Workspace, LOCKFILE_NAME};
 use std::{
     collections::HashMap, convert::TryInto, io::Cursor, path::Path, path::PathBuf, sync::Arc,
     time::Duration,
@@ -103,29 +103,102 @@ impl Driver {
         Ok((driver, file_id))
     }
 
-    /// Constructs a driver with a package manifest directory
+    /// Constructs a driver with a package or workspace manifest path. All members of the
+    /// workspace and the packages they depend on are loaded, and are compiled to the same output
+    /// directory.
     pub fn with_package_path<P: AsRef<Path>>(
         package_path: P,
         config: Config,
-    ) -> Result<(Package, Driver), anyhow::Error> {
-        // Load the manifest file as a package
-        let package = Package::from_file(package_path)?;
+    ) -> Result<(Workspace, Driver), anyhow::Error> {
+        // Load the manifest file as a workspace, together with all its dependencies
+        let workspace = Workspace::from_manifest_path(package_path)?;
+        let registry = config.registry.as_ref().map(Registry::open).transpose()?;
+        let graph = PackageGraph::from_workspace(&workspace, registry.as_ref())?;
 
         // Determine output directory
-        let output_dir = ensure_package_output_dir(&package, &config)
+        let output_dir = ensure_workspace_output_dir(&workspace, &config)
             .map_err(|e| anyhow::anyhow!("could not create package output directory: {}", e))?;
 
         // Construct the driver
         let mut driver = Driver::with_config(config, output_dir);
 
-        // Iterate over all files in the source directory of the package and store their information in
-        // the database
-        let source_directory = package.source_directory();
-        if !source_directory.is_dir() {
-            anyhow::bail!("the source directory does not exist")
+        // The package defined by the manifest itself is stored in the workspace source root
+        let root_index = workspace.root_package().map(|_| graph.members()[0]);
+
+        // Packages are added in dependency order, so their assemblies are also written in that
+        // order. Only the files of the root package are tracked by path, so changes to other
+        // packages require a new driver.
+        let mut package_set = PackageSet::default();
+        let mut package_ids = Vec::with_capacity(graph.packages().len());
+        for (idx, package) in graph.packages().iter().enumerate() {
+            let is_dependency =
+                (0..graph.packages().len()).any(|other| graph.dependencies(other).contains(&idx));
+
+            let source_root_id = if Some(idx) == root_index {
+                // Iterate over all files in the source directory of the package and store their
+                // information in the database
+                for (relative_path, file_contents) in read_source_files(package)? {
+                    let file_id = driver.alloc_file_id(&relative_path)?;
+                    driver.db.set_file_text(file_id, Arc::from(file_contents));
+                    driver.db.set_file_source_root(file_id, WORKSPACE);
+                    driver
+                        .source_root
+                        .insert_file(file_id, relative_path.clone());
+                }
+                WORKSPACE
+            } else {
+                let source_root_id = SourceRootId(idx as u32 + 1);
+                let mut source_root = SourceRoot::default();
+                for (relative_path, file_contents) in read_source_files(package)? {
+                    let file_id = driver.next_file_id()?;
+                    driver.db.set_file_text(file_id, Arc::from(file_contents));
+                    driver.db.set_file_source_root(file_id, source_root_id);
+                    source_root.insert_file(file_id, relative_path);
+                }
+                driver
+                    .db
+                    .set_source_root(source_root_id, Arc::new(source_root));
+                source_root_id
+            };
+
+            // The root package is not named unless other packages depend on it, so its assemblies
+            // are named after its modules only
+            package_ids.push(if Some(idx) == root_index && !is_dependency {
+                package_set.add_package(source_root_id)
+            } else {
+                package_set.add_named_package(source_root_id, package.name())
+            });
+        }
+
+        // Store the source root in the database
+        driver
+            .db
+            .set_source_root(WORKSPACE, Arc::new(driver.source_root.clone()));
+
+        for (idx, &package_id) in package_ids.iter().enumerate() {
+            for &dependency in graph.dependencies(idx) {
+                package_set.add_dependency(package_id, package_ids[dependency]);
+            }
         }
+        driver.db.set_packages(Arc::new(package_set));
+
+        Ok((workspace, driver))
+    }
+}
+
+/// Reads the contents of all source files of the specified `package`, together with their paths
+/// relative to the source directory.
+fn read_source_files(package: &Package) -> anyhow::Result<Vec<(RelativePathBuf, String)>> {
+    let source_directory = package.source_directory();
+    if !source_directory.is_dir() {
+        anyhow::bail!(
+            "the source directory of `{}` does not exist",
+            package.name()
+        )
+    }
 
-        for source_file_path in iter_source_files(&source_directory) {
+    iter_source_files(&source_directory)
+        .map(|source_file_path| {
             let relative_path = compute_source_relative_path(&source_directory, &source_file_path)?;
 
             // Load the contents of the file
@@ -137,25 +210,9 @@ impl Driver {
                 )
             })?;
 
-            let file_id = driver.alloc_file_id(&relative_path)?;
-            driver.db.set_file_text(file_id, Arc::from(file_contents));
-            driver.db.set_file_source_root(file_id, WORKSPACE);
-            driver
-                .source_root
-                .insert_file(file_id, relative_path.clone());
-        }
-
-        // Store the source root in the database
-        driver
-            .db
-            .set_source_root(WORKSPACE, Arc::new(driver.source_root.clone()));
-
-        let mut package_set = PackageSet::default();
-        package_set.add_package(WORKSPACE);
-        driver.db.set_packages(Arc::new(package_set));
-
-        Ok((package, driver))
-    }
+            Ok((relative_path, file_contents))
+        })
+        .collect()
 }
 
 impl Driver {
@@ -173,14 +230,7 @@ impl Driver {
         }
 
         // Allocate a new id
-        // TODO: See if we can figure out if the compiler cleared the cache of a certain file, at
-        //  which point we can sort of reset the `next_file_id`
-        let id = FileId(
-            self.next_file_id
-                .try_into()
-                .map_err(|_e| anyhow::anyhow!("too many active source files"))?,
-        );
-        self.next_file_id += 1;
+        let id = self.next_file_id()?;
 
         // Update bookkeeping
         self.path_to_file_id
@@ -193,6 +243,19 @@ impl Driver {
 }
 
 impl Driver {
+    /// Allocates a new file id that is not associated with a path.
+    fn next_file_id(&mut self) -> Result<FileId, anyhow::Error> {
+        // TODO: See if we can figure out if the compiler cleared the cache of a certain file, at
+        //  which point we can sort of reset the `next_file_id`
+        let id = FileId(
+            self.next_file_id
+                .try_into()
+                .map_err(|_e| anyhow::anyhow!("too many active source files"))?,
+        );
+        self.next_file_id += 1;
+        Ok(id)
+    }
+
     /// Sets the contents of a specific file.
     pub fn set_file_text(
         &mut self,
@@ -17,6 +17,10 @@ pub struct Config {
 
     /// Whether or not to emit an IR file instead of a munlib.
     pub emit_ir: bool,
+
+    /// The optional directory of the package registry from which dependencies that are not
+    /// specified by path are loaded.
+    pub registry: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -29,6 +33,7 @@ impl Default for Config {
             optimization_lvl: OptimizationLevel::Default,
             out_dir: None,
             emit_ir: false,
+            registry: None,
         }
     }
 }
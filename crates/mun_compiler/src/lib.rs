@@ -17,7 +17,7 @@ pub use mun_codegen::OptimizationLevel;
 
 pub use crate::db::CompilerDatabase;
 pub use annotate_snippets::snippet::AnnotationType;
-use mun_project::Package;
+use mun_project::{Package, Workspace};
 use std::ffi::OsStr;
 use std::io::stderr;
 
@@ -84,12 +84,26 @@ pub fn ensure_package_output_dir(
     Ok(out_dir)
 }
 
+/// Returns and creates the output dir for the specified workspace, which is shared by all its
+/// members
+pub fn ensure_workspace_output_dir(
+    workspace: &Workspace,
+    config: &Config,
+) -> Result<PathBuf, anyhow::Error> {
+    let out_dir = config
+        .out_dir
+        .clone()
+        .unwrap_or_else(|| workspace.root().join("target"));
+    std::fs::create_dir_all(&out_dir)?;
+    Ok(out_dir)
+}
+
 pub fn compile_manifest(
     manifest_path: &Path,
     config: Config,
     emit_colors: DisplayColor,
 ) -> Result<bool, anyhow::Error> {
-    let (_package, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    let (_workspace, mut driver) = Driver::with_package_path(manifest_path, config)?;
 
     // Emit diagnostics. If one of the snippets is an error, abort gracefully.
     if driver.emit_diagnostics(&mut stderr(), emit_colors)? {
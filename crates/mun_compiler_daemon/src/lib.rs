@@ -2,28 +2,33 @@ use std::sync::mpsc::channel;
 use std::time::Duration;
 
 use mun_compiler::{compute_source_relative_path, is_source_file, Config, DisplayColor, Driver};
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use mun_project::Package;
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
 
 use std::io::stderr;
 use std::path::Path;
 use std::sync::Arc;
 
-/// Compiles and watches the package at the specified path. Recompiles changes that occur.
+/// Compiles and watches the package or workspace at the specified path. Recompiles changes that
+/// occur.
 pub fn compile_and_watch_manifest(
     manifest_path: &Path,
     config: Config,
     display_color: DisplayColor,
 ) -> Result<bool, anyhow::Error> {
     // Create the compiler driver
-    let (package, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    let (workspace, mut driver) = Driver::with_package_path(manifest_path, config.clone())?;
 
-    // Start watching the source directory
+    // Start watching the source directories of all members. Only the files of the root package are
+    // tracked by the driver, changes to other members require a new driver.
     let (watcher_tx, watcher_rx) = channel();
     let mut watcher: RecommendedWatcher = Watcher::new(watcher_tx, Duration::from_millis(10))?;
-    let source_directory = package.source_directory();
-
-    watcher.watch(&source_directory, RecursiveMode::Recursive)?;
-    println!("Watching: {}", source_directory.display());
+    let source_directory = workspace.root_package().map(Package::source_directory);
+    for member in workspace.members() {
+        let member_source_directory = member.source_directory();
+        watcher.watch(&member_source_directory, RecursiveMode::Recursive)?;
+        println!("Watching: {}", member_source_directory.display());
+    }
 
     // Emit all current errors, and write the assemblies if no errors occured
     if !driver.emit_diagnostics(&mut stderr(), display_color)? {
@@ -41,10 +46,26 @@ pub fn compile_and_watch_manifest(
     // Start watching filesystem events.
     while !should_quit.load(std::sync::atomic::Ordering::SeqCst) {
         if let Ok(event) = watcher_rx.recv_timeout(Duration::from_millis(1)) {
+            let source_directory = match &source_directory {
+                Some(source_directory) if is_root_package_event(&event, source_directory) => {
+                    source_directory
+                }
+                _ => {
+                    if is_source_event(&event) {
+                        log::info!("Reloading workspace");
+                        driver = Driver::with_package_path(manifest_path, config.clone())?.1;
+                        if !driver.emit_diagnostics(&mut stderr(), display_color)? {
+                            driver.write_all_assemblies(false)?;
+                        }
+                    }
+                    continue;
+                }
+            };
+
             use notify::DebouncedEvent::*;
             match event {
                 Write(ref path) if is_source_file(path) => {
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
+                    let relative_path = compute_source_relative_path(source_directory, path)?;
                     let file_contents = std::fs::read_to_string(path)?;
                     log::info!("Modifying {}", relative_path);
                     driver.update_file(relative_path, file_contents);
@@ -53,7 +74,7 @@ pub fn compile_and_watch_manifest(
                     }
                 }
                 Create(ref path) if is_source_file(path) => {
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
+                    let relative_path = compute_source_relative_path(source_directory, path)?;
                     let file_contents = std::fs::read_to_string(path)?;
                     log::info!("Creating {}", relative_path);
                     driver.add_file(relative_path, file_contents);
@@ -63,7 +84,7 @@ pub fn compile_and_watch_manifest(
                 }
                 Remove(ref path) if is_source_file(path) => {
                     // Simply remove the source file from the source root
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
+                    let relative_path = compute_source_relative_path(source_directory, path)?;
                     log::info!("Removing {}", relative_path);
                     // TODO: Remove assembly files if there are no files referencing it.
                     // let assembly_path = driver.assembly_output_path(driver.get_file_id_for_path(&relative_path).expect("cannot remove a file that was not part of the compilation in the first place"));
@@ -77,8 +98,8 @@ pub fn compile_and_watch_manifest(
                     // Renaming is done by changing the relative path of the original source file but
                     // not modifying any text. This ensures that most of the cache for the renamed file
                     // stays alive. This is effectively a rename of the file_id in the database.
-                    let from_relative_path = compute_source_relative_path(&source_directory, from)?;
-                    let to_relative_path = compute_source_relative_path(&source_directory, to)?;
+                    let from_relative_path = compute_source_relative_path(source_directory, from)?;
+                    let to_relative_path = compute_source_relative_path(source_directory, to)?;
 
                     log::info!("Renaming {} to {}", from_relative_path, to_relative_path,);
                     driver.rename(from_relative_path, to_relative_path);
@@ -93,3 +114,23 @@ pub fn compile_and_watch_manifest(
 
     Ok(true)
 }
+
+/// Returns true if the `event` concerns a source file
+fn is_source_event(event: &DebouncedEvent) -> bool {
+    use notify::DebouncedEvent::*;
+    match event {
+        Write(path) | Create(path) | Remove(path) => is_source_file(path),
+        Rename(from, to) => is_source_file(from) || is_source_file(to),
+        _ => false,
+    }
+}
+
+/// Returns true if the `event` only concerns files in the `source_directory` of the root package
+fn is_root_package_event(event: &DebouncedEvent, source_directory: &Path) -> bool {
+    use notify::DebouncedEvent::*;
+    match event {
+        Write(path) | Create(path) | Remove(path) => path.starts_with(source_directory),
+        Rename(from, to) => from.starts_with(source_directory) && to.starts_with(source_directory),
+        _ => true,
+    }
+}
@@ -1,25 +1,40 @@
+mod r#const;
+mod r#enum;
 mod function;
+mod r#impl;
 mod module;
 mod package;
 pub(crate) mod src;
+mod r#static;
 mod r#struct;
+mod r#trait;
 mod type_alias;
 
-use crate::{expr::BodySourceMap, HirDatabase, Name};
+use crate::{expr::BodySourceMap, FileId, HirDatabase, Name, Ty};
 use std::sync::Arc;
 
 pub use self::{
     function::Function,
     module::{Module, ModuleDef},
     package::Package,
+    r#const::Const,
+    r#enum::{Enum, EnumVariant, LocalEnumVariantId, VariantField},
+    r#impl::{AssocItem, Impl},
+    r#static::Static,
     r#struct::{Field, LocalFieldId, Struct, StructKind, StructMemoryKind},
+    r#trait::Trait,
     src::HasSource,
     type_alias::TypeAlias,
 };
 
 pub use self::{
     function::FunctionData,
+    r#const::ConstData,
+    r#enum::{EnumData, EnumVariantData},
+    r#impl::ImplData,
+    r#static::StaticData,
     r#struct::{FieldData, StructData},
+    r#trait::TraitData,
     type_alias::TypeAliasData,
 };
 
@@ -27,19 +42,33 @@ pub use self::{
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DefWithBody {
     Function(Function),
+    Const(Const),
+    Static(Static),
 }
-impl_froms!(DefWithBody: Function);
+impl_froms!(DefWithBody: Function, Const, Static);
 
 impl DefWithBody {
     pub fn module(self, db: &dyn HirDatabase) -> Module {
         match self {
             DefWithBody::Function(f) => f.module(db),
+            DefWithBody::Const(c) => c.module(db),
+            DefWithBody::Static(s) => s.module(db),
+        }
+    }
+
+    pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
+        match self {
+            DefWithBody::Function(f) => f.file_id(db),
+            DefWithBody::Const(c) => c.file_id(db),
+            DefWithBody::Static(s) => s.file_id(db),
         }
     }
 
     pub fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         match self {
             DefWithBody::Function(f) => f.body_source_map(db),
+            DefWithBody::Const(c) => c.body_source_map(db),
+            DefWithBody::Static(s) => s.body_source_map(db),
         }
     }
 }
@@ -76,3 +105,47 @@ impl DefWithStruct {
         }
     }
 }
+
+/// A struct or an enum variant; the definitions that can be constructed with a struct literal or
+/// destructured with a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VariantDef {
+    Struct(Struct),
+    EnumVariant(EnumVariant),
+}
+impl_froms!(VariantDef: Struct, EnumVariant);
+
+impl VariantDef {
+    /// Returns whether this is a record, tuple or unit variant
+    pub fn kind(self, db: &dyn HirDatabase) -> StructKind {
+        match self {
+            VariantDef::Struct(s) => s.data(db.upcast()).kind,
+            VariantDef::EnumVariant(v) => v.kind(db),
+        }
+    }
+
+    /// Returns the names of the fields of the variant, in declaration order.
+    pub fn field_names(self, db: &dyn HirDatabase) -> Vec<Name> {
+        match self {
+            VariantDef::Struct(s) => s.fields(db).into_iter().map(|f| f.name(db)).collect(),
+            VariantDef::EnumVariant(v) => v.fields(db).into_iter().map(|f| f.name(db)).collect(),
+        }
+    }
+
+    /// Returns the types of the fields of the variant, in declaration order.
+    pub fn field_types(self, db: &dyn HirDatabase) -> Vec<Ty> {
+        match self {
+            VariantDef::Struct(s) => s.fields(db).into_iter().map(|f| f.ty(db)).collect(),
+            VariantDef::EnumVariant(v) => v.fields(db).into_iter().map(|f| f.ty(db)).collect(),
+        }
+    }
+
+    /// Returns the type of a value of this variant. For an enum variant this is the type of the
+    /// parent enum.
+    pub fn ty(self, db: &dyn HirDatabase) -> Ty {
+        match self {
+            VariantDef::Struct(s) => s.ty(db),
+            VariantDef::EnumVariant(v) => v.parent_enum().ty(db),
+        }
+    }
+}
@@ -1,16 +1,20 @@
-use super::Module;
+use super::{Impl, Module, Trait};
+use crate::code_model::src::HasSource;
+use crate::diagnostics::UnresolvedTrait;
 use crate::expr::validator::ExprValidator;
 use crate::expr::BodySourceMap;
-use crate::ids::{FunctionId, Lookup};
+use crate::generics::GenericParams;
+use crate::ids::{FunctionId, ItemContainerId, Lookup};
 use crate::name_resolution::Namespace;
-use crate::resolve::HasResolver;
+use crate::resolve::{HasResolver, TypeNs};
 use crate::type_ref::{LocalTypeRefId, TypeRefBuilder, TypeRefMap, TypeRefSourceMap};
 use crate::visibility::RawVisibility;
 use crate::{
     Body, DefDatabase, DiagnosticSink, FileId, HasVisibility, HirDatabase, InferenceResult, Name,
-    Ty, Visibility,
+    Path, Ty, Visibility,
 };
-use mun_syntax::ast::TypeAscriptionOwner;
+use mun_syntax::ast::{TypeAscriptionOwner, TypeParamsOwner};
+use mun_syntax::{AstNode, SyntaxNodePtr};
 use std::iter::once;
 use std::sync::Arc;
 
@@ -28,12 +32,15 @@ impl From<FunctionId> for Function {
 #[derive(Debug, PartialEq, Eq)]
 pub struct FunctionData {
     name: Name,
+    generic_params: Arc<GenericParams>,
     params: Vec<LocalTypeRefId>,
     visibility: RawVisibility,
     ret_type: LocalTypeRefId,
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
     is_extern: bool,
+    has_self_param: bool,
+    has_body: bool,
 }
 
 impl FunctionData {
@@ -47,6 +54,18 @@ impl FunctionData {
 
         let mut params = Vec::new();
         if let Some(param_list) = src.param_list() {
+            if param_list.self_param().is_some() {
+                // The type of `self` is the type of the `impl` block or the `Self` type of the
+                // trait that contains the function. Outside of an `impl` block or a trait an error
+                // has already been reported by the item tree.
+                let type_ref = match loc.container {
+                    ItemContainerId::ImplId(_) | ItemContainerId::TraitId(_) => {
+                        type_ref_builder.self_type()
+                    }
+                    ItemContainerId::ModuleId(_) => type_ref_builder.error(),
+                };
+                params.push(type_ref);
+            }
             for param in param_list.params() {
                 let type_ref = type_ref_builder.alloc_from_node_opt(param.ascribed_type().as_ref());
                 params.push(type_ref);
@@ -61,13 +80,23 @@ impl FunctionData {
 
         let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
 
+        let generic_params = match loc.container {
+            ItemContainerId::TraitId(_) => GenericParams::from_trait_item_ast(&src),
+            ItemContainerId::ModuleId(_) | ItemContainerId::ImplId(_) => {
+                GenericParams::from_ast(&src)
+            }
+        };
+
         Arc::new(FunctionData {
             name: func.name.clone(),
+            generic_params: Arc::new(generic_params),
             params,
             ret_type,
             type_ref_map,
             type_ref_source_map,
             is_extern: func.is_extern,
+            has_self_param: func.has_self_param,
+            has_body: src.body().is_some(),
             visibility: item_tree[func.visibility].clone(),
         })
     }
@@ -76,6 +105,11 @@ impl FunctionData {
         &self.name
     }
 
+    /// Returns the type parameters of the function, e.g. `T` for `fn max<T>(a: T, b: T)`.
+    pub fn generic_params(&self) -> Arc<GenericParams> {
+        self.generic_params.clone()
+    }
+
     pub fn params(&self) -> &[LocalTypeRefId] {
         &self.params
     }
@@ -95,24 +129,68 @@ impl FunctionData {
     pub fn type_ref_map(&self) -> &TypeRefMap {
         &self.type_ref_map
     }
+
+    /// Returns whether the first parameter of the function is `self`. If so, the first entry of
+    /// `params` is the type of `self`.
+    pub fn has_self_param(&self) -> bool {
+        self.has_self_param
+    }
+
+    /// Returns whether the function has a body. Only extern functions and functions declared in a
+    /// trait can be defined without one.
+    pub fn has_body(&self) -> bool {
+        self.has_body
+    }
 }
 
 impl Function {
     pub fn module(self, db: &dyn HirDatabase) -> Module {
         Module {
-            id: self.id.lookup(db.upcast()).module,
+            id: self.id.lookup(db.upcast()).container.module(db.upcast()),
+        }
+    }
+
+    /// Returns the `impl` block that contains this function, if any.
+    pub fn parent_impl(self, db: &dyn HirDatabase) -> Option<Impl> {
+        match self.id.lookup(db.upcast()).container {
+            ItemContainerId::ImplId(id) => Some(id.into()),
+            ItemContainerId::ModuleId(_) | ItemContainerId::TraitId(_) => None,
+        }
+    }
+
+    /// Returns the trait in which this function is declared, if any. Functions that implement a
+    /// trait in an `impl` block are not declared in the trait.
+    pub fn parent_trait(self, db: &dyn HirDatabase) -> Option<Trait> {
+        match self.id.lookup(db.upcast()).container {
+            ItemContainerId::TraitId(id) => Some(id.into()),
+            ItemContainerId::ModuleId(_) | ItemContainerId::ImplId(_) => None,
         }
     }
 
     /// Returns the full name of the function including all module specifiers (e.g: `foo::bar`).
+    /// For an associated function the name of the type is included as well (e.g:
+    /// `foo::Bar::new`). For a function declared in a trait the name of the trait is included
+    /// (e.g: `foo::Update::update`) and for a function that implements a trait both the type
+    /// and the trait are included (e.g: `foo::<Bar as Update>::update`).
     pub fn full_name(self, db: &dyn HirDatabase) -> String {
+        let container = if let Some(impl_def) = self.parent_impl(db) {
+            let self_ty = impl_def.self_ty(db).display(db).to_string();
+            match impl_def.target_trait(db) {
+                Some(target_trait) => Some(format!("<{} as {}>", self_ty, target_trait.name(db))),
+                None => Some(self_ty),
+            }
+        } else {
+            self.parent_trait(db)
+                .map(|trait_def| trait_def.name(db).to_string())
+        };
         itertools::Itertools::intersperse(
             self.module(db)
                 .path_to_root(db)
                 .into_iter()
                 .filter_map(|module| module.name(db))
-                .chain(once(self.name(db)))
-                .map(|name| name.to_string()),
+                .map(|name| name.to_string())
+                .chain(container)
+                .chain(once(self.name(db).to_string())),
             String::from("::"),
         )
         .collect()
@@ -153,6 +231,56 @@ impl Function {
         db.fn_data(self.id).is_extern
     }
 
+    /// Returns whether the function takes `self` as its first parameter, which makes it callable
+    /// with method call syntax (e.g. `foo.bar()`).
+    pub fn has_self_param(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).has_self_param
+    }
+
+    /// Returns whether the function has a body, see [`FunctionData::has_body`].
+    pub fn has_body(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).has_body
+    }
+
+    /// Returns whether the function has type parameters. A generic function is only compiled
+    /// for each set of type arguments it is called with. Functions declared in a trait are always
+    /// generic over the `Self` type of the trait.
+    pub fn is_generic(self, db: &dyn HirDatabase) -> bool {
+        !db.fn_data(self.id).generic_params.is_empty()
+    }
+
+    /// Returns the traits by which the type parameter at `idx` is bounded. The implicit `Self` type
+    /// parameter of a function declared in a trait is bounded by the trait itself.
+    pub fn type_param_bounds(self, db: &dyn HirDatabase, idx: u32) -> Vec<Trait> {
+        let data = self.data(db.upcast());
+        let parent_trait = if idx == 0 {
+            self.parent_trait(db)
+        } else {
+            None
+        };
+        parent_trait
+            .into_iter()
+            .chain(
+                data.generic_params()
+                    .bounds(idx)
+                    .iter()
+                    .filter_map(|path| self.resolve_trait(db, path)),
+            )
+            .collect()
+    }
+
+    /// Resolves the trait bound `path` in the scope of this function
+    fn resolve_trait(self, db: &dyn HirDatabase, path: &Path) -> Option<Trait> {
+        match self
+            .id
+            .resolver(db.upcast())
+            .resolve_path_as_type_fully(db.upcast(), path)?
+        {
+            (TypeNs::TraitId(id), _) => Some(id.into()),
+            _ => None,
+        }
+    }
+
     pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         db.body_with_source_map(self.id.into()).1
     }
@@ -161,14 +289,45 @@ impl Function {
         let body = self.body(db);
         body.add_diagnostics(db, self.into(), sink);
         let infer = self.infer(db);
-        infer.add_diagnostics(db, self, sink);
+        infer.add_diagnostics(db, self.into(), sink);
         let validator = ExprValidator::new(self, db);
         validator.validate_body(sink);
+
+        // Report all trait bounds that cannot be resolved
+        let src = self.source(db.upcast());
+        let bounds = src
+            .value
+            .type_param_list()
+            .into_iter()
+            .flat_map(|list| list.type_params())
+            .flat_map(|param| param.type_bound_list())
+            .flat_map(|list| list.bounds())
+            .filter_map(|bound| bound.path());
+        for bound in bounds {
+            let resolved = Path::from_ast(bound.clone())
+                .and_then(|path| self.resolve_trait(db, &path))
+                .is_some();
+            if !resolved {
+                sink.push(UnresolvedTrait {
+                    file: src.file_id,
+                    path: SyntaxNodePtr::new(bound.syntax()),
+                })
+            }
+        }
     }
 }
 
 impl HasVisibility for Function {
+    /// The functions of a trait, and the functions that implement them, are as visible as the
+    /// trait itself.
     fn visibility(&self, db: &dyn HirDatabase) -> Visibility {
+        let parent_trait = self.parent_trait(db).or_else(|| {
+            self.parent_impl(db)
+                .and_then(|impl_def| impl_def.target_trait(db))
+        });
+        if let Some(parent_trait) = parent_trait {
+            return parent_trait.visibility(db);
+        }
         self.data(db.upcast())
             .visibility
             .resolve(db.upcast(), &self.id.resolver(db.upcast()))
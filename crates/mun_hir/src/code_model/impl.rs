@@ -0,0 +1,270 @@
+use super::{Function, Module, Trait};
+use crate::{
+    code_model::src::HasSource,
+    diagnostics::{
+        MethodNotMemberOfTrait, MissingTraitItems, TraitMethodSignatureMismatch, UnresolvedTrait,
+    },
+    ids::{AssocItemId, FunctionLoc, ImplId, Intern, ItemContainerId, Lookup},
+    item_tree::{AssociatedItem, ItemTreeId},
+    resolve::{HasResolver, TypeNs},
+    ty::{lower::LowerBatchResult, FnSig, Substitution},
+    type_ref::{LocalTypeRefId, TypeRefBuilder, TypeRefMap, TypeRefSourceMap},
+    DefDatabase, DiagnosticSink, FileId, HirDatabase, HirDisplay, Name, Path, Ty, TyKind,
+};
+use mun_syntax::{AstNode, SyntaxNodePtr};
+use std::sync::Arc;
+
+/// An `impl` block that defines associated functions for a type. An `impl` block can also
+/// implement a trait for the type.
+/// ```mun
+/// impl Foo {
+///     fn new() -> Self { Foo { a: 0 } }
+///     fn get(self) -> i32 { self.a }
+/// }
+///
+/// impl Update for Foo {
+///     fn update(self, dt: f32) {}
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Impl {
+    pub(crate) id: ImplId,
+}
+
+impl From<ImplId> for Impl {
+    fn from(id: ImplId) -> Self {
+        Impl { id }
+    }
+}
+
+impl Impl {
+    pub fn module(self, db: &dyn HirDatabase) -> Module {
+        Module {
+            id: self.id.lookup(db.upcast()).module,
+        }
+    }
+
+    pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
+        self.id.lookup(db.upcast()).id.file_id
+    }
+
+    pub fn data(self, db: &dyn DefDatabase) -> Arc<ImplData> {
+        db.impl_data(self.id)
+    }
+
+    /// Returns the type for which this `impl` block defines items
+    pub fn self_ty(self, db: &dyn HirDatabase) -> Ty {
+        let data = self.data(db.upcast());
+        self.lower(db)[data.self_ty].clone()
+    }
+
+    /// Returns the trait that is implemented by this `impl` block, if any.
+    pub fn target_trait(self, db: &dyn HirDatabase) -> Option<Trait> {
+        let path = self.data(db.upcast()).target_trait.clone()?;
+        match self
+            .id
+            .resolver(db.upcast())
+            .resolve_path_as_type_fully(db.upcast(), &path)?
+        {
+            (TypeNs::TraitId(id), _) => Some(id.into()),
+            _ => None,
+        }
+    }
+
+    /// Returns the function with the specified `name` that is defined in this `impl` block, if
+    /// any.
+    pub fn function(self, db: &dyn HirDatabase, name: &Name) -> Option<Function> {
+        self.items(db)
+            .into_iter()
+            .map(|item| match item {
+                AssocItem::Function(f) => f,
+            })
+            .find(|f| f.name(db) == *name)
+    }
+
+    /// Returns all the items defined in this `impl` block, in declaration order.
+    pub fn items(self, db: &dyn HirDatabase) -> Vec<AssocItem> {
+        self.data(db.upcast())
+            .items
+            .iter()
+            .map(|item| match *item {
+                AssocItemId::FunctionId(id) => AssocItem::Function(id.into()),
+            })
+            .collect()
+    }
+
+    pub fn lower(self, db: &dyn HirDatabase) -> Arc<LowerBatchResult> {
+        db.lower_impl(self)
+    }
+
+    pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink) {
+        let data = self.data(db.upcast());
+        let lower = self.lower(db);
+        lower.add_diagnostics(db, self.file_id(db), data.type_ref_source_map(), sink);
+
+        for item in self.items(db) {
+            match item {
+                AssocItem::Function(f) => f.diagnostics(db, sink),
+            }
+        }
+
+        if data.target_trait.is_some() {
+            self.trait_diagnostics(db, sink);
+        }
+    }
+
+    /// Validates that the items of this `impl` block match the declarations of the trait it
+    /// implements.
+    fn trait_diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink) {
+        let src = self.source(db.upcast());
+        let trait_path = match src.value.path() {
+            Some(path) => SyntaxNodePtr::new(path.syntax()),
+            None => return,
+        };
+
+        let target_trait = match self.target_trait(db) {
+            Some(target_trait) => target_trait,
+            None => {
+                sink.push(UnresolvedTrait {
+                    file: src.file_id,
+                    path: trait_path,
+                });
+                return;
+            }
+        };
+
+        // Every function of the `impl` block must be declared in the trait with the same signature
+        let self_ty = self.self_ty(db);
+        for item in self.items(db) {
+            let AssocItem::Function(func) = item;
+            let name = func.name(db);
+            let func_src = func.source(db.upcast());
+            let trait_func = match target_trait.function(db, &name) {
+                Some(trait_func) => trait_func,
+                None => {
+                    sink.push(MethodNotMemberOfTrait {
+                        file: func_src.file_id,
+                        func: SyntaxNodePtr::new(func_src.value.syntax()),
+                        method_name: name,
+                        trait_name: target_trait.name(db),
+                    });
+                    continue;
+                }
+            };
+
+            // The type parameters of the trait function, except for `Self`, must correspond to
+            // the type parameters of the function in the `impl` block.
+            let func_params = func.data(db.upcast()).generic_params().bound_vars();
+            let trait_params = trait_func.data(db.upcast()).generic_params().bound_vars();
+            let expected = trait_method_sig(db, trait_func, &self_ty, &func_params);
+            let found = func.ty(db).callable_sig(db);
+            let signatures_match = func.has_self_param(db) == trait_func.has_self_param(db)
+                && trait_params.len() == func_params.len() + 1
+                && Some(expected) == found;
+            if !signatures_match && !self_ty.is_unknown() {
+                let expected = trait_method_sig(db, trait_func, &self_ty, &trait_params[1..]);
+                sink.push(TraitMethodSignatureMismatch {
+                    file: func_src.file_id,
+                    func: SyntaxNodePtr::new(func_src.value.syntax()),
+                    method_name: name,
+                    expected: TyKind::FnPtr(expected).intern().display(db).to_string(),
+                    found: found
+                        .map(|sig| TyKind::FnPtr(sig).intern().display(db).to_string())
+                        .unwrap_or_default(),
+                })
+            }
+        }
+
+        // Every function of the trait that does not have a default implementation must be defined
+        let missing: Vec<_> = target_trait
+            .items(db)
+            .into_iter()
+            .map(|item| match item {
+                AssocItem::Function(f) => f,
+            })
+            .filter(|f| !f.has_body(db) && self.function(db, &f.name(db)).is_none())
+            .map(|f| f.name(db))
+            .collect();
+        if !missing.is_empty() {
+            sink.push(MissingTraitItems {
+                file: src.file_id,
+                path: trait_path,
+                trait_name: target_trait.name(db),
+                missing,
+            })
+        }
+    }
+}
+
+/// Returns the signature of the function `trait_func` declared in a trait, as it must be defined
+/// in an `impl` block for the type `self_ty`. The remaining type parameters of `trait_func` are
+/// replaced by `type_params`.
+fn trait_method_sig(
+    db: &dyn HirDatabase,
+    trait_func: Function,
+    self_ty: &Ty,
+    type_params: &[Ty],
+) -> FnSig {
+    let substs: Substitution = std::iter::once(self_ty.clone())
+        .chain(type_params.iter().cloned())
+        .collect();
+    db.callable_sig(trait_func.into()).subst(&substs)
+}
+
+/// An item defined in an `impl` block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssocItem {
+    Function(Function),
+}
+impl_froms!(AssocItem: Function);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImplData {
+    pub items: Vec<AssocItemId>,
+    pub self_ty: LocalTypeRefId,
+    pub target_trait: Option<Path>,
+    type_ref_map: TypeRefMap,
+    type_ref_source_map: TypeRefSourceMap,
+}
+
+impl ImplData {
+    pub(crate) fn impl_data_query(db: &dyn DefDatabase, id: ImplId) -> Arc<ImplData> {
+        let loc = id.lookup(db);
+        let item_tree = db.item_tree(loc.id.file_id);
+        let impl_def = &item_tree[loc.id.value];
+        let src = item_tree.source(db, loc.id.value);
+
+        let mut type_ref_builder = TypeRefBuilder::default();
+        let self_ty = type_ref_builder.alloc_from_node_opt(src.type_ref().as_ref());
+
+        let items = impl_def
+            .items
+            .iter()
+            .map(|item| match *item {
+                AssociatedItem::Function(func) => FunctionLoc {
+                    container: ItemContainerId::ImplId(id),
+                    id: ItemTreeId::new(loc.id.file_id, func),
+                }
+                .intern(db)
+                .into(),
+            })
+            .collect();
+
+        let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
+        Arc::new(ImplData {
+            items,
+            self_ty,
+            target_trait: impl_def.target_trait.clone(),
+            type_ref_map,
+            type_ref_source_map,
+        })
+    }
+
+    pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
+        &self.type_ref_source_map
+    }
+
+    pub fn type_ref_map(&self) -> &TypeRefMap {
+        &self.type_ref_map
+    }
+}
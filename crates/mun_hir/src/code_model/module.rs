@@ -1,4 +1,4 @@
-use super::{Function, Package, Struct, TypeAlias};
+use super::{Const, Enum, Function, Impl, Package, Static, Struct, Trait, TypeAlias};
 use crate::ids::{ItemDefinitionId, ModuleId};
 use crate::primitive_type::PrimitiveType;
 use crate::{DiagnosticSink, FileId, HirDatabase, Name};
@@ -35,10 +35,14 @@ impl Module {
         })
     }
 
-    /// Returns the name of this module or None if this is the root module
+    /// Returns the name of this module or None if this is the root module of an unnamed package.
+    /// The root module of a named package - i.e. a dependency - is named after its package.
     pub fn name(self, db: &dyn HirDatabase) -> Option<Name> {
         let module_tree = db.module_tree(self.id.package);
-        let parent = module_tree[self.id.local_id].parent?;
+        let parent = match module_tree[self.id.local_id].parent {
+            Some(parent) => parent,
+            None => return db.packages()[self.id.package].name.clone(),
+        };
         module_tree[parent]
             .children
             .iter()
@@ -65,6 +69,15 @@ impl Module {
             .collect()
     }
 
+    /// Returns all the `impl` blocks defined in this module.
+    pub fn impls(self, db: &dyn HirDatabase) -> Vec<Impl> {
+        let package_defs = db.package_defs(self.id.package);
+        package_defs.modules[self.id.local_id]
+            .impls()
+            .map(Impl::from)
+            .collect()
+    }
+
     /// Iterate over all diagnostics from this `Module` by placing them in the `sink`
     pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink) {
         // Add diagnostics from the package definitions
@@ -84,10 +97,23 @@ impl Module {
             match decl {
                 ModuleDef::Function(f) => f.diagnostics(db, sink),
                 ModuleDef::Struct(s) => s.diagnostics(db, sink),
+                ModuleDef::Enum(e) => e.diagnostics(db, sink),
                 ModuleDef::TypeAlias(t) => t.diagnostics(db, sink),
+                ModuleDef::Const(c) => c.diagnostics(db, sink),
+                ModuleDef::Static(s) => s.diagnostics(db, sink),
+                ModuleDef::Trait(t) => t.diagnostics(db, sink),
                 _ => (),
             }
         }
+
+        // Add diagnostics from the `impl` blocks
+        for impl_def in self.impls(db) {
+            impl_def.diagnostics(db, sink);
+        }
+        db.inherent_impls_in_package(self.id.package)
+            .add_module_diagnostics(db, self.id, sink);
+        db.trait_impls_in_package(self.id.package)
+            .add_module_diagnostics(db, self.id, sink);
     }
 
     /// Returns all the child modules of this module
@@ -135,7 +161,11 @@ pub enum ModuleDef {
     Function(Function),
     PrimitiveType(PrimitiveType),
     Struct(Struct),
+    Enum(Enum),
     TypeAlias(TypeAlias),
+    Const(Const),
+    Static(Static),
+    Trait(Trait),
 }
 
 impl From<Function> for ModuleDef {
@@ -156,12 +186,36 @@ impl From<Struct> for ModuleDef {
     }
 }
 
+impl From<Enum> for ModuleDef {
+    fn from(t: Enum) -> Self {
+        ModuleDef::Enum(t)
+    }
+}
+
 impl From<TypeAlias> for ModuleDef {
     fn from(t: TypeAlias) -> Self {
         ModuleDef::TypeAlias(t)
     }
 }
 
+impl From<Const> for ModuleDef {
+    fn from(t: Const) -> Self {
+        ModuleDef::Const(t)
+    }
+}
+
+impl From<Static> for ModuleDef {
+    fn from(t: Static) -> Self {
+        ModuleDef::Static(t)
+    }
+}
+
+impl From<Trait> for ModuleDef {
+    fn from(t: Trait) -> Self {
+        ModuleDef::Trait(t)
+    }
+}
+
 impl From<Module> for ModuleDef {
     fn from(m: Module) -> Self {
         ModuleDef::Module(m)
@@ -174,7 +228,12 @@ impl From<ItemDefinitionId> for ModuleDef {
             ItemDefinitionId::ModuleId(id) => Module { id }.into(),
             ItemDefinitionId::FunctionId(id) => Function { id }.into(),
             ItemDefinitionId::StructId(id) => Struct { id }.into(),
+            ItemDefinitionId::EnumId(id) => Enum { id }.into(),
+            ItemDefinitionId::EnumVariantId(id) => Enum { id: id.parent }.into(),
             ItemDefinitionId::TypeAliasId(id) => TypeAlias { id }.into(),
+            ItemDefinitionId::ConstId(id) => Const { id }.into(),
+            ItemDefinitionId::StaticId(id) => Static { id }.into(),
+            ItemDefinitionId::TraitId(id) => Trait { id }.into(),
             ItemDefinitionId::PrimitiveType(id) => id.into(),
         }
     }
@@ -1,4 +1,6 @@
-use crate::code_model::{Field, Function, Struct, TypeAlias};
+use crate::code_model::{
+    Const, Enum, EnumVariant, Field, Function, Impl, Static, Struct, Trait, TypeAlias,
+};
 use crate::ids::{AssocItemLoc, Lookup};
 use crate::in_file::InFile;
 use crate::item_tree::{ItemTreeId, ItemTreeNode};
@@ -49,7 +51,7 @@ impl<N: ItemTreeNode> HasSource for AssocItemLoc<N> {
 impl HasSource for Function {
     type Ast = ast::FunctionDef;
     fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
-        self.id.lookup(db).source(db)
+        self.id.lookup(db).id.source(db)
     }
 }
 
@@ -60,6 +62,36 @@ impl HasSource for Struct {
     }
 }
 
+impl HasSource for Enum {
+    type Ast = ast::EnumDef;
+    fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
+        self.id.lookup(db).source(db)
+    }
+}
+
+impl HasSource for EnumVariant {
+    type Ast = ast::EnumVariant;
+
+    fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
+        let src = self.parent.source(db);
+        let file_id = src.file_id;
+        let variant_sources = src
+            .value
+            .variant_list()
+            .map(|list| list.variants().collect())
+            .unwrap_or_else(Vec::new);
+
+        let ast = variant_sources
+            .into_iter()
+            .zip(self.parent.data(db).variants.iter())
+            .find(|(_syntax, (id, _))| *id == self.id)
+            .unwrap()
+            .0;
+
+        InFile::new(file_id, ast)
+    }
+}
+
 impl HasSource for Field {
     type Ast = ast::RecordFieldDef;
 
@@ -83,9 +115,37 @@ impl HasSource for Field {
     }
 }
 
+impl HasSource for Impl {
+    type Ast = ast::ImplDef;
+    fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
+        self.id.lookup(db).source(db)
+    }
+}
+
+impl HasSource for Trait {
+    type Ast = ast::TraitDef;
+    fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
+        self.id.lookup(db).source(db)
+    }
+}
+
 impl HasSource for TypeAlias {
     type Ast = ast::TypeAliasDef;
     fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
         self.id.lookup(db).source(db)
     }
 }
+
+impl HasSource for Const {
+    type Ast = ast::ConstDef;
+    fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
+        self.id.lookup(db).source(db)
+    }
+}
+
+impl HasSource for Static {
+    type Ast = ast::StaticDef;
+    fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
+        self.id.lookup(db).source(db)
+    }
+}
@@ -0,0 +1,138 @@
+use super::Module;
+use crate::consteval::{self, ConstValue};
+use crate::expr::BodySourceMap;
+use crate::ids::{Lookup, StaticId};
+use crate::resolve::HasResolver;
+use crate::type_ref::{LocalTypeRefId, TypeRefBuilder, TypeRefMap, TypeRefSourceMap};
+use crate::visibility::RawVisibility;
+use crate::{
+    Body, DefDatabase, DiagnosticSink, FileId, HasVisibility, HirDatabase, InferenceResult, Name,
+    Ty, Visibility,
+};
+use mun_syntax::ast::TypeAscriptionOwner;
+use std::iter::once;
+use std::sync::Arc;
+
+/// A global variable (e.g. `static FOO: i32 = 5;`). A static refers to a single memory location
+/// that is stored in the assembly and that can be read and written by both Mun and the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Static {
+    pub(crate) id: StaticId,
+}
+
+impl From<StaticId> for Static {
+    fn from(id: StaticId) -> Self {
+        Static { id }
+    }
+}
+
+impl Static {
+    pub fn module(self, db: &dyn HirDatabase) -> Module {
+        Module {
+            id: self.id.lookup(db.upcast()).module,
+        }
+    }
+
+    pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
+        self.id.lookup(db.upcast()).id.file_id
+    }
+
+    pub fn data(self, db: &dyn DefDatabase) -> Arc<StaticData> {
+        db.static_data(self.id)
+    }
+
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        self.data(db.upcast()).name.clone()
+    }
+
+    /// Returns the full name of the static including all module specifiers (e.g: `foo::BAR`).
+    pub fn full_name(self, db: &dyn HirDatabase) -> String {
+        itertools::Itertools::intersperse(
+            self.module(db)
+                .path_to_root(db)
+                .into_iter()
+                .filter_map(|module| module.name(db))
+                .map(|name| name.to_string())
+                .chain(once(self.name(db).to_string())),
+            String::from("::"),
+        )
+        .collect()
+    }
+
+    /// Returns the declared type of the static.
+    pub fn ty(self, db: &dyn HirDatabase) -> Ty {
+        let resolver = self.id.resolver(db.upcast());
+        let data = self.data(db.upcast());
+        Ty::from_hir(db, &resolver, &data.type_ref_map, data.type_ref_id).ty
+    }
+
+    pub fn body(self, db: &dyn HirDatabase) -> Arc<Body> {
+        db.body(self.id.into())
+    }
+
+    pub fn infer(self, db: &dyn HirDatabase) -> Arc<InferenceResult> {
+        db.infer(self.id.into())
+    }
+
+    /// Returns the value the static is initialized with or `None` if its initializer could not be
+    /// evaluated at compile time.
+    pub fn initial_value(self, db: &dyn HirDatabase) -> Option<ConstValue> {
+        consteval::eval(db, self.id.into()).ok()
+    }
+
+    pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
+        db.body_with_source_map(self.id.into()).1
+    }
+
+    pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink) {
+        let body = self.body(db);
+        body.add_diagnostics(db, self.into(), sink);
+        let infer = self.infer(db);
+        infer.add_diagnostics(db, self.into(), sink);
+        consteval::add_diagnostics(db, self.into(), sink);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StaticData {
+    pub name: Name,
+    pub visibility: RawVisibility,
+    pub type_ref_id: LocalTypeRefId,
+    type_ref_map: TypeRefMap,
+    type_ref_source_map: TypeRefSourceMap,
+}
+
+impl StaticData {
+    pub(crate) fn static_data_query(db: &dyn DefDatabase, id: StaticId) -> Arc<StaticData> {
+        let loc = id.lookup(db);
+        let item_tree = db.item_tree(loc.id.file_id);
+        let static_def = &item_tree[loc.id.value];
+        let src = item_tree.source(db, loc.id.value);
+        let mut type_ref_builder = TypeRefBuilder::default();
+        let type_ref_id = type_ref_builder.alloc_from_node_opt(src.ascribed_type().as_ref());
+        let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
+        Arc::new(StaticData {
+            name: static_def.name.clone(),
+            visibility: item_tree[static_def.visibility].clone(),
+            type_ref_id,
+            type_ref_map,
+            type_ref_source_map,
+        })
+    }
+
+    pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
+        &self.type_ref_source_map
+    }
+
+    pub fn type_ref_map(&self) -> &TypeRefMap {
+        &self.type_ref_map
+    }
+}
+
+impl HasVisibility for Static {
+    fn visibility(&self, db: &dyn HirDatabase) -> Visibility {
+        self.data(db.upcast())
+            .visibility
+            .resolve(db.upcast(), &self.id.resolver(db.upcast()))
+    }
+}
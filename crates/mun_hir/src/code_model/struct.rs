@@ -1,6 +1,7 @@
 use super::Module;
 use crate::{
     arena::{Arena, Idx},
+    generics::GenericParams,
     ids::{Lookup, StructId},
     name::AsName,
     name_resolution::Namespace,
@@ -112,6 +113,11 @@ impl Struct {
             .map(|(id, _)| Field { parent: self, id })
     }
 
+    /// Returns whether the struct has type parameters.
+    pub fn is_generic(self, db: &dyn HirDatabase) -> bool {
+        !self.data(db.upcast()).generic_params.is_empty()
+    }
+
     pub fn ty(self, db: &dyn HirDatabase) -> Ty {
         // TODO: Add detection of cyclick types
         db.type_for_def(self.into(), Namespace::Types).0
@@ -171,6 +177,7 @@ pub type LocalFieldId = Idx<FieldData>;
 pub struct StructData {
     pub name: Name,
     pub visibility: RawVisibility,
+    pub generic_params: Arc<GenericParams>,
     pub fields: Arena<FieldData>,
     pub kind: StructKind,
     pub memory_kind: StructMemoryKind,
@@ -222,6 +229,7 @@ impl StructData {
         Arc::new(StructData {
             name: strukt.name.clone(),
             visibility,
+            generic_params: Arc::new(GenericParams::from_ast(&src)),
             fields,
             kind,
             memory_kind,
@@ -230,6 +238,11 @@ impl StructData {
         })
     }
 
+    /// Returns the type parameters of the struct, e.g. `A` and `B` for `struct Pair<A, B>`.
+    pub fn generic_params(&self) -> Arc<GenericParams> {
+        self.generic_params.clone()
+    }
+
     pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
         &self.type_ref_source_map
     }
@@ -0,0 +1,132 @@
+use super::{AssocItem, Function, Module};
+use crate::{
+    ids::{AssocItemId, FunctionLoc, Intern, ItemContainerId, Lookup, TraitId},
+    item_tree::{AssociatedItem, ItemTreeId},
+    resolve::HasResolver,
+    visibility::RawVisibility,
+    DefDatabase, DiagnosticSink, FileId, HasVisibility, HirDatabase, Name, Visibility,
+};
+use std::{iter::once, sync::Arc};
+
+/// A trait that declares functions which can be implemented for a type. A function that is
+/// declared with a body provides a default implementation.
+/// ```mun
+/// trait Update {
+///     fn update(self, dt: f32);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trait {
+    pub(crate) id: TraitId,
+}
+
+impl From<TraitId> for Trait {
+    fn from(id: TraitId) -> Self {
+        Trait { id }
+    }
+}
+
+impl Trait {
+    pub fn module(self, db: &dyn HirDatabase) -> Module {
+        Module {
+            id: self.id.lookup(db.upcast()).module,
+        }
+    }
+
+    pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
+        self.id.lookup(db.upcast()).id.file_id
+    }
+
+    pub fn data(self, db: &dyn DefDatabase) -> Arc<TraitData> {
+        db.trait_data(self.id)
+    }
+
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        self.data(db.upcast()).name.clone()
+    }
+
+    /// Returns the full name of the trait including all module specifiers (e.g: `foo::Update`).
+    pub fn full_name(self, db: &dyn HirDatabase) -> String {
+        itertools::Itertools::intersperse(
+            self.module(db)
+                .path_to_root(db)
+                .into_iter()
+                .filter_map(|module| module.name(db))
+                .map(|name| name.to_string())
+                .chain(once(self.name(db).to_string())),
+            String::from("::"),
+        )
+        .collect()
+    }
+
+    /// Returns all the items declared in this trait, in declaration order.
+    pub fn items(self, db: &dyn HirDatabase) -> Vec<AssocItem> {
+        self.data(db.upcast())
+            .items
+            .iter()
+            .map(|item| match *item {
+                AssocItemId::FunctionId(id) => AssocItem::Function(id.into()),
+            })
+            .collect()
+    }
+
+    /// Returns the function with the specified `name` that is declared in this trait, if any.
+    pub fn function(self, db: &dyn HirDatabase, name: &Name) -> Option<Function> {
+        self.items(db)
+            .into_iter()
+            .map(|item| match item {
+                AssocItem::Function(f) => f,
+            })
+            .find(|f| f.name(db) == *name)
+    }
+
+    pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink) {
+        for item in self.items(db) {
+            match item {
+                AssocItem::Function(f) => f.diagnostics(db, sink),
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TraitData {
+    pub name: Name,
+    pub visibility: RawVisibility,
+    pub items: Vec<AssocItemId>,
+}
+
+impl TraitData {
+    pub(crate) fn trait_data_query(db: &dyn DefDatabase, id: TraitId) -> Arc<TraitData> {
+        let loc = id.lookup(db);
+        let item_tree = db.item_tree(loc.id.file_id);
+        let trait_def = &item_tree[loc.id.value];
+
+        let items = trait_def
+            .items
+            .iter()
+            .map(|item| match *item {
+                AssociatedItem::Function(func) => FunctionLoc {
+                    container: ItemContainerId::TraitId(id),
+                    id: ItemTreeId::new(loc.id.file_id, func),
+                }
+                .intern(db)
+                .into(),
+            })
+            .collect();
+
+        Arc::new(TraitData {
+            name: trait_def.name.clone(),
+            visibility: item_tree[trait_def.visibility].clone(),
+            items,
+        })
+    }
+}
+
+impl HasVisibility for Trait {
+    fn visibility(&self, db: &dyn HirDatabase) -> Visibility {
+        self.data(db.upcast())
+            .visibility
+            .resolve(db.upcast(), &self.id.resolver(db.upcast()))
+    }
+}
@@ -0,0 +1,374 @@
+//! The values of constants and the initial values of statics are evaluated at compile time. Only
+//! literals of primitive types, references to other constants and operators applied to those are
+//! allowed in their initializers.
+//!
+//! Integers are evaluated as `i128`. This means that values of type `u128` that do not fit in an
+//! `i128` cannot be computed at compile time.
+
+use crate::{
+    code_model::DefWithBody,
+    diagnostics::{ConstEvalFailed, LargeStaticType, NonConstExpr, NonPrimitiveGlobalType},
+    expr::{ArithOp, BinaryOp, CmpOp, Expr, ExprId, Literal, LogicOp, Ordering, UnaryOp},
+    ids::DefWithBodyId,
+    resolve::{resolver_for_expr, ValueNs},
+    ty::{FloatTy, IntTy, ResolveBitness, Ty, TyKind},
+    Body, DiagnosticSink, FloatBitness, HirDatabase, InferenceResult, IntBitness,
+};
+use std::{cmp, convert::TryFrom};
+
+/// The value of an expression that was evaluated at compile time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstValue {
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+}
+
+/// An error that occurred while evaluating the initializer of a `const` or `static`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConstEvalError {
+    /// The expression cannot be evaluated at compile time
+    NotConst(ExprId),
+    /// The value of the expression does not fit in its type
+    Overflow(ExprId),
+    /// The right hand side of a division or remainder is zero
+    DivideByZero(ExprId),
+    /// The expression refers to a constant that (indirectly) refers to itself
+    Cycle(ExprId),
+    /// The initializer contains errors that are reported elsewhere
+    Invalid,
+}
+
+/// Evaluates the initializer of the `const` or `static` with the specified `def`.
+pub(crate) fn eval(db: &dyn HirDatabase, def: DefWithBodyId) -> Result<ConstValue, ConstEvalError> {
+    ConstEvaluator {
+        db,
+        stack: Vec::new(),
+    }
+    .eval_body(def)
+}
+
+/// Adds diagnostics for the type and the initializer of the `const` or `static` `owner`.
+pub(crate) fn add_diagnostics(db: &dyn HirDatabase, owner: DefWithBody, sink: &mut DiagnosticSink) {
+    let (def, ty): (DefWithBodyId, Ty) = match owner {
+        DefWithBody::Const(c) => (c.id.into(), c.ty(db)),
+        DefWithBody::Static(s) => (s.id.into(), s.ty(db)),
+        DefWithBody::Function(_) => return,
+    };
+
+    let file = owner.file_id(db);
+    let (body, source_map) = db.body_with_source_map(def);
+    match ty.interned() {
+        // Statics are accessed atomically, which is not supported for 128-bit values
+        TyKind::Int(IntTy {
+            bitness: IntBitness::X128,
+            ..
+        }) if matches!(owner, DefWithBody::Static(_)) => {
+            if let Some(type_ref) = source_map.type_ref_syntax(body.ret_type()) {
+                sink.push(LargeStaticType { file, type_ref });
+            }
+            return;
+        }
+        TyKind::Int(_) | TyKind::Float(_) | TyKind::Bool | TyKind::Unknown => {}
+        _ => {
+            if let Some(type_ref) = source_map.type_ref_syntax(body.ret_type()) {
+                sink.push(NonPrimitiveGlobalType { file, type_ref });
+            }
+            return;
+        }
+    }
+
+    let (expr, reason) = match eval(db, def) {
+        Ok(_) | Err(ConstEvalError::Invalid) => return,
+        Err(ConstEvalError::NotConst(expr)) => (expr, None),
+        Err(ConstEvalError::Overflow(expr)) => (
+            expr,
+            Some("attempt to compute a value that overflows its type"),
+        ),
+        Err(ConstEvalError::DivideByZero(expr)) => (expr, Some("attempt to divide by zero")),
+        Err(ConstEvalError::Cycle(expr)) => {
+            (expr, Some("cycle detected in the value of a constant"))
+        }
+    };
+
+    let expr = match source_map.expr_syntax(expr) {
+        Some(src) => src
+            .value
+            .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()),
+        None => return,
+    };
+    match reason {
+        None => sink.push(NonConstExpr { file, expr }),
+        Some(reason) => sink.push(ConstEvalFailed { file, expr, reason }),
+    }
+}
+
+struct ConstEvaluator<'a> {
+    db: &'a dyn HirDatabase,
+
+    /// The initializers that are currently being evaluated, used to detect cycles
+    stack: Vec<DefWithBodyId>,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    /// Evaluates the body of `def`.
+    fn eval_body(&mut self, def: DefWithBodyId) -> Result<ConstValue, ConstEvalError> {
+        let body = self.db.body(def);
+        let infer = self.db.infer(def);
+        if !infer.diagnostics.is_empty() {
+            return Err(ConstEvalError::Invalid);
+        }
+
+        self.stack.push(def);
+        let result = self.eval_expr(def, &body, &infer, body.body_expr());
+        self.stack.pop();
+        result
+    }
+
+    /// Evaluates the value of the constant `def` that is referred to by `expr`.
+    fn eval_const_ref(
+        &mut self,
+        def: DefWithBodyId,
+        expr: ExprId,
+    ) -> Result<ConstValue, ConstEvalError> {
+        if self.stack.contains(&def) {
+            return Err(ConstEvalError::Cycle(expr));
+        }
+
+        // Errors in the referred constant are reported for that constant
+        self.eval_body(def).map_err(|err| match err {
+            ConstEvalError::Cycle(_) => ConstEvalError::Cycle(expr),
+            _ => ConstEvalError::Invalid,
+        })
+    }
+
+    fn eval_expr(
+        &mut self,
+        def: DefWithBodyId,
+        body: &Body,
+        infer: &InferenceResult,
+        expr: ExprId,
+    ) -> Result<ConstValue, ConstEvalError> {
+        match &body[expr] {
+            Expr::Literal(Literal::Bool(value)) => Ok(ConstValue::Bool(*value)),
+            Expr::Literal(Literal::Int(lit)) => {
+                let value =
+                    i128::try_from(lit.value).map_err(|_| ConstEvalError::Overflow(expr))?;
+                self.check_int(value, &infer[expr], expr)
+            }
+            Expr::Literal(Literal::Float(lit)) => Ok(self.float(lit.value, &infer[expr])),
+            Expr::Literal(Literal::String(_)) => Err(ConstEvalError::NotConst(expr)),
+            Expr::Block {
+                statements,
+                tail: Some(tail),
+            } if statements.is_empty() => self.eval_expr(def, body, infer, *tail),
+            Expr::Path(path) => {
+                let resolver = resolver_for_expr(self.db.upcast(), def, expr);
+                match resolver.resolve_path_as_value_fully(self.db.upcast(), path) {
+                    Some((ValueNs::ConstId(id), _)) => self.eval_const_ref(id.into(), expr),
+                    Some(_) => Err(ConstEvalError::NotConst(expr)),
+                    None => Err(ConstEvalError::Invalid),
+                }
+            }
+            Expr::UnaryOp { expr: operand, op } => {
+                let value = self.eval_expr(def, body, infer, *operand)?;
+                let ty = &infer[expr];
+                match (op, value) {
+                    (UnaryOp::Not, ConstValue::Bool(value)) => Ok(ConstValue::Bool(!value)),
+                    (UnaryOp::Not, ConstValue::Int(value)) => {
+                        let (min, max) = self.int_range(ty);
+                        if min < 0 {
+                            Ok(ConstValue::Int(!value))
+                        } else if max == i128::MAX {
+                            Err(ConstEvalError::Overflow(expr))
+                        } else {
+                            Ok(ConstValue::Int(max - value))
+                        }
+                    }
+                    (UnaryOp::Neg, ConstValue::Int(value)) => {
+                        let value = value.checked_neg().ok_or(ConstEvalError::Overflow(expr))?;
+                        self.check_int(value, ty, expr)
+                    }
+                    (UnaryOp::Neg, ConstValue::Float(value)) => Ok(ConstValue::Float(-value)),
+                    _ => Err(ConstEvalError::Invalid),
+                }
+            }
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(op),
+            } => {
+                if let BinaryOp::Assignment { .. } = op {
+                    return Err(ConstEvalError::NotConst(expr));
+                }
+                let lhs = self.eval_expr(def, body, infer, *lhs)?;
+                let rhs = self.eval_expr(def, body, infer, *rhs)?;
+                match op {
+                    BinaryOp::LogicOp(op) => match (lhs, rhs) {
+                        (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => {
+                            Ok(ConstValue::Bool(match op {
+                                LogicOp::And => lhs && rhs,
+                                LogicOp::Or => lhs || rhs,
+                            }))
+                        }
+                        _ => Err(ConstEvalError::Invalid),
+                    },
+                    BinaryOp::CmpOp(op) => {
+                        let ordering = match (lhs, rhs) {
+                            (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => lhs.partial_cmp(&rhs),
+                            (ConstValue::Int(lhs), ConstValue::Int(rhs)) => lhs.partial_cmp(&rhs),
+                            (ConstValue::Float(lhs), ConstValue::Float(rhs)) => {
+                                lhs.partial_cmp(&rhs)
+                            }
+                            _ => return Err(ConstEvalError::Invalid),
+                        };
+                        Ok(ConstValue::Bool(compare(*op, ordering)))
+                    }
+                    BinaryOp::ArithOp(op) => match (lhs, rhs) {
+                        (ConstValue::Int(lhs), ConstValue::Int(rhs)) => {
+                            self.eval_int_op(*op, lhs, rhs, &infer[expr], expr)
+                        }
+                        (ConstValue::Float(lhs), ConstValue::Float(rhs)) => {
+                            let value = match op {
+                                ArithOp::Add => lhs + rhs,
+                                ArithOp::Subtract => lhs - rhs,
+                                ArithOp::Multiply => lhs * rhs,
+                                ArithOp::Divide => lhs / rhs,
+                                ArithOp::Remainder => lhs % rhs,
+                                _ => return Err(ConstEvalError::Invalid),
+                            };
+                            Ok(self.float(value, &infer[expr]))
+                        }
+                        _ => Err(ConstEvalError::Invalid),
+                    },
+                    BinaryOp::Assignment { .. } => unreachable!(),
+                }
+            }
+            Expr::Missing => Err(ConstEvalError::Invalid),
+            _ => Err(ConstEvalError::NotConst(expr)),
+        }
+    }
+
+    /// Evaluates an arithmetic operation on two integers of type `ty`.
+    fn eval_int_op(
+        &self,
+        op: ArithOp,
+        lhs: i128,
+        rhs: i128,
+        ty: &Ty,
+        expr: ExprId,
+    ) -> Result<ConstValue, ConstEvalError> {
+        let bits = self.int_ty(ty).map_or(128, |ty| bit_size(ty.bitness));
+        let value = match op {
+            ArithOp::Add => lhs.checked_add(rhs),
+            ArithOp::Subtract => lhs.checked_sub(rhs),
+            ArithOp::Multiply => lhs.checked_mul(rhs),
+            ArithOp::Divide | ArithOp::Remainder if rhs == 0 => {
+                return Err(ConstEvalError::DivideByZero(expr))
+            }
+            ArithOp::Divide => lhs.checked_div(rhs),
+            ArithOp::Remainder => lhs.checked_rem(rhs),
+            ArithOp::LeftShift | ArithOp::RightShift if rhs < 0 || rhs >= i128::from(bits) => None,
+            // Bits that are shifted out are discarded
+            ArithOp::LeftShift => return Ok(ConstValue::Int(self.truncate(lhs << rhs, ty))),
+            ArithOp::RightShift => Some(lhs >> rhs),
+            ArithOp::BitAnd => Some(lhs & rhs),
+            ArithOp::BitOr => Some(lhs | rhs),
+            ArithOp::BitXor => Some(lhs ^ rhs),
+        };
+        let value = value.ok_or(ConstEvalError::Overflow(expr))?;
+        self.check_int(value, ty, expr)
+    }
+
+    /// Returns the integer type of `ty` with a resolved bitness.
+    fn int_ty(&self, ty: &Ty) -> Option<IntTy> {
+        match ty.interned() {
+            TyKind::Int(int_ty) => Some(int_ty.resolve(&self.db.target_data_layout())),
+            _ => None,
+        }
+    }
+
+    /// Returns the minimum and maximum value of the integer type `ty`.
+    fn int_range(&self, ty: &Ty) -> (i128, i128) {
+        match self.int_ty(ty) {
+            Some(int_ty) => {
+                let bits = bit_size(int_ty.bitness);
+                if int_ty.signedness.is_signed() {
+                    let max = i128::MAX >> (128 - bits);
+                    (-max - 1, max)
+                } else if bits == 128 {
+                    (0, i128::MAX)
+                } else {
+                    (0, (1 << bits) - 1)
+                }
+            }
+            None => (i128::MIN, i128::MAX),
+        }
+    }
+
+    /// Checks whether `value` fits in the integer type `ty`.
+    fn check_int(&self, value: i128, ty: &Ty, expr: ExprId) -> Result<ConstValue, ConstEvalError> {
+        let (min, max) = self.int_range(ty);
+        if value < min || value > max {
+            Err(ConstEvalError::Overflow(expr))
+        } else {
+            Ok(ConstValue::Int(value))
+        }
+    }
+
+    /// Truncates `value` to the number of bits of the integer type `ty`.
+    fn truncate(&self, value: i128, ty: &Ty) -> i128 {
+        let int_ty = match self.int_ty(ty) {
+            Some(int_ty) => int_ty,
+            None => return value,
+        };
+        let bits = bit_size(int_ty.bitness);
+        if bits == 128 {
+            return value;
+        }
+        let value = value & ((1 << bits) - 1);
+        if int_ty.signedness.is_signed() && value >> (bits - 1) != 0 {
+            value - (1 << bits)
+        } else {
+            value
+        }
+    }
+
+    /// Rounds `value` to the precision of the floating-point type `ty`.
+    fn float(&self, value: f64, ty: &Ty) -> ConstValue {
+        match ty.interned() {
+            TyKind::Float(FloatTy {
+                bitness: FloatBitness::X32,
+            }) => ConstValue::Float(f64::from(value as f32)),
+            _ => ConstValue::Float(value),
+        }
+    }
+}
+
+/// Returns the number of bits of a resolved integer bitness.
+fn bit_size(bitness: IntBitness) -> u32 {
+    match bitness {
+        IntBitness::X8 => 8,
+        IntBitness::X16 => 16,
+        IntBitness::X32 => 32,
+        IntBitness::X64 => 64,
+        IntBitness::X128 => 128,
+        IntBitness::Xsize => unreachable!("unresolved bitness"),
+    }
+}
+
+/// Returns the result of the comparison `op` given the ordering of its operands.
+fn compare(op: CmpOp, ordering: Option<cmp::Ordering>) -> bool {
+    match op {
+        CmpOp::Eq { negated } => (ordering == Some(cmp::Ordering::Equal)) != negated,
+        CmpOp::Ord {
+            ordering: op,
+            strict,
+        } => match (ordering, op, strict) {
+            (Some(cmp::Ordering::Less), Ordering::Less, _)
+            | (Some(cmp::Ordering::Greater), Ordering::Greater, _)
+            | (Some(cmp::Ordering::Equal), _, false) => true,
+            _ => false,
+        },
+    }
+}
@@ -137,6 +137,31 @@ impl Diagnostic for CyclicType {
     }
 }
 
+#[derive(Debug)]
+pub struct TypeArgCountMismatch {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Diagnostic for TypeArgCountMismatch {
+    fn message(&self) -> String {
+        format!(
+            "this type takes {} type arguments but {} type arguments were supplied",
+            self.expected, self.found
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct PrivateAccess {
     pub file: FileId,
@@ -379,6 +404,260 @@ impl Diagnostic for DuplicateDefinition {
     }
 }
 
+/// An error that is emitted for a `self` parameter of a function that is not defined in an `impl`
+/// block.
+#[derive(Debug)]
+pub struct SelfParamOutsideImpl {
+    pub file: FileId,
+    pub self_param: SyntaxNodePtr,
+}
+
+impl Diagnostic for SelfParamOutsideImpl {
+    fn message(&self) -> String {
+        "`self` parameter is only allowed in associated functions".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.self_param)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for an `impl` block of a type that is not a struct or an enum (e.g.
+/// `impl i32 {}`).
+#[derive(Debug)]
+pub struct InvalidSelfTyImpl {
+    pub file: FileId,
+    pub type_ref: SyntaxNodePtr,
+}
+
+impl Diagnostic for InvalidSelfTyImpl {
+    fn message(&self) -> String {
+        "inherent `impl` blocks can only be defined for structs and enums".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a method call of a method that does not exist for the type of the
+/// receiver.
+#[derive(Debug)]
+pub struct UnresolvedMethod {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub receiver_ty: Ty,
+    pub method_name: Name,
+}
+
+impl Diagnostic for UnresolvedMethod {
+    fn message(&self) -> String {
+        format!("no method named `{}` found", self.method_name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for an `impl` block of a trait that cannot be found (e.g.
+/// `impl Unknown for Foo {}`).
+#[derive(Debug)]
+pub struct UnresolvedTrait {
+    pub file: FileId,
+    pub path: SyntaxNodePtr,
+}
+
+impl Diagnostic for UnresolvedTrait {
+    fn message(&self) -> String {
+        "undefined trait".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.path)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for an `impl` block of a trait that does not define all the functions
+/// of the trait that have no default implementation.
+#[derive(Debug)]
+pub struct MissingTraitItems {
+    pub file: FileId,
+    pub path: SyntaxNodePtr,
+    pub trait_name: Name,
+    pub missing: Vec<Name>,
+}
+
+impl Diagnostic for MissingTraitItems {
+    fn message(&self) -> String {
+        let missing: Vec<String> = self
+            .missing
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .collect();
+        format!(
+            "not all items of trait `{}` are implemented, missing: {}",
+            self.trait_name,
+            missing.join(", ")
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.path)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a function in an `impl` block of a trait that is not declared in
+/// the trait.
+#[derive(Debug)]
+pub struct MethodNotMemberOfTrait {
+    pub file: FileId,
+    pub func: SyntaxNodePtr,
+    pub method_name: Name,
+    pub trait_name: Name,
+}
+
+impl Diagnostic for MethodNotMemberOfTrait {
+    fn message(&self) -> String {
+        format!(
+            "method `{}` is not a member of trait `{}`",
+            self.method_name, self.trait_name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.func)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a function in an `impl` block of a trait whose signature differs
+/// from the one declared in the trait.
+#[derive(Debug)]
+pub struct TraitMethodSignatureMismatch {
+    pub file: FileId,
+    pub func: SyntaxNodePtr,
+    pub method_name: Name,
+    pub expected: String,
+    pub found: String,
+}
+
+impl Diagnostic for TraitMethodSignatureMismatch {
+    fn message(&self) -> String {
+        format!(
+            "method `{}` has an incompatible type for trait. expected `{}`, found `{}`",
+            self.method_name, self.expected, self.found
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.func)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for an `impl` block of a trait for a type that is not a struct or an
+/// enum (e.g. `impl Update for i32 {}`).
+#[derive(Debug)]
+pub struct InvalidSelfTyTraitImpl {
+    pub file: FileId,
+    pub type_ref: SyntaxNodePtr,
+}
+
+impl Diagnostic for InvalidSelfTyTraitImpl {
+    fn message(&self) -> String {
+        "traits can only be implemented for structs and enums".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a trait is implemented more than once for the same type.
+#[derive(Debug)]
+pub struct ConflictingTraitImpls {
+    pub file: FileId,
+    pub first_definition: SyntaxNodePtr,
+    pub definition: SyntaxNodePtr,
+    pub trait_name: Name,
+    pub self_ty: String,
+}
+
+impl Diagnostic for ConflictingTraitImpls {
+    fn message(&self) -> String {
+        format!(
+            "conflicting implementations of trait `{}` for type `{}`",
+            self.trait_name, self.self_ty
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.definition)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a generic function is called with a type argument that does not
+/// implement a trait the type parameter is bounded by.
+#[derive(Debug)]
+pub struct TraitBoundNotSatisfied {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub ty: String,
+    pub trait_name: Name,
+}
+
+impl Diagnostic for TraitBoundNotSatisfied {
+    fn message(&self) -> String {
+        format!(
+            "the trait `{}` is not implemented for `{}`",
+            self.trait_name, self.ty
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct ReturnMissingExpression {
     pub file: FileId,
@@ -419,6 +698,26 @@ impl Diagnostic for BreakOutsideLoop {
     }
 }
 
+#[derive(Debug)]
+pub struct ContinueOutsideLoop {
+    pub file: FileId,
+    pub continue_expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for ContinueOutsideLoop {
+    fn message(&self) -> String {
+        "`continue` outside of a loop".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.continue_expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct BreakWithValueOutsideLoop {
     pub file: FileId,
@@ -439,6 +738,47 @@ impl Diagnostic for BreakWithValueOutsideLoop {
     }
 }
 
+#[derive(Debug)]
+pub struct InvalidForIterable {
+    pub file: FileId,
+    pub iterable: SyntaxNodePtr,
+    pub found: Ty,
+}
+
+impl Diagnostic for InvalidForIterable {
+    fn message(&self) -> String {
+        "`for` loops can only iterate over integer ranges".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.iterable)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct RangeOutsideForLoop {
+    pub file: FileId,
+    pub range_expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for RangeOutsideForLoop {
+    fn message(&self) -> String {
+        "range expressions can only be used as the iterable of a `for` loop".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.range_expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct AccessUnknownField {
     pub file: FileId,
@@ -561,6 +901,47 @@ impl Diagnostic for NoFields {
         self
     }
 }
+#[derive(Debug)]
+pub struct CannotIndex {
+    pub file: FileId,
+    pub base_expr: SyntaxNodePtr,
+    pub found: Ty,
+}
+
+impl Diagnostic for CannotIndex {
+    fn message(&self) -> String {
+        "attempted to index a value that is not an array.".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.base_expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct TypeAnnotationsNeeded {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for TypeAnnotationsNeeded {
+    fn message(&self) -> String {
+        "type annotations needed".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct NoSuchField {
     pub file: FileId,
@@ -581,6 +962,167 @@ impl Diagnostic for NoSuchField {
     }
 }
 
+#[derive(Debug)]
+pub struct MismatchedStructPat {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+    pub expected: StructKind,
+    pub found: StructKind,
+}
+
+impl Diagnostic for MismatchedStructPat {
+    fn message(&self) -> String {
+        format!(
+            "mismatched pattern kind. expected `{}`, found `{}`",
+            self.expected, self.found
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct PatternFieldCountMismatch {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Diagnostic for PatternFieldCountMismatch {
+    fn message(&self) -> String {
+        format!(
+            "this pattern has {} field{}, but the corresponding variant has {} field{}",
+            self.found,
+            if self.found == 1 { "" } else { "s" },
+            self.expected,
+            if self.expected == 1 { "" } else { "s" },
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct MissingPatternFields {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+    pub field_names: Vec<Name>,
+}
+
+impl Diagnostic for MissingPatternFields {
+    fn message(&self) -> String {
+        use std::fmt::Write;
+        let mut message = "pattern does not mention fields:\n".to_string();
+        for field in &self.field_names {
+            writeln!(message, "- {}", field).unwrap();
+        }
+        message
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if the arms of a `match` expression do not cover all possible values
+/// of the matched expression.
+#[derive(Debug)]
+pub struct MissingMatchArms {
+    pub file: FileId,
+    pub match_expr: SyntaxNodePtr,
+    /// The patterns that are not covered, e.g. `State::Idle`.
+    pub missing: Vec<String>,
+}
+
+impl Diagnostic for MissingMatchArms {
+    fn message(&self) -> String {
+        let patterns = self
+            .missing
+            .iter()
+            .map(|pat| format!("`{}`", pat))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("non-exhaustive patterns: {} not covered", patterns)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.match_expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a `match` arm that can never be reached because the previous arms
+/// already cover all the values it matches.
+#[derive(Debug)]
+pub struct UnreachableMatchArm {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+}
+
+impl Diagnostic for UnreachableMatchArm {
+    fn message(&self) -> String {
+        "unreachable pattern".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a pattern in a `let` statement or function parameter that does not
+/// match all possible values, e.g. `let State::Idle = state;`.
+#[derive(Debug)]
+pub struct RefutablePattern {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+    /// The patterns that are not covered, e.g. `State::Idle`.
+    pub missing: Vec<String>,
+}
+
+impl Diagnostic for RefutablePattern {
+    fn message(&self) -> String {
+        let patterns = self
+            .missing
+            .iter()
+            .map(|pat| format!("`{}`", pat))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("refutable pattern in binding: {} not covered", patterns)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct PossiblyUninitializedVariable {
     pub file: FileId,
@@ -804,3 +1346,91 @@ impl Diagnostic for ImportDuplicateDefinition {
         self
     }
 }
+
+/// An error that is emitted if the initializer of a `const` or `static` cannot be evaluated at
+/// compile time.
+#[derive(Debug)]
+pub struct NonConstExpr {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for NonConstExpr {
+    fn message(&self) -> String {
+        "expected a constant expression".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if the evaluation of a constant expression fails (e.g. because of an
+/// overflow).
+#[derive(Debug)]
+pub struct ConstEvalFailed {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub reason: &'static str,
+}
+
+impl Diagnostic for ConstEvalFailed {
+    fn message(&self) -> String {
+        format!("evaluation of constant value failed: {}", self.reason)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a `const` or `static` has a type that is not a primitive type.
+#[derive(Debug)]
+pub struct NonPrimitiveGlobalType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for NonPrimitiveGlobalType {
+    fn message(&self) -> String {
+        "`const` and `static` items can only have primitive types".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a `static` has a 128-bit integer type. Statics are accessed
+/// atomically, which is not supported for 128-bit values on all targets.
+#[derive(Debug)]
+pub struct LargeStaticType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for LargeStaticType {
+    fn message(&self) -> String {
+        "`static` items cannot have 128-bit integer types".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
 
 //pub use mun_syntax::ast::PrefixOp as UnaryOp;
 use crate::code_model::src::HasSource;
-use crate::name::AsName;
+use crate::name::{name, AsName};
 use crate::type_ref::{LocalTypeRefId, TypeRef, TypeRefBuilder, TypeRefMap, TypeRefSourceMap};
 use either::Either;
 pub use mun_syntax::ast::PrefixOp as UnaryOp;
@@ -19,7 +19,7 @@ use std::sync::Arc;
 
 pub use self::scope::ExprScopes;
 use crate::diagnostics::DiagnosticSink;
-use crate::ids::{DefWithBodyId, Lookup};
+use crate::ids::{DefWithBodyId, ItemContainerId, Lookup};
 use crate::in_file::InFile;
 use crate::primitive_type::{PrimitiveFloat, PrimitiveInt};
 use std::borrow::Cow;
@@ -71,9 +71,20 @@ impl Body {
         match def {
             DefWithBodyId::FunctionId(f) => {
                 let f = f.lookup(db);
-                let src = f.source(db);
+                let src = f.id.source(db);
                 collector = ExprCollector::new(def, src.file_id, db);
-                collector.collect_fn_body(&src.value)
+                let is_associated = !matches!(f.container, ItemContainerId::ModuleId(_));
+                collector.collect_fn_body(&src.value, is_associated)
+            }
+            DefWithBodyId::ConstId(c) => {
+                let src = c.lookup(db).source(db);
+                collector = ExprCollector::new(def, src.file_id, db);
+                collector.collect_initializer_body(src.value.ascribed_type(), src.value.body())
+            }
+            DefWithBodyId::StaticId(s) => {
+                let src = s.lookup(db).source(db);
+                collector = ExprCollector::new(def, src.file_id, db);
+                collector.collect_initializer_body(src.value.ascribed_type(), src.value.body())
             }
         }
 
@@ -278,6 +289,12 @@ pub enum Expr {
         callee: ExprId,
         args: Vec<ExprId>,
     },
+    /// A call of an associated function that takes `self` (e.g. `foo.bar(a)`)
+    MethodCall {
+        receiver: ExprId,
+        method_name: Name,
+        args: Vec<ExprId>,
+    },
     Path(Path),
     If {
         condition: ExprId,
@@ -303,6 +320,7 @@ pub enum Expr {
     Break {
         expr: Option<ExprId>,
     },
+    Continue,
     Loop {
         body: ExprId,
     },
@@ -310,6 +328,18 @@ pub enum Expr {
         condition: ExprId,
         body: ExprId,
     },
+    /// A `for` loop over an iterable (e.g. `for i in 0..n { }`)
+    For {
+        pat: PatId,
+        iterable: ExprId,
+        body: ExprId,
+    },
+    /// A range of values (e.g. `a..b` or `a..=b`)
+    Range {
+        lhs: ExprId,
+        rhs: ExprId,
+        op: RangeOp,
+    },
     RecordLit {
         type_id: LocalTypeRefId,
         fields: Vec<RecordLitField>,
@@ -319,9 +349,32 @@ pub enum Expr {
         expr: ExprId,
         name: Name,
     },
+    Index {
+        base: ExprId,
+        index: ExprId,
+    },
+    Array(Vec<ExprId>),
+    Match {
+        expr: ExprId,
+        arms: Vec<MatchArm>,
+    },
+    /// A lambda expression (e.g. `|a: i32, b| a + b`). The types of the arguments and the return
+    /// type are optional.
+    Lambda {
+        args: Vec<(PatId, Option<LocalTypeRefId>)>,
+        ret_type: Option<LocalTypeRefId>,
+        body: ExprId,
+    },
     Literal(Literal),
 }
 
+/// A single arm of a `match` expression
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MatchArm {
+    pub pat: PatId,
+    pub expr: ExprId,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum BinaryOp {
     LogicOp(LogicOp),
@@ -330,6 +383,14 @@ pub enum BinaryOp {
     Assignment { op: Option<ArithOp> },
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RangeOp {
+    /// `a..b`, excludes the end of the range
+    Exclusive,
+    /// `a..=b`, includes the end of the range
+    Inclusive,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum LogicOp {
     And,
@@ -366,6 +427,7 @@ impl Expr {
     pub fn walk_child_exprs(&self, mut f: impl FnMut(ExprId)) {
         match self {
             Expr::Missing => {}
+            Expr::Continue => {}
             Expr::Path(_) => {}
             Expr::Block { statements, tail } => {
                 for stmt in statements {
@@ -388,6 +450,12 @@ impl Expr {
                     f(*arg);
                 }
             }
+            Expr::MethodCall { receiver, args, .. } => {
+                f(*receiver);
+                for arg in args {
+                    f(*arg);
+                }
+            }
             Expr::BinaryOp { lhs, rhs, .. } => {
                 f(*lhs);
                 f(*rhs);
@@ -424,6 +492,14 @@ impl Expr {
                 f(*condition);
                 f(*body);
             }
+            Expr::For { iterable, body, .. } => {
+                f(*iterable);
+                f(*body);
+            }
+            Expr::Range { lhs, rhs, .. } => {
+                f(*lhs);
+                f(*rhs);
+            }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields {
                     f(field.expr);
@@ -432,6 +508,24 @@ impl Expr {
                     f(*expr);
                 }
             }
+            Expr::Index { base, index } => {
+                f(*base);
+                f(*index);
+            }
+            Expr::Array(exprs) => {
+                for expr in exprs {
+                    f(*expr);
+                }
+            }
+            Expr::Match { expr, arms } => {
+                f(*expr);
+                for arm in arms {
+                    f(arm.expr);
+                }
+            }
+            Expr::Lambda { body, .. } => {
+                f(*body);
+            }
         }
     }
 }
@@ -439,14 +533,40 @@ impl Expr {
 /// Similar to `ast::PatKind`
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Pat {
-    Missing,             // Indicates an error
-    Wild,                // `_`
-    Path(Path),          // E.g. `foo::bar`
-    Bind { name: Name }, // E.g. `a`
+    Missing,    // Indicates an error
+    Wild,       // `_`
+    Path(Path), // E.g. `foo::bar`
+    Bind {
+        name: Name,
+    }, // E.g. `a`
+    TupleStruct {
+        // E.g. `Foo::Bar(a, _)`
+        path: Option<Path>,
+        args: Vec<PatId>,
+    },
+    Record {
+        // E.g. `Foo::Bar { a, b: _, .. }`
+        path: Option<Path>,
+        fields: Vec<RecordFieldPat>,
+        has_rest: bool,
+    },
+}
+
+/// A single field of a record pattern, e.g. `b: _` in `Foo::Bar { b: _ }`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RecordFieldPat {
+    pub name: Name,
+    pub pat: PatId,
 }
 
 impl Pat {
-    pub fn walk_child_pats(&self, mut _f: impl FnMut(PatId)) {}
+    pub fn walk_child_pats(&self, mut f: impl FnMut(PatId)) {
+        match self {
+            Pat::Missing | Pat::Wild | Pat::Path(_) | Pat::Bind { .. } => {}
+            Pat::TupleStruct { args, .. } => args.iter().copied().for_each(f),
+            Pat::Record { fields, .. } => fields.iter().for_each(|field| f(field.pat)),
+        }
+    }
 }
 
 // Queries
@@ -515,8 +635,19 @@ impl<'a> ExprCollector<'a> {
         self.exprs.alloc(Expr::Missing)
     }
 
-    fn collect_fn_body(&mut self, node: &ast::FunctionDef) {
+    /// Collects the parameters and body of a function. If `is_associated` is true, the function is
+    /// defined in an `impl` block or a trait and the `self` parameter refers to the `Self` type.
+    fn collect_fn_body(&mut self, node: &ast::FunctionDef, is_associated: bool) {
         if let Some(param_list) = node.param_list() {
+            if param_list.self_param().is_some() {
+                let self_pat = self.pats.alloc(Pat::Bind { name: name![self] });
+                let self_type = if is_associated {
+                    self.type_ref_builder.self_type()
+                } else {
+                    self.type_ref_builder.error()
+                };
+                self.params.push((self_pat, self_type));
+            }
             for param in param_list.params() {
                 let pat = if let Some(pat) = param.pat() {
                     pat
@@ -542,6 +673,20 @@ impl<'a> ExprCollector<'a> {
         self.ret_type = Some(ret_type);
     }
 
+    /// Collects the initializer of a `const` or `static`. The initializer is treated as the body
+    /// of a function without parameters that returns the declared type.
+    fn collect_initializer_body(
+        &mut self,
+        type_ref: Option<ast::TypeRef>,
+        body: Option<ast::Expr>,
+    ) {
+        let body = self.collect_expr_opt(body);
+        self.body_expr = Some(body);
+
+        let ret_type = self.type_ref_builder.alloc_from_node_opt(type_ref.as_ref());
+        self.ret_type = Some(ret_type);
+    }
+
     fn collect_block_opt(&mut self, block: Option<ast::BlockExpr>) -> ExprId {
         if let Some(block) = block {
             self.collect_block(block)
@@ -597,8 +742,11 @@ impl<'a> ExprCollector<'a> {
         match expr.kind() {
             ast::ExprKind::LoopExpr(expr) => self.collect_loop(expr),
             ast::ExprKind::WhileExpr(expr) => self.collect_while(expr),
+            ast::ExprKind::ForExpr(expr) => self.collect_for(expr),
+            ast::ExprKind::LambdaExpr(expr) => self.collect_lambda(expr),
             ast::ExprKind::ReturnExpr(r) => self.collect_return(r),
             ast::ExprKind::BreakExpr(r) => self.collect_break(r),
+            ast::ExprKind::ContinueExpr(_) => self.alloc_expr(Expr::Continue, syntax_ptr),
             ast::ExprKind::BlockExpr(b) => self.collect_block(b),
             ast::ExprKind::Literal(e) => match e.kind() {
                 ast::LiteralKind::Bool => {
@@ -630,8 +778,16 @@ impl<'a> ExprCollector<'a> {
                     expr_id
                 }
                 ast::LiteralKind::String => {
-                    let lit = Literal::String(Default::default());
-                    self.alloc_expr(Expr::Literal(lit), syntax_ptr)
+                    let (text, _) = e.text_and_suffix();
+                    let (lit, errors) = string_lit(&text);
+                    let expr_id = self.alloc_expr(Expr::Literal(lit), syntax_ptr);
+
+                    for err in errors {
+                        self.diagnostics
+                            .push(ExprDiagnostic::LiteralError { expr: expr_id, err })
+                    }
+
+                    expr_id
                 }
             },
             ast::ExprKind::PrefixExpr(e) => {
@@ -753,6 +909,15 @@ impl<'a> ExprCollector<'a> {
                     self.alloc_expr(Expr::BinaryOp { lhs, rhs, op: None }, syntax_ptr)
                 }
             }
+            ast::ExprKind::RangeExpr(e) => {
+                let lhs = self.collect_expr_opt(e.start());
+                let rhs = self.collect_expr_opt(e.end());
+                let op = match e.op_kind() {
+                    Some(ast::RangeOp::Inclusive) => RangeOp::Inclusive,
+                    Some(ast::RangeOp::Exclusive) | None => RangeOp::Exclusive,
+                };
+                self.alloc_expr(Expr::Range { lhs, rhs, op }, syntax_ptr)
+            }
             ast::ExprKind::PathExpr(e) => {
                 let path = e
                     .path()
@@ -815,6 +980,15 @@ impl<'a> ExprCollector<'a> {
                 };
                 self.alloc_expr(Expr::Field { expr, name }, syntax_ptr)
             }
+            ast::ExprKind::IndexExpr(e) => {
+                let base = self.collect_expr_opt(e.base());
+                let index = self.collect_expr_opt(e.index());
+                self.alloc_expr(Expr::Index { base, index }, syntax_ptr)
+            }
+            ast::ExprKind::ArrayExpr(e) => {
+                let exprs = e.exprs().map(|e| self.collect_expr(e)).collect();
+                self.alloc_expr(Expr::Array(exprs), syntax_ptr)
+            }
             ast::ExprKind::IfExpr(e) => {
                 let then_branch = self.collect_block_opt(e.then_branch());
 
@@ -837,6 +1011,19 @@ impl<'a> ExprCollector<'a> {
                     syntax_ptr,
                 )
             }
+            ast::ExprKind::MatchExpr(e) => {
+                let expr = self.collect_expr_opt(e.expr());
+                let arms = e
+                    .match_arm_list()
+                    .into_iter()
+                    .flat_map(|list| list.arms())
+                    .map(|arm| MatchArm {
+                        pat: self.collect_pat_opt(arm.pat()),
+                        expr: self.collect_expr_opt(arm.expr()),
+                    })
+                    .collect();
+                self.alloc_expr(Expr::Match { expr, arms }, syntax_ptr)
+            }
             ast::ExprKind::ParenExpr(e) => {
                 let inner = self.collect_expr_opt(e.expr());
                 // make the paren expr point to the inner expression as well
@@ -853,6 +1040,26 @@ impl<'a> ExprCollector<'a> {
                 };
                 self.alloc_expr(Expr::Call { callee, args }, syntax_ptr)
             }
+            ast::ExprKind::MethodCallExpr(e) => {
+                let receiver = self.collect_expr_opt(e.expr());
+                let method_name = e
+                    .name_ref()
+                    .map(|name_ref| name_ref.as_name())
+                    .unwrap_or_else(Name::missing);
+                let args = if let Some(arg_list) = e.arg_list() {
+                    arg_list.args().map(|e| self.collect_expr(e)).collect()
+                } else {
+                    Vec::new()
+                };
+                self.alloc_expr(
+                    Expr::MethodCall {
+                        receiver,
+                        method_name,
+                        args,
+                    },
+                    syntax_ptr,
+                )
+            }
         }
     }
 
@@ -881,6 +1088,37 @@ impl<'a> ExprCollector<'a> {
                 Pat::Bind { name }
             }
             ast::PatKind::PlaceholderPat(_) => Pat::Wild,
+            ast::PatKind::PathPat(p) => p
+                .path()
+                .and_then(Path::from_ast)
+                .map(Pat::Path)
+                .unwrap_or(Pat::Missing),
+            ast::PatKind::TupleStructPat(p) => {
+                let path = p.path().and_then(Path::from_ast);
+                let args = p.args().map(|arg| self.collect_pat(arg)).collect();
+                Pat::TupleStruct { path, args }
+            }
+            ast::PatKind::RecordPat(p) => {
+                let path = p.path().and_then(Path::from_ast);
+                let field_list = p.record_field_pat_list();
+                let fields = field_list
+                    .iter()
+                    .flat_map(|list| list.fields())
+                    .map(|field| RecordFieldPat {
+                        name: field
+                            .field_name()
+                            .map(Name::new)
+                            .unwrap_or_else(Name::missing),
+                        pat: self.collect_pat_opt(field.pat()),
+                    })
+                    .collect();
+                let has_rest = field_list.map_or(false, |list| list.has_rest());
+                Pat::Record {
+                    path,
+                    fields,
+                    has_rest,
+                }
+            }
         };
         let ptr = AstPtr::new(&pat);
         self.alloc_pat(pattern, ptr)
@@ -911,6 +1149,50 @@ impl<'a> ExprCollector<'a> {
         self.alloc_expr(Expr::While { condition, body }, syntax_node_ptr)
     }
 
+    fn collect_for(&mut self, expr: ast::ForExpr) -> ExprId {
+        let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let pat = self.collect_pat_opt(expr.pat());
+        let iterable = self.collect_expr_opt(expr.iterable());
+        let body = self.collect_block_opt(expr.loop_body());
+        self.alloc_expr(
+            Expr::For {
+                pat,
+                iterable,
+                body,
+            },
+            syntax_node_ptr,
+        )
+    }
+
+    fn collect_lambda(&mut self, expr: ast::LambdaExpr) -> ExprId {
+        let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let args = expr
+            .param_list()
+            .into_iter()
+            .flat_map(|list| list.params())
+            .map(|param| {
+                let pat = self.collect_pat_opt(param.pat());
+                let type_ref = param
+                    .ascribed_type()
+                    .map(|t| self.type_ref_builder.alloc_from_node(&t));
+                (pat, type_ref)
+            })
+            .collect();
+        let ret_type = expr
+            .ret_type()
+            .and_then(|rt| rt.type_ref())
+            .map(|t| self.type_ref_builder.alloc_from_node(&t));
+        let body = self.collect_expr_opt(expr.body());
+        self.alloc_expr(
+            Expr::Lambda {
+                args,
+                ret_type,
+                body,
+            },
+            syntax_node_ptr,
+        )
+    }
+
     fn finish(mut self) -> (Body, BodySourceMap) {
         let (type_refs, type_ref_source_map) = self.type_ref_builder.finish();
         let body = Body {
@@ -941,6 +1223,44 @@ fn strip_underscores(s: &str) -> Cow<str> {
     }
 }
 
+/// Parses the given quoted string into a string literal, resolving any escape sequences
+fn string_lit(str: &str) -> (Literal, Vec<LiteralError>) {
+    let mut errors = Vec::new();
+
+    let mut chars = str.chars();
+    let quote = chars.next();
+    let mut value = String::with_capacity(str.len());
+    let mut terminated = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some('0') => value.push('\0'),
+                Some(c @ '\\') | Some(c @ '\'') | Some(c @ '"') => value.push(c),
+                _ => {
+                    if !errors.contains(&LiteralError::LexerError) {
+                        errors.push(LiteralError::LexerError);
+                    }
+                }
+            },
+            c if Some(c) == quote => {
+                terminated = true;
+                break;
+            }
+            c => value.push(c),
+        }
+    }
+
+    // The lexer also produces a string token for unterminated strings
+    if !terminated && !errors.contains(&LiteralError::LexerError) {
+        errors.push(LiteralError::LexerError);
+    }
+
+    (Literal::String(value), errors)
+}
+
 /// Parses the given string into a float literal
 fn float_lit(str: &str, suffix: Option<&str>) -> (Literal, Vec<LiteralError>) {
     let str = strip_underscores(str);
@@ -1030,6 +1350,7 @@ fn integer_lit(str: &str, suffix: Option<&str>) -> (Literal, Vec<LiteralError>)
 
 #[cfg(test)]
 mod test {
+    use crate::expr::string_lit;
     use crate::expr::{float_lit, LiteralError, LiteralFloat, LiteralFloatKind};
     use crate::expr::{integer_lit, LiteralInt, LiteralIntKind};
     use crate::primitive_type::{PrimitiveFloat, PrimitiveInt};
@@ -1344,6 +1665,36 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_string_literals() {
+        assert_eq!(
+            string_lit(r#""hello, world""#),
+            (Literal::String("hello, world".to_owned()), vec![])
+        );
+        assert_eq!(
+            string_lit(r#"'single quoted'"#),
+            (Literal::String("single quoted".to_owned()), vec![])
+        );
+        assert_eq!(
+            string_lit(r#""\t\"escaped\"\\\n""#),
+            (Literal::String("\t\"escaped\"\\\n".to_owned()), vec![])
+        );
+        assert_eq!(
+            string_lit(r#""\q""#),
+            (
+                Literal::String("".to_owned()),
+                vec![LiteralError::LexerError]
+            )
+        );
+        assert_eq!(
+            string_lit(r#""unterminated"#),
+            (
+                Literal::String("unterminated".to_owned()),
+                vec![LiteralError::LexerError]
+            )
+        );
+    }
 }
 
 mod diagnostics {
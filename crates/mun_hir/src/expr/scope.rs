@@ -154,6 +154,33 @@ fn compute_expr_scopes(expr: ExprId, body: &Body, scopes: &mut ExprScopes, scope
         Expr::Block { statements, tail } => {
             compute_block_scopes(&statements, *tail, body, scopes, scope);
         }
+        Expr::Match { expr, arms } => {
+            compute_expr_scopes(*expr, body, scopes, scope);
+            for arm in arms {
+                let scope = scopes.new_scope(scope);
+                scopes.add_bindings(body, scope, arm.pat);
+                compute_expr_scopes(arm.expr, body, scopes, scope);
+            }
+        }
+        Expr::For {
+            pat,
+            iterable,
+            body: for_body,
+        } => {
+            compute_expr_scopes(*iterable, body, scopes, scope);
+            let scope = scopes.new_scope(scope);
+            scopes.add_bindings(body, scope, *pat);
+            compute_expr_scopes(*for_body, body, scopes, scope);
+        }
+        Expr::Lambda {
+            args,
+            body: lambda_body,
+            ..
+        } => {
+            let scope = scopes.new_scope(scope);
+            scopes.add_params_bindings(body, scope, args.iter().map(|arg| &arg.0));
+            compute_expr_scopes(*lambda_body, body, scopes, scope);
+        }
         e => e.walk_child_exprs(|e| compute_expr_scopes(e, body, scopes, scope)),
     };
 }
@@ -14,6 +14,7 @@ use mun_syntax::{AstNode, SyntaxNodePtr};
 use std::sync::Arc;
 
 mod literal_out_of_range;
+mod match_check;
 mod uninitialized_access;
 
 #[cfg(test)]
@@ -42,6 +43,8 @@ impl<'a> ExprValidator<'a> {
     pub fn validate_body(&self, sink: &mut DiagnosticSink) {
         self.validate_literal_ranges(sink);
         self.validate_uninitialized_access(sink);
+        self.validate_match_exhaustiveness(sink);
+        self.validate_irrefutable_patterns(sink);
         self.validate_extern(sink);
         self.validate_privacy(sink);
     }
@@ -0,0 +1,319 @@
+use super::ExprValidator;
+use crate::{
+    code_model::{src::HasSource, StructKind, VariantDef},
+    diagnostics::{DiagnosticSink, MissingMatchArms, RefutablePattern, UnreachableMatchArm},
+    expr::{ExprId, MatchArm, Pat, PatId, Statement},
+    Expr, Ty,
+};
+use std::iter::repeat;
+
+/// A pattern as seen by the exhaustiveness checker. Bindings and wildcards are both represented by
+/// `Wild` because they match any value.
+#[derive(Clone, Debug)]
+enum DeconstructedPat {
+    Wild,
+    /// Matches the constructor with the specified index and the sub-patterns of its fields. For an
+    /// enum the index is the index of the variant, a struct only has a single constructor.
+    Constructor {
+        index: usize,
+        fields: Vec<DeconstructedPat>,
+    },
+}
+
+impl<'a> ExprValidator<'a> {
+    /// Validates that the arms of every `match` expression cover all possible values of the
+    /// matched expression and that every arm can be reached.
+    pub fn validate_match_exhaustiveness(&self, sink: &mut DiagnosticSink) {
+        for (expr_id, expr) in self.body.exprs() {
+            if let Expr::Match { expr, arms } = expr {
+                self.validate_match(expr_id, *expr, arms, sink);
+            }
+        }
+    }
+
+    /// Validates that the patterns of `let` statements and function parameters match all possible
+    /// values of their type.
+    pub fn validate_irrefutable_patterns(&self, sink: &mut DiagnosticSink) {
+        let params = self.body.params().iter().map(|(pat, _)| *pat);
+        let let_statements = self
+            .body
+            .exprs()
+            .filter_map(|(_, expr)| match expr {
+                Expr::Block { statements, .. } => Some(statements),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|statement| match statement {
+                Statement::Let { pat, .. } => Some(*pat),
+                Statement::Expr(_) => None,
+            });
+
+        for pat in params.chain(let_statements) {
+            self.validate_irrefutable_pattern(pat, sink);
+        }
+    }
+
+    fn validate_irrefutable_pattern(&self, pat: PatId, sink: &mut DiagnosticSink) {
+        let ty = self.infer[pat].clone();
+        if ty.is_unknown() {
+            // An error has already been emitted for the pattern
+            return;
+        }
+
+        let deconstructed = match self.lower_pat(pat, &ty) {
+            Some(DeconstructedPat::Wild) | None => return,
+            Some(deconstructed) => deconstructed,
+        };
+
+        let missing = self.missing_patterns(&[vec![deconstructed]], &ty);
+        if !missing.is_empty() {
+            if let Some(ptr) = self.body_source_map.pat_syntax(pat) {
+                sink.push(RefutablePattern {
+                    file: self.func.source(self.db.upcast()).file_id,
+                    pat: ptr.value.syntax_node_ptr(),
+                    missing,
+                });
+            }
+        }
+    }
+
+    fn validate_match(
+        &self,
+        match_expr: ExprId,
+        scrutinee: ExprId,
+        arms: &[MatchArm],
+        sink: &mut DiagnosticSink,
+    ) {
+        let scrutinee_ty = self.infer[scrutinee].clone();
+        if scrutinee_ty.is_unknown() {
+            // An error has already been emitted for the matched expression
+            return;
+        }
+
+        // If one of the patterns could not be resolved or doesn't match the type of the matched
+        // expression an error has already been reported.
+        let pats = match arms
+            .iter()
+            .map(|arm| self.lower_pat(arm.pat, &scrutinee_ty))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(pats) => pats,
+            None => return,
+        };
+
+        let file = self.func.source(self.db.upcast()).file_id;
+        let tys = [scrutinee_ty.clone()];
+        let mut rows: Vec<Vec<DeconstructedPat>> = Vec::with_capacity(arms.len());
+        for (arm, pat) in arms.iter().zip(pats) {
+            let row = vec![pat];
+            if !self.is_useful(&rows, &row, &tys) {
+                if let Some(ptr) = self.body_source_map.pat_syntax(arm.pat) {
+                    sink.push(UnreachableMatchArm {
+                        file,
+                        pat: ptr.value.syntax_node_ptr(),
+                    });
+                }
+            }
+            rows.push(row);
+        }
+
+        let missing = self.missing_patterns(&rows, &scrutinee_ty);
+        if !missing.is_empty() {
+            let match_expr = self
+                .body_source_map
+                .expr_syntax(match_expr)
+                .expect("could not retrieve expr from source map")
+                .value
+                .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+            sink.push(MissingMatchArms {
+                file,
+                match_expr,
+                missing,
+            });
+        }
+    }
+
+    /// Converts a pattern that matches values of type `ty` to a `DeconstructedPat`. Returns `None`
+    /// if the pattern is erroneous.
+    fn lower_pat(&self, pat: PatId, ty: &Ty) -> Option<DeconstructedPat> {
+        let variant = match &self.body[pat] {
+            Pat::Wild | Pat::Bind { .. } | Pat::Missing => return Some(DeconstructedPat::Wild),
+            Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => {
+                self.infer.variant_resolution_for_pat(pat)?
+            }
+        };
+
+        if variant.ty(self.db) != *ty {
+            return None;
+        }
+
+        let index = match variant {
+            VariantDef::Struct(_) => 0,
+            VariantDef::EnumVariant(v) => v.index(self.db) as usize,
+        };
+
+        let field_tys = variant.field_types(self.db);
+        let mut fields = vec![DeconstructedPat::Wild; field_tys.len()];
+        match &self.body[pat] {
+            Pat::TupleStruct { args, .. } => {
+                for ((field, arg), field_ty) in fields.iter_mut().zip(args).zip(&field_tys) {
+                    *field = self.lower_pat(*arg, field_ty)?;
+                }
+            }
+            Pat::Record {
+                fields: field_pats, ..
+            } => {
+                let field_names = variant.field_names(self.db);
+                for field_pat in field_pats.iter() {
+                    if let Some(idx) = field_names.iter().position(|name| *name == field_pat.name) {
+                        fields[idx] = self.lower_pat(field_pat.pat, &field_tys[idx])?;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Some(DeconstructedPat::Constructor { index, fields })
+    }
+
+    /// Returns the constructors of values of type `ty`, or `None` if the values of the type cannot
+    /// be enumerated.
+    fn constructors(&self, ty: &Ty) -> Option<Vec<VariantDef>> {
+        if let Some(s) = ty.as_struct() {
+            Some(vec![s.into()])
+        } else {
+            ty.as_enum().map(|e| {
+                e.variants(self.db)
+                    .into_iter()
+                    .map(VariantDef::EnumVariant)
+                    .collect()
+            })
+        }
+    }
+
+    /// Returns whether the pattern vector `v` matches any values that are not matched by any of
+    /// the `rows`. Every column of the matrix matches values of the corresponding type in `tys`.
+    ///
+    /// This is the usefulness algorithm described in "Warnings for pattern matching" by Luc
+    /// Maranget.
+    fn is_useful(
+        &self,
+        rows: &[Vec<DeconstructedPat>],
+        v: &[DeconstructedPat],
+        tys: &[Ty],
+    ) -> bool {
+        let (head, tail) = match v.split_first() {
+            Some(split) => split,
+            None => return rows.is_empty(),
+        };
+
+        match head {
+            DeconstructedPat::Constructor { index, fields } => {
+                let constructors = self
+                    .constructors(&tys[0])
+                    .expect("a constructor pattern must match an enumerable type");
+                self.is_useful_specialized(rows, *index, &constructors[*index], fields, tail, tys)
+            }
+            DeconstructedPat::Wild => {
+                let constructors = self.constructors(&tys[0]).filter(|constructors| {
+                    (0..constructors.len()).all(|index| {
+                        rows.iter().any(|row| {
+                            matches!(&row[0], DeconstructedPat::Constructor { index: i, .. } if *i == index)
+                        })
+                    })
+                });
+
+                if let Some(constructors) = constructors {
+                    // All constructors are used by the rows, so a wildcard is only useful if it is
+                    // useful for one of the constructors
+                    constructors.iter().enumerate().any(|(index, variant)| {
+                        let fields =
+                            vec![DeconstructedPat::Wild; variant.field_types(self.db).len()];
+                        self.is_useful_specialized(rows, index, variant, &fields, tail, tys)
+                    })
+                } else {
+                    // Only the rows that start with a wildcard are relevant for constructors that
+                    // are not covered
+                    let default_rows: Vec<Vec<DeconstructedPat>> = rows
+                        .iter()
+                        .filter(|row| matches!(row[0], DeconstructedPat::Wild))
+                        .map(|row| row[1..].to_vec())
+                        .collect();
+                    self.is_useful(&default_rows, tail, &tys[1..])
+                }
+            }
+        }
+    }
+
+    /// Specializes the `rows` and `v` for the constructor with the specified `index` and
+    /// determines usefulness of the result.
+    fn is_useful_specialized(
+        &self,
+        rows: &[Vec<DeconstructedPat>],
+        index: usize,
+        variant: &VariantDef,
+        fields: &[DeconstructedPat],
+        tail: &[DeconstructedPat],
+        tys: &[Ty],
+    ) -> bool {
+        let field_tys = variant.field_types(self.db);
+        let specialized_rows: Vec<Vec<DeconstructedPat>> = rows
+            .iter()
+            .filter_map(|row| match &row[0] {
+                DeconstructedPat::Wild => Some(
+                    repeat(DeconstructedPat::Wild)
+                        .take(field_tys.len())
+                        .chain(row[1..].iter().cloned())
+                        .collect(),
+                ),
+                DeconstructedPat::Constructor { index: i, fields } if *i == index => {
+                    Some(fields.iter().chain(row[1..].iter()).cloned().collect())
+                }
+                DeconstructedPat::Constructor { .. } => None,
+            })
+            .collect();
+
+        let v: Vec<DeconstructedPat> = fields.iter().chain(tail.iter()).cloned().collect();
+        let tys: Vec<Ty> = field_tys
+            .into_iter()
+            .chain(tys[1..].iter().cloned())
+            .collect();
+        self.is_useful(&specialized_rows, &v, &tys)
+    }
+
+    /// Returns a description of the patterns that are not covered by the `rows`.
+    fn missing_patterns(&self, rows: &[Vec<DeconstructedPat>], ty: &Ty) -> Vec<String> {
+        let tys = [ty.clone()];
+        if !self.is_useful(rows, &[DeconstructedPat::Wild], &tys) {
+            return Vec::new();
+        }
+
+        let e = match ty.as_enum() {
+            Some(e) => e,
+            None => return vec!["_".to_string()],
+        };
+
+        e.variants(self.db)
+            .into_iter()
+            .filter(|variant| {
+                let pat = DeconstructedPat::Constructor {
+                    index: variant.index(self.db) as usize,
+                    fields: vec![DeconstructedPat::Wild; variant.fields(self.db).len()],
+                };
+                self.is_useful(rows, &[pat], &tys)
+            })
+            .map(|variant| {
+                let path = format!("{}::{}", e.name(self.db), variant.name(self.db));
+                match variant.kind(self.db) {
+                    StructKind::Record => format!("{} {{ .. }}", path),
+                    StructKind::Tuple => format!(
+                        "{}({})",
+                        path,
+                        vec!["_"; variant.fields(self.db).len()].join(", ")
+                    ),
+                    StructKind::Unit => path,
+                }
+            })
+            .collect()
+    }
+}
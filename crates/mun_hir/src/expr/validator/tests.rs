@@ -69,6 +69,147 @@ fn test_uninitialized_access_while() {
     )
 }
 
+#[test]
+fn test_uninitialized_access_continue() {
+    diagnostics_snapshot(
+        r#"
+    fn foo(b:int) {
+        let a:int;
+        while b < 4 {
+            b += 1;
+            if b == 2 { continue; }
+            a = b;
+            let c = a + 1;  // correct, `a` is initialized
+        }
+    }
+
+    fn bar(b:int) {
+        let a:int;
+        while b < 4 {
+            b += 1;
+            if b == 2 { a = 1; continue; }
+            let c = a + 1;  // `a` is possibly-unitialized
+        }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn test_match_exhaustiveness() {
+    diagnostics_snapshot(
+        r#"
+    enum State {
+        Idle,
+        Walking(f32),
+        Attacking { target: i32 },
+    }
+
+    fn exhaustive(state: State) -> i32 {
+        match state {
+            State::Idle => 0,
+            State::Walking(_) => 1,
+            State::Attacking { .. } => 2,
+        }
+    }
+
+    fn wildcard(state: State) -> i32 {
+        match state {
+            State::Walking(_) => 1,
+            _ => 0,
+        }
+    }
+
+    fn missing(state: State) -> i32 {
+        match state {
+            State::Idle => 0,
+        }
+    }
+
+    fn unreachable(state: State) -> i32 {
+        match state {
+            _ => 0,
+            State::Idle => 1,
+        }
+    }
+
+    fn duplicate(state: State) -> i32 {
+        match state {
+            State::Walking(speed) => 1,
+            State::Walking(_) => 2,
+            _ => 0,
+        }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn test_match_exhaustiveness_nested() {
+    diagnostics_snapshot(
+        r#"
+    enum Direction {
+        Left,
+        Right,
+    }
+
+    enum Input {
+        None,
+        Move(Direction),
+    }
+
+    fn exhaustive(input: Input) -> i32 {
+        match input {
+            Input::None => 0,
+            Input::Move(Direction::Left) => 1,
+            Input::Move(Direction::Right) => 2,
+        }
+    }
+
+    fn missing(input: Input) -> i32 {
+        match input {
+            Input::None => 0,
+            Input::Move(Direction::Left) => 1,
+        }
+    }
+
+    fn primitive(a: i32) -> i32 {
+        match a {
+            b => b,
+        }
+    }
+
+    fn primitive_missing(a: i32) -> i32 {
+        match a {}
+    }
+    "#,
+    )
+}
+
+#[test]
+fn test_refutable_pattern() {
+    diagnostics_snapshot(
+        r#"
+    enum Shape {
+        Circle(f32),
+        Square(f32),
+    }
+
+    struct Point(f32, f32);
+
+    fn irrefutable(Point(x, _): Point) -> f32 {
+        let Point(_, y) = Point(1.0, 2.0);
+        x + y
+    }
+
+    fn refutable(shape: Shape) -> f32 {
+        let Shape::Circle(radius) = shape;
+        radius
+    }
+    "#,
+    )
+}
+
 #[test]
 fn test_free_type_alias_without_type_ref() {
     diagnostics_snapshot(
@@ -46,6 +46,12 @@ impl<'d> ExprValidator<'d> {
                     self.validate_expr_access(sink, initialized_patterns, *arg, expr_side);
                 }
             }
+            Expr::MethodCall { receiver, args, .. } => {
+                self.validate_expr_access(sink, initialized_patterns, *receiver, expr_side);
+                for arg in args.iter() {
+                    self.validate_expr_access(sink, initialized_patterns, *arg, expr_side);
+                }
+            }
             Expr::Path(p) => {
                 let resolver = resolver_for_expr(self.db.upcast(), self.body.owner(), expr);
                 self.validate_path_access(
@@ -164,6 +170,25 @@ impl<'d> ExprValidator<'d> {
                     ExprKind::Normal,
                 );
             }
+            Expr::For {
+                pat,
+                iterable,
+                body,
+            } => {
+                self.validate_expr_access(sink, initialized_patterns, *iterable, ExprKind::Normal);
+                let mut body_initialized_patterns = initialized_patterns.clone();
+                self.initialize_pattern(&mut body_initialized_patterns, *pat);
+                self.validate_expr_access(
+                    sink,
+                    &mut body_initialized_patterns,
+                    *body,
+                    ExprKind::Normal,
+                );
+            }
+            Expr::Range { lhs, rhs, .. } => {
+                self.validate_expr_access(sink, initialized_patterns, *lhs, ExprKind::Normal);
+                self.validate_expr_access(sink, initialized_patterns, *rhs, ExprKind::Normal);
+            }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields.iter() {
                     self.validate_expr_access(
@@ -180,11 +205,71 @@ impl<'d> ExprValidator<'d> {
             Expr::Field { expr, .. } => {
                 self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
             }
+            Expr::Index { base, index } => {
+                self.validate_expr_access(sink, initialized_patterns, *base, ExprKind::Normal);
+                self.validate_expr_access(sink, initialized_patterns, *index, ExprKind::Normal);
+            }
+            Expr::Array(exprs) => {
+                for expr in exprs.iter() {
+                    self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
+                }
+            }
+            Expr::Match { expr, arms } => {
+                self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
+
+                // Only patterns that are initialized in all arms that do not diverge are
+                // initialized after the match expression
+                let mut arms_initialized_patterns: Option<HashSet<PatId>> = None;
+                for arm in arms.iter() {
+                    let mut arm_initialized_patterns = initialized_patterns.clone();
+                    self.initialize_pattern(&mut arm_initialized_patterns, arm.pat);
+                    self.validate_expr_access(
+                        sink,
+                        &mut arm_initialized_patterns,
+                        arm.expr,
+                        ExprKind::Normal,
+                    );
+                    if self.infer[arm.expr].is_never() {
+                        continue;
+                    }
+                    arms_initialized_patterns = Some(match arms_initialized_patterns {
+                        Some(patterns) => patterns
+                            .intersection(&arm_initialized_patterns)
+                            .copied()
+                            .collect(),
+                        None => arm_initialized_patterns,
+                    });
+                }
+                if let Some(patterns) = arms_initialized_patterns {
+                    initialized_patterns.extend(patterns);
+                }
+            }
+            Expr::Lambda { args, body, .. } => {
+                // Captured variables are copied into the lambda when it is created, so they must
+                // be initialized at that point.
+                let mut body_initialized_patterns = initialized_patterns.clone();
+                for (pat, _) in args.iter() {
+                    self.initialize_pattern(&mut body_initialized_patterns, *pat);
+                }
+                self.validate_expr_access(
+                    sink,
+                    &mut body_initialized_patterns,
+                    *body,
+                    ExprKind::Normal,
+                );
+            }
+            Expr::Continue => {}
             Expr::Literal(_) => {}
             Expr::Missing => {}
         }
     }
 
+    /// Marks the specified pattern and all its sub-patterns as initialized
+    fn initialize_pattern(&self, initialized_patterns: &mut HashSet<PatId>, pat: PatId) {
+        initialized_patterns.insert(pat);
+        self.body[pat].walk_child_pats(|pat| self.initialize_pattern(initialized_patterns, pat));
+    }
+
     fn validate_path_access(
         &self,
         sink: &mut DiagnosticSink,
@@ -0,0 +1,108 @@
+//! The generic type parameters of items, e.g. the `T` in `fn max<T>(a: T, b: T)` or the `A` and
+//! `B` in `struct Pair<A, B>`.
+
+use crate::{
+    name::{name, AsName},
+    ty::{Substitution, TyKind},
+    Name, Path,
+};
+use mun_syntax::ast::{self, NameOwner};
+
+/// The generic type parameters of a function or struct, in declaration order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GenericParams {
+    types: Vec<TypeParamData>,
+}
+
+/// A single type parameter together with the traits it is bounded by, e.g. `T: Update + Draw`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeParamData {
+    pub name: Name,
+    pub bounds: Vec<Path>,
+}
+
+impl GenericParams {
+    /// Lowers the type parameter list of the specified item
+    pub(crate) fn from_ast(node: &impl ast::TypeParamsOwner) -> Self {
+        let types = node
+            .type_param_list()
+            .into_iter()
+            .flat_map(|list| list.type_params())
+            .map(|param| TypeParamData {
+                name: param
+                    .name()
+                    .map(|n| n.as_name())
+                    .unwrap_or_else(Name::missing),
+                bounds: param
+                    .type_bound_list()
+                    .into_iter()
+                    .flat_map(|list| list.bounds())
+                    .filter_map(|bound| bound.path())
+                    .filter_map(Path::from_ast)
+                    .collect(),
+            })
+            .collect();
+        GenericParams { types }
+    }
+
+    /// Lowers the type parameter list of a function declared in a trait. The `Self` type of the
+    /// trait is added as an implicit first type parameter, which is substituted with the type that
+    /// implements the trait.
+    pub(crate) fn from_trait_item_ast(node: &impl ast::TypeParamsOwner) -> Self {
+        let mut params = Self::from_ast(node);
+        params.types.insert(
+            0,
+            TypeParamData {
+                name: name![Self],
+                bounds: Vec::new(),
+            },
+        );
+        params
+    }
+
+    /// Returns the number of type parameters
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Returns true if there are no type parameters
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Returns the names of the type parameters in declaration order
+    pub fn iter(&self) -> impl Iterator<Item = &Name> {
+        self.types.iter().map(|param| &param.name)
+    }
+
+    /// Returns the trait bounds of the type parameter at `idx`
+    pub fn bounds(&self, idx: u32) -> &[Path] {
+        self.types
+            .get(idx as usize)
+            .map_or(&[][..], |param| param.bounds.as_slice())
+    }
+
+    /// Returns the index of the type parameter with the specified `name`
+    pub fn find_by_name(&self, name: &Name) -> Option<u32> {
+        self.types
+            .iter()
+            .position(|param| &param.name == name)
+            .map(|idx| idx as u32)
+    }
+
+    /// Returns a substitution that maps every type parameter onto itself, e.g. `[T, U]` for
+    /// `fn foo<T, U>()`.
+    pub(crate) fn bound_vars(&self) -> Substitution {
+        self.types
+            .iter()
+            .enumerate()
+            .map(|(idx, param)| {
+                TyKind::Param {
+                    idx: idx as u32,
+                    name: param.name.clone(),
+                }
+                .intern()
+            })
+            .collect()
+    }
+}
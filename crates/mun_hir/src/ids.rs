@@ -1,5 +1,8 @@
 use crate::{
-    item_tree::{Function, ItemTreeId, ItemTreeNode, Struct, TypeAlias},
+    code_model::LocalEnumVariantId,
+    item_tree::{
+        Const, Enum, Function, Impl, ItemTreeId, ItemTreeNode, Static, Struct, Trait, TypeAlias,
+    },
     module_tree::LocalModuleId,
     primitive_type::PrimitiveType,
     DefDatabase, PackageId,
@@ -103,9 +106,35 @@ pub struct ModuleId {
     pub local_id: LocalModuleId,
 }
 
+/// Represents the item that contains an associated item: either a module, an `impl` block, or a
+/// trait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemContainerId {
+    ModuleId(ModuleId),
+    ImplId(ImplId),
+    TraitId(TraitId),
+}
+
+impl ItemContainerId {
+    /// Returns the module in which the container is defined
+    pub fn module(self, db: &dyn DefDatabase) -> ModuleId {
+        match self {
+            ItemContainerId::ModuleId(module) => module,
+            ItemContainerId::ImplId(impl_id) => impl_id.lookup(db).module,
+            ItemContainerId::TraitId(trait_id) => trait_id.lookup(db).module,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct FunctionId(salsa::InternId);
-pub(crate) type FunctionLoc = AssocItemLoc<Function>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FunctionLoc {
+    pub container: ItemContainerId,
+    pub id: ItemTreeId<Function>,
+}
+
 impl_intern!(
     FunctionId,
     FunctionLoc,
@@ -118,6 +147,18 @@ pub struct StructId(salsa::InternId);
 pub(crate) type StructLoc = AssocItemLoc<Struct>;
 impl_intern!(StructId, StructLoc, intern_struct, lookup_intern_struct);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnumId(salsa::InternId);
+pub(crate) type EnumLoc = AssocItemLoc<Enum>;
+impl_intern!(EnumId, EnumLoc, intern_enum, lookup_intern_enum);
+
+/// Represents an id of a variant inside an enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnumVariantId {
+    pub parent: EnumId,
+    pub local_id: LocalEnumVariantId,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TypeAliasId(salsa::InternId);
 pub(crate) type TypeAliasLoc = AssocItemLoc<TypeAlias>;
@@ -128,6 +169,26 @@ impl_intern!(
     lookup_intern_type_alias
 );
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstId(salsa::InternId);
+pub(crate) type ConstLoc = AssocItemLoc<Const>;
+impl_intern!(ConstId, ConstLoc, intern_const, lookup_intern_const);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticId(salsa::InternId);
+pub(crate) type StaticLoc = AssocItemLoc<Static>;
+impl_intern!(StaticId, StaticLoc, intern_static, lookup_intern_static);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImplId(salsa::InternId);
+pub(crate) type ImplLoc = AssocItemLoc<Impl>;
+impl_intern!(ImplId, ImplLoc, intern_impl, lookup_intern_impl);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraitId(salsa::InternId);
+pub(crate) type TraitLoc = AssocItemLoc<Trait>;
+impl_intern!(TraitId, TraitLoc, intern_trait, lookup_intern_trait);
+
 pub trait Intern {
     type ID;
     fn intern(self, db: &dyn DefDatabase) -> Self::ID;
@@ -143,7 +204,12 @@ pub enum ItemDefinitionId {
     ModuleId(ModuleId),
     FunctionId(FunctionId),
     StructId(StructId),
+    EnumId(EnumId),
+    EnumVariantId(EnumVariantId),
     TypeAliasId(TypeAliasId),
+    ConstId(ConstId),
+    StaticId(StaticId),
+    TraitId(TraitId),
     PrimitiveType(PrimitiveType),
 }
 
@@ -162,21 +228,60 @@ impl From<StructId> for ItemDefinitionId {
         ItemDefinitionId::StructId(id)
     }
 }
+impl From<EnumId> for ItemDefinitionId {
+    fn from(id: EnumId) -> Self {
+        ItemDefinitionId::EnumId(id)
+    }
+}
+impl From<EnumVariantId> for ItemDefinitionId {
+    fn from(id: EnumVariantId) -> Self {
+        ItemDefinitionId::EnumVariantId(id)
+    }
+}
 impl From<TypeAliasId> for ItemDefinitionId {
     fn from(id: TypeAliasId) -> Self {
         ItemDefinitionId::TypeAliasId(id)
     }
 }
+impl From<ConstId> for ItemDefinitionId {
+    fn from(id: ConstId) -> Self {
+        ItemDefinitionId::ConstId(id)
+    }
+}
+impl From<StaticId> for ItemDefinitionId {
+    fn from(id: StaticId) -> Self {
+        ItemDefinitionId::StaticId(id)
+    }
+}
+impl From<TraitId> for ItemDefinitionId {
+    fn from(id: TraitId) -> Self {
+        ItemDefinitionId::TraitId(id)
+    }
+}
 impl From<PrimitiveType> for ItemDefinitionId {
     fn from(id: PrimitiveType) -> Self {
         ItemDefinitionId::PrimitiveType(id)
     }
 }
 
+/// Items that can be defined in an `impl` block or a trait
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssocItemId {
+    FunctionId(FunctionId),
+}
+
+impl From<FunctionId> for AssocItemId {
+    fn from(id: FunctionId) -> Self {
+        AssocItemId::FunctionId(id)
+    }
+}
+
 /// Definitions which have a body
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DefWithBodyId {
     FunctionId(FunctionId),
+    ConstId(ConstId),
+    StaticId(StaticId),
 }
 
 impl From<FunctionId> for DefWithBodyId {
@@ -184,3 +289,15 @@ impl From<FunctionId> for DefWithBodyId {
         DefWithBodyId::FunctionId(id)
     }
 }
+
+impl From<ConstId> for DefWithBodyId {
+    fn from(id: ConstId) -> Self {
+        DefWithBodyId::ConstId(id)
+    }
+}
+
+impl From<StaticId> for DefWithBodyId {
+    fn from(id: StaticId) -> Self {
+        DefWithBodyId::StaticId(id)
+    }
+}
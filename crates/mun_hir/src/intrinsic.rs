@@ -0,0 +1,28 @@
+use crate::name::{name, Name};
+
+/// Functions that are built into the language. Intrinsics are resolved when a path cannot be
+/// resolved to any other value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intrinsic {
+    /// Returns the number of elements in an array: `len(a: [T]) -> usize`
+    Len,
+}
+
+impl Intrinsic {
+    pub const ALL: &'static [(Name, Intrinsic)] = &[(name![len], Intrinsic::Len)];
+
+    /// Returns the intrinsic with the specified `name`, if any.
+    pub fn from_name(name: &Name) -> Option<Intrinsic> {
+        Self::ALL
+            .iter()
+            .find(|(intrinsic_name, _)| intrinsic_name == name)
+            .map(|(_, intrinsic)| *intrinsic)
+    }
+
+    /// Returns the name of the intrinsic
+    pub fn name(self) -> Name {
+        match self {
+            Intrinsic::Len => name![len],
+        }
+    }
+}
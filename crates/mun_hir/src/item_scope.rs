@@ -1,6 +1,9 @@
 use crate::{
-    ids::ItemDefinitionId, module_tree::LocalModuleId, primitive_type::PrimitiveType,
-    visibility::Visibility, Name, PerNs,
+    ids::{ImplId, ItemDefinitionId},
+    module_tree::LocalModuleId,
+    primitive_type::PrimitiveType,
+    visibility::Visibility,
+    Name, PerNs,
 };
 use once_cell::sync::Lazy;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -41,6 +44,9 @@ pub struct ItemScope {
 
     /// All items that are defined in this scope
     defs: Vec<ItemDefinitionId>,
+
+    /// All `impl` blocks that are defined in this scope
+    impls: Vec<ImplId>,
 }
 
 /// A struct that is returned from `add_resolution_from_import`.
@@ -80,11 +86,21 @@ impl ItemScope {
         self.defs.iter().copied()
     }
 
+    /// Returns an iterator over all `impl` blocks defined within this scope
+    pub fn impls(&self) -> impl Iterator<Item = ImplId> + '_ {
+        self.impls.iter().copied()
+    }
+
     /// Adds an item definition to the list of definitions
     pub(crate) fn add_definition(&mut self, def: ItemDefinitionId) {
         self.defs.push(def)
     }
 
+    /// Adds an `impl` block to the list of `impl` blocks
+    pub(crate) fn add_impl(&mut self, imp: ImplId) {
+        self.impls.push(imp)
+    }
+
     /// Adds a named item resolution into the scope. Returns true if adding the resolution changes
     /// the scope.
     pub(crate) fn add_resolution(
@@ -207,7 +223,9 @@ impl PerNs<(ItemDefinitionId, Visibility)> {
         has_constructor: bool,
     ) -> PerNs<(ItemDefinitionId, Visibility)> {
         match def {
-            ItemDefinitionId::FunctionId(_) => PerNs::values((def, vis)),
+            ItemDefinitionId::FunctionId(_)
+            | ItemDefinitionId::ConstId(_)
+            | ItemDefinitionId::StaticId(_) => PerNs::values((def, vis)),
             ItemDefinitionId::StructId(_) => {
                 if has_constructor {
                     PerNs::both((def, vis), (def, vis))
@@ -215,7 +233,16 @@ impl PerNs<(ItemDefinitionId, Visibility)> {
                     PerNs::types((def, vis))
                 }
             }
+            ItemDefinitionId::EnumId(_) => PerNs::types((def, vis)),
+            ItemDefinitionId::EnumVariantId(_) => {
+                if has_constructor {
+                    PerNs::both((def, vis), (def, vis))
+                } else {
+                    PerNs::types((def, vis))
+                }
+            }
             ItemDefinitionId::TypeAliasId(_) => PerNs::types((def, vis)),
+            ItemDefinitionId::TraitId(_) => PerNs::types((def, vis)),
             ItemDefinitionId::PrimitiveType(_) => PerNs::types((def, vis)),
             ItemDefinitionId::ModuleId(_) => PerNs::types((def, vis)),
         }
@@ -109,7 +109,13 @@ struct ItemTreeData {
     functions: Arena<Function>,
     structs: Arena<Struct>,
     fields: Arena<Field>,
+    enums: Arena<Enum>,
+    variants: Arena<Variant>,
     type_aliases: Arena<TypeAlias>,
+    consts: Arena<Const>,
+    statics: Arena<Static>,
+    impls: Arena<Impl>,
+    traits: Arena<Trait>,
 
     visibilities: ItemVisibilities,
 }
@@ -225,7 +231,12 @@ macro_rules! mod_items {
 mod_items! {
     Function in functions -> ast::FunctionDef,
     Struct in structs -> ast::StructDef,
+    Enum in enums -> ast::EnumDef,
     TypeAlias in type_aliases -> ast::TypeAliasDef,
+    Const in consts -> ast::ConstDef,
+    Static in statics -> ast::StaticDef,
+    Impl in impls -> ast::ImplDef,
+    Trait in traits -> ast::TraitDef,
     Import in imports -> ast::Use,
 }
 
@@ -243,7 +254,7 @@ macro_rules! impl_index {
     };
 }
 
-impl_index!(fields: Field);
+impl_index!(fields: Field, variants: Variant);
 
 static VIS_PUB: RawVisibility = RawVisibility::Public;
 static VIS_PRIV: RawVisibility = RawVisibility::This;
@@ -299,6 +310,9 @@ pub struct Function {
     pub name: Name,
     pub visibility: RawVisibilityId,
     pub is_extern: bool,
+    /// Whether the first parameter of the function is `self`. The type of the `self` parameter is
+    /// included in `params`.
+    pub has_self_param: bool,
     pub params: Box<[TypeRef]>,
     pub ret_type: TypeRef,
     pub ast_id: FileAstId<ast::FunctionDef>,
@@ -313,6 +327,21 @@ pub struct Struct {
     pub kind: StructDefKind,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Enum {
+    pub name: Name,
+    pub visibility: RawVisibilityId,
+    pub variants: IdRange<Variant>,
+    pub ast_id: FileAstId<ast::EnumDef>,
+}
+
+/// A single variant of an enum
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Variant {
+    pub name: Name,
+    pub fields: Fields,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypeAlias {
     pub name: Name,
@@ -321,6 +350,49 @@ pub struct TypeAlias {
     pub ast_id: FileAstId<ast::TypeAliasDef>,
 }
 
+/// A constant (e.g. `const FOO: i32 = 5;`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Const {
+    pub name: Name,
+    pub visibility: RawVisibilityId,
+    pub type_ref: TypeRef,
+    pub ast_id: FileAstId<ast::ConstDef>,
+}
+
+/// A global variable (e.g. `static FOO: i32 = 5;`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Static {
+    pub name: Name,
+    pub visibility: RawVisibilityId,
+    pub type_ref: TypeRef,
+    pub ast_id: FileAstId<ast::StaticDef>,
+}
+
+/// An `impl` block that defines associated items for a type (e.g. `impl Foo { ... }`) or that
+/// implements a trait for a type (e.g. `impl Update for Foo { ... }`)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Impl {
+    pub self_ty: TypeRef,
+    pub target_trait: Option<Path>,
+    pub items: Box<[AssociatedItem]>,
+    pub ast_id: FileAstId<ast::ImplDef>,
+}
+
+/// A trait declaration (e.g. `trait Update { fn update(self, dt: f32); }`)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Trait {
+    pub name: Name,
+    pub visibility: RawVisibilityId,
+    pub items: Box<[AssociatedItem]>,
+    pub ast_id: FileAstId<ast::TraitDef>,
+}
+
+/// An item defined in an `impl` block or a trait
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AssociatedItem {
+    Function(LocalItemTreeId<Function>),
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum StructDefKind {
     /// `struct S { ... }` - type namespace only.
@@ -394,8 +466,8 @@ impl<T> PartialEq for IdRange<T> {
 impl<T> Eq for IdRange<T> {}
 
 mod diagnostics {
-    use super::{ItemTree, ModItem};
-    use crate::diagnostics::DuplicateDefinition;
+    use super::{Function, ItemTree, LocalItemTreeId, ModItem};
+    use crate::diagnostics::{DuplicateDefinition, SelfParamOutsideImpl};
     use crate::{DefDatabase, DiagnosticSink, HirDatabase, Name};
     use mun_syntax::{AstNode, SyntaxNodePtr};
 
@@ -406,6 +478,9 @@ mod diagnostics {
             first: ModItem,
             second: ModItem,
         },
+        SelfParamOutsideImpl {
+            function: LocalItemTreeId<Function>,
+        },
     }
 
     impl ItemTreeDiagnostic {
@@ -426,6 +501,17 @@ mod diagnostics {
                     first_definition: ast_ptr_from_mod(db.upcast(), item_tree, *first),
                     definition: ast_ptr_from_mod(db.upcast(), item_tree, *second),
                 }),
+                ItemTreeDiagnostic::SelfParamOutsideImpl { function } => {
+                    let self_param = item_tree
+                        .source(db.upcast(), *function)
+                        .param_list()
+                        .and_then(|param_list| param_list.self_param())
+                        .expect("function must have a self param");
+                    sink.push(SelfParamOutsideImpl {
+                        file: item_tree.file_id,
+                        self_param: SyntaxNodePtr::new(self_param.syntax()),
+                    })
+                }
             };
 
             fn ast_ptr_from_mod(
@@ -440,9 +526,16 @@ mod diagnostics {
                     ModItem::Struct(item) => {
                         SyntaxNodePtr::new(item_tree.source(db, item).syntax())
                     }
+                    ModItem::Enum(item) => SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
                     ModItem::TypeAlias(item) => {
                         SyntaxNodePtr::new(item_tree.source(db, item).syntax())
                     }
+                    ModItem::Const(item) => SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
+                    ModItem::Static(item) => {
+                        SyntaxNodePtr::new(item_tree.source(db, item).syntax())
+                    }
+                    ModItem::Impl(item) => SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
+                    ModItem::Trait(item) => SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
                     ModItem::Import(item) => {
                         SyntaxNodePtr::new(item_tree.source(db, item).syntax())
                     }
@@ -1,13 +1,14 @@
 //! This module implements the logic to convert an AST to an `ItemTree`.
 
 use super::{
-    diagnostics, Field, Fields, Function, IdRange, ItemTree, ItemTreeData, ItemTreeNode,
-    LocalItemTreeId, ModItem, RawVisibilityId, Struct, StructDefKind, TypeAlias,
+    diagnostics, AssociatedItem, Const, Enum, Field, Fields, Function, IdRange, Impl, ItemTree,
+    ItemTreeData, ItemTreeNode, LocalItemTreeId, ModItem, RawVisibilityId, Static, Struct,
+    StructDefKind, Trait, TypeAlias, Variant,
 };
 use crate::item_tree::Import;
 use crate::{
     arena::{Idx, RawId},
-    name::AsName,
+    name::{name, AsName},
     source_id::AstIdMap,
     type_ref::TypeRef,
     visibility::RawVisibility,
@@ -15,7 +16,9 @@ use crate::{
 };
 use mun_syntax::{
     ast,
-    ast::{ExternOwner, ModuleItemOwner, NameOwner, StructKind, TypeAscriptionOwner},
+    ast::{
+        ExternOwner, FunctionDefOwner, ModuleItemOwner, NameOwner, StructKind, TypeAscriptionOwner,
+    },
 };
 use smallvec::SmallVec;
 use std::{collections::HashMap, convert::TryInto, marker::PhantomData, sync::Arc};
@@ -66,14 +69,30 @@ impl Context {
             .flat_map(|items| items.0)
             .collect::<Vec<_>>();
 
+        // Only associated functions can have a `self` parameter
+        for item in top_level.iter() {
+            if let ModItem::Function(function) = item {
+                if self.data.functions[function.index].has_self_param {
+                    self.diagnostics
+                        .push(diagnostics::ItemTreeDiagnostic::SelfParamOutsideImpl {
+                            function: *function,
+                        })
+                }
+            }
+        }
+
         // Check duplicates
         let mut set = HashMap::<Name, &ModItem>::new();
         for item in top_level.iter() {
             let name = match item {
                 ModItem::Function(item) => Some(&self.data.functions[item.index].name),
                 ModItem::Struct(item) => Some(&self.data.structs[item.index].name),
+                ModItem::Enum(item) => Some(&self.data.enums[item.index].name),
                 ModItem::TypeAlias(item) => Some(&self.data.type_aliases[item.index].name),
-                ModItem::Import(_) => None,
+                ModItem::Const(item) => Some(&self.data.consts[item.index].name),
+                ModItem::Static(item) => Some(&self.data.statics[item.index].name),
+                ModItem::Trait(item) => Some(&self.data.traits[item.index].name),
+                ModItem::Impl(_) | ModItem::Import(_) => None,
             };
             if let Some(name) = name {
                 if let Some(first_item) = set.get(&name) {
@@ -102,7 +121,12 @@ impl Context {
         match item.kind() {
             ast::ModuleItemKind::FunctionDef(ast) => self.lower_function(&ast).map(Into::into),
             ast::ModuleItemKind::StructDef(ast) => self.lower_struct(&ast).map(Into::into),
+            ast::ModuleItemKind::EnumDef(ast) => self.lower_enum(&ast).map(Into::into),
             ast::ModuleItemKind::TypeAliasDef(ast) => self.lower_type_alias(&ast).map(Into::into),
+            ast::ModuleItemKind::ConstDef(ast) => self.lower_const(&ast).map(Into::into),
+            ast::ModuleItemKind::StaticDef(ast) => self.lower_static(&ast).map(Into::into),
+            ast::ModuleItemKind::ImplDef(ast) => Some(self.lower_impl(&ast).into()),
+            ast::ModuleItemKind::TraitDef(ast) => self.lower_trait(&ast).map(Into::into),
             ast::ModuleItemKind::Use(ast) => Some(ModItems(
                 self.lower_use(&ast).into_iter().map(Into::into).collect(),
             )),
@@ -145,7 +169,12 @@ impl Context {
 
         // Lower all the params
         let mut params = Vec::new();
+        let mut has_self_param = false;
         if let Some(param_list) = func.param_list() {
+            if param_list.self_param().is_some() {
+                has_self_param = true;
+                params.push(TypeRef::Path(name![Self].into()));
+            }
             for param in param_list.params() {
                 let type_ref = self.lower_type_ref_opt(param.ascribed_type());
                 params.push(type_ref);
@@ -165,6 +194,7 @@ impl Context {
             name,
             visibility,
             is_extern,
+            has_self_param,
             params: params.into_boxed_slice(),
             ret_type,
             ast_id,
@@ -173,6 +203,69 @@ impl Context {
         Some(self.data.functions.alloc(res).into())
     }
 
+    /// Lowers an `impl` block (e.g. `impl Foo { ... }` or `impl Update for Foo { ... }`). The
+    /// associated items are not added to the top level items of the module.
+    fn lower_impl(&mut self, impl_def: &ast::ImplDef) -> LocalItemTreeId<Impl> {
+        let self_ty = self.lower_type_ref_opt(impl_def.type_ref());
+        let target_trait = impl_def.path().and_then(Path::from_ast);
+        let items = self.lower_associated_items(impl_def.item_list());
+        let ast_id = self.source_ast_id_map.ast_id(impl_def);
+        let res = Impl {
+            self_ty,
+            target_trait,
+            items,
+            ast_id,
+        };
+        self.data.impls.alloc(res).into()
+    }
+
+    /// Lowers a trait declaration (e.g. `trait Update { ... }`). The associated items are not
+    /// added to the top level items of the module.
+    fn lower_trait(&mut self, trait_def: &ast::TraitDef) -> Option<LocalItemTreeId<Trait>> {
+        let name = trait_def.name()?.as_name();
+        let visibility = self.lower_visibility(trait_def);
+        let items = self.lower_associated_items(trait_def.item_list());
+        let ast_id = self.source_ast_id_map.ast_id(trait_def);
+        let res = Trait {
+            name,
+            visibility,
+            items,
+            ast_id,
+        };
+        Some(self.data.traits.alloc(res).into())
+    }
+
+    /// Lowers the items of an `impl` block or a trait and checks them for duplicate names
+    fn lower_associated_items(
+        &mut self,
+        item_list: Option<ast::ItemList>,
+    ) -> Box<[AssociatedItem]> {
+        let items = item_list
+            .into_iter()
+            .flat_map(|item_list| item_list.functions())
+            .filter_map(|func| self.lower_function(&func))
+            .map(AssociatedItem::Function)
+            .collect::<Vec<_>>();
+
+        // Check duplicates
+        let mut set = HashMap::<Name, LocalItemTreeId<Function>>::new();
+        for AssociatedItem::Function(item) in items.iter() {
+            let name = &self.data.functions[item.index].name;
+            if let Some(first_item) = set.get(name) {
+                self.diagnostics
+                    .push(diagnostics::ItemTreeDiagnostic::DuplicateDefinition {
+                        name: name.clone(),
+                        first: (*first_item).into(),
+                        second: (*item).into(),
+                    })
+            } else {
+                set.insert(name.clone(), *item);
+            }
+        }
+
+        items.into_boxed_slice()
+    }
+
     /// Lowers a struct
     fn lower_struct(&mut self, strukt: &ast::StructDef) -> Option<LocalItemTreeId<Struct>> {
         let name = strukt.name()?.as_name();
@@ -194,6 +287,43 @@ impl Context {
         Some(self.data.structs.alloc(res).into())
     }
 
+    /// Lowers an enum
+    fn lower_enum(&mut self, enum_def: &ast::EnumDef) -> Option<LocalItemTreeId<Enum>> {
+        let name = enum_def.name()?.as_name();
+        let visibility = self.lower_visibility(enum_def);
+        let variants = match enum_def.variant_list() {
+            Some(variant_list) => self.lower_variants(&variant_list),
+            None => IdRange::new(self.next_variant_idx()..self.next_variant_idx()),
+        };
+        let ast_id = self.source_ast_id_map.ast_id(enum_def);
+        let res = Enum {
+            name,
+            visibility,
+            variants,
+            ast_id,
+        };
+        Some(self.data.enums.alloc(res).into())
+    }
+
+    /// Lowers the variants of an enum (e.g. `{ A, B(i32), C { a: f32 } }`)
+    fn lower_variants(&mut self, variants: &ast::EnumVariantList) -> IdRange<Variant> {
+        let start = self.next_variant_idx();
+        for variant in variants.variants() {
+            if let Some(data) = self.lower_variant(&variant) {
+                let _idx = self.data.variants.alloc(data);
+            }
+        }
+        let end = self.next_variant_idx();
+        IdRange::new(start..end)
+    }
+
+    /// Lowers a single enum variant (e.g. `B(i32)`)
+    fn lower_variant(&mut self, variant: &ast::EnumVariant) -> Option<Variant> {
+        let name = variant.name()?.as_name();
+        let fields = self.lower_fields(&variant.kind());
+        Some(Variant { name, fields })
+    }
+
     /// Lowers the fields of a struct or enum
     fn lower_fields(&mut self, struct_kind: &ast::StructKind) -> Fields {
         match struct_kind {
@@ -265,6 +395,36 @@ impl Context {
         Some(self.data.type_aliases.alloc(res).into())
     }
 
+    /// Lowers a constant (e.g. `const FOO: i32 = 5;`)
+    fn lower_const(&mut self, const_def: &ast::ConstDef) -> Option<LocalItemTreeId<Const>> {
+        let name = const_def.name()?.as_name();
+        let visibility = self.lower_visibility(const_def);
+        let type_ref = self.lower_type_ref_opt(const_def.ascribed_type());
+        let ast_id = self.source_ast_id_map.ast_id(const_def);
+        let res = Const {
+            name,
+            visibility,
+            type_ref,
+            ast_id,
+        };
+        Some(self.data.consts.alloc(res).into())
+    }
+
+    /// Lowers a global variable (e.g. `static FOO: i32 = 5;`)
+    fn lower_static(&mut self, static_def: &ast::StaticDef) -> Option<LocalItemTreeId<Static>> {
+        let name = static_def.name()?.as_name();
+        let visibility = self.lower_visibility(static_def);
+        let type_ref = self.lower_type_ref_opt(static_def.ascribed_type());
+        let ast_id = self.source_ast_id_map.ast_id(static_def);
+        let res = Static {
+            name,
+            visibility,
+            type_ref,
+            ast_id,
+        };
+        Some(self.data.statics.alloc(res).into())
+    }
+
     /// Lowers an `ast::TypeRef`
     fn lower_type_ref(&self, type_ref: &ast::TypeRef) -> TypeRef {
         TypeRef::from_ast(type_ref.clone())
@@ -283,6 +443,17 @@ impl Context {
         self.data.visibilities.alloc(vis)
     }
 
+    /// Returns the `Idx` of the next `Variant`
+    fn next_variant_idx(&self) -> Idx<Variant> {
+        let idx: u32 = self
+            .data
+            .variants
+            .len()
+            .try_into()
+            .expect("too many variants");
+        Idx::from_raw(RawId::from(idx))
+    }
+
     /// Returns the `Idx` of the next `Field`
     fn next_field_idx(&self) -> Idx<Field> {
         let idx: u32 = self.data.fields.len().try_into().expect("too many fields");
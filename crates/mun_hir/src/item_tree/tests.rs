@@ -1,7 +1,7 @@
 use crate::with_fixture::WithFixture;
 use crate::{
     item_tree::Fields,
-    item_tree::{ItemTree, ModItem},
+    item_tree::{AssociatedItem, ItemTree, ModItem},
     mock::MockDatabase,
     DefDatabase,
 };
@@ -41,9 +41,49 @@ fn format_mod_item(out: &mut String, tree: &ItemTree, item: ModItem) -> fmt::Res
                 _ => {}
             };
         }
+        ModItem::Enum(item) => {
+            write!(out, "{:?}", tree[item])?;
+            for variant in tree[item].variants.clone() {
+                write!(children, "{:?}\n", tree[variant])?;
+                match &tree[variant].fields {
+                    Fields::Record(a) | Fields::Tuple(a) => {
+                        for field in a.clone() {
+                            write!(children, "  {:?}\n", tree[field])?;
+                        }
+                    }
+                    _ => {}
+                };
+            }
+        }
         ModItem::TypeAlias(item) => {
             write!(out, "{:?}", tree[item])?;
         }
+        ModItem::Const(item) => {
+            write!(out, "{:?}", tree[item])?;
+        }
+        ModItem::Static(item) => {
+            write!(out, "{:?}", tree[item])?;
+        }
+        ModItem::Impl(item) => {
+            write!(out, "{:?}", tree[item])?;
+            for associated_item in tree[item].items.iter() {
+                match associated_item {
+                    AssociatedItem::Function(item) => {
+                        write!(children, "{:?}\n", tree[*item])?;
+                    }
+                }
+            }
+        }
+        ModItem::Trait(item) => {
+            write!(out, "{:?}", tree[item])?;
+            for associated_item in tree[item].items.iter() {
+                match associated_item {
+                    AssociatedItem::Function(item) => {
+                        write!(children, "{:?}\n", tree[*item])?;
+                    }
+                }
+            }
+        }
         ModItem::Import(item) => {
             write!(out, "{:?}", tree[item])?;
         }
@@ -85,3 +125,60 @@ fn top_level_items() {
     )
     .unwrap());
 }
+
+#[test]
+fn enums() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    enum Foo {
+        A,
+        B(i32, u8),
+        C { a: f32 },
+    }
+    pub enum Bar {}
+    "#
+    )
+    .unwrap());
+}
+
+#[test]
+fn impls() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    struct Foo;
+    impl Foo {
+        fn new() -> Foo {}
+        pub fn get(self, a: i32) -> i32 {}
+    }
+    "#
+    )
+    .unwrap());
+}
+
+#[test]
+fn consts_and_statics() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    const FOO: i32 = 5;
+    pub static BAR: f64 = 1.0;
+    static BAZ = 3;
+    "#
+    )
+    .unwrap());
+}
+
+#[test]
+fn traits() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    pub trait Update {
+        fn update(self, dt: f32);
+    }
+    struct Foo;
+    impl Update for Foo {
+        fn update(self, dt: f32) {}
+    }
+    "#
+    )
+    .unwrap());
+}
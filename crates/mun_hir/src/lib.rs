@@ -8,6 +8,7 @@
 pub use salsa;
 
 pub use crate::{
+    consteval::ConstValue,
     db::{
         AstDatabase, AstDatabaseStorage, DefDatabase, DefDatabaseStorage, HirDatabase,
         HirDatabaseStorage, InternDatabase, InternDatabaseStorage, SourceDatabase,
@@ -16,12 +17,14 @@ pub use crate::{
     diagnostics::{Diagnostic, DiagnosticSink},
     display::HirDisplay,
     expr::{
-        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Literal, LogicOp, Ordering, Pat,
-        PatId, RecordLitField, Statement, UnaryOp,
+        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Literal, LogicOp, MatchArm,
+        Ordering, Pat, PatId, RangeOp, RecordFieldPat, RecordLitField, Statement, UnaryOp,
     },
+    generics::GenericParams,
     ids::{ItemLoc, ModuleId},
     in_file::InFile,
     input::{FileId, SourceRoot, SourceRootId},
+    intrinsic::Intrinsic,
     name::Name,
     name_resolution::PerNs,
     package_set::{PackageId, PackageSet},
@@ -29,16 +32,17 @@ pub use crate::{
     primitive_type::{FloatBitness, IntBitness, Signedness},
     resolve::{resolver_for_expr, resolver_for_scope, Resolver, TypeNs, ValueNs},
     ty::{
-        lower::CallableDef, FloatTy, InferenceResult, IntTy, ResolveBitness, Substitution, Ty,
-        TyKind,
+        lower::CallableDef, FloatTy, FnSig, InferenceResult, IntTy, ResolveBitness, Substitution,
+        Ty, TyKind,
     },
     visibility::{HasVisibility, Visibility},
 };
 use crate::{name::AsName, source_id::AstIdMap};
 
 pub use self::code_model::{
-    Field, Function, FunctionData, HasSource, Module, ModuleDef, Package, Struct, StructMemoryKind,
-    TypeAlias,
+    AssocItem, Const, Enum, EnumVariant, Field, Function, FunctionData, HasSource, Impl, Module,
+    ModuleDef, Package, Static, Struct, StructMemoryKind, Trait, TypeAlias, VariantDef,
+    VariantField,
 };
 
 #[macro_use]
@@ -46,13 +50,16 @@ mod macros;
 #[macro_use]
 mod arena;
 mod code_model;
+mod consteval;
 mod db;
 pub mod diagnostics;
 mod display;
 mod expr;
+mod generics;
 mod ids;
 mod in_file;
 mod input;
+mod intrinsic;
 mod item_tree;
 pub mod line_index;
 mod module_tree;
@@ -106,11 +106,26 @@ pub mod known {
     known_names!(
         // Primitives
         int, isize, i8, i16, i32, i64, i128, uint, usize, u8, u16, u32, u64, u128, float, f32, f64,
-        bool,
+        bool, string,
     );
 
+    known_names!(
+        // Intrinsics
+        len,
+    );
+
+    // `self` and `Self` are keywords and can therefore not be defined with `known_names!`
+    pub const SELF_PARAM: super::Name = super::Name::new_inline("self");
+    pub const SELF_TYPE: super::Name = super::Name::new_inline("Self");
+
     #[macro_export]
     macro_rules! name {
+        (self) => {
+            $crate::name::known::SELF_PARAM
+        };
+        (Self) => {
+            $crate::name::known::SELF_TYPE
+        };
         ($ident:ident) => {
             $crate::name::known::$ident
         };
@@ -1,5 +1,6 @@
 use crate::{
-    ids::{ItemDefinitionId, ModuleId},
+    code_model::StructKind,
+    ids::{EnumVariantId, ItemDefinitionId, ModuleId},
     item_scope::BUILTIN_SCOPE,
     module_tree::LocalModuleId,
     package_defs::PackageDefs,
@@ -62,18 +63,42 @@ impl PackageDefs {
         (res.resolved_def, res.segment_index)
     }
 
-    /// Resolves the specified `name` from within the specified `module`
+    /// Resolves the specified `name` from within the specified `module`. Besides the items in
+    /// scope of the module, this includes the packages the package depends on and builtin types.
     fn resolve_name_in_module(
         &self,
-        _db: &dyn DefDatabase,
+        db: &dyn DefDatabase,
         module: LocalModuleId,
         name: &Name,
     ) -> PerNs<(ItemDefinitionId, Visibility)> {
         self[module]
             .get(name)
+            .or(self.resolve_name_in_dependencies(db, name))
             .or(BUILTIN_SCOPE.get(name).copied().unwrap_or_else(PerNs::none))
     }
 
+    /// Resolves the specified `name` to the root module of a package the package depends on
+    fn resolve_name_in_dependencies(
+        &self,
+        db: &dyn DefDatabase,
+        name: &Name,
+    ) -> PerNs<(ItemDefinitionId, Visibility)> {
+        match db
+            .packages()
+            .find_dependency(self.module_tree.package, name)
+        {
+            Some(package) => PerNs::types((
+                ModuleId {
+                    package,
+                    local_id: db.module_tree(package).root,
+                }
+                .into(),
+                Visibility::Public,
+            )),
+            None => PerNs::none(),
+        }
+    }
+
     /// Resolves the specified `path` from within the specified `module`. Also returns whether or
     /// not additions to the `PackageDef` would change the result or whether a fixed point has been
     /// reached. This is useful when resolving all imports.
@@ -118,6 +143,9 @@ impl PackageDefs {
             )),
         };
 
+        // The package in which the path is currently being resolved
+        let mut package = self.module_tree.package;
+
         for (i, segment) in segments {
             let (curr, vis) = match curr_per_ns.take_types() {
                 Some(r) => r,
@@ -126,25 +154,64 @@ impl PackageDefs {
                 }
             };
 
+            if let ItemDefinitionId::ModuleId(module) = curr {
+                package = module.package;
+            }
+
             curr_per_ns = match curr {
-                ItemDefinitionId::ModuleId(module) => self[module.local_id].get(&segment),
-                // TODO: Enum variants
+                ItemDefinitionId::ModuleId(module)
+                    if module.package == self.module_tree.package =>
+                {
+                    self[module.local_id].get(&segment)
+                }
+                ItemDefinitionId::ModuleId(module) => {
+                    // Only the public items of other packages are accessible
+                    db.package_defs(module.package)[module.local_id]
+                        .get(&segment)
+                        .and_then(|(item, vis)| {
+                            if vis.is_externally_visible() {
+                                Some((item, vis))
+                            } else {
+                                None
+                            }
+                        })
+                }
+                ItemDefinitionId::EnumId(e) => {
+                    let data = db.enum_data(e);
+                    match data.variant(segment) {
+                        Some(local_id) => {
+                            let variant = EnumVariantId {
+                                parent: e,
+                                local_id,
+                            };
+                            // Variants share the visibility of their enum
+                            PerNs::from_definition(
+                                variant.into(),
+                                vis,
+                                data.variants[local_id].kind != StructKind::Record,
+                            )
+                        }
+                        None => {
+                            return ResolvePathResult::with(
+                                PerNs::types((e.into(), vis)),
+                                ReachedFixedPoint::Yes,
+                                Some(i),
+                                Some(package),
+                            );
+                        }
+                    }
+                }
                 s => {
                     return ResolvePathResult::with(
                         PerNs::types((s, vis)),
                         ReachedFixedPoint::Yes,
                         Some(i),
-                        Some(self.module_tree.package),
+                        Some(package),
                     );
                 }
             };
         }
 
-        ResolvePathResult::with(
-            curr_per_ns,
-            ReachedFixedPoint::Yes,
-            None,
-            Some(self.module_tree.package),
-        )
+        ResolvePathResult::with(curr_per_ns, ReachedFixedPoint::Yes, None, Some(package))
     }
 }
@@ -1,12 +1,15 @@
 use super::PackageDefs;
 use crate::{
     ids::ItemDefinitionId,
-    ids::{FunctionLoc, Intern, StructLoc, TypeAliasLoc},
+    ids::{
+        ConstLoc, EnumLoc, FunctionLoc, ImplLoc, Intern, ItemContainerId, StaticLoc, StructLoc,
+        TraitLoc, TypeAliasLoc,
+    },
     item_scope::ImportType,
     item_scope::{ItemScope, PerNsGlobImports},
     item_tree::{
-        self, Function, ItemTree, ItemTreeId, LocalItemTreeId, ModItem, Struct, StructDefKind,
-        TypeAlias,
+        self, Const, Function, ItemTree, ItemTreeId, LocalItemTreeId, ModItem, Static, Struct,
+        StructDefKind, Trait, TypeAlias,
     },
     module_tree::LocalModuleId,
     name_resolution::ReachedFixedPoint,
@@ -280,6 +283,33 @@ impl<'db> DefCollector<'db> {
 
         if import.is_glob {
             match resolution.take_types() {
+                Some((ItemDefinitionId::ModuleId(m), _)) if m.package != self.package_id => {
+                    // The definitions of other packages are complete, so only their public items
+                    // have to be imported once.
+                    let package_defs = self.db.package_defs(m.package);
+                    let resolutions = package_defs[m.local_id]
+                        .entries()
+                        .map(|(n, res)| ImportResolution {
+                            name: Some(n.clone()),
+                            resolution: res.and_then(|(item, vis)| {
+                                if vis.is_externally_visible() {
+                                    Some((item, vis))
+                                } else {
+                                    None
+                                }
+                            }),
+                        })
+                        .filter(|res| !res.resolution.is_none())
+                        .collect::<Vec<_>>();
+
+                    self.update(
+                        import_module_id,
+                        import_visibility,
+                        ImportType::Glob,
+                        import.source,
+                        &resolutions,
+                    );
+                }
                 Some((ItemDefinitionId::ModuleId(m), _)) => {
                     let scope = &self.package_defs[m.local_id];
 
@@ -502,7 +532,15 @@ impl<'a> ModCollectorContext<'a, '_> {
             } = match item {
                 ModItem::Function(id) => self.collect_function(id),
                 ModItem::Struct(id) => self.collect_struct(id),
+                ModItem::Enum(id) => self.collect_enum(id),
                 ModItem::TypeAlias(id) => self.collect_type_alias(id),
+                ModItem::Const(id) => self.collect_const(id),
+                ModItem::Static(id) => self.collect_static(id),
+                ModItem::Trait(id) => self.collect_trait(id),
+                ModItem::Impl(id) => {
+                    self.collect_impl(id);
+                    continue;
+                }
                 ModItem::Import(id) => {
                     self.collect_import(id);
                     continue;
@@ -531,16 +569,30 @@ impl<'a> ModCollectorContext<'a, '_> {
         });
     }
 
+    /// Collects an `impl` block. The items of an `impl` block are not added to the scope of the
+    /// module, they are accessed through the type of the `impl` block instead.
+    fn collect_impl(&mut self, id: LocalItemTreeId<item_tree::Impl>) {
+        let impl_id = ImplLoc {
+            module: ModuleId {
+                package: self.def_collector.package_id,
+                local_id: self.module_id,
+            },
+            id: ItemTreeId::new(self.file_id, id),
+        }
+        .intern(self.def_collector.db);
+        self.def_collector.package_defs.modules[self.module_id].add_impl(impl_id);
+    }
+
     /// Collects the definition data from a `Function`
     #[warn(clippy::unnecessary_wraps)]
     fn collect_function(&self, id: LocalItemTreeId<Function>) -> DefData<'a> {
         let func = &self.item_tree[id];
         DefData {
             id: FunctionLoc {
-                module: ModuleId {
+                container: ItemContainerId::ModuleId(ModuleId {
                     package: self.def_collector.package_id,
                     local_id: self.module_id,
-                },
+                }),
                 id: ItemTreeId::new(self.file_id, id),
             }
             .intern(self.def_collector.db)
@@ -570,6 +622,25 @@ impl<'a> ModCollectorContext<'a, '_> {
         }
     }
 
+    /// Collects the definition data from an `Enum`
+    fn collect_enum(&self, id: LocalItemTreeId<item_tree::Enum>) -> DefData<'a> {
+        let enum_def = &self.item_tree[id];
+        DefData {
+            id: EnumLoc {
+                module: ModuleId {
+                    package: self.def_collector.package_id,
+                    local_id: self.module_id,
+                },
+                id: ItemTreeId::new(self.file_id, id),
+            }
+            .intern(self.def_collector.db)
+            .into(),
+            name: &enum_def.name,
+            visibility: &self.item_tree[enum_def.visibility],
+            has_constructor: false,
+        }
+    }
+
     /// Collects the definition data from a `TypeAlias`
     fn collect_type_alias(&self, id: LocalItemTreeId<TypeAlias>) -> DefData<'a> {
         let type_alias = &self.item_tree[id];
@@ -588,6 +659,63 @@ impl<'a> ModCollectorContext<'a, '_> {
             has_constructor: false,
         }
     }
+
+    /// Collects the definition data from a `Const`
+    fn collect_const(&self, id: LocalItemTreeId<Const>) -> DefData<'a> {
+        let const_def = &self.item_tree[id];
+        DefData {
+            id: ConstLoc {
+                module: ModuleId {
+                    package: self.def_collector.package_id,
+                    local_id: self.module_id,
+                },
+                id: ItemTreeId::new(self.file_id, id),
+            }
+            .intern(self.def_collector.db)
+            .into(),
+            name: &const_def.name,
+            visibility: &self.item_tree[const_def.visibility],
+            has_constructor: false,
+        }
+    }
+
+    /// Collects the definition data from a `Static`
+    fn collect_static(&self, id: LocalItemTreeId<Static>) -> DefData<'a> {
+        let static_def = &self.item_tree[id];
+        DefData {
+            id: StaticLoc {
+                module: ModuleId {
+                    package: self.def_collector.package_id,
+                    local_id: self.module_id,
+                },
+                id: ItemTreeId::new(self.file_id, id),
+            }
+            .intern(self.def_collector.db)
+            .into(),
+            name: &static_def.name,
+            visibility: &self.item_tree[static_def.visibility],
+            has_constructor: false,
+        }
+    }
+
+    /// Collects the definition data from a `Trait`
+    fn collect_trait(&self, id: LocalItemTreeId<Trait>) -> DefData<'a> {
+        let trait_def = &self.item_tree[id];
+        DefData {
+            id: TraitLoc {
+                module: ModuleId {
+                    package: self.def_collector.package_id,
+                    local_id: self.module_id,
+                },
+                id: ItemTreeId::new(self.file_id, id),
+            }
+            .intern(self.def_collector.db)
+            .into(),
+            name: &trait_def.name,
+            visibility: &self.item_tree[trait_def.visibility],
+            has_constructor: false,
+        }
+    }
 }
 
 struct DefData<'a> {
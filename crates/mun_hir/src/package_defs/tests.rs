@@ -1,9 +1,11 @@
 use crate::{
     db::DefDatabase, ids::ItemDefinitionId, mock::MockDatabase, package_defs::PackageDefs,
-    with_fixture::WithFixture, DiagnosticSink, Function, HirDatabase, Module, Package, Struct,
-    TypeAlias,
+    with_fixture::WithFixture, Const, Diagnostic, DiagnosticSink, Enum, FileId, Function,
+    HirDatabase, Module, Name, Package, PackageSet, SourceDatabase, SourceRoot, SourceRootId,
+    Static, Struct, Trait, TypeAlias,
 };
 use rustc_hash::FxHashSet;
+use std::sync::Arc;
 
 #[test]
 fn use_alias() {
@@ -70,6 +72,19 @@ fn use_wildcard() {
     )
 }
 
+#[test]
+fn use_enum() {
+    resolve_snapshot(
+        r#"
+    //- /foo.mun
+    pub enum Color { Red, Green }
+
+    //- /bar.mun
+    use super::foo::Color;
+    "#,
+    )
+}
+
 #[test]
 fn use_self() {
     resolve_snapshot(
@@ -144,6 +159,99 @@ fn use_() {
     )
 }
 
+#[test]
+fn use_trait() {
+    resolve_snapshot(
+        r#"
+    //- /foo.mun
+    pub trait Update {
+        fn update(self, dt: f32);
+    }
+
+    //- /bar.mun
+    use package::foo::Update;
+
+    pub struct Player;
+    impl Update for Player {
+        fn update(self, dt: f32) {}
+    }
+    "#,
+    )
+}
+
+#[test]
+fn use_dependency() {
+    let mut db = MockDatabase::default();
+    let mut packages = PackageSet::default();
+
+    let main = packages.add_package(SourceRootId(0));
+    let math = packages.add_named_package(SourceRootId(1), "math");
+    packages.add_dependency(main, math);
+
+    for (file_id, source_root_id, text) in [
+        (
+            FileId(0),
+            SourceRootId(0),
+            r#"
+            use math::add;          // works
+            use math::vec::*;       // works, imports `Vec2`
+            use math::internal;     // doesnt work (internal is only visible within math)
+            "#,
+        ),
+        (
+            FileId(1),
+            SourceRootId(1),
+            "pub fn add(a: i32, b: i32) -> i32 { a + b }\npub(package) fn internal() {}",
+        ),
+    ]
+    .iter()
+    {
+        db.set_file_text(*file_id, Arc::from(*text));
+        db.set_file_source_root(*file_id, *source_root_id);
+        let mut source_root = SourceRoot::default();
+        source_root.insert_file(*file_id, "mod.mun");
+        db.set_source_root(*source_root_id, Arc::new(source_root));
+    }
+
+    // Add a submodule to the dependency
+    let mut source_root = SourceRoot::clone(&db.source_root(SourceRootId(1)));
+    db.set_file_text(FileId(2), Arc::from("pub struct Vec2;"));
+    db.set_file_source_root(FileId(2), SourceRootId(1));
+    source_root.insert_file(FileId(2), "vec.mun");
+    db.set_source_root(SourceRootId(1), Arc::new(source_root));
+
+    db.set_packages(Arc::new(packages));
+
+    let main_module = Package { id: main }.root_module(&db);
+    let mut diagnostics = Vec::new();
+    main_module.diagnostics(
+        &db,
+        &mut DiagnosticSink::new(|diag| diagnostics.push(diag.message())),
+    );
+    assert_eq!(diagnostics, vec!["unresolved import".to_owned()]);
+
+    let package_defs = db.package_defs(main);
+    let scope = &package_defs[main_module.id.local_id];
+    for name in ["add", "Vec2"].iter() {
+        let (def, _) = scope
+            .get(&Name::new(name))
+            .take_types()
+            .or_else(|| scope.get(&Name::new(name)).take_values())
+            .unwrap_or_else(|| panic!("`{}` is not imported", name));
+        let module: Module = match def {
+            ItemDefinitionId::FunctionId(f) => Function::from(f).module(&db),
+            ItemDefinitionId::StructId(s) => Struct::from(s).module(&db),
+            _ => panic!("unexpected definition for `{}`", name),
+        };
+        assert_eq!(module.id.package, math);
+    }
+    assert!(scope.get(&Name::new("internal")).is_none());
+
+    // Items of a dependency are prefixed with the name of its package
+    let math_root = Package { id: math }.root_module(&db);
+    assert_eq!(math_root.full_name(&db), "math");
+}
+
 fn resolve_snapshot(text: &str) {
     let text = text.trim().replace("\n    ", "\n");
     let resolved = resolve(&text);
@@ -245,6 +353,21 @@ fn tree_for_module(
                     node.push(format!("use struct {}", fully_qualified_name));
                 }
             }
+            ItemDefinitionId::EnumId(e) => {
+                let enum_def: Enum = (*e).into();
+                let name = enum_def.name(db);
+                if is_local {
+                    node.push(format!("enum {}", name));
+                } else {
+                    let fully_qualified_name = format!(
+                        "{}::{}",
+                        fully_qualified_module_path(db, enum_def.module(db)),
+                        name
+                    );
+                    node.push(format!("use enum {}", fully_qualified_name));
+                }
+            }
+            ItemDefinitionId::EnumVariantId(_) => {}
             ItemDefinitionId::TypeAliasId(alias) => {
                 let alias: TypeAlias = (*alias).into();
                 let name = alias.name(db);
@@ -259,6 +382,48 @@ fn tree_for_module(
                     node.push(format!("use type {}", fully_qualified_name));
                 }
             }
+            ItemDefinitionId::ConstId(c) => {
+                let const_def: Const = (*c).into();
+                let name = const_def.name(db);
+                if is_local {
+                    node.push(format!("const {}", name));
+                } else {
+                    let fully_qualified_name = format!(
+                        "{}::{}",
+                        fully_qualified_module_path(db, const_def.module(db)),
+                        name
+                    );
+                    node.push(format!("use const {}", fully_qualified_name));
+                }
+            }
+            ItemDefinitionId::StaticId(s) => {
+                let static_def: Static = (*s).into();
+                let name = static_def.name(db);
+                if is_local {
+                    node.push(format!("static {}", name));
+                } else {
+                    let fully_qualified_name = format!(
+                        "{}::{}",
+                        fully_qualified_module_path(db, static_def.module(db)),
+                        name
+                    );
+                    node.push(format!("use static {}", fully_qualified_name));
+                }
+            }
+            ItemDefinitionId::TraitId(t) => {
+                let trait_def: Trait = (*t).into();
+                let name = trait_def.name(db);
+                if is_local {
+                    node.push(format!("trait {}", name));
+                } else {
+                    let fully_qualified_name = format!(
+                        "{}::{}",
+                        fully_qualified_module_path(db, trait_def.module(db)),
+                        name
+                    );
+                    node.push(format!("use trait {}", fully_qualified_name));
+                }
+            }
             ItemDefinitionId::PrimitiveType(_) => {}
         }
     }
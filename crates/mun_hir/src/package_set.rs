@@ -1,4 +1,4 @@
-use crate::SourceRootId;
+use crate::{Name, SourceRootId};
 use rustc_hash::FxHashMap;
 use std::ops::Index;
 
@@ -7,6 +7,13 @@ use std::ops::Index;
 pub struct PackageData {
     /// The source root that holds the source files
     pub source_root: SourceRootId,
+
+    /// The name of the package, if it can be depended upon by other packages. The items of a named
+    /// package are prefixed with its name.
+    pub name: Option<Name>,
+
+    /// The packages that this package depends on
+    pub dependencies: Vec<PackageId>,
 }
 
 /// Represents the id of a single package, all packages have a unique id, the main package and all
@@ -23,16 +30,63 @@ pub struct PackageSet {
 impl PackageSet {
     /// Adds a new package to the package set
     pub fn add_package(&mut self, source_root: SourceRootId) -> PackageId {
-        let data = PackageData { source_root };
-        let package_id = PackageId(self.arena.len() as u32);
-        self.arena.insert(package_id, data);
-        package_id
+        self.alloc_package(source_root, None)
+    }
+
+    /// Adds a new package with the specified `name` to the package set. Other packages can depend
+    /// on it with [`PackageSet::add_dependency`].
+    pub fn add_named_package(
+        &mut self,
+        source_root: SourceRootId,
+        name: impl AsRef<str>,
+    ) -> PackageId {
+        self.alloc_package(source_root, Some(Name::new(name)))
+    }
+
+    /// Adds a dependency of the package `from` on the package `to`, which makes the root module of
+    /// `to` accessible from `from` through its name.
+    ///
+    /// Panics if `to` was not added with a name.
+    pub fn add_dependency(&mut self, from: PackageId, to: PackageId) {
+        assert!(
+            self.arena[&to].name.is_some(),
+            "cannot depend on a package without a name"
+        );
+
+        let dependencies = &mut self
+            .arena
+            .get_mut(&from)
+            .expect("unknown package")
+            .dependencies;
+        if !dependencies.contains(&to) {
+            dependencies.push(to);
+        }
+    }
+
+    /// Finds the dependency of the package `from` with the specified `name`
+    pub fn find_dependency(&self, from: PackageId, name: &Name) -> Option<PackageId> {
+        self.arena[&from]
+            .dependencies
+            .iter()
+            .copied()
+            .find(|dependency| self.arena[dependency].name.as_ref() == Some(name))
     }
 
     /// Iterates over all packages
     pub fn iter(&self) -> impl Iterator<Item = PackageId> + '_ {
         self.arena.keys().copied()
     }
+
+    fn alloc_package(&mut self, source_root: SourceRootId, name: Option<Name>) -> PackageId {
+        let data = PackageData {
+            source_root,
+            name,
+            dependencies: Vec::new(),
+        };
+        let package_id = PackageId(self.arena.len() as u32);
+        self.arena.insert(package_id, data);
+        package_id
+    }
 }
 
 impl Index<PackageId> for PackageSet {
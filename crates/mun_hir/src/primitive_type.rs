@@ -45,6 +45,7 @@ pub enum PrimitiveType {
     Float(PrimitiveFloat),
     Int(PrimitiveInt),
     Bool,
+    String,
 }
 
 impl PrimitiveType {
@@ -52,6 +53,8 @@ impl PrimitiveType {
     pub const ALL: &'static [(Name, PrimitiveType)] = &[
         (name![bool], PrimitiveType::Bool),
 
+        (name![string], PrimitiveType::String),
+
         (name![isize], PrimitiveType::Int(PrimitiveInt::ISIZE)),
         (name![i8], PrimitiveType::Int(PrimitiveInt::I8)),
         (name![i16], PrimitiveType::Int(PrimitiveInt::I16)),
@@ -75,6 +78,7 @@ impl fmt::Display for PrimitiveType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let type_name = match self {
             PrimitiveType::Bool => "bool",
+            PrimitiveType::String => "string",
             PrimitiveType::Int(PrimitiveInt {
                 signedness,
                 bitness,
@@ -1,14 +1,18 @@
+use crate::generics::GenericParams;
 use crate::ids::{
-    DefWithBodyId, FunctionId, ItemDefinitionId, Lookup, ModuleId, StructId, TypeAliasId,
+    ConstId, DefWithBodyId, EnumId, EnumVariantId, FunctionId, ImplId, ItemContainerId,
+    ItemDefinitionId, Lookup, ModuleId, StaticId, StructId, TraitId, TypeAliasId,
 };
+use crate::intrinsic::Intrinsic;
 use crate::item_scope::BUILTIN_SCOPE;
 use crate::module_tree::LocalModuleId;
+use crate::name::name;
 use crate::package_defs::PackageDefs;
 use crate::primitive_type::PrimitiveType;
 use crate::visibility::RawVisibility;
 use crate::{
-    expr::scope::LocalScopeId, expr::PatId, DefDatabase, ExprId, ExprScopes, Name, Path, PerNs,
-    Visibility,
+    expr::scope::LocalScopeId, expr::PatId, DefDatabase, ExprId, ExprScopes, Name, Path, PathKind,
+    PerNs, Visibility,
 };
 use std::sync::Arc;
 
@@ -22,6 +26,12 @@ pub(crate) enum Scope {
     /// All the items and imported names of a module
     ModuleScope(ModuleItemMap),
 
+    /// Brings the `Self` type of an `impl` block into scope
+    ImplScope(ImplId),
+
+    /// Brings the type parameters of a generic function or struct into scope
+    GenericParams(Arc<GenericParams>),
+
     /// Local bindings
     ExprScope(ExprScope),
 }
@@ -50,13 +60,25 @@ pub enum ValueNs {
     LocalBinding(PatId),
     FunctionId(FunctionId),
     StructId(StructId),
+    EnumVariantId(EnumVariantId),
+    ConstId(ConstId),
+    StaticId(StaticId),
+    Intrinsic(Intrinsic),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TypeNs {
     StructId(StructId),
+    EnumId(EnumId),
+    EnumVariantId(EnumVariantId),
     TypeAliasId(TypeAliasId),
     PrimitiveType(PrimitiveType),
+    /// A trait, which is not a type on its own but can be used to bound type parameters
+    TraitId(TraitId),
+    /// The `Self` type of an `impl` block
+    SelfType(ImplId),
+    /// A type parameter of a generic function or struct, identified by its index
+    GenericParam(u32),
 }
 
 /// An item definition visible from a certain scope.
@@ -84,6 +106,21 @@ impl Resolver {
         }))
     }
 
+    /// Adds the scope of an `impl` block from which it can resolve the `Self` type
+    pub(crate) fn push_impl_scope(self, impl_id: ImplId) -> Resolver {
+        self.push_scope(Scope::ImplScope(impl_id))
+    }
+
+    /// Adds the type parameters of a generic item from which it can resolve types. Nothing is
+    /// added if the item has no type parameters.
+    pub(crate) fn push_generic_params_scope(self, params: Arc<GenericParams>) -> Resolver {
+        if params.is_empty() {
+            self
+        } else {
+            self.push_scope(Scope::GenericParams(params))
+        }
+    }
+
     /// Adds an expression scope from which it can resolve names
     pub(crate) fn push_expr_scope(
         self,
@@ -150,8 +187,14 @@ impl Resolver {
         db: &dyn DefDatabase,
         path: &Path,
     ) -> Option<ResolveValueResult> {
+        // The `self` parameter is a local binding named `self`
+        let self_param = name![self];
         let segments_count = path.segments.len();
-        let first_name = path.segments.first()?;
+        let first_name = if path.kind == PathKind::Super(0) && segments_count == 0 {
+            &self_param
+        } else {
+            path.segments.first()?
+        };
         for scope in self.scopes.iter().rev() {
             match scope {
                 Scope::ExprScope(scope) if segments_count <= 1 => {
@@ -168,7 +211,18 @@ impl Resolver {
                         ));
                     }
                 }
-                Scope::ExprScope(_) => continue,
+                Scope::ExprScope(_) | Scope::GenericParams(_) => continue,
+
+                // An associated function can be referenced through the `Self` type (e.g.
+                // `Self::new`)
+                Scope::ImplScope(impl_id) => {
+                    if path.kind == PathKind::Plain
+                        && segments_count > 1
+                        && *first_name == name![Self]
+                    {
+                        return Some(ResolveValueResult::Partial(TypeNs::SelfType(*impl_id), 1));
+                    }
+                }
 
                 Scope::ModuleScope(m) => {
                     let (module_def, idx) =
@@ -176,18 +230,29 @@ impl Resolver {
                             .resolve_path_in_module(db, m.module_id, &path);
                     return match idx {
                         None => {
-                            let (value, vis) = to_value_ns(module_def)?;
+                            let (value, vis) = to_value_ns(module_def).or_else(|| {
+                                // Intrinsics are only resolved if nothing else matches the name
+                                let intrinsic = Intrinsic::from_name(path.as_ident()?)?;
+                                Some((ValueNs::Intrinsic(intrinsic), Visibility::Public))
+                            })?;
                             Some(ResolveValueResult::ValueNs(value, vis))
                         }
                         Some(idx) => {
                             let ty = match module_def.take_types()? {
                                 (ItemDefinitionId::StructId(id), _) => TypeNs::StructId(id),
+                                (ItemDefinitionId::EnumId(id), _) => TypeNs::EnumId(id),
+                                (ItemDefinitionId::EnumVariantId(id), _) => {
+                                    TypeNs::EnumVariantId(id)
+                                }
                                 (ItemDefinitionId::TypeAliasId(id), _) => TypeNs::TypeAliasId(id),
                                 (ItemDefinitionId::PrimitiveType(id), _) => {
                                     TypeNs::PrimitiveType(id)
                                 }
+                                (ItemDefinitionId::TraitId(id), _) => TypeNs::TraitId(id),
                                 (ItemDefinitionId::ModuleId(_), _)
-                                | (ItemDefinitionId::FunctionId(_), _) => return None,
+                                | (ItemDefinitionId::FunctionId(_), _)
+                                | (ItemDefinitionId::ConstId(_), _)
+                                | (ItemDefinitionId::StaticId(_), _) => return None,
                             };
                             Some(ResolveValueResult::Partial(ty, idx))
                         }
@@ -203,8 +268,13 @@ impl Resolver {
             let (res, vis) = match per_ns.take_values()? {
                 (ItemDefinitionId::FunctionId(id), vis) => (ValueNs::FunctionId(id), vis),
                 (ItemDefinitionId::StructId(id), vis) => (ValueNs::StructId(id), vis),
+                (ItemDefinitionId::EnumVariantId(id), vis) => (ValueNs::EnumVariantId(id), vis),
+                (ItemDefinitionId::ConstId(id), vis) => (ValueNs::ConstId(id), vis),
+                (ItemDefinitionId::StaticId(id), vis) => (ValueNs::StaticId(id), vis),
                 (ItemDefinitionId::ModuleId(_), _)
+                | (ItemDefinitionId::EnumId(_), _)
                 | (ItemDefinitionId::TypeAliasId(_), _)
+                | (ItemDefinitionId::TraitId(_), _)
                 | (ItemDefinitionId::PrimitiveType(_), _) => return None,
             };
             Some((res, vis))
@@ -233,6 +303,21 @@ impl Resolver {
         for scope in self.scopes.iter().rev() {
             match scope {
                 Scope::ExprScope(_) => continue,
+                Scope::ImplScope(impl_id) => {
+                    if path.kind == PathKind::Plain && path.segments.first() == Some(&name![Self]) {
+                        let idx = if path.segments.len() > 1 {
+                            Some(1)
+                        } else {
+                            None
+                        };
+                        return Some((TypeNs::SelfType(*impl_id), Visibility::Public, idx));
+                    }
+                }
+                Scope::GenericParams(params) => {
+                    if let Some(idx) = path.as_ident().and_then(|name| params.find_by_name(name)) {
+                        return Some((TypeNs::GenericParam(idx), Visibility::Public, None));
+                    }
+                }
                 Scope::ModuleScope(m) => {
                     let (module_def, idx) =
                         m.package_defs
@@ -248,10 +333,16 @@ impl Resolver {
         ) -> Option<(TypeNs, Visibility)> {
             let (res, vis) = match per_ns.take_types()? {
                 (ItemDefinitionId::StructId(id), vis) => (TypeNs::StructId(id), vis),
+                (ItemDefinitionId::EnumId(id), vis) => (TypeNs::EnumId(id), vis),
+                (ItemDefinitionId::EnumVariantId(id), vis) => (TypeNs::EnumVariantId(id), vis),
                 (ItemDefinitionId::TypeAliasId(id), vis) => (TypeNs::TypeAliasId(id), vis),
                 (ItemDefinitionId::PrimitiveType(id), vis) => (TypeNs::PrimitiveType(id), vis),
+                (ItemDefinitionId::TraitId(id), vis) => (TypeNs::TraitId(id), vis),
 
-                (ItemDefinitionId::ModuleId(_), _) | (ItemDefinitionId::FunctionId(_), _) => {
+                (ItemDefinitionId::ModuleId(_), _)
+                | (ItemDefinitionId::FunctionId(_), _)
+                | (ItemDefinitionId::ConstId(_), _)
+                | (ItemDefinitionId::StaticId(_), _) => {
                     return None;
                 }
             };
@@ -309,6 +400,7 @@ impl Scope {
                     visitor(name.clone(), ScopeDef::PerNs(def));
                 })
             }
+            Scope::ImplScope(_) | Scope::GenericParams(_) => {}
             Scope::ExprScope(scope) => scope
                 .expr_scopes
                 .entries(scope.scope_id)
@@ -352,12 +444,46 @@ impl HasResolver for ModuleId {
 }
 
 impl HasResolver for FunctionId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        self.lookup(db)
+            .container
+            .resolver(db)
+            .push_generic_params_scope(db.fn_data(self).generic_params())
+    }
+}
+
+impl HasResolver for ImplId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        self.lookup(db).module.resolver(db).push_impl_scope(self)
+    }
+}
+
+impl HasResolver for ItemContainerId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        match self {
+            ItemContainerId::ModuleId(module) => module.resolver(db),
+            ItemContainerId::ImplId(impl_id) => impl_id.resolver(db),
+            ItemContainerId::TraitId(trait_id) => trait_id.resolver(db),
+        }
+    }
+}
+
+impl HasResolver for TraitId {
     fn resolver(self, db: &dyn DefDatabase) -> Resolver {
         self.lookup(db).module.resolver(db)
     }
 }
 
 impl HasResolver for StructId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        self.lookup(db)
+            .module
+            .resolver(db)
+            .push_generic_params_scope(db.struct_data(self).generic_params())
+    }
+}
+
+impl HasResolver for EnumId {
     fn resolver(self, db: &dyn DefDatabase) -> Resolver {
         self.lookup(db).module.resolver(db)
     }
@@ -369,10 +495,24 @@ impl HasResolver for TypeAliasId {
     }
 }
 
+impl HasResolver for ConstId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        self.lookup(db).module.resolver(db)
+    }
+}
+
+impl HasResolver for StaticId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        self.lookup(db).module.resolver(db)
+    }
+}
+
 impl HasResolver for DefWithBodyId {
     fn resolver(self, db: &dyn DefDatabase) -> Resolver {
         match self {
             DefWithBodyId::FunctionId(f) => f.resolver(db),
+            DefWithBodyId::ConstId(c) => c.resolver(db),
+            DefWithBodyId::StaticId(s) => s.resolver(db),
         }
     }
 }
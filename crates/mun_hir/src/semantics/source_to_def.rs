@@ -1,6 +1,9 @@
 use crate::{
     code_model::src::HasSource,
-    ids::{DefWithBodyId, FunctionId, ItemDefinitionId, Lookup, StructId, TypeAliasId},
+    ids::{
+        AssocItemId, ConstId, DefWithBodyId, EnumId, FunctionId, ImplId, ItemDefinitionId, Lookup,
+        StaticId, StructId, TypeAliasId,
+    },
     item_scope::ItemScope,
     DefDatabase, FileId, HirDatabase, InFile, ModuleId,
 };
@@ -33,6 +36,14 @@ impl SourceToDefContext<'_, '_> {
                         let def = self.fn_to_def(container.with_value(it))?;
                         DefWithBodyId::from(def).into()
                     },
+                    ast::ConstDef(it) => {
+                        let def = self.const_to_def(container.with_value(it))?;
+                        DefWithBodyId::from(def).into()
+                    },
+                    ast::StaticDef(it) => {
+                        let def = self.static_to_def(container.with_value(it))?;
+                        DefWithBodyId::from(def).into()
+                    },
                     _ => continue,
                 }
             };
@@ -54,6 +65,28 @@ impl SourceToDefContext<'_, '_> {
         def_map.functions.get(&src).copied()
     }
 
+    /// Find the `ConstId` associated with the specified syntax tree node.
+    fn const_to_def(&mut self, src: InFile<ast::ConstDef>) -> Option<ConstId> {
+        let container = self.find_container(src.as_ref().map(|it| it.syntax()))?;
+        let db = self.db;
+        let def_map = &*self
+            .cache
+            .entry(container)
+            .or_insert_with(|| container.source_to_def_map(db));
+        def_map.consts.get(&src).copied()
+    }
+
+    /// Find the `StaticId` associated with the specified syntax tree node.
+    fn static_to_def(&mut self, src: InFile<ast::StaticDef>) -> Option<StaticId> {
+        let container = self.find_container(src.as_ref().map(|it| it.syntax()))?;
+        let db = self.db;
+        let def_map = &*self
+            .cache
+            .entry(container)
+            .or_insert_with(|| container.source_to_def_map(db));
+        def_map.statics.get(&src).copied()
+    }
+
     /// Finds the `ModuleId` associated with the specified `file`
     fn file_to_def(&self, file_id: FileId) -> Option<ModuleId> {
         let source_root_id = self.db.file_source_root(file_id);
@@ -127,22 +160,57 @@ impl SourceToDef for ItemScope {
         let mut result = SourceToDefMap::default();
         self.declarations()
             .for_each(|item| add_module_def(db.upcast(), &mut result, item));
+        self.impls()
+            .for_each(|id| add_impl(db.upcast(), &mut result, id));
         return result;
 
+        fn add_impl(db: &dyn DefDatabase, map: &mut SourceToDefMap, id: ImplId) {
+            for item in db.impl_data(id).items.iter() {
+                match item {
+                    AssocItemId::FunctionId(id) => {
+                        let src = id.lookup(db).id.source(db);
+                        map.functions.insert(src, *id);
+                    }
+                }
+            }
+        }
+
         fn add_module_def(db: &dyn DefDatabase, map: &mut SourceToDefMap, item: ItemDefinitionId) {
             match item {
                 ItemDefinitionId::FunctionId(id) => {
-                    let src = id.lookup(db).source(db);
+                    let src = id.lookup(db).id.source(db);
                     map.functions.insert(src, id);
                 }
                 ItemDefinitionId::StructId(id) => {
                     let src = id.lookup(db).source(db);
                     map.structs.insert(src, id);
                 }
+                ItemDefinitionId::EnumId(id) => {
+                    let src = id.lookup(db).source(db);
+                    map.enums.insert(src, id);
+                }
                 ItemDefinitionId::TypeAliasId(id) => {
                     let src = id.lookup(db).source(db);
                     map.type_aliases.insert(src, id);
                 }
+                ItemDefinitionId::ConstId(id) => {
+                    let src = id.lookup(db).source(db);
+                    map.consts.insert(src, id);
+                }
+                ItemDefinitionId::StaticId(id) => {
+                    let src = id.lookup(db).source(db);
+                    map.statics.insert(src, id);
+                }
+                ItemDefinitionId::TraitId(id) => {
+                    for item in db.trait_data(id).items.iter() {
+                        match item {
+                            AssocItemId::FunctionId(id) => {
+                                let src = id.lookup(db).id.source(db);
+                                map.functions.insert(src, *id);
+                            }
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -154,5 +222,8 @@ impl SourceToDef for ItemScope {
 pub(crate) struct SourceToDefMap {
     functions: FxHashMap<InFile<ast::FunctionDef>, FunctionId>,
     structs: FxHashMap<InFile<ast::StructDef>, StructId>,
+    enums: FxHashMap<InFile<ast::EnumDef>, EnumId>,
     type_aliases: FxHashMap<InFile<ast::TypeAliasDef>, TypeAliasId>,
+    consts: FxHashMap<InFile<ast::ConstDef>, ConstId>,
+    statics: FxHashMap<InFile<ast::StaticDef>, StaticId>,
 }
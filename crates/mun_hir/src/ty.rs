@@ -1,23 +1,31 @@
 mod infer;
 pub(super) mod lower;
+mod method_resolution;
 mod op;
 mod primitives;
 mod resolve;
 
 use crate::display::{HirDisplay, HirFormatter};
 use crate::ty::infer::InferTy;
-use crate::ty::lower::fn_sig_for_struct_constructor;
+use crate::ty::lower::{
+    fn_sig_for_enum_variant_constructor, fn_sig_for_intrinsic, fn_sig_for_struct_constructor,
+};
 use crate::HasVisibility;
 use crate::Visibility;
-use crate::{HirDatabase, Struct, StructMemoryKind, TypeAlias};
+use crate::{Enum, HirDatabase, Name, Struct, StructMemoryKind, TypeAlias};
 pub(crate) use infer::infer_query;
 pub use infer::InferenceResult;
 pub(crate) use lower::{
     callable_item_sig, fn_sig_for_fn, type_for_cycle_recover, type_for_def, CallableDef, TypableDef,
 };
+pub(crate) use method_resolution::{
+    find_trait_impl, lookup_associated_function, resolve_trait_method,
+};
+pub use method_resolution::{InherentImpls, TraitImpls};
 pub use primitives::{FloatTy, IntTy};
 pub use resolve::ResolveBitness;
 use smallvec::SmallVec;
+use std::iter::FromIterator;
 use std::ops::Deref;
 use std::sync::Arc;
 use std::{fmt, mem};
@@ -28,9 +36,13 @@ mod tests;
 /// A kind of type.
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub enum TyKind {
-    /// An abstract datatype (structures, tuples, or enumerations)
-    /// TODO: Add enumerations
-    Struct(Struct),
+    /// An abstract datatype (structures or tuples). The substitution contains the type arguments
+    /// of a generic struct, e.g. the `i32` and `f64` in `Pair<i32, f64>`.
+    Struct(Struct, Substitution),
+
+    /// An enumeration. Enums are value types that store a tag, identifying the active variant,
+    /// followed by the fields of that variant.
+    Enum(Enum),
 
     /// The primitive floating point type. Written as `float`.
     Float(FloatTy),
@@ -41,12 +53,24 @@ pub enum TyKind {
     /// The primitive boolean type. Written as `bool`.
     Bool,
 
+    /// The primitive string type. Written as `string`. Strings are immutable and allocated on the
+    /// heap.
+    String,
+
     /// A tuple type. For example `(f32, f64, bool)`.
     Tuple(usize, Substitution),
 
+    /// A dynamically sized array type. For example `[i32]`. Arrays are always allocated on the
+    /// heap.
+    Array(Ty),
+
     /// A type variable used during type checking. Not to be confused with a type parameter.
     InferenceVar(InferTy),
 
+    /// A generic type parameter of a function or struct, e.g. the `T` in `fn max<T>(a: T, b: T)`.
+    /// The `idx` refers to the position of the parameter in the declaration of the item.
+    Param { idx: u32, name: Name },
+
     /// A type alias
     TypeAlias(TypeAlias),
 
@@ -67,6 +91,11 @@ pub enum TyKind {
     /// ```
     FnDef(CallableDef, Substitution),
 
+    /// A pointer to a function, e.g. `fn(i32) -> i32`. Both lambda expressions and functions can
+    /// be used as a value of this type. Values of this type are allocated on the heap and contain
+    /// the variables that are captured by a lambda.
+    FnPtr(FnSig),
+
     /// A placeholder for a type which could not be computed; this is propagated to avoid useless
     /// error messages. Doubles as a placeholder where type variables are inserted before type
     /// checking, since we want to try to infer a better type here anyway -- for the IDE use case,
@@ -94,9 +123,11 @@ impl TyKind {
 impl HasVisibility for TyKind {
     fn visibility(&self, db: &dyn HirDatabase) -> Visibility {
         match self {
-            TyKind::Struct(strukt) => strukt.visibility(db),
+            TyKind::Struct(strukt, _) => strukt.visibility(db),
+            TyKind::Enum(e) => e.visibility(db),
             TyKind::TypeAlias(type_alias) => type_alias.visibility(db),
             TyKind::FnDef(callable_def, _) => callable_def.visibility(db),
+            TyKind::Array(element_ty) => element_ty.visibility(db),
             _ => Visibility::Public,
         }
     }
@@ -125,15 +156,28 @@ impl Ty {
         TyKind::Tuple(0, Substitution::empty()).intern()
     }
 
-    /// Constructs a new struct type
-    pub fn struct_ty(strukt: Struct) -> Ty {
-        TyKind::Struct(strukt).intern()
+    /// Constructs a new struct type with the specified type arguments
+    pub fn struct_ty(strukt: Struct, substs: Substitution) -> Ty {
+        TyKind::Struct(strukt, substs).intern()
+    }
+
+    /// Constructs a new enum type
+    pub fn enum_ty(e: Enum) -> Ty {
+        TyKind::Enum(e).intern()
     }
 
     /// If this type represents a struct type, returns the type of the struct.
     pub fn as_struct(&self) -> Option<Struct> {
         match self.interned() {
-            TyKind::Struct(s) => Some(*s),
+            TyKind::Struct(s, _) => Some(*s),
+            _ => None,
+        }
+    }
+
+    /// If this type represents an enum type, returns the type of the enum.
+    pub fn as_enum(&self) -> Option<Enum> {
+        match self.interned() {
+            TyKind::Enum(e) => Some(*e),
             _ => None,
         }
     }
@@ -146,6 +190,14 @@ impl Ty {
         }
     }
 
+    /// If this type represents an array type, returns the type of the elements of the array.
+    pub fn as_array(&self) -> Option<&Ty> {
+        match self.interned() {
+            TyKind::Array(element_ty) => Some(element_ty),
+            _ => None,
+        }
+    }
+
     /// Returns true if this type represents the empty tuple type
     pub fn is_empty(&self) -> bool {
         matches!(self.interned(), TyKind::Tuple(0, _))
@@ -168,7 +220,16 @@ impl Ty {
     /// Returns the callable signature of the type, if the type is callable.
     pub fn callable_sig(&self, db: &dyn HirDatabase) -> Option<FnSig> {
         match self.interned() {
-            TyKind::FnDef(def, _) => Some(db.callable_sig(*def)),
+            TyKind::FnDef(def, substs) => Some(db.callable_sig(*def).subst(substs)),
+            TyKind::FnPtr(sig) => Some(sig.clone()),
+            _ => None,
+        }
+    }
+
+    /// If this type represents a function pointer type, returns the signature of the function.
+    pub fn as_fn_ptr(&self) -> Option<&FnSig> {
+        match self.interned() {
+            TyKind::FnPtr(sig) => Some(sig),
             _ => None,
         }
     }
@@ -178,8 +239,16 @@ impl Ty {
     /// This name needs to be unique as it is used to generate a type's `Guid`.
     pub fn guid_string(&self, db: &dyn HirDatabase) -> Option<String> {
         match self.interned() {
-            &TyKind::Struct(s) => {
-                let name = s.name(db).to_string();
+            TyKind::Struct(s, substs) => {
+                let name = if substs.is_empty() {
+                    s.name(db).to_string()
+                } else {
+                    let args = substs
+                        .iter()
+                        .map(|ty| ty.guid_string(db))
+                        .collect::<Option<Vec<_>>>()?;
+                    format!("{}<{}>", s.name(db), args.join(","))
+                };
 
                 Some(if s.data(db.upcast()).memory_kind == StructMemoryKind::Gc {
                     format!("struct {}", name)
@@ -190,6 +259,7 @@ impl Ty {
                         .map(|f| {
                             let ty_string = f
                                 .ty(db)
+                                .subst(substs)
                                 .guid_string(db)
                                 .expect("type should be convertible to a string");
                             format!("{}: {}", f.name(db).to_string(), ty_string)
@@ -203,13 +273,65 @@ impl Ty {
                     )
                 })
             }
+            &TyKind::Enum(e) => {
+                let variants: Vec<String> = e
+                    .variants(db)
+                    .into_iter()
+                    .map(|v| {
+                        let fields: Vec<String> = v
+                            .fields(db)
+                            .into_iter()
+                            .map(|f| {
+                                let ty_string = f
+                                    .ty(db)
+                                    .guid_string(db)
+                                    .expect("type should be convertible to a string");
+                                format!("{}: {}", f.name(db).to_string(), ty_string)
+                            })
+                            .collect();
+                        format!("{}{{{}}}", v.name(db).to_string(), fields.join(","))
+                    })
+                    .collect();
+
+                Some(format!(
+                    "enum {name}{{{variants}}}",
+                    name = e.name(db).to_string(),
+                    variants = variants.join(",")
+                ))
+            }
             TyKind::Bool => Some("core::bool".to_string()),
+            TyKind::String => Some("core::string".to_string()),
+            TyKind::Array(element_ty) => Some(format!("[{}]", element_ty.guid_string(db)?)),
+            TyKind::FnPtr(sig) => {
+                let params = sig
+                    .params()
+                    .iter()
+                    .map(|ty| ty.guid_string(db))
+                    .collect::<Option<Vec<_>>>()?;
+                if sig.ret().is_empty() {
+                    Some(format!("fn({})", params.join(",")))
+                } else {
+                    Some(format!(
+                        "fn({}) -> {}",
+                        params.join(","),
+                        sig.ret().guid_string(db)?
+                    ))
+                }
+            }
             TyKind::Float(ty) => Some(format!("core::{}", ty.as_str())),
             TyKind::Int(ty) => Some(format!("core::{}", ty.as_str())),
             _ => None,
         }
     }
 
+    /// Returns true if this instance is of an unknown type or contains a type that is unknown, e.g.
+    /// `[{unknown}]`.
+    pub fn contains_unknown(&self) -> bool {
+        let mut contains_unknown = false;
+        self.walk(&mut |ty| contains_unknown |= ty.is_unknown());
+        contains_unknown
+    }
+
     /// Returns true if this instance represents a known type.
     pub fn is_known(&self) -> bool {
         !matches!(self.interned(), TyKind::Unknown)
@@ -224,7 +346,9 @@ impl Ty {
     /// if `self` is an `Option<u32>`, this returns the `u32`
     pub fn type_parameters(&self) -> Option<&Substitution> {
         match self.interned() {
-            TyKind::Tuple(_, substs) | TyKind::FnDef(_, substs) => Some(substs),
+            TyKind::Tuple(_, substs) | TyKind::FnDef(_, substs) | TyKind::Struct(_, substs) => {
+                Some(substs)
+            }
             _ => None,
         }
     }
@@ -233,10 +357,32 @@ impl Ty {
     /// ADT or function); so if `self` is an `Option<u32>`, this returns the `u32`
     pub fn type_parameters_mut(&mut self) -> Option<&mut Substitution> {
         match self.interned_mut() {
-            TyKind::Tuple(_, substs) | TyKind::FnDef(_, substs) => Some(substs),
+            TyKind::Tuple(_, substs) | TyKind::FnDef(_, substs) | TyKind::Struct(_, substs) => {
+                Some(substs)
+            }
             _ => None,
         }
     }
+
+    /// Returns true if this type is or contains a generic type parameter, e.g. `T` or `[T]`.
+    pub fn contains_param(&self) -> bool {
+        let mut contains_param = false;
+        self.walk(&mut |ty| contains_param |= matches!(ty.interned(), TyKind::Param { .. }));
+        contains_param
+    }
+
+    /// Replaces all generic type parameters in this type with the corresponding types of
+    /// `substs`, e.g. substituting `[i32]` in `[T]` results in `[[i32]]`.
+    pub fn subst(self, substs: &Substitution) -> Ty {
+        self.fold(&mut |ty| {
+            if let TyKind::Param { idx, .. } = ty.interned() {
+                if let Some(substituted) = substs.get(*idx as usize) {
+                    return substituted.clone();
+                }
+            }
+            ty
+        })
+    }
 }
 
 /// A list of substitutions for generic parameters.
@@ -264,6 +410,12 @@ impl Substitution {
     }
 }
 
+impl FromIterator<Ty> for Substitution {
+    fn from_iter<T: IntoIterator<Item = Ty>>(iter: T) -> Self {
+        Substitution(iter.into_iter().collect())
+    }
+}
+
 impl Deref for Substitution {
     type Target = [Ty];
 
@@ -288,7 +440,7 @@ impl TypeWalk for Substitution {
 
 /// A function signature as seen by type inference: Several parameter types and
 /// one return type.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub struct FnSig {
     params_and_return: Arc<[Ty]>,
 }
@@ -309,12 +461,32 @@ impl FnSig {
         &self.params_and_return[self.params_and_return.len() - 1]
     }
 
+    /// Replaces all generic type parameters in the signature with the corresponding types of
+    /// `substs`.
+    pub fn subst(&self, substs: &Substitution) -> FnSig {
+        FnSig {
+            params_and_return: self
+                .params_and_return
+                .iter()
+                .map(|ty| ty.clone().subst(substs))
+                .collect(),
+        }
+    }
+
+    /// Returns true if the signature contains a type that is unknown
+    pub fn contains_unknown(&self) -> bool {
+        self.params_and_return.iter().any(Ty::contains_unknown)
+    }
+
     pub fn marshallable(&self, db: &dyn HirDatabase) -> bool {
         for ty in self.params_and_return.iter() {
             if let Some(s) = ty.as_struct() {
                 if s.data(db.upcast()).memory_kind == StructMemoryKind::Value {
                     return false;
                 }
+            } else if ty.as_enum().is_some() {
+                // Enums are always value types
+                return false;
             }
         }
         true
@@ -324,10 +496,21 @@ impl FnSig {
 impl HirDisplay for Ty {
     fn hir_fmt(&self, f: &mut HirFormatter) -> fmt::Result {
         match self.interned() {
-            TyKind::Struct(s) => write!(f, "{}", s.name(f.db)),
+            TyKind::Struct(s, substs) => {
+                write!(f, "{}", s.name(f.db))?;
+                if !substs.is_empty() {
+                    write!(f, "<")?;
+                    f.write_joined(substs.iter(), ", ")?;
+                    write!(f, ">")?;
+                }
+                Ok(())
+            }
+            TyKind::Enum(e) => write!(f, "{}", e.name(f.db)),
             TyKind::Float(ty) => write!(f, "{}", ty),
             TyKind::Int(ty) => write!(f, "{}", ty),
             TyKind::Bool => write!(f, "bool"),
+            TyKind::String => write!(f, "string"),
+            TyKind::Array(element_ty) => write!(f, "[{}]", element_ty.display(f.db)),
             TyKind::Tuple(_, elems) => {
                 write!(f, "(")?;
                 f.write_joined(elems.iter(), ", ")?;
@@ -341,24 +524,49 @@ impl HirDisplay for Ty {
                 InferTy::IntVar(_) => write!(f, "{{integer}}"),
                 InferTy::FloatVar(_) => write!(f, "{{float}}"),
             },
+            TyKind::Param { name, .. } => write!(f, "{}", name),
             TyKind::TypeAlias(def) => write!(f, "{}", def.name(f.db)),
             TyKind::Never => write!(f, "never"),
-            &TyKind::FnDef(CallableDef::Function(def), _) => {
-                let sig = fn_sig_for_fn(f.db, def);
+            TyKind::FnDef(CallableDef::Function(def), substs) => {
+                let sig = fn_sig_for_fn(f.db, *def).subst(substs);
                 let name = def.name(f.db);
                 write!(f, "function {}", name)?;
                 write!(f, "(")?;
                 f.write_joined(sig.params(), ", ")?;
                 write!(f, ") -> {}", sig.ret().display(f.db))
             }
-            &TyKind::FnDef(CallableDef::Struct(def), _) => {
-                let sig = fn_sig_for_struct_constructor(f.db, def);
+            TyKind::FnDef(CallableDef::Struct(def), substs) => {
+                let sig = fn_sig_for_struct_constructor(f.db, *def).subst(substs);
                 let name = def.name(f.db);
                 write!(f, "ctor {}", name)?;
                 write!(f, "(")?;
                 f.write_joined(sig.params(), ", ")?;
                 write!(f, ") -> {}", sig.ret().display(f.db))
             }
+            &TyKind::FnDef(CallableDef::EnumVariant(def), _) => {
+                let sig = fn_sig_for_enum_variant_constructor(f.db, def);
+                let name = def.name(f.db);
+                write!(f, "ctor {}::{}", def.parent_enum().name(f.db), name)?;
+                write!(f, "(")?;
+                f.write_joined(sig.params(), ", ")?;
+                write!(f, ") -> {}", sig.ret().display(f.db))
+            }
+            &TyKind::FnDef(CallableDef::Intrinsic(def), _) => {
+                let sig = fn_sig_for_intrinsic(def);
+                write!(f, "intrinsic {}", def.name())?;
+                write!(f, "(")?;
+                f.write_joined(sig.params(), ", ")?;
+                write!(f, ") -> {}", sig.ret().display(f.db))
+            }
+            TyKind::FnPtr(sig) => {
+                write!(f, "fn(")?;
+                f.write_joined(sig.params(), ", ")?;
+                write!(f, ")")?;
+                if !sig.ret().is_empty() {
+                    write!(f, " -> {}", sig.ret().display(f.db))?;
+                }
+                Ok(())
+            }
             TyKind::Unknown => write!(f, "{{unknown}}"),
         }
     }
@@ -392,10 +600,27 @@ pub trait TypeWalk {
     }
 }
 
+impl TypeWalk for FnSig {
+    fn walk(&self, f: &mut impl FnMut(&Ty)) {
+        for ty in self.params_and_return.iter() {
+            ty.walk(f);
+        }
+    }
+
+    fn walk_mut(&mut self, f: &mut impl FnMut(&mut Ty)) {
+        let mut params_and_return = self.params_and_return.to_vec();
+        for ty in params_and_return.iter_mut() {
+            ty.walk_mut(f);
+        }
+        self.params_and_return = params_and_return.into();
+    }
+}
+
 impl TypeWalk for Ty {
     fn walk(&self, f: &mut impl FnMut(&Ty)) {
-        #[allow(clippy::match_single_binding)]
         match self.interned() {
+            TyKind::Array(element_ty) => element_ty.walk(f),
+            TyKind::FnPtr(sig) => sig.walk(f),
             _ => {
                 if let Some(substs) = self.type_parameters() {
                     substs.walk(f)
@@ -406,8 +631,9 @@ impl TypeWalk for Ty {
     }
 
     fn walk_mut(&mut self, f: &mut impl FnMut(&mut Ty)) {
-        #[allow(clippy::match_single_binding)]
         match self.interned_mut() {
+            TyKind::Array(element_ty) => element_ty.walk_mut(f),
+            TyKind::FnPtr(sig) => sig.walk_mut(f),
             _ => {
                 if let Some(substs) = self.type_parameters_mut() {
                     substs.walk_mut(f)
@@ -1,19 +1,25 @@
 use crate::{
     arena::map::ArenaMap,
-    code_model::{Struct, StructKind},
+    code_model::{DefWithBody, EnumVariant, Struct, StructKind, VariantDef},
     diagnostics::DiagnosticSink,
-    expr::{Body, Expr, ExprId, Literal, Pat, PatId, RecordLitField, Statement, UnaryOp},
+    expr::{
+        Body, Expr, ExprId, Literal, MatchArm, Pat, PatId, RecordFieldPat, RecordLitField,
+        Statement, UnaryOp,
+    },
     name_resolution::Namespace,
-    resolve::{Resolver, TypeNs, ValueNs},
+    resolve::{ResolveValueResult, Resolver, TypeNs, ValueNs},
     ty::infer::diagnostics::InferenceDiagnostic,
     ty::infer::type_variable::TypeVariableTable,
     ty::lower::LowerDiagnostic,
     ty::op,
-    ty::{Ty, TypableDef},
-    type_ref::LocalTypeRefId,
-    BinaryOp, Function, HirDatabase, Name, Path,
+    ty::{
+        find_trait_impl, lookup_associated_function, resolve_trait_method, CallableDef, FnSig,
+        Substitution, Ty, TypableDef,
+    },
+    type_ref::{LocalTypeRefId, TypeRef},
+    BinaryOp, Function, HasVisibility, HirDatabase, Impl, Name, Path, Trait,
 };
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::ops::Index;
 use std::sync::Arc;
 
@@ -49,6 +55,9 @@ impl Default for InternedStandardTypes {
 pub struct InferenceResult {
     pub(crate) type_of_expr: ArenaMap<ExprId, Ty>,
     pub(crate) type_of_pat: ArenaMap<PatId, Ty>,
+    pub(crate) variant_resolutions: FxHashMap<ExprOrPatId, VariantDef>,
+    pub(crate) method_resolutions: FxHashMap<ExprId, (Function, Substitution)>,
+    pub(crate) lambda_captures: FxHashMap<ExprId, Vec<PatId>>,
     pub(crate) diagnostics: Vec<diagnostics::InferenceDiagnostic>,
 
     /// Interned Unknown to return references to.
@@ -74,11 +83,66 @@ impl Index<PatId> for InferenceResult {
 }
 
 impl InferenceResult {
+    /// Returns the struct or enum variant that is constructed by the specified expression, if any.
+    pub fn variant_resolution_for_expr(&self, id: ExprId) -> Option<VariantDef> {
+        self.variant_resolutions
+            .get(&ExprOrPatId::from(id))
+            .copied()
+    }
+
+    /// Returns the struct or enum variant that is destructured by the specified pattern, if any.
+    pub fn variant_resolution_for_pat(&self, id: PatId) -> Option<VariantDef> {
+        self.variant_resolutions
+            .get(&ExprOrPatId::from(id))
+            .copied()
+    }
+
+    /// Returns the function that is called by the specified method call expression, if any.
+    pub fn method_resolution(&self, expr: ExprId) -> Option<Function> {
+        self.method_resolutions
+            .get(&expr)
+            .map(|(method, _)| *method)
+    }
+
+    /// Returns the type arguments of the generic function that is called by the specified method
+    /// call expression. The substitution is empty if the method is not generic.
+    pub fn method_substitution(&self, expr: ExprId) -> Option<&Substitution> {
+        self.method_resolutions.get(&expr).map(|(_, substs)| substs)
+    }
+
+    /// Returns the local variables that are captured by the specified lambda expression, in the
+    /// order in which they are first used.
+    pub fn lambda_captures(&self, expr: ExprId) -> &[PatId] {
+        self.lambda_captures
+            .get(&expr)
+            .map_or(&[][..], Vec::as_slice)
+    }
+
+    /// Returns a copy of this result in which all the type parameters of a generic function are
+    /// replaced with the type arguments of `substs`. This results in the types of a single
+    /// instantiation of the function. Calls of trait functions whose `Self` type becomes known are
+    /// resolved to the functions that implement them.
+    pub fn subst(&self, db: &dyn HirDatabase, substs: &Substitution) -> InferenceResult {
+        let mut result = self.clone();
+        for (_, ty) in result.type_of_expr.iter_mut() {
+            *ty = ty.clone().subst(substs);
+        }
+        for (_, ty) in result.type_of_pat.iter_mut() {
+            *ty = ty.clone().subst(substs);
+        }
+        for (_, resolution) in result.method_resolutions.iter_mut() {
+            let (method, method_substs) = resolution.clone();
+            let method_substs = method_substs.fold(&mut |ty| ty.subst(substs));
+            *resolution = resolve_trait_method(db, method, method_substs);
+        }
+        result
+    }
+
     /// Adds all the `InferenceDiagnostic`s of the result to the `DiagnosticSink`.
     pub(crate) fn add_diagnostics(
         &self,
         db: &dyn HirDatabase,
-        owner: Function,
+        owner: DefWithBody,
         sink: &mut DiagnosticSink,
     ) {
         self.diagnostics
@@ -95,7 +159,9 @@ pub fn infer_query(db: &dyn HirDatabase, def: DefWithBodyId) -> Arc<InferenceRes
     let mut ctx = InferenceResultBuilder::new(db, body, resolver);
 
     match def {
-        DefWithBodyId::FunctionId(_) => ctx.infer_signature(),
+        DefWithBodyId::FunctionId(_) | DefWithBodyId::ConstId(_) | DefWithBodyId::StaticId(_) => {
+            ctx.infer_signature()
+        }
     }
 
     ctx.infer_body();
@@ -103,6 +169,38 @@ pub fn infer_query(db: &dyn HirDatabase, def: DefWithBodyId) -> Arc<InferenceRes
     Arc::new(ctx.resolve_all())
 }
 
+/// Collects all the patterns that are bound by the specified expression and its sub-expressions,
+/// e.g. the parameters of a lambda and the variables declared in its body.
+fn collect_bound_pats(body: &Body, expr: ExprId, pats: &mut FxHashSet<PatId>) {
+    fn collect_pat(body: &Body, pat: PatId, pats: &mut FxHashSet<PatId>) {
+        pats.insert(pat);
+        body[pat].walk_child_pats(|pat| collect_pat(body, pat, pats));
+    }
+
+    match &body[expr] {
+        Expr::Block { statements, .. } => {
+            for statement in statements.iter() {
+                if let Statement::Let { pat, .. } = statement {
+                    collect_pat(body, *pat, pats);
+                }
+            }
+        }
+        Expr::For { pat, .. } => collect_pat(body, *pat, pats),
+        Expr::Match { arms, .. } => {
+            for arm in arms.iter() {
+                collect_pat(body, arm.pat, pats);
+            }
+        }
+        Expr::Lambda { args, .. } => {
+            for (pat, _) in args.iter() {
+                collect_pat(body, *pat, pats);
+            }
+        }
+        _ => {}
+    }
+    body[expr].walk_child_exprs(|expr| collect_bound_pats(body, expr, pats));
+}
+
 /// Placeholders required during type inferencing. There are seperate values for integer and
 /// floating-point types and for generic type variables. The first being used to distinguish
 /// literals; e.g `100` can be represented by a lot of different integer types.
@@ -144,6 +242,9 @@ struct InferenceResultBuilder<'a> {
 
     type_of_expr: ArenaMap<ExprId, Ty>,
     type_of_pat: ArenaMap<PatId, Ty>,
+    variant_resolutions: FxHashMap<ExprOrPatId, VariantDef>,
+    method_resolutions: FxHashMap<ExprId, (Function, Substitution)>,
+    lambda_captures: FxHashMap<ExprId, Vec<PatId>>,
     diagnostics: Vec<InferenceDiagnostic>,
 
     type_variables: TypeVariableTable,
@@ -154,8 +255,13 @@ struct InferenceResultBuilder<'a> {
     /// encountered.
     active_loop: Option<ActiveLoop>,
 
-    /// The return type of the function being inferred.
+    /// The return type of the function or lambda being inferred.
     return_ty: Ty,
+
+    /// The lambda expressions that are currently being inferred, from the outermost to the
+    /// innermost, together with the patterns that are bound within each lambda. Local variables
+    /// that are bound outside of a lambda are captured by it.
+    active_lambdas: Vec<(ExprId, FxHashSet<PatId>)>,
 }
 
 impl<'a> InferenceResultBuilder<'a> {
@@ -164,6 +270,9 @@ impl<'a> InferenceResultBuilder<'a> {
         InferenceResultBuilder {
             type_of_expr: ArenaMap::default(),
             type_of_pat: ArenaMap::default(),
+            variant_resolutions: FxHashMap::default(),
+            method_resolutions: FxHashMap::default(),
+            lambda_captures: FxHashMap::default(),
             diagnostics: Vec::default(),
             active_loop: None,
             type_variables: TypeVariableTable::default(),
@@ -171,6 +280,7 @@ impl<'a> InferenceResultBuilder<'a> {
             body,
             resolver,
             return_ty: TyKind::Unknown.intern(), // set in collect_fn_signature
+            active_lambdas: Vec::new(),
         }
     }
 
@@ -204,12 +314,60 @@ impl<'a> InferenceResultBuilder<'a> {
                 }
                 LowerDiagnostic::CyclicType { id } => InferenceDiagnostic::CyclicType { id },
                 LowerDiagnostic::TypeIsPrivate { id } => InferenceDiagnostic::TypeIsPrivate { id },
+                LowerDiagnostic::TypeArgCountMismatch {
+                    id,
+                    expected,
+                    found,
+                } => InferenceDiagnostic::TypeArgCountMismatch {
+                    id,
+                    expected,
+                    found,
+                },
             };
             self.diagnostics.push(diag);
         }
 
         result.ty
     }
+
+    /// Replaces the type parameters of a generic function or struct type with fresh type
+    /// variables. Every use of a generic item is a separate instantiation whose type arguments are
+    /// inferred from its context.
+    fn instantiate(&mut self, ty: Ty) -> Ty {
+        match ty.interned() {
+            TyKind::FnDef(def, substs) if !substs.is_empty() => {
+                let substs = self.fresh_substs(substs.len());
+                TyKind::FnDef(*def, substs).intern()
+            }
+            TyKind::Struct(s, substs) if !substs.is_empty() => {
+                let substs = self.fresh_substs(substs.len());
+                TyKind::Struct(*s, substs).intern()
+            }
+            _ => ty,
+        }
+    }
+
+    /// Returns a substitution of `len` fresh type variables
+    fn fresh_substs(&mut self, len: usize) -> Substitution {
+        (0..len)
+            .map(|_| self.type_variables.new_type_var())
+            .collect()
+    }
+
+    /// Returns the instantiated type of a struct or enum variant together with the types of its
+    /// fields.
+    fn instantiate_variant(&mut self, variant: VariantDef) -> (Ty, Vec<Ty>) {
+        let ty = self.instantiate(variant.ty(self.db));
+        let field_tys = match ty.interned() {
+            TyKind::Struct(_, substs) => variant
+                .field_types(self.db)
+                .into_iter()
+                .map(|field_ty| field_ty.subst(substs))
+                .collect(),
+            _ => variant.field_types(self.db),
+        };
+        (ty, field_tys)
+    }
 }
 
 impl<'a> InferenceResultBuilder<'a> {
@@ -232,15 +390,210 @@ impl<'a> InferenceResultBuilder<'a> {
     /// Record the type of the specified pattern and all sub-patterns.
     fn infer_pat(&mut self, pat: PatId, ty: Ty) {
         let body = Arc::clone(&self.body); // avoid borrow checker problem
-        #[allow(clippy::single_match)]
         match &body[pat] {
             Pat::Bind { .. } => {
                 self.set_pat_type(pat, ty);
             }
-            _ => {}
+            Pat::Path(path) => {
+                let ty = self.infer_path_pat(pat, path, ty);
+                self.set_pat_type(pat, ty);
+            }
+            Pat::TupleStruct { path, args } => {
+                let ty = self.infer_tuple_struct_pat(pat, path.as_ref(), args, ty);
+                self.set_pat_type(pat, ty);
+            }
+            Pat::Record {
+                path,
+                fields,
+                has_rest,
+            } => {
+                let ty = self.infer_record_pat(pat, path.as_ref(), fields, *has_rest, ty);
+                self.set_pat_type(pat, ty);
+            }
+            Pat::Wild | Pat::Missing => {}
         }
     }
 
+    /// Inferences the type of a pattern that matches a unit struct or unit enum variant, e.g.
+    /// `State::Idle`.
+    fn infer_path_pat(&mut self, pat: PatId, path: &Path, expected: Ty) -> Ty {
+        let variant = match self.resolve_variant_pat(pat, Some(path)) {
+            Some(variant) => variant,
+            None => return expected,
+        };
+
+        let kind = variant.kind(self.db);
+        if kind != StructKind::Unit {
+            self.diagnostics
+                .push(InferenceDiagnostic::MismatchedStructPat {
+                    id: pat,
+                    expected: kind,
+                    found: StructKind::Unit,
+                });
+        }
+
+        let (ty, _) = self.instantiate_variant(variant);
+        self.check_pat_type(pat, ty, expected)
+    }
+
+    /// Inferences the type of a pattern that destructures a tuple struct or tuple enum variant,
+    /// e.g. `State::Walking(speed)`.
+    fn infer_tuple_struct_pat(
+        &mut self,
+        pat: PatId,
+        path: Option<&Path>,
+        args: &[PatId],
+        expected: Ty,
+    ) -> Ty {
+        let variant = match self.resolve_variant_pat(pat, path) {
+            Some(variant) => variant,
+            None => {
+                // Still derive the types of the sub-patterns
+                for arg in args.iter() {
+                    self.infer_pat(*arg, self.error_type());
+                }
+                return expected;
+            }
+        };
+
+        let kind = variant.kind(self.db);
+        let (ty, field_tys) = self.instantiate_variant(variant);
+        let field_tys = if kind == StructKind::Tuple {
+            if field_tys.len() != args.len() {
+                self.diagnostics
+                    .push(InferenceDiagnostic::PatternFieldCountMismatch {
+                        id: pat,
+                        expected: field_tys.len(),
+                        found: args.len(),
+                    });
+            }
+            field_tys
+        } else {
+            self.diagnostics
+                .push(InferenceDiagnostic::MismatchedStructPat {
+                    id: pat,
+                    expected: kind,
+                    found: StructKind::Tuple,
+                });
+            Vec::new()
+        };
+
+        for (idx, arg) in args.iter().enumerate() {
+            let field_ty = field_tys
+                .get(idx)
+                .cloned()
+                .unwrap_or_else(|| self.error_type());
+            self.infer_pat(*arg, field_ty);
+        }
+
+        self.check_pat_type(pat, ty, expected)
+    }
+
+    /// Inferences the type of a pattern that destructures a record struct or record enum variant,
+    /// e.g. `State::Attacking { target, .. }`.
+    fn infer_record_pat(
+        &mut self,
+        pat: PatId,
+        path: Option<&Path>,
+        fields: &[RecordFieldPat],
+        has_rest: bool,
+        expected: Ty,
+    ) -> Ty {
+        let variant = match self.resolve_variant_pat(pat, path) {
+            Some(variant) => variant,
+            None => {
+                // Still derive the types of the sub-patterns
+                for field in fields.iter() {
+                    self.infer_pat(field.pat, self.error_type());
+                }
+                return expected;
+            }
+        };
+
+        let kind = variant.kind(self.db);
+        let (ty, field_tys) = self.instantiate_variant(variant);
+        if kind != StructKind::Record {
+            self.diagnostics
+                .push(InferenceDiagnostic::MismatchedStructPat {
+                    id: pat,
+                    expected: kind,
+                    found: StructKind::Record,
+                });
+            for field in fields.iter() {
+                self.infer_pat(field.pat, self.error_type());
+            }
+            return self.check_pat_type(pat, ty, expected);
+        }
+
+        let field_names = variant.field_names(self.db);
+        for field in fields.iter() {
+            let field_ty = match field_names.iter().position(|name| *name == field.name) {
+                Some(idx) => field_tys[idx].clone(),
+                None => {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::NoSuchPatternField { id: field.pat });
+                    self.error_type()
+                }
+            };
+            self.infer_pat(field.pat, field_ty);
+        }
+
+        if !has_rest {
+            let pat_fields: FxHashSet<_> = fields.iter().map(|f| &f.name).collect();
+            let missed_fields: Vec<Name> = field_names
+                .into_iter()
+                .filter(|name| !pat_fields.contains(name))
+                .collect();
+            if !missed_fields.is_empty() {
+                self.diagnostics
+                    .push(InferenceDiagnostic::MissingPatternFields {
+                        id: pat,
+                        names: missed_fields,
+                    });
+            }
+        }
+
+        self.check_pat_type(pat, ty, expected)
+    }
+
+    /// Resolves the path of a pattern to the struct or enum variant that it destructures. An error
+    /// is emitted if the path could not be resolved.
+    fn resolve_variant_pat(&mut self, pat: PatId, path: Option<&Path>) -> Option<VariantDef> {
+        // If there is no path a syntax error has already been emitted
+        let path = path?;
+        let variant = match self
+            .resolver
+            .resolve_path_as_type_fully(self.db.upcast(), path)
+        {
+            Some((TypeNs::StructId(s), _)) => Some(VariantDef::Struct(s.into())),
+            Some((TypeNs::EnumVariantId(v), _)) => Some(VariantDef::EnumVariant(v.into())),
+            _ => None,
+        };
+
+        match variant {
+            Some(variant) => {
+                self.variant_resolutions.insert(pat.into(), variant);
+            }
+            None => self
+                .diagnostics
+                .push(InferenceDiagnostic::UnresolvedValue { id: pat.into() }),
+        }
+        variant
+    }
+
+    /// Checks that the type of a pattern matches the type of the value it is matched against.
+    fn check_pat_type(&mut self, pat: PatId, ty: Ty, expected: Ty) -> Ty {
+        if !self.unify(&ty, &expected) {
+            self.diagnostics
+                .push(InferenceDiagnostic::MismatchedPatternType {
+                    id: pat,
+                    expected,
+                    found: ty.clone(),
+                });
+        }
+        ty
+    }
+
     /// Infer the types of all the expressions and sub-expressions in the body.
     fn infer_body(&mut self) {
         self.infer_expr_coerce(
@@ -273,6 +626,7 @@ impl<'a> InferenceResultBuilder<'a> {
     /// Performs implicit coercion of the specified `Ty` to an expected type. Returns the type after
     /// possible coercion. Adds a diagnostic message if coercion failed.
     fn coerce_expr_ty(&mut self, expr: ExprId, ty: Ty, expected: &Expectation) -> Ty {
+        let is_fn_def = ty.as_callable_def().is_some();
         let ty = if !self.coerce(&ty, &expected.ty) {
             self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
                 expected: expected.ty.clone(),
@@ -286,7 +640,14 @@ impl<'a> InferenceResultBuilder<'a> {
             expected.ty.clone()
         };
 
-        self.resolve_ty_as_far_as_possible(ty)
+        let ty = self.resolve_ty_as_far_as_possible(ty);
+
+        // A function that is used as a value is coerced to a function pointer
+        if is_fn_def && ty.as_fn_ptr().is_some() {
+            self.set_expr_type(expr, ty.clone());
+        }
+
+        ty
     }
 
     /// Returns a type used for errors
@@ -349,7 +710,7 @@ impl<'a> InferenceResultBuilder<'a> {
             Expr::Block { statements, tail } => self.infer_block(statements, *tail, expected),
             Expr::Call { callee: call, args } => self.infer_call(tgt_expr, *call, args, expected),
             Expr::Literal(lit) => match lit {
-                Literal::String(_) => TyKind::Unknown.intern(),
+                Literal::String(_) => TyKind::String.intern(),
                 Literal::Bool(_) => TyKind::Bool.intern(),
                 Literal::Int(LiteralInt {
                     kind: LiteralIntKind::Suffixed(suffix),
@@ -386,48 +747,82 @@ impl<'a> InferenceResultBuilder<'a> {
                 TyKind::Never.intern()
             }
             Expr::Break { expr } => self.infer_break(tgt_expr, *expr),
+            Expr::Continue => {
+                if self.active_loop.is_none() {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::ContinueOutsideLoop { id: tgt_expr });
+                }
+                TyKind::Never.intern()
+            }
             Expr::Loop { body } => self.infer_loop_expr(tgt_expr, *body, expected),
             Expr::While { condition, body } => {
                 self.infer_while_expr(tgt_expr, *condition, *body, expected)
             }
+            Expr::For {
+                pat,
+                iterable,
+                body,
+            } => self.infer_for_expr(*pat, *iterable, *body),
+            Expr::Range { lhs, rhs, .. } => {
+                self.infer_range_bounds(*lhs, *rhs);
+                self.diagnostics
+                    .push(InferenceDiagnostic::RangeOutsideForLoop { id: tgt_expr });
+                self.error_type()
+            }
+            Expr::Match { expr, arms } => self.infer_match(*expr, arms, expected),
+            Expr::Lambda {
+                args,
+                ret_type,
+                body,
+            } => self.infer_lambda(tgt_expr, args, *ret_type, *body, expected),
             Expr::RecordLit {
                 type_id,
                 fields,
                 spread,
             } => {
-                let ty = self.resolve_type(*type_id);
-                let def_id = ty.as_struct();
+                let (ty, variant) = self.resolve_record_lit_type(*type_id);
+                if let Some(variant) = variant {
+                    self.variant_resolutions.insert(tgt_expr.into(), variant);
+                }
                 self.unify(&ty, &expected.ty);
 
+                let (field_names, field_tys) = match variant {
+                    Some(variant) => (variant.field_names(self.db), self.field_types(&ty, variant)),
+                    None => (Vec::new(), Vec::new()),
+                };
                 for (idx, field) in fields.iter().enumerate() {
-                    let field_ty = def_id
-                        .as_ref()
-                        .and_then(|it| match it.field(self.db, &field.name) {
-                            Some(field) => Some(field),
+                    let field_ty = if variant.is_some() {
+                        match field_names.iter().position(|name| *name == field.name) {
+                            Some(field_idx) => field_tys[field_idx].clone(),
                             None => {
                                 self.diagnostics.push(InferenceDiagnostic::NoSuchField {
                                     id: tgt_expr,
                                     field: idx,
                                 });
-                                None
+                                self.error_type()
                             }
-                        })
-                        .map_or(self.error_type(), |field| field.ty(self.db));
+                        }
+                    } else {
+                        self.error_type()
+                    };
                     self.infer_expr_coerce(field.expr, &Expectation::has_type(field_ty));
                 }
                 if let Some(expr) = spread {
                     self.infer_expr(*expr, &Expectation::has_type(ty.clone()));
                 }
-                if let Some(s) = ty.as_struct() {
-                    self.check_record_lit(tgt_expr, &ty, s, &fields);
+                if let Some(variant) = variant {
+                    self.check_record_lit(tgt_expr, &ty, variant, &fields);
                 }
                 ty
             }
             Expr::Field { expr, name } => {
                 let receiver_ty = self.infer_expr(*expr, &Expectation::none());
                 match receiver_ty.interned() {
-                    TyKind::Struct(s) => {
-                        match s.field(self.db, name).map(|field| field.ty(self.db)) {
+                    TyKind::Struct(s, substs) => {
+                        match s
+                            .field(self.db, name)
+                            .map(|field| field.ty(self.db).subst(substs))
+                        {
                             Some(field_ty) => field_ty,
                             None => {
                                 self.diagnostics
@@ -450,6 +845,45 @@ impl<'a> InferenceResultBuilder<'a> {
                     }
                 }
             }
+            Expr::MethodCall {
+                receiver,
+                method_name,
+                args,
+            } => self.infer_method_call(tgt_expr, *receiver, method_name, args),
+            Expr::Index { base, index } => {
+                let base_ty = self.infer_expr(*base, &Expectation::none());
+                match base_ty.as_array() {
+                    Some(element_ty) => {
+                        let element_ty = element_ty.clone();
+                        self.infer_expr_coerce(
+                            *index,
+                            &Expectation::has_type(TyKind::Int(IntTy::usize()).intern()),
+                        );
+                        element_ty
+                    }
+                    None => {
+                        self.infer_expr(*index, &Expectation::none());
+                        if !base_ty.is_unknown() {
+                            self.diagnostics.push(InferenceDiagnostic::CannotIndex {
+                                id: *base,
+                                found: base_ty,
+                            });
+                        }
+                        self.error_type()
+                    }
+                }
+            }
+            Expr::Array(exprs) => {
+                let expected_ty = self.resolve_ty_as_far_as_possible(expected.ty.clone());
+                let element_ty = match expected_ty.as_array() {
+                    Some(element_ty) if !element_ty.is_unknown() => element_ty.clone(),
+                    _ => self.type_variables.new_type_var(),
+                };
+                for expr in exprs.iter() {
+                    self.infer_expr_coerce(*expr, &Expectation::has_type(element_ty.clone()));
+                }
+                TyKind::Array(element_ty).intern()
+            }
             Expr::UnaryOp { expr, op } => {
                 let inner_ty =
                     self.infer_expr_inner(*expr, &Expectation::none(), &CheckParams::default());
@@ -530,6 +964,98 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
+    /// Inferences the type of a match expression. The type of the expression is the merged type of
+    /// all its arms.
+    fn infer_match(&mut self, expr: ExprId, arms: &[MatchArm], expected: &Expectation) -> Ty {
+        let input_ty = self.infer_expr(expr, &Expectation::none());
+
+        let mut result_ty: Option<Ty> = None;
+        for arm in arms.iter() {
+            self.infer_pat(arm.pat, input_ty.clone());
+            let arm_ty = self.infer_expr_coerce(arm.expr, expected);
+            result_ty = Some(match result_ty {
+                None => arm_ty,
+                Some(ty) => match self.coerce_merge_branch(&ty, &arm_ty) {
+                    Some(ty) => ty,
+                    None => {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::IncompatibleBranches {
+                                id: arm.expr,
+                                then_ty: ty.clone(),
+                                else_ty: arm_ty,
+                            });
+                        ty
+                    }
+                },
+            });
+        }
+
+        // A match without arms can never produce a value
+        result_ty.unwrap_or_else(|| TyKind::Never.intern())
+    }
+
+    /// Inferences the type of a lambda expression. Parameter and return types that are not
+    /// explicitly specified are taken from the expected function pointer type, if any.
+    fn infer_lambda(
+        &mut self,
+        tgt_expr: ExprId,
+        args: &[(PatId, Option<LocalTypeRefId>)],
+        ret_type: Option<LocalTypeRefId>,
+        body: ExprId,
+        expected: &Expectation,
+    ) -> Ty {
+        let expected_sig = self
+            .resolve_ty_as_far_as_possible(expected.ty.clone())
+            .as_fn_ptr()
+            .filter(|sig| sig.params().len() == args.len())
+            .cloned();
+
+        let mut param_tys = Vec::with_capacity(args.len());
+        for (idx, (pat, type_ref)) in args.iter().enumerate() {
+            let ty = match (type_ref, &expected_sig) {
+                (Some(type_ref), _) => self.resolve_type(*type_ref),
+                (None, Some(sig)) => sig.params()[idx].clone(),
+                (None, None) => self.type_variables.new_type_var(),
+            };
+            self.infer_pat(*pat, ty.clone());
+            param_tys.push(ty);
+        }
+        let ret_ty = match (ret_type, &expected_sig) {
+            (Some(type_ref), _) => self.resolve_type(type_ref),
+            (None, Some(sig)) => sig.ret().clone(),
+            (None, None) => self.type_variables.new_type_var(),
+        };
+
+        // The body of the lambda has its own return type and is never part of an enclosing loop
+        let mut bound_pats = FxHashSet::default();
+        collect_bound_pats(&self.body, tgt_expr, &mut bound_pats);
+        self.active_lambdas.push((tgt_expr, bound_pats));
+        self.lambda_captures.insert(tgt_expr, Vec::new());
+        let outer_return_ty = std::mem::replace(&mut self.return_ty, ret_ty.clone());
+        let outer_loop = self.active_loop.take();
+
+        self.infer_expr_coerce(body, &Expectation::has_type(ret_ty.clone()));
+
+        self.active_loop = outer_loop;
+        self.return_ty = outer_return_ty;
+        self.active_lambdas.pop();
+
+        TyKind::FnPtr(FnSig::from_params_and_return(param_tys, ret_ty)).intern()
+    }
+
+    /// Records that the local variable bound by `pat` is captured by all active lambdas in which
+    /// it is not bound.
+    fn capture_local_binding(&mut self, pat: PatId) {
+        for (lambda, bound_pats) in self.active_lambdas.iter() {
+            if !bound_pats.contains(&pat) {
+                let captures = self.lambda_captures.entry(*lambda).or_default();
+                if !captures.contains(&pat) {
+                    captures.push(pat);
+                }
+            }
+        }
+    }
+
     /// Inferences the type of a call expression.
     fn infer_call(
         &mut self,
@@ -547,7 +1073,7 @@ impl<'a> InferenceResultBuilder<'a> {
         );
 
         match callee_ty.interned() {
-            TyKind::Struct(s) => {
+            TyKind::Struct(s, _) => {
                 // Erroneously found either a unit struct or record struct literal. Record struct
                 // literals can never be used as a value so that will have already been reported.
                 if s.data(self.db.upcast()).kind == StructKind::Unit {
@@ -566,13 +1092,47 @@ impl<'a> InferenceResultBuilder<'a> {
 
                 callee_ty
             }
+            TyKind::Enum(_)
+                if self
+                    .variant_resolutions
+                    .contains_key(&ExprOrPatId::from(callee)) =>
+            {
+                // Erroneously found either a unit variant or record variant literal. Record
+                // variants can never be used as a value so that will have already been reported.
+                if self.variant_resolutions[&ExprOrPatId::from(callee)].kind(self.db)
+                    == StructKind::Unit
+                {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::MismatchedStructLit {
+                            id: tgt_expr,
+                            expected: StructKind::Unit,
+                            found: StructKind::Tuple,
+                        });
+                }
+
+                // Still derive subtypes
+                for arg in args.iter() {
+                    self.infer_expr(*arg, &Expectation::none());
+                }
+
+                callee_ty
+            }
+            TyKind::FnPtr(sig) => {
+                let (param_tys, ret_ty) = (sig.params().to_vec(), sig.ret().clone());
+                self.check_call_argument_count(tgt_expr, false, args.len(), param_tys.len());
+                for (&arg, param_ty) in args.iter().zip(param_tys.iter()) {
+                    self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone()));
+                }
+
+                ret_ty
+            }
             TyKind::FnDef(def, _substs) => {
                 // Found either a tuple struct literal or function
                 let sig = callee_ty.callable_sig(self.db).unwrap();
                 let (param_tys, ret_ty) = (sig.params().to_vec(), sig.ret().clone());
                 self.check_call_argument_count(
                     tgt_expr,
-                    def.is_struct(),
+                    def.is_struct() || def.is_enum_variant(),
                     args.len(),
                     param_tys.len(),
                 );
@@ -597,14 +1157,84 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
-    /// Checks whether the specified struct type is a unit struct.
-    fn check_unit_struct_lit(&mut self, tgt_expr: ExprId, expected: Struct) {
-        let struct_data = expected.data(self.db.upcast());
-        if struct_data.kind != StructKind::Unit {
+    /// Inferences the type of a method call expression. The method is looked up in the `impl`
+    /// blocks of the type of the receiver. If the receiver is of a generic type, the method is
+    /// looked up in the traits that bound the type parameter.
+    fn infer_method_call(
+        &mut self,
+        tgt_expr: ExprId,
+        receiver: ExprId,
+        method_name: &Name,
+        args: &[ExprId],
+    ) -> Ty {
+        let receiver_ty = self.infer_expr(receiver, &Expectation::none());
+        let receiver_ty = self.resolve_ty_as_far_as_possible(receiver_ty);
+
+        let method = match receiver_ty.interned() {
+            TyKind::Param { idx, .. } => self
+                .type_param_bounds(*idx)
+                .into_iter()
+                .find_map(|bound| bound.function(self.db, method_name)),
+            _ => lookup_associated_function(self.db, &receiver_ty, method_name),
+        }
+        .filter(|method| method.has_self_param(self.db));
+        let method = match method {
+            Some(method) => method,
+            None => {
+                // If the type of the receiver is unknown, an error has already been emitted
+                if !receiver_ty.is_unknown() {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::UnresolvedMethod {
+                            id: tgt_expr,
+                            receiver_ty,
+                            method_name: method_name.clone(),
+                        });
+                }
+
+                // Still derive subtypes
+                for arg in args.iter() {
+                    self.infer_expr(*arg, &Expectation::none());
+                }
+
+                return self.error_type();
+            }
+        };
+
+        if !method.visibility(self.db).is_visible_from(
+            self.db,
+            self.resolver
+                .module()
+                .expect("resolver must have a module to be able to resolve modules"),
+        ) {
+            self.diagnostics
+                .push(InferenceDiagnostic::PathIsPrivate { id: tgt_expr });
+        }
+        // The first parameter of a method is always the receiver
+        let method_ty = self.instantiate(method.ty(self.db));
+        let substs = method_ty
+            .type_parameters()
+            .cloned()
+            .unwrap_or_else(Substitution::empty);
+        self.unify_trait_self_ty(method, &substs, &receiver_ty);
+        self.method_resolutions.insert(tgt_expr, (method, substs));
+        let sig = method_ty.callable_sig(self.db).unwrap();
+        let (param_tys, ret_ty) = (sig.params()[1..].to_vec(), sig.ret().clone());
+        self.check_call_argument_count(tgt_expr, false, args.len(), param_tys.len());
+        for (&arg, param_ty) in args.iter().zip(param_tys.iter()) {
+            self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone()));
+        }
+
+        ret_ty
+    }
+
+    /// Checks whether the specified struct or enum variant is a unit struct or unit variant.
+    fn check_unit_struct_lit(&mut self, tgt_expr: ExprId, expected: VariantDef) {
+        let kind = expected.kind(self.db);
+        if kind != StructKind::Unit {
             self.diagnostics
                 .push(InferenceDiagnostic::MismatchedStructLit {
                     id: tgt_expr,
-                    expected: struct_data.kind,
+                    expected: kind,
                     found: StructKind::Unit,
                 });
         }
@@ -636,37 +1266,30 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
-    // Checks whether the passed fields match the fields of a struct definition.
+    // Checks whether the passed fields match the fields of a struct or enum variant definition.
     fn check_record_lit(
         &mut self,
         tgt_expr: ExprId,
         ty: &Ty,
-        expected: Struct,
+        expected: VariantDef,
         fields: &[RecordLitField],
     ) {
-        let struct_data = expected.data(self.db.upcast());
-        if struct_data.kind != StructKind::Record {
+        let kind = expected.kind(self.db);
+        if kind != StructKind::Record {
             self.diagnostics
                 .push(InferenceDiagnostic::MismatchedStructLit {
                     id: tgt_expr,
-                    expected: struct_data.kind,
+                    expected: kind,
                     found: StructKind::Record,
                 });
             return;
         }
 
         let lit_fields: FxHashSet<_> = fields.iter().map(|f| &f.name).collect();
-        let missed_fields: Vec<Name> = struct_data
-            .fields
-            .iter()
-            .filter_map(|(_f, d)| {
-                let name = d.name.clone();
-                if lit_fields.contains(&name) {
-                    None
-                } else {
-                    Some(name)
-                }
-            })
+        let missed_fields: Vec<Name> = expected
+            .field_names(self.db)
+            .into_iter()
+            .filter(|name| !lit_fields.contains(name))
             .collect();
 
         if !missed_fields.is_empty() {
@@ -678,6 +1301,76 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
+    /// Resolves the type of a record literal. Next to structs, a record literal can also construct
+    /// an enum variant (e.g. `State::Attacking { target: 0 }`) in which case the type of the
+    /// literal is the type of the enum.
+    fn resolve_record_lit_type(&mut self, type_id: LocalTypeRefId) -> (Ty, Option<VariantDef>) {
+        let body = Arc::clone(&self.body); // avoid borrow checker problem
+        if let TypeRef::Path(path) = &body.type_refs()[type_id] {
+            if let Some((TypeNs::EnumVariantId(variant), vis)) = self
+                .resolver
+                .resolve_path_as_type_fully(self.db.upcast(), path)
+            {
+                if !vis.is_visible_from(
+                    self.db,
+                    self.resolver
+                        .module()
+                        .expect("resolver must have a module to be able to resolve modules"),
+                ) {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::TypeIsPrivate { id: type_id });
+                }
+
+                let variant = EnumVariant::from(variant);
+                return (
+                    variant.parent_enum().ty(self.db),
+                    Some(VariantDef::EnumVariant(variant)),
+                );
+            }
+        }
+
+        // The type arguments of a generic struct are inferred from the fields of the literal if
+        // they are not specified, e.g. `Pair { a: 1, b: 2.0 }`
+        if let TypeRef::Path(path) = &body.type_refs()[type_id] {
+            if let Some((TypeNs::StructId(strukt), vis)) = self
+                .resolver
+                .resolve_path_as_type_fully(self.db.upcast(), path)
+            {
+                let strukt = Struct::from(strukt);
+                if strukt.is_generic(self.db) {
+                    if !vis.is_visible_from(
+                        self.db,
+                        self.resolver
+                            .module()
+                            .expect("resolver must have a module to be able to resolve modules"),
+                    ) {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::TypeIsPrivate { id: type_id });
+                    }
+                    let ty = self.instantiate(strukt.ty(self.db));
+                    return (ty, Some(VariantDef::Struct(strukt)));
+                }
+            }
+        }
+
+        let ty = self.resolve_type(type_id);
+        let variant = ty.as_struct().map(VariantDef::Struct);
+        (ty, variant)
+    }
+
+    /// Returns the types of the fields of a struct or enum variant, where `ty` is the (possibly
+    /// instantiated) type of the struct or enum.
+    fn field_types(&self, ty: &Ty, variant: VariantDef) -> Vec<Ty> {
+        let field_tys = variant.field_types(self.db);
+        match ty.interned() {
+            TyKind::Struct(_, substs) => field_tys
+                .into_iter()
+                .map(|field_ty| field_ty.subst(substs))
+                .collect(),
+            _ => field_tys,
+        }
+    }
+
     fn infer_path_expr(
         &mut self,
         resolver: &Resolver,
@@ -685,7 +1378,19 @@ impl<'a> InferenceResultBuilder<'a> {
         id: ExprId,
         check_params: &CheckParams,
     ) -> Option<Ty> {
-        match resolver.resolve_path_as_value_fully(self.db.upcast(), path) {
+        let resolved = match resolver.resolve_path_as_value(self.db.upcast(), path) {
+            Some(ResolveValueResult::ValueNs(value, vis)) => Some((value, vis)),
+            Some(ResolveValueResult::Partial(type_ns, idx)) => {
+                // The path might refer to an associated function of a type (e.g. `Foo::new`)
+                if let Some(ty) = self.infer_assoc_fn_path(path, id, type_ns, idx) {
+                    return Some(ty);
+                }
+                None
+            }
+            None => None,
+        };
+
+        match resolved {
             Some((value, vis)) => {
                 // Check visibility of this item
                 if !vis.is_visible_from(
@@ -700,22 +1405,51 @@ impl<'a> InferenceResultBuilder<'a> {
 
                 // Match based on what type of value we found
                 match value {
-                    ValueNs::LocalBinding(pat) => Some(self.type_of_pat.get(pat)?.clone()),
+                    ValueNs::LocalBinding(pat) => {
+                        self.capture_local_binding(pat);
+                        Some(self.type_of_pat.get(pat)?.clone())
+                    }
                     ValueNs::FunctionId(f) => {
                         let (ty, _) = self
                             .db
                             .type_for_def(TypableDef::Function(f.into()), Namespace::Values);
-                        Some(ty)
+                        Some(self.instantiate(ty))
                     }
                     ValueNs::StructId(s) => {
                         if check_params.is_unit_struct {
-                            self.check_unit_struct_lit(id, s.into())
+                            self.check_unit_struct_lit(id, Struct::from(s).into())
                         }
                         let (ty, _) = self
                             .db
                             .type_for_def(TypableDef::Struct(s.into()), Namespace::Values);
+                        Some(self.instantiate(ty))
+                    }
+                    ValueNs::EnumVariantId(v) => {
+                        let variant = EnumVariant::from(v);
+                        if check_params.is_unit_struct {
+                            self.check_unit_struct_lit(id, variant.into())
+                        }
+                        self.variant_resolutions.insert(id.into(), variant.into());
+                        let (ty, _) = self
+                            .db
+                            .type_for_def(TypableDef::EnumVariant(variant), Namespace::Values);
                         Some(ty)
                     }
+                    ValueNs::ConstId(c) => {
+                        let (ty, _) = self
+                            .db
+                            .type_for_def(TypableDef::Const(c.into()), Namespace::Values);
+                        Some(ty)
+                    }
+                    ValueNs::StaticId(s) => {
+                        let (ty, _) = self
+                            .db
+                            .type_for_def(TypableDef::Static(s.into()), Namespace::Values);
+                        Some(ty)
+                    }
+                    ValueNs::Intrinsic(intrinsic) => {
+                        Some(TyKind::FnDef(intrinsic.into(), Substitution::empty()).intern())
+                    }
                 }
             }
 
@@ -751,7 +1485,27 @@ impl<'a> InferenceResultBuilder<'a> {
                     let (ty, _) = self
                         .db
                         .type_for_def(TypableDef::Struct(struct_id.into()), Namespace::Values);
-                    return Some(ty);
+                    return Some(self.instantiate(ty));
+                }
+
+                // Similarly, a record enum variant cannot be used as a value
+                if let Some((TypeNs::EnumVariantId(variant_id), _)) = ty {
+                    let variant = EnumVariant::from(variant_id);
+                    debug_assert_eq!(variant.kind(self.db), StructKind::Record);
+
+                    self.diagnostics
+                        .push(InferenceDiagnostic::MismatchedStructLit {
+                            id,
+                            expected: StructKind::Record,
+                            found: if check_params.is_unit_struct {
+                                StructKind::Unit
+                            } else {
+                                StructKind::Tuple
+                            },
+                        });
+
+                    self.variant_resolutions.insert(id.into(), variant.into());
+                    return Some(variant.parent_enum().ty(self.db));
                 }
 
                 // If the path also cannot be resolved as type, it must be considered an invalid
@@ -763,14 +1517,122 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
+    /// Tries to resolve the type of a path that refers to an associated function of a type (e.g.
+    /// `Foo::new` or `Self::new`). `type_ns` is the type that was resolved from the first `idx`
+    /// segments of the path.
+    fn infer_assoc_fn_path(
+        &mut self,
+        path: &Path,
+        id: ExprId,
+        type_ns: TypeNs,
+        idx: usize,
+    ) -> Option<Ty> {
+        // Only a single segment can follow the type
+        if idx + 1 != path.segments.len() {
+            return None;
+        }
+
+        let ty = match type_ns {
+            TypeNs::SelfType(impl_id) => Impl::from(impl_id).self_ty(self.db),
+            TypeNs::StructId(struct_id) => {
+                self.db
+                    .type_for_def(TypableDef::Struct(struct_id.into()), Namespace::Types)
+                    .0
+            }
+            TypeNs::EnumId(enum_id) => {
+                self.db
+                    .type_for_def(TypableDef::Enum(enum_id.into()), Namespace::Types)
+                    .0
+            }
+            TypeNs::TypeAliasId(type_alias_id) => {
+                self.db
+                    .type_for_def(
+                        TypableDef::TypeAlias(type_alias_id.into()),
+                        Namespace::Types,
+                    )
+                    .0
+            }
+            TypeNs::EnumVariantId(_)
+            | TypeNs::PrimitiveType(_)
+            | TypeNs::GenericParam(_)
+            | TypeNs::TraitId(_) => return None,
+        };
+
+        let func = lookup_associated_function(self.db, &ty, &path.segments[idx])?;
+        if !func.visibility(self.db).is_visible_from(
+            self.db,
+            self.resolver
+                .module()
+                .expect("resolver must have a module to be able to resolve modules"),
+        ) {
+            self.diagnostics
+                .push(InferenceDiagnostic::PathIsPrivate { id })
+        }
+
+        let (fn_ty, _) = self
+            .db
+            .type_for_def(TypableDef::Function(func), Namespace::Values);
+        let fn_ty = self.instantiate(fn_ty);
+        if let Some(substs) = fn_ty.type_parameters() {
+            self.unify_trait_self_ty(func, substs, &ty);
+        }
+        Some(fn_ty)
+    }
+
+    /// If `func` is declared in a trait, unifies its `Self` type, the first type argument in
+    /// `substs`, with `self_ty`: the type for which the function is called.
+    fn unify_trait_self_ty(&mut self, func: Function, substs: &Substitution, self_ty: &Ty) {
+        if func.parent_trait(self.db).is_some() {
+            if let Some(trait_self_ty) = substs.first() {
+                self.unify(trait_self_ty, self_ty);
+            }
+        }
+    }
+
+    /// Returns the traits that bound the type parameter at `idx` of the function whose body is
+    /// inferred.
+    fn type_param_bounds(&self, idx: u32) -> Vec<Trait> {
+        match self.body.owner() {
+            DefWithBodyId::FunctionId(func) => Function::from(func).type_param_bounds(self.db, idx),
+            DefWithBodyId::ConstId(_) | DefWithBodyId::StaticId(_) => Vec::new(),
+        }
+    }
+
+    /// Returns true if `ty` implements `bound`. A type parameter implements the traits it is
+    /// bounded by.
+    fn implements_trait(&self, ty: &Ty, bound: Trait) -> bool {
+        match ty.interned() {
+            TyKind::Param { idx, .. } => self.type_param_bounds(*idx).contains(&bound),
+            TyKind::Unknown | TyKind::InferenceVar(_) => true,
+            _ => find_trait_impl(self.db, bound, ty).is_some(),
+        }
+    }
+
+    /// Verifies that the type arguments of a call of `func` implement the traits that the type
+    /// parameters of `func` are bounded by.
+    fn check_trait_bounds(&mut self, id: ExprId, func: Function, substs: &Substitution) {
+        for (idx, ty) in substs.iter().enumerate() {
+            for bound in func.type_param_bounds(self.db, idx as u32) {
+                if !self.implements_trait(ty, bound) {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::TraitBoundNotSatisfied {
+                            id,
+                            ty: ty.clone(),
+                            trait_name: bound.name(self.db),
+                        });
+                }
+            }
+        }
+    }
+
     fn resolve_all(mut self) -> InferenceResult {
         // FIXME resolve obligations as well (use Guidance if necessary)
         //let mut tv_stack = Vec::new();
         let mut expr_types = std::mem::take(&mut self.type_of_expr);
         for (expr, ty) in expr_types.iter_mut() {
-            let was_unknown = ty.is_unknown();
+            let was_unknown = ty.contains_unknown();
             let resolved = self.type_variables.resolve_ty_completely(ty.clone());
-            if !was_unknown && resolved.is_unknown() {
+            if !was_unknown && resolved.contains_unknown() {
                 self.report_expr_inference_failure(expr);
             }
             *ty = resolved;
@@ -784,13 +1646,42 @@ impl<'a> InferenceResultBuilder<'a> {
             }
             *ty = resolved;
         }
+        let mut method_resolutions = std::mem::take(&mut self.method_resolutions);
+        for (_, (_, substs)) in method_resolutions.iter_mut() {
+            *substs = substs
+                .clone()
+                .fold(&mut |ty| self.type_variables.resolve_ty_completely(ty));
+        }
+
+        // Verify the trait bounds of all generic functions that are called or referred to
+        for (expr, ty) in expr_types.iter() {
+            let callee = match (&self.body[expr], ty.interned()) {
+                (Expr::Path(_), TyKind::FnDef(CallableDef::Function(func), substs)) => {
+                    Some((*func, substs.clone()))
+                }
+                (Expr::MethodCall { .. }, _) => method_resolutions.get(&expr).cloned(),
+                _ => None,
+            };
+            if let Some((func, substs)) = callee {
+                self.check_trait_bounds(expr, func, &substs);
+            }
+        }
+
+        // Calls of trait functions for which the `Self` type is known are resolved to the
+        // functions that implement them
+        for (_, resolution) in method_resolutions.iter_mut() {
+            let (method, substs) = resolution.clone();
+            *resolution = resolve_trait_method(self.db, method, substs);
+        }
         InferenceResult {
             //            method_resolutions: self.method_resolutions,
             //            field_resolutions: self.field_resolutions,
-            //            variant_resolutions: self.variant_resolutions,
             //            assoc_resolutions: self.assoc_resolutions,
             type_of_expr: expr_types,
             type_of_pat: pat_types,
+            variant_resolutions: self.variant_resolutions,
+            method_resolutions,
+            lambda_captures: self.lambda_captures,
             diagnostics: self.diagnostics,
             standard_types: Default::default(),
         }
@@ -926,6 +1817,35 @@ impl<'a> InferenceResultBuilder<'a> {
         Ty::unit()
     }
 
+    fn infer_for_expr(&mut self, pat: PatId, iterable: ExprId, body: ExprId) -> Ty {
+        let elem_ty = match self.body[iterable] {
+            Expr::Range { lhs, rhs, .. } => self.infer_range_bounds(lhs, rhs),
+            _ => {
+                let iterable_ty = self.infer_expr(iterable, &Expectation::none());
+                if !iterable_ty.is_unknown() {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::InvalidForIterable {
+                            id: iterable,
+                            found: iterable_ty,
+                        });
+                }
+                self.error_type()
+            }
+        };
+        self.infer_pat(pat, elem_ty);
+        self.infer_loop_block(body, ActiveLoop::For);
+        Ty::unit()
+    }
+
+    /// Infers the bounds of a range expression, both of which must be of the same integer type.
+    /// Returns the type of the elements in the range.
+    fn infer_range_bounds(&mut self, lhs: ExprId, rhs: ExprId) -> Ty {
+        let elem_ty = self.type_variables.new_integer_var();
+        self.infer_expr(lhs, &Expectation::has_type(elem_ty.clone()));
+        self.infer_expr(rhs, &Expectation::has_type(elem_ty.clone()));
+        self.resolve_ty_as_far_as_possible(elem_ty)
+    }
+
     pub fn report_pat_inference_failure(&mut self, _pat: PatId) {
         //        self.diagnostics.push(InferenceDiagnostic::PatInferenceFailed {
         //            pat
@@ -935,13 +1855,11 @@ impl<'a> InferenceResultBuilder<'a> {
         panic!("pattern failed inferencing");
     }
 
-    pub fn report_expr_inference_failure(&mut self, _expr: ExprId) {
-        //        self.diagnostics.push(InferenceDiagnostic::ExprInferenceFailed {
-        //            expr
-        //        });
-        // Currently this should never happen because we can only infer integer and floating-point
-        // types which always have a fallback value.
-        panic!("expression failed inferencing");
+    pub fn report_expr_inference_failure(&mut self, expr: ExprId) {
+        // Integer and floating-point variables always have a fallback value, this can only happen
+        // if the element type of an array could not be inferred.
+        self.diagnostics
+            .push(InferenceDiagnostic::TypeAnnotationsNeeded { id: expr });
     }
 }
 
@@ -1009,17 +1927,21 @@ impl From<PatId> for ExprOrPatId {
 mod diagnostics {
     use crate::diagnostics::PrivateAccess;
     use crate::{
-        code_model::{src::HasSource, StructKind},
+        code_model::{DefWithBody, StructKind},
         diagnostics::{
             AccessUnknownField, BreakOutsideLoop, BreakWithValueOutsideLoop, CannotApplyBinaryOp,
-            CannotApplyUnaryOp, ExpectedFunction, FieldCountMismatch, IncompatibleBranch,
-            InvalidLhs, LiteralOutOfRange, MismatchedStructLit, MismatchedType, MissingElseBranch,
-            MissingFields, NoFields, NoSuchField, ParameterCountMismatch, ReturnMissingExpression,
+            CannotApplyUnaryOp, CannotIndex, ContinueOutsideLoop, ExpectedFunction,
+            FieldCountMismatch, IncompatibleBranch, InvalidForIterable, InvalidLhs,
+            LiteralOutOfRange, MismatchedStructLit, MismatchedStructPat, MismatchedType,
+            MissingElseBranch, MissingFields, MissingPatternFields, NoFields, NoSuchField,
+            ParameterCountMismatch, PatternFieldCountMismatch, RangeOutsideForLoop,
+            ReturnMissingExpression, TraitBoundNotSatisfied, TypeAnnotationsNeeded,
+            TypeArgCountMismatch, UnresolvedMethod,
         },
         diagnostics::{CyclicType, DiagnosticSink, UnresolvedType, UnresolvedValue},
         ty::infer::ExprOrPatId,
         type_ref::LocalTypeRefId,
-        ExprId, Function, HirDatabase, IntTy, Name, Ty,
+        ExprId, HirDatabase, HirDisplay, IntTy, Name, PatId, Ty,
     };
 
     #[derive(Debug, PartialEq, Eq, Clone)]
@@ -1078,11 +2000,31 @@ mod diagnostics {
         BreakWithValueOutsideLoop {
             id: ExprId,
         },
+        ContinueOutsideLoop {
+            id: ExprId,
+        },
+        InvalidForIterable {
+            id: ExprId,
+            found: Ty,
+        },
+        RangeOutsideForLoop {
+            id: ExprId,
+        },
         AccessUnknownField {
             id: ExprId,
             receiver_ty: Ty,
             name: Name,
         },
+        UnresolvedMethod {
+            id: ExprId,
+            receiver_ty: Ty,
+            method_name: Name,
+        },
+        TraitBoundNotSatisfied {
+            id: ExprId,
+            ty: Ty,
+            trait_name: Name,
+        },
         FieldCountMismatch {
             id: ExprId,
             found: usize,
@@ -1106,6 +2048,13 @@ mod diagnostics {
             id: ExprId,
             field: usize,
         },
+        CannotIndex {
+            id: ExprId,
+            found: Ty,
+        },
+        TypeAnnotationsNeeded {
+            id: ExprId,
+        },
         LiteralOutOfRange {
             id: ExprId,
             literal_ty: IntTy,
@@ -1113,19 +2062,46 @@ mod diagnostics {
         TypeIsPrivate {
             id: LocalTypeRefId,
         },
+        TypeArgCountMismatch {
+            id: LocalTypeRefId,
+            expected: usize,
+            found: usize,
+        },
         PathIsPrivate {
             id: ExprId,
         },
+        MismatchedPatternType {
+            id: PatId,
+            expected: Ty,
+            found: Ty,
+        },
+        MismatchedStructPat {
+            id: PatId,
+            expected: StructKind,
+            found: StructKind,
+        },
+        PatternFieldCountMismatch {
+            id: PatId,
+            found: usize,
+            expected: usize,
+        },
+        NoSuchPatternField {
+            id: PatId,
+        },
+        MissingPatternFields {
+            id: PatId,
+            names: Vec<Name>,
+        },
     }
 
     impl InferenceDiagnostic {
         pub(crate) fn add_to(
             &self,
             db: &dyn HirDatabase,
-            owner: Function,
+            owner: DefWithBody,
             sink: &mut DiagnosticSink,
         ) {
-            let file = owner.source(db.upcast()).file_id;
+            let file = owner.file_id(db);
             let body = owner.body_source_map(db);
             match self {
                 InferenceDiagnostic::UnresolvedValue { id } => {
@@ -1157,6 +2133,19 @@ mod diagnostics {
                         expr: type_ref.syntax_node_ptr(),
                     });
                 }
+                InferenceDiagnostic::TypeArgCountMismatch {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    let type_ref = body.type_ref_syntax(*id).expect("If this is not found, it must be a type ref generated by the library which should never be unresolved.");
+                    sink.push(TypeArgCountMismatch {
+                        file,
+                        type_ref,
+                        expected: *expected,
+                        found: *found,
+                    });
+                }
                 InferenceDiagnostic::PathIsPrivate { id } => {
                     let expr_syntax = body
                         .expr_syntax(*id)
@@ -1320,6 +2309,40 @@ mod diagnostics {
                         break_expr: id,
                     });
                 }
+                InferenceDiagnostic::ContinueOutsideLoop { id } => {
+                    let id = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(ContinueOutsideLoop {
+                        file,
+                        continue_expr: id,
+                    });
+                }
+                InferenceDiagnostic::InvalidForIterable { id, found } => {
+                    let iterable = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(InvalidForIterable {
+                        file,
+                        iterable,
+                        found: found.clone(),
+                    });
+                }
+                InferenceDiagnostic::RangeOutsideForLoop { id } => {
+                    let id = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(RangeOutsideForLoop {
+                        file,
+                        range_expr: id,
+                    });
+                }
                 InferenceDiagnostic::AccessUnknownField {
                     id,
                     receiver_ty,
@@ -1337,6 +2360,36 @@ mod diagnostics {
                         name: name.clone(),
                     })
                 }
+                InferenceDiagnostic::UnresolvedMethod {
+                    id,
+                    receiver_ty,
+                    method_name,
+                } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(UnresolvedMethod {
+                        file,
+                        expr,
+                        receiver_ty: receiver_ty.clone(),
+                        method_name: method_name.clone(),
+                    })
+                }
+                InferenceDiagnostic::TraitBoundNotSatisfied { id, ty, trait_name } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(TraitBoundNotSatisfied {
+                        file,
+                        expr,
+                        ty: ty.display(db).to_string(),
+                        trait_name: trait_name.clone(),
+                    })
+                }
                 InferenceDiagnostic::FieldCountMismatch {
                     id,
                     expected,
@@ -1401,6 +2454,26 @@ mod diagnostics {
                         found: found.clone(),
                     })
                 }
+                InferenceDiagnostic::CannotIndex { id, found } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(CannotIndex {
+                        file,
+                        base_expr: expr,
+                        found: found.clone(),
+                    })
+                }
+                InferenceDiagnostic::TypeAnnotationsNeeded { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(TypeAnnotationsNeeded { file, expr })
+                }
                 InferenceDiagnostic::NoSuchField { id, field } => {
                     let field = owner.body_source_map(db).field_syntax(*id, *field).into();
                     sink.push(NoSuchField { file, field });
@@ -1421,6 +2494,57 @@ mod diagnostics {
                         int_ty: *literal_ty,
                     })
                 }
+                InferenceDiagnostic::MismatchedPatternType {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    let pat = body.pat_syntax(*id).unwrap().value.syntax_node_ptr();
+                    sink.push(MismatchedType {
+                        file,
+                        expr: pat,
+                        found: found.clone(),
+                        expected: expected.clone(),
+                    });
+                }
+                InferenceDiagnostic::MismatchedStructPat {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    let pat = body.pat_syntax(*id).unwrap().value.syntax_node_ptr();
+                    sink.push(MismatchedStructPat {
+                        file,
+                        pat,
+                        expected: *expected,
+                        found: *found,
+                    });
+                }
+                InferenceDiagnostic::PatternFieldCountMismatch {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    let pat = body.pat_syntax(*id).unwrap().value.syntax_node_ptr();
+                    sink.push(PatternFieldCountMismatch {
+                        file,
+                        pat,
+                        expected: *expected,
+                        found: *found,
+                    });
+                }
+                InferenceDiagnostic::NoSuchPatternField { id } => {
+                    let field = body.pat_syntax(*id).unwrap().value.syntax_node_ptr();
+                    sink.push(NoSuchField { file, field });
+                }
+                InferenceDiagnostic::MissingPatternFields { id, names } => {
+                    let pat = body.pat_syntax(*id).unwrap().value.syntax_node_ptr();
+                    sink.push(MissingPatternFields {
+                        file,
+                        pat,
+                        field_names: names.to_vec(),
+                    });
+                }
             }
         }
     }
@@ -1,5 +1,8 @@
 use super::InferenceResultBuilder;
-use crate::{ty::TyKind, Ty};
+use crate::{
+    ty::{CallableDef, TyKind},
+    Ty,
+};
 
 impl<'a> InferenceResultBuilder<'a> {
     /// Unify two types, but may coerce the first one to the second using implicit coercion rules if
@@ -24,6 +27,16 @@ impl<'a> InferenceResultBuilder<'a> {
     fn coerce_inner(&mut self, from_ty: Ty, to_ty: &Ty) -> bool {
         match (from_ty.interned(), to_ty.interned()) {
             (TyKind::Never, ..) => return true,
+
+            // A non-generic function can be coerced to a function pointer with the same signature
+            (TyKind::FnDef(CallableDef::Function(_), substs), TyKind::FnPtr(_))
+                if substs.is_empty() =>
+            {
+                let sig = from_ty
+                    .callable_sig(self.db)
+                    .expect("a function must have a signature");
+                return self.unify(&TyKind::FnPtr(sig).intern(), to_ty);
+            }
             _ => {
                 if self.type_variables.unify_inner_trivial(&from_ty, &to_ty) {
                     return true;
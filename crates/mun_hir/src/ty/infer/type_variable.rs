@@ -1,6 +1,6 @@
 use crate::{
     ty::infer::InferTy,
-    ty::{TyKind, TypeWalk},
+    ty::{Substitution, TyKind, TypeWalk},
     Ty,
 };
 use ena::unify::{InPlaceUnificationTable, NoError, UnifyKey, UnifyValue};
@@ -182,11 +182,44 @@ impl TypeVariableTable {
                 true
             }
 
+            // Arrays unify if their element types unify
+            (TyKind::Array(a), TyKind::Array(b)) => self.unify_inner(a, b),
+
+            // Function pointers unify if their parameter and return types unify
+            (TyKind::FnPtr(a), TyKind::FnPtr(b)) => {
+                a.params().len() == b.params().len()
+                    && a.params()
+                        .iter()
+                        .chain(std::iter::once(a.ret()))
+                        .zip(b.params().iter().chain(std::iter::once(b.ret())))
+                        .fold(true, |unified, (a, b)| self.unify_inner(a, b) && unified)
+            }
+
+            // Instances of the same generic struct, function or tuple unify if all their type
+            // arguments unify
+            (TyKind::Struct(a, a_substs), TyKind::Struct(b, b_substs)) if a == b => {
+                self.unify_substs(a_substs, b_substs)
+            }
+            (TyKind::FnDef(a, a_substs), TyKind::FnDef(b, b_substs)) if a == b => {
+                self.unify_substs(a_substs, b_substs)
+            }
+            (TyKind::Tuple(a, a_substs), TyKind::Tuple(b, b_substs)) if a == b => {
+                self.unify_substs(a_substs, b_substs)
+            }
+
             // Was not able to unify the types
             _ => false,
         }
     }
 
+    /// Unifies the type arguments of two instances of the same generic type pairwise.
+    fn unify_substs(&mut self, a: &Substitution, b: &Substitution) -> bool {
+        a.len() == b.len()
+            && a.iter()
+                .zip(b.iter())
+                .fold(true, |unified, (a, b)| self.unify_inner(a, b) && unified)
+    }
+
     /// Records that `a == b`
     fn equate(&mut self, a: TypeVarId, b: TypeVarId) {
         debug_assert!(self.eq_relations.probe_value(a).is_unknown());
@@ -1,16 +1,19 @@
 pub(crate) use self::diagnostics::LowerDiagnostic;
+use crate::ids::Lookup;
 use crate::resolve::{HasResolver, TypeNs};
 use crate::ty::{Substitution, TyKind};
 use crate::{
     arena::map::ArenaMap,
     code_model::StructKind,
     diagnostics::DiagnosticSink,
+    intrinsic::Intrinsic,
     name_resolution::Namespace,
     primitive_type::PrimitiveType,
     resolve::Resolver,
-    ty::{FnSig, Ty},
+    ty::{FnSig, IntTy, Ty},
     type_ref::{LocalTypeRefId, TypeRef, TypeRefMap, TypeRefSourceMap},
-    FileId, Function, HirDatabase, ModuleDef, Path, Struct, TypeAlias,
+    Const, Enum, EnumVariant, FileId, Function, HirDatabase, Impl, ModuleDef, Path, Static, Struct,
+    TypeAlias,
 };
 use crate::{HasVisibility, Visibility};
 use std::{ops::Index, sync::Arc};
@@ -86,12 +89,8 @@ impl Ty {
         diagnostics: &mut Vec<LowerDiagnostic>,
         type_ref: LocalTypeRefId,
     ) -> Ty {
-        let res = match &type_ref_map[type_ref] {
-            TypeRef::Path(path) => Ty::from_hir_path(db, resolver, type_ref, path, diagnostics),
-            TypeRef::Error => Some((TyKind::Unknown.intern(), false)),
-            TypeRef::Empty => Some((Ty::unit(), false)),
-            TypeRef::Never => Some((TyKind::Never.intern(), false)),
-        };
+        let res =
+            Ty::from_hir_type_ref(db, resolver, &type_ref_map[type_ref], type_ref, diagnostics);
         if let Some((ty, is_cyclic)) = res {
             if is_cyclic {
                 diagnostics.push(LowerDiagnostic::CyclicType { id: type_ref })
@@ -103,21 +102,81 @@ impl Ty {
         }
     }
 
+    /// Lowers the specified `TypeRef`. Diagnostics are reported on `type_ref_id` which is the
+    /// type reference that contains `type_ref`.
+    fn from_hir_type_ref(
+        db: &dyn HirDatabase,
+        resolver: &Resolver,
+        type_ref: &TypeRef,
+        type_ref_id: LocalTypeRefId,
+        diagnostics: &mut Vec<LowerDiagnostic>,
+    ) -> Option<(Self, bool)> {
+        match type_ref {
+            TypeRef::Path(path) => {
+                Ty::from_hir_path(db, resolver, type_ref_id, path, &[], diagnostics)
+            }
+            TypeRef::Generic(path, args) => {
+                Ty::from_hir_path(db, resolver, type_ref_id, path, args, diagnostics)
+            }
+            TypeRef::Array(element) => {
+                let (element_ty, is_cyclic) =
+                    Ty::from_hir_type_ref(db, resolver, element, type_ref_id, diagnostics)?;
+                Some((TyKind::Array(element_ty).intern(), is_cyclic))
+            }
+            TypeRef::Fn(params, ret_type) => {
+                // Function pointers are heap allocated so they never result in cyclic types
+                let mut param_tys = Vec::with_capacity(params.len());
+                for param in params.iter() {
+                    let (param_ty, _) =
+                        Ty::from_hir_type_ref(db, resolver, param, type_ref_id, diagnostics)?;
+                    param_tys.push(param_ty);
+                }
+                let (ret_ty, _) =
+                    Ty::from_hir_type_ref(db, resolver, ret_type, type_ref_id, diagnostics)?;
+                let sig = FnSig::from_params_and_return(param_tys, ret_ty);
+                Some((TyKind::FnPtr(sig).intern(), false))
+            }
+            TypeRef::Error => Some((TyKind::Unknown.intern(), false)),
+            TypeRef::Empty => Some((Ty::unit(), false)),
+            TypeRef::Never => Some((TyKind::Never.intern(), false)),
+        }
+    }
+
     fn from_hir_path(
         db: &dyn HirDatabase,
         resolver: &Resolver,
         type_ref: LocalTypeRefId,
         path: &Path,
+        args: &[TypeRef],
         diagnostics: &mut Vec<LowerDiagnostic>,
     ) -> Option<(Self, bool)> {
         // Find the type
         let (ty, vis) = resolver.resolve_path_as_type_fully(db.upcast(), path)?;
 
+        // A type parameter refers to the type it is substituted with
+        if let TypeNs::GenericParam(idx) = ty {
+            let name = path.segments.last()?.clone();
+            if !args.is_empty() {
+                diagnostics.push(LowerDiagnostic::TypeArgCountMismatch {
+                    id: type_ref,
+                    expected: 0,
+                    found: args.len(),
+                });
+            }
+            return Some((TyKind::Param { idx, name }.intern(), false));
+        }
+
         // Get the definition and visibility
         let def = match ty {
             TypeNs::StructId(id) => TypableDef::Struct(id.into()),
+            TypeNs::EnumId(id) => TypableDef::Enum(id.into()),
             TypeNs::TypeAliasId(id) => TypableDef::TypeAlias(id.into()),
             TypeNs::PrimitiveType(id) => TypableDef::PrimitiveType(id),
+            // Enum variants and traits are not types on their own
+            TypeNs::EnumVariantId(_) | TypeNs::TraitId(_) => return None,
+            // The `Self` type is always visible from within its `impl` block
+            TypeNs::SelfType(id) => return Some((Impl::from(id).self_ty(db), false)),
+            TypeNs::GenericParam(_) => unreachable!("type parameters are resolved above"),
         };
 
         // Get the current module and see if the type is visible from here
@@ -127,7 +186,35 @@ impl Ty {
             }
         }
 
-        Some(db.type_for_def(def, Namespace::Types))
+        let (ty, is_cyclic) = db.type_for_def(def, Namespace::Types);
+
+        // Substitute the type arguments for the type parameters of a generic struct
+        let expected = match def {
+            TypableDef::Struct(s) => s.data(db.upcast()).generic_params.len(),
+            _ => 0,
+        };
+        if args.len() != expected {
+            diagnostics.push(LowerDiagnostic::TypeArgCountMismatch {
+                id: type_ref,
+                expected,
+                found: args.len(),
+            });
+        }
+        if expected == 0 {
+            return Some((ty, is_cyclic));
+        }
+        let mut substs = Vec::with_capacity(expected);
+        for arg in args.iter().take(expected) {
+            let (arg_ty, arg_is_cyclic) =
+                Ty::from_hir_type_ref(db, resolver, arg, type_ref, diagnostics)?;
+            if arg_is_cyclic {
+                diagnostics.push(LowerDiagnostic::CyclicType { id: type_ref })
+            }
+            substs.push(arg_ty);
+        }
+        substs.resize(expected, TyKind::Unknown.intern());
+        let substs: Substitution = substs.into_iter().collect();
+        Some((ty.subst(&substs), is_cyclic))
     }
 }
 
@@ -153,17 +240,33 @@ pub fn lower_struct_query(db: &dyn HirDatabase, s: Struct) -> Arc<LowerBatchResu
     types_from_hir(db, &s.id.resolver(db.upcast()), data.type_ref_map())
 }
 
+pub fn lower_enum_query(db: &dyn HirDatabase, e: Enum) -> Arc<LowerBatchResult> {
+    let data = e.data(db.upcast());
+    types_from_hir(db, &e.id.resolver(db.upcast()), data.type_ref_map())
+}
+
 pub fn lower_type_alias_query(db: &dyn HirDatabase, t: TypeAlias) -> Arc<LowerBatchResult> {
     let data = t.data(db.upcast());
     types_from_hir(db, &t.id.resolver(db.upcast()), data.type_ref_map())
 }
 
+pub fn lower_impl_query(db: &dyn HirDatabase, i: Impl) -> Arc<LowerBatchResult> {
+    let data = i.data(db.upcast());
+    // The `Self` type is not available in the header of the `impl` block itself
+    let resolver = i.id.lookup(db.upcast()).module.resolver(db.upcast());
+    types_from_hir(db, &resolver, data.type_ref_map())
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TypableDef {
     Function(Function),
     PrimitiveType(PrimitiveType),
     Struct(Struct),
+    Enum(Enum),
+    EnumVariant(EnumVariant),
     TypeAlias(TypeAlias),
+    Const(Const),
+    Static(Static),
 }
 
 impl From<Function> for TypableDef {
@@ -184,14 +287,41 @@ impl From<Struct> for TypableDef {
     }
 }
 
+impl From<Enum> for TypableDef {
+    fn from(f: Enum) -> Self {
+        TypableDef::Enum(f)
+    }
+}
+
+impl From<EnumVariant> for TypableDef {
+    fn from(f: EnumVariant) -> Self {
+        TypableDef::EnumVariant(f)
+    }
+}
+
+impl From<Const> for TypableDef {
+    fn from(f: Const) -> Self {
+        TypableDef::Const(f)
+    }
+}
+
+impl From<Static> for TypableDef {
+    fn from(f: Static) -> Self {
+        TypableDef::Static(f)
+    }
+}
+
 impl From<ModuleDef> for Option<TypableDef> {
     fn from(d: ModuleDef) -> Self {
         match d {
             ModuleDef::Function(f) => Some(TypableDef::Function(f)),
             ModuleDef::PrimitiveType(t) => Some(TypableDef::PrimitiveType(t)),
             ModuleDef::Struct(t) => Some(TypableDef::Struct(t)),
+            ModuleDef::Enum(t) => Some(TypableDef::Enum(t)),
             ModuleDef::TypeAlias(t) => Some(TypableDef::TypeAlias(t)),
-            ModuleDef::Module(_) => None,
+            ModuleDef::Const(c) => Some(TypableDef::Const(c)),
+            ModuleDef::Static(s) => Some(TypableDef::Static(s)),
+            ModuleDef::Module(_) | ModuleDef::Trait(_) => None,
         }
     }
 }
@@ -200,8 +330,10 @@ impl From<ModuleDef> for Option<TypableDef> {
 pub enum CallableDef {
     Function(Function),
     Struct(Struct),
+    EnumVariant(EnumVariant),
+    Intrinsic(Intrinsic),
 }
-impl_froms!(CallableDef: Function, Struct);
+impl_froms!(CallableDef: Function, Struct, EnumVariant, Intrinsic);
 
 impl CallableDef {
     pub fn is_function(self) -> bool {
@@ -211,6 +343,14 @@ impl CallableDef {
     pub fn is_struct(self) -> bool {
         matches!(self, CallableDef::Struct(_))
     }
+
+    pub fn is_enum_variant(self) -> bool {
+        matches!(self, CallableDef::EnumVariant(_))
+    }
+
+    pub fn is_intrinsic(self) -> bool {
+        matches!(self, CallableDef::Intrinsic(_))
+    }
 }
 
 impl HasVisibility for CallableDef {
@@ -218,6 +358,8 @@ impl HasVisibility for CallableDef {
         match self {
             CallableDef::Struct(strukt) => strukt.visibility(db),
             CallableDef::Function(function) => function.visibility(db),
+            CallableDef::EnumVariant(variant) => variant.parent_enum().visibility(db),
+            CallableDef::Intrinsic(_) => Visibility::Public,
         }
     }
 }
@@ -232,12 +374,20 @@ pub(crate) fn type_for_def(db: &dyn HirDatabase, def: TypableDef, ns: Namespace)
         (TypableDef::PrimitiveType(t), Namespace::Types) => type_for_primitive(t),
         (TypableDef::Struct(s), Namespace::Values) => type_for_struct_constructor(db, s),
         (TypableDef::Struct(s), Namespace::Types) => type_for_struct(db, s),
+        (TypableDef::Enum(e), Namespace::Types) => type_for_enum(db, e),
+        (TypableDef::EnumVariant(v), Namespace::Values) => type_for_enum_variant_constructor(db, v),
         (TypableDef::TypeAlias(t), Namespace::Types) => type_for_type_alias(db, t),
+        (TypableDef::Const(c), Namespace::Values) => c.ty(db),
+        (TypableDef::Static(s), Namespace::Values) => s.ty(db),
 
         // 'error' cases:
         (TypableDef::Function(_), Namespace::Types) => TyKind::Unknown.intern(),
         (TypableDef::PrimitiveType(_), Namespace::Values) => TyKind::Unknown.intern(),
+        (TypableDef::Enum(_), Namespace::Values) => TyKind::Unknown.intern(),
+        (TypableDef::EnumVariant(_), Namespace::Types) => TyKind::Unknown.intern(),
         (TypableDef::TypeAlias(_), Namespace::Values) => TyKind::Unknown.intern(),
+        (TypableDef::Const(_), Namespace::Types) => TyKind::Unknown.intern(),
+        (TypableDef::Static(_), Namespace::Types) => TyKind::Unknown.intern(),
     };
     (ty, false)
 }
@@ -258,20 +408,24 @@ fn type_for_primitive(def: PrimitiveType) -> Ty {
         PrimitiveType::Float(f) => TyKind::Float(f.into()),
         PrimitiveType::Int(i) => TyKind::Int(i.into()),
         PrimitiveType::Bool => TyKind::Bool,
+        PrimitiveType::String => TyKind::String,
     }
     .intern()
 }
 
 /// Build the declared type of a function. This should not need to look at the
 /// function body.
-fn type_for_fn(_db: &dyn HirDatabase, def: Function) -> Ty {
-    TyKind::FnDef(def.into(), Substitution::empty()).intern()
+fn type_for_fn(db: &dyn HirDatabase, def: Function) -> Ty {
+    let generic_params = def.data(db.upcast()).generic_params();
+    TyKind::FnDef(def.into(), generic_params.bound_vars()).intern()
 }
 
 pub(crate) fn callable_item_sig(db: &dyn HirDatabase, def: CallableDef) -> FnSig {
     match def {
         CallableDef::Function(f) => fn_sig_for_fn(db, f),
         CallableDef::Struct(s) => fn_sig_for_struct_constructor(db, s),
+        CallableDef::EnumVariant(v) => fn_sig_for_enum_variant_constructor(db, v),
+        CallableDef::Intrinsic(i) => fn_sig_for_intrinsic(i),
     }
 }
 
@@ -287,6 +441,17 @@ pub(crate) fn fn_sig_for_fn(db: &dyn HirDatabase, def: Function) -> FnSig {
     FnSig::from_params_and_return(params, ret)
 }
 
+/// Returns the signature of an intrinsic. Intrinsics are not (yet) generic, parameters that accept
+/// any type use `TyKind::Unknown`.
+pub(crate) fn fn_sig_for_intrinsic(def: Intrinsic) -> FnSig {
+    match def {
+        Intrinsic::Len => FnSig::from_params_and_return(
+            vec![TyKind::Array(TyKind::Unknown.intern()).intern()],
+            TyKind::Int(IntTy::usize()).intern(),
+        ),
+    }
+}
+
 pub(crate) fn fn_sig_for_struct_constructor(db: &dyn HirDatabase, def: Struct) -> FnSig {
     let data = def.data(db.upcast());
     let resolver = def.id.resolver(db.upcast());
@@ -303,14 +468,41 @@ pub(crate) fn fn_sig_for_struct_constructor(db: &dyn HirDatabase, def: Struct) -
 fn type_for_struct_constructor(db: &dyn HirDatabase, def: Struct) -> Ty {
     let struct_data = db.struct_data(def.id);
     if struct_data.kind == StructKind::Tuple {
-        TyKind::FnDef(def.into(), Substitution::empty()).intern()
+        TyKind::FnDef(def.into(), struct_data.generic_params.bound_vars()).intern()
     } else {
         type_for_struct(db, def)
     }
 }
 
-fn type_for_struct(_db: &dyn HirDatabase, def: Struct) -> Ty {
-    TyKind::Struct(def).intern()
+/// Build the type of a struct. The type of a generic struct refers to its own type parameters,
+/// e.g. `Pair<A, B>`.
+fn type_for_struct(db: &dyn HirDatabase, def: Struct) -> Ty {
+    let generic_params = db.struct_data(def.id).generic_params();
+    TyKind::Struct(def, generic_params.bound_vars()).intern()
+}
+
+pub(crate) fn fn_sig_for_enum_variant_constructor(db: &dyn HirDatabase, def: EnumVariant) -> FnSig {
+    let params = def
+        .fields(db)
+        .into_iter()
+        .map(|field| field.ty(db))
+        .collect::<Vec<_>>();
+    let ret = type_for_enum(db, def.parent_enum());
+    FnSig::from_params_and_return(params, ret)
+}
+
+/// Build the type of an enum variant constructor. Tuple variants are constructed by calling them,
+/// unit variants are values of the enum type.
+fn type_for_enum_variant_constructor(db: &dyn HirDatabase, def: EnumVariant) -> Ty {
+    if def.kind(db) == StructKind::Tuple {
+        TyKind::FnDef(def.into(), Substitution::empty()).intern()
+    } else {
+        type_for_enum(db, def.parent_enum())
+    }
+}
+
+fn type_for_enum(_db: &dyn HirDatabase, def: Enum) -> Ty {
+    TyKind::Enum(def).intern()
 }
 
 fn type_for_type_alias(db: &dyn HirDatabase, def: TypeAlias) -> Ty {
@@ -321,7 +513,7 @@ fn type_for_type_alias(db: &dyn HirDatabase, def: TypeAlias) -> Ty {
 }
 
 pub mod diagnostics {
-    use crate::diagnostics::{CyclicType, PrivateAccess, UnresolvedType};
+    use crate::diagnostics::{CyclicType, PrivateAccess, TypeArgCountMismatch, UnresolvedType};
     use crate::{
         diagnostics::DiagnosticSink,
         type_ref::{LocalTypeRefId, TypeRefSourceMap},
@@ -330,9 +522,20 @@ pub mod diagnostics {
 
     #[derive(Debug, PartialEq, Eq, Clone)]
     pub(crate) enum LowerDiagnostic {
-        UnresolvedType { id: LocalTypeRefId },
-        TypeIsPrivate { id: LocalTypeRefId },
-        CyclicType { id: LocalTypeRefId },
+        UnresolvedType {
+            id: LocalTypeRefId,
+        },
+        TypeIsPrivate {
+            id: LocalTypeRefId,
+        },
+        CyclicType {
+            id: LocalTypeRefId,
+        },
+        TypeArgCountMismatch {
+            id: LocalTypeRefId,
+            expected: usize,
+            found: usize,
+        },
     }
 
     impl LowerDiagnostic {
@@ -356,6 +559,16 @@ pub mod diagnostics {
                     file: file_id,
                     expr: source_map.type_ref_syntax(*id).unwrap().syntax_node_ptr(),
                 }),
+                LowerDiagnostic::TypeArgCountMismatch {
+                    id,
+                    expected,
+                    found,
+                } => sink.push(TypeArgCountMismatch {
+                    file: file_id,
+                    type_ref: source_map.type_ref_syntax(*id).unwrap(),
+                    expected: *expected,
+                    found: *found,
+                }),
             }
         }
     }
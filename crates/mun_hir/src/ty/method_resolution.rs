@@ -0,0 +1,366 @@
+//! This module implements the lookup of associated functions in the `impl` blocks of a type,
+//! including the `impl` blocks that implement a trait for the type.
+
+use crate::{
+    code_model::src::HasSource,
+    diagnostics::{
+        ConflictingTraitImpls, DuplicateDefinition, InvalidSelfTyImpl, InvalidSelfTyTraitImpl,
+    },
+    ids::{AssocItemId, FunctionId, ImplId, Lookup, TraitId},
+    ty::Substitution,
+    DiagnosticSink, Function, HirDatabase, HirDisplay, Impl, ModuleId, Name, PackageId, Trait, Ty,
+    TyKind,
+};
+use mun_syntax::{AstNode, SyntaxNodePtr};
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+
+/// Holds all the inherent `impl` blocks of a package, grouped by the type for which they define
+/// items.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InherentImpls {
+    map: FxHashMap<Ty, Vec<ImplId>>,
+    diagnostics: Vec<InherentImplsDiagnostic>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum InherentImplsDiagnostic {
+    /// An `impl` block was defined for a type that is not a struct or an enum
+    InvalidSelfTy(ImplId),
+
+    /// Two `impl` blocks for the same type define a function with the same name
+    DuplicateDefinition {
+        name: Name,
+        first: FunctionId,
+        second: FunctionId,
+    },
+}
+
+impl InherentImpls {
+    pub(crate) fn inherent_impls_in_package_query(
+        db: &dyn HirDatabase,
+        package: PackageId,
+    ) -> Arc<Self> {
+        let mut impls = InherentImpls {
+            map: FxHashMap::default(),
+            diagnostics: Vec::new(),
+        };
+
+        let package_defs = db.package_defs(package);
+        for (_, scope) in package_defs.modules.iter() {
+            for impl_id in scope.impls() {
+                // `impl` blocks of traits are collected by `TraitImpls`
+                if db.impl_data(impl_id).target_trait.is_none() {
+                    impls.collect_impl(db, impl_id);
+                }
+            }
+        }
+
+        // Find the associated functions that are defined in more than one `impl` block of the
+        // same type. Duplicates within a single `impl` block are reported by the item tree.
+        for impl_ids in impls.map.values() {
+            let mut names = FxHashMap::<Name, (ImplId, FunctionId)>::default();
+            for &impl_id in impl_ids {
+                for item in db.impl_data(impl_id).items.iter() {
+                    let AssocItemId::FunctionId(func) = *item;
+                    let name = db.fn_data(func).name().clone();
+                    match names.get(&name) {
+                        Some(&(first_impl, first)) if first_impl != impl_id => impls
+                            .diagnostics
+                            .push(InherentImplsDiagnostic::DuplicateDefinition {
+                                name,
+                                first,
+                                second: func,
+                            }),
+                        Some(_) => {}
+                        None => {
+                            names.insert(name, (impl_id, func));
+                        }
+                    }
+                }
+            }
+        }
+
+        Arc::new(impls)
+    }
+
+    /// Adds the specified `impl` block to the map
+    fn collect_impl(&mut self, db: &dyn HirDatabase, impl_id: ImplId) {
+        let self_ty = Impl::from(impl_id).self_ty(db);
+        match self_ty.interned() {
+            TyKind::Struct(..) | TyKind::Enum(_) => {
+                self.map.entry(self_ty).or_default().push(impl_id)
+            }
+            // An error has already been emitted for the type of the `impl` block
+            TyKind::Unknown => {}
+            _ => self
+                .diagnostics
+                .push(InherentImplsDiagnostic::InvalidSelfTy(impl_id)),
+        }
+    }
+
+    /// Returns all the `impl` blocks that define items for the specified type.
+    pub fn for_self_ty(&self, self_ty: &Ty) -> &[ImplId] {
+        self.map.get(self_ty).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Adds all the diagnostics of `impl` blocks in the specified `module` to the `sink`.
+    pub(crate) fn add_module_diagnostics(
+        &self,
+        db: &dyn HirDatabase,
+        module: ModuleId,
+        sink: &mut DiagnosticSink,
+    ) {
+        for diagnostic in self.diagnostics.iter() {
+            match diagnostic {
+                InherentImplsDiagnostic::InvalidSelfTy(impl_id) => {
+                    if impl_id.lookup(db.upcast()).module != module {
+                        continue;
+                    }
+                    let src = Impl::from(*impl_id).source(db.upcast());
+                    let type_ref = src
+                        .value
+                        .type_ref()
+                        .expect("an `impl` block with a known type must have a type ref");
+                    sink.push(InvalidSelfTyImpl {
+                        file: src.file_id,
+                        type_ref: SyntaxNodePtr::new(type_ref.syntax()),
+                    })
+                }
+                InherentImplsDiagnostic::DuplicateDefinition {
+                    name,
+                    first,
+                    second,
+                } => {
+                    let second = Function::from(*second);
+                    if second.module(db).id != module {
+                        continue;
+                    }
+                    let first = Function::from(*first).source(db.upcast());
+                    let second = second.source(db.upcast());
+                    sink.push(DuplicateDefinition {
+                        file: second.file_id,
+                        name: name.to_string(),
+                        first_definition: SyntaxNodePtr::new(first.value.syntax()),
+                        definition: SyntaxNodePtr::new(second.value.syntax()),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Holds all the `impl` blocks of a package that implement a trait, grouped by the type for which
+/// they implement the trait.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TraitImpls {
+    map: FxHashMap<Ty, Vec<(TraitId, ImplId)>>,
+    diagnostics: Vec<TraitImplsDiagnostic>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum TraitImplsDiagnostic {
+    /// A trait was implemented for a type that is not a struct or an enum
+    InvalidSelfTy(ImplId),
+
+    /// A trait was implemented more than once for the same type
+    ConflictingImpls { first: ImplId, second: ImplId },
+}
+
+impl TraitImpls {
+    pub(crate) fn trait_impls_in_package_query(
+        db: &dyn HirDatabase,
+        package: PackageId,
+    ) -> Arc<Self> {
+        let mut impls = TraitImpls {
+            map: FxHashMap::default(),
+            diagnostics: Vec::new(),
+        };
+
+        let package_defs = db.package_defs(package);
+        for (_, scope) in package_defs.modules.iter() {
+            for impl_id in scope.impls() {
+                impls.collect_impl(db, impl_id);
+            }
+        }
+
+        Arc::new(impls)
+    }
+
+    /// Adds the specified `impl` block to the map if it implements a trait
+    fn collect_impl(&mut self, db: &dyn HirDatabase, impl_id: ImplId) {
+        // An error is emitted by the `impl` block itself if the trait cannot be resolved
+        let target_trait = match Impl::from(impl_id).target_trait(db) {
+            Some(target_trait) => target_trait.id,
+            None => return,
+        };
+
+        let self_ty = Impl::from(impl_id).self_ty(db);
+        match self_ty.interned() {
+            TyKind::Struct(..) | TyKind::Enum(_) => {
+                let impls = self.map.entry(self_ty).or_default();
+                match impls.iter().find(|(trait_id, _)| *trait_id == target_trait) {
+                    Some(&(_, first)) => {
+                        self.diagnostics
+                            .push(TraitImplsDiagnostic::ConflictingImpls {
+                                first,
+                                second: impl_id,
+                            })
+                    }
+                    None => impls.push((target_trait, impl_id)),
+                }
+            }
+            // An error has already been emitted for the type of the `impl` block
+            TyKind::Unknown => {}
+            _ => self
+                .diagnostics
+                .push(TraitImplsDiagnostic::InvalidSelfTy(impl_id)),
+        }
+    }
+
+    /// Returns the `impl` block that implements the specified trait for `self_ty`, if any.
+    pub fn for_trait_and_ty(&self, trait_id: TraitId, self_ty: &Ty) -> Option<ImplId> {
+        self.for_self_ty(self_ty)
+            .find(|(id, _)| *id == trait_id)
+            .map(|(_, impl_id)| impl_id)
+    }
+
+    /// Returns all the traits that are implemented for `self_ty` together with the `impl` blocks
+    /// that implement them, in declaration order.
+    pub fn for_self_ty(&self, self_ty: &Ty) -> impl Iterator<Item = (TraitId, ImplId)> + '_ {
+        self.map
+            .get(self_ty)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+            .iter()
+            .copied()
+    }
+
+    /// Adds all the diagnostics of trait `impl` blocks in the specified `module` to the `sink`.
+    pub(crate) fn add_module_diagnostics(
+        &self,
+        db: &dyn HirDatabase,
+        module: ModuleId,
+        sink: &mut DiagnosticSink,
+    ) {
+        for diagnostic in self.diagnostics.iter() {
+            match diagnostic {
+                TraitImplsDiagnostic::InvalidSelfTy(impl_id) => {
+                    if impl_id.lookup(db.upcast()).module != module {
+                        continue;
+                    }
+                    let src = Impl::from(*impl_id).source(db.upcast());
+                    let type_ref = src
+                        .value
+                        .type_ref()
+                        .expect("an `impl` block with a known type must have a type ref");
+                    sink.push(InvalidSelfTyTraitImpl {
+                        file: src.file_id,
+                        type_ref: SyntaxNodePtr::new(type_ref.syntax()),
+                    })
+                }
+                TraitImplsDiagnostic::ConflictingImpls { first, second } => {
+                    if second.lookup(db.upcast()).module != module {
+                        continue;
+                    }
+                    let second = Impl::from(*second);
+                    let trait_name = second
+                        .target_trait(db)
+                        .expect("a conflicting `impl` block must implement a trait")
+                        .name(db);
+                    let self_ty = second.self_ty(db).display(db).to_string();
+                    let first = Impl::from(*first).source(db.upcast());
+                    let second = second.source(db.upcast());
+                    sink.push(ConflictingTraitImpls {
+                        file: second.file_id,
+                        first_definition: SyntaxNodePtr::new(first.value.syntax()),
+                        definition: SyntaxNodePtr::new(second.value.syntax()),
+                        trait_name,
+                        self_ty,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Returns the package in which the struct or enum `ty` is defined.
+fn package_of_ty(db: &dyn HirDatabase, ty: &Ty) -> Option<PackageId> {
+    let module = match ty.interned() {
+        TyKind::Struct(s, _) => s.module(db),
+        TyKind::Enum(e) => e.module(db),
+        _ => return None,
+    };
+    Some(module.id.package)
+}
+
+/// Looks up the function with the specified `name` that is defined in one of the `impl` blocks of
+/// the type `ty`. Only structs and enums can have associated functions. Functions of inherent
+/// `impl` blocks take precedence over functions of traits that are implemented for the type. If
+/// the function is not defined by the `impl` block of a trait, its default implementation in the
+/// trait is returned.
+pub(crate) fn lookup_associated_function(
+    db: &dyn HirDatabase,
+    ty: &Ty,
+    name: &Name,
+) -> Option<Function> {
+    let package = package_of_ty(db, ty)?;
+
+    let impls = db.inherent_impls_in_package(package);
+    let inherent = impls
+        .for_self_ty(ty)
+        .iter()
+        .flat_map(|impl_id| db.impl_data(*impl_id).items.clone())
+        .map(|item| match item {
+            AssocItemId::FunctionId(id) => Function::from(id),
+        })
+        .find(|func| func.name(db) == *name);
+    if inherent.is_some() {
+        return inherent;
+    }
+
+    db.trait_impls_in_package(package)
+        .for_self_ty(ty)
+        .find_map(|(trait_id, impl_id)| {
+            Impl::from(impl_id)
+                .function(db, name)
+                .or_else(|| Trait::from(trait_id).function(db, name))
+        })
+}
+
+/// Returns the `impl` block that implements `target_trait` for the type `ty`, if any. The `impl`
+/// block is defined either in the package of the trait or in the package of the type.
+pub(crate) fn find_trait_impl(db: &dyn HirDatabase, target_trait: Trait, ty: &Ty) -> Option<Impl> {
+    let trait_package = target_trait.module(db).id.package;
+    std::iter::once(trait_package)
+        .chain(package_of_ty(db, ty).filter(|package| *package != trait_package))
+        .find_map(|package| {
+            db.trait_impls_in_package(package)
+                .for_trait_and_ty(target_trait.id, ty)
+        })
+        .map(Impl::from)
+}
+
+/// Resolves a call of `func` with the type arguments `substs` to the function that is actually
+/// called. If `func` is declared in a trait and the `Self` type (the first type argument) is known,
+/// this is the function of the `impl` block that implements the trait for the `Self` type. The
+/// remaining type arguments are the type arguments of that function. If the `impl` block does not
+/// define the function, the default implementation of the trait is called.
+pub(crate) fn resolve_trait_method(
+    db: &dyn HirDatabase,
+    func: Function,
+    substs: Substitution,
+) -> (Function, Substitution) {
+    let target_trait = match func.parent_trait(db) {
+        Some(target_trait) => target_trait,
+        None => return (func, substs),
+    };
+    let impl_func = substs
+        .first()
+        .and_then(|self_ty| find_trait_impl(db, target_trait, self_ty))
+        .and_then(|impl_def| impl_def.function(db, &func.name(db)));
+    match impl_func {
+        Some(impl_func) => (impl_func, substs.iter().skip(1).cloned().collect()),
+        None => (func, substs),
+    }
+}
@@ -20,7 +20,10 @@ pub(super) fn binary_op_rhs_expectation(op: BinaryOp, lhs_ty: Ty) -> Ty {
             TyKind::Int(_)
             | TyKind::Float(_)
             | TyKind::Bool
-            | TyKind::Struct(_)
+            | TyKind::Struct(..)
+            | TyKind::Enum(_)
+            | TyKind::FnPtr(_)
+            | TyKind::Param { .. }
             | TyKind::InferenceVar(InferTy::FloatVar(_))
             | TyKind::InferenceVar(InferTy::IntVar(_)) => lhs_ty,
             _ => TyKind::Unknown.intern(),
@@ -1,6 +1,6 @@
 use crate::{
     diagnostics::DiagnosticSink, expr::BodySourceMap, mock::MockDatabase,
-    with_fixture::WithFixture, HirDisplay, InferenceResult, ModuleDef, Package,
+    with_fixture::WithFixture, AssocItem, HirDisplay, InferenceResult, ModuleDef, Package,
 };
 use std::{fmt::Write, sync::Arc};
 
@@ -206,6 +206,45 @@ fn infer_literals() {
     )
 }
 
+#[test]
+fn infer_string() {
+    infer_snapshot(
+        r#"
+    fn foo(a: string) -> string {
+        a
+    }
+
+    fn main() {
+        let a = "Hello, world!";
+        let b: string = foo(a);
+        let c = 'single quoted';
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_array() {
+    infer_snapshot(
+        r#"
+    fn foo(a: [i32]) -> i32 {
+        a[0]
+    }
+
+    fn main() {
+        let a = [1, 2, 3];
+        let b: [f64] = [1.0, 2.0];
+        let c = foo(a) + a[1];
+        a[2] = 5;
+        let d = len(b);
+        let e = [[true], []];
+        let f = c[0];   // error: cannot index
+        let g = [];     // error: type annotations needed
+    }
+    "#,
+    )
+}
+
 #[test]
 fn infer_suffix_literals() {
     infer_snapshot(
@@ -422,6 +461,20 @@ fn infer_break() {
     )
 }
 
+#[test]
+fn infer_continue() {
+    infer_snapshot(
+        r#"
+    fn foo() {
+        continue; // error: not in a loop
+        loop { continue; }
+        while true { continue; }
+        for i in 0..3 { continue; }
+    }
+    "#,
+    )
+}
+
 #[test]
 fn infer_while() {
     infer_snapshot(
@@ -437,6 +490,28 @@ fn infer_while() {
     )
 }
 
+#[test]
+fn infer_for() {
+    infer_snapshot(
+        r#"
+    fn foo(n: i64) -> i64 {
+        let sum = 0;
+        for i in 0..n { sum += i; };
+        for i in 1..=10 { break; };
+        for _ in 0u8..3 {};
+        sum
+    }
+
+    fn bar() {
+        for i in 0.0..1.0 {};       // error: mismatched type
+        for i in true {};           // error: not a range
+        let a = 0..10;              // error: range outside of a for loop
+        for i in 0..3 { break 3; }; // error: break with value can only appear in a loop
+    }
+    "#,
+    )
+}
+
 #[test]
 fn invalid_binary_ops() {
     infer_snapshot(
@@ -499,6 +574,118 @@ fn struct_lit() {
     )
 }
 
+#[test]
+fn enum_lit_and_match() {
+    infer_snapshot(
+        r#"
+    enum State {
+        Idle,
+        Walking(f32),
+        Attacking { target: i32 },
+    }
+
+    fn update(state: State) -> i32 {
+        match state {
+            State::Idle => 0,
+            State::Walking(speed) => 1,
+            State::Attacking { target } => target,
+        }
+    }
+
+    fn main() {
+        let a = State::Idle;
+        let b = State::Walking(1.0);
+        let c = State::Attacking { target: 5 };
+    }
+    "#,
+    )
+}
+
+#[test]
+fn enum_errors() {
+    infer_snapshot(
+        r#"
+    enum State {
+        Idle,
+        Walking(f32),
+        Attacking { target: i32 },
+    }
+
+    fn main(state: State) {
+        let a = State::Idle(1); // error: mismatched struct literal kind. expected `unit struct`, found `tuple`
+        let b = State::Walking(1.0, 2.0); // error: this tuple struct literal has 1 field but 2 fields were supplied
+        let c = State::Attacking {}; // error: missing record fields: target
+        let d = State::Jumping; // error: undefined value
+        match state {
+            State::Walking(x, y) => {}, // error: this pattern has 2 fields, but the corresponding variant has 1 field
+            State::Idle { .. } => {}, // error: mismatched pattern kind. expected `unit struct`, found `record`
+            State::Attacking { foo, .. } => {}, // error: no such field
+        }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn impl_methods() {
+    infer_snapshot(
+        r#"
+    struct Foo {
+        a: i32,
+    }
+
+    impl Foo {
+        fn new(a: i32) -> Self {
+            Self { a: a }
+        }
+
+        fn get(self) -> i32 {
+            self.a
+        }
+
+        fn add(self, other: Foo) -> Foo {
+            Self::new(self.get() + other.get())
+        }
+    }
+
+    fn main() {
+        let foo = Foo::new(3);
+        let bar = foo.add(Foo::new(4)).get();
+    }
+    "#,
+    )
+}
+
+#[test]
+fn impl_errors() {
+    infer_snapshot(
+        r#"
+    struct Foo;
+
+    impl Foo {
+        fn new() -> Self { Foo }
+        fn get(self) -> i32 { 0 }
+    }
+
+    impl Foo {
+        fn new() -> Self { Foo } // error: the name `new` is defined multiple times
+    }
+
+    impl i32 {} // error: inherent `impl` blocks can only be defined for structs and enums
+
+    fn bar(self) {} // error: `self` parameter is only allowed in associated functions
+
+    fn main() {
+        let foo = Foo::new();
+        foo.baz(); // error: no method named `baz` found
+        foo.new(); // error: no method named `new` found
+        foo.get(1); // error: this function takes 0 parameters but 1 parameters was supplied
+        Foo::baz(); // error: undefined value
+    }
+    "#,
+    )
+}
+
 #[test]
 fn struct_field_index() {
     infer_snapshot(
@@ -572,6 +759,169 @@ fn infer_type_alias() {
     )
 }
 
+#[test]
+fn infer_generics() {
+    infer_snapshot(
+        r#"
+    struct Pair<A, B> { a: A, b: B }
+    struct Wrapper<T>(T);
+
+    fn pick<T>(cond: bool, a: T, b: T) -> T {
+        if cond { a } else { b }
+    }
+
+    fn swap<A, B>(p: Pair<A, B>) -> Pair<B, A> {
+        Pair { a: p.b, b: p.a }
+    }
+
+    fn main() {
+        let a = pick(true, 1, 2);
+        let b = pick(false, 1.5, 2.5);
+        let p = swap(Pair { a: 1, b: true });
+        let w: Wrapper<Pair<bool, i32>> = Wrapper(p);
+        let c: Pair<i32> = Pair { a: 5, b: false }; // error: this type takes 2 type arguments but 1 type arguments were supplied
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_lambda() {
+    infer_snapshot(
+        r#"
+    struct Button { on_click: fn(i32) -> i32 }
+
+    fn add(a: i32, b: i32) -> i32 { a + b }
+
+    fn apply(f: fn(i32) -> i32, value: i32) -> i32 {
+        f(value)
+    }
+
+    fn main() {
+        let offset = 3;
+        let a = |x: i32| x + offset;
+        let b = apply(a, 2);
+        let c = apply(|x| -> i32 { x * 2 }, b);
+        let d: fn(i32, i32) -> i32 = add;
+        let e = Button { on_click: |x| x };
+        e.on_click = a;
+        let f = (e.on_click)(1);
+        let g: fn() = add; // error: mismatched type
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_consts_and_statics() {
+    infer_snapshot(
+        r#"
+    const MAX: i32 = 10 * 10;
+    static COUNTER: i64 = 5;
+    const HALF: f64 = 1.0 / 2.0;
+    const ENABLED: bool = MAX > 50 && !false;
+
+    fn main() {
+        let a = MAX + 1;
+        COUNTER = COUNTER + 1;
+        let b = HALF;
+        MAX = 3; // error: invalid left hand side of expression
+    }
+    "#,
+    )
+}
+
+#[test]
+fn const_eval_errors() {
+    infer_snapshot(
+        r#"
+    fn foo() -> i32 { 5 }
+    struct Foo;
+
+    const A: i32 = foo(); // error: expected a constant expression
+    const B: u8 = 255 + 1; // error: evaluation of constant value failed: attempt to compute a value that overflows its type
+    const C: i32 = 1 / 0; // error: evaluation of constant value failed: attempt to divide by zero
+    const D: i32 = D + 1; // error: evaluation of constant value failed: cycle detected in the value of a constant
+    static E: i32 = A + 1;
+    static F: Foo = Foo; // error: `const` and `static` items can only have primitive types
+    static G: u128 = 0; // error: `static` items cannot have 128-bit integer types
+    const H: u128 = 0;
+    "#,
+    )
+}
+
+#[test]
+fn infer_traits() {
+    infer_snapshot(
+        r#"
+    trait Update {
+        fn update(self, dt: f32);
+
+        fn update_twice(self, dt: f32) {
+            self.update(dt);
+            self.update(dt)
+        }
+    }
+
+    struct Player { pos: f32 }
+
+    impl Update for Player {
+        fn update(self, dt: f32) {
+            let pos = self.pos + dt;
+        }
+    }
+
+    fn tick<T: Update>(entity: T, dt: f32) {
+        entity.update(dt)
+    }
+
+    fn main() {
+        let player = Player { pos: 0.0 };
+        tick(player, 0.5);
+        player.update(0.5);
+        player.update_twice(0.5);
+    }
+    "#,
+    )
+}
+
+#[test]
+fn trait_errors() {
+    infer_snapshot(
+        r#"
+    trait Update {
+        fn update(self, dt: f32);
+        fn draw(self);
+    }
+
+    struct Foo;
+    struct Bar;
+
+    impl Update for Foo { // error: not all items of trait `Update` are implemented, missing: `draw`
+        fn update(self, dt: f32) {}
+        fn render(self) {} // error: method `render` is not a member of trait `Update`
+    }
+
+    impl Update for Bar {
+        fn update(self) {} // error: method `update` has an incompatible type for trait. expected `fn(Bar, f32)`, found `fn(Bar)`
+        fn draw(self) {}
+    }
+
+    impl Update for Bar { fn update(self, dt: f32) {} fn draw(self) {} } // error: conflicting implementations of trait `Update` for type `Bar`
+
+    impl Unknown for Foo {} // error: undefined trait
+
+    fn tick<T: Update>(entity: T) {}
+
+    fn apply<T: Missing>(entity: T) {} // error: undefined trait
+
+    fn main() {
+        tick(5); // error: the trait `Update` is not implemented for `i32`
+    }
+    "#,
+    )
+}
+
 #[test]
 fn recursive_alias() {
     infer_snapshot(
@@ -669,10 +1019,46 @@ fn infer(content: &str) -> String {
 
                 infer_def(infer_result, source_map);
             }
+            ModuleDef::Const(def) => {
+                let source_map = def.body_source_map(&db);
+                let infer_result = def.infer(&db);
+
+                infer_def(infer_result, source_map);
+            }
+            ModuleDef::Static(def) => {
+                let source_map = def.body_source_map(&db);
+                let infer_result = def.infer(&db);
+
+                infer_def(infer_result, source_map);
+            }
+            ModuleDef::Trait(def) => {
+                for AssocItem::Function(fun) in def.items(&db) {
+                    let source_map = fun.body_source_map(&db);
+                    let infer_result = fun.infer(&db);
+
+                    infer_def(infer_result, source_map);
+                }
+            }
             _ => {}
         }
     }
 
+    for item in Package::all(&db)
+        .iter()
+        .flat_map(|pkg| pkg.modules(&db))
+        .flat_map(|module| module.impls(&db))
+        .flat_map(|impl_def| impl_def.items(&db))
+    {
+        match item {
+            AssocItem::Function(fun) => {
+                let source_map = fun.body_source_map(&db);
+                let infer_result = fun.infer(&db);
+
+                infer_def(infer_result, source_map);
+            }
+        }
+    }
+
     drop(diag_sink);
 
     acc.truncate(acc.trim_end().len());
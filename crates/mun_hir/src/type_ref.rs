@@ -3,9 +3,13 @@
 
 use crate::{
     arena::{map::ArenaMap, Arena, Idx},
+    name::name,
     Path,
 };
-use mun_syntax::{ast, AstPtr};
+use mun_syntax::{
+    ast::{self, TypeAscriptionOwner},
+    AstPtr,
+};
 use rustc_hash::FxHashMap;
 use std::ops::Index;
 
@@ -16,6 +20,11 @@ pub type LocalTypeRefId = Idx<TypeRef>;
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum TypeRef {
     Path(Path),
+    /// A path with type arguments, e.g. `Pair<i32, f64>`
+    Generic(Path, Vec<TypeRef>),
+    Array(Box<TypeRef>),
+    /// A function pointer type, e.g. `fn(i32) -> bool`
+    Fn(Vec<TypeRef>, Box<TypeRef>),
     Never,
     Empty,
     Error,
@@ -26,13 +35,41 @@ impl TypeRef {
     pub fn from_ast(node: ast::TypeRef) -> Self {
         match node.kind() {
             ast::TypeRefKind::NeverType(..) => TypeRef::Never,
+            ast::TypeRefKind::ArrayType(inner) => {
+                TypeRef::Array(Box::new(TypeRef::from_ast_opt(inner.type_ref())))
+            }
+            ast::TypeRefKind::FnType(inner) => {
+                let params = inner
+                    .param_list()
+                    .into_iter()
+                    .flat_map(|list| list.params())
+                    .map(|param| TypeRef::from_ast_opt(param.ascribed_type()))
+                    .collect();
+                let ret_type = match inner.ret_type() {
+                    Some(ret_type) => TypeRef::from_ast_opt(ret_type.type_ref()),
+                    None => TypeRef::Empty,
+                };
+                TypeRef::Fn(params, Box::new(ret_type))
+            }
             ast::TypeRefKind::PathType(inner) => {
                 // FIXME: Use `Path::from_src`
-                inner
-                    .path()
-                    .and_then(Path::from_ast)
-                    .map(TypeRef::Path)
-                    .unwrap_or(TypeRef::Error)
+                let path = match inner.path() {
+                    Some(path) => path,
+                    None => return TypeRef::Error,
+                };
+                let args = path
+                    .segment()
+                    .and_then(|segment| segment.type_arg_list())
+                    .map(|list| {
+                        list.type_args()
+                            .map(|arg| TypeRef::from_ast_opt(arg.type_ref()))
+                            .collect::<Vec<_>>()
+                    });
+                match (Path::from_ast(path), args) {
+                    (Some(path), Some(args)) => TypeRef::Generic(path, args),
+                    (Some(path), None) => TypeRef::Path(path),
+                    (None, _) => TypeRef::Error,
+                }
             }
         }
     }
@@ -105,16 +142,8 @@ impl TypeRefBuilder {
     }
 
     pub fn alloc_from_node(&mut self, node: &ast::TypeRef) -> LocalTypeRefId {
-        use mun_syntax::ast::TypeRefKind::*;
         let ptr = AstPtr::new(node);
-        let type_ref = match node.kind() {
-            PathType(path) => path
-                .path()
-                .and_then(Path::from_ast)
-                .map(TypeRef::Path)
-                .unwrap_or(TypeRef::Error),
-            NeverType(_) => TypeRef::Never,
-        };
+        let type_ref = TypeRef::from_ast(node.clone());
         self.alloc_type_ref(type_ref, ptr)
     }
 
@@ -126,6 +155,13 @@ impl TypeRefBuilder {
         self.map.type_refs.alloc(TypeRef::Error)
     }
 
+    /// Allocates a reference to the `Self` type, used as the type of a `self` parameter.
+    pub fn self_type(&mut self) -> LocalTypeRefId {
+        self.map
+            .type_refs
+            .alloc(TypeRef::Path(Path::from(name![Self])))
+    }
+
     pub fn finish(self) -> (TypeRefMap, TypeRefSourceMap) {
         (self.map, self.source_map)
     }
@@ -1,5 +1,5 @@
 use crate::db::AnalysisDatabase;
-use hir::{AstDatabase, InFile, ModuleId, PackageId, SourceDatabase};
+use hir::{AstDatabase, InFile, ModuleId, SourceDatabase};
 use mun_diagnostics::DiagnosticForWith;
 use mun_syntax::{Location, TextRange};
 use std::cell::RefCell;
@@ -60,14 +60,21 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: hir::FileId) -> Vec<Di
         }));
     });
 
-    let package_id = PackageId(0);
-    let module_tree = db.module_tree(package_id);
-    if let Some(local_id) = module_tree.module_for_file(file_id) {
-        let module_id = ModuleId {
-            package: package_id,
-            local_id,
-        };
-        hir::Module::from(module_id).diagnostics(db, &mut sink);
+    // Find the package that contains the file
+    let source_root = db.file_source_root(file_id);
+    let packages = db.packages();
+    let package_id = packages
+        .iter()
+        .find(|&package_id| packages[package_id].source_root == source_root);
+    if let Some(package_id) = package_id {
+        let module_tree = db.module_tree(package_id);
+        if let Some(local_id) = module_tree.module_for_file(file_id) {
+            let module_id = ModuleId {
+                package: package_id,
+                local_id,
+            };
+            hir::Module::from(module_id).diagnostics(db, &mut sink);
+        }
     }
     drop(sink);
 
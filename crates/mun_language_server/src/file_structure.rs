@@ -1,6 +1,6 @@
 use crate::SymbolKind;
 use mun_syntax::{
-    ast::{self, NameOwner},
+    ast::{self, NameOwner, TypeAscriptionOwner},
     match_ast, AstNode, SourceFile, SyntaxNode, TextRange, WalkEvent,
 };
 
@@ -125,7 +125,30 @@ fn try_convert_to_structure_node(node: &SyntaxNode) -> Option<StructureNode> {
                 decl_with_detail(&it, Some(detail), SymbolKind::Function)
             },
             ast::StructDef(it) => decl(it, SymbolKind::Struct),
+            ast::EnumDef(it) => decl(it, SymbolKind::Enum),
+            ast::EnumVariant(it) => decl(it, SymbolKind::EnumVariant),
             ast::TypeAliasDef(it) => decl_with_type_ref(&it, it.type_ref(), SymbolKind::TypeAlias),
+            ast::ConstDef(it) => decl_with_type_ref(&it, it.ascribed_type(), SymbolKind::Const),
+            ast::StaticDef(it) => decl_with_type_ref(&it, it.ascribed_type(), SymbolKind::Static),
+            ast::TraitDef(it) => decl(it, SymbolKind::Trait),
+            ast::ImplDef(it) => {
+                let type_ref = it.type_ref()?;
+                let mut label = String::from("impl ");
+                if let Some(path) = it.path() {
+                    collapse_whitespaces(path.syntax(), &mut label);
+                    label.push_str(" for ");
+                }
+                collapse_whitespaces(type_ref.syntax(), &mut label);
+
+                Some(StructureNode {
+                    parent: None,
+                    label,
+                    navigation_range: type_ref.syntax().text_range(),
+                    node_range: it.syntax().text_range(),
+                    kind: SymbolKind::Impl,
+                    detail: None,
+                })
+            },
             _ => None
         }
     }
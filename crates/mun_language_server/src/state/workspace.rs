@@ -9,24 +9,60 @@ use std::{
 impl LanguageServerState {
     /// Called to update all workspaces from the files
     pub(crate) fn fetch_workspaces(&mut self) {
-        // Load all the manifests as packages
-        let packages = self
+        // Load all the manifests as workspaces and resolve the packages they depend on. The packages
+        // of all workspaces are analyzed together, packages that are shared by multiple workspaces
+        // are only added once.
+        let mut packages: Vec<project::Package> = Vec::new();
+        let mut dependencies: Vec<Vec<usize>> = Vec::new();
+        for project in self
             .config
             .discovered_projects
             .clone()
             .into_iter()
             .flatten()
-            .filter_map(|project| match project::Package::from_file(&project.path) {
-                Ok(package) => Some(package),
-                Err(err) => {
-                    self.show_message(
-                        lsp_types::MessageType::Error,
-                        format!("mun failed to load package: {:#}", err),
-                    );
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
+        {
+            let graph =
+                match project::Workspace::from_manifest_path(&project.path).and_then(|workspace| {
+                    project::PackageGraph::resolve_members(
+                        workspace.members().to_vec(),
+                        Default::default(),
+                    )
+                }) {
+                    Ok(graph) => graph,
+                    Err(err) => {
+                        self.show_message(
+                            lsp_types::MessageType::Error,
+                            format!("mun failed to load package: {:#}", err),
+                        );
+                        continue;
+                    }
+                };
+
+            let indices = graph
+                .packages()
+                .iter()
+                .map(|package| {
+                    match packages
+                        .iter()
+                        .position(|p| p.manifest_path() == package.manifest_path())
+                    {
+                        Some(index) => index,
+                        None => {
+                            packages.push(package.clone());
+                            dependencies.push(Vec::new());
+                            packages.len() - 1
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            for (idx, &index) in indices.iter().enumerate() {
+                dependencies[index] = graph
+                    .dependencies(idx)
+                    .iter()
+                    .map(|&dependency| indices[dependency])
+                    .collect();
+            }
+        }
 
         // If these packages are the same as the ones we already had, there is little to do.
         if *self.packages == packages {
@@ -87,10 +123,25 @@ impl LanguageServerState {
 
         self.vfs_monitor.set_config(monitor_config);
 
-        // Create the set of packages
+        // Create the set of packages. Only packages that other packages depend on need a name by
+        // which they can be referred to.
         let mut package_set = hir::PackageSet::default();
-        for (idx, _package) in packages.iter().enumerate() {
-            package_set.add_package(hir::SourceRootId(idx as u32));
+        let package_ids = packages
+            .iter()
+            .enumerate()
+            .map(|(idx, package)| {
+                let source_root = hir::SourceRootId(idx as u32);
+                if dependencies.iter().any(|d| d.contains(&idx)) {
+                    package_set.add_named_package(source_root, package.name())
+                } else {
+                    package_set.add_package(source_root)
+                }
+            })
+            .collect::<Vec<_>>();
+        for (idx, package_dependencies) in dependencies.iter().enumerate() {
+            for &dependency in package_dependencies {
+                package_set.add_dependency(package_ids[idx], package_ids[dependency]);
+            }
         }
         change.set_packages(package_set);
 
@@ -1,10 +1,16 @@
 /// Defines a set of symbols that can live in a document.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum SymbolKind {
+    Const,
+    Enum,
+    EnumVariant,
     Field,
     Function,
+    Impl,
     Local,
     Module,
+    Static,
     Struct,
+    Trait,
     TypeAlias,
 }
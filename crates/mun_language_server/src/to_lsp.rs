@@ -69,12 +69,18 @@ pub(crate) fn position(
 /// Converts a symbol kind from this crate to one for the LSP protocol.
 pub(crate) fn symbol_kind(symbol_kind: SymbolKind) -> lsp_types::SymbolKind {
     match symbol_kind {
+        SymbolKind::Const => lsp_types::SymbolKind::Constant,
+        SymbolKind::Enum => lsp_types::SymbolKind::Enum,
+        SymbolKind::EnumVariant => lsp_types::SymbolKind::EnumMember,
         SymbolKind::Function => lsp_types::SymbolKind::Function,
+        SymbolKind::Impl => lsp_types::SymbolKind::Object,
         SymbolKind::Struct => lsp_types::SymbolKind::Struct,
+        SymbolKind::Trait => lsp_types::SymbolKind::Interface,
         SymbolKind::TypeAlias => lsp_types::SymbolKind::TypeParameter,
         SymbolKind::Field => lsp_types::SymbolKind::Field,
         SymbolKind::Local => lsp_types::SymbolKind::Variable,
         SymbolKind::Module => lsp_types::SymbolKind::Module,
+        SymbolKind::Static => lsp_types::SymbolKind::Variable,
     }
 }
 
@@ -119,11 +125,17 @@ pub(crate) fn completion_item_kind(
         CompletionItemKind::Snippet => lsp_types::CompletionItemKind::Snippet,
         CompletionItemKind::UnresolvedReference => lsp_types::CompletionItemKind::Reference,
         CompletionItemKind::SymbolKind(symbol) => match symbol {
+            SymbolKind::Const => lsp_types::CompletionItemKind::Constant,
+            SymbolKind::Enum => lsp_types::CompletionItemKind::Enum,
+            SymbolKind::EnumVariant => lsp_types::CompletionItemKind::EnumMember,
             SymbolKind::Field => lsp_types::CompletionItemKind::Field,
             SymbolKind::Function => lsp_types::CompletionItemKind::Function,
+            SymbolKind::Impl => lsp_types::CompletionItemKind::Text,
             SymbolKind::Local => lsp_types::CompletionItemKind::Variable,
             SymbolKind::Module => lsp_types::CompletionItemKind::Module,
+            SymbolKind::Static => lsp_types::CompletionItemKind::Variable,
             SymbolKind::Struct => lsp_types::CompletionItemKind::Struct,
+            SymbolKind::Trait => lsp_types::CompletionItemKind::Interface,
             SymbolKind::TypeAlias => lsp_types::CompletionItemKind::Struct,
         },
         CompletionItemKind::Attribute => lsp_types::CompletionItemKind::EnumMember,
@@ -49,3 +49,45 @@ fn test_document_symbols() {
 
     insta::assert_debug_snapshot!(symbols);
 }
+
+#[test]
+fn test_workspace() {
+    let server = Project::with_fixture(
+        r#"
+    //- /mun.toml
+    [workspace]
+    members = ["game", "math"]
+
+    //- /game/mun.toml
+    [package]
+    name = "game"
+    version = "0.0.0"
+
+    [dependencies]
+    math = { path = "../math" }
+
+    //- /game/src/mod.mun
+    pub fn main() -> f32 { math::add(1.0, 2.0) }
+
+    //- /math/mun.toml
+    [package]
+    name = "math"
+    version = "0.0.0"
+
+    //- /math/src/mod.mun
+    pub fn add(a: f32, b: f32) -> f32 { a + b }
+    "#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    // The files of all members are part of the analysis
+    let symbols = server.send_request::<lsp_types::request::DocumentSymbolRequest>(
+        lsp_types::DocumentSymbolParams {
+            text_document: server.doc_id("math/src/mod.mun"),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    );
+    assert!(symbols.is_some());
+}
@@ -81,10 +81,12 @@ where
     let (deletions, insertions) = myers::split_diff(&diff);
 
     // ASSUMPTION: `Primitive` types can never be converted to `Struct` types, hence they can be
-    // compared separately.
+    // compared separately. `Array` types are uniquely identified by their element type, so they are
+    // compared the same way as `Primitive` types. The same holds for `Enum` types, whose identity
+    // includes all of their variants.
     let deleted_primitives = deletions
         .iter()
-        .filter(|idx| unsafe { old.get_unchecked(**idx) }.group() == TypeGroup::Primitive)
+        .filter(|idx| unsafe { old.get_unchecked(**idx) }.group() != TypeGroup::Struct)
         .cloned()
         .collect();
     let deleted_structs = deletions
@@ -95,7 +97,7 @@ where
 
     let inserted_primitives = insertions
         .iter()
-        .filter(|idx| unsafe { new.get_unchecked(**idx) }.group() == TypeGroup::Primitive)
+        .filter(|idx| unsafe { new.get_unchecked(**idx) }.group() != TypeGroup::Struct)
         .cloned()
         .collect();
     let inserted_structs = insertions
@@ -1,18 +1,60 @@
+mod generational;
 mod mark_sweep;
+mod policy;
 mod ptr;
 mod root_ptr;
+mod snapshot;
 
 use crate::TypeMemory;
-use std::marker::PhantomData;
+use std::{alloc::Layout, marker::PhantomData, time::Duration};
 
+pub use generational::Generational;
 pub use mark_sweep::MarkSweep;
+pub use policy::CollectionPolicy;
 pub use ptr::{GcPtr, HasIndirectionPtr, RawGcPtr};
 pub use root_ptr::GcRootPtr;
+pub use snapshot::{HeapSnapshot, ObjectSnapshot, TypeSummary};
 
 /// Contains stats about the current state of a GC implementation
 #[derive(Debug, Clone, Default)]
 pub struct Stats {
+    /// The number of bytes that are currently allocated
     pub allocated_memory: usize,
+    /// The number of objects that are currently allocated
+    pub allocated_objects: usize,
+    /// The number of collections that have run
+    pub collections: usize,
+    /// The total number of bytes that were freed by collections
+    pub freed_memory: usize,
+    /// The duration of the last collection
+    pub last_pause: Duration,
+}
+
+/// The header of an array object. The elements of an array object are stored directly after its
+/// header.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ArrayHeader {
+    /// The number of elements stored in the array
+    pub length: usize,
+}
+
+impl ArrayHeader {
+    /// Returns the memory layout of an array object that contains `length` elements of the
+    /// specified `element` layout, together with the offset of the first element.
+    pub fn layout(element: Layout, length: usize) -> (Layout, usize) {
+        let elements_size = element
+            .pad_to_align()
+            .size()
+            .checked_mul(length)
+            .expect("array size exceeds the maximum allowed size");
+        let elements = Layout::from_size_align(elements_size, element.align())
+            .expect("invalid array element layout");
+        let (layout, offset) = Layout::new::<ArrayHeader>()
+            .extend(elements)
+            .expect("array size exceeds the maximum allowed size");
+        (layout.pad_to_align(), offset)
+    }
 }
 
 /// A trait used to trace an object type.
@@ -28,6 +70,11 @@ pub trait GcRuntime<T: TypeMemory + TypeTrace>: Send + Sync {
     /// Allocates an object of the given type returning a GcPtr
     fn alloc(&self, ty: T) -> GcPtr;
 
+    /// Allocates an array object of the given type, that contains `length` zero-initialized
+    /// elements of the specified `element` layout, returning a GcPtr. The memory of the object
+    /// starts with an `ArrayHeader`.
+    fn alloc_array(&self, ty: T, element: Layout, length: usize) -> GcPtr;
+
     /// Returns the type of the specified `obj`.
     fn ptr_type(&self, obj: GcPtr) -> T;
 
@@ -44,6 +91,12 @@ pub trait GcRuntime<T: TypeMemory + TypeTrace>: Send + Sync {
 
     /// Returns stats about the current state of the runtime.
     fn stats(&self) -> Stats;
+
+    /// Notifies the runtime that a reference to another object was stored in `obj`. Collectors
+    /// that only trace part of the heap use this to find references into that part from objects
+    /// that are not traced. Mun code calls this through a write barrier after every store of a
+    /// value that can contain references into a garbage collected object.
+    fn write_barrier(&self, _obj: GcPtr) {}
 }
 
 /// The `Observer` trait allows receiving of `Event`s.
@@ -0,0 +1,454 @@
+use crate::{
+    gc::{
+        mark_sweep::{alloc_array_obj, alloc_obj, map_objects, Color, ObjectInfo},
+        policy::Trigger,
+        CollectionPolicy, Event, GcPtr, GcRuntime, HeapSnapshot, Observer, RawGcPtr, Stats,
+        TypeTrace,
+    },
+    mapping::{Mapping, MemoryMapper},
+    TypeDesc, TypeMemory,
+};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    alloc::Layout,
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Deref,
+    pin::Pin,
+    time::Instant,
+};
+
+/// Implements a generational garbage collector with two generations.
+///
+/// Newly allocated objects are stored in the young generation. A minor collection only traces and
+/// sweeps the young generation, and promotes the objects that survive it to the old generation.
+/// Objects in the old generation are only reclaimed by a major collection, which traces the
+/// entire heap.
+///
+/// Mun code emits a write barrier whenever it stores a value that can contain references into a
+/// garbage collected object. The collector records the old objects that were written to in a
+/// remembered set, and a minor collection treats their references to young objects as roots. As a
+/// result, a minor collection only traces the young generation and the remembered set, instead of
+/// the entire heap.
+///
+/// When its `CollectionPolicy` determines that a collection is due, a minor collection is run.
+/// Objects that are promoted to the old generation count as allocations in the old generation; if
+/// the policy determines that a collection of the old generation is due as well, a major
+/// collection is run.
+#[derive(Debug)]
+pub struct Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    heap: RwLock<Heap<T>>,
+    observer: O,
+    stats: RwLock<Stats>,
+    /// Determines when a minor collection is due
+    minor_trigger: Mutex<Trigger>,
+    /// Determines when a major collection is due
+    major_trigger: Mutex<Trigger>,
+}
+
+/// The objects managed by a `Generational` garbage collector.
+#[derive(Debug)]
+struct Heap<T: TypeMemory + TypeTrace + Clone> {
+    /// All objects, regardless of their generation
+    objects: HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>,
+    /// The handles of the objects in the young generation
+    young: HashSet<GcPtr>,
+    /// The handles of the objects in the old generation that were written to since the last
+    /// collection, and can therefore reference young objects
+    remembered: HashSet<GcPtr>,
+}
+
+impl<T: TypeMemory + TypeTrace + Clone> Default for Heap<T> {
+    fn default() -> Self {
+        Heap {
+            objects: HashMap::new(),
+            young: HashSet::new(),
+            remembered: HashSet::new(),
+        }
+    }
+}
+
+impl<T, O> Default for Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event> + Default,
+{
+    fn default() -> Self {
+        Generational {
+            heap: RwLock::new(Heap::default()),
+            observer: O::default(),
+            stats: RwLock::new(Stats::default()),
+            minor_trigger: Mutex::new(Trigger::default()),
+            major_trigger: Mutex::new(Trigger::default()),
+        }
+    }
+}
+
+impl<T, O> Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    /// Creates a `Generational` memory collector with the specified `Observer`.
+    pub fn with_observer(observer: O) -> Self {
+        Self {
+            heap: RwLock::new(Heap::default()),
+            observer,
+            stats: RwLock::new(Stats::default()),
+            minor_trigger: Mutex::new(Trigger::default()),
+            major_trigger: Mutex::new(Trigger::default()),
+        }
+    }
+
+    /// Logs an allocation and evaluates the collection policy
+    fn log_alloc(&self, handle: GcPtr, layout: Layout) {
+        let allocated_memory = {
+            let mut stats = self.stats.write();
+            stats.allocated_memory += layout.size();
+            stats.allocated_objects += 1;
+            stats.allocated_memory
+        };
+
+        self.minor_trigger.lock().on_alloc(1, allocated_memory);
+        self.observer.event(Event::Allocation(handle));
+    }
+
+    /// Logs the end of a collection that started at `start`, returning the number of bytes that
+    /// remain allocated
+    fn log_collect(&self, start: Instant) -> usize {
+        let mut stats = self.stats.write();
+        stats.collections += 1;
+        stats.last_pause = start.elapsed();
+        stats.allocated_memory
+    }
+
+    /// Deallocates an object and logs the deallocation
+    fn dealloc(&self, handle: GcPtr, object: Pin<Box<ObjectInfo<T>>>) {
+        unsafe { std::alloc::dealloc(object.ptr, object.layout) };
+        self.observer.event(Event::Deallocation(handle));
+        {
+            let mut stats = self.stats.write();
+            stats.allocated_memory -= object.layout.size();
+            stats.allocated_objects -= 1;
+            stats.freed_memory += object.layout.size();
+        }
+    }
+
+    /// Returns the policy that determines when a collection is due
+    pub fn policy(&self) -> CollectionPolicy {
+        self.minor_trigger.lock().policy()
+    }
+
+    /// Sets the policy that determines when a collection is due
+    pub fn set_policy(&self, policy: CollectionPolicy) {
+        self.minor_trigger.lock().set_policy(policy);
+        self.major_trigger.lock().set_policy(policy);
+    }
+
+    /// Returns the observer
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Returns the number of objects in the young generation.
+    pub fn young_len(&self) -> usize {
+        self.heap.read().young.len()
+    }
+
+    /// Returns the number of objects in the old generation.
+    pub fn old_len(&self) -> usize {
+        let heap = self.heap.read();
+        heap.objects.len() - heap.young.len()
+    }
+
+    /// Stores a newly allocated object in the young generation and returns its handle
+    fn insert_obj(&self, object: Pin<Box<ObjectInfo<T>>>) -> GcPtr {
+        let layout = object.layout;
+
+        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+
+        {
+            let mut heap = self.heap.write();
+            heap.objects.insert(handle, object);
+            heap.young.insert(handle);
+        }
+
+        self.log_alloc(handle, layout);
+        handle
+    }
+}
+
+impl<T, O> GcRuntime<T> for Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    fn alloc(&self, ty: T) -> GcPtr {
+        self.insert_obj(alloc_obj(ty))
+    }
+
+    fn alloc_array(&self, ty: T, element: Layout, length: usize) -> GcPtr {
+        self.insert_obj(alloc_array_obj(ty, element, length))
+    }
+
+    fn ptr_type(&self, handle: GcPtr) -> T {
+        let _heap = self.heap.read();
+
+        // Convert the handle to our internal representation
+        let object_info: *const ObjectInfo<T> = handle.into();
+
+        // Return the type of the object
+        unsafe { (*object_info).ty.clone() }
+    }
+
+    fn root(&self, handle: GcPtr) {
+        let _heap = self.heap.write();
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo<T> = handle.into();
+
+        unsafe { (*object_info).roots += 1 };
+    }
+
+    fn unroot(&self, handle: GcPtr) {
+        let _heap = self.heap.write();
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo<T> = handle.into();
+
+        unsafe { (*object_info).roots -= 1 };
+    }
+
+    fn stats(&self) -> Stats {
+        self.stats.read().clone()
+    }
+
+    fn write_barrier(&self, handle: GcPtr) {
+        let mut heap = self.heap.write();
+        if !heap.young.contains(&handle) {
+            heap.remembered.insert(handle);
+        }
+    }
+}
+
+impl<T, O> Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    /// Collects all memory in the young generation that is no longer referenced by rooted or old
+    /// objects, and promotes the remaining young objects to the old generation. Only the young
+    /// generation and the old objects in the remembered set are traced. Returns `true` if memory
+    /// was reclaimed, `false` otherwise.
+    pub fn collect_minor(&self) -> bool {
+        self.observer.event(Event::Start);
+        let start = Instant::now();
+
+        let mut heap = self.heap.write();
+        let Heap {
+            objects,
+            young,
+            remembered,
+        } = &mut *heap;
+
+        // Young objects are reachable if they are rooted or referenced by an old object. Old
+        // objects can only reference young objects if they were written to since the last
+        // collection, in which case they are remembered.
+        let mut gray = VecDeque::new();
+        for handle in young.iter() {
+            let obj = objects.get(handle).expect("found invalid reference");
+            if obj.roots > 0 {
+                shade(objects, *handle, &mut gray);
+            }
+        }
+        for handle in remembered.drain() {
+            let obj = objects.get(&handle).expect("found invalid reference");
+            for reference in obj.ty.trace(handle) {
+                if young.contains(&reference) {
+                    shade(objects, reference, &mut gray);
+                }
+            }
+        }
+
+        // Trace all reachable young objects
+        mark(objects, gray, |reference| young.contains(reference));
+
+        // Sweep all non-reachable young objects and promote the others
+        let mut reclaimed = false;
+        let mut promoted = 0;
+        for handle in young.drain() {
+            let obj = objects.get_mut(&handle).expect("found invalid reference");
+            if obj.color == Color::Black {
+                unsafe {
+                    obj.as_mut().get_unchecked_mut().color = Color::White;
+                }
+                promoted += 1;
+            } else {
+                let obj = objects.remove(&handle).expect("found invalid reference");
+                self.dealloc(handle, obj);
+                reclaimed = true;
+            }
+        }
+
+        let allocated_memory = self.log_collect(start);
+        self.minor_trigger.lock().on_collect(allocated_memory);
+        self.major_trigger
+            .lock()
+            .on_alloc(promoted, allocated_memory);
+
+        self.observer.event(Event::End);
+
+        reclaimed
+    }
+
+    /// Collects all memory that is no longer referenced by rooted objects, regardless of its
+    /// generation. All remaining objects are promoted to the old generation. Returns `true` if
+    /// memory was reclaimed, `false` otherwise.
+    pub fn collect(&self) -> bool {
+        self.observer.event(Event::Start);
+        let start = Instant::now();
+
+        let mut heap = self.heap.write();
+        let Heap {
+            objects,
+            young,
+            remembered,
+        } = &mut *heap;
+
+        // Get all roots
+        let mut gray = VecDeque::new();
+        for (handle, obj) in objects.iter() {
+            if obj.roots > 0 {
+                shade(objects, *handle, &mut gray);
+            }
+        }
+
+        // Trace all reachable objects
+        mark(objects, gray, |_| true);
+
+        // Sweep all non-reachable objects
+        let unreachable = objects
+            .iter()
+            .filter_map(|(handle, obj)| {
+                if obj.color == Color::Black {
+                    None
+                } else {
+                    Some(*handle)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for obj in objects.values_mut() {
+            unsafe {
+                obj.as_mut().get_unchecked_mut().color = Color::White;
+            }
+        }
+
+        for handle in unreachable.iter() {
+            let obj = objects.remove(handle).expect("found invalid reference");
+            self.dealloc(*handle, obj);
+        }
+        young.clear();
+        remembered.clear();
+
+        let allocated_memory = self.log_collect(start);
+        self.minor_trigger.lock().on_collect(allocated_memory);
+        self.major_trigger.lock().on_collect(allocated_memory);
+
+        self.observer.event(Event::End);
+
+        !unreachable.is_empty()
+    }
+
+    /// Returns a snapshot of all objects that are currently allocated.
+    pub fn snapshot(&self) -> HeapSnapshot<T> {
+        let heap = self.heap.read();
+        HeapSnapshot::from_objects(&heap.objects)
+    }
+
+    /// Runs a minor collection if one is due according to the collection policy, followed by a
+    /// major collection if that is due as well. Returns `true` if memory was reclaimed, `false`
+    /// otherwise.
+    pub fn collect_if_due(&self) -> bool {
+        let is_due = self.minor_trigger.lock().is_due();
+        if !is_due {
+            return false;
+        }
+
+        let reclaimed = self.collect_minor();
+        let is_due = self.major_trigger.lock().is_due();
+        if is_due {
+            self.collect() || reclaimed
+        } else {
+            reclaimed
+        }
+    }
+}
+
+/// Marks the object of `handle` as seen, if it has not been seen yet, and queues it to be traced.
+fn shade<T: TypeMemory + TypeTrace + Clone>(
+    objects: &HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>,
+    handle: GcPtr,
+    gray: &mut VecDeque<*mut ObjectInfo<T>>,
+) {
+    let obj = objects.get(&handle).expect("found invalid reference");
+    if obj.color == Color::White {
+        let ptr = obj.as_ref().get_ref() as *const _ as *mut ObjectInfo<T>;
+        unsafe { (*ptr).color = Color::Gray };
+        gray.push_back(ptr);
+    }
+}
+
+/// Traces all `gray` objects, and the objects they transitively reference, coloring them black.
+/// Only references for which `follow` returns `true` are traced.
+fn mark<T: TypeMemory + TypeTrace + Clone>(
+    objects: &HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>,
+    mut gray: VecDeque<*mut ObjectInfo<T>>,
+    follow: impl Fn(&GcPtr) -> bool,
+) {
+    while let Some(next) = gray.pop_front() {
+        let handle = (next as *const _ as RawGcPtr).into();
+
+        // Trace all other objects
+        for reference in unsafe { (*next).ty.trace(handle) } {
+            if follow(&reference) {
+                shade(objects, reference, &mut gray);
+            }
+        }
+
+        // This object has been traced
+        unsafe {
+            (*next).color = Color::Black;
+        }
+    }
+}
+
+impl<T, O> MemoryMapper<T> for Generational<T, O>
+where
+    T: TypeDesc + TypeMemory + TypeTrace + Clone + Eq + Hash,
+    O: Observer<Event = Event>,
+{
+    fn map_memory(&self, mapping: Mapping<T, T>) -> Vec<GcPtr> {
+        let (deleted, migrations) = {
+            let mut heap = self.heap.write();
+            let Heap { objects, .. } = &mut *heap;
+
+            // Objects that are allocated while mapping are stored in fields of existing objects,
+            // without a write barrier, so they are stored in the old generation.
+            map_objects(objects, mapping, |handle, layout| self.log_alloc(handle, layout))
+        };
+
+        // Migrations are run after unlocking the heap, as they are allowed to allocate
+        for migration in migrations {
+            // Safety: Mapped objects are alive until the next collection
+            unsafe { migration.run() };
+        }
+
+        deleted
+    }
+}
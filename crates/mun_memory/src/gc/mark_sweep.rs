@@ -1,17 +1,22 @@
 use crate::{
     cast,
-    gc::{Event, GcPtr, GcRuntime, Observer, RawGcPtr, Stats, TypeTrace},
+    gc::{
+        policy::Trigger, ArrayHeader, CollectionPolicy, Event, GcPtr, GcRuntime, HeapSnapshot,
+        Observer, RawGcPtr, Stats, TypeTrace,
+    },
     mapping::{self, FieldMapping, MemoryMapper},
     TypeDesc, TypeGroup, TypeMemory,
 };
-use mapping::{Conversion, Mapping};
-use parking_lot::RwLock;
+use mapping::{Conversion, Mapping, PendingMigration};
+use parking_lot::{Mutex, RwLock};
 use std::{
+    alloc::Layout,
     collections::{HashMap, VecDeque},
     hash::Hash,
     ops::Deref,
     pin::Pin,
     ptr::NonNull,
+    time::Instant,
 };
 
 /// Implements a simple mark-sweep type garbage collector.
@@ -24,6 +29,7 @@ where
     objects: RwLock<HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>>,
     observer: O,
     stats: RwLock<Stats>,
+    trigger: Mutex<Trigger>,
 }
 
 impl<T, O> Default for MarkSweep<T, O>
@@ -36,6 +42,7 @@ where
             objects: RwLock::new(HashMap::new()),
             observer: O::default(),
             stats: RwLock::new(Stats::default()),
+            trigger: Mutex::new(Trigger::default()),
         }
     }
 }
@@ -51,32 +58,81 @@ where
             objects: RwLock::new(HashMap::new()),
             observer,
             stats: RwLock::new(Stats::default()),
+            trigger: Mutex::new(Trigger::default()),
         }
     }
 
-    /// Logs an allocation
-    fn log_alloc(&self, handle: GcPtr, ty: T) {
-        {
+    /// Logs an allocation and evaluates the collection policy
+    fn log_alloc(&self, handle: GcPtr, layout: Layout) {
+        let allocated_memory = {
             let mut stats = self.stats.write();
-            stats.allocated_memory += ty.layout().size();
-        }
+            stats.allocated_memory += layout.size();
+            stats.allocated_objects += 1;
+            stats.allocated_memory
+        };
 
+        self.trigger.lock().on_alloc(1, allocated_memory);
         self.observer.event(Event::Allocation(handle));
     }
 
+    /// Returns the policy that determines when a collection is due
+    pub fn policy(&self) -> CollectionPolicy {
+        self.trigger.lock().policy()
+    }
+
+    /// Sets the policy that determines when a collection is due
+    pub fn set_policy(&self, policy: CollectionPolicy) {
+        self.trigger.lock().set_policy(policy)
+    }
+
     /// Returns the observer
     pub fn observer(&self) -> &O {
         &self.observer
     }
+
+    /// Stores a newly allocated object and returns its handle
+    fn insert_obj(&self, object: Pin<Box<ObjectInfo<T>>>) -> GcPtr {
+        let layout = object.layout;
+
+        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+
+        {
+            let mut objects = self.objects.write();
+            objects.insert(handle, object);
+        }
+
+        self.log_alloc(handle, layout);
+        handle
+    }
+}
+
+pub(super) fn alloc_obj<T: Clone + TypeMemory + TypeTrace>(ty: T) -> Pin<Box<ObjectInfo<T>>> {
+    let layout = ty.layout();
+    let ptr = unsafe { std::alloc::alloc(layout) };
+    Box::pin(ObjectInfo {
+        ptr,
+        ty,
+        roots: 0,
+        color: Color::White,
+        layout,
+    })
 }
 
-fn alloc_obj<T: Clone + TypeMemory + TypeTrace>(ty: T) -> Pin<Box<ObjectInfo<T>>> {
-    let ptr = unsafe { std::alloc::alloc(ty.layout()) };
+pub(super) fn alloc_array_obj<T: Clone + TypeMemory + TypeTrace>(
+    ty: T,
+    element: Layout,
+    length: usize,
+) -> Pin<Box<ObjectInfo<T>>> {
+    let (layout, _) = ArrayHeader::layout(element, length);
+    let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+    unsafe { *ptr.cast::<ArrayHeader>() = ArrayHeader { length } };
     Box::pin(ObjectInfo {
         ptr,
         ty,
         roots: 0,
         color: Color::White,
+        layout,
     })
 }
 
@@ -86,18 +142,11 @@ where
     O: Observer<Event = Event>,
 {
     fn alloc(&self, ty: T) -> GcPtr {
-        let object = alloc_obj(ty.clone());
-
-        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
-        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
-
-        {
-            let mut objects = self.objects.write();
-            objects.insert(handle, object);
-        }
+        self.insert_obj(alloc_obj(ty))
+    }
 
-        self.log_alloc(handle, ty);
-        handle
+    fn alloc_array(&self, ty: T, element: Layout, length: usize) -> GcPtr {
+        self.insert_obj(alloc_array_obj(ty, element, length))
     }
 
     fn ptr_type(&self, handle: GcPtr) -> T {
@@ -142,6 +191,7 @@ where
     /// was reclaimed, `false` otherwise.
     pub fn collect(&self) -> bool {
         self.observer.event(Event::Start);
+        let start = Instant::now();
 
         let mut objects = self.objects.write();
 
@@ -188,21 +238,45 @@ where
                 }
                 true
             } else {
-                unsafe { std::alloc::dealloc(obj.ptr, obj.ty.layout()) };
+                unsafe { std::alloc::dealloc(obj.ptr, obj.layout) };
                 self.observer.event(Event::Deallocation(*h));
                 {
                     let mut stats = self.stats.write();
-                    stats.allocated_memory -= obj.ty.layout().size();
+                    stats.allocated_memory -= obj.layout.size();
+                    stats.allocated_objects -= 1;
+                    stats.freed_memory += obj.layout.size();
                 }
                 false
             }
         });
         let size_after = objects.len();
 
+        let allocated_memory = {
+            let mut stats = self.stats.write();
+            stats.collections += 1;
+            stats.last_pause = start.elapsed();
+            stats.allocated_memory
+        };
+        self.trigger.lock().on_collect(allocated_memory);
+
         self.observer.event(Event::End);
 
         size_before != size_after
     }
+
+    /// Returns a snapshot of all objects that are currently allocated.
+    pub fn snapshot(&self) -> HeapSnapshot<T> {
+        let objects = self.objects.read();
+        HeapSnapshot::from_objects(&objects)
+    }
+
+    /// Collects all memory that is no longer referenced by rooted objects, if a collection is due
+    /// according to the collection policy. Returns `true` if memory was reclaimed, `false`
+    /// otherwise.
+    pub fn collect_if_due(&self) -> bool {
+        let is_due = self.trigger.lock().is_due();
+        is_due && self.collect()
+    }
 }
 
 impl<T, O> MemoryMapper<T> for MarkSweep<T, O>
@@ -211,295 +285,337 @@ where
     O: Observer<Event = Event>,
 {
     fn map_memory(&self, mapping: Mapping<T, T>) -> Vec<GcPtr> {
-        let mut objects = self.objects.write();
-
-        // Determine which types are still allocated with deleted types
-        let deleted = objects
-            .iter()
-            .filter_map(|(ptr, object_info)| {
-                if mapping.deletions.contains(&object_info.ty) {
-                    Some(*ptr)
-                } else {
-                    None
-                }
+        let (deleted, migrations) = {
+            let mut objects = self.objects.write();
+            map_objects(&mut objects, mapping, |handle, layout| {
+                self.log_alloc(handle, layout)
             })
-            .collect();
-
-        // Update type pointers of types that didn't change
-        for (old_ty, new_ty) in mapping.identical {
-            for object_info in objects.values_mut() {
-                if object_info.ty == old_ty {
-                    object_info.set(ObjectInfo {
-                        ptr: object_info.ptr,
-                        roots: object_info.roots,
-                        color: object_info.color,
-                        ty: new_ty.clone(),
-                    });
-                }
-            }
+        };
+
+        // Migrations are run after unlocking the objects, as they are allowed to allocate
+        for migration in migrations {
+            // Safety: Mapped objects are alive until the next collection
+            unsafe { migration.run() };
         }
 
-        let mut new_allocations = Vec::new();
+        deleted
+    }
+}
 
-        for (old_ty, conversion) in mapping.conversions.iter() {
-            for object_info in objects.values_mut() {
-                if object_info.ty == *old_ty {
-                    let src = unsafe { NonNull::new_unchecked(object_info.ptr) };
-                    let dest = unsafe {
-                        NonNull::new_unchecked(std::alloc::alloc_zeroed(conversion.new_ty.layout()))
-                    };
+/// Maps the memory of all `objects` according to the specified `mapping`, returning the handles of
+/// objects whose type was deleted and the user-defined migrations that still need to be run.
+/// `on_alloc` is called for every object that is allocated while mapping, after it has been stored
+/// in `objects`.
+pub(super) fn map_objects<T>(
+    objects: &mut HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>,
+    mapping: Mapping<T, T>,
+    mut on_alloc: impl FnMut(GcPtr, Layout),
+) -> (Vec<GcPtr>, Vec<PendingMigration>)
+where
+    T: TypeDesc + TypeMemory + TypeTrace + Clone + Eq + Hash,
+{
+    // Determine which types are still allocated with deleted types
+    let deleted = objects
+        .iter()
+        .filter_map(|(ptr, object_info)| {
+            if mapping.deletions.contains(&object_info.ty) {
+                Some(*ptr)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Update type pointers of types that didn't change
+    for (old_ty, new_ty) in mapping.identical {
+        for object_info in objects.values_mut() {
+            if object_info.ty == old_ty {
+                object_info.set(ObjectInfo {
+                    ptr: object_info.ptr,
+                    roots: object_info.roots,
+                    color: object_info.color,
+                    ty: new_ty.clone(),
+                    layout: object_info.layout,
+                });
+            }
+        }
+    }
 
-                    map_fields(
-                        self,
-                        &mut new_allocations,
-                        &mapping.conversions,
-                        &conversion.field_mapping,
-                        src,
-                        dest,
-                    );
+    let mut new_allocations = Vec::new();
+    let mut migrations = Vec::new();
 
-                    unsafe { std::alloc::dealloc(src.as_ptr(), old_ty.layout()) };
+    for (old_ty, conversion) in mapping.conversions.iter() {
+        for (handle, object_info) in objects.iter_mut() {
+            if object_info.ty == *old_ty {
+                let src = unsafe { NonNull::new_unchecked(object_info.ptr) };
+                let dest = unsafe {
+                    NonNull::new_unchecked(std::alloc::alloc_zeroed(conversion.new_ty.layout()))
+                };
 
-                    object_info.set(ObjectInfo {
-                        ptr: dest.as_ptr(),
-                        roots: object_info.roots,
-                        color: object_info.color,
-                        ty: conversion.new_ty.clone(),
-                    });
+                map_fields(
+                    &mut new_allocations,
+                    &mapping.conversions,
+                    &conversion.field_mapping,
+                    src,
+                    dest,
+                );
+
+                if let Some(migration) = conversion.migration.as_ref() {
+                    // The old memory is deallocated after running the migration
+                    migrations.push(PendingMigration::new(
+                        migration.clone(),
+                        src,
+                        object_info.layout,
+                        *handle,
+                    ));
+                } else {
+                    unsafe { std::alloc::dealloc(src.as_ptr(), object_info.layout) };
                 }
+
+                object_info.set(ObjectInfo {
+                    ptr: dest.as_ptr(),
+                    roots: object_info.roots,
+                    color: object_info.color,
+                    ty: conversion.new_ty.clone(),
+                    layout: conversion.new_ty.layout(),
+                });
             }
         }
+    }
 
-        // Retroactively store newly allocated objects
-        // This cannot be done while mapping because we hold a mutable reference to objects
-        for object in new_allocations {
-            let ty = object.ty.clone();
-            // We want to return a pointer to the `ObjectInfo`, to
-            // be used as handle.
-            let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
-            objects.insert(handle, object);
+    // Retroactively store newly allocated objects
+    // This cannot be done while mapping because we hold a mutable reference to objects
+    for object in new_allocations {
+        let layout = object.layout;
+        // We want to return a pointer to the `ObjectInfo`, to
+        // be used as handle.
+        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+        objects.insert(handle, object);
 
-            self.log_alloc(handle, ty);
-        }
+        on_alloc(handle, layout);
+    }
 
-        return deleted;
-
-        fn map_fields<T, O>(
-            gc: &MarkSweep<T, O>,
-            new_allocations: &mut Vec<Pin<Box<ObjectInfo<T>>>>,
-            conversions: &HashMap<T, Conversion<T>>,
-            mapping: &[FieldMapping<T>],
-            src: NonNull<u8>,
-            dest: NonNull<u8>,
-        ) where
-            T: TypeDesc + TypeMemory + TypeTrace + Clone + Eq + Hash,
-            O: Observer<Event = Event>,
+    return (deleted, migrations);
+
+    fn map_fields<T>(
+        new_allocations: &mut Vec<Pin<Box<ObjectInfo<T>>>>,
+        conversions: &HashMap<T, Conversion<T>>,
+        mapping: &[FieldMapping<T>],
+        src: NonNull<u8>,
+        dest: NonNull<u8>,
+    ) where
+        T: TypeDesc + TypeMemory + TypeTrace + Clone + Eq + Hash,
+    {
+        for FieldMapping {
+            new_ty,
+            new_offset,
+            action,
+        } in mapping.iter()
         {
-            for FieldMapping {
-                new_ty,
-                new_offset,
-                action,
-            } in mapping.iter()
-            {
-                let field_dest = {
-                    let mut dest = dest.as_ptr() as usize;
-                    dest += new_offset;
-                    dest as *mut u8
-                };
+            let field_dest = {
+                let mut dest = dest.as_ptr() as usize;
+                dest += new_offset;
+                dest as *mut u8
+            };
+
+            match action {
+                mapping::Action::Cast { old_offset, old_ty } => {
+                    let field_src = {
+                        let mut src = src.as_ptr() as usize;
+                        src += old_offset;
+                        src as *mut u8
+                    };
 
-                match action {
-                    mapping::Action::Cast { old_offset, old_ty } => {
-                        let field_src = {
-                            let mut src = src.as_ptr() as usize;
-                            src += old_offset;
-                            src as *mut u8
-                        };
-
-                        if old_ty.group() == TypeGroup::Struct {
-                            debug_assert_eq!(new_ty.group(), TypeGroup::Struct);
-
-                            // When the name is the same, we are dealing with the same struct,
-                            // but different internals
-                            let is_same_struct = old_ty.name() == new_ty.name();
-
-                            // If the same struct changed, there must also be a conversion
-                            let conversion = conversions.get(old_ty);
-
-                            if old_ty.is_stack_allocated() {
-                                if new_ty.is_stack_allocated() {
-                                    // struct(value) -> struct(value)
-                                    if is_same_struct {
-                                        // Map in-memory struct to in-memory struct
-                                        map_fields(
-                                            gc,
-                                            new_allocations,
-                                            conversions,
-                                            &conversion.as_ref().unwrap().field_mapping,
-                                            unsafe { NonNull::new_unchecked(field_src) },
-                                            unsafe { NonNull::new_unchecked(field_dest) },
-                                        );
-                                    } else {
-                                        // Use previously zero-initialized memory
-                                    }
-                                } else {
-                                    // struct(value) -> struct(gc)
-                                    let object = alloc_obj(new_ty.clone());
-
-                                    // We want to return a pointer to the `ObjectInfo`, to be used as handle.
-                                    let handle =
-                                        (object.as_ref().deref() as *const _ as RawGcPtr).into();
-
-                                    if is_same_struct {
-                                        // Map in-memory struct to heap-allocated struct
-                                        map_fields(
-                                            gc,
-                                            new_allocations,
-                                            conversions,
-                                            &conversion.as_ref().unwrap().field_mapping,
-                                            unsafe { NonNull::new_unchecked(field_src) },
-                                            unsafe { NonNull::new_unchecked(object.ptr) },
-                                        );
-                                    } else {
-                                        // Zero initialize heap-allocated object
-                                        unsafe {
-                                            std::ptr::write_bytes(
-                                                (*object).ptr,
-                                                0,
-                                                new_ty.layout().size(),
-                                            )
-                                        };
-                                    }
-
-                                    // Write handle to field
-                                    let field_handle = field_dest.cast::<GcPtr>();
-                                    unsafe { *field_handle = handle };
-
-                                    new_allocations.push(object);
-                                }
-                            } else if !new_ty.is_stack_allocated() {
-                                // struct(gc) -> struct(gc)
-                                let field_src = field_src.cast::<GcPtr>();
-                                let field_dest = field_dest.cast::<GcPtr>();
+                    if old_ty.group() == TypeGroup::Struct {
+                        debug_assert_eq!(new_ty.group(), TypeGroup::Struct);
+
+                        // When the name is the same, we are dealing with the same struct,
+                        // but different internals
+                        let is_same_struct = old_ty.name() == new_ty.name();
 
+                        // If the same struct changed, there must also be a conversion
+                        let conversion = conversions.get(old_ty);
+
+                        if old_ty.is_stack_allocated() {
+                            if new_ty.is_stack_allocated() {
+                                // struct(value) -> struct(value)
                                 if is_same_struct {
-                                    // Only copy the `GcPtr`. Memory will already be mapped.
-                                    unsafe {
-                                        *field_dest = *field_src;
-                                    }
+                                    // Map in-memory struct to in-memory struct
+                                    map_fields(
+                                        new_allocations,
+                                        conversions,
+                                        &conversion.as_ref().unwrap().field_mapping,
+                                        unsafe { NonNull::new_unchecked(field_src) },
+                                        unsafe { NonNull::new_unchecked(field_dest) },
+                                    );
                                 } else {
-                                    let object = alloc_obj(new_ty.clone());
+                                    // Use previously zero-initialized memory
+                                }
+                            } else {
+                                // struct(value) -> struct(gc)
+                                let object = alloc_obj(new_ty.clone());
 
-                                    // We want to return a pointer to the `ObjectInfo`, to
-                                    // be used as handle.
-                                    let handle =
-                                        (object.as_ref().deref() as *const _ as RawGcPtr).into();
+                                // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+                                let handle =
+                                    (object.as_ref().deref() as *const _ as RawGcPtr).into();
 
-                                    // Zero-initialize heap-allocated object
+                                if is_same_struct {
+                                    // Map in-memory struct to heap-allocated struct
+                                    map_fields(
+                                        new_allocations,
+                                        conversions,
+                                        &conversion.as_ref().unwrap().field_mapping,
+                                        unsafe { NonNull::new_unchecked(field_src) },
+                                        unsafe { NonNull::new_unchecked(object.ptr) },
+                                    );
+                                } else {
+                                    // Zero initialize heap-allocated object
                                     unsafe {
-                                        std::ptr::write_bytes(object.ptr, 0, new_ty.layout().size())
+                                        std::ptr::write_bytes(
+                                            (*object).ptr,
+                                            0,
+                                            new_ty.layout().size(),
+                                        )
                                     };
+                                }
 
-                                    // Write handle to field
-                                    unsafe {
-                                        *field_dest = handle;
-                                    }
+                                // Write handle to field
+                                let field_handle = field_dest.cast::<GcPtr>();
+                                unsafe { *field_handle = handle };
+
+                                new_allocations.push(object);
+                            }
+                        } else if !new_ty.is_stack_allocated() {
+                            // struct(gc) -> struct(gc)
+                            let field_src = field_src.cast::<GcPtr>();
+                            let field_dest = field_dest.cast::<GcPtr>();
 
-                                    new_allocations.push(object);
+                            if is_same_struct {
+                                // Only copy the `GcPtr`. Memory will already be mapped.
+                                unsafe {
+                                    *field_dest = *field_src;
                                 }
                             } else {
-                                // struct(gc) -> struct(value)
-                                let field_handle = unsafe { *field_src.cast::<GcPtr>() };
+                                let object = alloc_obj(new_ty.clone());
 
-                                // Convert the handle to our internal representation
-                                // Safety: we already hold a write lock on `objects`, so
-                                // this is legal.
-                                let obj: *mut ObjectInfo<T> = field_handle.into();
-                                let obj = unsafe { &*obj };
+                                // We want to return a pointer to the `ObjectInfo`, to
+                                // be used as handle.
+                                let handle =
+                                    (object.as_ref().deref() as *const _ as RawGcPtr).into();
 
-                                if is_same_struct {
-                                    if obj.ty == *old_ty {
-                                        // The object still needs to be mapped
-                                        // Map heap-allocated struct to in-memory struct
-                                        map_fields(
-                                            gc,
-                                            new_allocations,
-                                            conversions,
-                                            &conversion.as_ref().unwrap().field_mapping,
-                                            unsafe { NonNull::new_unchecked(obj.ptr) },
-                                            unsafe { NonNull::new_unchecked(field_dest) },
-                                        );
-                                    } else {
-                                        // The object was already mapped
-                                        debug_assert!(obj.ty == *new_ty);
-
-                                        // Copy from heap-allocated struct to in-memory struct
-                                        unsafe {
-                                            std::ptr::copy_nonoverlapping(
-                                                obj.ptr,
-                                                field_dest,
-                                                obj.ty.layout().size(),
-                                            )
-                                        };
-                                    }
+                                // Zero-initialize heap-allocated object
+                                unsafe {
+                                    std::ptr::write_bytes(object.ptr, 0, new_ty.layout().size())
+                                };
+
+                                // Write handle to field
+                                unsafe {
+                                    *field_dest = handle;
+                                }
+
+                                new_allocations.push(object);
+                            }
+                        } else {
+                            // struct(gc) -> struct(value)
+                            let field_handle = unsafe { *field_src.cast::<GcPtr>() };
+
+                            // Convert the handle to our internal representation
+                            // Safety: we already hold a write lock on `objects`, so
+                            // this is legal.
+                            let obj: *mut ObjectInfo<T> = field_handle.into();
+                            let obj = unsafe { &*obj };
+
+                            if is_same_struct {
+                                if obj.ty == *old_ty {
+                                    // The object still needs to be mapped
+                                    // Map heap-allocated struct to in-memory struct
+                                    map_fields(
+                                        new_allocations,
+                                        conversions,
+                                        &conversion.as_ref().unwrap().field_mapping,
+                                        unsafe { NonNull::new_unchecked(obj.ptr) },
+                                        unsafe { NonNull::new_unchecked(field_dest) },
+                                    );
                                 } else {
-                                    // Use previously zero-initialized memory
+                                    // The object was already mapped
+                                    debug_assert!(obj.ty == *new_ty);
+
+                                    // Copy from heap-allocated struct to in-memory struct
+                                    unsafe {
+                                        std::ptr::copy_nonoverlapping(
+                                            obj.ptr,
+                                            field_dest,
+                                            obj.ty.layout().size(),
+                                        )
+                                    };
                                 }
+                            } else {
+                                // Use previously zero-initialized memory
                             }
-                        } else if !cast::try_cast_from_to(
-                            *old_ty.guid(),
-                            *new_ty.guid(),
-                            unsafe { NonNull::new_unchecked(field_src) },
-                            unsafe { NonNull::new_unchecked(field_dest) },
-                        ) {
-                            // Failed to cast. Use the previously zero-initialized value instead
+                        }
+                    } else if !cast::try_cast_from_to(
+                        *old_ty.guid(),
+                        *new_ty.guid(),
+                        unsafe { NonNull::new_unchecked(field_src) },
+                        unsafe { NonNull::new_unchecked(field_dest) },
+                    ) {
+                        // Failed to cast. Use the previously zero-initialized value instead
+                        if !new_ty.is_stack_allocated() {
+                            insert_zeroed_obj(new_allocations, new_ty, field_dest);
                         }
                     }
-                    mapping::Action::Copy { old_offset } => {
-                        let field_src = {
-                            let mut src = src.as_ptr() as usize;
-                            src += old_offset;
-                            src as *mut u8
-                        };
-
-                        unsafe {
-                            std::ptr::copy_nonoverlapping(
-                                field_src,
-                                field_dest,
-                                new_ty.layout().size(),
-                            )
-                        };
+                }
+                mapping::Action::Copy { old_offset } => {
+                    let field_src = {
+                        let mut src = src.as_ptr() as usize;
+                        src += old_offset;
+                        src as *mut u8
+                    };
+
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(field_src, field_dest, new_ty.layout().size())
+                    };
+                }
+                mapping::Action::Insert => {
+                    if !new_ty.is_stack_allocated() {
+                        insert_zeroed_obj(new_allocations, new_ty, field_dest);
+                    } else {
+                        // Use the previously zero-initialized value
                     }
-                    mapping::Action::Insert => {
-                        if !new_ty.is_stack_allocated() {
-                            let object = alloc_obj(new_ty.clone());
+                }
+            }
+        }
+    }
 
-                            // We want to return a pointer to the `ObjectInfo`, to be used as
-                            // handle.
-                            let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+    /// Allocates a zero-initialized object of type `ty` and writes its handle to `field_dest`.
+    fn insert_zeroed_obj<T>(
+        new_allocations: &mut Vec<Pin<Box<ObjectInfo<T>>>>,
+        ty: &T,
+        field_dest: *mut u8,
+    ) where
+        T: TypeMemory + TypeTrace + Clone,
+    {
+        let object = alloc_obj(ty.clone());
 
-                            // Zero-initialize heap-allocated object
-                            unsafe { std::ptr::write_bytes(object.ptr, 0, new_ty.layout().size()) };
+        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
 
-                            // Write handle to field
-                            let field_dest = field_dest.cast::<GcPtr>();
-                            unsafe {
-                                *field_dest = handle;
-                            }
+        // Zero-initialize heap-allocated object
+        unsafe { std::ptr::write_bytes(object.ptr, 0, ty.layout().size()) };
 
-                            new_allocations.push(object);
-                        } else {
-                            // Use the previously zero-initialized value
-                        }
-                    }
-                }
-            }
+        // Write handle to field
+        let field_dest = field_dest.cast::<GcPtr>();
+        unsafe {
+            *field_dest = handle;
         }
+
+        new_allocations.push(object);
     }
 }
 
 /// Coloring used in the Mark Sweep phase.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Color {
+pub(super) enum Color {
     /// A white object has not been seen yet by the mark phase
     White,
 
@@ -514,11 +630,14 @@ enum Color {
 /// meta information.
 #[derive(Debug)]
 #[repr(C)]
-struct ObjectInfo<T: TypeMemory + TypeTrace + Clone> {
+pub(super) struct ObjectInfo<T: TypeMemory + TypeTrace + Clone> {
     pub ptr: *mut u8,
     pub roots: u32,
     pub color: Color,
     pub ty: T,
+    /// The memory layout of the object. This can differ from the layout of `ty` for objects that
+    /// store a variable number of elements.
+    pub layout: Layout,
 }
 
 /// An `ObjectInfo` is thread-safe.
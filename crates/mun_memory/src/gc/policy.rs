@@ -0,0 +1,81 @@
+/// Determines when a garbage collector is due to collect memory, based on the allocations it
+/// performed since its last collection.
+///
+/// The policy is evaluated upon every allocation, but memory is only collected when the owner of
+/// the garbage collector reaches a point at which it is safe to do so. Objects that are only
+/// referenced from the stack are not rooted, so collecting memory during an allocation could
+/// reclaim objects that are still in use.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CollectionPolicy {
+    /// Memory is only collected when explicitly requested.
+    Manual,
+
+    /// A collection is due when the allocated memory exceeds the specified number of bytes.
+    AllocatedBytes(usize),
+
+    /// A collection is due when the allocated memory exceeds `factor` times the memory that
+    /// remained allocated after the last collection, or `min_bytes` if that is larger.
+    GrowthFactor { factor: f64, min_bytes: usize },
+
+    /// A collection is due after the specified number of allocations.
+    Allocations(usize),
+}
+
+impl Default for CollectionPolicy {
+    fn default() -> Self {
+        CollectionPolicy::Manual
+    }
+}
+
+/// Tracks the allocations since the last collection to evaluate a `CollectionPolicy`.
+#[derive(Debug, Default)]
+pub(super) struct Trigger {
+    policy: CollectionPolicy,
+    /// The number of allocations since the last collection
+    allocations: usize,
+    /// The number of bytes that remained allocated after the last collection
+    retained_memory: usize,
+    /// Whether a collection is due
+    due: bool,
+}
+
+impl Trigger {
+    /// Returns the policy of the trigger
+    pub fn policy(&self) -> CollectionPolicy {
+        self.policy
+    }
+
+    /// Replaces the policy of the trigger. The new policy is evaluated upon the next allocation.
+    pub fn set_policy(&mut self, policy: CollectionPolicy) {
+        self.policy = policy;
+        self.due = false;
+    }
+
+    /// Registers `count` allocations, after which `allocated_memory` bytes are allocated, and
+    /// evaluates the policy.
+    pub fn on_alloc(&mut self, count: usize, allocated_memory: usize) {
+        self.allocations += count;
+        self.due = match self.policy {
+            CollectionPolicy::Manual => false,
+            CollectionPolicy::AllocatedBytes(bytes) => allocated_memory > bytes,
+            CollectionPolicy::GrowthFactor { factor, min_bytes } => {
+                let threshold = (self.retained_memory as f64 * factor).max(min_bytes as f64);
+                allocated_memory as f64 > threshold
+            }
+            CollectionPolicy::Allocations(count) => self.allocations >= count,
+        };
+    }
+
+    /// Resets the trigger after a collection, after which `allocated_memory` bytes remained
+    /// allocated.
+    pub fn on_collect(&mut self, allocated_memory: usize) {
+        self.allocations = 0;
+        self.retained_memory = allocated_memory;
+        self.due = false;
+    }
+
+    /// Returns whether a collection is due
+    pub fn is_due(&self) -> bool {
+        self.due
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    gc::{mark_sweep::ObjectInfo, GcPtr, TypeTrace},
+    TypeMemory,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    hash::Hash,
+    pin::Pin,
+};
+
+/// Information about a single object in a `HeapSnapshot`.
+#[derive(Clone, Debug)]
+pub struct ObjectSnapshot<T> {
+    /// The handle of the object
+    pub handle: GcPtr,
+    /// The type of the object
+    pub ty: T,
+    /// The number of bytes allocated for the object
+    pub size: usize,
+    /// The number of times the object is rooted
+    pub roots: u32,
+    /// The handles of the objects that are referenced by the object
+    pub references: Vec<GcPtr>,
+}
+
+/// The number of objects of a single type in a `HeapSnapshot`, and the memory they occupy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeSummary<T> {
+    /// The type of the objects
+    pub ty: T,
+    /// The number of objects of the type
+    pub count: usize,
+    /// The total number of bytes allocated for objects of the type
+    pub size: usize,
+}
+
+/// A snapshot of all objects that were allocated by a garbage collector at a certain point in
+/// time, including the references between them.
+#[derive(Clone, Debug)]
+pub struct HeapSnapshot<T> {
+    /// All objects, ordered by their handle
+    objects: Vec<ObjectSnapshot<T>>,
+    /// Maps the handle of an object to its index in `objects`
+    indices: HashMap<GcPtr, usize>,
+}
+
+impl<T> HeapSnapshot<T> {
+    /// Constructs a snapshot from the specified `objects`.
+    pub fn new(mut objects: Vec<ObjectSnapshot<T>>) -> Self {
+        objects.sort_by_key(|object| object.handle);
+        let indices = objects
+            .iter()
+            .enumerate()
+            .map(|(idx, object)| (object.handle, idx))
+            .collect();
+        HeapSnapshot { objects, indices }
+    }
+
+    /// Constructs a snapshot from the objects stored by a garbage collector.
+    pub(super) fn from_objects(objects: &HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>) -> Self
+    where
+        T: TypeMemory + TypeTrace + Clone,
+    {
+        Self::new(
+            objects
+                .iter()
+                .map(|(handle, object)| ObjectSnapshot {
+                    handle: *handle,
+                    ty: object.ty.clone(),
+                    size: object.layout.size(),
+                    roots: object.roots,
+                    references: object.ty.trace(*handle).collect(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns all objects in the snapshot, ordered by their handle.
+    pub fn objects(&self) -> &[ObjectSnapshot<T>] {
+        &self.objects
+    }
+
+    /// Returns the object with the specified `handle`, if it exists.
+    pub fn get(&self, handle: GcPtr) -> Option<&ObjectSnapshot<T>> {
+        self.indices.get(&handle).map(|idx| &self.objects[*idx])
+    }
+
+    /// Returns the total number of bytes allocated for all objects in the snapshot.
+    pub fn total_size(&self) -> usize {
+        self.objects.iter().map(|object| object.size).sum()
+    }
+
+    /// Returns the shortest chain of references that keeps the object with the specified `handle`
+    /// alive. The chain starts at a rooted object and ends at `handle`. Returns `None` if the
+    /// object is not reachable from any rooted object, or if it is not part of the snapshot.
+    pub fn retaining_path(&self, handle: GcPtr) -> Option<Vec<GcPtr>> {
+        let target = *self.indices.get(&handle)?;
+
+        // Breadth-first search from all rooted objects, storing the object through which every
+        // object was first reached.
+        let mut parents: Vec<Option<usize>> = vec![None; self.objects.len()];
+        let mut visited = vec![false; self.objects.len()];
+        let mut queue = VecDeque::new();
+        for (idx, object) in self.objects.iter().enumerate() {
+            if object.roots > 0 {
+                visited[idx] = true;
+                queue.push_back(idx);
+            }
+        }
+
+        while let Some(idx) = queue.pop_front() {
+            if idx == target {
+                let mut path = vec![self.objects[idx].handle];
+                let mut current = idx;
+                while let Some(parent) = parents[current] {
+                    path.push(self.objects[parent].handle);
+                    current = parent;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for reference in self.objects[idx].references.iter() {
+                if let Some(&reference) = self.indices.get(reference) {
+                    if !visited[reference] {
+                        visited[reference] = true;
+                        parents[reference] = Some(idx);
+                        queue.push_back(reference);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns a snapshot in which the type of every object is replaced by the result of `f`.
+    pub fn map_types<U>(self, mut f: impl FnMut(&T) -> U) -> HeapSnapshot<U> {
+        HeapSnapshot {
+            objects: self
+                .objects
+                .into_iter()
+                .map(|object| ObjectSnapshot {
+                    handle: object.handle,
+                    ty: f(&object.ty),
+                    size: object.size,
+                    roots: object.roots,
+                    references: object.references,
+                })
+                .collect(),
+            indices: self.indices,
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> HeapSnapshot<T> {
+    /// Returns the number of objects per type and the memory they occupy, ordered from the type
+    /// that occupies the most memory to the type that occupies the least.
+    pub fn summary(&self) -> Vec<TypeSummary<T>> {
+        let mut summary: Vec<TypeSummary<T>> = Vec::new();
+        let mut indices = HashMap::new();
+        for object in self.objects.iter() {
+            let idx = *indices.entry(&object.ty).or_insert_with(|| {
+                summary.push(TypeSummary {
+                    ty: object.ty.clone(),
+                    count: 0,
+                    size: 0,
+                });
+                summary.len() - 1
+            });
+            summary[idx].count += 1;
+            summary[idx].size += object.size;
+        }
+
+        summary.sort_by(|a, b| b.size.cmp(&a.size).then(b.count.cmp(&a.count)));
+        summary
+    }
+}
+
+impl<T: Clone + Eq + Hash + fmt::Display> HeapSnapshot<T> {
+    /// Serializes the snapshot to JSON. Every object is identified by the address of its handle.
+    pub fn to_json(&self) -> String {
+        let handle = |handle: &GcPtr| handle.as_ptr() as usize;
+
+        let objects: Vec<serde_json::Value> = self
+            .objects
+            .iter()
+            .map(|object| {
+                serde_json::json!({
+                    "handle": handle(&object.handle),
+                    "type": object.ty.to_string(),
+                    "size": object.size,
+                    "roots": object.roots,
+                    "references": object.references.iter().map(handle).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+
+        let summary: Vec<serde_json::Value> = self
+            .summary()
+            .into_iter()
+            .map(|summary| {
+                serde_json::json!({
+                    "type": summary.ty.to_string(),
+                    "count": summary.count,
+                    "size": summary.size,
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "objects": objects,
+            "summary": summary,
+        })
+        .to_string()
+    }
+}
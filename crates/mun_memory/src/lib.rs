@@ -14,6 +14,8 @@ pub mod prelude {
 pub enum TypeGroup {
     Primitive,
     Struct,
+    Array,
+    Enum,
 }
 
 impl<'t> From<&'t abi::TypeInfoData> for TypeGroup {
@@ -21,6 +23,8 @@ impl<'t> From<&'t abi::TypeInfoData> for TypeGroup {
         match data {
             abi::TypeInfoData::Primitive => TypeGroup::Primitive,
             abi::TypeInfoData::Struct(_) => TypeGroup::Struct,
+            abi::TypeInfoData::Array(_) => TypeGroup::Array,
+            abi::TypeInfoData::Enum(_) => TypeGroup::Enum,
         }
     }
 }
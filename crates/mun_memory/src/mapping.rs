@@ -4,8 +4,11 @@ use crate::{
     TypeDesc, TypeFields, TypeGroup, TypeMemory,
 };
 use std::{
+    alloc::Layout,
     collections::{HashMap, HashSet},
     hash::Hash,
+    ptr::NonNull,
+    sync::Arc,
 };
 
 pub struct Mapping<T: Eq + Hash, U: TypeDesc + TypeMemory> {
@@ -17,6 +20,60 @@ pub struct Mapping<T: Eq + Hash, U: TypeDesc + TypeMemory> {
 pub struct Conversion<T: TypeDesc + TypeMemory> {
     pub field_mapping: Vec<FieldMapping<T>>,
     pub new_ty: T,
+    /// A user-defined migration that completes the conversion, after the fields have been mapped
+    pub migration: Option<Migration>,
+}
+
+/// A user-defined function that completes the conversion of an object, after its fields have been
+/// mapped. It receives a pointer to the old memory of the object and the handle of the object,
+/// which refers to its new memory. This allows it to carry over values that cannot be derived from
+/// the difference between the old and new type, e.g. when a field is split into two.
+///
+/// Migrations are run after the memory of all objects has been mapped and the garbage collector
+/// has been unlocked, so they are allowed to allocate. Migrations are only run for heap-allocated
+/// objects; not for `struct(value)`s that are stored inside other objects. The old memory is
+/// deallocated after the migration has run.
+#[derive(Clone)]
+pub struct Migration(Arc<dyn Fn(NonNull<u8>, GcPtr) + Send + Sync>);
+
+impl Migration {
+    /// Constructs a new migration from the function `migrate`, which receives a pointer to the old
+    /// memory of an object and the handle of the object.
+    pub fn new(migrate: impl Fn(NonNull<u8>, GcPtr) + Send + Sync + 'static) -> Self {
+        Migration(Arc::new(migrate))
+    }
+}
+
+/// A migration of an object of which the fields have been mapped, but that has yet to be run.
+pub(crate) struct PendingMigration {
+    migration: Migration,
+    old: NonNull<u8>,
+    old_layout: Layout,
+    new: GcPtr,
+}
+
+impl PendingMigration {
+    /// Constructs a migration that still needs to be run for the `old` memory - which will be
+    /// deallocated afterwards - of the object referred to by the handle `new`.
+    pub fn new(migration: Migration, old: NonNull<u8>, old_layout: Layout, new: GcPtr) -> Self {
+        PendingMigration {
+            migration,
+            old,
+            old_layout,
+            new,
+        }
+    }
+
+    /// Runs the migration and deallocates the old memory.
+    ///
+    /// # Safety
+    ///
+    /// The old memory must have been allocated with its layout, and the object must still be
+    /// alive.
+    pub unsafe fn run(self) {
+        (self.migration.0)(self.old, self.new);
+        std::alloc::dealloc(self.old.as_ptr(), self.old_layout);
+    }
 }
 
 /// Description of the mapping of a single field. When stored together with the new index, this
@@ -82,8 +139,8 @@ where
         // identical types.
         let mut new_candidates: HashSet<T> = new
             .iter()
-            // Filter non-struct types
-            .filter(|ty| ty.group() == TypeGroup::Struct)
+            // Filter types that cannot be allocated on the heap
+            .filter(|ty| ty.group() != TypeGroup::Primitive)
             // Filter inserted structs
             .filter(|ty| !insertions.contains(*ty))
             .cloned()
@@ -91,8 +148,8 @@ where
 
         let mut old_candidates: HashSet<T> = old
             .iter()
-            // Filter non-struct types
-            .filter(|ty| ty.group() == TypeGroup::Struct)
+            // Filter types that cannot be allocated on the heap
+            .filter(|ty| ty.group() != TypeGroup::Primitive)
             // Filter deleted structs
             .filter(|ty| !deletions.contains(*ty))
             // Filter edited types
@@ -261,6 +318,7 @@ pub unsafe fn field_mapping<T: Clone + TypeDesc + TypeFields<T> + TypeMemory>(
             })
             .collect(),
         new_ty,
+        migration: None,
     }
 }
 
@@ -1,6 +1,6 @@
 use super::util::{EventAggregator, HasTypeInfo, TypeInfo};
-use mun_memory::gc::{Event, GcRootPtr, GcRuntime, MarkSweep};
-use std::sync::Arc;
+use mun_memory::gc::{ArrayHeader, Event, GcRootPtr, GcRuntime, HasIndirectionPtr, MarkSweep};
+use std::{alloc::Layout, sync::Arc};
 
 #[test]
 fn alloc() {
@@ -14,6 +14,34 @@ fn alloc() {
     assert_eq!(events.next(), None);
 }
 
+#[test]
+fn alloc_array() {
+    let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default();
+    let element = Layout::new::<i64>();
+    let handle = runtime.alloc_array(i64::type_info(), element, 3);
+
+    let (layout, offset) = ArrayHeader::layout(element, 3);
+    assert_eq!(runtime.stats().allocated_memory, layout.size());
+
+    // The header should contain the length of the array, followed by zero-initialized elements
+    let header = unsafe { handle.deref::<ArrayHeader>() };
+    assert_eq!(unsafe { (*header).length }, 3);
+    let elements = unsafe { handle.deref::<u8>().add(offset).cast::<i64>() };
+    for idx in 0..3 {
+        assert_eq!(unsafe { *elements.add(idx) }, 0);
+    }
+
+    runtime.collect();
+    assert_eq!(runtime.stats().allocated_memory, 0);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(handle)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(handle)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
 #[test]
 fn collect_simple() {
     let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default();
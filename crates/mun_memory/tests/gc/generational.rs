@@ -0,0 +1,114 @@
+use super::util::{EventAggregator, HasTypeInfo, Trace, TypeInfo};
+use crate::{assert_variant, impl_struct_ty};
+use mun_memory::gc::{Event, GcPtr, GcRootPtr, GcRuntime, Generational, HasIndirectionPtr};
+use std::sync::Arc;
+
+struct Node {
+    next: GcPtr,
+}
+
+impl Trace for Node {
+    fn trace(&self, handles: &mut Vec<GcPtr>) {
+        handles.push(self.next)
+    }
+}
+
+impl_struct_ty!(Node);
+
+#[test]
+fn collect_minor_simple() {
+    let runtime = Generational::<&'static TypeInfo, EventAggregator<Event>>::default();
+    let handle = runtime.alloc(i64::type_info());
+    assert_eq!(runtime.young_len(), 1);
+
+    assert!(runtime.collect_minor());
+    assert_eq!(runtime.young_len(), 0);
+    assert_eq!(runtime.old_len(), 0);
+    assert_eq!(runtime.stats().allocated_memory, 0);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(handle)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(handle)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn collect_minor_promotes_survivors() {
+    let runtime = Arc::new(Generational::<&'static TypeInfo, EventAggregator<Event>>::default());
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+
+    // The rooted object survives and is promoted to the old generation
+    assert!(!runtime.collect_minor());
+    assert_eq!(runtime.young_len(), 0);
+    assert_eq!(runtime.old_len(), 1);
+
+    // Old objects are not collected by a minor collection, even if they are no longer rooted
+    let handle = rooted.unroot();
+    assert!(!runtime.collect_minor());
+    assert_eq!(runtime.old_len(), 1);
+
+    // A major collection collects the old generation
+    assert!(runtime.collect());
+    assert_eq!(runtime.old_len(), 0);
+    assert_eq!(runtime.stats().allocated_memory, 0);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(handle)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(handle)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn collect_minor_traces_old_references() {
+    let runtime = Arc::new(Generational::<&'static TypeInfo, EventAggregator<Event>>::default());
+    let mut node = GcRootPtr::new(&runtime, runtime.alloc(Node::type_info()));
+    unsafe {
+        (*node.deref_mut::<Node>()).next = node.handle();
+    }
+
+    // Promote the node to the old generation
+    runtime.collect_minor();
+    assert_eq!(runtime.old_len(), 1);
+
+    // Assign a young object to the old node, which is remembered through the write barrier
+    let mut young = runtime.alloc(Node::type_info());
+    let garbage = runtime.alloc(i64::type_info());
+    unsafe {
+        (*node.deref_mut::<Node>()).next = young;
+        (*young.deref_mut::<Node>()).next = young;
+    }
+    runtime.write_barrier(node.handle());
+
+    // Only the unreferenced young object should be collected
+    assert!(runtime.collect_minor());
+    assert_eq!(runtime.young_len(), 0);
+    assert_eq!(runtime.old_len(), 2);
+
+    // Drop the node, after which a major collection collects both nodes
+    let node = node.unroot();
+    assert!(runtime.collect());
+    assert_eq!(runtime.old_len(), 0);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(node)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Allocation(young)));
+    assert_eq!(events.next(), Some(Event::Allocation(garbage)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(garbage)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_variant!(events.next(), Some(Event::Deallocation(..))); // Don't care about the order
+    assert_variant!(events.next(), Some(Event::Deallocation(..)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
@@ -1,4 +1,7 @@
 mod alloc;
+mod generational;
+mod policy;
+mod snapshot;
 mod structs;
 #[macro_use]
 mod util;
@@ -0,0 +1,130 @@
+use super::util::{EventAggregator, HasTypeInfo, TypeInfo};
+use crate::assert_variant;
+use mun_memory::gc::{CollectionPolicy, Event, GcRootPtr, GcRuntime, Generational, MarkSweep};
+use std::sync::Arc;
+
+#[test]
+fn manual() {
+    let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default();
+    assert_eq!(runtime.policy(), CollectionPolicy::Manual);
+
+    runtime.alloc(i64::type_info());
+    assert!(!runtime.collect_if_due());
+    assert_eq!(runtime.stats().collections, 0);
+}
+
+#[test]
+fn allocated_bytes() {
+    let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default();
+    runtime.set_policy(CollectionPolicy::AllocatedBytes(16));
+
+    let first = runtime.alloc(i64::type_info());
+    let second = runtime.alloc(i64::type_info());
+    assert!(!runtime.collect_if_due());
+
+    // Exceeding the threshold should trigger a collection
+    let third = runtime.alloc(i64::type_info());
+    assert!(runtime.collect_if_due());
+
+    let stats = runtime.stats();
+    assert_eq!(stats.collections, 1);
+    assert_eq!(stats.allocated_memory, 0);
+    assert_eq!(stats.allocated_objects, 0);
+    assert_eq!(stats.freed_memory, 24);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(first)));
+    assert_eq!(events.next(), Some(Event::Allocation(second)));
+    assert_eq!(events.next(), Some(Event::Allocation(third)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_variant!(events.next(), Some(Event::Deallocation(..))); // Don't care about the order
+    assert_variant!(events.next(), Some(Event::Deallocation(..)));
+    assert_variant!(events.next(), Some(Event::Deallocation(..)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn growth_factor() {
+    let runtime = Arc::new(MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default());
+    runtime.set_policy(CollectionPolicy::GrowthFactor {
+        factor: 2.0,
+        min_bytes: 8,
+    });
+
+    let _rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    assert!(!runtime.collect_if_due());
+
+    // Exceeding the minimum threshold should trigger a collection
+    runtime.alloc(i64::type_info());
+    assert!(runtime.collect_if_due());
+    assert_eq!(runtime.stats().allocated_memory, 8);
+
+    // The threshold is now twice the memory that remained allocated
+    runtime.alloc(i64::type_info());
+    assert!(!runtime.collect_if_due());
+    runtime.alloc(i64::type_info());
+    assert!(runtime.collect_if_due());
+    assert_eq!(runtime.stats().collections, 2);
+    assert_eq!(runtime.stats().freed_memory, 24);
+}
+
+#[test]
+fn allocations() {
+    let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default();
+    runtime.set_policy(CollectionPolicy::Allocations(2));
+
+    runtime.alloc(i64::type_info());
+    assert!(!runtime.collect_if_due());
+
+    runtime.alloc(i64::type_info());
+    assert!(runtime.collect_if_due());
+    assert_eq!(runtime.stats().collections, 1);
+
+    // The number of allocations is reset by a collection
+    runtime.alloc(i64::type_info());
+    assert!(!runtime.collect_if_due());
+}
+
+#[test]
+fn generational_minor() {
+    let runtime = Generational::<&'static TypeInfo, EventAggregator<Event>>::default();
+    runtime.set_policy(CollectionPolicy::Allocations(2));
+
+    let first = runtime.alloc(i64::type_info());
+    let second = runtime.alloc(i64::type_info());
+
+    // Nothing is promoted, so only a minor collection is due
+    assert!(runtime.collect_if_due());
+    assert_eq!(runtime.stats().collections, 1);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(first)));
+    assert_eq!(events.next(), Some(Event::Allocation(second)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_variant!(events.next(), Some(Event::Deallocation(..))); // Don't care about the order
+    assert_variant!(events.next(), Some(Event::Deallocation(..)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn generational_major() {
+    let runtime = Arc::new(Generational::<&'static TypeInfo, EventAggregator<Event>>::default());
+    runtime.set_policy(CollectionPolicy::Allocations(1));
+
+    // Promoting the rooted object makes a major collection due
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    assert!(!runtime.collect_if_due());
+    assert_eq!(runtime.stats().collections, 2);
+    assert_eq!(runtime.old_len(), 1);
+
+    let handle = rooted.unroot();
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(handle)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
@@ -0,0 +1,140 @@
+use super::util::{EventAggregator, HasTypeInfo, Trace, TypeInfo};
+use crate::impl_struct_ty;
+use mun_memory::gc::{
+    Event, GcPtr, GcRootPtr, GcRuntime, HasIndirectionPtr, HeapSnapshot, MarkSweep, RawGcPtr,
+    TypeSummary,
+};
+use std::sync::Arc;
+
+struct Node {
+    next: GcPtr,
+}
+
+impl Trace for Node {
+    fn trace(&self, handles: &mut Vec<GcPtr>) {
+        handles.push(self.next)
+    }
+}
+
+impl_struct_ty!(Node);
+
+/// Replaces the types of a snapshot by their names
+fn with_names(snapshot: HeapSnapshot<&'static TypeInfo>) -> HeapSnapshot<&'static str> {
+    snapshot.map_types(|ty| {
+        if std::ptr::eq(*ty, Node::type_info()) {
+            "Node"
+        } else {
+            "i64"
+        }
+    })
+}
+
+#[test]
+fn snapshot() {
+    let runtime = Arc::new(MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default());
+
+    // Construct the chain `first -> second -> value`, of which only `first` is rooted
+    let mut first = GcRootPtr::new(&runtime, runtime.alloc(Node::type_info()));
+    let mut second = runtime.alloc(Node::type_info());
+    let value = runtime.alloc(i64::type_info());
+    let garbage = [
+        runtime.alloc(i64::type_info()),
+        runtime.alloc(i64::type_info()),
+    ];
+    unsafe {
+        (*first.deref_mut::<Node>()).next = second;
+        (*second.deref_mut::<Node>()).next = value;
+    }
+
+    let snapshot = with_names(runtime.snapshot());
+    assert_eq!(snapshot.objects().len(), 5);
+    assert_eq!(snapshot.total_size(), runtime.stats().allocated_memory);
+
+    let first_object = snapshot.get(first.handle()).unwrap();
+    assert_eq!(first_object.ty, "Node");
+    assert_eq!(first_object.roots, 1);
+    assert_eq!(first_object.references, vec![second]);
+
+    let value_object = snapshot.get(value).unwrap();
+    assert_eq!(value_object.ty, "i64");
+    assert_eq!(value_object.size, 8);
+    assert_eq!(value_object.roots, 0);
+    assert!(value_object.references.is_empty());
+
+    assert_eq!(
+        snapshot.summary(),
+        vec![
+            TypeSummary {
+                ty: "i64",
+                count: 3,
+                size: 24,
+            },
+            TypeSummary {
+                ty: "Node",
+                count: 2,
+                size: 16,
+            },
+        ]
+    );
+    assert!(garbage.iter().all(|handle| snapshot.get(*handle).is_some()));
+}
+
+#[test]
+fn retaining_path() {
+    let runtime = Arc::new(MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default());
+
+    let mut first = GcRootPtr::new(&runtime, runtime.alloc(Node::type_info()));
+    let mut second = runtime.alloc(Node::type_info());
+    let value = runtime.alloc(i64::type_info());
+    let garbage = runtime.alloc(i64::type_info());
+    unsafe {
+        (*first.deref_mut::<Node>()).next = second;
+        (*second.deref_mut::<Node>()).next = value;
+    }
+
+    let snapshot = runtime.snapshot();
+    assert_eq!(
+        snapshot.retaining_path(value),
+        Some(vec![first.handle(), second, value])
+    );
+    assert_eq!(
+        snapshot.retaining_path(first.handle()),
+        Some(vec![first.handle()])
+    );
+
+    // Objects that are not reachable from a root are not retained
+    assert_eq!(snapshot.retaining_path(garbage), None);
+
+    // Objects that are not part of the snapshot are not retained
+    runtime.collect();
+    assert_eq!(runtime.snapshot().retaining_path(garbage), None);
+}
+
+#[test]
+fn to_json() {
+    let runtime = Arc::new(MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default());
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&with_names(runtime.snapshot()).to_json()).unwrap();
+
+    let address: RawGcPtr = rooted.handle().into();
+    let address = address as usize;
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "objects": [{
+                "handle": address,
+                "type": "i64",
+                "size": 8,
+                "roots": 1,
+                "references": [],
+            }],
+            "summary": [{
+                "type": "i64",
+                "count": 1,
+                "size": 8,
+            }],
+        })
+    );
+}
@@ -1,10 +1,21 @@
-pub use manifest::{Manifest, ManifestMetadata, PackageId};
+pub use lockfile::{LockedPackage, Lockfile, PackageSource};
+pub use manifest::{
+    Dependency, DependencySource, Manifest, ManifestMetadata, PackageId, WorkspaceManifest,
+};
 pub use package::Package;
+pub use package_graph::{PackageGraph, ResolveOptions};
 pub use project_manifest::ProjectManifest;
+pub use registry::Registry;
+pub use workspace::Workspace;
 
+mod lockfile;
 mod manifest;
 mod package;
+mod package_graph;
 mod project_manifest;
+mod registry;
+mod workspace;
 
 pub const MANIFEST_FILENAME: &str = "mun.toml";
 pub const LOCKFILE_NAME: &str = ".munlock";
+pub const DEPENDENCY_LOCKFILE_NAME: &str = "mun.lock";
@@ -0,0 +1,129 @@
+use semver::Version;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// The header that is written at the top of every lockfile
+const HEADER: &str =
+    "# This file is automatically generated by Mun.\n# It is not intended for manual editing.\n";
+
+/// Records the exact versions of all packages that were selected when resolving the dependencies
+/// of a package. When a lockfile is present, the same versions are selected again as long as they
+/// still satisfy the requirements, which makes builds reproducible.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Lockfile {
+    #[serde(default, rename = "package")]
+    packages: Vec<LockedPackage>,
+}
+
+/// A single package in a [`Lockfile`]
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: Version,
+    pub source: PackageSource,
+    /// The names of the packages that this package depends on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+}
+
+/// The location from which a package was loaded
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PackageSource {
+    /// The package was loaded from a directory
+    Path,
+
+    /// The package was loaded from the package registry
+    Registry,
+}
+
+impl Lockfile {
+    /// Constructs a lockfile from the specified packages. The packages are sorted by name.
+    pub fn new(mut packages: Vec<LockedPackage>) -> Self {
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+        for package in packages.iter_mut() {
+            package.dependencies.sort();
+        }
+        Self { packages }
+    }
+
+    /// Try to read a lockfile from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Lockfile, anyhow::Error> {
+        let file_contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("could not read lockfile: {}", e))?;
+        file_contents.parse()
+    }
+
+    /// Writes the lockfile to a file
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), anyhow::Error> {
+        std::fs::write(path, self.to_string())
+            .map_err(|e| anyhow::anyhow!("could not write lockfile: {}", e))
+    }
+
+    /// Returns all locked packages, sorted by name
+    pub fn packages(&self) -> &[LockedPackage] {
+        &self.packages
+    }
+
+    /// Returns the locked version of the package with the specified `name` from the registry
+    pub fn registry_version(&self, name: &str) -> Option<&Version> {
+        self.packages
+            .iter()
+            .find(|package| package.name == name && package.source == PackageSource::Registry)
+            .map(|package| &package.version)
+    }
+}
+
+impl fmt::Display for Lockfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let contents = ::toml::to_string(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}\n{}", HEADER, contents)
+    }
+}
+
+impl std::str::FromStr for Lockfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ::toml::from_str(s).map_err(|e| anyhow::anyhow!("could not parse lockfile: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LockedPackage, Lockfile, PackageSource};
+    use semver::Version;
+
+    #[test]
+    fn roundtrip() {
+        let lockfile = Lockfile::new(vec![
+            LockedPackage {
+                name: "game".to_owned(),
+                version: Version::new(0, 1, 0),
+                source: PackageSource::Path,
+                dependencies: vec!["utils".to_owned(), "math".to_owned()],
+            },
+            LockedPackage {
+                name: "math".to_owned(),
+                version: Version::new(1, 2, 3),
+                source: PackageSource::Registry,
+                dependencies: vec![],
+            },
+        ]);
+
+        let text = lockfile.to_string();
+        assert!(text.starts_with("# This file is automatically generated by Mun."));
+        assert_eq!(text.parse::<Lockfile>().unwrap(), lockfile);
+
+        assert_eq!(
+            lockfile.packages()[0].dependencies,
+            vec!["math".to_owned(), "utils".to_owned()]
+        );
+        assert_eq!(
+            lockfile.registry_version("math"),
+            Some(&Version::new(1, 2, 3))
+        );
+        assert_eq!(lockfile.registry_version("game"), None);
+    }
+}
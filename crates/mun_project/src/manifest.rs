@@ -1,5 +1,5 @@
 use std::fmt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 mod toml;
@@ -9,6 +9,7 @@ mod toml;
 pub struct Manifest {
     package_id: PackageId,
     metadata: ManifestMetadata,
+    dependencies: Vec<Dependency>,
 }
 
 /// General metadata for a package.
@@ -17,6 +18,31 @@ pub struct ManifestMetadata {
     pub authors: Vec<String>,
 }
 
+/// A dependency of a package on another package.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Dependency {
+    name: String,
+    version_req: semver::VersionReq,
+    source: DependencySource,
+}
+
+/// The location from which a dependency is loaded
+#[derive(PartialEq, Clone, Debug)]
+pub enum DependencySource {
+    /// The directory that contains the dependency, relative to the package that depends on it
+    Path(PathBuf),
+
+    /// The package registry
+    Registry,
+}
+
+/// The `workspace` section of a manifest, which lists the packages that are built together.
+#[derive(PartialEq, Clone, Debug)]
+pub struct WorkspaceManifest {
+    members: Vec<PathBuf>,
+    is_virtual: bool,
+}
+
 /// Unique identifier of a package and version
 #[derive(PartialEq, Clone, Debug)]
 pub struct PackageId {
@@ -52,6 +78,57 @@ impl Manifest {
     pub fn metadata(&self) -> &ManifestMetadata {
         &self.metadata
     }
+
+    /// Returns the dependencies of the package, sorted by name
+    pub fn dependencies(&self) -> &[Dependency] {
+        &self.dependencies
+    }
+}
+
+impl Dependency {
+    /// Returns the name of the dependency, by which it is referred to from source code
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the versions of the dependency that are compatible
+    pub fn version_req(&self) -> &semver::VersionReq {
+        &self.version_req
+    }
+
+    /// Returns the location from which the dependency is loaded
+    pub fn source(&self) -> &DependencySource {
+        &self.source
+    }
+}
+
+impl WorkspaceManifest {
+    /// Try to read the workspace section of a manifest from a file. Returns `None` if the manifest
+    /// does not define a workspace.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Option<WorkspaceManifest>, anyhow::Error> {
+        // Load the contents of the file
+        let file_contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("could not read manifest file: {}", e))?;
+        Self::parse(&file_contents)
+    }
+
+    /// Parses the workspace section of a manifest. Returns `None` if the manifest does not define
+    /// a workspace.
+    fn parse(s: &str) -> Result<Option<WorkspaceManifest>, anyhow::Error> {
+        let manifest = ::toml::from_str::<toml::TomlWorkspaceManifest>(s)
+            .map_err(|e| anyhow::anyhow!("could not parse manifest: {}", e))?;
+        manifest.into_real_manifest()
+    }
+
+    /// Returns the directories of the member packages, relative to the manifest
+    pub fn members(&self) -> &[PathBuf] {
+        &self.members
+    }
+
+    /// Returns true if the manifest only defines a workspace, and not a package of its own
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
 }
 
 impl PackageId {
@@ -85,7 +162,9 @@ impl std::str::FromStr for Manifest {
 
 #[cfg(test)]
 mod tests {
-    use crate::Manifest;
+    use crate::{DependencySource, Manifest, WorkspaceManifest};
+    use semver::VersionReq;
+    use std::path::PathBuf;
     use std::str::FromStr;
 
     #[test]
@@ -107,5 +186,119 @@ mod tests {
         );
         assert_eq!(manifest.metadata().authors, vec!["Mun Team"]);
         assert_eq!(format!("{}", manifest.package_id()), "test v0.2.0");
+        assert!(manifest.dependencies().is_empty());
+    }
+
+    #[test]
+    fn parse_dependencies() {
+        let manifest = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [dependencies]
+        math = { path = "../math" }
+        physics = "0.3"
+        utils = { path = "libs/utils", version = "1.2" }
+        "#,
+        )
+        .unwrap();
+
+        let dependencies = manifest.dependencies();
+        assert_eq!(dependencies.len(), 3);
+        assert_eq!(dependencies[0].name(), "math");
+        assert_eq!(
+            dependencies[0].source(),
+            &DependencySource::Path(PathBuf::from("../math"))
+        );
+        assert_eq!(dependencies[0].version_req(), &VersionReq::any());
+        assert_eq!(dependencies[1].name(), "physics");
+        assert_eq!(dependencies[1].source(), &DependencySource::Registry);
+        assert_eq!(
+            dependencies[1].version_req(),
+            &VersionReq::parse("0.3").unwrap()
+        );
+        assert_eq!(dependencies[2].name(), "utils");
+        assert_eq!(
+            dependencies[2].source(),
+            &DependencySource::Path(PathBuf::from("libs/utils"))
+        );
+        assert_eq!(
+            dependencies[2].version_req(),
+            &VersionReq::parse("1.2").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_dependency_without_source() {
+        let result = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [dependencies]
+        math = {}
+        "#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_invalid_dependency_name() {
+        let result = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [dependencies]
+        math-utils = { path = "../math" }
+        "#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_workspace() {
+        let workspace = WorkspaceManifest::parse(
+            r#"
+        [workspace]
+        members = ["game", "libs/math"]
+        "#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            workspace.members(),
+            &[PathBuf::from("game"), PathBuf::from("libs/math")]
+        );
+        assert!(workspace.is_virtual());
+
+        let workspace = WorkspaceManifest::parse(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [workspace]
+        members = ["math"]
+        "#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(workspace.members(), &[PathBuf::from("math")]);
+        assert!(!workspace.is_virtual());
+
+        let workspace = WorkspaceManifest::parse(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(workspace, None);
     }
 }
@@ -1,11 +1,16 @@
-use super::{Manifest, ManifestMetadata, PackageId};
+use super::{
+    Dependency, DependencySource, Manifest, ManifestMetadata, PackageId, WorkspaceManifest,
+};
 use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 /// A manifest as specified in a mun.toml file.
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct TomlManifest {
     package: TomlProject,
+    dependencies: Option<BTreeMap<String, TomlDependency>>,
 }
 
 /// Represents the `package` section of a mun.toml file.
@@ -16,6 +21,38 @@ pub struct TomlProject {
     authors: Option<Vec<String>>,
 }
 
+/// Represents a single entry of the `dependencies` section of a mun.toml file.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum TomlDependency {
+    /// A dependency from the registry with a version requirement, e.g. `math = "0.1"`
+    Simple(semver::VersionReq),
+
+    /// A dependency with additional information, e.g. `math = { path = "../math" }`
+    Detailed(DetailedTomlDependency),
+}
+
+/// Represents a single entry of the `dependencies` section of a mun.toml file that is specified
+/// as a table.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DetailedTomlDependency {
+    path: Option<PathBuf>,
+    version: Option<semver::VersionReq>,
+}
+
+/// A manifest as specified in a mun.toml file, of which only the `workspace` section is read.
+#[derive(Debug, Deserialize)]
+pub struct TomlWorkspaceManifest {
+    package: Option<serde::de::IgnoredAny>,
+    workspace: Option<TomlWorkspace>,
+}
+
+/// Represents the `workspace` section of a mun.toml file.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TomlWorkspace {
+    members: Vec<PathBuf>,
+}
+
 impl TomlManifest {
     /// Convert this toml manifest into a "real" manifest.
     pub fn into_real_manifest(self) -> Result<Manifest, anyhow::Error> {
@@ -24,6 +61,52 @@ impl TomlManifest {
             anyhow::bail!("package name cannot be an empty string");
         }
 
+        let dependencies = self
+            .dependencies
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, dependency)| {
+                let name = name.trim();
+                if name.is_empty() {
+                    anyhow::bail!("dependency name cannot be an empty string");
+                }
+                // The name of a dependency is used to refer to it from source code
+                if !is_valid_dependency_name(name) {
+                    anyhow::bail!(
+                        "dependency name `{}` must start with a letter and may only contain letters, digits and underscores",
+                        name
+                    );
+                }
+
+                let (version_req, source) = match dependency {
+                    TomlDependency::Simple(version_req) => {
+                        (version_req, DependencySource::Registry)
+                    }
+                    TomlDependency::Detailed(DetailedTomlDependency {
+                        path: Some(path),
+                        version,
+                    }) => (
+                        version.unwrap_or_else(semver::VersionReq::any),
+                        DependencySource::Path(path),
+                    ),
+                    TomlDependency::Detailed(DetailedTomlDependency {
+                        path: None,
+                        version: Some(version_req),
+                    }) => (version_req, DependencySource::Registry),
+                    TomlDependency::Detailed(DetailedTomlDependency {
+                        path: None,
+                        version: None,
+                    }) => anyhow::bail!("dependency `{}` must specify a version or a path", name),
+                };
+
+                Ok(Dependency {
+                    name: name.to_owned(),
+                    version_req,
+                    source,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
         Ok(Manifest {
             package_id: PackageId {
                 name: name.to_owned(),
@@ -32,6 +115,42 @@ impl TomlManifest {
             metadata: ManifestMetadata {
                 authors: self.package.authors.unwrap_or_default(),
             },
+            dependencies,
         })
     }
 }
+
+/// Returns true if `name` can be used to refer to a dependency from source code
+fn is_valid_dependency_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first_char) => {
+            first_char.is_alphabetic() && chars.all(|c| c.is_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+impl TomlWorkspaceManifest {
+    /// Convert the workspace section of this toml manifest into a "real" workspace manifest.
+    /// Returns `None` if the manifest does not define a workspace.
+    pub fn into_real_manifest(self) -> Result<Option<WorkspaceManifest>, anyhow::Error> {
+        let workspace = match self.workspace {
+            Some(workspace) => workspace,
+            None => return Ok(None),
+        };
+
+        if workspace
+            .members
+            .iter()
+            .any(|member| member.as_os_str().is_empty())
+        {
+            anyhow::bail!("workspace member path cannot be an empty string");
+        }
+
+        Ok(Some(WorkspaceManifest {
+            members: workspace.members,
+            is_virtual: self.package.is_none(),
+        }))
+    }
+}
@@ -0,0 +1,651 @@
+use crate::{
+    DependencySource, LockedPackage, Lockfile, Package, PackageSource, Registry, Workspace,
+    MANIFEST_FILENAME,
+};
+use anyhow::bail;
+use semver::VersionReq;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A set of packages together with all the packages they directly or indirectly depend on.
+///
+/// The packages are ordered such that every package comes after all of its dependencies. Packages
+/// are referred to by their index in this order.
+#[derive(Clone, Debug)]
+pub struct PackageGraph {
+    packages: Vec<Package>,
+    sources: Vec<PackageSource>,
+    dependencies: Vec<Vec<usize>>,
+    members: Vec<usize>,
+}
+
+/// Options that influence how the dependencies of a package are resolved
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResolveOptions<'a> {
+    /// The registry from which dependencies that are not specified by path are loaded
+    pub registry: Option<&'a Registry>,
+
+    /// The lockfile with the versions of registry packages that are preferred
+    pub lockfile: Option<&'a Lockfile>,
+}
+
+impl PackageGraph {
+    /// Loads the package or workspace at the specified manifest path and resolves its
+    /// dependencies. Registry dependencies are loaded from `registry`.
+    pub fn from_manifest_path<P: AsRef<Path>>(
+        path: P,
+        registry: Option<&Registry>,
+    ) -> anyhow::Result<Self> {
+        Self::from_workspace(&Workspace::from_manifest_path(path)?, registry)
+    }
+
+    /// Resolves the dependencies of all members of the specified workspace. Registry dependencies
+    /// are loaded from `registry`.
+    ///
+    /// The versions that are recorded in the lockfile of the workspace are preferred. If the
+    /// members have dependencies, the lockfile is updated with the resolved versions.
+    pub fn from_workspace(
+        workspace: &Workspace,
+        registry: Option<&Registry>,
+    ) -> anyhow::Result<Self> {
+        let lockfile_path = workspace.lockfile_path();
+        let lockfile = if lockfile_path.is_file() {
+            Some(Lockfile::from_file(&lockfile_path)?)
+        } else {
+            None
+        };
+
+        let graph = Self::resolve_members(
+            workspace.members().to_vec(),
+            ResolveOptions {
+                registry,
+                lockfile: lockfile.as_ref(),
+            },
+        )?;
+
+        // Record the resolved versions so subsequent builds select the same versions
+        let resolved = graph.lockfile();
+        if (graph.packages.len() > graph.members.len() || lockfile.is_some())
+            && lockfile.as_ref() != Some(&resolved)
+        {
+            resolved.write(&lockfile_path)?;
+        }
+
+        Ok(graph)
+    }
+
+    /// Resolves the dependencies of the `root` package, by loading the manifests of all packages
+    /// it directly or indirectly depends on.
+    pub fn resolve(root: Package, options: ResolveOptions) -> anyhow::Result<Self> {
+        Self::resolve_members(vec![root], options)
+    }
+
+    /// Resolves the dependencies of all `members`, by loading the manifests of all packages they
+    /// directly or indirectly depend on. Members may depend on each other.
+    ///
+    /// Every package can only be selected in a single version. Resolving fails if a dependency
+    /// cannot be found, if no version of a dependency satisfies the requirements of all packages
+    /// that depend on it, if packages depend on each other cyclically, or if multiple different
+    /// packages have the same name.
+    pub fn resolve_members(members: Vec<Package>, options: ResolveOptions) -> anyhow::Result<Self> {
+        // A registry package is selected in the highest version that satisfies the requirements
+        // that are known at that point. If a later requirement conflicts with the selected
+        // version, resolving starts over with that requirement added to the package's
+        // requirements.
+        let mut requirements = HashMap::new();
+        loop {
+            let mut resolver = Resolver {
+                options,
+                packages: Vec::new(),
+                sources: Vec::new(),
+                dependencies: Vec::new(),
+                resolved: HashMap::new(),
+                stack: Vec::new(),
+                requirements,
+                conflict: None,
+            };
+            let result = members
+                .iter()
+                .cloned()
+                .map(|member| resolver.visit(member, PackageSource::Path))
+                .collect::<anyhow::Result<_>>();
+            match (result, resolver.conflict) {
+                (Ok(member_indices), _) => {
+                    return Ok(Self {
+                        packages: resolver.packages,
+                        sources: resolver.sources,
+                        dependencies: resolver.dependencies,
+                        members: member_indices,
+                    })
+                }
+                (Err(_), Some((name, version_req))) => {
+                    requirements = resolver.requirements;
+                    requirements
+                        .entry(name)
+                        .or_insert_with(Vec::new)
+                        .push(version_req);
+                }
+                (Err(e), None) => return Err(e),
+            }
+        }
+    }
+
+    /// Returns the indices of the packages for which the dependencies were resolved, in the order
+    /// in which they were specified
+    pub fn members(&self) -> &[usize] {
+        &self.members
+    }
+
+    /// Returns all packages, ordered such that every package comes after its dependencies
+    pub fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+
+    /// Returns the location from which the package at `index` was loaded
+    pub fn source(&self, index: usize) -> PackageSource {
+        self.sources[index]
+    }
+
+    /// Returns the indices of the packages that the package at `index` directly depends on
+    pub fn dependencies(&self, index: usize) -> &[usize] {
+        &self.dependencies[index]
+    }
+
+    /// Returns a lockfile that records the versions of all packages in the graph
+    pub fn lockfile(&self) -> Lockfile {
+        Lockfile::new(
+            self.packages
+                .iter()
+                .enumerate()
+                .map(|(idx, package)| LockedPackage {
+                    name: package.name().to_owned(),
+                    version: package.version().clone(),
+                    source: self.sources[idx],
+                    dependencies: self.dependencies[idx]
+                        .iter()
+                        .map(|&dependency| self.packages[dependency].name().to_owned())
+                        .collect(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Keeps track of the state while resolving the dependencies of a package
+struct Resolver<'a> {
+    options: ResolveOptions<'a>,
+    packages: Vec<Package>,
+    sources: Vec<PackageSource>,
+    dependencies: Vec<Vec<usize>>,
+    /// Maps the canonical manifest path of every resolved package to its index
+    resolved: HashMap<PathBuf, usize>,
+    /// The canonical manifest paths and names of the packages that are currently being resolved
+    stack: Vec<(PathBuf, String)>,
+    /// The requirements that registry packages must satisfy in addition to the requirement of the
+    /// package that depends on them, which were collected from earlier conflicts
+    requirements: HashMap<String, Vec<VersionReq>>,
+    /// The name of the registry package and the requirement that conflicted with the version
+    /// that was selected for it
+    conflict: Option<(String, VersionReq)>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Resolves the dependencies of `package` and adds it to the graph, returning its index
+    fn visit(&mut self, package: Package, source: PackageSource) -> anyhow::Result<usize> {
+        let manifest_path = canonical_path(package.manifest_path());
+        if let Some(&index) = self.resolved.get(&manifest_path) {
+            return Ok(index);
+        }
+
+        if let Some(start) = self
+            .stack
+            .iter()
+            .position(|(path, _)| *path == manifest_path)
+        {
+            let cycle = self.stack[start..]
+                .iter()
+                .map(|(_, name)| name.as_str())
+                .chain(std::iter::once(package.name()))
+                .collect::<Vec<_>>();
+            bail!("cyclic dependency between packages: {}", cycle.join(" -> "));
+        }
+
+        self.stack
+            .push((manifest_path.clone(), package.name().to_owned()));
+
+        let mut dependencies = Vec::with_capacity(package.manifest().dependencies().len());
+        for dependency in package.manifest().dependencies() {
+            let (dependency_package, dependency_source) = match dependency.source() {
+                DependencySource::Path(path) => {
+                    let dependency_manifest_path =
+                        package.root().join(path).join(MANIFEST_FILENAME);
+                    let dependency_package = Package::from_file(&dependency_manifest_path)
+                        .map_err(|e| {
+                            anyhow::anyhow!(
+                                "could not load dependency `{}` of `{}` from '{}': {}",
+                                dependency.name(),
+                                package.name(),
+                                dependency_manifest_path.display(),
+                                e
+                            )
+                        })?;
+                    (dependency_package, PackageSource::Path)
+                }
+                DependencySource::Registry => {
+                    // A package can only be selected once, so reuse it if it is compatible
+                    if let Some(index) = self
+                        .packages
+                        .iter()
+                        .position(|p| p.name() == dependency.name())
+                    {
+                        let selected = &self.packages[index];
+                        if !dependency.version_req().matches(selected.version()) {
+                            // Another version of a registry package may satisfy all requirements
+                            if self.sources[index] == PackageSource::Registry {
+                                self.conflict = Some((
+                                    dependency.name().to_owned(),
+                                    dependency.version_req().clone(),
+                                ));
+                            }
+                            bail!(
+                                "dependency `{}` of `{}` requires version `{}`, but v{} was already selected",
+                                dependency.name(),
+                                package.name(),
+                                dependency.version_req(),
+                                selected.version()
+                            );
+                        }
+                        dependencies.push(index);
+                        continue;
+                    }
+
+                    let registry = self.options.registry.ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "could not resolve dependency `{}` of `{}`: no package registry was specified",
+                            dependency.name(),
+                            package.name()
+                        )
+                    })?;
+                    let locked_version = self
+                        .options
+                        .lockfile
+                        .and_then(|lockfile| lockfile.registry_version(dependency.name()));
+                    let mut version_reqs = vec![dependency.version_req()];
+                    if let Some(requirements) = self.requirements.get(dependency.name()) {
+                        version_reqs.extend(
+                            requirements
+                                .iter()
+                                .filter(|version_req| *version_req != dependency.version_req()),
+                        );
+                    }
+                    let dependency_package = registry
+                        .find(dependency.name(), &version_reqs, locked_version)
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "could not find a version of `{}` that matches `{}` in the registry '{}'",
+                                dependency.name(),
+                                version_reqs
+                                    .iter()
+                                    .map(|version_req| version_req.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                                registry.path().display()
+                            )
+                        })?;
+                    (dependency_package.clone(), PackageSource::Registry)
+                }
+            };
+
+            if dependency_package.name() != dependency.name() {
+                bail!(
+                    "dependency `{}` of `{}` refers to a package named `{}`",
+                    dependency.name(),
+                    package.name(),
+                    dependency_package.name()
+                );
+            }
+
+            if !dependency
+                .version_req()
+                .matches(dependency_package.version())
+            {
+                bail!(
+                    "dependency `{}` of `{}` requires version `{}`, but found v{}",
+                    dependency.name(),
+                    package.name(),
+                    dependency.version_req(),
+                    dependency_package.version()
+                );
+            }
+
+            dependencies.push(self.visit(dependency_package, dependency_source)?);
+        }
+
+        self.stack.pop();
+
+        // Packages are referred to by name from source code, so their names must be unique
+        if let Some(other) = self.packages.iter().find(|p| p.name() == package.name()) {
+            bail!(
+                "multiple packages are named `{}`: '{}' and '{}'",
+                package.name(),
+                other.manifest_path().display(),
+                package.manifest_path().display()
+            );
+        }
+
+        let index = self.packages.len();
+        self.packages.push(package);
+        self.sources.push(source);
+        self.dependencies.push(dependencies);
+        self.resolved.insert(manifest_path, index);
+        Ok(index)
+    }
+}
+
+/// Returns the canonical form of `path`, or `path` itself if it cannot be canonicalized
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PackageGraph, ResolveOptions};
+    use crate::{Lockfile, Package, PackageSource, Registry};
+    use std::fs;
+    use std::path::Path;
+
+    /// Writes a package to `dir`. Every dependency is specified as its name and the toml value of
+    /// its entry in the `dependencies` section.
+    fn write_package(dir: &Path, name: &str, version: &str, dependencies: &[(&str, &str)]) {
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let mut manifest = format!(
+            "[package]\nname=\"{}\"\nversion=\"{}\"\n\n[dependencies]\n",
+            name, version
+        );
+        for (name, dependency) in dependencies {
+            manifest.push_str(&format!("{} = {}\n", name, dependency));
+        }
+        fs::write(dir.join("mun.toml"), manifest).unwrap();
+    }
+
+    /// Resolves the package in `dir` with the specified registry and lockfile
+    fn resolve_package(
+        dir: &Path,
+        registry: Option<&Registry>,
+        lockfile: Option<&Lockfile>,
+    ) -> anyhow::Result<PackageGraph> {
+        let root = Package::from_file(dir.join("mun.toml")).unwrap();
+        PackageGraph::resolve(root, ResolveOptions { registry, lockfile })
+    }
+
+    #[test]
+    fn resolve() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[
+                ("math", "{ path = \"../math\" }"),
+                ("utils", "{ path = \"../utils\" }"),
+            ],
+        );
+        write_package(
+            &dir.path().join("math"),
+            "math",
+            "0.1.0",
+            &[("utils", "{ path = \"../utils\" }")],
+        );
+        write_package(&dir.path().join("utils"), "utils", "0.1.0", &[]);
+
+        let graph =
+            PackageGraph::from_manifest_path(dir.path().join("game/mun.toml"), None).unwrap();
+        let names = graph
+            .packages()
+            .iter()
+            .map(|package| package.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["utils", "math", "game"]);
+        assert_eq!(graph.members(), &[2]);
+        assert_eq!(graph.dependencies(0), &[] as &[usize]);
+        assert_eq!(graph.dependencies(1), &[0]);
+        assert_eq!(graph.dependencies(2), &[1, 0]);
+
+        // The resolved packages are recorded in a lockfile
+        let lockfile = Lockfile::from_file(dir.path().join("game/mun.lock")).unwrap();
+        assert_eq!(lockfile, graph.lockfile());
+    }
+
+    #[test]
+    fn resolve_without_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(&dir.path().join("game"), "game", "0.1.0", &[]);
+
+        PackageGraph::from_manifest_path(dir.path().join("game/mun.toml"), None).unwrap();
+        assert!(!dir.path().join("game/mun.lock").exists());
+    }
+
+    #[test]
+    fn resolve_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("mun.toml"),
+            "[workspace]\nmembers = [\"game\", \"math\"]\n",
+        )
+        .unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[("math", "{ path = \"../math\" }")],
+        );
+        write_package(&dir.path().join("math"), "math", "0.1.0", &[]);
+
+        let graph = PackageGraph::from_manifest_path(dir.path().join("mun.toml"), None).unwrap();
+        let names = graph
+            .packages()
+            .iter()
+            .map(|package| package.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["math", "game"]);
+        assert_eq!(graph.members(), &[1, 0]);
+        assert_eq!(graph.dependencies(1), &[0]);
+
+        // All dependencies are members, so nothing needs to be locked
+        assert!(!dir.path().join("mun.lock").exists());
+    }
+
+    #[test]
+    fn resolve_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[("math", "{ path = \"../math\" }")],
+        );
+        write_package(
+            &dir.path().join("math"),
+            "math",
+            "0.1.0",
+            &[("game", "{ path = \"../game\" }")],
+        );
+
+        let err = resolve_package(&dir.path().join("game"), None, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cyclic dependency between packages: game -> math -> game"
+        );
+    }
+
+    #[test]
+    fn resolve_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[("math", "{ path = \"../math\" }")],
+        );
+
+        assert!(resolve_package(&dir.path().join("game"), None, None).is_err());
+    }
+
+    #[test]
+    fn resolve_name_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[("math", "{ path = \"../math\" }")],
+        );
+        write_package(&dir.path().join("math"), "maths", "0.1.0", &[]);
+
+        let err = resolve_package(&dir.path().join("game"), None, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "dependency `math` of `game` refers to a package named `maths`"
+        );
+    }
+
+    #[test]
+    fn resolve_path_version_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[("math", "{ path = \"../math\", version = \"0.2\" }")],
+        );
+        write_package(&dir.path().join("math"), "math", "0.1.0", &[]);
+
+        let err = resolve_package(&dir.path().join("game"), None, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "dependency `math` of `game` requires version `^0.2`, but found v0.1.0"
+        );
+    }
+
+    #[test]
+    fn resolve_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[("math", "\"0.1\"")],
+        );
+        write_package(
+            &dir.path().join("registry/math-0.1.0"),
+            "math",
+            "0.1.0",
+            &[],
+        );
+        write_package(
+            &dir.path().join("registry/math-0.1.2"),
+            "math",
+            "0.1.2",
+            &[],
+        );
+        write_package(
+            &dir.path().join("registry/math-0.2.0"),
+            "math",
+            "0.2.0",
+            &[],
+        );
+        let registry = Registry::open(dir.path().join("registry")).unwrap();
+
+        // Without a registry, registry dependencies cannot be resolved
+        assert!(resolve_package(&dir.path().join("game"), None, None).is_err());
+
+        // The highest matching version is selected
+        let graph = resolve_package(&dir.path().join("game"), Some(&registry), None).unwrap();
+        assert_eq!(graph.packages()[0].name(), "math");
+        assert_eq!(graph.packages()[0].version().to_string(), "0.1.2");
+        assert_eq!(graph.source(0), PackageSource::Registry);
+        assert_eq!(graph.source(1), PackageSource::Path);
+
+        // Unless the lockfile contains another matching version
+        let mut lockfile = graph.lockfile().to_string();
+        lockfile = lockfile.replace("0.1.2", "0.1.0");
+        let lockfile = lockfile.parse::<Lockfile>().unwrap();
+        let graph =
+            resolve_package(&dir.path().join("game"), Some(&registry), Some(&lockfile)).unwrap();
+        assert_eq!(graph.packages()[0].version().to_string(), "0.1.0");
+    }
+
+    #[test]
+    fn resolve_registry_version_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[("math", "\"0.3\"")],
+        );
+        write_package(
+            &dir.path().join("registry/math-0.2.0"),
+            "math",
+            "0.2.0",
+            &[],
+        );
+        let registry = Registry::open(dir.path().join("registry")).unwrap();
+
+        let err = resolve_package(&dir.path().join("game"), Some(&registry), None).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("could not find a version of `math` that matches `^0.3` in the registry"));
+    }
+
+    #[test]
+    fn resolve_registry_intersecting_requirements() {
+        let dir = tempfile::tempdir().unwrap();
+        write_package(
+            &dir.path().join("game"),
+            "game",
+            "0.1.0",
+            &[
+                ("math", "\">=1\""),
+                ("physics", "{ path = \"../physics\" }"),
+            ],
+        );
+        write_package(
+            &dir.path().join("physics"),
+            "physics",
+            "0.1.0",
+            &[("math", "\"1\"")],
+        );
+        for version in &["1.0.0", "1.2.0", "2.0.0"] {
+            write_package(
+                &dir.path().join(format!("registry/math-{}", version)),
+                "math",
+                version,
+                &[],
+            );
+        }
+        let registry = Registry::open(dir.path().join("registry")).unwrap();
+
+        // The highest version that satisfies both requirements is selected
+        let graph = resolve_package(&dir.path().join("game"), Some(&registry), None).unwrap();
+        let names = graph
+            .packages()
+            .iter()
+            .map(|package| package.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["math", "physics", "game"]);
+        assert_eq!(graph.packages()[0].version().to_string(), "1.2.0");
+
+        // Requirements that have no version in common cannot be resolved
+        write_package(
+            &dir.path().join("physics"),
+            "physics",
+            "0.1.0",
+            &[("math", "\"0.1\"")],
+        );
+        let err = resolve_package(&dir.path().join("game"), Some(&registry), None).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "could not find a version of `math` that matches `>=1, ^0.1` in the registry"
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{Package, MANIFEST_FILENAME};
+use semver::{Version, VersionReq};
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+/// A local package registry: a directory of which every subdirectory that contains a manifest is a
+/// package. Multiple versions of the same package can exist side by side, e.g. in the
+/// subdirectories `math-0.1.0` and `math-0.2.0`.
+#[derive(Clone, Debug)]
+pub struct Registry {
+    path: PathBuf,
+    packages: Vec<Package>,
+}
+
+impl Registry {
+    /// Opens the registry at the specified directory by loading the manifests of all its packages
+    pub fn open<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let entries = read_dir(path)
+            .map_err(|e| anyhow::anyhow!("could not read registry '{}': {}", path.display(), e))?;
+
+        let mut packages = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            let manifest_path = entry.path().join(MANIFEST_FILENAME);
+            if manifest_path.is_file() {
+                packages.push(Package::from_file(&manifest_path).map_err(|e| {
+                    anyhow::anyhow!(
+                        "could not load registry package '{}': {}",
+                        manifest_path.display(),
+                        e
+                    )
+                })?);
+            }
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            packages,
+        })
+    }
+
+    /// Returns the directory of the registry
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns all packages in the registry
+    pub fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+
+    /// Finds the package with the specified `name` and the highest version that satisfies all
+    /// `version_reqs`. If the `preferred` version - e.g. from a lockfile - satisfies the
+    /// requirements and exists, that version is returned instead.
+    pub fn find(
+        &self,
+        name: &str,
+        version_reqs: &[&VersionReq],
+        preferred: Option<&Version>,
+    ) -> Option<&Package> {
+        let candidates = self.packages.iter().filter(|package| {
+            package.name() == name
+                && version_reqs
+                    .iter()
+                    .all(|version_req| version_req.matches(package.version()))
+        });
+
+        if let Some(preferred) = preferred {
+            if let Some(package) = candidates
+                .clone()
+                .find(|package| package.version() == preferred)
+            {
+                return Some(package);
+            }
+        }
+
+        candidates.max_by(|a, b| a.version().cmp(b.version()))
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{Package, WorkspaceManifest, DEPENDENCY_LOCKFILE_NAME, MANIFEST_FILENAME};
+use std::path::{Path, PathBuf};
+
+/// A set of packages that are built together. All members of a workspace share a single lockfile
+/// and output directory.
+///
+/// A workspace is defined by a manifest with a `workspace` section. A manifest without one defines
+/// a workspace that only contains its own package.
+#[derive(Clone, Debug)]
+pub struct Workspace {
+    // The location of the manifest which marks the root of the workspace
+    manifest_path: PathBuf,
+    // The member packages; if the manifest defines a package, it is the first member
+    members: Vec<Package>,
+    // Whether the manifest defines a package of its own
+    has_root_package: bool,
+}
+
+impl Workspace {
+    /// Loads the workspace defined by the manifest at the specified path, together with all its
+    /// members.
+    pub fn from_manifest_path<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let manifest_path = path.as_ref();
+        let workspace_manifest = match WorkspaceManifest::from_file(manifest_path)? {
+            Some(workspace_manifest) => workspace_manifest,
+            None => {
+                return Ok(Self {
+                    manifest_path: manifest_path.to_path_buf(),
+                    members: vec![Package::from_file(manifest_path)?],
+                    has_root_package: true,
+                })
+            }
+        };
+
+        let root = manifest_path.parent().unwrap();
+        let mut members = Vec::with_capacity(workspace_manifest.members().len() + 1);
+        if !workspace_manifest.is_virtual() {
+            members.push(Package::from_file(manifest_path)?);
+        }
+
+        for member in workspace_manifest.members() {
+            let member_manifest_path = root.join(member).join(MANIFEST_FILENAME);
+            members.push(Package::from_file(&member_manifest_path).map_err(|e| {
+                anyhow::anyhow!(
+                    "could not load workspace member '{}': {}",
+                    member.display(),
+                    e
+                )
+            })?);
+        }
+
+        if members.is_empty() {
+            anyhow::bail!("workspace '{}' has no members", manifest_path.display());
+        }
+
+        Ok(Self {
+            manifest_path: manifest_path.to_path_buf(),
+            members,
+            has_root_package: !workspace_manifest.is_virtual(),
+        })
+    }
+
+    /// Returns the path of the manifest
+    pub fn manifest_path(&self) -> &Path {
+        &self.manifest_path
+    }
+
+    /// Returns the root folder of the workspace
+    pub fn root(&self) -> &Path {
+        self.manifest_path().parent().unwrap()
+    }
+
+    /// Returns the member packages of the workspace
+    pub fn members(&self) -> &[Package] {
+        &self.members
+    }
+
+    /// Returns the package that is defined by the manifest of the workspace itself, if any. This
+    /// is always the first member.
+    pub fn root_package(&self) -> Option<&Package> {
+        if self.has_root_package {
+            self.members.first()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the path of the lockfile that records the dependencies of all members
+    pub fn lockfile_path(&self) -> PathBuf {
+        self.root().join(DEPENDENCY_LOCKFILE_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Workspace;
+    use std::fs;
+
+    #[test]
+    fn from_manifest_path() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("mun.toml"),
+            "[workspace]\nmembers = [\"game\", \"libs/math\"]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("game")).unwrap();
+        fs::write(
+            dir.path().join("game/mun.toml"),
+            "[package]\nname=\"game\"\nversion=\"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("libs/math")).unwrap();
+        fs::write(
+            dir.path().join("libs/math/mun.toml"),
+            "[package]\nname=\"math\"\nversion=\"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::from_manifest_path(dir.path().join("mun.toml")).unwrap();
+        let names = workspace
+            .members()
+            .iter()
+            .map(|package| package.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["game", "math"]);
+        assert!(workspace.root_package().is_none());
+        assert_eq!(workspace.lockfile_path(), dir.path().join("mun.lock"));
+
+        // A package manifest defines a workspace with only that package
+        let workspace = Workspace::from_manifest_path(dir.path().join("game/mun.toml")).unwrap();
+        assert_eq!(workspace.members().len(), 1);
+        assert_eq!(workspace.root_package().unwrap().name(), "game");
+        assert_eq!(workspace.root(), dir.path().join("game"));
+    }
+
+    #[test]
+    fn from_manifest_path_missing_member() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("mun.toml"),
+            "[package]\nname=\"game\"\nversion=\"0.1.0\"\n\n[workspace]\nmembers = [\"math\"]\n",
+        )
+        .unwrap();
+
+        let err = Workspace::from_manifest_path(dir.path().join("mun.toml")).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("could not load workspace member 'math'"));
+    }
+}
@@ -17,7 +17,7 @@ pub fn fibonacci_benchmark(c: &mut Criterion) {
     for i in [100i64, 200i64, 500i64, 1000i64, 4000i64, 8000i64].iter() {
         // Run Mun fibonacci
         group.bench_with_input(BenchmarkId::new("mun", i), i, |b, i| {
-            let runtime_ref = runtime.borrow();
+            let runtime_ref = runtime.read();
             b.iter(|| {
                 let _: i64 = invoke_fn!(runtime_ref, "main", *i).unwrap();
             })
@@ -78,11 +78,18 @@ pub fn empty_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("empty");
 
     group.bench_function("mun", |b| {
-        let runtime_ref = runtime.borrow();
+        let runtime_ref = runtime.read();
         b.iter(|| {
             let _: i64 = invoke_fn!(runtime_ref, "empty", black_box(20i64)).unwrap();
         })
     });
+    group.bench_function("mun_handle", |b| {
+        let runtime_ref = runtime.read();
+        let mut empty = runtime_ref.get_fn::<(i64,), i64>("empty").unwrap();
+        b.iter(|| {
+            let _: i64 = empty.call(&runtime_ref, (black_box(20i64),)).unwrap();
+        })
+    });
     group.bench_function("rust", |b| b.iter(|| empty(black_box(20))));
     group.bench_function("luajit", |b| {
         b.iter(|| {
@@ -114,7 +121,7 @@ struct RustParent<'a> {
 pub fn get_struct_field_benchmark(c: &mut Criterion) {
     // Perform setup (not part of the benchmark)
     let runtime = util::runtime_from_file("struct.mun");
-    let runtime_ref = runtime.borrow_mut();
+    let runtime_ref = runtime.write();
     let mun_gc_parent: StructRef = invoke_fn!(runtime_ref, "make_gc_parent").unwrap();
     let mun_value_parent: StructRef = invoke_fn!(runtime_ref, "make_value_parent").unwrap();
 
@@ -159,7 +166,7 @@ pub fn get_struct_field_benchmark(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("mun struct(gc)", i), i, |b, i| {
             b.iter(|| {
                 for _ in 0..*i {
-                    // TODO: Optimise `RefCell::borrow` cost for sequential marshalling
+                    // TODO: Optimise `RwLock::read` cost for sequential marshalling
                     let _child = black_box(mun_gc_parent.get::<StructRef>("child").unwrap());
                     // TODO: Optimise `Drop` cost for temporary structs
                 }
@@ -203,7 +210,7 @@ pub fn get_struct_field_benchmark(c: &mut Criterion) {
 pub fn set_struct_field_benchmark(c: &mut Criterion) {
     // Perform setup (not part of the benchmark)
     let runtime = util::runtime_from_file("struct.mun");
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
     let mut mun_gc_parent: StructRef = invoke_fn!(runtime_ref, "make_gc_parent").unwrap();
     let mut mun_value_parent: StructRef = invoke_fn!(runtime_ref, "make_value_parent").unwrap();
 
@@ -228,7 +235,7 @@ pub fn set_struct_field_benchmark(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("mun fundamental", i), i, |b, i| {
             b.iter(|| {
                 for _ in 0..*i {
-                    // TODO: Optimise `RefCell::borrow` cost for sequential marshalling
+                    // TODO: Optimise `RwLock::read` cost for sequential marshalling
                     gc_child.set("0", -3.14f32).unwrap();
                 }
             })
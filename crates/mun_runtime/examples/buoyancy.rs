@@ -17,7 +17,7 @@ fn main() {
         .spawn()
         .expect("Failed to spawn Runtime");
 
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
     let ctx: StructRef = invoke_fn!(runtime_ref, "new_sim").unwrap();
 
     let mut previous = time::Instant::now();
@@ -33,10 +33,10 @@ fn main() {
             elapsed.as_secs_f32()
         };
 
-        let runtime_ref = runtime.borrow();
+        let runtime_ref = runtime.read();
         let _: () = invoke_fn!(runtime_ref, "sim_update", ctx.clone(), elapsed_secs).unwrap();
         previous = now;
 
-        runtime.borrow_mut().update();
+        runtime.write().update();
     }
 }
@@ -13,7 +13,7 @@ fn main() {
         .spawn()
         .expect("Failed to spawn Runtime");
 
-    let mut runtime_ref = runtime.borrow_mut();
+    let mut runtime_ref = runtime.write();
 
     loop {
         let n: i64 = invoke_fn!(runtime_ref, "nth").unwrap_or_else(|e| e.wait(&mut runtime_ref));
@@ -1,4 +1,4 @@
-use crate::garbage_collector::{GcPtr, GcRootPtr, UnsafeTypeInfo};
+use crate::garbage_collector::{GarbageCollector, GcPtr, GcRootPtr, UnsafeTypeInfo};
 use crate::{
     marshal::Marshal,
     reflection::{
@@ -8,20 +8,19 @@ use crate::{
 };
 use memory::gc::{GcRuntime, HasIndirectionPtr};
 use once_cell::sync::OnceCell;
-use std::cell::{Ref, RefCell};
+use parking_lot::{RwLock, RwLockReadGuard};
 use std::{
     marker::PhantomPinned,
     mem::MaybeUninit,
     pin::Pin,
     ptr::{self, NonNull},
-    rc::Rc,
     sync::Arc,
 };
 
 /// Represents a Mun struct pointer.
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct RawStruct(GcPtr);
+pub struct RawStruct(pub(crate) GcPtr);
 
 impl RawStruct {
     /// Returns a pointer to the struct memory.
@@ -53,7 +52,7 @@ impl<'s> StructRef<'s> {
     }
 
     /// Roots the `StructRef`.
-    pub fn root(self, runtime: Rc<RefCell<Runtime>>) -> RootedStruct {
+    pub fn root(self, runtime: Arc<RwLock<Runtime>>) -> RootedStruct {
         RootedStruct::new(&self.runtime.gc, runtime, self.raw)
     }
 
@@ -117,7 +116,7 @@ impl<'s> StructRef<'s> {
 
     /// Replaces the value of the field corresponding to the specified `field_name` and returns the
     /// old value.
-    pub fn replace<T: ArgumentReflection + Marshal<'s>>(
+    pub fn replace<T: ArgumentReflection + ReturnTypeReflection + Marshal<'s>>(
         &mut self,
         field_name: &str,
         value: T,
@@ -148,7 +147,9 @@ impl<'s> StructRef<'s> {
         let field_ptr =
             unsafe { self.field_offset_unchecked::<T::MunType>(struct_info, field_idx) };
         let old = Marshal::marshal_from_ptr(field_ptr, self.runtime, Some(field_type));
-        Marshal::marshal_to_ptr(value, field_ptr, Some(field_type));
+        Marshal::marshal_to_ptr(value, field_ptr, self.runtime, Some(field_type));
+        // Notify the garbage collector that the struct may now reference another object
+        self.runtime.gc().write_barrier(self.raw.0);
         Ok(old)
     }
 
@@ -180,7 +181,9 @@ impl<'s> StructRef<'s> {
 
         let field_ptr =
             unsafe { self.field_offset_unchecked::<T::MunType>(struct_info, field_idx) };
-        Marshal::marshal_to_ptr(value, field_ptr, Some(field_type));
+        Marshal::marshal_to_ptr(value, field_ptr, self.runtime, Some(field_type));
+        // Notify the garbage collector that the struct may now reference another object
+        self.runtime.gc().write_barrier(self.raw.0);
         Ok(())
     }
 }
@@ -222,7 +225,7 @@ impl<'s> Marshal<'s> for StructRef<'s> {
         StructRef::new(value, runtime)
     }
 
-    fn marshal_into<'r>(self) -> Self::MunType {
+    fn marshal_into(self, _runtime: &Runtime) -> Self::MunType {
         self.into_raw()
     }
 
@@ -271,6 +274,7 @@ impl<'s> Marshal<'s> for StructRef<'s> {
     fn marshal_to_ptr(
         value: Self,
         mut ptr: NonNull<Self::MunType>,
+        _runtime: &Runtime,
         type_info: Option<&abi::TypeInfo>,
     ) {
         // `type_info` is only `None` for the `()` type
@@ -287,30 +291,222 @@ impl<'s> Marshal<'s> for StructRef<'s> {
     }
 }
 
+/// Represents a Mun enum pointer.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RawEnum(pub(crate) GcPtr);
+
+impl RawEnum {
+    /// Returns a pointer to the enum memory.
+    pub unsafe fn get_ptr(&self) -> *const u8 {
+        self.0.deref()
+    }
+}
+
+/// Type-agnostic wrapper for interoperability with a Mun enum. Enums are value types, so an
+/// `EnumRef` always holds a copy of the Mun enum, that will be garbage collected when it is no
+/// longer used.
+#[derive(Clone)]
+pub struct EnumRef<'e> {
+    raw: RawEnum,
+    runtime: &'e Runtime,
+}
+
+impl<'e> EnumRef<'e> {
+    /// Creates an `EnumRef` that wraps a raw Mun enum.
+    fn new<'r>(raw: RawEnum, runtime: &'r Runtime) -> Self
+    where
+        'r: 'e,
+    {
+        Self { raw, runtime }
+    }
+
+    /// Consumes the `EnumRef`, returning a raw Mun enum.
+    pub fn into_raw(self) -> RawEnum {
+        self.raw
+    }
+
+    /// Returns the type information of the enum.
+    pub fn type_info(&self) -> &abi::TypeInfo {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As the lifetime of `TypeInfo` is tied to the lifetime of
+        // `Runtime`, this is safe.
+        unsafe { &*self.runtime.gc.ptr_type(self.raw.0).into_inner().as_ptr() }
+    }
+
+    /// Returns the tag of the active variant, which is the index of the variant in the enum
+    /// declaration.
+    pub fn tag(&self) -> u16 {
+        // Safety: The tag is always stored at the start of the enum's memory
+        unsafe { *self.raw.get_ptr().cast::<u16>() }
+    }
+
+    /// Returns the name of the active variant.
+    pub fn variant_name(&self) -> &str {
+        // Safety: `as_enum` is guaranteed to return `Some` for `EnumRef`s.
+        let enum_info = self.type_info().as_enum().unwrap();
+        enum_info
+            .variant_names()
+            .nth(self.tag() as usize)
+            .expect("the tag of an enum must refer to one of its variants")
+    }
+
+    /// Returns whether the active variant has the specified `variant_name`.
+    pub fn is_variant(&self, variant_name: &str) -> bool {
+        self.variant_name() == variant_name
+    }
+
+    /// Retrieves the value of the field corresponding to the specified `field_name` of the active
+    /// variant. The fields of tuple variants are named by their index, e.g. `0`.
+    pub fn get<T: ReturnTypeReflection + Marshal<'e>>(&self, field_name: &str) -> Result<T, String>
+    where
+        T: 'e,
+    {
+        let type_info = self.type_info();
+
+        // Safety: `as_enum` is guaranteed to return `Some` for `EnumRef`s.
+        let enum_info = type_info.as_enum().unwrap();
+        let variant_info = &enum_info.variant_infos()[self.tag() as usize];
+        let variant_name = self.variant_name();
+        let field_idx = abi::StructInfo::find_field_index(
+            &format!("{}::{}", type_info.name(), variant_name),
+            variant_info,
+            field_name,
+        )?;
+
+        // Safety: If we found the `field_idx`, we are guaranteed to also have the `field_type` and
+        // `field_offset`.
+        let field_type = unsafe { variant_info.field_types().get_unchecked(field_idx) };
+        equals_return_type::<T>(field_type).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}::{}::{}`. Expected: `{}`. Found: `{}`.",
+                type_info.name(),
+                variant_name,
+                field_name,
+                expected,
+                found,
+            )
+        })?;
+
+        let field_ptr = unsafe {
+            let offset = *variant_info.field_offsets().get_unchecked(field_idx);
+            // Safety: self.raw's memory pointer is never null
+            NonNull::new_unchecked(
+                self.raw.get_ptr().add(offset as usize).cast::<T::MunType>() as *mut _
+            )
+        };
+        Ok(Marshal::marshal_from_ptr(
+            field_ptr,
+            self.runtime,
+            Some(field_type),
+        ))
+    }
+}
+
+impl<'r> ArgumentReflection for EnumRef<'r> {
+    fn type_guid(&self, runtime: &Runtime) -> abi::Guid {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+        unsafe { runtime.gc().ptr_type(self.raw.0).into_inner().as_ref().guid }
+    }
+
+    fn type_name(&self, runtime: &Runtime) -> &str {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+        unsafe { (&*runtime.gc().ptr_type(self.raw.0).into_inner().as_ptr()).name() }
+    }
+}
+
+impl<'r> ReturnTypeReflection for EnumRef<'r> {
+    fn type_name() -> &'static str {
+        "enum"
+    }
+
+    fn type_guid() -> abi::Guid {
+        // TODO: Once `const_fn` lands, replace this with a const md5 hash
+        static GUID: OnceCell<abi::Guid> = OnceCell::new();
+        *GUID.get_or_init(|| abi::Guid(md5::compute(<Self as ReturnTypeReflection>::type_name()).0))
+    }
+}
+
+impl<'e> Marshal<'e> for EnumRef<'e> {
+    type MunType = RawEnum;
+
+    fn marshal_from<'r>(value: Self::MunType, runtime: &'r Runtime) -> Self
+    where
+        Self: 'e,
+        'r: 'e,
+    {
+        EnumRef::new(value, runtime)
+    }
+
+    fn marshal_into(self, _runtime: &Runtime) -> Self::MunType {
+        self.into_raw()
+    }
+
+    fn marshal_from_ptr<'r>(
+        ptr: NonNull<Self::MunType>,
+        runtime: &'r Runtime,
+        type_info: Option<&abi::TypeInfo>,
+    ) -> EnumRef<'e>
+    where
+        Self: 'e,
+        'r: 'e,
+    {
+        // Safety: `type_info` is only `None` for the `()` type
+        let type_info = type_info.unwrap();
+
+        // An enum is a value type, so `ptr` points to an enum value. Copy it into a new object
+        // using the runtime's intrinsic.
+        let mut gc_handle = runtime.gc().alloc(
+            // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
+            UnsafeTypeInfo::new(unsafe {
+                NonNull::new_unchecked(type_info as *const abi::TypeInfo as *mut _)
+            }),
+        );
+
+        let src = ptr.cast::<u8>().as_ptr() as *const _;
+        let dest = unsafe { gc_handle.deref_mut::<u8>() };
+        let size = type_info.size_in_bytes();
+        unsafe { ptr::copy_nonoverlapping(src, dest, size) };
+
+        EnumRef::new(RawEnum(gc_handle), runtime)
+    }
+
+    fn marshal_to_ptr(
+        value: Self,
+        ptr: NonNull<Self::MunType>,
+        _runtime: &Runtime,
+        type_info: Option<&abi::TypeInfo>,
+    ) {
+        // `type_info` is only `None` for the `()` type
+        let type_info = type_info.unwrap();
+
+        let dest = ptr.cast::<u8>().as_ptr();
+        let size = type_info.size_in_bytes();
+        unsafe { ptr::copy_nonoverlapping(value.into_raw().get_ptr(), dest, size) };
+    }
+}
+
 /// Type-agnostic wrapper for interoperability with a Mun struct, that has been rooted. To marshal,
 /// obtain a `StructRef` for the `RootedStruct`.
+///
+/// A `RootedStruct` is `Send`, so it can be moved to another thread together with its runtime.
 #[derive(Clone)]
 pub struct RootedStruct {
     handle: GcRootPtr,
-    runtime: Rc<RefCell<Runtime>>,
+    runtime: Arc<RwLock<Runtime>>,
 }
 
 impl RootedStruct {
     /// Creates a `RootedStruct` that wraps a raw Mun struct.
-    fn new<G: GcRuntime<UnsafeTypeInfo>>(
-        gc: &Arc<G>,
-        runtime: Rc<RefCell<Runtime>>,
-        raw: RawStruct,
-    ) -> Self {
-        let handle = {
-            let runtime_ref = runtime.borrow();
-            // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
-            // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
-            assert!(unsafe { gc.ptr_type(raw.0).into_inner().as_ref().data.is_struct() });
-
-            GcRootPtr::new(&runtime_ref.gc, raw.0)
-        };
+    fn new(gc: &Arc<GarbageCollector>, runtime: Arc<RwLock<Runtime>>, raw: RawStruct) -> Self {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As the `StructRef` that is being rooted holds a shared
+        // reference to `Runtime`, this is safe.
+        assert!(unsafe { gc.ptr_type(raw.0).into_inner().as_ref().data.is_struct() });
 
+        let handle = GcRootPtr::new(gc, raw.0);
         Self { handle, runtime }
     }
 
@@ -331,20 +527,23 @@ impl RootedStruct {
     }
 
     /// Borrows the struct's runtime.
-    pub fn borrow_runtime(&self) -> Ref<Runtime> {
-        self.runtime.borrow()
+    ///
+    /// The runtime is often already borrowed by the calling thread, so a recursive read lock is
+    /// acquired. This prevents a deadlock when another thread is waiting to acquire a write lock.
+    pub fn borrow_runtime(&self) -> RwLockReadGuard<Runtime> {
+        self.runtime.read_recursive()
     }
 }
 
 /// Type-agnostic wrapper for safely obtaining a `StructRef` from a `RootedStruct`.
 pub struct RootedStructRef<'s> {
-    runtime: Ref<'s, Runtime>,
+    runtime: RwLockReadGuard<'s, Runtime>,
     struct_ref: MaybeUninit<StructRef<'s>>,
     _pin: PhantomPinned,
 }
 
 impl<'s> RootedStructRef<'s> {
-    fn new(raw: RawStruct, runtime: Ref<'s, Runtime>) -> Pin<Box<Self>> {
+    fn new(raw: RawStruct, runtime: RwLockReadGuard<'s, Runtime>) -> Pin<Box<Self>> {
         let struct_ref = RootedStructRef {
             runtime,
             struct_ref: MaybeUninit::uninit(),
@@ -0,0 +1,282 @@
+use crate::garbage_collector::{element_layout, GcPtr, UnsafeTypeInfo};
+use crate::{
+    marshal::Marshal,
+    reflection::{
+        equals_argument_type, equals_return_type, ArgumentReflection, ReturnTypeReflection,
+    },
+    Runtime, Value,
+};
+use memory::gc::{ArrayHeader, GcRuntime, HasIndirectionPtr};
+use once_cell::sync::OnceCell;
+use std::{marker::PhantomData, ptr::NonNull};
+
+/// Represents a Mun array pointer.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RawArray(pub(crate) GcPtr);
+
+impl RawArray {
+    /// Returns the number of elements in the array.
+    ///
+    /// # Safety
+    ///
+    /// The array is owned by the garbage collector. It is only valid as long as the array has not
+    /// been collected.
+    pub unsafe fn len(&self) -> usize {
+        (*self.0.deref::<ArrayHeader>()).length
+    }
+
+    /// Returns a pointer to the element at `index`, without doing bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be smaller than the length of the array and `element_type` must be the type of
+    /// the elements of the array.
+    unsafe fn element_ptr_unchecked(&self, element_type: &abi::TypeInfo, index: usize) -> *mut u8 {
+        let element = element_layout(element_type);
+        let (_, offset) = ArrayHeader::layout(element, self.len());
+        self.0
+            .deref::<u8>()
+            .add(offset + index * element.pad_to_align().size()) as *mut _
+    }
+}
+
+/// Allocates an array in the garbage collector `gc` of the array type `ty`, that can hold `length`
+/// zero-initialized elements.
+pub(crate) fn alloc_array<G: GcRuntime<UnsafeTypeInfo> + ?Sized>(
+    gc: &G,
+    ty: UnsafeTypeInfo,
+    length: usize,
+) -> GcPtr {
+    // Safety: `ty` is guaranteed to not be `ptr::null()`.
+    let type_info = unsafe { ty.into_inner().as_ref() };
+    let array_info = type_info
+        .as_array()
+        .unwrap_or_else(|| panic!("expected an array type, found: {}", type_info.name()));
+    gc.alloc_array(ty, element_layout(array_info.element_type()), length)
+}
+
+/// Typed wrapper for interoperability with a Mun array. This is merely a reference to the Mun
+/// array, that will be garbage collected unless it is referenced by a rooted object.
+pub struct ArrayRef<'a, T> {
+    raw: RawArray,
+    runtime: &'a Runtime,
+    _element: PhantomData<T>,
+}
+
+impl<'a, T> Clone for ArrayRef<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            raw: self.raw.clone(),
+            runtime: self.runtime,
+            _element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> ArrayRef<'a, T> {
+    /// Creates an `ArrayRef` that wraps a raw Mun array.
+    fn new<'r>(raw: RawArray, runtime: &'r Runtime) -> Self
+    where
+        'r: 'a,
+    {
+        Self {
+            raw,
+            runtime,
+            _element: PhantomData,
+        }
+    }
+
+    /// Consumes the `ArrayRef`, returning a raw Mun array.
+    pub fn into_raw(self) -> RawArray {
+        self.raw
+    }
+
+    /// Returns the type information of the array.
+    pub fn type_info(&self) -> &abi::TypeInfo {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As the lifetime of `TypeInfo` is tied to the lifetime of
+        // `Runtime`, this is safe.
+        unsafe { &*self.runtime.gc.ptr_type(self.raw.0).into_inner().as_ptr() }
+    }
+
+    /// Returns the type information of the elements of the array.
+    pub fn element_type_info(&self) -> &abi::TypeInfo {
+        // Safety: `as_array` is guaranteed to return `Some` for `ArrayRef`s.
+        self.type_info().as_array().unwrap().element_type()
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> usize {
+        // Safety: The array is kept alive by the garbage collector for the lifetime of `Runtime`.
+        unsafe { self.raw.len() }
+    }
+
+    /// Returns true if the array contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a pointer to the element at `index` or an error if it is out of bounds.
+    fn element_ptr(&self, index: usize) -> Result<NonNull<u8>, String> {
+        let length = self.len();
+        if index >= length {
+            return Err(format!(
+                "Index out of bounds for `{}`: the len is {} but the index is {}.",
+                self.type_info().name(),
+                length,
+                index
+            ));
+        }
+
+        // Safety: `index` was checked to be within the bounds of the array and an array's memory
+        // pointer is never null.
+        Ok(unsafe {
+            NonNull::new_unchecked(
+                self.raw
+                    .element_ptr_unchecked(self.element_type_info(), index),
+            )
+        })
+    }
+}
+
+impl<'a, T: ReturnTypeReflection + Marshal<'a>> ArrayRef<'a, T>
+where
+    T: 'a,
+{
+    /// Retrieves the value of the element at `index`.
+    pub fn get(&self, index: usize) -> Result<T, String> {
+        let element_type = self.element_type_info();
+        equals_return_type::<T>(element_type).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for elements of `{}`. Expected: `{}`. Found: `{}`.",
+                self.type_info().name(),
+                expected,
+                found,
+            )
+        })?;
+
+        let element_ptr = self.element_ptr(index)?.cast::<T::MunType>();
+        Ok(Marshal::marshal_from_ptr(
+            element_ptr,
+            self.runtime,
+            Some(element_type),
+        ))
+    }
+
+    /// Returns an iterator over the values of all elements of the array.
+    pub fn iter(&self) -> impl Iterator<Item = Result<T, String>> + 'a {
+        let array = self.clone();
+        (0..self.len()).map(move |index| array.get(index))
+    }
+}
+
+impl<'a> ArrayRef<'a, Value<'a>> {
+    /// Retrieves the value of the element at `index` as a dynamically typed [`Value`]. Returns an
+    /// error if the elements of the array cannot be represented by a `Value`, e.g. value structs.
+    pub fn get(&self, index: usize) -> Result<Value<'a>, String> {
+        let element_ptr = self.element_ptr(index)?;
+
+        // Safety: `element_ptr` points to an element of the array, which has the element type of
+        // the array and is kept alive by the garbage collector for the lifetime of `Runtime`.
+        unsafe { Value::from_ptr(element_ptr, self.element_type_info(), self.runtime) }.ok_or_else(
+            || {
+                format!(
+                    "Elements of `{}` cannot be represented by a `Value`.",
+                    self.type_info().name()
+                )
+            },
+        )
+    }
+}
+
+impl<'a, T: ArgumentReflection + Marshal<'a>> ArrayRef<'a, T>
+where
+    T: 'a,
+{
+    /// Sets the value of the element at `index`.
+    pub fn set(&mut self, index: usize, value: T) -> Result<(), String> {
+        let element_type = self.element_type_info();
+        equals_argument_type(self.runtime, element_type, &value).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for elements of `{}`. Expected: `{}`. Found: `{}`.",
+                self.type_info().name(),
+                expected,
+                found,
+            )
+        })?;
+
+        let element_ptr = self.element_ptr(index)?.cast::<T::MunType>();
+        Marshal::marshal_to_ptr(value, element_ptr, self.runtime, Some(element_type));
+        // Notify the garbage collector that the array may now reference another object
+        self.runtime.gc().write_barrier(self.raw.0);
+        Ok(())
+    }
+}
+
+impl<'r, T> ArgumentReflection for ArrayRef<'r, T> {
+    fn type_guid(&self, runtime: &Runtime) -> abi::Guid {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+        unsafe { runtime.gc().ptr_type(self.raw.0).into_inner().as_ref().guid }
+    }
+
+    fn type_name(&self, runtime: &Runtime) -> &str {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+        unsafe { (&*runtime.gc().ptr_type(self.raw.0).into_inner().as_ptr()).name() }
+    }
+}
+
+/// The element type of an `ArrayRef` is only validated when its elements are accessed, so all
+/// `ArrayRef`s share the same type name and guid.
+impl<'r, T> ReturnTypeReflection for ArrayRef<'r, T> {
+    fn type_name() -> &'static str {
+        "array"
+    }
+
+    fn type_guid() -> abi::Guid {
+        // TODO: Once `const_fn` lands, replace this with a const md5 hash
+        static GUID: OnceCell<abi::Guid> = OnceCell::new();
+        *GUID.get_or_init(|| abi::Guid(md5::compute(<Self as ReturnTypeReflection>::type_name()).0))
+    }
+}
+
+impl<'a, T> Marshal<'a> for ArrayRef<'a, T> {
+    type MunType = RawArray;
+
+    fn marshal_from<'r>(value: Self::MunType, runtime: &'r Runtime) -> Self
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        ArrayRef::new(value, runtime)
+    }
+
+    fn marshal_into(self, _runtime: &Runtime) -> Self::MunType {
+        self.into_raw()
+    }
+
+    fn marshal_from_ptr<'r>(
+        ptr: NonNull<Self::MunType>,
+        runtime: &'r Runtime,
+        _type_info: Option<&abi::TypeInfo>,
+    ) -> Self
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        // Arrays are always stored as a `GcPtr`
+        let value = unsafe { ptr.as_ref() }.clone();
+        Marshal::marshal_from(value, runtime)
+    }
+
+    fn marshal_to_ptr(
+        value: Self,
+        mut ptr: NonNull<Self::MunType>,
+        _runtime: &Runtime,
+        _type_info: Option<&abi::TypeInfo>,
+    ) {
+        unsafe { *ptr.as_mut() = value.into_raw() };
+    }
+}
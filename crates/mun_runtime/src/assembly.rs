@@ -1,5 +1,8 @@
 use crate::{
+    closure,
     garbage_collector::{GarbageCollector, UnsafeTypeInfo},
+    migration,
+    reload::ReloadEvent,
     DispatchTable,
 };
 use abi::{AssemblyInfo, FunctionPrototype};
@@ -8,7 +11,7 @@ use libloader::{MunLibrary, TempLibrary};
 use log::error;
 use memory::mapping::{Mapping, MemoryMapper};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ffi::c_void,
     path::{Path, PathBuf},
     ptr::NonNull,
@@ -22,6 +25,12 @@ pub struct Assembly {
     legacy_libs: Vec<TempLibrary>,
     info: AssemblyInfo,
     allocator: Arc<GarbageCollector>,
+    /// The version of the assembly that was replaced by the last hot reload, which can be reverted
+    /// to.
+    last_known_good: Option<Box<Assembly>>,
+    /// Whether objects of the assembly's types outlived a hot reload, in which case its library
+    /// needs to be retained when the assembly is discarded.
+    retain_library: bool,
 }
 
 impl Assembly {
@@ -49,6 +58,8 @@ impl Assembly {
             legacy_libs: Vec::new(),
             info,
             allocator: gc,
+            last_known_good: None,
+            retain_library: false,
         };
 
         Ok(assembly)
@@ -131,13 +142,16 @@ impl Assembly {
         Ok(dispatch_table)
     }
 
-    /// Tries to link the `assemblies`, resulting in a new [`DispatchTable`] on success. This leaves
-    /// the original `dispatch_table` intact, in case of linking errors.
+    /// Tries to link the `assemblies`, resulting in a new [`DispatchTable`] and a description of
+    /// the changes on success. This leaves the original `dispatch_table` intact, in case of
+    /// linking errors.
     pub(super) fn relink_all(
         unlinked_assemblies: &mut HashMap<PathBuf, Assembly>,
         linked_assemblies: &mut HashMap<PathBuf, Assembly>,
         dispatch_table: &DispatchTable,
-    ) -> anyhow::Result<DispatchTable> {
+    ) -> anyhow::Result<(DispatchTable, ReloadEvent)> {
+        let old_dispatch_table = dispatch_table;
+
         let mut assemblies = unlinked_assemblies
             .iter_mut()
             .map(|(old_path, asm)| {
@@ -175,48 +189,27 @@ impl Assembly {
 
         Assembly::link_all_impl(&mut dispatch_table, to_link)?;
 
+        // The old assemblies are still alive, so their functions can be compared
+        let mut event = ReloadEvent {
+            assemblies: assemblies
+                .iter()
+                .map(|(asm, _)| asm.library_path().to_path_buf())
+                .collect(),
+            ..ReloadEvent::default()
+        };
+        event.assemblies.sort();
+        event.diff_functions(old_dispatch_table, &dispatch_table);
+
         let assemblies_to_map: Vec<_> = assemblies
             .into_iter()
             .filter_map(|(new_asm, old_asm)| old_asm.map(|old_asm| (old_asm, new_asm)))
             .collect();
 
-        let mut assemblies_to_keep = HashMap::new();
+        let mut assemblies_to_keep = HashSet::new();
         for (old_assembly, new_assembly) in assemblies_to_map.iter() {
-            let old_types: Vec<UnsafeTypeInfo> = old_assembly
-                .info
-                .symbols
-                .types()
-                .iter()
-                .map(|ty| {
-                    // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
-                    UnsafeTypeInfo::new(unsafe {
-                        NonNull::new_unchecked(*ty as *const abi::TypeInfo as *mut _)
-                    })
-                })
-                .collect();
-
-            let new_types: Vec<UnsafeTypeInfo> = new_assembly
-                .info
-                .symbols
-                .types()
-                .iter()
-                .map(|ty| {
-                    // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
-                    UnsafeTypeInfo::new(unsafe {
-                        NonNull::new_unchecked(*ty as *const abi::TypeInfo as *mut _)
-                    })
-                })
-                .collect();
-
-            let mapping = Mapping::new(&old_types, &new_types);
-            let deleted_objects = old_assembly.allocator.map_memory(mapping);
-
-            if !deleted_objects.is_empty() {
+            if Assembly::migrate(old_assembly, new_assembly, true, &mut event) {
                 // Retain the previous assembly
-                assemblies_to_keep.insert(
-                    old_assembly.library_path().to_path_buf(),
-                    new_assembly.library_path().to_path_buf(),
-                );
+                assemblies_to_keep.insert(old_assembly.library_path().to_path_buf());
             }
         }
 
@@ -228,23 +221,274 @@ impl Assembly {
                 .remove(&old_path)
                 .expect("Assembly must exist.");
 
-            let new_path = if let Some(new_path) = assemblies_to_keep.remove(&old_path) {
-                // Retain all existing legacy libs
-                new_assembly
-                    .legacy_libs
-                    .append(&mut old_assembly.legacy_libs);
+            if assemblies_to_keep.remove(old_assembly.library_path()) {
+                old_assembly.retain_library = true;
+            }
 
-                new_assembly.legacy_libs.push(old_assembly.into_library());
+            // Only a single version is kept to revert to
+            if let Some(previous) = old_assembly.last_known_good.take() {
+                previous.discard(&mut new_assembly);
+            }
 
-                new_path
-            } else {
-                new_assembly.library_path().to_path_buf()
-            };
+            // Retain all existing legacy libs
+            new_assembly
+                .legacy_libs
+                .append(&mut old_assembly.legacy_libs);
+            new_assembly.last_known_good = Some(Box::new(old_assembly));
 
-            linked_assemblies.insert(new_path, new_assembly);
+            linked_assemblies.insert(new_assembly.library_path().to_path_buf(), new_assembly);
         }
 
-        Ok(dispatch_table)
+        Ok((dispatch_table, event))
+    }
+
+    /// Tries to revert the assemblies at `library_paths` to the versions they replaced, resulting
+    /// in a new [`DispatchTable`] and a description of the changes on success. This leaves the
+    /// assemblies and the original `dispatch_table` intact, in case of linking errors, including
+    /// those of assemblies that call functions of the reverted assemblies.
+    pub(super) fn revert_all(
+        library_paths: &[PathBuf],
+        linked_assemblies: &mut HashMap<PathBuf, Assembly>,
+        dispatch_table: &DispatchTable,
+    ) -> anyhow::Result<(DispatchTable, ReloadEvent)> {
+        for library_path in library_paths {
+            let assembly = linked_assemblies
+                .get(library_path)
+                .ok_or_else(|| anyhow!("Assembly '{}' does not exist.", library_path.display()))?;
+
+            if assembly.last_known_good.is_none() {
+                return Err(anyhow!(
+                    "Assembly '{}' has no last known good version.",
+                    library_path.display()
+                ));
+            }
+        }
+
+        let old_dispatch_table = dispatch_table;
+
+        // Clone the dispatch table, such that we can roll back if linking fails
+        let mut dispatch_table = dispatch_table.clone();
+
+        // Replace the assemblies' functions by those of their last known good version
+        for library_path in library_paths {
+            for function in linked_assemblies[library_path].info.symbols.functions() {
+                dispatch_table.remove_fn(function.prototype.name());
+            }
+        }
+        for library_path in library_paths {
+            let previous = linked_assemblies[library_path]
+                .last_known_good
+                .as_ref()
+                .unwrap();
+            for function in previous.info.symbols.functions() {
+                dispatch_table.insert_fn(function.prototype.name(), function.clone());
+            }
+        }
+
+        // The previous versions may point to functions that have since been reloaded, so all of
+        // their functions are linked again. Other assemblies may still point to functions of the
+        // reverted assemblies. All function pointers are resolved before any of them is assigned,
+        // so the assemblies are left intact if one of them fails to link.
+        let mut relinked_ptrs = Vec::new();
+        for library_path in library_paths {
+            let previous = linked_assemblies[library_path]
+                .last_known_good
+                .as_ref()
+                .unwrap();
+            relinked_ptrs.push((
+                library_path,
+                previous.resolve_dispatch_table(&dispatch_table)?,
+            ));
+        }
+        let mut other_relinked_ptrs = Vec::new();
+        for (library_path, assembly) in linked_assemblies.iter() {
+            if !library_paths.contains(library_path) {
+                let fn_ptrs = assembly
+                    .resolve_dispatch_table(&dispatch_table)
+                    .map_err(|e| {
+                        anyhow!(
+                            "Failed to relink assembly '{}', due to {}",
+                            library_path.display(),
+                            e
+                        )
+                    })?;
+                other_relinked_ptrs.push((library_path.clone(), fn_ptrs));
+            }
+        }
+
+        for (library_path, fn_ptrs) in relinked_ptrs {
+            linked_assemblies
+                .get_mut(library_path)
+                .and_then(|assembly| assembly.last_known_good.as_mut())
+                .unwrap()
+                .set_dispatch_ptrs(&fn_ptrs);
+        }
+
+        let mut event = ReloadEvent {
+            assemblies: library_paths.to_vec(),
+            ..ReloadEvent::default()
+        };
+        event.assemblies.sort();
+        event.diff_functions(old_dispatch_table, &dispatch_table);
+
+        for library_path in library_paths {
+            let mut assembly = linked_assemblies
+                .remove(library_path)
+                .expect("Assembly must exist.");
+            let mut previous = assembly.last_known_good.take().unwrap();
+
+            // Migration functions describe how to convert the old types to the new types, so they
+            // cannot be used to revert.
+            if Assembly::migrate(&assembly, &previous, false, &mut event) {
+                assembly.retain_library = true;
+            }
+            assembly.discard(&mut previous);
+
+            linked_assemblies.insert(library_path.clone(), *previous);
+        }
+
+        for (library_path, fn_ptrs) in other_relinked_ptrs {
+            linked_assemblies
+                .get_mut(&library_path)
+                .expect("Assembly must exist.")
+                .set_dispatch_ptrs(&fn_ptrs);
+        }
+
+        Ok((dispatch_table, event))
+    }
+
+    /// Resolves the function pointers of all entries of the assembly's dispatch table to the
+    /// functions in `dispatch_table`, including those that were linked before. Entries of functions
+    /// that are not part of the `dispatch_table` keep the pointer that was assigned by the
+    /// compiler. The assembly's dispatch table is not modified; see
+    /// [`Assembly::set_dispatch_ptrs`].
+    fn resolve_dispatch_table(
+        &self,
+        dispatch_table: &DispatchTable,
+    ) -> anyhow::Result<Vec<*const c_void>> {
+        self.info
+            .dispatch_table
+            .iter()
+            .map(
+                |(dispatch_ptr, fn_prototype)| match dispatch_table.get_fn(fn_prototype.name()) {
+                    Some(fn_def) if fn_prototype.signature == fn_def.prototype.signature => {
+                        Ok(fn_def.fn_ptr)
+                    }
+                    Some(fn_def) => Err(anyhow!("Failed to link: function '{}' is missing. A function with the same name does exist, but the signatures do not match (expected: {}, found: {}).", fn_prototype.name(), fn_prototype, fn_def.prototype)),
+                    None if dispatch_ptr.is_null() => Err(anyhow!(
+                        "Failed to link: function `{}` is missing.",
+                        fn_prototype.name()
+                    )),
+                    None => Ok(*dispatch_ptr),
+                },
+            )
+            .collect()
+    }
+
+    /// Assigns the function pointers `fn_ptrs`, as resolved by [`Assembly::resolve_dispatch_table`],
+    /// to the entries of the assembly's dispatch table.
+    fn set_dispatch_ptrs(&mut self, fn_ptrs: &[*const c_void]) {
+        self.info.dispatch_table.ptrs_mut().copy_from_slice(fn_ptrs);
+    }
+
+    /// Migrates the state of the `old` assembly to the `new` assembly, by copying the values of
+    /// its `static`s and converting its objects to the types of the `new` assembly. If
+    /// `run_migrations` is set, the migration functions declared by the `new` assembly are run for
+    /// converted objects. Returns whether objects of the `old` assembly's types could not be
+    /// converted, in which case its library needs to be retained.
+    fn migrate(
+        old: &Assembly,
+        new: &Assembly,
+        run_migrations: bool,
+        event: &mut ReloadEvent,
+    ) -> bool {
+        Assembly::copy_static_values(&old.info, &new.info);
+
+        let migration_fns = if run_migrations {
+            migration::migration_fns(&new.info)
+        } else {
+            Vec::new()
+        };
+
+        // Types that describe old types for migration functions are not part of the mapping, as
+        // they would be mistaken for the old types.
+        let mut new_types = new.type_infos();
+        new_types.retain(|ty| {
+            // Safety: The types of the `new` assembly are alive.
+            let ty = unsafe { ty.into_inner().as_ref() };
+            !migration_fns
+                .iter()
+                .any(|function| function.is_old_type(ty))
+        });
+
+        let mut mapping = Mapping::new(&old.type_infos(), &new_types);
+        event.add_mapping(&mapping);
+        event.migrated_types.append(&mut migration::add_migrations(
+            &migration_fns,
+            &mut mapping,
+            &new.allocator,
+        ));
+        closure::retain_closure_types(&mut mapping);
+
+        let deleted_objects = old.allocator.map_memory(mapping);
+        !deleted_objects.is_empty()
+    }
+
+    /// Returns the types of the assembly.
+    fn type_infos(&self) -> Vec<UnsafeTypeInfo> {
+        self.info
+            .symbols
+            .types()
+            .iter()
+            .map(|ty| {
+                // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
+                UnsafeTypeInfo::new(unsafe {
+                    NonNull::new_unchecked(*ty as *const abi::TypeInfo as *mut _)
+                })
+            })
+            .collect()
+    }
+
+    /// Discards the assembly in favor of its `successor`, which takes over the libraries that need
+    /// to be retained.
+    fn discard(mut self, successor: &mut Assembly) {
+        successor.legacy_libs.append(&mut self.legacy_libs);
+        if let Some(previous) = self.last_known_good.take() {
+            previous.discard(successor);
+        }
+        if self.retain_library {
+            successor.legacy_libs.push(self.into_library());
+        }
+    }
+
+    /// Copies the values of the `static`s of the `old` assembly into the `static`s of the `new`
+    /// assembly that have the same name and type. This preserves the state of an assembly across
+    /// hot reloads.
+    fn copy_static_values(old: &AssemblyInfo, new: &AssemblyInfo) {
+        for new_global in new.symbols.globals() {
+            if !new_global.is_mutable() {
+                continue;
+            }
+
+            let old_global = match old.symbols.get_global(new_global.name()) {
+                Some(old_global) if old_global.is_mutable() => old_global,
+                _ => continue,
+            };
+
+            let type_info = new_global.type_info();
+            if old_global.type_info().guid != type_info.guid {
+                continue;
+            }
+
+            // Safety: both globals have the same type, so their values have the same size.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    old_global.value_ptr().cast::<u8>(),
+                    new_global.value_ptr().cast::<u8>(),
+                    type_info.size_in_bytes(),
+                );
+            }
+        }
     }
 
     /// Returns the assembly's information.
@@ -252,6 +496,11 @@ impl Assembly {
         &self.info
     }
 
+    /// Returns whether the assembly has a last known good version that it can be reverted to.
+    pub fn has_last_known_good(&self) -> bool {
+        self.last_known_good.is_some()
+    }
+
     /// Returns the path corresponding to the assembly's library.
     pub fn library_path(&self) -> &Path {
         self.library_path.as_path()
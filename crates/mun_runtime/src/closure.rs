@@ -0,0 +1,407 @@
+//! Values of a Mun function pointer type, e.g. `fn(i32) -> i32`, are closures: garbage collected
+//! objects of which the memory starts with a pointer to the function that is called, followed by
+//! the variables captured by a lambda. The object itself is passed as the first argument of the
+//! function.
+//!
+//! A closure keeps pointing to the code of the assembly that created it. When an assembly is
+//! reloaded, the types of its closures are therefore never mapped to the new assembly. Instead,
+//! they are treated as deleted, which retains the old assembly for as long as its closures are
+//! alive. As a result, closures that are alive during a hot reload keep running the code they
+//! were created with.
+
+use crate::garbage_collector::{GarbageCollector, GcPtr, GcRootPtr, UnsafeTypeInfo};
+use crate::{
+    marshal::Marshal,
+    reflection::{equals_return_type, ArgumentReflection, ReturnTypeReflection},
+    Runtime,
+};
+use memory::{
+    gc::{GcRuntime, HasIndirectionPtr},
+    mapping::Mapping,
+};
+use once_cell::sync::OnceCell;
+use parking_lot::{RwLock, RwLockReadGuard};
+use std::{ffi::c_void, ptr::NonNull, sync::Arc};
+
+/// The prefix of the names of all closure types.
+const CLOSURE_TYPE_PREFIX: &str = "fn(";
+
+/// Returns whether `type_info` describes a closure.
+pub(crate) fn is_closure_type(type_info: &abi::TypeInfo) -> bool {
+    type_info.data.is_struct() && type_info.name().starts_with(CLOSURE_TYPE_PREFIX)
+}
+
+/// Marks all closure types in `mapping` as deleted, so closures keep the types - and thus the
+/// assembly - they were created with.
+pub(crate) fn retain_closure_types(mapping: &mut Mapping<UnsafeTypeInfo, UnsafeTypeInfo>) {
+    // Safety: The types of a mapping are guaranteed to be alive while the mapping is created.
+    let is_closure = |ty: &UnsafeTypeInfo| unsafe { is_closure_type(ty.into_inner().as_ref()) };
+
+    let deletions = &mut mapping.deletions;
+    mapping.identical.retain(|(old_ty, _)| {
+        if is_closure(old_ty) {
+            deletions.insert(*old_ty);
+            false
+        } else {
+            true
+        }
+    });
+
+    let converted: Vec<UnsafeTypeInfo> = mapping
+        .conversions
+        .keys()
+        .filter(|ty| is_closure(ty))
+        .cloned()
+        .collect();
+    for old_ty in converted {
+        mapping.conversions.remove(&old_ty);
+        mapping.deletions.insert(old_ty);
+    }
+}
+
+/// Splits the signature of a function pointer type, e.g. `fn(core::i32, core::f32) -> core::i32`,
+/// into the names of its parameter types and the name of its return type.
+fn parse_signature(signature: &str) -> Option<(Vec<&str>, Option<&str>)> {
+    let params = signature.strip_prefix(CLOSURE_TYPE_PREFIX)?;
+    let bytes = params.as_bytes();
+
+    let mut param_types = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, c) in params.char_indices() {
+        match c {
+            // The arrow of a nested function pointer type
+            '>' if idx > 0 && bytes[idx - 1] == b'-' => (),
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' | '>' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                param_types.push(params[start..idx].trim());
+                start = idx + 1;
+            }
+            ')' => {
+                let last = params[start..idx].trim();
+                if !last.is_empty() {
+                    param_types.push(last);
+                }
+                let ret_type = params[idx + 1..].trim().strip_prefix("->").map(str::trim);
+                return Some((param_types, ret_type));
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Returns the type information of the Mun type called `name`, if it can be passed to or returned
+/// from a closure by the runtime.
+fn marshallable_type_info<'r>(
+    runtime: &'r Runtime,
+    name: &str,
+) -> Result<&'r abi::TypeInfo, String> {
+    let type_info = runtime
+        .get_type_info(name)
+        .ok_or_else(|| format!("Failed to obtain type information of `{}`.", name))?;
+
+    // Closures receive value structs and enums by value, which cannot be marshalled
+    let by_value = match &type_info.data {
+        abi::TypeInfoData::Struct(s) => s.memory_kind == abi::StructMemoryKind::Value,
+        abi::TypeInfoData::Enum(_) => true,
+        _ => false,
+    };
+    if by_value {
+        Err(format!(
+            "Closures with parameters or return values of type `{}` cannot be invoked.",
+            name
+        ))
+    } else {
+        Ok(type_info)
+    }
+}
+
+/// Represents a Mun closure pointer.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RawClosure(pub(crate) GcPtr);
+
+impl RawClosure {
+    /// Returns a pointer to the function of the closure, or `None` if the closure has not been
+    /// initialized.
+    ///
+    /// # Safety
+    ///
+    /// The closure is owned by the garbage collector. It is only valid as long as the closure has
+    /// not been collected.
+    unsafe fn fn_ptr(&self) -> Option<NonNull<c_void>> {
+        // The pointer to the function is the first field of a closure
+        NonNull::new(*self.0.deref::<*mut c_void>())
+    }
+}
+
+/// The arguments with which a closure can be invoked. This trait is implemented for tuples of
+/// marshallable values.
+pub trait ClosureArgs<'a>: Sized {
+    /// Returns the guids and names of the types of the arguments.
+    fn arg_types<'r>(&'r self, runtime: &'r Runtime) -> Vec<(abi::Guid, &'r str)>;
+
+    /// Calls the closure's function `fn_ptr` with `closure` followed by the arguments.
+    ///
+    /// # Safety
+    ///
+    /// The signature of the function must match the types of the arguments and `Output`.
+    unsafe fn call<Output>(
+        self,
+        fn_ptr: NonNull<c_void>,
+        closure: RawClosure,
+        runtime: &Runtime,
+    ) -> Output;
+}
+
+macro_rules! impl_closure_args {
+    ($($T:ident),*) => {
+        impl<'a, $($T: ArgumentReflection + Marshal<'a>,)*> ClosureArgs<'a> for ($($T,)*) {
+            #[allow(non_snake_case, unused_variables)]
+            fn arg_types<'r>(&'r self, runtime: &'r Runtime) -> Vec<(abi::Guid, &'r str)> {
+                let ($($T,)*) = self;
+                vec![$(($T.type_guid(runtime), $T.type_name(runtime)),)*]
+            }
+
+            #[allow(non_snake_case, unused_variables)]
+            unsafe fn call<Output>(
+                self,
+                fn_ptr: NonNull<c_void>,
+                closure: RawClosure,
+                runtime: &Runtime,
+            ) -> Output {
+                let function: fn(RawClosure, $($T::MunType),*) -> Output =
+                    core::mem::transmute(fn_ptr.as_ptr());
+                let ($($T,)*) = self;
+                function(closure, $($T.marshal_into(runtime)),*)
+            }
+        }
+    };
+}
+
+impl_closure_args!();
+impl_closure_args!(A);
+impl_closure_args!(A, B);
+impl_closure_args!(A, B, C);
+impl_closure_args!(A, B, C, D);
+impl_closure_args!(A, B, C, D, E);
+impl_closure_args!(A, B, C, D, E, F);
+
+/// Typed wrapper for interoperability with a Mun closure. This is merely a reference to the Mun
+/// closure, that will be garbage collected unless it is referenced by a rooted object.
+#[derive(Clone)]
+pub struct ClosureRef<'c> {
+    raw: RawClosure,
+    runtime: &'c Runtime,
+}
+
+impl<'c> ClosureRef<'c> {
+    /// Creates a `ClosureRef` that wraps a raw Mun closure.
+    fn new<'r>(raw: RawClosure, runtime: &'r Runtime) -> Self
+    where
+        'r: 'c,
+    {
+        Self { raw, runtime }
+    }
+
+    /// Consumes the `ClosureRef`, returning a raw Mun closure.
+    pub fn into_raw(self) -> RawClosure {
+        self.raw
+    }
+
+    /// Roots the `ClosureRef`, which allows a host to store the closure and invoke it later.
+    pub fn root(self, runtime: Arc<RwLock<Runtime>>) -> RootedClosure {
+        RootedClosure::new(&self.runtime.gc, runtime, self.raw)
+    }
+
+    /// Returns the type information of the closure's object.
+    pub fn type_info(&self) -> &abi::TypeInfo {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As the lifetime of `TypeInfo` is tied to the lifetime of
+        // `Runtime`, this is safe.
+        unsafe { &*self.runtime.gc.ptr_type(self.raw.0).into_inner().as_ptr() }
+    }
+
+    /// Returns the signature of the closure, e.g. `fn(core::i32) -> core::i32`.
+    pub fn signature(&self) -> &str {
+        signature(self.type_info())
+    }
+
+    /// Invokes the closure with `args`, e.g. `closure.invoke::<_, i32>((1, 2))`.
+    pub fn invoke<'o, A: ClosureArgs<'c>, Output>(&self, args: A) -> Result<Output, String>
+    where
+        Output: 'o + ReturnTypeReflection + Marshal<'o>,
+        'c: 'o,
+    {
+        let signature = self.signature();
+        let (param_types, ret_type) = parse_signature(signature)
+            .ok_or_else(|| format!("Invalid closure signature `{}`.", signature))?;
+
+        // Validate the signature of the closure
+        let arg_types = args.arg_types(self.runtime);
+        if param_types.len() != arg_types.len() {
+            return Err(format!(
+                "Invalid number of arguments for `{}`. Expected: {}. Found: {}.",
+                signature,
+                param_types.len(),
+                arg_types.len(),
+            ));
+        }
+        for (idx, (param_type, (guid, name))) in param_types.iter().zip(arg_types).enumerate() {
+            let type_info = marshallable_type_info(self.runtime, param_type)?;
+            if type_info.guid != guid {
+                return Err(format!(
+                    "Invalid argument type at index {}. Expected: {}. Found: {}.",
+                    idx,
+                    type_info.name(),
+                    name,
+                ));
+            }
+        }
+
+        if let Some(ret_type) = ret_type {
+            equals_return_type::<Output>(marshallable_type_info(self.runtime, ret_type)?)
+        } else if <() as ReturnTypeReflection>::type_guid() != Output::type_guid() {
+            Err((
+                <() as ReturnTypeReflection>::type_name(),
+                Output::type_name(),
+            ))
+        } else {
+            Ok(())
+        }
+        .map_err(|(expected, found)| {
+            format!(
+                "Invalid return type. Expected: {}. Found: {}",
+                expected, found,
+            )
+        })?;
+
+        // Safety: The closure is kept alive by the garbage collector for the lifetime of
+        // `Runtime`, and its signature was validated.
+        let result = unsafe {
+            let fn_ptr = self
+                .raw
+                .fn_ptr()
+                .ok_or_else(|| format!("Closure `{}` is not initialized.", signature))?;
+            args.call::<Output::MunType>(fn_ptr, self.raw.clone(), self.runtime)
+        };
+        Ok(Marshal::marshal_from(result, self.runtime))
+    }
+}
+
+/// Returns the signature of the closure type `type_info`, which prefixes the name of its type.
+fn signature(type_info: &abi::TypeInfo) -> &str {
+    let name = type_info.name();
+    // The type of a lambda that captures variables is suffixed with the name of the lambda
+    name.find(" {").map_or(name, |idx| &name[..idx])
+}
+
+impl<'c> ArgumentReflection for ClosureRef<'c> {
+    fn type_guid(&self, _runtime: &Runtime) -> abi::Guid {
+        // All closures with the same signature share the guid of their function pointer type
+        abi::Guid(md5::compute(self.signature()).0)
+    }
+
+    fn type_name(&self, _runtime: &Runtime) -> &str {
+        self.signature()
+    }
+}
+
+/// The signature of a `ClosureRef` is only validated when it is invoked, so all `ClosureRef`s
+/// share the same type name and guid.
+impl<'c> ReturnTypeReflection for ClosureRef<'c> {
+    fn type_name() -> &'static str {
+        "closure"
+    }
+
+    fn type_guid() -> abi::Guid {
+        // TODO: Once `const_fn` lands, replace this with a const md5 hash
+        static GUID: OnceCell<abi::Guid> = OnceCell::new();
+        *GUID.get_or_init(|| abi::Guid(md5::compute(<Self as ReturnTypeReflection>::type_name()).0))
+    }
+}
+
+impl<'c> Marshal<'c> for ClosureRef<'c> {
+    type MunType = RawClosure;
+
+    fn marshal_from<'r>(value: Self::MunType, runtime: &'r Runtime) -> Self
+    where
+        Self: 'c,
+        'r: 'c,
+    {
+        ClosureRef::new(value, runtime)
+    }
+
+    fn marshal_into(self, _runtime: &Runtime) -> Self::MunType {
+        self.into_raw()
+    }
+
+    fn marshal_from_ptr<'r>(
+        ptr: NonNull<Self::MunType>,
+        runtime: &'r Runtime,
+        _type_info: Option<&abi::TypeInfo>,
+    ) -> Self
+    where
+        Self: 'c,
+        'r: 'c,
+    {
+        // Closures are always stored as a `GcPtr`
+        let value = unsafe { ptr.as_ref() }.clone();
+        Marshal::marshal_from(value, runtime)
+    }
+
+    fn marshal_to_ptr(
+        value: Self,
+        mut ptr: NonNull<Self::MunType>,
+        _runtime: &Runtime,
+        _type_info: Option<&abi::TypeInfo>,
+    ) {
+        unsafe { *ptr.as_mut() = value.into_raw() };
+    }
+}
+
+/// Wrapper for a Mun closure that has been rooted, so it is not garbage collected. A host can
+/// store a `RootedClosure` to invoke it at a later time. To invoke it, obtain a `ClosureRef` for
+/// the `RootedClosure`.
+///
+/// A `RootedClosure` is `Send`, so it can be moved to another thread together with its runtime.
+#[derive(Clone)]
+pub struct RootedClosure {
+    handle: GcRootPtr,
+    runtime: Arc<RwLock<Runtime>>,
+}
+
+impl RootedClosure {
+    /// Creates a `RootedClosure` that wraps a raw Mun closure.
+    fn new(gc: &Arc<GarbageCollector>, runtime: Arc<RwLock<Runtime>>, raw: RawClosure) -> Self {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As the `ClosureRef` that is being rooted holds a shared
+        // reference to `Runtime`, this is safe.
+        assert!(is_closure_type(unsafe {
+            gc.ptr_type(raw.0).into_inner().as_ref()
+        }));
+
+        let handle = GcRootPtr::new(gc, raw.0);
+        Self { handle, runtime }
+    }
+
+    /// Converts the `RootedClosure` into a `ClosureRef`, using an external shared reference to a
+    /// `Runtime`.
+    ///
+    /// # Safety
+    ///
+    /// The `RootedClosure` should have been allocated by the `Runtime`.
+    pub unsafe fn as_ref<'r>(&self, runtime: &'r Runtime) -> ClosureRef<'r> {
+        ClosureRef::new(RawClosure(self.handle.handle()), runtime)
+    }
+
+    /// Borrows the closure's runtime.
+    ///
+    /// The runtime is often already borrowed by the calling thread, so a recursive read lock is
+    /// acquired. This prevents a deadlock when another thread is waiting to acquire a write lock.
+    pub fn borrow_runtime(&self) -> RwLockReadGuard<Runtime> {
+        self.runtime.read_recursive()
+    }
+}
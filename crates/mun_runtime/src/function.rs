@@ -0,0 +1,225 @@
+//! Typed handles to Mun functions.
+//!
+//! Invoking a function through [`invoke_fn!`] looks up the function by name and validates its
+//! signature on every call. A [`FunctionHandle`] does this once, when it is obtained through
+//! [`Runtime::get_fn`], and afterwards only when the runtime has relinked its assemblies.
+
+use crate::{
+    marshal::Marshal,
+    reflection::{
+        equals_argument_type, equals_return_type, ArgumentReflection, ReturnTypeReflection,
+    },
+    Runtime,
+};
+use std::{ffi::c_void, marker::PhantomData};
+
+/// The arguments with which a [`FunctionHandle`] can be called. This trait is implemented for
+/// tuples of marshallable values.
+pub trait FunctionArgs<'a>: Sized {
+    /// Validates the types of the arguments against the types of a function's parameters.
+    ///
+    /// Only the kind of struct, enum, array, and closure arguments is validated, as their exact
+    /// type is only known once a value is passed. This is done by [`FunctionArgs::validate_values`].
+    fn validate_types(arg_types: &[&abi::TypeInfo]) -> Result<(), String>;
+
+    /// Validates the exact types of all struct, enum, array, and closure arguments against the
+    /// types of a function's parameters.
+    fn validate_values(
+        &self,
+        runtime: &Runtime,
+        arg_types: &[&abi::TypeInfo],
+    ) -> Result<(), String>;
+
+    /// Calls the function `fn_ptr` with the arguments.
+    ///
+    /// # Safety
+    ///
+    /// The signature of the function must match the types of the arguments and `Output`.
+    unsafe fn call<Output>(self, fn_ptr: *const c_void, runtime: &Runtime) -> Output;
+}
+
+/// Formats an error that occurred while validating the type of the argument at `idx`.
+fn invalid_argument_type(idx: usize, (expected, found): (&str, &str)) -> String {
+    format!(
+        "Invalid argument type at index {}. Expected: {}. Found: {}.",
+        idx, expected, found,
+    )
+}
+
+macro_rules! count_idents {
+    () => { 0 };
+    ($first:ident $(, $rest:ident)*) => { 1 + count_idents!($($rest),*) };
+}
+
+macro_rules! impl_function_args {
+    ($($T:ident),*) => {
+        impl<'a, $($T: ArgumentReflection + ReturnTypeReflection + Marshal<'a>,)*> FunctionArgs<'a>
+            for ($($T,)*)
+        {
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn validate_types(arg_types: &[&abi::TypeInfo]) -> Result<(), String> {
+                let num_args = count_idents!($($T),*);
+                if arg_types.len() != num_args {
+                    return Err(format!(
+                        "Invalid number of arguments. Expected: {}. Found: {}.",
+                        arg_types.len(),
+                        num_args,
+                    ));
+                }
+
+                let mut idx = 0;
+                $(
+                    equals_return_type::<$T>(arg_types[idx])
+                        .map_err(|err| invalid_argument_type(idx, err))?;
+                    idx += 1;
+                )*
+                Ok(())
+            }
+
+            #[allow(non_snake_case, unused_assignments, unused_mut, unused_variables)]
+            fn validate_values(
+                &self,
+                runtime: &Runtime,
+                arg_types: &[&abi::TypeInfo],
+            ) -> Result<(), String> {
+                let ($($T,)*) = self;
+                let mut idx = 0;
+                $(
+                    // The types of primitive arguments are fully validated by `validate_types`
+                    if !matches!(arg_types[idx].data, abi::TypeInfoData::Primitive) {
+                        equals_argument_type(runtime, arg_types[idx], $T)
+                            .map_err(|err| invalid_argument_type(idx, err))?;
+                    }
+                    idx += 1;
+                )*
+                Ok(())
+            }
+
+            #[allow(non_snake_case, unused_variables)]
+            unsafe fn call<Output>(self, fn_ptr: *const c_void, runtime: &Runtime) -> Output {
+                let function: fn($($T::MunType),*) -> Output = core::mem::transmute(fn_ptr);
+                let ($($T,)*) = self;
+                function($($T.marshal_into(runtime)),*)
+            }
+        }
+    };
+}
+
+impl_function_args!();
+impl_function_args!(A);
+impl_function_args!(A, B);
+impl_function_args!(A, B, C);
+impl_function_args!(A, B, C, D);
+impl_function_args!(A, B, C, D, E);
+impl_function_args!(A, B, C, D, E, F);
+impl_function_args!(A, B, C, D, E, F, G);
+impl_function_args!(A, B, C, D, E, F, G, H);
+impl_function_args!(A, B, C, D, E, F, G, H, I);
+impl_function_args!(A, B, C, D, E, F, G, H, I, J);
+impl_function_args!(A, B, C, D, E, F, G, H, I, J, K);
+impl_function_args!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// A handle to a Mun function with parameters `Args` - a tuple - and return type `Output`,
+/// obtained through [`Runtime::get_fn`].
+///
+/// The handle caches the function and its validated signature. When the runtime relinks its
+/// assemblies - e.g. after a hot reload - the function is looked up and validated again on the
+/// next call, so the handle remains valid for as long as the function exists with a matching
+/// signature.
+///
+/// As calling a handle can update its cache, it requires mutable access. To call a function from
+/// multiple threads, clone the handle for every thread.
+pub struct FunctionHandle<Args, Output> {
+    name: String,
+    /// The link generation of the runtime for which `function` was resolved
+    generation: usize,
+    function: abi::FunctionDefinition,
+    _phantom: PhantomData<fn(Args) -> Output>,
+}
+
+impl<Args, Output> Clone for FunctionHandle<Args, Output> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            generation: self.generation,
+            function: self.function.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, Args, Output> FunctionHandle<Args, Output>
+where
+    Args: FunctionArgs<'a>,
+    Output: ReturnTypeReflection,
+{
+    /// Looks up the function `name` in `runtime` and validates its signature.
+    pub(crate) fn new(runtime: &Runtime, name: &str) -> Result<Self, String> {
+        Ok(Self {
+            name: name.to_owned(),
+            generation: runtime.link_generation(),
+            function: Self::resolve(runtime, name)?,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Looks up the function `name` in `runtime` and validates that its signature matches `Args`
+    /// and `Output`.
+    fn resolve(runtime: &Runtime, name: &str) -> Result<abi::FunctionDefinition, String> {
+        let function_info = runtime
+            .get_function_definition(name)
+            .ok_or_else(|| format!("Failed to obtain function '{}'", name))?;
+
+        let signature = &function_info.prototype.signature;
+        Args::validate_types(signature.arg_types())?;
+
+        if let Some(return_type) = signature.return_type() {
+            equals_return_type::<Output>(return_type)
+        } else if <() as ReturnTypeReflection>::type_guid() != Output::type_guid() {
+            Err((
+                <() as ReturnTypeReflection>::type_name(),
+                Output::type_name(),
+            ))
+        } else {
+            Ok(())
+        }
+        .map_err(|(expected, found)| {
+            format!(
+                "Invalid return type. Expected: {}. Found: {}",
+                expected, found,
+            )
+        })?;
+
+        Ok(function_info.clone())
+    }
+
+    /// Returns the name of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Calls the function with `args`.
+    ///
+    /// If `runtime` relinked its assemblies since the previous call, or if the handle was obtained
+    /// from a different runtime, the function is first looked up and validated again. An error is
+    /// returned if it no longer exists or if its signature changed. The handle remains usable, so
+    /// a subsequent call succeeds once the function has been restored.
+    pub fn call<'r, 'o>(&mut self, runtime: &'r Runtime, args: Args) -> Result<Output, String>
+    where
+        Output: 'o + Marshal<'o>,
+        'r: 'o,
+    {
+        let generation = runtime.link_generation();
+        if self.generation != generation {
+            self.function = Self::resolve(runtime, &self.name)?;
+            self.generation = generation;
+        }
+
+        let signature = &self.function.prototype.signature;
+        args.validate_values(runtime, signature.arg_types())?;
+
+        // Safety: The signature of the function was validated against `Args` and `Output`
+        let result: Output::MunType = unsafe { args.call(self.function.fn_ptr, runtime) };
+        Ok(Marshal::marshal_from(result, runtime))
+    }
+}
@@ -1,5 +1,7 @@
+use abi::HasStaticTypeInfo;
 use memory::{
-    gc::{self, HasIndirectionPtr},
+    gc::{self, ArrayHeader, GcRuntime, HasIndirectionPtr},
+    mapping::{Mapping, MemoryMapper},
     TypeGroup,
 };
 use std::{alloc::Layout, hash::Hash, ptr::NonNull};
@@ -86,30 +88,161 @@ impl memory::TypeFields<UnsafeTypeInfo> for UnsafeTypeInfo {
 unsafe impl Send for UnsafeTypeInfo {}
 unsafe impl Sync for UnsafeTypeInfo {}
 
+/// Returns whether the specified type is stored in memory as a `GcPtr` to a heap-allocated object.
+pub(crate) fn is_gc_ptr(ty: &abi::TypeInfo) -> bool {
+    match &ty.data {
+        abi::TypeInfoData::Struct(s) => s.memory_kind == abi::StructMemoryKind::Gc,
+        abi::TypeInfoData::Array(_) => true,
+        abi::TypeInfoData::Enum(_) => false,
+        abi::TypeInfoData::Primitive => ty.guid == <str as HasStaticTypeInfo>::type_info().guid,
+    }
+}
+
+/// Returns the memory layout of a single element of type `ty`, when stored in an array.
+pub(crate) fn element_layout(ty: &abi::TypeInfo) -> Layout {
+    if is_gc_ptr(ty) {
+        Layout::new::<GcPtr>()
+    } else {
+        Layout::from_size_align(ty.size_in_bytes(), ty.alignment())
+            .unwrap_or_else(|_| panic!("invalid layout from Mun Type: {:?}", ty))
+    }
+}
+
+/// An iterator over the `GcPtr`s that are referenced by an object. Objects stored by value, e.g.
+/// the fields of a value struct, are traced recursively.
 pub struct Trace {
-    obj: GcPtr,
-    ty: UnsafeTypeInfo,
-    index: usize,
+    /// The values that remain to be traced, with the innermost value last
+    stack: Vec<TraceFrame>,
+}
+
+/// A value that contains members, which can be `GcPtr`s or other values.
+enum TraceFrame {
+    /// The fields of a struct
+    Fields {
+        base: *const u8,
+        struct_ty: NonNull<abi::StructInfo>,
+        index: usize,
+    },
+
+    /// The elements of an array
+    Elements {
+        base: *const u8,
+        element_ty: NonNull<abi::TypeInfo>,
+        stride: usize,
+        length: usize,
+        index: usize,
+    },
+}
+
+impl TraceFrame {
+    /// Returns the type and address of the next member of the value.
+    ///
+    /// # Safety
+    ///
+    /// The type information of the value must outlive the frame.
+    unsafe fn next(&mut self) -> Option<(NonNull<abi::TypeInfo>, *const u8)> {
+        match self {
+            TraceFrame::Fields {
+                base,
+                struct_ty,
+                index,
+            } => {
+                let struct_ty = &*struct_ty.as_ptr();
+                let field_ty = *struct_ty.field_types().get(*index)?;
+                let offset = struct_ty.field_offsets()[*index];
+                *index += 1;
+                Some((NonNull::from(field_ty), base.add(offset as usize)))
+            }
+            TraceFrame::Elements {
+                base,
+                element_ty,
+                stride,
+                length,
+                index,
+            } => {
+                if *index >= *length {
+                    return None;
+                }
+                let element = base.add(*index * *stride);
+                *index += 1;
+                Some((*element_ty, element))
+            }
+        }
+    }
+}
+
+impl Trace {
+    /// Constructs a `Trace` for the object `obj` of type `ty`.
+    fn new(obj: GcPtr, ty: &abi::TypeInfo) -> Self {
+        let mut trace = Trace { stack: Vec::new() };
+        let base = unsafe { obj.deref::<u8>() };
+        if let abi::TypeInfoData::Array(array_ty) = &ty.data {
+            let element_ty = array_ty.element_type();
+            let element = element_layout(element_ty);
+            let length = unsafe { (*obj.deref::<ArrayHeader>()).length };
+            let (_, offset) = ArrayHeader::layout(element, length);
+            trace.stack.push(TraceFrame::Elements {
+                base: unsafe { base.add(offset) },
+                element_ty: NonNull::from(element_ty),
+                stride: element.pad_to_align().size(),
+                length,
+                index: 0,
+            });
+        } else {
+            trace.push_value(ty, base);
+        }
+        trace
+    }
+
+    /// Queues the members of the value of type `ty`, stored at `base`, to be traced.
+    fn push_value(&mut self, ty: &abi::TypeInfo, base: *const u8) {
+        match &ty.data {
+            abi::TypeInfoData::Struct(struct_ty) => self.stack.push(TraceFrame::Fields {
+                base,
+                struct_ty: NonNull::from(struct_ty),
+                index: 0,
+            }),
+            abi::TypeInfoData::Enum(enum_ty) => {
+                // Only the fields of the active variant contain valid values
+                let tag = unsafe { *base.cast::<u16>() };
+                if let Some(variant_ty) = enum_ty.variant_infos().get(tag as usize) {
+                    self.stack.push(TraceFrame::Fields {
+                        base,
+                        struct_ty: NonNull::from(variant_ty),
+                        index: 0,
+                    })
+                }
+            }
+            abi::TypeInfoData::Array(_) | abi::TypeInfoData::Primitive => (),
+        }
+    }
 }
 
 impl Iterator for Trace {
     type Item = GcPtr;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let struct_ty = unsafe { self.ty.0.as_ref() }.as_struct()?;
-        let field_count = struct_ty.field_types().len();
-        while self.index < field_count {
-            let index = self.index;
-            self.index += 1;
-
-            let field_ty = struct_ty.field_types()[index];
-            if let Some(field_struct_ty) = field_ty.as_struct() {
-                if field_struct_ty.memory_kind == abi::StructMemoryKind::Gc {
-                    let offset = struct_ty.field_offsets()[index];
-                    return Some(unsafe {
-                        *self.obj.deref::<u8>().add(offset as usize).cast::<GcPtr>()
-                    });
+        while let Some(frame) = self.stack.last_mut() {
+            // Safety: the type information of a traced object outlives its trace
+            let (member_ty, member) = match unsafe { frame.next() } {
+                Some(member) => member,
+                None => {
+                    self.stack.pop();
+                    continue;
                 }
+            };
+
+            let member_ty = unsafe { member_ty.as_ref() };
+            if is_gc_ptr(member_ty) {
+                // Memory is zero-initialized upon allocation, so skip any reference that has not
+                // been assigned yet.
+                let handle = unsafe { *member.cast::<*const GcPtr>() };
+                if !handle.is_null() {
+                    return Some(unsafe { *member.cast::<GcPtr>() });
+                }
+            } else {
+                // Trace the members of values that are stored inline
+                self.push_value(member_ty, member);
             }
         }
         None
@@ -124,12 +257,7 @@ impl memory::TypeMemory for UnsafeTypeInfo {
     }
 
     fn is_stack_allocated(&self) -> bool {
-        unsafe {
-            self.0
-                .as_ref()
-                .as_struct()
-                .map_or(true, |s| s.memory_kind == abi::StructMemoryKind::Value)
-        }
+        !is_gc_ptr(unsafe { self.0.as_ref() })
     }
 }
 
@@ -137,16 +265,157 @@ impl gc::TypeTrace for UnsafeTypeInfo {
     type Trace = Trace;
 
     fn trace(&self, obj: GcPtr) -> Self::Trace {
-        Trace {
-            ty: *self,
-            obj,
-            index: 0,
-        }
+        Trace::new(obj, unsafe { self.0.as_ref() })
+    }
+}
+
+/// The garbage collection strategy used by a [`Runtime`](crate::Runtime).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GcStrategy {
+    /// A stop-the-world mark-sweep collector that traces the entire heap on every collection.
+    MarkSweep,
+
+    /// A generational collector that can collect short-lived objects without sweeping the
+    /// objects that survived earlier collections.
+    Generational,
+}
+
+impl Default for GcStrategy {
+    fn default() -> Self {
+        GcStrategy::MarkSweep
     }
 }
 
 /// Defines the garbage collector used by the `Runtime`.
-pub type GarbageCollector = gc::MarkSweep<UnsafeTypeInfo, gc::NoopObserver<gc::Event>>;
+pub enum GarbageCollector {
+    MarkSweep(gc::MarkSweep<UnsafeTypeInfo, gc::NoopObserver<gc::Event>>),
+    Generational(gc::Generational<UnsafeTypeInfo, gc::NoopObserver<gc::Event>>),
+}
+
+impl GarbageCollector {
+    /// Constructs a new garbage collector that implements the specified `strategy`, and collects
+    /// memory according to `policy`.
+    pub fn new(strategy: GcStrategy, policy: gc::CollectionPolicy) -> Self {
+        let gc = match strategy {
+            GcStrategy::MarkSweep => GarbageCollector::MarkSweep(Default::default()),
+            GcStrategy::Generational => GarbageCollector::Generational(Default::default()),
+        };
+        gc.set_policy(policy);
+        gc
+    }
+
+    /// Returns the policy that determines when a collection is due
+    pub fn policy(&self) -> gc::CollectionPolicy {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.policy(),
+            GarbageCollector::Generational(gc) => gc.policy(),
+        }
+    }
+
+    /// Sets the policy that determines when a collection is due
+    pub fn set_policy(&self, policy: gc::CollectionPolicy) {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.set_policy(policy),
+            GarbageCollector::Generational(gc) => gc.set_policy(policy),
+        }
+    }
+
+    /// Collects all memory that is no longer referenced by rooted objects. Returns `true` if memory
+    /// was reclaimed, `false` otherwise.
+    pub fn collect(&self) -> bool {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.collect(),
+            GarbageCollector::Generational(gc) => gc.collect(),
+        }
+    }
+
+    /// Collects the memory of recently allocated objects that are no longer referenced. Returns
+    /// `true` if memory was reclaimed, `false` otherwise. Collectors without generations perform a
+    /// full collection.
+    pub fn collect_minor(&self) -> bool {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.collect(),
+            GarbageCollector::Generational(gc) => gc.collect_minor(),
+        }
+    }
+
+    /// Returns a snapshot of all objects that are currently allocated.
+    pub fn snapshot(&self) -> gc::HeapSnapshot<UnsafeTypeInfo> {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.snapshot(),
+            GarbageCollector::Generational(gc) => gc.snapshot(),
+        }
+    }
+
+    /// Collects memory if a collection is due according to the collection policy. Returns `true`
+    /// if memory was reclaimed, `false` otherwise.
+    pub fn collect_if_due(&self) -> bool {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.collect_if_due(),
+            GarbageCollector::Generational(gc) => gc.collect_if_due(),
+        }
+    }
+}
+
+impl GcRuntime<UnsafeTypeInfo> for GarbageCollector {
+    fn alloc(&self, ty: UnsafeTypeInfo) -> GcPtr {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.alloc(ty),
+            GarbageCollector::Generational(gc) => gc.alloc(ty),
+        }
+    }
+
+    fn alloc_array(&self, ty: UnsafeTypeInfo, element: Layout, length: usize) -> GcPtr {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.alloc_array(ty, element, length),
+            GarbageCollector::Generational(gc) => gc.alloc_array(ty, element, length),
+        }
+    }
+
+    fn ptr_type(&self, obj: GcPtr) -> UnsafeTypeInfo {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.ptr_type(obj),
+            GarbageCollector::Generational(gc) => gc.ptr_type(obj),
+        }
+    }
+
+    fn root(&self, obj: GcPtr) {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.root(obj),
+            GarbageCollector::Generational(gc) => gc.root(obj),
+        }
+    }
+
+    fn unroot(&self, obj: GcPtr) {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.unroot(obj),
+            GarbageCollector::Generational(gc) => gc.unroot(obj),
+        }
+    }
+
+    fn stats(&self) -> gc::Stats {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.stats(),
+            GarbageCollector::Generational(gc) => gc.stats(),
+        }
+    }
+
+    fn write_barrier(&self, obj: GcPtr) {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.write_barrier(obj),
+            GarbageCollector::Generational(gc) => gc.write_barrier(obj),
+        }
+    }
+}
+
+impl MemoryMapper<UnsafeTypeInfo> for GarbageCollector {
+    fn map_memory(&self, mapping: Mapping<UnsafeTypeInfo, UnsafeTypeInfo>) -> Vec<GcPtr> {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.map_memory(mapping),
+            GarbageCollector::Generational(gc) => gc.map_memory(mapping),
+        }
+    }
+}
 
 pub use gc::GcPtr;
 pub type GcRootPtr = gc::GcRootPtr<UnsafeTypeInfo, GarbageCollector>;
@@ -10,38 +10,57 @@ mod macros;
 #[macro_use]
 mod garbage_collector;
 mod adt;
+mod array;
+mod closure;
+mod function;
 mod marshal;
+mod migration;
 mod reflection;
+mod reload;
+mod string;
+mod value;
 
 use anyhow::Result;
 use garbage_collector::GarbageCollector;
 use log::{debug, error, info};
-use memory::gc::{self, GcRuntime};
+use memory::{
+    gc::{self, GcRuntime},
+    TypeDesc,
+};
 use mun_project::LOCKFILE_NAME;
 use notify::{RawEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::{Mutex, RwLock};
+use reload::ReloadCallbacks;
 use rustc_hash::FxHashMap;
 use std::{
-    cell::RefCell,
     collections::{HashMap, VecDeque},
     ffi, io, mem,
+    panic::{self, AssertUnwindSafe},
     path::{Path, PathBuf},
     ptr::NonNull,
-    rc::Rc,
     string::ToString,
     sync::{
+        atomic::{AtomicU16, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering},
         mpsc::{channel, Receiver},
         Arc,
     },
 };
 
 pub use crate::{
-    adt::{RootedStruct, StructRef},
+    adt::{EnumRef, RawEnum, RootedStruct, StructRef},
+    array::{ArrayRef, RawArray},
     assembly::Assembly,
-    garbage_collector::UnsafeTypeInfo,
+    closure::{ClosureArgs, ClosureRef, RawClosure, RootedClosure},
+    function::{FunctionArgs, FunctionHandle},
+    garbage_collector::{GcStrategy, UnsafeTypeInfo},
     marshal::Marshal,
     reflection::{ArgumentReflection, ReturnTypeReflection},
+    reload::{ReloadEvent, ReloadFailedEvent},
+    string::RawString,
+    value::Value,
 };
 pub use abi::IntoFunctionDefinition;
+pub use memory::gc::{CollectionPolicy, GcPtr, HeapSnapshot, ObjectSnapshot, TypeSummary};
 
 /// Options for the construction of a [`Runtime`].
 pub struct RuntimeOptions {
@@ -49,6 +68,10 @@ pub struct RuntimeOptions {
     pub library_path: PathBuf,
     /// Custom user injected functions
     pub user_functions: Vec<(abi::FunctionDefinition, abi::FunctionDefinitionStorage)>,
+    /// The garbage collection strategy
+    pub gc_strategy: GcStrategy,
+    /// The policy that determines when memory is automatically collected
+    pub gc_policy: CollectionPolicy,
 }
 
 /// Retrieve the allocator using the provided handle.
@@ -79,6 +102,90 @@ extern "C" fn new(
     handle.into()
 }
 
+extern "C" fn new_string(
+    bytes: *const u8,
+    length: usize,
+    alloc_handle: *mut ffi::c_void,
+) -> *const *mut ffi::c_void {
+    // Safety: `new_string` is only called from within Mun assemblies' core logic, so we are
+    // guaranteed that the `Runtime` and its `GarbageCollector` still exist if this function is
+    // called, and will continue to do so for the duration of this function.
+    let allocator = unsafe { get_allocator(alloc_handle) };
+    // Safety: the Mun Compiler guarantees that `bytes` points to `length` valid UTF-8 bytes.
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, length) };
+    let handle = string::alloc_string(allocator.as_ref(), bytes);
+
+    // Prevent destruction of the allocator
+    mem::forget(allocator);
+
+    handle.into()
+}
+
+extern "C" fn new_array(
+    type_info: *const abi::TypeInfo,
+    length: usize,
+    alloc_handle: *mut ffi::c_void,
+) -> *const *mut ffi::c_void {
+    // Safety: `new_array` is only called from within Mun assemblies' core logic, so we are
+    // guaranteed that the `Runtime` and its `GarbageCollector` still exist if this function is
+    // called, and will continue to do so for the duration of this function.
+    let allocator = unsafe { get_allocator(alloc_handle) };
+    // Safety: the Mun Compiler guarantees that `new_array` is never called with `ptr::null()`.
+    let type_info = UnsafeTypeInfo::new(unsafe { NonNull::new_unchecked(type_info as *mut _) });
+    let handle = array::alloc_array(allocator.as_ref(), type_info, length);
+
+    // Prevent destruction of the allocator
+    mem::forget(allocator);
+
+    handle.into()
+}
+
+extern "C" fn panic_index_out_of_bounds(index: usize, length: usize) {
+    // Unwinding across the FFI boundary is undefined behavior, so abort instead
+    eprintln!(
+        "index out of bounds: the len is {} but the index is {}",
+        length, index
+    );
+    std::process::abort();
+}
+
+extern "C" fn write_barrier(obj: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) {
+    // Safety: `write_barrier` is only called from within Mun assemblies' core logic, so we are
+    // guaranteed that the `Runtime` and its `GarbageCollector` still exist if this function is
+    // called, and will continue to do so for the duration of this function.
+    let allocator = unsafe { get_allocator(alloc_handle) };
+    allocator.write_barrier(obj.into());
+
+    // Prevent destruction of the allocator
+    mem::forget(allocator);
+}
+
+/// Atomically loads the value of `size` bytes at `ptr`, the same way Mun functions load the value
+/// of a `static`. The value is stored at the start of the returned integer.
+///
+/// # Safety
+///
+/// `ptr` must point to a valid value of `size` bytes that is aligned to its size, and `size` must
+/// be 1, 2, 4, or 8.
+unsafe fn atomic_load(ptr: *const ffi::c_void, size: usize) -> u64 {
+    let mut value = 0u64;
+    let value_ptr: *mut u64 = &mut value;
+    match size {
+        1 => value_ptr
+            .cast::<u8>()
+            .write((*ptr.cast::<AtomicU8>()).load(Ordering::SeqCst)),
+        2 => value_ptr
+            .cast::<u16>()
+            .write((*ptr.cast::<AtomicU16>()).load(Ordering::SeqCst)),
+        4 => value_ptr
+            .cast::<u32>()
+            .write((*ptr.cast::<AtomicU32>()).load(Ordering::SeqCst)),
+        8 => value_ptr.write((*ptr.cast::<AtomicU64>()).load(Ordering::SeqCst)),
+        _ => unreachable!("a `static` cannot have a size of {} bytes", size),
+    }
+    value
+}
+
 /// A builder for the [`Runtime`].
 pub struct RuntimeBuilder {
     options: RuntimeOptions,
@@ -91,6 +198,8 @@ impl RuntimeBuilder {
             options: RuntimeOptions {
                 library_path: library_path.into(),
                 user_functions: Default::default(),
+                gc_strategy: Default::default(),
+                gc_policy: Default::default(),
             },
         }
     }
@@ -105,9 +214,30 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Sets the garbage collection strategy used by the [`Runtime`]. Defaults to
+    /// [`GcStrategy::MarkSweep`].
+    pub fn set_gc_strategy(mut self, strategy: GcStrategy) -> Self {
+        self.options.gc_strategy = strategy;
+        self
+    }
+
+    /// Sets the policy that determines when the [`Runtime`] automatically collects memory.
+    /// Defaults to [`CollectionPolicy::Manual`].
+    ///
+    /// Objects that are only referenced from the stack of Mun functions are not rooted, so memory
+    /// is only collected during [`Runtime::update`], when no Mun functions are being executed.
+    pub fn set_gc_policy(mut self, policy: CollectionPolicy) -> Self {
+        self.options.gc_policy = policy;
+        self
+    }
+
     /// Spawns a [`Runtime`] with the builder's options.
-    pub fn spawn(self) -> anyhow::Result<Rc<RefCell<Runtime>>> {
-        Runtime::new(self.options).map(|runtime| Rc::new(RefCell::new(runtime)))
+    ///
+    /// The returned handle can be shared between threads. Any number of threads can concurrently
+    /// invoke functions while holding a read lock, whereas [`Runtime::update`] requires a write
+    /// lock.
+    pub fn spawn(self) -> anyhow::Result<Arc<RwLock<Runtime>>> {
+        Runtime::new(self.options).map(|runtime| Arc::new(RwLock::new(runtime)))
     }
 }
 
@@ -189,16 +319,74 @@ impl DispatchTable {
 ///
 /// [log]: https://docs.rs/log
 /// [log-impl]: https://docs.rs/log/0.4.13/log/#available-logging-implementations
+///
+/// # Multithreading
+///
+/// A `Runtime` is `Send` and `Sync`. Functions only require a shared reference to the runtime, so
+/// they can be invoked from multiple threads at the same time. Hot reloading and garbage
+/// collection require an exclusive reference, which guarantees that no functions are executing
+/// while assemblies are being relinked or while memory that is only referenced from the stack of a
+/// Mun function is being collected.
+///
+/// All loads and stores of a `static` are atomic, both in Mun functions and in
+/// [`Runtime::get_global`], so functions that access the same `static` can run concurrently.
+/// Mun functions are not otherwise synchronized with each other: a compound assignment such as
+/// `COUNTER += 1` loads and stores the `static` separately, so concurrent updates can be lost.
+///
+/// # Migrating objects
+///
+/// When the type of a struct changes during a hot reload, existing objects are converted by
+/// field name: fields that still exist are copied or cast to their new type, and new fields are
+/// zero-initialized. To carry over values that cannot be derived this way - e.g. when a field is
+/// split into two - a script can declare a migration function for the struct: a public associated
+/// function named `migrate`, that receives a struct with the fields of the old type, followed by
+/// the struct itself.
+///
+/// ```mun
+/// // The fields of `Shape` before it was changed
+/// pub struct ShapeV1 {
+///     radius: f32,
+/// }
+///
+/// pub struct Shape {
+///     width: f32,
+///     height: f32,
+/// }
+///
+/// impl Shape {
+///     pub fn migrate(old: ShapeV1, new: Shape) {
+///         new.width = old.radius * 2.0;
+///         new.height = old.radius * 2.0;
+///     }
+/// }
+/// ```
+///
+/// The function is called for every converted object, after its fields have been converted by
+/// name. If the fields of its first parameter do not match the old type, the migration is skipped.
+/// The first argument is a copy of the old object and the second argument is the converted object
+/// itself, so both remain valid if they are stored.
 pub struct Runtime {
     assemblies: HashMap<PathBuf, Assembly>,
     /// Assemblies that have changed and thus need to be relinked. Maps the old to the (potentially) new path.
     assemblies_to_relink: VecDeque<(PathBuf, PathBuf)>,
     dispatch_table: DispatchTable,
     watcher: RecommendedWatcher,
-    watcher_rx: Receiver<RawEvent>,
+    watcher_rx: Mutex<Receiver<RawEvent>>,
     renamed_files: HashMap<u32, PathBuf>,
     gc: Arc<GarbageCollector>,
     _user_functions: Vec<abi::FunctionDefinitionStorage>,
+    /// Identifies the current dispatch table. It changes whenever assemblies are relinked and is
+    /// unique across all runtimes.
+    link_generation: usize,
+    reload_callbacks: Mutex<ReloadCallbacks>,
+}
+
+/// The next link generation of any runtime.
+static NEXT_LINK_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a new, unique link generation.
+fn next_link_generation() -> usize {
+    NEXT_LINK_GENERATION.fetch_add(1, Ordering::Relaxed)
 }
 
 impl Runtime {
@@ -215,6 +403,28 @@ impl Runtime {
             new as extern "C" fn(*const abi::TypeInfo, *mut ffi::c_void) -> *const *mut ffi::c_void,
             "new",
         ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            new_string
+                as extern "C" fn(*const u8, usize, *mut ffi::c_void) -> *const *mut ffi::c_void,
+            "new_string",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            new_array
+                as extern "C" fn(
+                    *const abi::TypeInfo,
+                    usize,
+                    *mut ffi::c_void,
+                ) -> *const *mut ffi::c_void,
+            "new_array",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            panic_index_out_of_bounds as extern "C" fn(usize, usize),
+            "panic_index_out_of_bounds",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            write_barrier as extern "C" fn(*const *mut ffi::c_void, *mut ffi::c_void),
+            "write_barrier",
+        ));
 
         let mut storages = Vec::with_capacity(options.user_functions.len());
         for (info, storage) in options.user_functions.into_iter() {
@@ -228,10 +438,15 @@ impl Runtime {
             assemblies_to_relink: VecDeque::new(),
             dispatch_table,
             watcher,
-            watcher_rx: rx,
+            watcher_rx: Mutex::new(rx),
             renamed_files: HashMap::new(),
-            gc: Arc::new(self::garbage_collector::GarbageCollector::default()),
+            gc: Arc::new(GarbageCollector::new(
+                options.gc_strategy,
+                options.gc_policy,
+            )),
             _user_functions: storages,
+            link_generation: next_link_generation(),
+            reload_callbacks: Mutex::new(ReloadCallbacks::default()),
         };
 
         runtime.add_assembly(&options.library_path)?;
@@ -299,6 +514,50 @@ impl Runtime {
         self.dispatch_table.get_fn(function_name)
     }
 
+    /// Invokes the function `function_name` with dynamically typed `args`. In contrast to
+    /// [`invoke_fn!`], the arguments and return type are not known at compile time, but are
+    /// checked against the function's signature when it is invoked.
+    ///
+    /// 128-bit integers cannot be passed to or returned from functions that are invoked this way.
+    pub fn invoke<'r>(
+        &'r self,
+        function_name: &str,
+        args: &[Value<'r>],
+    ) -> Result<Value<'r>, String> {
+        let function_info = self
+            .get_function_definition(function_name)
+            .ok_or_else(|| format!("Failed to obtain function '{}'", function_name))?;
+        value::invoke(self, function_info, args)
+    }
+
+    /// Retrieves a handle to the function `function_name` with parameters `Args` - a tuple - and
+    /// return type `Output`. The signature of the function is validated once, after which the
+    /// handle can be called without looking up the function by name. The handle remains valid
+    /// across hot reloads, as the function is looked up again when assemblies have been relinked.
+    ///
+    /// ```ignore
+    /// let mut fibonacci = runtime.get_fn::<(i64,), i64>("fibonacci")?;
+    /// loop {
+    ///     let result = fibonacci.call(&runtime, (10,))?;
+    ///     runtime.update();
+    /// }
+    /// ```
+    pub fn get_fn<'a, Args, Output>(
+        &self,
+        function_name: &str,
+    ) -> Result<FunctionHandle<Args, Output>, String>
+    where
+        Args: FunctionArgs<'a>,
+        Output: ReturnTypeReflection,
+    {
+        FunctionHandle::new(self, function_name)
+    }
+
+    /// Returns the link generation of the runtime, which changes whenever assemblies are relinked.
+    pub(crate) fn link_generation(&self) -> usize {
+        self.link_generation
+    }
+
     /// Retrieves the type definition corresponding to `type_name`, if available.
     pub fn get_type_info(&self, type_name: &str) -> Option<&abi::TypeInfo> {
         for assembly in self.assemblies.values() {
@@ -312,14 +571,97 @@ impl Runtime {
         None
     }
 
-    /// Updates the state of the runtime. This includes checking for file changes, and reloading
-    /// compiled assemblies.
+    /// Retrieves the global (a `static` or an exported `const`) corresponding to `name`, if
+    /// available.
+    fn get_global_info(&self, name: &str) -> Option<&abi::GlobalInfo> {
+        self.assemblies
+            .values()
+            .find_map(|assembly| assembly.info().symbols.get_global(name))
+    }
+
+    /// Retrieves the value of the global (a `static` or an exported `const`) corresponding to
+    /// `name`.
+    pub fn get_global<'r, T: ReturnTypeReflection + Marshal<'r>>(
+        &'r self,
+        name: &str,
+    ) -> Result<T, String>
+    where
+        T: 'r,
+    {
+        let global = self
+            .get_global_info(name)
+            .ok_or_else(|| format!("Global `{}` does not exist.", name))?;
+
+        let type_info = global.type_info();
+        reflection::equals_return_type::<T>(type_info).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}`. Expected: `{}`. Found: `{}`.",
+                name, expected, found,
+            )
+        })?;
+
+        if global.is_mutable() {
+            // Functions that are executing on other threads can write to the `static` at the same
+            // time, so its value is loaded atomically, just like Mun functions do.
+            // Safety: the Mun Compiler guarantees that a `static` has a primitive type and that its
+            // value is aligned to its size.
+            let value = unsafe { atomic_load(global.value_ptr(), type_info.size_in_bytes()) };
+            let value_ptr = NonNull::from(&value).cast::<T::MunType>();
+            Ok(Marshal::marshal_from_ptr(value_ptr, self, Some(type_info)))
+        } else {
+            // Safety: the Mun Compiler guarantees that the value of a global is never `null`.
+            let value_ptr =
+                unsafe { NonNull::new_unchecked(global.value_ptr().cast::<T::MunType>()) };
+            Ok(Marshal::marshal_from_ptr(value_ptr, self, Some(type_info)))
+        }
+    }
+
+    /// Sets the value of the `static` corresponding to `name`. The value of a `const` cannot be
+    /// changed.
+    pub fn set_global<'r, T: ArgumentReflection + Marshal<'r>>(
+        &'r mut self,
+        name: &str,
+        value: T,
+    ) -> Result<(), String> {
+        let runtime: &'r Runtime = self;
+        let global = runtime
+            .get_global_info(name)
+            .ok_or_else(|| format!("Global `{}` does not exist.", name))?;
+
+        if !global.is_mutable() {
+            return Err(format!("Cannot assign to constant `{}`.", name));
+        }
+
+        let type_info = global.type_info();
+        reflection::equals_argument_type(runtime, type_info, &value).map_err(
+            |(expected, found)| {
+                format!(
+                    "Mismatched types for `{}`. Expected: `{}`. Found: `{}`.",
+                    name, expected, found,
+                )
+            },
+        )?;
+
+        // Safety: the Mun Compiler guarantees that the value of a global is never `null`.
+        let value_ptr = unsafe { NonNull::new_unchecked(global.value_ptr().cast::<T::MunType>()) };
+        Marshal::marshal_to_ptr(value, value_ptr, runtime, Some(type_info));
+        Ok(())
+    }
+
+    /// Updates the state of the runtime. This includes checking for file changes, reloading
+    /// compiled assemblies, and collecting memory if a collection is due according to the
+    /// runtime's [`CollectionPolicy`].
+    ///
+    /// Returns `true` if assemblies were reloaded, and `false` otherwise. This includes assemblies
+    /// that were reverted because the health check set with [`Runtime::set_health_check`] failed.
     pub fn update(&mut self) -> bool {
         fn is_lockfile(path: &Path) -> bool {
             path.file_name().expect("Invalid file path.") == LOCKFILE_NAME
         }
 
-        fn relink_assemblies(runtime: &mut Runtime) -> anyhow::Result<DispatchTable> {
+        fn relink_assemblies(
+            runtime: &mut Runtime,
+        ) -> anyhow::Result<(DispatchTable, ReloadEvent)> {
             let mut loaded = HashMap::new();
             let to_load = &mut runtime.assemblies_to_relink;
 
@@ -369,7 +711,9 @@ impl Runtime {
             )
         }
 
-        while let Ok(event) = self.watcher_rx.try_recv() {
+        self.gc.collect_if_due();
+
+        while let Ok(event) = self.watcher_rx.get_mut().try_recv() {
             if let Some(path) = event.path {
                 let op = event.op.expect("Invalid event.");
 
@@ -380,16 +724,32 @@ impl Runtime {
                     if op.contains(notify::op::REMOVE) {
                         debug!("Lockfile deleted");
 
+                        let assemblies = self
+                            .assemblies_to_relink
+                            .iter()
+                            .map(|(_, new_path)| new_path.clone())
+                            .collect();
+
                         match relink_assemblies(self) {
-                            Ok(table) => {
+                            Ok((table, event)) => {
                                 info!("Succesfully reloaded assemblies.");
 
                                 self.dispatch_table = table;
+                                self.link_generation = next_link_generation();
                                 self.assemblies_to_relink.clear();
 
-                                return true;
+                                return self.finish_reload(event);
+                            }
+                            Err(e) => {
+                                error!("Failed to relink assemblies, due to {}.", e);
+
+                                self.reload_callbacks
+                                    .get_mut()
+                                    .reload_failed(&ReloadFailedEvent {
+                                        assemblies,
+                                        error: e.to_string(),
+                                    });
                             }
-                            Err(e) => error!("Failed to relink assemblies, due to {}.", e),
                         }
                     }
                 } else {
@@ -416,6 +776,125 @@ impl Runtime {
         false
     }
 
+    /// Registers a `callback` that is invoked by [`Runtime::update`] after assemblies have been
+    /// hot reloaded, describing the assemblies, functions, and types that changed.
+    ///
+    /// The callback is invoked while the runtime is mutably borrowed, so it cannot invoke
+    /// functions. To e.g. re-run initialization code, record the event and act on it after
+    /// [`Runtime::update`] returns.
+    pub fn on_reload(&mut self, callback: impl FnMut(&ReloadEvent) + Send + 'static) {
+        self.reload_callbacks
+            .get_mut()
+            .add_on_reload(Box::new(callback));
+    }
+
+    /// Registers a `callback` that is invoked by [`Runtime::update`] when hot reloading assemblies
+    /// fails, describing the assemblies and the error. The runtime keeps running the previously
+    /// loaded assemblies.
+    pub fn on_reload_failed(&mut self, callback: impl FnMut(&ReloadFailedEvent) + Send + 'static) {
+        self.reload_callbacks
+            .get_mut()
+            .add_on_reload_failed(Box::new(callback));
+    }
+
+    /// Sets a `health_check` that is invoked by [`Runtime::update`] after assemblies have been hot
+    /// reloaded. If it returns `false` or panics, the reloaded assemblies are reverted to their
+    /// last known good version and the callbacks registered with [`Runtime::on_reload_failed`]
+    /// are invoked.
+    pub fn set_health_check(
+        &mut self,
+        health_check: impl FnMut(&Runtime) -> bool + Send + 'static,
+    ) {
+        self.reload_callbacks
+            .get_mut()
+            .set_health_check(Box::new(health_check));
+    }
+
+    /// Checks the health of the runtime after assemblies have been hot reloaded, reverting them if
+    /// the runtime is unhealthy, and notifies the registered callbacks. Returns whether the
+    /// reloaded assemblies were kept.
+    fn finish_reload(&mut self, event: ReloadEvent) -> bool {
+        if self.check_health() {
+            self.reload_callbacks.get_mut().reloaded(&event);
+            return true;
+        }
+
+        let error = match self.revert_assemblies(&event.assemblies) {
+            Ok(_) => "Health check failed. Reverted to the last known good assemblies.".to_string(),
+            Err(e) => format!(
+                "Health check failed. Failed to revert assemblies, due to {}.",
+                e
+            ),
+        };
+        error!("{}", error);
+
+        self.reload_callbacks
+            .get_mut()
+            .reload_failed(&ReloadFailedEvent {
+                assemblies: event.assemblies,
+                error,
+            });
+        false
+    }
+
+    /// Returns whether the runtime is healthy according to its health check. The runtime is
+    /// considered unhealthy if the health check panics.
+    fn check_health(&mut self) -> bool {
+        let mut health_check = match self.reload_callbacks.get_mut().health_check.take() {
+            Some(health_check) => health_check,
+            None => return true,
+        };
+
+        let runtime = &*self;
+        let healthy =
+            panic::catch_unwind(AssertUnwindSafe(|| health_check(runtime))).unwrap_or(false);
+
+        self.reload_callbacks.get_mut().health_check = Some(health_check);
+        healthy
+    }
+
+    /// Reverts all assemblies that were hot reloaded to their last known good version: the
+    /// version that was loaded before their last hot reload. The state of the assemblies'
+    /// `static`s and objects is converted back to the last known good version.
+    ///
+    /// Only a single version is retained per assembly, so an assembly cannot be reverted twice
+    /// without being reloaded in between.
+    pub fn revert(&mut self) -> anyhow::Result<ReloadEvent> {
+        let library_paths: Vec<PathBuf> = self
+            .assemblies
+            .iter()
+            .filter(|(_, assembly)| assembly.has_last_known_good())
+            .map(|(library_path, _)| library_path.clone())
+            .collect();
+
+        if library_paths.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No assemblies have a last known good version."
+            ));
+        }
+
+        self.revert_assemblies(&library_paths)
+    }
+
+    /// Reverts the assembly corresponding to the library at `library_path` to its last known good
+    /// version. See [`Runtime::revert`].
+    pub fn revert_assembly(&mut self, library_path: &Path) -> anyhow::Result<ReloadEvent> {
+        let library_path = library_path
+            .canonicalize()
+            .unwrap_or_else(|_| library_path.to_path_buf());
+        self.revert_assemblies(&[library_path])
+    }
+
+    /// Reverts the assemblies corresponding to `library_paths` to their last known good version.
+    fn revert_assemblies(&mut self, library_paths: &[PathBuf]) -> anyhow::Result<ReloadEvent> {
+        let (table, event) =
+            Assembly::revert_all(library_paths, &mut self.assemblies, &self.dispatch_table)?;
+
+        self.dispatch_table = table;
+        self.link_generation = next_link_generation();
+        Ok(event)
+    }
+
     /// Returns a shared reference to the runtime's garbage collector.
     ///
     /// We cannot return an `Arc` here, because the lifetime of data contained in `GarbageCollector`
@@ -426,10 +905,41 @@ impl Runtime {
 
     /// Collects all memory that is no longer referenced by rooted objects. Returns `true` if memory
     /// was reclaimed, `false` otherwise. This behavior will likely change in the future.
-    pub fn gc_collect(&self) -> bool {
+    pub fn gc_collect(&mut self) -> bool {
         self.gc.collect()
     }
 
+    /// Collects the memory of recently allocated objects that are no longer referenced by rooted
+    /// objects. Returns `true` if memory was reclaimed, `false` otherwise.
+    ///
+    /// With the [`GcStrategy::Generational`] strategy, this is cheaper than
+    /// [`Runtime::gc_collect`]: only objects allocated since the last collection are swept, and
+    /// only those objects and the older objects that were written to since the last collection are
+    /// traced. Other strategies perform a full collection.
+    pub fn gc_collect_minor(&mut self) -> bool {
+        self.gc.collect_minor()
+    }
+
+    /// Returns the policy that determines when memory is automatically collected.
+    pub fn gc_policy(&self) -> CollectionPolicy {
+        self.gc.policy()
+    }
+
+    /// Sets the policy that determines when memory is automatically collected.
+    pub fn set_gc_policy(&self, policy: CollectionPolicy) {
+        self.gc.set_policy(policy)
+    }
+
+    /// Returns a snapshot of all objects that are currently allocated by the garbage collector, in
+    /// which the type of every object is identified by its name.
+    ///
+    /// The snapshot can be used to find which types occupy the most memory
+    /// ([`HeapSnapshot::summary`]), to find the chain of references that keeps an object alive
+    /// ([`HeapSnapshot::retaining_path`]), or be exported as JSON ([`HeapSnapshot::to_json`]).
+    pub fn gc_snapshot(&self) -> HeapSnapshot<String> {
+        self.gc.snapshot().map_types(|ty| ty.name().to_string())
+    }
+
     /// Returns statistics about the garbage collector.
     pub fn gc_stats(&self) -> gc::Stats {
         self.gc.stats()
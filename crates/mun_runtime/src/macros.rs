@@ -177,7 +177,7 @@ macro_rules! invoke_fn_impl {
                             let function: fn($($T::MunType),*) -> Output::MunType = unsafe {
                                 core::mem::transmute(function_info.fn_ptr)
                             };
-                            let result = function($($Arg.marshal_into()),*);
+                            let result = function($($Arg.marshal_into(runtime)),*);
 
                             // Marshall the result
                             return Ok(Marshal::marshal_from(result, runtime))
@@ -193,11 +193,17 @@ macro_rules! invoke_fn_impl {
 /// Invokes a runtime function and returns a [`Result`] that contains either the output value or
 /// an error that can be used to retry the function invocation.
 ///
-/// The first argument `invoke_fn` receives is a `Ref<Runtime>` and the second argument is a
-/// function string. This must be a `&str`.
+/// The first argument `invoke_fn` receives is a `RwLockReadGuard<Runtime>` and the second argument
+/// is a function string. This must be a `&str`.
 ///
 /// Additional parameters passed to `invoke_fn` are the arguments of the function in the order
 /// given.
+///
+/// At most 15 arguments can be passed. To invoke functions with more arguments, or functions of
+/// which the signature is not known at compile time, use [`Runtime::invoke`].
+///
+/// Functions can be invoked from multiple threads at the same time. See [`Runtime`] for how they
+/// interact.
 #[macro_export]
 macro_rules! invoke_fn {
     ($Runtime:expr, $FnName:expr) => {
@@ -0,0 +1,152 @@
+//! Migration functions declared by scripts, which are run for objects of which the type is
+//! converted during a hot reload. See the `Runtime` documentation for details.
+
+use crate::garbage_collector::{GarbageCollector, GcRootPtr, UnsafeTypeInfo};
+use abi::AssemblyInfo;
+use log::warn;
+use memory::{
+    gc::{GcRuntime, HasIndirectionPtr, RawGcPtr},
+    mapping::{Mapping, Migration},
+};
+use std::{ffi::c_void, ptr::NonNull, sync::Arc};
+
+/// The name of the associated function through which a struct declares its migration.
+const MIGRATION_FN_NAME: &str = "migrate";
+
+/// A migration function declared by an assembly.
+pub(crate) struct MigrationFn<'a> {
+    name: &'a str,
+    /// The type that describes the fields of the old type
+    old_ty: &'a abi::TypeInfo,
+    new_ty: &'a abi::TypeInfo,
+    fn_ptr: *const c_void,
+}
+
+impl<'a> MigrationFn<'a> {
+    /// Returns whether `type_info` describes the fields of an old type, in which case it should not
+    /// be mapped to types of the old assembly.
+    pub fn is_old_type(&self, type_info: &abi::TypeInfo) -> bool {
+        self.old_ty.guid == type_info.guid
+    }
+}
+
+/// Returns whether `type_info` describes a garbage collected struct, which Mun functions receive
+/// as a handle.
+fn is_gc_struct(type_info: &abi::TypeInfo) -> bool {
+    type_info
+        .as_struct()
+        .map_or(false, |s| s.memory_kind == abi::StructMemoryKind::Gc)
+}
+
+/// Returns whether the structs `a` and `b` have the same fields, with the same types and at the
+/// same offsets.
+fn has_same_fields(a: &abi::TypeInfo, b: &abi::TypeInfo) -> bool {
+    match (a.as_struct(), b.as_struct()) {
+        (Some(a), Some(b)) => {
+            a.field_names().eq(b.field_names())
+                && a.field_offsets() == b.field_offsets()
+                && a.field_types()
+                    .iter()
+                    .zip(b.field_types().iter())
+                    .all(|(a, b)| a.guid == b.guid)
+        }
+        _ => false,
+    }
+}
+
+/// Returns the migration functions declared by the assembly `info`: the associated `migrate`
+/// functions of its structs.
+pub(crate) fn migration_fns(info: &AssemblyInfo) -> Vec<MigrationFn<'_>> {
+    info.symbols
+        .functions()
+        .iter()
+        .filter_map(|function| {
+            let name = function.prototype.name();
+            let type_name = name.strip_suffix(MIGRATION_FN_NAME)?.strip_suffix("::")?;
+
+            let signature = &function.prototype.signature;
+            match signature.arg_types() {
+                [old_ty, new_ty]
+                    if signature.return_type().is_none()
+                        && is_gc_struct(old_ty)
+                        && is_gc_struct(new_ty)
+                        && new_ty.name() == type_name =>
+                {
+                    Some(MigrationFn {
+                        name,
+                        old_ty,
+                        new_ty,
+                        fn_ptr: function.fn_ptr,
+                    })
+                }
+                _ => {
+                    warn!(
+                        "Migration function `{}` must receive a struct with the fields of the old type, followed by a `{}`, and return nothing.",
+                        name, type_name
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Adds the migration functions `fns` to the conversions of `mapping`, returning the names of the
+/// types that are migrated.
+pub(crate) fn add_migrations(
+    fns: &[MigrationFn],
+    mapping: &mut Mapping<UnsafeTypeInfo, UnsafeTypeInfo>,
+    gc: &Arc<GarbageCollector>,
+) -> Vec<String> {
+    let mut migrated_types = Vec::new();
+    for function in fns {
+        // Only types of which the objects are converted need to be migrated
+        let (old_ty, conversion) = match mapping.conversions.iter_mut().find(|(_, conversion)| {
+            // Safety: The types of a mapping are guaranteed to be alive while the mapping is used.
+            unsafe { conversion.new_ty.into_inner().as_ref() }.guid == function.new_ty.guid
+        }) {
+            Some(conversion) => conversion,
+            None => continue,
+        };
+
+        // Safety: The types of a mapping are guaranteed to be alive while the mapping is used.
+        let old_ty = unsafe { old_ty.into_inner().as_ref() };
+        if !has_same_fields(function.old_ty, old_ty) {
+            warn!(
+                "Skipped migration function `{}`, because the fields of `{}` do not match the fields of the old `{}`.",
+                function.name,
+                function.old_ty.name(),
+                old_ty.name()
+            );
+            continue;
+        }
+
+        let fn_ptr = function.fn_ptr as usize;
+        let gc = gc.clone();
+        let old_fields_ty = UnsafeTypeInfo::new(NonNull::from(function.old_ty));
+        let old_size = function.old_ty.size_in_bytes();
+        conversion.migration = Some(Migration::new(move |old, new| {
+            // The old memory is deallocated after the migration, so it is copied into an object of
+            // the type that describes the old fields. This object is rooted during the call, and
+            // remains valid if the migration function stores it.
+            let old_object = GcRootPtr::new(&gc, gc.alloc(old_fields_ty));
+            // Safety: The fields of the old type match the fields of `old_fields_ty`, so they have
+            // the same size.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    old.as_ptr(),
+                    old_object.deref::<u8>() as *mut u8,
+                    old_size,
+                )
+            };
+
+            // Mun functions receive `struct(gc)`s as handles: pointers to pointers to their memory
+            let function: fn(RawGcPtr, RawGcPtr) = unsafe { std::mem::transmute(fn_ptr) };
+            function(old_object.handle().into(), new.into());
+        }));
+        migrated_types.push(old_ty.name().to_owned());
+    }
+
+    migrated_types.sort();
+    migrated_types
+}
@@ -1,4 +1,4 @@
-use crate::{marshal::Marshal, Runtime, StructRef};
+use crate::{closure, marshal::Marshal, ArrayRef, ClosureRef, EnumRef, Runtime, StructRef};
 use abi::HasStaticTypeInfo;
 use once_cell::sync::OnceCell;
 
@@ -25,11 +25,26 @@ pub fn equals_return_type<T: ReturnTypeReflection>(
                 return Err((type_info.name(), T::type_name()));
             }
         }
+        abi::TypeInfoData::Struct(_) if closure::is_closure_type(type_info) => {
+            if <ClosureRef as ReturnTypeReflection>::type_guid() != T::type_guid() {
+                return Err(("closure", T::type_name()));
+            }
+        }
         abi::TypeInfoData::Struct(_) => {
             if <StructRef as ReturnTypeReflection>::type_guid() != T::type_guid() {
                 return Err(("struct", T::type_name()));
             }
         }
+        abi::TypeInfoData::Array(_) => {
+            if <ArrayRef<()> as ReturnTypeReflection>::type_guid() != T::type_guid() {
+                return Err(("array", T::type_name()));
+            }
+        }
+        abi::TypeInfoData::Enum(_) => {
+            if <EnumRef as ReturnTypeReflection>::type_guid() != T::type_guid() {
+                return Err(("enum", T::type_name()));
+            }
+        }
     }
     Ok(())
 }
@@ -86,7 +101,7 @@ macro_rules! impl_primitive_type {
                     value
                 }
 
-                fn marshal_into<'r>(self) -> Self::MunType {
+                fn marshal_into(self, _runtime: &Runtime) -> Self::MunType {
                     self
                 }
 
@@ -107,6 +122,7 @@ macro_rules! impl_primitive_type {
                 fn marshal_to_ptr(
                     value: Self,
                     mut ptr: std::ptr::NonNull<Self::MunType>,
+                    _runtime: &Runtime,
                     _type_info: Option<&abi::TypeInfo>,
                 ) {
                     unsafe { *ptr.as_mut() = value };
@@ -142,7 +158,7 @@ impl<'t> Marshal<'t> for () {
         value
     }
 
-    fn marshal_into<'r>(self) -> Self::MunType {
+    fn marshal_into(self, _runtime: &Runtime) -> Self::MunType {
         self
     }
 
@@ -160,6 +176,7 @@ impl<'t> Marshal<'t> for () {
     fn marshal_to_ptr(
         _value: Self,
         mut ptr: std::ptr::NonNull<Self::MunType>,
+        _runtime: &Runtime,
         _type_info: Option<&abi::TypeInfo>,
     ) {
         unsafe { *ptr.as_mut() = () };
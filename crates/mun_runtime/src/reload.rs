@@ -0,0 +1,146 @@
+//! Events that describe the outcome of hot reloading assemblies, and the callbacks through which
+//! they are reported by [`Runtime::update`].
+//!
+//! [`Runtime::update`]: crate::Runtime::update
+
+use crate::{garbage_collector::UnsafeTypeInfo, DispatchTable, Runtime};
+use memory::{mapping::Mapping, TypeDesc};
+use std::path::PathBuf;
+
+/// Describes a successful hot reload. Passed to the callbacks registered with
+/// [`Runtime::on_reload`].
+///
+/// [`Runtime::on_reload`]: crate::Runtime::on_reload
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReloadEvent {
+    /// The library paths of the assemblies that were reloaded
+    pub assemblies: Vec<PathBuf>,
+    /// The names of the functions that were added
+    pub added_functions: Vec<String>,
+    /// The names of the functions that were removed
+    pub removed_functions: Vec<String>,
+    /// The names of the functions of which the signature changed
+    pub changed_functions: Vec<String>,
+    /// The names of the types of which the existing objects were converted to a new memory layout
+    pub converted_types: Vec<String>,
+    /// The names of the converted types of which the objects were migrated by a migration function
+    /// declared by a script. See [`Runtime`].
+    ///
+    /// [`Runtime`]: crate::Runtime
+    pub migrated_types: Vec<String>,
+    /// The names of the types that were removed. Existing objects of these types remain alive, but
+    /// can no longer be passed to functions of the reloaded assemblies.
+    pub deleted_types: Vec<String>,
+}
+
+impl ReloadEvent {
+    /// Records the functions that were added, removed, or changed signature between the `old` and
+    /// `new` dispatch table.
+    ///
+    /// The function information of `old` must still be alive.
+    pub(crate) fn diff_functions(&mut self, old: &DispatchTable, new: &DispatchTable) {
+        for (name, new_fn) in new.functions.iter() {
+            match old.functions.get(name) {
+                Some(old_fn) if old_fn.prototype.signature != new_fn.prototype.signature => {
+                    self.changed_functions.push(name.clone())
+                }
+                Some(_) => (),
+                None => self.added_functions.push(name.clone()),
+            }
+        }
+
+        self.removed_functions.extend(
+            old.functions
+                .keys()
+                .filter(|name| !new.functions.contains_key(*name))
+                .cloned(),
+        );
+
+        self.added_functions.sort();
+        self.removed_functions.sort();
+        self.changed_functions.sort();
+    }
+
+    /// Records the types that are converted or deleted by the memory `mapping`.
+    ///
+    /// The old types of the mapping must still be alive.
+    pub(crate) fn add_mapping(&mut self, mapping: &Mapping<UnsafeTypeInfo, UnsafeTypeInfo>) {
+        let mut converted: Vec<String> = mapping
+            .conversions
+            .keys()
+            .map(|ty| ty.name().to_owned())
+            .collect();
+        converted.sort();
+        self.converted_types.append(&mut converted);
+
+        let mut deleted: Vec<String> = mapping
+            .deletions
+            .iter()
+            .map(|ty| ty.name().to_owned())
+            .collect();
+        deleted.sort();
+        self.deleted_types.append(&mut deleted);
+    }
+}
+
+/// Describes a failed hot reload. Passed to the callbacks registered with
+/// [`Runtime::on_reload_failed`].
+///
+/// When reloading fails, the runtime keeps running the previously loaded assemblies.
+///
+/// [`Runtime::on_reload_failed`]: crate::Runtime::on_reload_failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReloadFailedEvent {
+    /// The library paths of the assemblies that failed to reload
+    pub assemblies: Vec<PathBuf>,
+    /// A description of the error that caused the reload to fail
+    pub error: String,
+}
+
+/// A callback that is invoked after a successful hot reload.
+type OnReload = Box<dyn FnMut(&ReloadEvent) + Send>;
+
+/// A callback that is invoked after a failed hot reload.
+type OnReloadFailed = Box<dyn FnMut(&ReloadFailedEvent) + Send>;
+
+/// A function that checks whether the runtime is healthy after a hot reload.
+type HealthCheck = Box<dyn FnMut(&Runtime) -> bool + Send>;
+
+/// The callbacks that are invoked when assemblies are hot reloaded.
+#[derive(Default)]
+pub(crate) struct ReloadCallbacks {
+    on_reload: Vec<OnReload>,
+    on_reload_failed: Vec<OnReloadFailed>,
+    pub health_check: Option<HealthCheck>,
+}
+
+impl ReloadCallbacks {
+    /// Adds a callback that is invoked after a successful hot reload.
+    pub fn add_on_reload(&mut self, callback: OnReload) {
+        self.on_reload.push(callback);
+    }
+
+    /// Adds a callback that is invoked after a failed hot reload.
+    pub fn add_on_reload_failed(&mut self, callback: OnReloadFailed) {
+        self.on_reload_failed.push(callback);
+    }
+
+    /// Sets the function that checks whether the runtime is healthy after a hot reload.
+    pub fn set_health_check(&mut self, health_check: HealthCheck) {
+        self.health_check = Some(health_check);
+    }
+
+    /// Invokes all callbacks for a successful hot reload, in the order they were added.
+    pub fn reloaded(&mut self, event: &ReloadEvent) {
+        for callback in self.on_reload.iter_mut() {
+            callback(event);
+        }
+    }
+
+    /// Invokes all callbacks for a failed hot reload, in the order they were added.
+    pub fn reload_failed(&mut self, event: &ReloadFailedEvent) {
+        for callback in self.on_reload_failed.iter_mut() {
+            callback(event);
+        }
+    }
+}
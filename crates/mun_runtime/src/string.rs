@@ -0,0 +1,172 @@
+use crate::garbage_collector::{GcPtr, UnsafeTypeInfo};
+use crate::{
+    marshal::Marshal,
+    reflection::{ArgumentReflection, ReturnTypeReflection},
+    Runtime,
+};
+use abi::HasStaticTypeInfo;
+use memory::gc::{ArrayHeader, GcRuntime, HasIndirectionPtr};
+use std::{alloc::Layout, ptr, ptr::NonNull, slice, str};
+
+/// Represents a Mun string pointer.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RawString(pub(crate) GcPtr);
+
+impl RawString {
+    /// Returns the UTF-8 encoded bytes of the string.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice points to memory that is owned by the garbage collector. It is only valid
+    /// as long as the string has not been collected.
+    pub unsafe fn as_bytes<'s>(&self) -> &'s [u8] {
+        let header = self.0.deref::<ArrayHeader>();
+        let length = (*header).length;
+        let (_, offset) = ArrayHeader::layout(Layout::new::<u8>(), length);
+        slice::from_raw_parts(self.0.deref::<u8>().add(offset), length)
+    }
+
+    /// Returns the contents of the string.
+    ///
+    /// # Safety
+    ///
+    /// The returned `str` points to memory that is owned by the garbage collector. It is only valid
+    /// as long as the string has not been collected.
+    pub unsafe fn as_str<'s>(&self) -> &'s str {
+        // Safety: Mun strings are always valid UTF-8
+        str::from_utf8_unchecked(self.as_bytes())
+    }
+}
+
+/// Allocates a string in the garbage collector `gc` that contains the specified UTF-8 encoded
+/// `bytes`.
+pub(crate) fn alloc_string<G: GcRuntime<UnsafeTypeInfo> + ?Sized>(gc: &G, bytes: &[u8]) -> GcPtr {
+    let type_info = UnsafeTypeInfo::new(NonNull::from(<str as HasStaticTypeInfo>::type_info()));
+    let element = Layout::new::<u8>();
+    let mut handle = gc.alloc_array(type_info, element, bytes.len());
+
+    let (_, offset) = ArrayHeader::layout(element, bytes.len());
+    // Safety: The object was allocated to fit exactly `bytes.len()` bytes after its header
+    unsafe {
+        ptr::copy_nonoverlapping(
+            bytes.as_ptr(),
+            handle.deref_mut::<u8>().add(offset),
+            bytes.len(),
+        )
+    };
+
+    handle
+}
+
+impl ArgumentReflection for String {
+    fn type_guid(&self, _runtime: &Runtime) -> abi::Guid {
+        <str as HasStaticTypeInfo>::type_info().guid
+    }
+
+    fn type_name(&self, _runtime: &Runtime) -> &str {
+        <str as HasStaticTypeInfo>::type_info().name()
+    }
+}
+
+impl ReturnTypeReflection for String {
+    fn type_guid() -> abi::Guid {
+        <str as HasStaticTypeInfo>::type_info().guid
+    }
+
+    fn type_name() -> &'static str {
+        <str as HasStaticTypeInfo>::type_info().name()
+    }
+}
+
+/// Mun strings are marshalled by copying them, so the returned `String` remains valid after the
+/// Mun string has been garbage collected.
+impl<'t> Marshal<'t> for String {
+    type MunType = RawString;
+
+    fn marshal_from<'r>(value: Self::MunType, _runtime: &'r Runtime) -> Self
+    where
+        Self: 't,
+        'r: 't,
+    {
+        // Safety: The string is copied before the garbage collector has a chance to run
+        unsafe { value.as_str() }.to_owned()
+    }
+
+    fn marshal_into(self, runtime: &Runtime) -> Self::MunType {
+        RawString(alloc_string(runtime.gc(), self.as_bytes()))
+    }
+
+    fn marshal_from_ptr<'r>(
+        ptr: NonNull<Self::MunType>,
+        runtime: &'r Runtime,
+        _type_info: Option<&abi::TypeInfo>,
+    ) -> Self
+    where
+        Self: 't,
+        'r: 't,
+    {
+        let value = unsafe { ptr.as_ref() }.clone();
+        Marshal::marshal_from(value, runtime)
+    }
+
+    fn marshal_to_ptr(
+        value: Self,
+        mut ptr: NonNull<Self::MunType>,
+        runtime: &Runtime,
+        _type_info: Option<&abi::TypeInfo>,
+    ) {
+        unsafe { *ptr.as_mut() = value.marshal_into(runtime) };
+    }
+}
+
+impl<'s> ArgumentReflection for &'s str {
+    fn type_guid(&self, _runtime: &Runtime) -> abi::Guid {
+        <str as HasStaticTypeInfo>::type_info().guid
+    }
+
+    fn type_name(&self, _runtime: &Runtime) -> &str {
+        <str as HasStaticTypeInfo>::type_info().name()
+    }
+}
+
+/// A `&str` can only be passed to Mun, in which case it is copied into a new Mun string. It does
+/// not implement `ReturnTypeReflection`, because a `&str` into a Mun string would not keep the
+/// string alive, allowing the garbage collector to free it while it is still referenced. Use
+/// `String` to retrieve Mun strings instead.
+impl<'s> Marshal<'s> for &'s str {
+    type MunType = RawString;
+
+    fn marshal_from<'r>(_value: Self::MunType, _runtime: &'r Runtime) -> Self
+    where
+        Self: 's,
+        'r: 's,
+    {
+        unreachable!("a `&str` cannot be marshalled from Mun")
+    }
+
+    fn marshal_into(self, runtime: &Runtime) -> Self::MunType {
+        RawString(alloc_string(runtime.gc(), self.as_bytes()))
+    }
+
+    fn marshal_from_ptr<'r>(
+        _ptr: NonNull<Self::MunType>,
+        _runtime: &'r Runtime,
+        _type_info: Option<&abi::TypeInfo>,
+    ) -> Self
+    where
+        Self: 's,
+        'r: 's,
+    {
+        unreachable!("a `&str` cannot be marshalled from Mun")
+    }
+
+    fn marshal_to_ptr(
+        value: Self,
+        mut ptr: NonNull<Self::MunType>,
+        runtime: &Runtime,
+        _type_info: Option<&abi::TypeInfo>,
+    ) {
+        unsafe { *ptr.as_mut() = value.marshal_into(runtime) };
+    }
+}
@@ -0,0 +1,563 @@
+//! Dynamically typed values make it possible to invoke functions of which the signature is only
+//! known at runtime, e.g. from a scripting console. The arguments are checked against the
+//! signature of the function, after which a call is constructed using libffi.
+
+use crate::{
+    adt::{RawEnum, RawStruct},
+    array::RawArray,
+    closure::{self, RawClosure},
+    garbage_collector::{is_gc_ptr, GcPtr},
+    marshal::Marshal,
+    reflection::{equals_argument_type, ArgumentReflection, ReturnTypeReflection},
+    string::RawString,
+    ArrayRef, ClosureRef, EnumRef, Runtime, StructRef,
+};
+use abi::HasStaticTypeInfo;
+use libffi::{
+    low::{ffi_arg, ffi_sarg},
+    middle::{Arg, Cif, CodePtr, Type},
+};
+use memory::gc::GcRuntime;
+use std::{ffi::c_void, fmt, ptr::NonNull};
+
+/// A dynamically typed value that can be passed to and returned from [`Runtime::invoke`].
+#[derive(Clone)]
+pub enum Value<'r> {
+    /// The empty type `()`, returned by functions without a return type
+    Unit,
+    /// A `bool`
+    Bool(bool),
+    /// An `i8`
+    I8(i8),
+    /// An `i16`
+    I16(i16),
+    /// An `i32`
+    I32(i32),
+    /// An `i64`
+    I64(i64),
+    /// A `u8`
+    U8(u8),
+    /// A `u16`
+    U16(u16),
+    /// A `u32`
+    U32(u32),
+    /// A `u64`
+    U64(u64),
+    /// An `f32`
+    F32(f32),
+    /// An `f64`
+    F64(f64),
+    /// A string, which is copied into and out of the garbage collector
+    String(String),
+    /// A reference to a Mun struct
+    Struct(StructRef<'r>),
+    /// A reference to a Mun enum
+    Enum(EnumRef<'r>),
+    /// A reference to a Mun closure
+    Closure(ClosureRef<'r>),
+    /// A reference to a Mun array, of which the elements can be retrieved as `Value`s
+    Array(ArrayRef<'r, Value<'r>>),
+}
+
+impl<'r> Value<'r> {
+    /// Constructs a `Value` for the object `ptr`. Strings are copied, whereas structs, enums,
+    /// closures, and arrays reference the object. Returns `None` if `ptr` does not refer to an
+    /// object.
+    ///
+    /// # Safety
+    ///
+    /// The object should have been allocated by the garbage collector of `runtime`.
+    pub unsafe fn from_gc_ptr(ptr: GcPtr, runtime: &'r Runtime) -> Option<Self> {
+        let type_info = &*runtime.gc.ptr_type(ptr).into_inner().as_ptr();
+        let value = match type_info.data {
+            abi::TypeInfoData::Primitive
+                if type_info.guid == <str as HasStaticTypeInfo>::type_info().guid =>
+            {
+                Value::String(String::marshal_from(RawString(ptr), runtime))
+            }
+            abi::TypeInfoData::Struct(_) if closure::is_closure_type(type_info) => {
+                Value::Closure(ClosureRef::marshal_from(RawClosure(ptr), runtime))
+            }
+            abi::TypeInfoData::Struct(_) => {
+                Value::Struct(StructRef::marshal_from(RawStruct(ptr), runtime))
+            }
+            abi::TypeInfoData::Enum(_) => Value::Enum(EnumRef::marshal_from(RawEnum(ptr), runtime)),
+            abi::TypeInfoData::Array(_) => {
+                Value::Array(ArrayRef::marshal_from(RawArray(ptr), runtime))
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Constructs a `Value` from the value of type `type_info` that is stored at `ptr`, e.g. the
+    /// element of an array. Returns `None` for values that cannot be represented by a `Value`, such
+    /// as value structs, which are not stored in a separate object.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` should point to a valid value of type `type_info`, and objects should have been
+    /// allocated by the garbage collector of `runtime`.
+    pub(crate) unsafe fn from_ptr(
+        ptr: NonNull<u8>,
+        type_info: &abi::TypeInfo,
+        runtime: &'r Runtime,
+    ) -> Option<Self> {
+        if is_gc_ptr(type_info) {
+            return Self::from_gc_ptr(*ptr.cast::<GcPtr>().as_ref(), runtime);
+        }
+
+        let value = match ReturnKind::new(Some(type_info))? {
+            ReturnKind::Bool => Value::Bool(*ptr.cast().as_ref()),
+            ReturnKind::I8 => Value::I8(*ptr.cast().as_ref()),
+            ReturnKind::I16 => Value::I16(*ptr.cast().as_ref()),
+            ReturnKind::I32 => Value::I32(*ptr.cast().as_ref()),
+            ReturnKind::I64 => Value::I64(*ptr.cast().as_ref()),
+            ReturnKind::U8 => Value::U8(*ptr.cast().as_ref()),
+            ReturnKind::U16 => Value::U16(*ptr.cast().as_ref()),
+            ReturnKind::U32 => Value::U32(*ptr.cast().as_ref()),
+            ReturnKind::U64 => Value::U64(*ptr.cast().as_ref()),
+            ReturnKind::F32 => Value::F32(*ptr.cast().as_ref()),
+            ReturnKind::F64 => Value::F64(*ptr.cast().as_ref()),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Returns the handle of the object that is referenced by the value, if the value references
+    /// an object.
+    pub fn gc_ptr(&self) -> Option<GcPtr> {
+        match self {
+            Value::Struct(value) => Some(value.clone().into_raw().0),
+            Value::Enum(value) => Some(value.clone().into_raw().0),
+            Value::Closure(value) => Some(value.clone().into_raw().0),
+            Value::Array(value) => Some(value.clone().into_raw().0),
+            _ => None,
+        }
+    }
+
+    /// Converts the value into its representation in the Mun ABI. Returns `None` for `()`, which
+    /// cannot be passed to a function.
+    fn into_raw(self, runtime: &Runtime) -> Option<RawValue> {
+        let raw = match self {
+            Value::Unit => return None,
+            Value::Bool(value) => RawValue::Bool(value),
+            Value::I8(value) => RawValue::I8(value),
+            Value::I16(value) => RawValue::I16(value),
+            Value::I32(value) => RawValue::I32(value),
+            Value::I64(value) => RawValue::I64(value),
+            Value::U8(value) => RawValue::U8(value),
+            Value::U16(value) => RawValue::U16(value),
+            Value::U32(value) => RawValue::U32(value),
+            Value::U64(value) => RawValue::U64(value),
+            Value::F32(value) => RawValue::F32(value),
+            Value::F64(value) => RawValue::F64(value),
+            Value::String(value) => RawValue::Object(value.marshal_into(runtime).0.into()),
+            Value::Struct(value) => RawValue::Object(value.into_raw().0.into()),
+            Value::Enum(value) => RawValue::Object(value.into_raw().0.into()),
+            Value::Closure(value) => RawValue::Object(value.into_raw().0.into()),
+            Value::Array(value) => RawValue::Object(value.into_raw().0.into()),
+        };
+        Some(raw)
+    }
+}
+
+impl<'r> ArgumentReflection for Value<'r> {
+    fn type_guid(&self, runtime: &Runtime) -> abi::Guid {
+        match self {
+            Value::Unit => <() as ReturnTypeReflection>::type_guid(),
+            Value::Bool(value) => value.type_guid(runtime),
+            Value::I8(value) => value.type_guid(runtime),
+            Value::I16(value) => value.type_guid(runtime),
+            Value::I32(value) => value.type_guid(runtime),
+            Value::I64(value) => value.type_guid(runtime),
+            Value::U8(value) => value.type_guid(runtime),
+            Value::U16(value) => value.type_guid(runtime),
+            Value::U32(value) => value.type_guid(runtime),
+            Value::U64(value) => value.type_guid(runtime),
+            Value::F32(value) => value.type_guid(runtime),
+            Value::F64(value) => value.type_guid(runtime),
+            Value::String(value) => value.type_guid(runtime),
+            Value::Struct(value) => value.type_guid(runtime),
+            Value::Enum(value) => value.type_guid(runtime),
+            Value::Closure(value) => value.type_guid(runtime),
+            Value::Array(value) => value.type_guid(runtime),
+        }
+    }
+
+    fn type_name<'s>(&'s self, runtime: &'s Runtime) -> &'s str {
+        match self {
+            Value::Unit => <() as ReturnTypeReflection>::type_name(),
+            Value::Bool(value) => value.type_name(runtime),
+            Value::I8(value) => value.type_name(runtime),
+            Value::I16(value) => value.type_name(runtime),
+            Value::I32(value) => value.type_name(runtime),
+            Value::I64(value) => value.type_name(runtime),
+            Value::U8(value) => value.type_name(runtime),
+            Value::U16(value) => value.type_name(runtime),
+            Value::U32(value) => value.type_name(runtime),
+            Value::U64(value) => value.type_name(runtime),
+            Value::F32(value) => value.type_name(runtime),
+            Value::F64(value) => value.type_name(runtime),
+            Value::String(value) => value.type_name(runtime),
+            Value::Struct(value) => value.type_name(runtime),
+            Value::Enum(value) => value.type_name(runtime),
+            Value::Closure(value) => value.type_name(runtime),
+            Value::Array(value) => value.type_name(runtime),
+        }
+    }
+}
+
+impl<'r> fmt::Display for Value<'r> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unit => write!(f, "()"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::I8(value) => write!(f, "{}", value),
+            Value::I16(value) => write!(f, "{}", value),
+            Value::I32(value) => write!(f, "{}", value),
+            Value::I64(value) => write!(f, "{}", value),
+            Value::U8(value) => write!(f, "{}", value),
+            Value::U16(value) => write!(f, "{}", value),
+            Value::U32(value) => write!(f, "{}", value),
+            Value::U64(value) => write!(f, "{}", value),
+            Value::F32(value) => write!(f, "{}", value),
+            Value::F64(value) => write!(f, "{}", value),
+            Value::String(value) => write!(f, "{}", value),
+            Value::Struct(value) => write!(f, "{}", value.type_info().name()),
+            Value::Enum(value) => write!(f, "{}", value.type_info().name()),
+            Value::Closure(value) => write!(f, "{}", value.type_info().name()),
+            Value::Array(value) => write!(f, "{}", value.type_info().name()),
+        }
+    }
+}
+
+impl<'r> fmt::Debug for Value<'r> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Value::Unit => return write!(f, "Unit"),
+            Value::Bool(_) => "Bool",
+            Value::I8(_) => "I8",
+            Value::I16(_) => "I16",
+            Value::I32(_) => "I32",
+            Value::I64(_) => "I64",
+            Value::U8(_) => "U8",
+            Value::U16(_) => "U16",
+            Value::U32(_) => "U32",
+            Value::U64(_) => "U64",
+            Value::F32(_) => "F32",
+            Value::F64(_) => "F64",
+            Value::String(value) => return write!(f, "String({:?})", value),
+            Value::Struct(_) => "Struct",
+            Value::Enum(_) => "Enum",
+            Value::Closure(_) => "Closure",
+            Value::Array(_) => "Array",
+        };
+        write!(f, "{}({})", kind, self)
+    }
+}
+
+macro_rules! impl_from {
+    ($($ty:ty => $variant:ident),+) => {
+        $(
+            impl<'r> From<$ty> for Value<'r> {
+                fn from(value: $ty) -> Self {
+                    Value::$variant(value)
+                }
+            }
+        )+
+    }
+}
+
+impl_from!(
+    bool => Bool,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    f32 => F32,
+    f64 => F64,
+    String => String,
+    StructRef<'r> => Struct,
+    EnumRef<'r> => Enum,
+    ClosureRef<'r> => Closure,
+    ArrayRef<'r, Value<'r>> => Array
+);
+
+impl<'r> From<()> for Value<'r> {
+    fn from(_value: ()) -> Self {
+        Value::Unit
+    }
+}
+
+impl<'r> From<&str> for Value<'r> {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_owned())
+    }
+}
+
+/// The representation of a [`Value`] in the Mun ABI. Objects are passed as a pointer to their
+/// handle.
+enum RawValue {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    Object(*const *mut c_void),
+}
+
+impl RawValue {
+    /// Returns the libffi argument that points to the value.
+    fn as_arg(&self) -> Arg {
+        match self {
+            RawValue::Bool(value) => Arg::new(value),
+            RawValue::I8(value) => Arg::new(value),
+            RawValue::I16(value) => Arg::new(value),
+            RawValue::I32(value) => Arg::new(value),
+            RawValue::I64(value) => Arg::new(value),
+            RawValue::U8(value) => Arg::new(value),
+            RawValue::U16(value) => Arg::new(value),
+            RawValue::U32(value) => Arg::new(value),
+            RawValue::U64(value) => Arg::new(value),
+            RawValue::F32(value) => Arg::new(value),
+            RawValue::F64(value) => Arg::new(value),
+            RawValue::Object(value) => Arg::new(value),
+        }
+    }
+
+    /// Returns the libffi type of the value.
+    fn ffi_type(&self) -> Type {
+        match self {
+            RawValue::Bool(_) | RawValue::U8(_) => Type::u8(),
+            RawValue::I8(_) => Type::i8(),
+            RawValue::I16(_) => Type::i16(),
+            RawValue::I32(_) => Type::i32(),
+            RawValue::I64(_) => Type::i64(),
+            RawValue::U16(_) => Type::u16(),
+            RawValue::U32(_) => Type::u32(),
+            RawValue::U64(_) => Type::u64(),
+            RawValue::F32(_) => Type::f32(),
+            RawValue::F64(_) => Type::f64(),
+            RawValue::Object(_) => Type::pointer(),
+        }
+    }
+}
+
+/// The kinds of values that can be returned from a function, determined from its return type.
+#[derive(Clone, Copy)]
+enum ReturnKind {
+    Unit,
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    String,
+    Struct,
+    Enum,
+    Closure,
+    Array,
+}
+
+impl ReturnKind {
+    /// Determines the kind of value that is returned for the specified `return_type`. Returns
+    /// `None` if values of the type cannot be represented by a [`Value`].
+    fn new(return_type: Option<&abi::TypeInfo>) -> Option<ReturnKind> {
+        let type_info = match return_type {
+            Some(type_info) => type_info,
+            None => return Some(ReturnKind::Unit),
+        };
+
+        let kind = match type_info.data {
+            abi::TypeInfoData::Primitive => {
+                let primitives = [
+                    (bool::type_info().guid, ReturnKind::Bool),
+                    (i8::type_info().guid, ReturnKind::I8),
+                    (i16::type_info().guid, ReturnKind::I16),
+                    (i32::type_info().guid, ReturnKind::I32),
+                    (i64::type_info().guid, ReturnKind::I64),
+                    (u8::type_info().guid, ReturnKind::U8),
+                    (u16::type_info().guid, ReturnKind::U16),
+                    (u32::type_info().guid, ReturnKind::U32),
+                    (u64::type_info().guid, ReturnKind::U64),
+                    (f32::type_info().guid, ReturnKind::F32),
+                    (f64::type_info().guid, ReturnKind::F64),
+                    (
+                        <str as HasStaticTypeInfo>::type_info().guid,
+                        ReturnKind::String,
+                    ),
+                ];
+                return primitives
+                    .iter()
+                    .find(|(guid, _)| *guid == type_info.guid)
+                    .map(|(_, kind)| *kind);
+            }
+            abi::TypeInfoData::Struct(_) if closure::is_closure_type(type_info) => {
+                ReturnKind::Closure
+            }
+            abi::TypeInfoData::Struct(_) => ReturnKind::Struct,
+            abi::TypeInfoData::Enum(_) => ReturnKind::Enum,
+            abi::TypeInfoData::Array(_) => ReturnKind::Array,
+        };
+        Some(kind)
+    }
+
+    /// Returns the libffi type of the returned value.
+    fn ffi_type(self) -> Type {
+        match self {
+            ReturnKind::Unit => Type::void(),
+            ReturnKind::Bool | ReturnKind::U8 => Type::u8(),
+            ReturnKind::I8 => Type::i8(),
+            ReturnKind::I16 => Type::i16(),
+            ReturnKind::I32 => Type::i32(),
+            ReturnKind::I64 => Type::i64(),
+            ReturnKind::U16 => Type::u16(),
+            ReturnKind::U32 => Type::u32(),
+            ReturnKind::U64 => Type::u64(),
+            ReturnKind::F32 => Type::f32(),
+            ReturnKind::F64 => Type::f64(),
+            ReturnKind::String
+            | ReturnKind::Struct
+            | ReturnKind::Enum
+            | ReturnKind::Closure
+            | ReturnKind::Array => Type::pointer(),
+        }
+    }
+
+    /// Calls the function at `code_ptr` and converts the returned value.
+    ///
+    /// # Safety
+    ///
+    /// `cif` should describe the signature of the function at `code_ptr`, and `args` should match
+    /// that signature.
+    unsafe fn call<'r>(
+        self,
+        cif: &Cif,
+        code_ptr: CodePtr,
+        args: &[Arg],
+        runtime: &'r Runtime,
+    ) -> Value<'r> {
+        // libffi widens integral return values that are smaller than a register to an `ffi_arg`
+        // (or `ffi_sarg` for signed values), so the result buffer must have that size.
+        match self {
+            ReturnKind::Unit => {
+                cif.call::<()>(code_ptr, args);
+                Value::Unit
+            }
+            ReturnKind::Bool => Value::Bool(cif.call::<ffi_arg>(code_ptr, args) as u8 != 0),
+            ReturnKind::I8 => Value::I8(cif.call::<ffi_sarg>(code_ptr, args) as i8),
+            ReturnKind::I16 => Value::I16(cif.call::<ffi_sarg>(code_ptr, args) as i16),
+            ReturnKind::I32 => Value::I32(cif.call::<ffi_sarg>(code_ptr, args) as i32),
+            ReturnKind::I64 => Value::I64(cif.call::<i64>(code_ptr, args)),
+            ReturnKind::U8 => Value::U8(cif.call::<ffi_arg>(code_ptr, args) as u8),
+            ReturnKind::U16 => Value::U16(cif.call::<ffi_arg>(code_ptr, args) as u16),
+            ReturnKind::U32 => Value::U32(cif.call::<ffi_arg>(code_ptr, args) as u32),
+            ReturnKind::U64 => Value::U64(cif.call::<u64>(code_ptr, args)),
+            ReturnKind::F32 => Value::F32(cif.call::<f32>(code_ptr, args)),
+            ReturnKind::F64 => Value::F64(cif.call::<f64>(code_ptr, args)),
+            ReturnKind::String
+            | ReturnKind::Struct
+            | ReturnKind::Enum
+            | ReturnKind::Closure
+            | ReturnKind::Array => {
+                let handle: GcPtr = cif.call::<*const *mut c_void>(code_ptr, args).into();
+                match self {
+                    ReturnKind::String => {
+                        Value::String(String::marshal_from(RawString(handle), runtime))
+                    }
+                    ReturnKind::Struct => {
+                        Value::Struct(StructRef::marshal_from(RawStruct(handle), runtime))
+                    }
+                    ReturnKind::Enum => {
+                        Value::Enum(EnumRef::marshal_from(RawEnum(handle), runtime))
+                    }
+                    ReturnKind::Array => {
+                        Value::Array(ArrayRef::marshal_from(RawArray(handle), runtime))
+                    }
+                    _ => Value::Closure(ClosureRef::marshal_from(RawClosure(handle), runtime)),
+                }
+            }
+        }
+    }
+}
+
+/// Invokes the function described by `function_info` with the specified `args`, after checking
+/// them against the function's signature.
+pub(crate) fn invoke<'r>(
+    runtime: &'r Runtime,
+    function_info: &abi::FunctionDefinition,
+    args: &[Value<'r>],
+) -> Result<Value<'r>, String> {
+    let arg_types = function_info.prototype.signature.arg_types();
+    if arg_types.len() != args.len() {
+        return Err(format!(
+            "Invalid number of arguments. Expected: {}. Found: {}.",
+            arg_types.len(),
+            args.len(),
+        ));
+    }
+
+    for (idx, (arg_type, arg)) in arg_types.iter().zip(args.iter()).enumerate() {
+        equals_argument_type(runtime, arg_type, arg).map_err(|(expected, found)| {
+            format!(
+                "Invalid argument type at index {}. Expected: {}. Found: {}.",
+                idx, expected, found,
+            )
+        })?;
+    }
+
+    let return_type = function_info.prototype.signature.return_type();
+    let return_kind = ReturnKind::new(return_type).ok_or_else(|| {
+        format!(
+            "Unsupported return type: {}.",
+            return_type.map_or("()", |type_info| type_info.name())
+        )
+    })?;
+
+    let raw_args = args
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(idx, arg)| {
+            arg.into_raw(runtime).ok_or_else(|| {
+                format!(
+                    "Invalid argument type at index {}. `()` cannot be passed to a function.",
+                    idx
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let cif = Cif::new(
+        raw_args.iter().map(RawValue::ffi_type),
+        return_kind.ffi_type(),
+    );
+    let ffi_args: Vec<Arg> = raw_args.iter().map(RawValue::as_arg).collect();
+
+    // Safety: The arguments were checked against the signature of the function, which was used to
+    // construct the `cif`.
+    Ok(unsafe {
+        return_kind.call(
+            &cif,
+            CodePtr(function_info.fn_ptr as *mut _),
+            &ffi_args,
+            runtime,
+        )
+    })
+}
@@ -1,7 +1,14 @@
 #[macro_use]
 mod util;
 
+use mun_runtime::{invoke_fn, StructRef};
 use mun_test::CompileAndRunTestDriver;
+use parking_lot::Mutex;
+use std::{
+    sync::Arc,
+    thread::sleep,
+    time::{Duration, Instant},
+};
 
 #[test]
 fn hotreloadable() {
@@ -16,7 +23,7 @@ fn hotreloadable() {
 
     let runtime = driver.runtime();
     driver.update(
-        runtime.borrow(),
+        runtime.read(),
         "mod.mun",
         r"
     pub fn main() -> i32 { 10 }
@@ -48,7 +55,7 @@ fn hotreload_struct_decl() {
 
     let runtime = driver.runtime();
     driver.update(
-        runtime.borrow(),
+        runtime.read(),
         "mod.mun",
         r#"
     pub struct(gc) Args {
@@ -66,3 +73,371 @@ fn hotreload_struct_decl() {
     "#,
     );
 }
+
+#[test]
+fn hotreload_preserves_statics() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub static COUNTER: i32 = 0;
+    pub static RATIO: f64 = 1.0;
+
+    pub fn increment() -> i32 {
+        COUNTER += 1;
+        COUNTER
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(i32, 1, driver, "increment");
+    assert_invoke_eq!(i32, 2, driver, "increment");
+
+    let runtime = driver.runtime();
+    driver.update(
+        runtime.read(),
+        "mod.mun",
+        r"
+    pub static COUNTER: i32 = 100;
+    pub static RATIO: i32 = 7;
+    pub static ADDED: i32 = 5;
+
+    pub fn increment() -> i32 {
+        COUNTER += ADDED;
+        COUNTER
+    }
+    ",
+    );
+
+    // The value of a static is preserved if its type did not change
+    let runtime_ref = runtime.read();
+    assert_eq!(runtime_ref.get_global::<i32>("COUNTER"), Ok(2));
+    assert_eq!(runtime_ref.get_global::<i32>("RATIO"), Ok(7));
+    assert_eq!(runtime_ref.get_global::<i32>("ADDED"), Ok(5));
+    drop(runtime_ref);
+
+    assert_invoke_eq!(i32, 7, driver, "increment");
+}
+
+#[test]
+fn hotreload_function_handle() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let mut add = runtime
+        .read()
+        .get_fn::<(i32, i32), i32>("add")
+        .expect("Failed to obtain function handle");
+    assert_eq!(add.name(), "add");
+    assert_eq!(add.call(&runtime.read(), (2, 3)), Ok(5));
+
+    // The handle is resolved again after a hot reload
+    driver.update(
+        runtime.read(),
+        "mod.mun",
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a * b }
+    ",
+    );
+    assert_eq!(add.call(&runtime.read(), (2, 3)), Ok(6));
+
+    // A handle cannot be called when the signature of its function changed
+    driver.update(
+        runtime.read(),
+        "mod.mun",
+        r"
+    pub fn add(a: i32, b: f32) -> i32 { a }
+    ",
+    );
+    assert_eq!(
+        add.call(&runtime.read(), (2, 3)),
+        Err("Invalid argument type at index 1. Expected: core::f32. Found: core::i32.".to_owned())
+    );
+
+    // ... until the signature is restored
+    driver.update(
+        runtime.read(),
+        "mod.mun",
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a - b }
+    ",
+    );
+    assert_eq!(add.call(&runtime.read(), (2, 3)), Ok(-1));
+
+    // Obtaining a handle validates the signature
+    let runtime_ref = runtime.read();
+    assert_eq!(
+        runtime_ref.get_fn::<(i32,), i32>("add").err(),
+        Some("Invalid number of arguments. Expected: 2. Found: 1.".to_owned())
+    );
+    assert_eq!(
+        runtime_ref.get_fn::<(i32, i32), f64>("add").err(),
+        Some("Invalid return type. Expected: core::i32. Found: core::f64".to_owned())
+    );
+    assert_eq!(
+        runtime_ref.get_fn::<(), ()>("sub").err(),
+        Some("Failed to obtain function 'sub'".to_owned())
+    );
+}
+
+#[test]
+fn hotreload_callbacks() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(gc) Args {
+        n: i32,
+        foo: Bar,
+    }
+
+    struct(gc) Bar {
+        m: f64,
+    }
+
+    pub struct Baz {
+        x: i32,
+    }
+
+    pub fn args() -> Args {
+        Args { n: 3, foo: Bar { m: 1.0 }, }
+    }
+
+    pub fn baz() -> Baz {
+        Baz { x: 1 }
+    }
+
+    pub fn scale(a: i32) -> i32 {
+        a * 2
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let runtime = driver.runtime();
+    {
+        let events = events.clone();
+        runtime
+            .write()
+            .on_reload(move |event| events.lock().push(event.clone()));
+    }
+
+    driver.update(
+        runtime.read(),
+        "mod.mun",
+        r#"
+    pub struct(gc) Args {
+        n: i32,
+        foo: Bar,
+    }
+
+    struct(gc) Bar {
+        m: i32,
+    }
+
+    pub fn args() -> Args {
+        Args { n: 3, foo: Bar { m: 1 }, }
+    }
+
+    pub fn scale(a: f64) -> f64 {
+        a * 2.0
+    }
+
+    pub fn bar(m: i32) -> i32 {
+        m
+    }
+    "#,
+    );
+
+    let events = events.lock();
+    assert_eq!(events.len(), 1);
+
+    let event = &events[0];
+    assert_eq!(event.assemblies.len(), 1);
+    assert_eq!(event.added_functions, vec!["bar".to_owned()]);
+    assert_eq!(event.removed_functions, vec!["baz".to_owned()]);
+    assert_eq!(event.changed_functions, vec!["scale".to_owned()]);
+    assert!(event.converted_types.contains(&"Bar".to_owned()));
+    assert!(event.deleted_types.contains(&"Baz".to_owned()));
+}
+
+#[test]
+fn hotreload_revert() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub static COUNTER: i32 = 0;
+
+    pub fn main() -> i32 {
+        COUNTER += 1;
+        COUNTER
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(i32, 1, driver, "main");
+
+    // Nothing has been reloaded yet
+    let runtime = driver.runtime();
+    assert!(runtime.write().revert().is_err());
+
+    driver.update(
+        runtime.read(),
+        "mod.mun",
+        r"
+    pub static COUNTER: i32 = 0;
+
+    pub fn main() -> i32 {
+        COUNTER += 10;
+        COUNTER
+    }
+
+    pub fn reset() {
+        COUNTER = 0;
+    }
+    ",
+    );
+    assert_invoke_eq!(i32, 11, driver, "main");
+
+    let event = runtime
+        .write()
+        .revert()
+        .expect("Failed to revert assemblies");
+    assert_eq!(event.assemblies.len(), 1);
+    assert_eq!(event.removed_functions, vec!["reset".to_owned()]);
+
+    // The state is preserved when reverting
+    assert_invoke_eq!(i32, 12, driver, "main");
+    assert!(runtime.read().get_function_definition("reset").is_none());
+
+    // Only a single version is retained
+    assert!(runtime.write().revert().is_err());
+}
+
+#[test]
+fn hotreload_health_check() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    pub fn is_healthy() -> bool { true }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let failures = Arc::new(Mutex::new(Vec::new()));
+    let runtime = driver.runtime();
+    {
+        let mut runtime = runtime.write();
+        runtime.set_health_check(|runtime| {
+            let healthy: bool = invoke_fn!(runtime, "is_healthy").unwrap_or(false);
+            healthy
+        });
+
+        let failures = failures.clone();
+        runtime.on_reload_failed(move |event| failures.lock().push(event.clone()));
+    }
+
+    driver.update(
+        runtime.read(),
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 10 }
+    pub fn is_healthy() -> bool { true }
+    ",
+    );
+    assert_invoke_eq!(i32, 10, driver, "main");
+
+    // An unhealthy reload is reverted
+    driver.update_source(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 15 }
+    pub fn is_healthy() -> bool { false }
+    ",
+    );
+
+    let start_time = Instant::now();
+    while failures.lock().is_empty() {
+        assert!(!runtime.write().update());
+        if start_time.elapsed() > Duration::from_secs(10) {
+            panic!("runtime did not revert after recompilation within 10 seconds");
+        }
+        sleep(Duration::from_millis(1));
+    }
+
+    assert_eq!(failures.lock().len(), 1);
+    assert_eq!(
+        failures.lock()[0].error,
+        "Health check failed. Reverted to the last known good assemblies."
+    );
+    assert_invoke_eq!(i32, 10, driver, "main");
+}
+
+#[test]
+fn hotreload_migration() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Shape {
+        radius: f32,
+    }
+
+    pub fn new_shape(radius: f32) -> Shape {
+        Shape { radius }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let runtime = driver.runtime();
+    {
+        let events = events.clone();
+        runtime
+            .write()
+            .on_reload(move |event| events.lock().push(event.clone()));
+    }
+
+    let runtime_ref = runtime.read();
+    let shape: StructRef = invoke_fn!(runtime_ref, "new_shape", 2.0f32).unwrap();
+    let shape = shape.root(driver.runtime());
+
+    driver.update(
+        runtime_ref,
+        "mod.mun",
+        r#"
+    pub struct ShapeV1 {
+        radius: f32,
+    }
+
+    pub struct Shape {
+        width: f32,
+        height: f32,
+    }
+
+    impl Shape {
+        pub fn migrate(old: ShapeV1, new: Shape) {
+            new.width = old.radius * 2.0;
+            new.height = old.radius * 2.0;
+        }
+    }
+
+    // Only associated `migrate` functions are migration functions
+    pub fn migrate_shape(old: ShapeV1, new: Shape) {
+        new.width = 0.0;
+    }
+    "#,
+    );
+    assert_eq!(shape.by_ref().get::<f32>("width").unwrap(), 4.0);
+    assert_eq!(shape.by_ref().get::<f32>("height").unwrap(), 4.0);
+
+    let events = events.lock();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].migrated_types, vec!["Shape".to_owned()]);
+}
@@ -1,4 +1,6 @@
-use mun_runtime::{invoke_fn, ArgumentReflection, Marshal, ReturnTypeReflection, StructRef};
+use mun_runtime::{
+    invoke_fn, ArgumentReflection, ArrayRef, EnumRef, Marshal, ReturnTypeReflection, StructRef,
+};
 
 use mun_test::CompileAndRunTestDriver;
 
@@ -257,7 +259,7 @@ fn compiler_valid_utf8() {
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
     let foo_func = runtime_ref.get_function_definition("foo").unwrap();
     assert_eq!(
         unsafe { CStr::from_ptr(foo_func.prototype.name) }
@@ -390,7 +392,7 @@ fn marshal_struct() {
     }
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let int_data = TestData(3i32, 6i32);
     let bool_data = TestData(true, false);
@@ -448,7 +450,7 @@ fn marshal_struct() {
     // TODO: Find an ergonomic solution for this:
     // .unwrap_or_else(|e| e.wait(&mut runtime_ref));
 
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
     let c1: StructRef = invoke_fn!(runtime_ref, "foo_new", int_data.0, bool_data.0).unwrap();
     let c2: StructRef = invoke_fn!(runtime_ref, "foo_new", int_data.1, bool_data.1).unwrap();
     test_struct(&mut baz2, c1, c2);
@@ -654,7 +656,7 @@ fn test_primitive_types() {
     }
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let mut foo: StructRef = invoke_fn!(
         runtime_ref,
@@ -704,7 +706,7 @@ fn can_add_external_without_return() {
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let _: () = invoke_fn!(runtime_ref, "main").unwrap();
 }
@@ -728,3 +730,194 @@ fn signed_and_unsigned_rem() {
     assert_invoke_eq!(i32, -2, driver, "signed");
     assert_invoke_eq!(i32, 2, driver, "unsigned");
 }
+
+#[test]
+fn marshal_string() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Greeting { text: string, count: i32 }
+
+    pub fn hello() -> string {
+        "Hello, world!"
+    }
+
+    pub fn echo(s: string) -> string {
+        s
+    }
+
+    pub fn new_greeting(text: string) -> Greeting {
+        Greeting { text: text, count: 1 }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(String, "Hello, world!".to_owned(), driver, "hello");
+    assert_invoke_eq!(String, "echo".to_owned(), driver, "echo", "echo");
+    assert_invoke_eq!(
+        String,
+        "Grüße, 世界".to_owned(),
+        driver,
+        "echo",
+        String::from("Grüße, 世界")
+    );
+    assert_invoke_eq!(String, String::new(), driver, "echo", "");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.read();
+
+    let mut greeting: StructRef = invoke_fn!(runtime_ref, "new_greeting", "Hi").unwrap();
+    assert_eq!(Ok(String::from("Hi")), greeting.get::<String>("text"));
+    greeting.set("text", "Hello").unwrap();
+    assert_eq!(Ok(String::from("Hello")), greeting.get::<String>("text"));
+    assert_eq!(
+        Ok(String::from("Hello")),
+        greeting.replace("text", String::from("Bye"))
+    );
+    assert_eq!(Ok(String::from("Bye")), greeting.get::<String>("text"));
+    assert_eq!(Ok(1), greeting.get::<i32>("count"));
+}
+
+#[test]
+fn marshal_array() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32 }
+    pub struct(value) Bar { b: f64 }
+
+    pub fn ints() -> [i32] {
+        [1, 2, 3]
+    }
+
+    pub fn sum(a: [i32]) -> i32 {
+        a[0] + a[1] + a[2]
+    }
+
+    pub fn length(a: [i32]) -> usize {
+        len(a)
+    }
+
+    pub fn foos() -> [Foo] {
+        [Foo { a: 4 }, Foo { a: 5 }]
+    }
+
+    pub fn bars() -> [Bar] {
+        [Bar { b: 1.5 }]
+    }
+
+    pub fn strings() -> [string] {
+        ["foo", "bar"]
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.read();
+
+    let mut ints: ArrayRef<i32> = invoke_fn!(runtime_ref, "ints").unwrap();
+    assert_eq!(ints.len(), 3);
+    assert!(!ints.is_empty());
+    assert_eq!(ints.type_info().name(), "[core::i32]");
+    assert_eq!(
+        ints.iter().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![1, 2, 3])
+    );
+    assert!(ints.get(3).is_err());
+
+    ints.set(1, 5).unwrap();
+    assert_eq!(ints.get(1), Ok(5));
+    assert!(ints.set(3, 5).is_err());
+
+    let sum: i32 = invoke_fn!(runtime_ref, "sum", ints.clone()).unwrap();
+    assert_eq!(sum, 9);
+    let length: usize = invoke_fn!(runtime_ref, "length", ints).unwrap();
+    assert_eq!(length, 3);
+
+    let foos: ArrayRef<StructRef> = invoke_fn!(runtime_ref, "foos").unwrap();
+    assert_eq!(foos.get(1).unwrap().get::<i32>("a"), Ok(5));
+    assert!(foos.get(0).unwrap().get::<i32>("b").is_err());
+
+    let bars: ArrayRef<StructRef> = invoke_fn!(runtime_ref, "bars").unwrap();
+    assert_eq!(bars.get(0).unwrap().get::<f64>("b"), Ok(1.5));
+
+    let strings: ArrayRef<String> = invoke_fn!(runtime_ref, "strings").unwrap();
+    assert_eq!(
+        strings.iter().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![String::from("foo"), String::from("bar")])
+    );
+
+    let mismatched: ArrayRef<f32> = invoke_fn!(runtime_ref, "ints").unwrap();
+    assert!(mismatched.get(0).is_err());
+}
+
+#[test]
+fn marshal_enum() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub enum Shape {
+        Empty,
+        Circle(f32),
+        Rect { width: f32, height: f32 },
+    }
+
+    pub fn empty() -> Shape {
+        Shape::Empty
+    }
+
+    pub fn circle(radius: f32) -> Shape {
+        Shape::Circle(radius)
+    }
+
+    pub fn rect(width: f32, height: f32) -> Shape {
+        Shape::Rect { height, width }
+    }
+
+    pub fn area(shape: Shape) -> f32 {
+        match shape {
+            Shape::Empty => 0.0,
+            Shape::Circle(radius) => 3.0 * radius * radius,
+            Shape::Rect { width, height } => width * height,
+        }
+    }
+
+    pub fn shapes() -> [Shape] {
+        [Shape::Circle(1.0), Shape::Empty]
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.read();
+
+    let empty: EnumRef = invoke_fn!(runtime_ref, "empty").unwrap();
+    assert_eq!(empty.type_info().name(), "Shape");
+    assert_eq!(empty.tag(), 0);
+    assert!(empty.is_variant("Empty"));
+
+    let circle: EnumRef = invoke_fn!(runtime_ref, "circle", 2.0f32).unwrap();
+    assert_eq!(circle.variant_name(), "Circle");
+    assert_eq!(circle.get::<f32>("0"), Ok(2.0));
+    assert!(circle.get::<i32>("0").is_err());
+
+    let rect: EnumRef = invoke_fn!(runtime_ref, "rect", 2.0f32, 3.0f32).unwrap();
+    assert_eq!(rect.variant_name(), "Rect");
+    assert_eq!(rect.get::<f32>("width"), Ok(2.0));
+    assert_eq!(rect.get::<f32>("height"), Ok(3.0));
+    assert!(rect.get::<f32>("radius").is_err());
+
+    let area: f32 = invoke_fn!(runtime_ref, "area", empty).unwrap();
+    assert_eq!(area, 0.0);
+    let area: f32 = invoke_fn!(runtime_ref, "area", circle).unwrap();
+    assert_eq!(area, 12.0);
+    let area: f32 = invoke_fn!(runtime_ref, "area", rect).unwrap();
+    assert_eq!(area, 6.0);
+
+    let shapes: ArrayRef<EnumRef> = invoke_fn!(runtime_ref, "shapes").unwrap();
+    assert!(shapes.get(0).unwrap().is_variant("Circle"));
+    assert!(shapes.get(1).unwrap().is_variant("Empty"));
+}
@@ -1,11 +1,59 @@
-use mun_runtime::{invoke_fn, StructRef};
+use mun_runtime::{invoke_fn, CollectionPolicy, GcStrategy, StructRef};
 use mun_test::CompileAndRunTestDriver;
 
 #[macro_use]
 mod util;
 
-#[test]
-fn gc_trace() {
+/// Generates a test per garbage collection strategy for each of the specified test functions.
+macro_rules! gc_tests {
+    ($($name:ident),+ $(,)?) => {
+        mod mark_sweep {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name(super::GcStrategy::MarkSweep)
+                }
+            )+
+        }
+
+        mod generational {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name(super::GcStrategy::Generational)
+                }
+            )+
+        }
+    };
+}
+
+gc_tests!(
+    gc_trace,
+    gc_trace_array,
+    gc_trace_value_struct,
+    gc_trace_tuple_struct,
+    gc_trace_value_struct_array,
+    gc_policy,
+    gc_snapshot,
+    map_struct_insert_field1,
+    map_struct_insert_field2,
+    map_struct_insert_field3,
+    map_struct_remove_field1,
+    map_struct_remove_field2,
+    map_struct_remove_field3,
+    map_struct_cast_fields1,
+    map_struct_cast_fields2,
+    map_struct_swap_fields1,
+    map_struct_swap_fields2,
+    map_struct_rename_field1,
+    map_struct_rename_field2,
+    map_struct_all,
+    delete_used_struct,
+    nested_structs,
+    insert_struct,
+);
+
+fn gc_trace(strategy: GcStrategy) {
     let driver = CompileAndRunTestDriver::new(
         r#"
     pub struct Foo {
@@ -26,12 +74,12 @@ fn gc_trace() {
         }
     }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let mut runtime_ref = runtime.write();
 
     let value: StructRef = invoke_fn!(runtime_ref, "new_foo").unwrap();
     let value = value.root(driver.runtime());
@@ -45,8 +93,301 @@ fn gc_trace() {
     assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
 }
 
-#[test]
-fn map_struct_insert_field1() {
+fn gc_trace_array(strategy: GcStrategy) {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        bars: [Bar],
+    }
+
+    pub struct Bar {
+        baz: i64
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo {
+            bars: [Bar { baz: 1 }, Bar { baz: 2 }],
+        }
+    }
+    "#,
+        |builder| builder.set_gc_strategy(strategy),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let mut runtime_ref = runtime.write();
+
+    let value: StructRef = invoke_fn!(runtime_ref, "new_foo").unwrap();
+    let value = value.root(driver.runtime());
+
+    // The array and its elements are reachable through the rooted struct
+    assert_eq!(runtime_ref.gc_collect(), false);
+    assert!(runtime_ref.gc_stats().allocated_memory > 0);
+
+    drop(value);
+
+    assert_eq!(runtime_ref.gc_collect(), true);
+    assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
+}
+
+fn gc_trace_value_struct(strategy: GcStrategy) {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(gc) Inner {
+        value: i64,
+    }
+
+    pub struct(value) Wrapper {
+        inner: Inner,
+    }
+
+    pub struct Outer {
+        wrapper: Wrapper,
+    }
+
+    pub fn new_outer(value: i64) -> Outer {
+        Outer {
+            wrapper: Wrapper {
+                inner: Inner { value },
+            },
+        }
+    }
+
+    pub fn inner_value(outer: Outer) -> i64 {
+        outer.wrapper.inner.value
+    }
+    "#,
+        |builder| builder.set_gc_strategy(strategy),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let mut runtime_ref = runtime.write();
+
+    let value: StructRef = invoke_fn!(runtime_ref, "new_outer", 5i64).unwrap();
+    let value = value.root(driver.runtime());
+
+    // The inner struct is reachable through the value struct stored in the rooted struct
+    assert_eq!(runtime_ref.gc_collect(), false);
+    let inner_value: i64 = invoke_fn!(runtime_ref, "inner_value", unsafe {
+        value.as_ref(&runtime_ref)
+    })
+    .unwrap();
+    assert_eq!(inner_value, 5);
+
+    drop(value);
+
+    assert_eq!(runtime_ref.gc_collect(), true);
+    assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
+}
+
+fn gc_trace_tuple_struct(strategy: GcStrategy) {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(gc) Inner(i64);
+    pub struct(value) Pair(Inner, Inner);
+    pub struct Outer(f32, Pair);
+
+    pub fn new_outer(a: i64, b: i64) -> Outer {
+        Outer(1.0, Pair(Inner(a), Inner(b)))
+    }
+
+    pub fn sum(outer: Outer) -> i64 {
+        let pair = outer.1;
+        let a = pair.0;
+        let b = pair.1;
+        a.0 + b.0
+    }
+    "#,
+        |builder| builder.set_gc_strategy(strategy),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let mut runtime_ref = runtime.write();
+
+    let value: StructRef = invoke_fn!(runtime_ref, "new_outer", 2i64, 3i64).unwrap();
+    let value = value.root(driver.runtime());
+
+    // Both inner structs are reachable through the tuple struct stored in the rooted struct
+    assert_eq!(runtime_ref.gc_collect(), false);
+    let sum: i64 = invoke_fn!(runtime_ref, "sum", unsafe { value.as_ref(&runtime_ref) }).unwrap();
+    assert_eq!(sum, 5);
+
+    drop(value);
+
+    assert_eq!(runtime_ref.gc_collect(), true);
+    assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
+}
+
+fn gc_trace_value_struct_array(strategy: GcStrategy) {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(gc) Inner {
+        value: i64,
+    }
+
+    pub struct(value) Wrapper {
+        inner: Inner,
+    }
+
+    pub struct Outer {
+        wrappers: [Wrapper],
+    }
+
+    pub fn new_outer() -> Outer {
+        Outer {
+            wrappers: [
+                Wrapper { inner: Inner { value: 1 } },
+                Wrapper { inner: Inner { value: 2 } },
+            ],
+        }
+    }
+
+    pub fn sum(outer: Outer) -> i64 {
+        outer.wrappers[0].inner.value + outer.wrappers[1].inner.value
+    }
+    "#,
+        |builder| builder.set_gc_strategy(strategy),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let mut runtime_ref = runtime.write();
+
+    let value: StructRef = invoke_fn!(runtime_ref, "new_outer").unwrap();
+    let value = value.root(driver.runtime());
+
+    // The inner structs are reachable through the value structs stored in the array
+    assert_eq!(runtime_ref.gc_collect(), false);
+    let sum: i64 = invoke_fn!(runtime_ref, "sum", unsafe { value.as_ref(&runtime_ref) }).unwrap();
+    assert_eq!(sum, 3);
+
+    drop(value);
+
+    assert_eq!(runtime_ref.gc_collect(), true);
+    assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
+}
+
+fn gc_policy(strategy: GcStrategy) {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        bar: Bar,
+    }
+
+    pub struct Bar {
+        baz: i64
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo {
+            bar: Bar {
+                baz: 3
+            }
+        }
+    }
+    "#,
+        |builder| {
+            builder
+                .set_gc_strategy(strategy)
+                .set_gc_policy(CollectionPolicy::Allocations(1))
+        },
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    {
+        let runtime_ref = runtime.read();
+        let _: StructRef = invoke_fn!(runtime_ref, "new_foo").unwrap();
+
+        // Memory is not collected while Mun functions can still reference it
+        let stats = runtime_ref.gc_stats();
+        assert_eq!(stats.allocated_objects, 2);
+        assert_eq!(stats.collections, 0);
+    }
+
+    // A collection is due, so updating the runtime collects the unreferenced objects
+    runtime.write().update();
+
+    let stats = runtime.read().gc_stats();
+    assert_eq!(stats.allocated_memory, 0);
+    assert_eq!(stats.allocated_objects, 0);
+    assert_eq!(stats.collections, 1);
+    assert!(stats.freed_memory > 0);
+}
+
+fn gc_snapshot(strategy: GcStrategy) {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        bars: [Bar],
+    }
+
+    pub struct Bar {
+        baz: i64
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo {
+            bars: [Bar { baz: 1 }, Bar { baz: 2 }],
+        }
+    }
+
+    pub fn new_bar() -> Bar {
+        Bar { baz: 3 }
+    }
+    "#,
+        |builder| builder.set_gc_strategy(strategy),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.read();
+
+    let foo: StructRef = invoke_fn!(runtime_ref, "new_foo").unwrap();
+    let _foo = foo.root(driver.runtime());
+    let _: StructRef = invoke_fn!(runtime_ref, "new_bar").unwrap();
+
+    let snapshot = runtime_ref.gc_snapshot();
+    assert_eq!(snapshot.objects().len(), 5);
+    assert_eq!(
+        snapshot.total_size(),
+        runtime_ref.gc_stats().allocated_memory
+    );
+
+    let summary = snapshot.summary();
+    let bar_summary = summary.iter().find(|summary| summary.ty == "Bar").unwrap();
+    assert_eq!(bar_summary.count, 3);
+    let foo_summary = summary.iter().find(|summary| summary.ty == "Foo").unwrap();
+    assert_eq!(foo_summary.count, 1);
+
+    // Only the `Bar`s in the array of the rooted `Foo` are retained
+    let foo_handle = snapshot
+        .objects()
+        .iter()
+        .find(|object| object.ty == "Foo")
+        .unwrap()
+        .handle;
+    let retained_bars = snapshot
+        .objects()
+        .iter()
+        .filter(|object| object.ty == "Bar")
+        .filter_map(|object| snapshot.retaining_path(object.handle))
+        .collect::<Vec<_>>();
+    assert_eq!(retained_bars.len(), 2);
+    for path in retained_bars {
+        // `Foo` -> `[Bar]` -> `Bar`
+        assert_eq!(path.len(), 3);
+        assert_eq!(path[0], foo_handle);
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&snapshot.to_json()).unwrap();
+    assert_eq!(json["objects"].as_array().unwrap().len(), 5);
+    assert_eq!(json["summary"].as_array().unwrap().len(), 3);
+}
+
+fn map_struct_insert_field1(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -58,12 +399,12 @@ fn map_struct_insert_field1() {
             Foo { b, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let b = 5i64;
     let c = 3.0f64;
@@ -86,8 +427,7 @@ fn map_struct_insert_field1() {
     assert_eq!(foo.by_ref().get::<f64>("c").unwrap(), c);
 }
 
-#[test]
-fn map_struct_insert_field2() {
+fn map_struct_insert_field2(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -99,12 +439,12 @@ fn map_struct_insert_field2() {
             Foo { a, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 5i64;
     let c = 3.0f64;
@@ -127,8 +467,7 @@ fn map_struct_insert_field2() {
     assert_eq!(foo.by_ref().get::<f64>("c").unwrap(), c);
 }
 
-#[test]
-fn map_struct_insert_field3() {
+fn map_struct_insert_field3(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -140,12 +479,12 @@ fn map_struct_insert_field3() {
             Foo { a, b }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 5i64;
     let b = 3.0f64;
@@ -168,8 +507,7 @@ fn map_struct_insert_field3() {
     assert_eq!(foo.by_ref().get::<f64>("c").unwrap(), 0.0);
 }
 
-#[test]
-fn map_struct_remove_field1() {
+fn map_struct_remove_field1(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -182,12 +520,12 @@ fn map_struct_remove_field1() {
             Foo { a, b, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 1.0f64;
     let b = 3.0f64;
@@ -207,8 +545,7 @@ fn map_struct_remove_field1() {
     assert_eq!(foo.by_ref().get::<i64>("c").unwrap(), c);
 }
 
-#[test]
-fn map_struct_remove_field2() {
+fn map_struct_remove_field2(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -221,12 +558,12 @@ fn map_struct_remove_field2() {
             Foo { a, b, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 1.0f64;
     let b = 5i64;
@@ -246,8 +583,7 @@ fn map_struct_remove_field2() {
     assert_eq!(foo.by_ref().get::<i64>("b").unwrap(), b);
 }
 
-#[test]
-fn map_struct_remove_field3() {
+fn map_struct_remove_field3(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -260,12 +596,12 @@ fn map_struct_remove_field3() {
             Foo { a, b, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 5i64;
     let b = 1.0f64;
@@ -285,8 +621,7 @@ fn map_struct_remove_field3() {
     assert_eq!(foo.by_ref().get::<i64>("a").unwrap(), a);
 }
 
-#[test]
-fn map_struct_cast_fields1() {
+fn map_struct_cast_fields1(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo(
@@ -301,12 +636,12 @@ fn map_struct_cast_fields1() {
             Foo(a, b, c, d, e)
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 1u8;
     let b = -2i16;
@@ -336,8 +671,7 @@ fn map_struct_cast_fields1() {
     assert_eq!(foo.by_ref().get::<f64>("4").unwrap(), e.into());
 }
 
-#[test]
-fn map_struct_cast_fields2() {
+fn map_struct_cast_fields2(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo(
@@ -348,12 +682,12 @@ fn map_struct_cast_fields2() {
             Foo(a)
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = -2i16;
     let foo: StructRef = invoke_fn!(runtime_ref, "foo_new", a).unwrap();
@@ -372,8 +706,7 @@ fn map_struct_cast_fields2() {
     assert_eq!(foo.by_ref().get::<u16>("0").unwrap(), 0);
 }
 
-#[test]
-fn map_struct_swap_fields1() {
+fn map_struct_swap_fields1(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -386,12 +719,12 @@ fn map_struct_swap_fields1() {
             Foo { a, b, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 1.0f64;
     let b = 3i64;
@@ -415,8 +748,7 @@ fn map_struct_swap_fields1() {
     assert_eq!(foo.by_ref().get::<f64>("c").unwrap(), c);
 }
 
-#[test]
-fn map_struct_swap_fields2() {
+fn map_struct_swap_fields2(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -430,12 +762,12 @@ fn map_struct_swap_fields2() {
             Foo { a, b, c, d }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 1.0f64;
     let b = 3i64;
@@ -462,8 +794,7 @@ fn map_struct_swap_fields2() {
     assert_eq!(foo.by_ref().get::<i64>("d").unwrap(), d);
 }
 
-#[test]
-fn map_struct_rename_field1() {
+fn map_struct_rename_field1(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -476,12 +807,12 @@ fn map_struct_rename_field1() {
             Foo { a, b, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 5i64;
     let b = 1.0f64;
@@ -505,8 +836,7 @@ fn map_struct_rename_field1() {
     assert_eq!(foo.by_ref().get::<f64>("c").unwrap(), c);
 }
 
-#[test]
-fn map_struct_rename_field2() {
+fn map_struct_rename_field2(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -519,12 +849,12 @@ fn map_struct_rename_field2() {
             Foo { a, b, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 5i64;
     let b = 1.0f64;
@@ -548,8 +878,7 @@ fn map_struct_rename_field2() {
     assert_eq!(foo.by_ref().get::<f64>("f").unwrap(), c);
 }
 
-#[test]
-fn map_struct_all() {
+fn map_struct_all(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -563,12 +892,12 @@ fn map_struct_all() {
             Foo { a, b, c, d }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 5i32;
     let b = 1.0f64;
@@ -596,8 +925,7 @@ fn map_struct_all() {
     assert_eq!(foo.by_ref().get::<i32>("f").unwrap(), 0);
 }
 
-#[test]
-fn delete_used_struct() {
+fn delete_used_struct(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -610,12 +938,12 @@ fn delete_used_struct() {
             Foo { a, b, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 5i64;
     let b = 1.0f64;
@@ -637,12 +965,12 @@ fn delete_used_struct() {
 
     assert!(driver
         .runtime()
-        .borrow()
+        .read()
         .get_function_definition("foo_new")
         .is_none());
     assert!(driver
         .runtime()
-        .borrow()
+        .read()
         .get_function_definition("bar_new")
         .is_some());
     assert_eq!(foo.by_ref().get::<i64>("a").unwrap(), a);
@@ -650,8 +978,7 @@ fn delete_used_struct() {
     assert_eq!(foo.by_ref().get::<f64>("c").unwrap(), c);
 }
 
-#[test]
-fn nested_structs() {
+fn nested_structs(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
     pub struct(gc) GcStruct(f32, f32);
@@ -676,12 +1003,12 @@ fn nested_structs() {
         ValueWrapper(a, b)
     }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = -3.14f32;
     let b = 6.18f32;
@@ -738,7 +1065,7 @@ fn nested_structs() {
 
     // Tests an identity mapping
     driver.update(
-        runtime.borrow(),
+        runtime.read(),
         "mod.mun",
         r#"
     pub struct(gc) GcStruct(f64, f64);
@@ -772,7 +1099,7 @@ fn nested_structs() {
 
     // Tests mapping of `gc -> value`, `value -> gc`
     driver.update(
-        runtime.borrow(),
+        runtime.read(),
         "mod.mun",
         r#"
     struct(value) GcStruct(f64, f64);
@@ -798,7 +1125,7 @@ fn nested_structs() {
     // Tests mapping of different struct type, when `gc -> value`, `value -> gc`, and
     // retention of an old library (due to removal of `GcStruct` and `ValueStruct`)
     driver.update(
-        runtime.borrow(),
+        runtime.read(),
         "mod.mun",
         r#"
     struct(gc) GcStruct2(f64);
@@ -846,7 +1173,7 @@ fn nested_structs() {
 
     // Tests mapping of different struct type, when `gc -> gc`, `value -> value`
     driver.update(
-        runtime.borrow(),
+        runtime.read(),
         "mod.mun",
         r#"
     struct(gc) GcStruct(f64, f64);
@@ -881,8 +1208,7 @@ fn nested_structs() {
     assert_eq!(value_1.get::<f64>("1"), Ok(0.0));
 }
 
-#[test]
-fn insert_struct() {
+fn insert_struct(strategy: GcStrategy) {
     let mut driver = CompileAndRunTestDriver::new(
         r#"
         pub struct Foo {
@@ -894,12 +1220,12 @@ fn insert_struct() {
             Foo { a, c }
         }
     "#,
-        |builder| builder,
+        |builder| builder.set_gc_strategy(strategy),
     )
     .expect("Failed to build test driver");
 
     let runtime = driver.runtime();
-    let runtime_ref = runtime.borrow();
+    let runtime_ref = runtime.read();
 
     let a = 5i64;
     let c = 3.0f64;
@@ -931,3 +1257,95 @@ fn insert_struct() {
     let d = foo.by_ref().get::<StructRef>("d").unwrap();
     assert_eq!(d.get::<f64>("0"), Ok(0.0));
 }
+
+#[test]
+fn gc_collect_minor() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        bar: Bar,
+    }
+
+    pub struct Bar {
+        baz: i64
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo {
+            bar: Bar {
+                baz: 3
+            }
+        }
+    }
+    "#,
+        |builder| builder.set_gc_strategy(GcStrategy::Generational),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let mut runtime_ref = runtime.write();
+
+    // Unreferenced objects are collected by a minor collection
+    let _: StructRef = invoke_fn!(runtime_ref, "new_foo").unwrap();
+    assert_eq!(runtime_ref.gc_collect_minor(), true);
+    assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
+
+    // Objects that survive a minor collection are only collected by a full collection
+    let value: StructRef = invoke_fn!(runtime_ref, "new_foo").unwrap();
+    let value = value.root(driver.runtime());
+    assert_eq!(runtime_ref.gc_collect_minor(), false);
+
+    drop(value);
+
+    assert_eq!(runtime_ref.gc_collect_minor(), false);
+    assert!(runtime_ref.gc_stats().allocated_memory > 0);
+    assert_eq!(runtime_ref.gc_collect(), true);
+    assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn gc_collect_minor_write_barrier() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        bar: Bar,
+    }
+
+    pub struct Bar {
+        baz: i64
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo {
+            bar: Bar {
+                baz: 3
+            }
+        }
+    }
+
+    pub fn set_bar(foo: Foo, baz: i64) {
+        foo.bar = Bar { baz: baz };
+    }
+    "#,
+        |builder| builder.set_gc_strategy(GcStrategy::Generational),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let mut runtime_ref = runtime.write();
+
+    // Promote `foo` to the old generation
+    let foo: StructRef = invoke_fn!(runtime_ref, "new_foo").unwrap();
+    let foo = foo.root(driver.runtime());
+    assert_eq!(runtime_ref.gc_collect_minor(), false);
+
+    // The young object that is stored in the old object is traced through the write barrier
+    // Safety: `foo` was allocated by `runtime_ref`
+    let foo_ref = unsafe { foo.as_ref(&runtime_ref) };
+    let _: () = invoke_fn!(runtime_ref, "set_bar", foo_ref, 5i64).unwrap();
+    assert_eq!(runtime_ref.gc_collect_minor(), false);
+
+    let foo_ref = unsafe { foo.as_ref(&runtime_ref) };
+    let bar = foo_ref.get::<StructRef>("bar").unwrap();
+    assert_eq!(bar.get::<i64>("baz"), Ok(5));
+}
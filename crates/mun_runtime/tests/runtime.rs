@@ -1,5 +1,6 @@
+use mun_runtime::{invoke_fn, ClosureRef, RootedClosure, StructRef, Value};
 use mun_test::CompileAndRunTestDriver;
-use std::io;
+use std::{io, thread};
 
 #[macro_use]
 mod util;
@@ -114,3 +115,605 @@ fn arg_missing_bug() {
     );
     driver.unwrap();
 }
+
+#[test]
+fn associated_functions() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Foo {
+        a: i32,
+    }
+
+    impl Foo {
+        pub fn new(a: i32) -> Self {
+            Self { a: a }
+        }
+
+        pub fn get(self) -> i32 {
+            self.a
+        }
+
+        fn double(self) -> i32 {
+            self.a * 2
+        }
+    }
+
+    pub fn main() -> i32 {
+        let foo = Foo::new(5);
+        foo.get() + foo.double()
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 15, driver, "main");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.read();
+    assert!(runtime_ref.get_function_definition("Foo::new").is_some());
+    assert!(runtime_ref.get_function_definition("Foo::get").is_some());
+    assert!(runtime_ref.get_function_definition("Foo::double").is_none());
+}
+
+#[test]
+fn for_loops() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn sum(n: i32) -> i32 {
+        let sum = 0;
+        for i in 0..n {
+            sum += i;
+        }
+        sum
+    }
+
+    pub fn sum_inclusive(n: u32) -> u32 {
+        let sum = 0;
+        for i in 0..=n {
+            sum += i;
+        }
+        sum
+    }
+
+    pub fn count_inclusive(n: u8) -> u32 {
+        let count = 0;
+        for _ in 0..=n {
+            count += 1;
+        }
+        count
+    }
+
+    pub fn first_multiple_of(n: i32, max: i32) -> i32 {
+        let found = -1;
+        for i in 1..max {
+            if i % n == 0 {
+                found = i;
+                break;
+            }
+        }
+        found
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 45, driver, "sum", 10i32);
+    assert_invoke_eq!(i32, 0, driver, "sum", 0i32);
+    assert_invoke_eq!(u32, 55, driver, "sum_inclusive", 10u32);
+    assert_invoke_eq!(u32, 256, driver, "count_inclusive", 255u8);
+    assert_invoke_eq!(i32, 7, driver, "first_multiple_of", 7i32, 20i32);
+    assert_invoke_eq!(i32, -1, driver, "first_multiple_of", 30i32, 20i32);
+}
+
+#[test]
+fn continue_in_loops() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn sum_odd_for(n: i32) -> i32 {
+        let sum = 0;
+        for i in 0..n {
+            if i % 2 == 0 {
+                continue;
+            }
+            sum += i;
+        }
+        sum
+    }
+
+    pub fn sum_odd_while(n: i32) -> i32 {
+        let sum = 0;
+        let i = 0;
+        while i < n {
+            i += 1;
+            if i % 2 == 0 {
+                continue;
+            }
+            sum += i;
+        }
+        sum
+    }
+
+    pub fn sum_odd_loop(n: i32) -> i32 {
+        let sum = 0;
+        let i = 0;
+        loop {
+            i += 1;
+            if i > n {
+                break sum;
+            }
+            if i % 2 == 0 {
+                continue;
+            }
+            sum += i;
+        }
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 25, driver, "sum_odd_for", 10i32);
+    assert_invoke_eq!(i32, 25, driver, "sum_odd_while", 10i32);
+    assert_invoke_eq!(i32, 25, driver, "sum_odd_loop", 10i32);
+}
+
+#[test]
+fn generics() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Pair<A, B> {
+        a: A,
+        b: B,
+    }
+
+    struct(value) Wrapper<T>(T);
+
+    fn pick<T>(cond: bool, a: T, b: T) -> T {
+        if cond { a } else { b }
+    }
+
+    fn swap<A, B>(p: Pair<A, B>) -> Pair<B, A> {
+        Pair { a: p.b, b: p.a }
+    }
+
+    fn unwrap<T>(w: Wrapper<T>) -> T {
+        w.0
+    }
+
+    pub fn pick_int(cond: bool, a: i32, b: i32) -> i32 {
+        pick(cond, a, b)
+    }
+
+    pub fn pick_float(cond: bool, a: f64, b: f64) -> f64 {
+        pick(cond, a, b)
+    }
+
+    pub fn swapped(a: i32, b: f64) -> Pair<f64, i32> {
+        swap(Pair { a: a, b: b })
+    }
+
+    pub fn new_pair(a: i32, b: f64) -> Pair<i32, f64> {
+        Pair { a: unwrap(Wrapper(a)), b: unwrap(Wrapper(b)) }
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 1, driver, "pick_int", true, 1i32, 2i32);
+    assert_invoke_eq!(i32, 2, driver, "pick_int", false, 1i32, 2i32);
+    assert_invoke_eq!(f64, 2.5, driver, "pick_float", false, 1.5f64, 2.5f64);
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.read();
+    assert!(runtime_ref.get_function_definition("pick").is_none());
+
+    let pair: StructRef = invoke_fn!(runtime_ref, "new_pair", 3i32, 4.5f64).unwrap();
+    assert_eq!(pair.get::<i32>("a"), Ok(3));
+    assert_eq!(pair.get::<f64>("b"), Ok(4.5));
+
+    let swapped: StructRef = invoke_fn!(runtime_ref, "swapped", 3i32, 4.5f64).unwrap();
+    assert_eq!(swapped.get::<f64>("a"), Ok(4.5));
+    assert_eq!(swapped.get::<i32>("b"), Ok(3));
+
+    // Every instantiation of a generic struct is a distinct type
+    assert_eq!(pair.type_info().name(), "Pair<core::i32, core::f64>");
+    assert_eq!(swapped.type_info().name(), "Pair<core::f64, core::i32>");
+    assert_ne!(pair.type_info().guid, swapped.type_info().guid);
+}
+
+#[test]
+fn traits() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    trait Update {
+        fn update(self, dt: f32);
+
+        fn update_twice(self, dt: f32) {
+            self.update(dt);
+            self.update(dt);
+        }
+    }
+
+    pub struct Player {
+        pos: f32,
+        speed: f32,
+    }
+
+    pub struct Timer {
+        elapsed: f32,
+    }
+
+    impl Update for Player {
+        fn update(self, dt: f32) {
+            self.pos += self.speed * dt;
+        }
+    }
+
+    impl Update for Timer {
+        fn update(self, dt: f32) {
+            self.elapsed += dt;
+        }
+    }
+
+    fn tick<T: Update>(entity: T, dt: f32) {
+        entity.update_twice(dt)
+    }
+
+    pub fn simulate(dt: f32) -> f32 {
+        let player = Player { pos: 0.0, speed: 2.0 };
+        let timer = Timer { elapsed: 0.0 };
+        tick(player, dt);
+        tick(timer, dt);
+        timer.update(dt);
+        player.pos + timer.elapsed
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(f32, 3.5, driver, "simulate", 0.5f32);
+
+    // Functions declared in a trait are only generated for the types that implement the trait
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.read();
+    assert!(runtime_ref
+        .get_function_definition("Update::update_twice")
+        .is_none());
+}
+
+#[test]
+fn closures() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Button {
+        clicks: i32,
+        on_click: fn(i32) -> i32,
+    }
+
+    fn double(value: i32) -> i32 {
+        value * 2
+    }
+
+    pub fn counter(start: i32) -> fn() -> i32 {
+        let count = start;
+        || {
+            count += 1;
+            count
+        }
+    }
+
+    pub fn adder(offset: i32) -> fn(i32, i32) -> i32 {
+        |a, b| a + b + offset
+    }
+
+    pub fn new_button(scale: i32) -> Button {
+        Button { clicks: 0, on_click: |x| x * scale }
+    }
+
+    pub fn new_doubling_button() -> Button {
+        Button { clicks: 0, on_click: double }
+    }
+
+    pub fn click(button: Button) -> i32 {
+        button.clicks += 1;
+        (button.on_click)(button.clicks)
+    }
+
+    pub fn apply(f: fn(i32) -> i32, value: i32) -> i32 {
+        f(value)
+    }
+
+    pub fn apply_twice(value: i32) -> i32 {
+        let twice = |f: fn(i32) -> i32, x: i32| f(f(x));
+        twice(double, value)
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 20, driver, "apply_twice", 5i32);
+
+    let runtime = driver.runtime();
+
+    // A host can store a closure and invoke it later, the captured variables persist between
+    // calls
+    let counter: RootedClosure = {
+        let runtime_ref = runtime.read();
+        let counter: ClosureRef = invoke_fn!(runtime_ref, "counter", 10i32).unwrap();
+        assert_eq!(counter.signature(), "fn() -> core::i32");
+        counter.root(runtime.clone())
+    };
+    runtime.write().gc_collect();
+    {
+        let runtime_ref = runtime.read();
+        let counter = unsafe { counter.as_ref(&runtime_ref) };
+        assert_eq!(counter.invoke::<_, i32>(()), Ok(11));
+        assert_eq!(counter.invoke::<_, i32>(()), Ok(12));
+        assert!(counter.invoke::<_, i32>((1i32,)).is_err());
+        assert!(counter.invoke::<_, f64>(()).is_err());
+    }
+
+    let runtime_ref = runtime.read();
+    let adder: ClosureRef = invoke_fn!(runtime_ref, "adder", 100i32).unwrap();
+    assert_eq!(adder.invoke::<_, i32>((1i32, 2i32)), Ok(103));
+    assert!(adder.invoke::<_, i32>((1i32, 2.0f64)).is_err());
+
+    // Closures can be stored in fields
+    let button: StructRef = invoke_fn!(runtime_ref, "new_button", 3i32).unwrap();
+    let result: i32 = invoke_fn!(runtime_ref, "click", button.clone()).unwrap();
+    assert_eq!(result, 3);
+    let result: i32 = invoke_fn!(runtime_ref, "click", button.clone()).unwrap();
+    assert_eq!(result, 6);
+    let on_click = button.get::<ClosureRef>("on_click").unwrap();
+    assert_eq!(on_click.invoke::<_, i32>((5i32,)), Ok(15));
+
+    // Functions can be used as values
+    let button: StructRef = invoke_fn!(runtime_ref, "new_doubling_button").unwrap();
+    let result: i32 = invoke_fn!(runtime_ref, "click", button.clone()).unwrap();
+    assert_eq!(result, 2);
+
+    // Closures can be passed as arguments
+    let result: i32 = invoke_fn!(runtime_ref, "apply", on_click, 7i32).unwrap();
+    assert_eq!(result, 21);
+    let result: Result<i32, _> = invoke_fn!(runtime_ref, "apply", adder, 7i32);
+    assert!(result.is_err());
+}
+
+#[test]
+fn globals() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub const MAX: i32 = 10 * 10;
+    const STEP: i32 = 3;
+    pub static COUNTER: i32 = MAX - 90;
+    static RATIO: f64 = 1.0 / 2.0;
+
+    pub fn increment() -> i32 {
+        COUNTER += STEP;
+        COUNTER
+    }
+
+    pub fn max() -> i32 { MAX }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 100, driver, "max");
+
+    let runtime = driver.runtime();
+    {
+        let runtime_ref = runtime.read();
+        assert_eq!(runtime_ref.get_global::<i32>("MAX"), Ok(100));
+        assert_eq!(runtime_ref.get_global::<i32>("COUNTER"), Ok(10));
+        assert_eq!(runtime_ref.get_global::<f64>("RATIO"), Ok(0.5));
+
+        // Private constants are inlined and not exported
+        assert!(runtime_ref.get_global::<i32>("STEP").is_err());
+        assert!(runtime_ref.get_global::<f64>("COUNTER").is_err());
+    }
+
+    assert_invoke_eq!(i32, 13, driver, "increment");
+    assert_eq!(runtime.read().get_global::<i32>("COUNTER"), Ok(13));
+
+    // The host can write to statics, but not to constants
+    {
+        let mut runtime_ref = runtime.write();
+        assert_eq!(runtime_ref.set_global("COUNTER", 20i32), Ok(()));
+        assert!(runtime_ref.set_global("COUNTER", 20.0f64).is_err());
+        assert!(runtime_ref.set_global("MAX", 20i32).is_err());
+        assert!(runtime_ref.set_global("UNKNOWN", 20i32).is_err());
+    }
+    assert_invoke_eq!(i32, 23, driver, "increment");
+    assert_eq!(runtime.read().get_global::<i32>("MAX"), Ok(100));
+}
+
+#[test]
+fn multithreaded() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    pub fn fibonacci(n: i64) -> i64 {
+        if n <= 1 {
+            n
+        } else {
+            fibonacci(n - 1) + fibonacci(n - 2)
+        }
+    }
+
+    pub fn new_vec2(x: f32, y: f32) -> Vec2 {
+        Vec2 { x: x, y: y }
+    }
+
+    pub static LAST: i64 = -1;
+    pub static ENABLED: bool = false;
+
+    pub fn set_last(value: i64) -> bool {
+        LAST = value;
+        ENABLED = !ENABLED;
+        ENABLED
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+
+    // Functions can be invoked concurrently from multiple threads
+    let threads: Vec<_> = (0..4i64)
+        .map(|idx| {
+            let runtime = runtime.clone();
+            thread::spawn(move || {
+                let runtime_ref = runtime.read();
+                let result: i64 = invoke_fn!(runtime_ref, "fibonacci", 10 + idx).unwrap();
+                result
+            })
+        })
+        .collect();
+    let results: Vec<i64> = threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect();
+    assert_eq!(results, vec![55, 89, 144, 233]);
+
+    // Statics are accessed atomically, so functions that access the same statics can be invoked
+    // concurrently, while the host reads them
+    let threads: Vec<_> = (0..4i64)
+        .map(|idx| {
+            let runtime = runtime.clone();
+            thread::spawn(move || {
+                let runtime_ref = runtime.read();
+                for _ in 0..100 {
+                    let _: bool = invoke_fn!(runtime_ref, "set_last", idx).unwrap();
+                    let last = runtime_ref.get_global::<i64>("LAST").unwrap();
+                    assert!((0..4).contains(&last));
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert!(runtime.read().get_global::<bool>("ENABLED").is_ok());
+
+    // Rooted structs can be moved to other threads
+    let vec2 = {
+        let runtime_ref = runtime.read();
+        let vec2: StructRef = invoke_fn!(runtime_ref, "new_vec2", 1.0f32, 2.0f32).unwrap();
+        vec2.root(runtime.clone())
+    };
+    let y = thread::spawn(move || vec2.by_ref().get::<f32>("y").unwrap())
+        .join()
+        .unwrap();
+    assert_eq!(y, 2.0);
+
+    // The runtime can be updated while no other threads are invoking functions
+    assert!(!runtime.write().update());
+}
+
+#[test]
+fn invoke() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    pub fn is_positive(a: f64) -> bool { a > 0.0 }
+    pub fn negate(a: i8) -> i8 { -a }
+    pub fn echo(s: string) -> string { s }
+    pub fn new_vec2(x: f32, y: f32) -> Vec2 { Vec2 { x: x, y: y } }
+    pub fn length_squared(v: Vec2) -> f32 { v.x * v.x + v.y * v.y }
+    pub fn nothing() {}
+    pub fn multiples(n: i32) -> [i32] { [n, n * 2, n * 3] }
+    pub fn sum_three(a: [i32]) -> i32 { a[0] + a[1] + a[2] }
+    pub fn greetings() -> [string] { ["hello", "world"] }
+
+    pub fn sum(
+        a: i64, b: i64, c: i64, d: i64, e: i64, f: i64, g: i64, h: i64,
+        i: i64, j: i64, k: i64, l: i64, m: i64, n: i64, o: i64, p: i64, q: i64,
+    ) -> i64 {
+        a + b + c + d + e + f + g + h + i + j + k + l + m + n + o + p + q
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.read();
+
+    assert!(matches!(
+        runtime_ref.invoke("is_positive", &[Value::F64(2.0)]),
+        Ok(Value::Bool(true))
+    ));
+    assert!(matches!(
+        runtime_ref.invoke("negate", &[Value::I8(5)]),
+        Ok(Value::I8(-5))
+    ));
+    assert!(matches!(
+        runtime_ref.invoke("echo", &["echo".into()]),
+        Ok(Value::String(s)) if s == "echo"
+    ));
+    assert!(matches!(
+        runtime_ref.invoke("nothing", &[]),
+        Ok(Value::Unit)
+    ));
+
+    // Structs can be passed to and returned from functions
+    let vec2 = runtime_ref
+        .invoke("new_vec2", &[Value::F32(3.0), Value::F32(4.0)])
+        .unwrap();
+    assert!(matches!(&vec2, Value::Struct(s) if s.get::<f32>("x") == Ok(3.0)));
+    assert!(matches!(
+        runtime_ref.invoke("length_squared", &[vec2]),
+        Ok(Value::F32(length)) if length == 25.0
+    ));
+
+    // Arrays can be passed to and returned from functions
+    let multiples = runtime_ref.invoke("multiples", &[Value::I32(2)]).unwrap();
+    assert!(matches!(
+        &multiples,
+        Value::Array(a) if a.len() == 3 && matches!(a.get(1), Ok(Value::I32(4)))
+    ));
+    assert!(matches!(
+        runtime_ref.invoke("sum_three", &[multiples]),
+        Ok(Value::I32(12))
+    ));
+    assert!(matches!(
+        runtime_ref.invoke("greetings", &[]),
+        Ok(Value::Array(a)) if matches!(a.get(1), Ok(Value::String(s)) if s == "world")
+    ));
+
+    // The number of arguments is not limited
+    let args: Vec<Value> = (1..=17i64).map(Value::from).collect();
+    assert!(matches!(
+        runtime_ref.invoke("sum", &args),
+        Ok(Value::I64(153))
+    ));
+
+    // Arguments are checked against the signature of the function
+    assert_eq!(
+        runtime_ref.invoke("negate", &[]).unwrap_err(),
+        "Invalid number of arguments. Expected: 1. Found: 0."
+    );
+    assert_eq!(
+        runtime_ref.invoke("negate", &[Value::I32(5)]).unwrap_err(),
+        "Invalid argument type at index 0. Expected: core::i8. Found: core::i32."
+    );
+    assert_eq!(
+        runtime_ref.invoke("unknown", &[]).unwrap_err(),
+        "Failed to obtain function 'unknown'"
+    );
+}
@@ -4,7 +4,7 @@ macro_rules! assert_invoke_eq {
     ($ExpectedType:ty, $ExpectedResult:expr, $Driver:expr, $($Arg:tt)+) => {
         {
             let runtime = $Driver.runtime();
-            let runtime_ref = runtime.borrow();
+            let runtime_ref = runtime.read();
             let result: $ExpectedType = mun_runtime::invoke_fn!(runtime_ref, $($Arg)*).unwrap();
             assert_eq!(
                 result, $ExpectedResult, "{} == {:?}",
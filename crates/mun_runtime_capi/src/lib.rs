@@ -7,6 +7,7 @@
 pub mod error;
 pub mod gc;
 pub mod hub;
+pub mod value;
 
 #[cfg(test)]
 mod tests;
@@ -16,7 +17,7 @@ use std::{
     os::raw::c_char,
 };
 
-use crate::{error::ErrorHandle, hub::HUB};
+use crate::{error::ErrorHandle, hub::HUB, value::Value};
 use anyhow::anyhow;
 use runtime::Runtime;
 
@@ -130,6 +131,8 @@ pub unsafe extern "C" fn mun_runtime_create(
     let runtime_options = runtime::RuntimeOptions {
         library_path: library_path.into(),
         user_functions,
+        gc_strategy: Default::default(),
+        gc_policy: Default::default(),
     };
 
     let runtime = match Runtime::new(runtime_options) {
@@ -220,6 +223,93 @@ pub unsafe extern "C" fn mun_runtime_get_function_definition(
     ErrorHandle::default()
 }
 
+/// Invokes the function `fn_name` with the `num_args` values in `args`, in the runtime
+/// corresponding to `handle`. The arguments are checked against the signature of the function. If
+/// successful, `result` is set, otherwise a non-zero error handle is returned.
+///
+/// A returned string must be manually destructed using [`mun_destroy_string`]. A returned object
+/// is not rooted, so it must be rooted using [`gc::mun_gc_root`] before the next collection.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_invoke(
+    handle: RuntimeHandle,
+    fn_name: *const c_char,
+    args: *const Value,
+    num_args: usize,
+    result: *mut Value,
+) -> ErrorHandle {
+    let runtime = match (handle.0 as *mut Runtime).as_ref() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    if fn_name.is_null() {
+        return HUB
+            .errors
+            .register(anyhow!("Invalid argument: 'fn_name' is null pointer."));
+    }
+
+    let fn_name = match CStr::from_ptr(fn_name).to_str() {
+        Ok(name) => name,
+        Err(_) => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'fn_name' is not UTF-8 encoded."))
+        }
+    };
+
+    if args.is_null() && num_args > 0 {
+        return HUB
+            .errors
+            .register(anyhow!("Invalid argument: 'args' is null pointer."));
+    }
+
+    let result = match result.as_mut() {
+        Some(result) => result,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'result' is null pointer."))
+        }
+    };
+
+    let args = if num_args > 0 {
+        std::slice::from_raw_parts(args, num_args)
+    } else {
+        &[]
+    };
+    let args = match args
+        .iter()
+        .map(|arg| arg.into_runtime(runtime))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(args) => args,
+        Err(e) => return HUB.errors.register(e),
+    };
+
+    match runtime
+        .invoke(fn_name, &args)
+        .map_err(|e| anyhow!("{}", e))
+        .and_then(Value::from_runtime)
+    {
+        Ok(value) => *result = value,
+        Err(e) => return HUB.errors.register(e),
+    }
+
+    ErrorHandle::default()
+}
+
 /// Updates the runtime corresponding to `handle`. If successful, `updated` is set, otherwise a
 /// non-zero error handle is returned.
 ///
@@ -1,4 +1,4 @@
-use crate::{error::*, gc::*, *};
+use crate::{error::*, gc::*, value::*, *};
 use compiler::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
 use memory::gc::{GcPtr, HasIndirectionPtr, RawGcPtr};
 use runtime::UnsafeTypeInfo;
@@ -95,6 +95,7 @@ macro_rules! test_invalid_runtime {
 
 test_invalid_runtime!(
     runtime_get_function_definition(ptr::null(), ptr::null_mut(), ptr::null_mut()),
+    runtime_invoke(ptr::null(), ptr::null(), 0, ptr::null_mut()),
     runtime_update(ptr::null_mut()),
     gc_alloc(UnsafeTypeInfo::new(NonNull::dangling()), ptr::null_mut()),
     gc_ptr_type(mem::zeroed::<GcPtr>(), ptr::null_mut()),
@@ -294,6 +295,137 @@ fn test_runtime_get_function_info() {
     let _fn_definition = unsafe { fn_definition.assume_init() };
 }
 
+#[test]
+fn test_runtime_invoke_invalid_fn_name() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn main() -> i32 { 3 }
+    "#,
+    );
+
+    let mut result = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_invoke(
+            driver.runtime,
+            ptr::null(),
+            ptr::null(),
+            0,
+            result.as_mut_ptr(),
+        )
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'fn_name' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_invoke_invalid_result() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn main() -> i32 { 3 }
+    "#,
+    );
+
+    let fn_name = CString::new("main").expect("Invalid function name");
+    let handle = unsafe {
+        mun_runtime_invoke(
+            driver.runtime,
+            fn_name.as_ptr(),
+            ptr::null(),
+            0,
+            ptr::null_mut(),
+        )
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'result' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_invoke_invalid_arg_type() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+    "#,
+    );
+
+    let fn_name = CString::new("add").expect("Invalid function name");
+    let args = [
+        Value {
+            ty: ValueType::I32,
+            data: ValueData { i32: 3 },
+        },
+        Value {
+            ty: ValueType::F32,
+            data: ValueData { f32: 4.0 },
+        },
+    ];
+    let mut result = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_invoke(
+            driver.runtime,
+            fn_name.as_ptr(),
+            args.as_ptr(),
+            args.len(),
+            result.as_mut_ptr(),
+        )
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument type at index 1. Expected: core::i32. Found: core::f32."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_invoke() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+    "#,
+    );
+
+    let fn_name = CString::new("add").expect("Invalid function name");
+    let args = [
+        Value {
+            ty: ValueType::I32,
+            data: ValueData { i32: 3 },
+        },
+        Value {
+            ty: ValueType::I32,
+            data: ValueData { i32: 4 },
+        },
+    ];
+    let mut result = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_invoke(
+            driver.runtime,
+            fn_name.as_ptr(),
+            args.as_ptr(),
+            args.len(),
+            result.as_mut_ptr(),
+        )
+    };
+    assert_eq!(handle.token(), 0);
+
+    let result = unsafe { result.assume_init() };
+    assert_eq!(result.ty, ValueType::I32);
+    assert_eq!(unsafe { result.data.i32 }, 7);
+}
+
 #[test]
 fn test_runtime_update_invalid_updated() {
     let driver = TestDriver::new(
@@ -0,0 +1,168 @@
+//! Exposes dynamically typed values, used to invoke functions through [`mun_runtime_invoke`].
+//!
+//! [`mun_runtime_invoke`]: crate::mun_runtime_invoke
+
+use anyhow::anyhow;
+use memory::gc::GcPtr;
+use runtime::Runtime;
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+};
+
+/// The type of a [`Value`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueType {
+    /// The empty type `()`, returned by functions without a return type
+    Unit,
+    /// A `bool`, stored in [`ValueData::bool`]
+    Bool,
+    /// An `i8`, stored in [`ValueData::i8`]
+    I8,
+    /// An `i16`, stored in [`ValueData::i16`]
+    I16,
+    /// An `i32`, stored in [`ValueData::i32`]
+    I32,
+    /// An `i64`, stored in [`ValueData::i64`]
+    I64,
+    /// A `u8`, stored in [`ValueData::u8`]
+    U8,
+    /// A `u16`, stored in [`ValueData::u16`]
+    U16,
+    /// A `u32`, stored in [`ValueData::u32`]
+    U32,
+    /// A `u64`, stored in [`ValueData::u64`]
+    U64,
+    /// An `f32`, stored in [`ValueData::f32`]
+    F32,
+    /// An `f64`, stored in [`ValueData::f64`]
+    F64,
+    /// A UTF-8 encoded, null-terminated string, stored in [`ValueData::string`]
+    String,
+    /// A struct, enum, closure, or array, stored in [`ValueData::gc_ptr`]
+    Object,
+}
+
+/// The data of a [`Value`]. Which field is valid depends on the [`ValueType`].
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union ValueData {
+    /// The value of a [`ValueType::Bool`]
+    pub bool: bool,
+    /// The value of a [`ValueType::I8`]
+    pub i8: i8,
+    /// The value of a [`ValueType::I16`]
+    pub i16: i16,
+    /// The value of a [`ValueType::I32`]
+    pub i32: i32,
+    /// The value of a [`ValueType::I64`]
+    pub i64: i64,
+    /// The value of a [`ValueType::U8`]
+    pub u8: u8,
+    /// The value of a [`ValueType::U16`]
+    pub u16: u16,
+    /// The value of a [`ValueType::U32`]
+    pub u32: u32,
+    /// The value of a [`ValueType::U64`]
+    pub u64: u64,
+    /// The value of a [`ValueType::F32`]
+    pub f32: f32,
+    /// The value of a [`ValueType::F64`]
+    pub f64: f64,
+    /// The value of a [`ValueType::String`]
+    pub string: *const c_char,
+    /// The value of a [`ValueType::Object`]
+    pub gc_ptr: GcPtr,
+}
+
+/// A dynamically typed value that can be passed to and returned from a function.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Value {
+    /// The type of the value
+    pub ty: ValueType,
+    /// The data of the value
+    pub data: ValueData,
+}
+
+impl Value {
+    /// Converts the value into a runtime value.
+    ///
+    /// # Safety
+    ///
+    /// The field of `data` that corresponds to `ty` must be valid. Strings must be null-terminated
+    /// and objects must have been allocated by the garbage collector of `runtime`.
+    pub(crate) unsafe fn into_runtime<'r>(
+        self,
+        runtime: &'r Runtime,
+    ) -> Result<runtime::Value<'r>, anyhow::Error> {
+        let value = match self.ty {
+            ValueType::Unit => runtime::Value::Unit,
+            ValueType::Bool => runtime::Value::Bool(self.data.bool),
+            ValueType::I8 => runtime::Value::I8(self.data.i8),
+            ValueType::I16 => runtime::Value::I16(self.data.i16),
+            ValueType::I32 => runtime::Value::I32(self.data.i32),
+            ValueType::I64 => runtime::Value::I64(self.data.i64),
+            ValueType::U8 => runtime::Value::U8(self.data.u8),
+            ValueType::U16 => runtime::Value::U16(self.data.u16),
+            ValueType::U32 => runtime::Value::U32(self.data.u32),
+            ValueType::U64 => runtime::Value::U64(self.data.u64),
+            ValueType::F32 => runtime::Value::F32(self.data.f32),
+            ValueType::F64 => runtime::Value::F64(self.data.f64),
+            ValueType::String => {
+                if self.data.string.is_null() {
+                    return Err(anyhow!("Invalid argument: string is null pointer."));
+                }
+                let string = CStr::from_ptr(self.data.string)
+                    .to_str()
+                    .map_err(|_| anyhow!("Invalid argument: string is not UTF-8 encoded."))?;
+                runtime::Value::String(string.to_owned())
+            }
+            ValueType::Object => runtime::Value::from_gc_ptr(self.data.gc_ptr, runtime)
+                .ok_or_else(|| {
+                    anyhow!("Invalid argument: `gc_ptr` does not refer to an object.")
+                })?,
+        };
+        Ok(value)
+    }
+
+    /// Converts a runtime value into a `Value`. Strings are allocated and must be manually
+    /// destructed using [`mun_destroy_string`].
+    ///
+    /// [`mun_destroy_string`]: crate::mun_destroy_string
+    pub(crate) fn from_runtime(value: runtime::Value) -> Result<Value, anyhow::Error> {
+        let (ty, data) = match value {
+            runtime::Value::Unit => (ValueType::Unit, ValueData { u64: 0 }),
+            runtime::Value::Bool(value) => (ValueType::Bool, ValueData { bool: value }),
+            runtime::Value::I8(value) => (ValueType::I8, ValueData { i8: value }),
+            runtime::Value::I16(value) => (ValueType::I16, ValueData { i16: value }),
+            runtime::Value::I32(value) => (ValueType::I32, ValueData { i32: value }),
+            runtime::Value::I64(value) => (ValueType::I64, ValueData { i64: value }),
+            runtime::Value::U8(value) => (ValueType::U8, ValueData { u8: value }),
+            runtime::Value::U16(value) => (ValueType::U16, ValueData { u16: value }),
+            runtime::Value::U32(value) => (ValueType::U32, ValueData { u32: value }),
+            runtime::Value::U64(value) => (ValueType::U64, ValueData { u64: value }),
+            runtime::Value::F32(value) => (ValueType::F32, ValueData { f32: value }),
+            runtime::Value::F64(value) => (ValueType::F64, ValueData { f64: value }),
+            runtime::Value::String(value) => {
+                let string = CString::new(value)
+                    .map_err(|_| anyhow!("The returned string contains a null character."))?;
+                (
+                    ValueType::String,
+                    ValueData {
+                        string: string.into_raw(),
+                    },
+                )
+            }
+            value => (
+                ValueType::Object,
+                ValueData {
+                    // All other values reference an object
+                    gc_ptr: value.gc_ptr().unwrap(),
+                },
+            ),
+        };
+        Ok(Value { ty, data })
+    }
+}
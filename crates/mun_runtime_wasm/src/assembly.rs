@@ -0,0 +1,243 @@
+use std::fmt::{self, Formatter};
+use wasmi::MemoryRef;
+
+// The offsets of the fields of the ABI structs on the `wasm32` target, where pointers are 32 bits
+// wide. These mirror the `#[repr(C)]` structs in the `mun_abi` crate.
+const ASSEMBLY_INFO_DISPATCH_TABLE_OFFSET: u32 = 28;
+const ASSEMBLY_INFO_NUM_DEPENDENCIES_OFFSET: u32 = 44;
+const MODULE_INFO_FUNCTIONS_OFFSET: u32 = 4;
+const MODULE_INFO_NUM_FUNCTIONS_OFFSET: u32 = 16;
+const DISPATCH_TABLE_FN_PTRS_OFFSET: u32 = 4;
+const DISPATCH_TABLE_NUM_ENTRIES_OFFSET: u32 = 8;
+const FUNCTION_PROTOTYPE_ARG_TYPES_OFFSET: u32 = 4;
+const FUNCTION_PROTOTYPE_RETURN_TYPE_OFFSET: u32 = 8;
+const FUNCTION_PROTOTYPE_NUM_ARG_TYPES_OFFSET: u32 = 12;
+const FUNCTION_PROTOTYPE_SIZE: u32 = 16;
+const FUNCTION_DEFINITION_FN_PTR_OFFSET: u32 = 16;
+const FUNCTION_DEFINITION_SIZE: u32 = 20;
+const TYPE_INFO_NAME_OFFSET: u32 = 16;
+const TYPE_INFO_SIZE_IN_BITS_OFFSET: u32 = 20;
+const TYPE_INFO_ALIGNMENT_OFFSET: u32 = 24;
+const TYPE_INFO_DATA_OFFSET: u32 = 28;
+const TYPE_INFO_DATA_PAYLOAD_OFFSET: u32 = 32;
+const STRUCT_INFO_MEMORY_KIND_OFFSET: u32 = 14;
+pub(crate) const POINTER_SIZE: u32 = 4;
+
+/// The name and type signature of a function in a wasm assembly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionPrototype {
+    /// Function name
+    pub name: String,
+    /// The names of the argument types
+    pub arg_types: Vec<String>,
+    /// The name of the return type, if any
+    pub return_type: Option<String>,
+}
+
+impl fmt::Display for FunctionPrototype {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "fn {}({})", self.name, self.arg_types.join(", "))?;
+        if let Some(ret_type) = &self.return_type {
+            write!(f, ":{}", ret_type)?
+        }
+        Ok(())
+    }
+}
+
+/// A function that is defined by a wasm assembly.
+#[derive(Clone, Debug)]
+pub struct FunctionDefinition {
+    /// Function prototype
+    pub prototype: FunctionPrototype,
+    /// The index of the function in the indirect function table of the module. This is the value
+    /// of a function pointer in wasm.
+    pub(crate) fn_index: u32,
+}
+
+/// The function dispatch table of a wasm assembly, which has to be filled by the runtime.
+pub(crate) struct DispatchTable {
+    /// The functions that are called through the dispatch table
+    pub prototypes: Vec<FunctionPrototype>,
+    /// The address of the function pointers in linear memory
+    pub fn_ptrs: u32,
+}
+
+impl DispatchTable {
+    /// Returns the address of the function pointer of the entry at `index`.
+    pub fn fn_ptr_address(&self, index: usize) -> u32 {
+        self.fn_ptrs + index as u32 * POINTER_SIZE
+    }
+}
+
+/// The type information of a wasm assembly, as far as the runtime needs it to allocate objects.
+pub(crate) struct TypeInfo {
+    /// Type name
+    pub name: String,
+    /// The size of the type in bytes
+    pub size: u32,
+    /// The alignment of the type in bytes
+    pub alignment: u32,
+    /// Type group
+    pub data: TypeInfoData,
+}
+
+/// Contains data specific to a group of types.
+pub(crate) enum TypeInfoData {
+    Primitive,
+    Struct {
+        /// Whether the struct is garbage collected, rather than a value struct
+        is_gc: bool,
+    },
+    Array {
+        /// The address of the `MunTypeInfo` of the elements of the array
+        element_type: u32,
+    },
+    Enum,
+}
+
+impl TypeInfo {
+    /// Decodes the `MunTypeInfo` struct at the address `ptr` of the specified linear memory.
+    pub fn read(memory: &MemoryRef, ptr: u32) -> anyhow::Result<Self> {
+        let reader = Reader(memory);
+
+        let payload_ptr = ptr + TYPE_INFO_DATA_PAYLOAD_OFFSET;
+        let data = match reader.u8(ptr + TYPE_INFO_DATA_OFFSET)? {
+            0 => TypeInfoData::Primitive,
+            1 => TypeInfoData::Struct {
+                // `StructMemoryKind::Gc` is the first variant
+                is_gc: reader.u8(payload_ptr + STRUCT_INFO_MEMORY_KIND_OFFSET)? == 0,
+            },
+            2 => TypeInfoData::Array {
+                element_type: reader.u32(payload_ptr)?,
+            },
+            3 => TypeInfoData::Enum,
+            tag => return Err(anyhow::anyhow!("invalid type group `{}`", tag)),
+        };
+
+        Ok(TypeInfo {
+            name: reader
+                .type_name(ptr)?
+                .ok_or_else(|| anyhow::anyhow!("type cannot be null"))?,
+            size: (reader.u32(ptr + TYPE_INFO_SIZE_IN_BITS_OFFSET)? + 7) / 8,
+            alignment: u32::from(reader.u8(ptr + TYPE_INFO_ALIGNMENT_OFFSET)?),
+            data,
+        })
+    }
+
+    /// Returns whether values of this type are references to garbage collected objects, which
+    /// take up the size of a pointer when they are stored in another object.
+    pub fn is_gc_reference(&self) -> bool {
+        match self.data {
+            TypeInfoData::Struct { is_gc } => is_gc,
+            TypeInfoData::Array { .. } => true,
+            TypeInfoData::Primitive => self.name == "core::string",
+            TypeInfoData::Enum => false,
+        }
+    }
+}
+
+/// The information of a wasm assembly, as returned by its `get_info` function.
+pub(crate) struct AssemblyInfo {
+    /// The functions defined in the assembly
+    pub functions: Vec<FunctionDefinition>,
+    /// The dispatch table of the assembly
+    pub dispatch_table: DispatchTable,
+    /// The number of assemblies that this assembly depends on
+    pub num_dependencies: u32,
+}
+
+impl AssemblyInfo {
+    /// Decodes the `MunAssemblyInfo` struct at the address `ptr` of the specified linear memory.
+    pub fn read(memory: &MemoryRef, ptr: u32) -> anyhow::Result<Self> {
+        let reader = Reader(memory);
+
+        let functions_ptr = reader.u32(ptr + MODULE_INFO_FUNCTIONS_OFFSET)?;
+        let num_functions = reader.u32(ptr + MODULE_INFO_NUM_FUNCTIONS_OFFSET)?;
+        let functions = (0..num_functions)
+            .map(|idx| {
+                let fn_def_ptr = functions_ptr + idx * FUNCTION_DEFINITION_SIZE;
+                Ok(FunctionDefinition {
+                    prototype: reader.prototype(fn_def_ptr)?,
+                    fn_index: reader.u32(fn_def_ptr + FUNCTION_DEFINITION_FN_PTR_OFFSET)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let dispatch_table_ptr = ptr + ASSEMBLY_INFO_DISPATCH_TABLE_OFFSET;
+        let prototypes_ptr = reader.u32(dispatch_table_ptr)?;
+        let num_entries = reader.u32(dispatch_table_ptr + DISPATCH_TABLE_NUM_ENTRIES_OFFSET)?;
+        let prototypes = (0..num_entries)
+            .map(|idx| reader.prototype(prototypes_ptr + idx * FUNCTION_PROTOTYPE_SIZE))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(AssemblyInfo {
+            functions,
+            dispatch_table: DispatchTable {
+                prototypes,
+                fn_ptrs: reader.u32(dispatch_table_ptr + DISPATCH_TABLE_FN_PTRS_OFFSET)?,
+            },
+            num_dependencies: reader.u32(ptr + ASSEMBLY_INFO_NUM_DEPENDENCIES_OFFSET)?,
+        })
+    }
+}
+
+/// Reads ABI values from the linear memory of a wasm module.
+struct Reader<'a>(&'a MemoryRef);
+
+impl Reader<'_> {
+    fn u8(&self, ptr: u32) -> anyhow::Result<u8> {
+        let mut bytes = [0u8; 1];
+        self.0.get_into(ptr, &mut bytes)?;
+        Ok(bytes[0])
+    }
+
+    fn u16(&self, ptr: u32) -> anyhow::Result<u16> {
+        let mut bytes = [0u8; 2];
+        self.0.get_into(ptr, &mut bytes)?;
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    fn u32(&self, ptr: u32) -> anyhow::Result<u32> {
+        Ok(self.0.get_value::<u32>(ptr)?)
+    }
+
+    /// Reads the null-terminated string at `ptr`.
+    fn str(&self, ptr: u32) -> anyhow::Result<String> {
+        let bytes = self.0.with_direct_access(|memory| {
+            memory
+                .get(ptr as usize..)
+                .and_then(|bytes| bytes.iter().position(|&b| b == 0).map(|len| &bytes[..len]))
+                .map(<[u8]>::to_vec)
+        });
+        let bytes = bytes.ok_or_else(|| anyhow::anyhow!("invalid string at address {:#x}", ptr))?;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// Reads the name of the `MunTypeInfo` at `ptr`, or `None` if `ptr` is null.
+    fn type_name(&self, ptr: u32) -> anyhow::Result<Option<String>> {
+        if ptr == 0 {
+            return Ok(None);
+        }
+        let name_ptr = self.u32(ptr + TYPE_INFO_NAME_OFFSET)?;
+        self.str(name_ptr).map(Some)
+    }
+
+    /// Reads the `MunFunctionPrototype` at `ptr`.
+    fn prototype(&self, ptr: u32) -> anyhow::Result<FunctionPrototype> {
+        let arg_types_ptr = self.u32(ptr + FUNCTION_PROTOTYPE_ARG_TYPES_OFFSET)?;
+        let num_arg_types = self.u16(ptr + FUNCTION_PROTOTYPE_NUM_ARG_TYPES_OFFSET)?;
+        let arg_types = (0..num_arg_types as u32)
+            .map(|idx| {
+                let type_ptr = self.u32(arg_types_ptr + idx * POINTER_SIZE)?;
+                self.type_name(type_ptr)?
+                    .ok_or_else(|| anyhow::anyhow!("argument type cannot be null"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(FunctionPrototype {
+            name: self.str(self.u32(ptr)?)?,
+            arg_types,
+            return_type: self.type_name(self.u32(ptr + FUNCTION_PROTOTYPE_RETURN_TYPE_OFFSET)?)?,
+        })
+    }
+}
@@ -0,0 +1,280 @@
+use crate::assembly::{TypeInfo, TypeInfoData, POINTER_SIZE};
+use std::fmt;
+use wasmi::{
+    memory_units::{Bytes, Pages, RoundUpTo},
+    Externals, FuncInstance, FuncRef, HostError, MemoryRef, RuntimeArgs, RuntimeValue, Signature,
+    Trap, TrapKind, ValueType,
+};
+
+/// The intrinsics that the runtime provides to wasm assemblies as host functions. Their
+/// signatures mirror those of the intrinsics in `mun_codegen` on the `wasm32` target, where
+/// pointers and `usize`s are passed as `i32`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Intrinsic {
+    New,
+    NewString,
+    NewArray,
+    PanicIndexOutOfBounds,
+    WriteBarrier,
+}
+
+impl Intrinsic {
+    /// All intrinsics, ordered by their host function index
+    pub const ALL: [Intrinsic; 5] = [
+        Intrinsic::New,
+        Intrinsic::NewString,
+        Intrinsic::NewArray,
+        Intrinsic::PanicIndexOutOfBounds,
+        Intrinsic::WriteBarrier,
+    ];
+
+    /// Returns the intrinsic with the specified name, if it exists.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|intrinsic| intrinsic.name() == name)
+    }
+
+    /// Returns the name of the intrinsic, as it appears in dispatch tables.
+    pub fn name(self) -> &'static str {
+        match self {
+            Intrinsic::New => "new",
+            Intrinsic::NewString => "new_string",
+            Intrinsic::NewArray => "new_array",
+            Intrinsic::PanicIndexOutOfBounds => "panic_index_out_of_bounds",
+            Intrinsic::WriteBarrier => "write_barrier",
+        }
+    }
+
+    /// Returns the wasm signature of the intrinsic.
+    pub fn signature(self) -> Signature {
+        use ValueType::I32;
+        match self {
+            Intrinsic::New => Signature::new(&[I32, I32][..], Some(I32)),
+            Intrinsic::NewString | Intrinsic::NewArray => {
+                Signature::new(&[I32, I32, I32][..], Some(I32))
+            }
+            Intrinsic::PanicIndexOutOfBounds | Intrinsic::WriteBarrier => {
+                Signature::new(&[I32, I32][..], None)
+            }
+        }
+    }
+
+    /// Allocates a host function that invokes the intrinsic, which can be stored in the indirect
+    /// function table of a wasm module.
+    pub fn func(self) -> FuncRef {
+        FuncInstance::alloc_host(self.signature(), self as usize)
+    }
+}
+
+/// An error that is raised by an intrinsic, which aborts the invocation of a wasm function.
+#[derive(Debug)]
+pub(crate) struct IntrinsicError(String);
+
+impl fmt::Display for IntrinsicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl HostError for IntrinsicError {}
+
+fn trap<S: Into<String>>(message: S) -> Trap {
+    Trap::new(TrapKind::Host(Box::new(IntrinsicError(message.into()))))
+}
+
+/// Allocates the objects of a wasm assembly in its linear memory, behind the memory that the
+/// assembly occupies. Objects are laid out like they are by the garbage collector of the native
+/// runtime: a handle is the address of a slot that contains the address of the object's memory.
+/// Objects are never freed; their memory is reclaimed when the runtime is dropped.
+pub(crate) struct Arena {
+    memory: MemoryRef,
+    /// The address at which the arena starts, which also serves as the allocator handle of the
+    /// assembly
+    start: u32,
+    /// The address of the first byte that has not been allocated
+    next: u64,
+}
+
+impl Arena {
+    /// Constructs an arena that allocates memory behind the current end of `memory`.
+    pub fn new(memory: MemoryRef) -> Self {
+        let Bytes(end) = memory.current_size().into();
+        Arena {
+            memory,
+            start: end as u32,
+            next: end as u64,
+        }
+    }
+
+    /// Returns the allocator handle that the assembly passes to the allocating intrinsics.
+    pub fn handle(&self) -> u32 {
+        self.start
+    }
+
+    fn check_handle(&self, alloc_handle: u32) -> Result<(), Trap> {
+        if alloc_handle == self.start {
+            Ok(())
+        } else {
+            Err(trap(format!(
+                "invalid allocator handle {:#x}",
+                alloc_handle
+            )))
+        }
+    }
+
+    fn type_info(&self, ptr: u32) -> Result<TypeInfo, Trap> {
+        TypeInfo::read(&self.memory, ptr).map_err(|e| trap(e.to_string()))
+    }
+
+    /// Allocates zero-initialized memory of `size` bytes that is aligned to `alignment`, and
+    /// returns the addresses of its handle and of the memory itself.
+    fn alloc(&mut self, size: u64, alignment: u64) -> Result<(u32, u32), Trap> {
+        let out_of_memory = || trap("out of memory");
+
+        let handle = align_up(self.next, u64::from(POINTER_SIZE));
+        let object = align_up(handle + u64::from(POINTER_SIZE), alignment);
+        let end = object + size;
+        if end > u64::from(u32::MAX) {
+            return Err(out_of_memory());
+        }
+
+        // The arena starts at the end of the memory and only ever grows it, so all memory that it
+        // hands out is zero-initialized.
+        let Bytes(memory_end) = self.memory.current_size().into();
+        if end > memory_end as u64 {
+            let additional: Pages = Bytes((end - memory_end as u64) as usize).round_up_to();
+            self.memory.grow(additional).map_err(|_| out_of_memory())?;
+        }
+
+        let (handle, object) = (handle as u32, object as u32);
+        self.memory
+            .set_value(handle, object)
+            .map_err(|e| trap(e.to_string()))?;
+        self.next = end;
+        Ok((handle, object))
+    }
+
+    /// Allocates an array object that holds `length` elements with the specified layout, and
+    /// returns the addresses of its handle and of its first element.
+    fn alloc_array(
+        &mut self,
+        length: u32,
+        element_size: u32,
+        element_alignment: u32,
+    ) -> Result<(u32, u32), Trap> {
+        let element_alignment = u64::from(element_alignment.max(1));
+        let elements_offset = align_up(u64::from(POINTER_SIZE), element_alignment);
+        let stride = align_up(u64::from(element_size), element_alignment);
+        let alignment = element_alignment.max(u64::from(POINTER_SIZE));
+        let size = align_up(elements_offset + stride * u64::from(length), alignment);
+
+        let (handle, object) = self.alloc(size, alignment)?;
+        self.memory
+            .set_value(object, length)
+            .map_err(|e| trap(e.to_string()))?;
+        Ok((handle, object + elements_offset as u32))
+    }
+
+    /// Allocates an object of the struct at `type_ptr`.
+    fn new_object(&mut self, type_ptr: u32) -> Result<u32, Trap> {
+        let ty = self.type_info(type_ptr)?;
+        match ty.data {
+            TypeInfoData::Struct { .. } => self
+                .alloc(u64::from(ty.size), u64::from(ty.alignment.max(1)))
+                .map(|(handle, _)| handle),
+            _ => Err(trap(format!(
+                "cannot allocate an object of type `{}`",
+                ty.name
+            ))),
+        }
+    }
+
+    /// Allocates a string that is initialized with the `length` bytes at `bytes`.
+    fn new_string(&mut self, bytes: u32, length: u32) -> Result<u32, Trap> {
+        let (handle, elements) = self.alloc_array(length, 1, 1)?;
+        self.memory
+            .copy(bytes as usize, elements as usize, length as usize)
+            .map_err(|e| trap(e.to_string()))?;
+        Ok(handle)
+    }
+
+    /// Allocates an array of the array type at `type_ptr` that can hold `length` elements.
+    fn new_array(&mut self, type_ptr: u32, length: u32) -> Result<u32, Trap> {
+        let ty = self.type_info(type_ptr)?;
+        let element_type = match ty.data {
+            TypeInfoData::Array { element_type } => self.type_info(element_type)?,
+            _ => {
+                return Err(trap(format!(
+                    "cannot allocate an array of type `{}`",
+                    ty.name
+                )))
+            }
+        };
+
+        let (size, alignment) = if element_type.is_gc_reference() {
+            (POINTER_SIZE, POINTER_SIZE)
+        } else {
+            (element_type.size, element_type.alignment)
+        };
+        self.alloc_array(length, size, alignment)
+            .map(|(handle, _)| handle)
+    }
+}
+
+impl Externals for Arena {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        let intrinsic = Intrinsic::ALL
+            .get(index)
+            .copied()
+            .ok_or_else(|| trap(format!("unknown host function `{}`", index)))?;
+
+        let handle = match intrinsic {
+            Intrinsic::New => {
+                self.check_handle(args.nth_checked(1)?)?;
+                self.new_object(args.nth_checked(0)?)?
+            }
+            Intrinsic::NewString => {
+                self.check_handle(args.nth_checked(2)?)?;
+                self.new_string(args.nth_checked(0)?, args.nth_checked(1)?)?
+            }
+            Intrinsic::NewArray => {
+                self.check_handle(args.nth_checked(2)?)?;
+                self.new_array(args.nth_checked(0)?, args.nth_checked(1)?)?
+            }
+            Intrinsic::PanicIndexOutOfBounds => {
+                let index: u32 = args.nth_checked(0)?;
+                let length: u32 = args.nth_checked(1)?;
+                return Err(trap(format!(
+                    "index out of bounds: the len is {} but the index is {}",
+                    length, index
+                )));
+            }
+            Intrinsic::WriteBarrier => {
+                // The arena never frees objects, so it does not need to know about references
+                // between them.
+                self.check_handle(args.nth_checked(1)?)?;
+                return Ok(None);
+            }
+        };
+        Ok(Some(RuntimeValue::I32(handle as i32)))
+    }
+}
+
+/// Returns the error message of an intrinsic that caused `trap`, or a description of the trap.
+pub(crate) fn trap_message(trap: &Trap) -> String {
+    match trap.kind() {
+        TrapKind::Host(err) => err.to_string(),
+        _ => trap.to_string(),
+    }
+}
+
+/// Rounds `value` up to a multiple of `alignment`, which must be a power of two.
+fn align_up(value: u64, alignment: u64) -> u64 {
+    (value + alignment - 1) & !(alignment - 1)
+}
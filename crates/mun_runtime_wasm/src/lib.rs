@@ -0,0 +1,266 @@
+//! The Mun WebAssembly Runtime
+//!
+//! The Mun WebAssembly Runtime loads Mun assemblies that were compiled for the
+//! `wasm32-unknown-unknown` target and invokes their functions in a sandboxed interpreter. An
+//! assembly can only access its own linear memory. The only host functions it can call are the
+//! intrinsics that allocate objects, which are placed in an arena behind the memory of the
+//! assembly. Objects are never freed; their memory is reclaimed when the runtime is dropped.
+#![warn(missing_docs)]
+
+mod assembly;
+mod intrinsics;
+
+use assembly::AssemblyInfo;
+use intrinsics::{Arena, Intrinsic};
+use std::{cell::RefCell, collections::HashMap, path::Path};
+use wasmi::{
+    memory_units::{Bytes, Pages},
+    ExternVal, FuncInstance, ImportsBuilder, MemoryRef, Module, ModuleImportResolver,
+    ModuleInstance, ModuleRef, NopExternals, TableDescriptor, TableInstance, TableRef,
+};
+
+pub use crate::assembly::{FunctionDefinition, FunctionPrototype};
+pub use wasmi::RuntimeValue;
+
+/// The name of the linear memory exported by a wasm assembly
+const MEMORY_EXPORT_NAME: &str = "memory";
+
+/// The module from which a wasm assembly imports its indirect function table
+const TABLE_IMPORT_MODULE_NAME: &str = "env";
+
+/// The name of the indirect function table imported by a wasm assembly
+const TABLE_IMPORT_NAME: &str = "__indirect_function_table";
+
+/// A runtime for a single Mun assembly that was compiled to a WebAssembly module.
+pub struct Runtime {
+    // Functions only hold a weak reference to their module, so the instance has to be kept alive
+    instance: ModuleRef,
+    table: TableRef,
+    functions: HashMap<String, FunctionDefinition>,
+    arena: RefCell<Arena>,
+}
+
+impl Runtime {
+    /// Loads the wasm assembly at `assembly_path`.
+    pub fn from_file<P: AsRef<Path>>(assembly_path: P) -> anyhow::Result<Self> {
+        let assembly_path = assembly_path.as_ref();
+        let bytes = std::fs::read(assembly_path).map_err(|e| {
+            anyhow::anyhow!(
+                "could not read assembly '{}': {}",
+                assembly_path.display(),
+                e
+            )
+        })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Loads a wasm assembly from its binary representation. The assembly is instantiated with an
+    /// indirect function table that is provided by the runtime, which also contains the
+    /// intrinsics. Its dispatch table is linked against the functions it defines and the intrinsics.
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let module = Module::from_buffer(bytes)?;
+        let table_resolver = TableResolver::default();
+        let imports =
+            ImportsBuilder::new().with_resolver(TABLE_IMPORT_MODULE_NAME, &table_resolver);
+        let instance = ModuleInstance::new(&module, &imports)?.run_start(&mut NopExternals)?;
+
+        let version = instance
+            .invoke_export(abi::GET_VERSION_FN_NAME, &[], &mut NopExternals)?
+            .and_then(|version| version.try_into::<i32>())
+            .ok_or_else(|| anyhow::anyhow!("invalid `{}` function", abi::GET_VERSION_FN_NAME))?
+            as u32;
+        if abi::ABI_VERSION != version {
+            return Err(anyhow::anyhow!(
+                "ABI version mismatch. munlib is `{}` but runtime is `{}`",
+                version,
+                abi::ABI_VERSION
+            ));
+        }
+
+        let memory = match instance.export_by_name(MEMORY_EXPORT_NAME) {
+            Some(ExternVal::Memory(memory)) => memory,
+            _ => return Err(anyhow::anyhow!("assembly does not export its memory")),
+        };
+        let (table, intrinsics_index) = table_resolver
+            .0
+            .into_inner()
+            .ok_or_else(|| anyhow::anyhow!("assembly does not import its function table"))?;
+
+        let info = get_info(&instance, &memory)?;
+        if info.num_dependencies > 0 {
+            return Err(anyhow::anyhow!(
+                "assemblies with dependencies are not supported"
+            ));
+        }
+
+        let functions = info
+            .functions
+            .into_iter()
+            .map(|fn_def| (fn_def.prototype.name.clone(), fn_def))
+            .collect::<HashMap<_, _>>();
+
+        // Link the dispatch table by storing the table indices of the called functions
+        for (idx, fn_prototype) in info.dispatch_table.prototypes.iter().enumerate() {
+            if let Some(intrinsic) = Intrinsic::from_name(&fn_prototype.name) {
+                if fn_prototype.arg_types.len() != intrinsic.signature().params().len() {
+                    return Err(anyhow::anyhow!(
+                        "Failed to link: intrinsic `{}` has an invalid signature: {}.",
+                        fn_prototype.name,
+                        fn_prototype
+                    ));
+                }
+                memory.set_value(
+                    info.dispatch_table.fn_ptr_address(idx),
+                    intrinsics_index + intrinsic as u32,
+                )?;
+                continue;
+            }
+
+            match functions.get(&fn_prototype.name) {
+                Some(fn_def) if fn_def.prototype == *fn_prototype => memory.set_value(
+                    info.dispatch_table.fn_ptr_address(idx),
+                    fn_def.fn_index,
+                )?,
+                Some(fn_def) => return Err(anyhow::anyhow!("Failed to link: function '{}' is missing. A function with the same name does exist, but the signatures do not match (expected: {}, found: {}).", fn_prototype.name, fn_prototype, fn_def.prototype)),
+                None => {
+                    return Err(anyhow::anyhow!(
+                        "Failed to link: function `{}` is missing.",
+                        fn_prototype.name
+                    ))
+                }
+            }
+        }
+
+        // Objects are allocated behind all memory that is in use, including the assembly info
+        let arena = Arena::new(memory);
+        instance.invoke_export(
+            abi::SET_ALLOCATOR_HANDLE_FN_NAME,
+            &[RuntimeValue::I32(arena.handle() as i32)],
+            &mut NopExternals,
+        )?;
+
+        Ok(Runtime {
+            instance,
+            table,
+            functions,
+            arena: RefCell::new(arena),
+        })
+    }
+
+    /// Returns the definition of the function with the specified `fn_name`, if it exists.
+    pub fn get_function_definition(&self, fn_name: &str) -> Option<&FunctionDefinition> {
+        self.functions.get(fn_name)
+    }
+
+    /// Returns an iterator over the functions that are defined by the assembly.
+    pub fn functions(&self) -> impl Iterator<Item = &FunctionDefinition> {
+        self.functions.values()
+    }
+
+    /// Invokes the function with the specified `fn_name`. Only functions of which all argument
+    /// types and the return type are primitives can be invoked. Integers of up to 32 bits, and
+    /// `bool`s, are passed as `RuntimeValue::I32`.
+    pub fn invoke(
+        &self,
+        fn_name: &str,
+        args: &[RuntimeValue],
+    ) -> anyhow::Result<Option<RuntimeValue>> {
+        let fn_def = self
+            .get_function_definition(fn_name)
+            .ok_or_else(|| anyhow::anyhow!("failed to obtain function '{}'", fn_name))?;
+
+        let prototype = &fn_def.prototype;
+        if let Some(ty) = prototype
+            .arg_types
+            .iter()
+            .chain(prototype.return_type.iter())
+            .find(|ty| !is_primitive(ty))
+        {
+            return Err(anyhow::anyhow!(
+                "cannot invoke `{}`: type `{}` is not supported",
+                prototype,
+                ty
+            ));
+        }
+
+        let func = self
+            .table
+            .get(fn_def.fn_index)?
+            .ok_or_else(|| anyhow::anyhow!("function '{}' is not in the table", fn_name))?;
+        FuncInstance::invoke(&func, args, &mut *self.arena.borrow_mut()).map_err(|trap| {
+            anyhow::anyhow!(
+                "failed to invoke `{}`: {}",
+                prototype,
+                intrinsics::trap_message(&trap)
+            )
+        })
+    }
+
+    /// Returns the instance of the wasm module
+    pub fn instance(&self) -> &ModuleRef {
+        &self.instance
+    }
+}
+
+/// Provides the indirect function table that a wasm assembly imports, together with the table
+/// index of the first intrinsic. The intrinsics are stored behind the elements of the assembly.
+#[derive(Default)]
+struct TableResolver(RefCell<Option<(TableRef, u32)>>);
+
+impl ModuleImportResolver for TableResolver {
+    fn resolve_table(
+        &self,
+        field_name: &str,
+        descriptor: &TableDescriptor,
+    ) -> Result<TableRef, wasmi::Error> {
+        if field_name != TABLE_IMPORT_NAME {
+            return Err(wasmi::Error::Instantiation(format!(
+                "Export {} not found",
+                field_name
+            )));
+        }
+
+        let intrinsics_index = descriptor.initial();
+        let table = TableInstance::alloc(intrinsics_index + Intrinsic::ALL.len() as u32, None)?;
+        for (idx, intrinsic) in Intrinsic::ALL.iter().enumerate() {
+            table.set(intrinsics_index + idx as u32, Some(intrinsic.func()))?;
+        }
+
+        *self.0.borrow_mut() = Some((table.clone(), intrinsics_index));
+        Ok(table)
+    }
+}
+
+/// Calls the `get_info` function of the wasm assembly, which writes its `MunAssemblyInfo` to
+/// memory that is provided by the caller. A new page is allocated for it, as the assembly does
+/// not manage its memory beyond its initial size.
+fn get_info(instance: &ModuleRef, memory: &MemoryRef) -> anyhow::Result<AssemblyInfo> {
+    let info_ptr: Bytes = memory.grow(Pages(1))?.into();
+    let info_ptr = info_ptr.0 as u32;
+
+    instance.invoke_export(
+        abi::GET_INFO_FN_NAME,
+        &[RuntimeValue::I32(info_ptr as i32)],
+        &mut NopExternals,
+    )?;
+    AssemblyInfo::read(memory, info_ptr)
+}
+
+/// Returns whether the Mun type with the specified name is a primitive that can be passed to and
+/// returned from wasm functions by value.
+fn is_primitive(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "core::bool"
+            | "core::i8"
+            | "core::i16"
+            | "core::i32"
+            | "core::i64"
+            | "core::u8"
+            | "core::u16"
+            | "core::u32"
+            | "core::u64"
+            | "core::f32"
+            | "core::f64"
+    )
+}
@@ -0,0 +1,364 @@
+use mun_runtime_wasm::{Runtime, RuntimeValue};
+
+/// Encodes a `u32` as a little-endian wat data string
+fn le(value: u32) -> String {
+    value
+        .to_le_bytes()
+        .iter()
+        .map(|b| format!("\\{:02x}", b))
+        .collect()
+}
+
+/// Constructs a wasm module that is laid out like an assembly emitted for the
+/// `wasm32-unknown-unknown` target. It defines the functions `add` and `call_add`, the latter of
+/// which calls the function `dispatch_fn_name` through the dispatch table.
+fn assembly(dispatch_fn_name: &str, abi_version: u32) -> Vec<u8> {
+    // Data layout:
+    // 1024: "add"
+    // 1028: "core::i32"
+    // 1040: "" (module path)
+    // 1042: `dispatch_fn_name`
+    // 1048: TypeInfo of `core::i32`
+    // 1088: argument types
+    // 1096: function definitions
+    // 1136: dispatch table prototypes
+    // 1152: dispatch table function pointers
+    // 1160: "call_add"
+    let type_info = format!("{}{}{}", "\\00".repeat(16), le(1028), le(32));
+    let arg_types = format!("{}{}", le(1048), le(1048));
+    let signature = format!("{}{}{}", le(1088), le(1048), le(2));
+    let functions = format!(
+        "{}{}{}{}{}{}",
+        le(1024),
+        signature,
+        le(1),
+        le(1160),
+        signature,
+        le(2)
+    );
+    let prototypes = format!("{}{}", le(1042), signature);
+
+    let wat = format!(
+        r#"
+        (module
+            (type $binary (func (param i32 i32) (result i32)))
+            (import "env" "__indirect_function_table" (table 3 funcref))
+            (memory (export "memory") 1)
+            (elem (i32.const 1) $add $call_add)
+            (data (i32.const 1024) "add\00core::i32\00\00\00\00\00{dispatch_fn_name}\00")
+            (data (i32.const 1048) "{type_info}")
+            (data (i32.const 1088) "{arg_types}{functions}{prototypes}")
+            (data (i32.const 1160) "call_add\00")
+
+            (func $add (type $binary)
+                (i32.add (local.get 0) (local.get 1)))
+            (func $call_add (type $binary)
+                (call_indirect (type $binary) (local.get 0) (local.get 1) (i32.load (i32.const 1152))))
+
+            (func (export "get_version") (result i32)
+                (i32.const {abi_version}))
+            (func (export "set_allocator_handle") (param i32))
+            (func (export "get_info") (param $result i32)
+                ;; symbols.path
+                (i32.store offset=0 (local.get $result) (i32.const 1040))
+                ;; symbols.functions
+                (i32.store offset=4 (local.get $result) (i32.const 1096))
+                ;; symbols.num_functions
+                (i32.store offset=16 (local.get $result) (i32.const 2))
+                ;; dispatch_table.prototypes
+                (i32.store offset=28 (local.get $result) (i32.const 1136))
+                ;; dispatch_table.fn_ptrs
+                (i32.store offset=32 (local.get $result) (i32.const 1152))
+                ;; dispatch_table.num_entries
+                (i32.store offset=36 (local.get $result) (i32.const 1)))
+        )
+        "#,
+        dispatch_fn_name = dispatch_fn_name,
+        type_info = type_info,
+        arg_types = arg_types,
+        functions = functions,
+        prototypes = prototypes,
+        abi_version = abi_version,
+    );
+    wat::parse_str(wat).unwrap()
+}
+
+/// Lays out the data segment of a wasm module, which starts at address `Data::BASE`.
+#[derive(Default)]
+struct Data(Vec<u8>);
+
+impl Data {
+    const BASE: u32 = 1024;
+
+    /// Appends `bytes` at the next address that is aligned to `alignment`, and returns it.
+    fn push(&mut self, bytes: &[u8], alignment: usize) -> u32 {
+        while self.0.len() % alignment != 0 {
+            self.0.push(0);
+        }
+        let address = Self::BASE + self.0.len() as u32;
+        self.0.extend_from_slice(bytes);
+        address
+    }
+
+    fn str(&mut self, value: &str) -> u32 {
+        let address = self.push(value.as_bytes(), 1);
+        self.push(&[0], 1);
+        address
+    }
+
+    fn u32s(&mut self, values: &[u32]) -> u32 {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        self.push(&bytes, 4)
+    }
+
+    /// Appends a `MunTypeInfo` of the type group `tag`, which is followed by `payload`.
+    fn type_info(
+        &mut self,
+        name: &str,
+        size_in_bits: u32,
+        alignment: u8,
+        tag: u8,
+        payload: &[u8],
+    ) -> u32 {
+        let name = self.str(name);
+        let mut bytes = vec![0; 16];
+        bytes.extend_from_slice(&name.to_le_bytes());
+        bytes.extend_from_slice(&size_in_bits.to_le_bytes());
+        bytes.extend_from_slice(&[alignment, 0, 0, 0, tag, 0, 0, 0]);
+        bytes.extend_from_slice(payload);
+        self.push(&bytes, 4)
+    }
+
+    /// Appends the name and argument types of a function, and returns its `MunFunctionPrototype`.
+    fn prototype(&mut self, name: &str, arg_types: &[u32], return_type: u32) -> Vec<u8> {
+        let name = self.str(name);
+        let arg_types_ptr = self.u32s(arg_types);
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&name.to_le_bytes());
+        bytes.extend_from_slice(&arg_types_ptr.to_le_bytes());
+        bytes.extend_from_slice(&return_type.to_le_bytes());
+        bytes.extend_from_slice(&(arg_types.len() as u32).to_le_bytes());
+        bytes
+    }
+
+    fn wat(&self) -> String {
+        let bytes: String = self.0.iter().map(|b| format!("\\{:02x}", b)).collect();
+        format!("(data (i32.const {}) \"{}\")", Self::BASE, bytes)
+    }
+}
+
+#[test]
+fn invoke() {
+    let runtime = Runtime::from_bytes(&assembly("add", abi::ABI_VERSION)).unwrap();
+
+    let add = runtime.get_function_definition("add").unwrap();
+    assert_eq!(
+        add.prototype.to_string(),
+        "fn add(core::i32, core::i32):core::i32"
+    );
+    assert_eq!(runtime.functions().count(), 2);
+
+    let args = [RuntimeValue::I32(3), RuntimeValue::I32(4)];
+    assert_eq!(
+        runtime.invoke("add", &args).unwrap(),
+        Some(RuntimeValue::I32(7))
+    );
+    assert_eq!(
+        runtime.invoke("call_add", &args).unwrap(),
+        Some(RuntimeValue::I32(7))
+    );
+
+    assert!(runtime
+        .invoke("add", &[RuntimeValue::I64(3), RuntimeValue::I64(4)])
+        .is_err());
+    assert_eq!(
+        runtime.invoke("sub", &args).unwrap_err().to_string(),
+        "failed to obtain function 'sub'"
+    );
+}
+
+#[test]
+fn link_missing_function() {
+    let err = Runtime::from_bytes(&assembly("sub", abi::ABI_VERSION))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Failed to link: function `sub` is missing."
+    );
+}
+
+#[test]
+fn abi_version_mismatch() {
+    let err = Runtime::from_bytes(&assembly("add", abi::ABI_VERSION + 1))
+        .err()
+        .unwrap();
+    assert!(err.to_string().starts_with("ABI version mismatch"));
+}
+
+/// Constructs a wasm assembly that allocates objects through the intrinsics in its dispatch table.
+fn allocating_assembly() -> Vec<u8> {
+    let mut data = Data::default();
+    let path = data.str("");
+    let hello = data.str("hello");
+    let i32_type = data.type_info("core::i32", 32, 4, 0, &[]);
+    let i64_type = data.type_info("core::i64", 64, 8, 0, &[]);
+    // A garbage collected struct with two `core::i32` fields
+    let pair_type = data.type_info("Pair", 64, 4, 1, &[0; 16]);
+    let array_type = data.type_info("[core::i64]", 32, 4, 2, &i64_type.to_le_bytes());
+
+    let mut functions = Vec::new();
+    for (fn_index, (name, arg_types, return_type)) in [
+        ("string_len", &[][..], i32_type),
+        ("array_sum", &[][..], i64_type),
+        ("struct_sum", &[][..], i32_type),
+        ("index", &[i32_type][..], i32_type),
+    ]
+    .iter()
+    .enumerate()
+    {
+        functions.extend(data.prototype(name, arg_types, *return_type));
+        functions.extend_from_slice(&(fn_index as u32 + 1).to_le_bytes());
+    }
+    let functions = data.push(&functions, 4);
+
+    let mut prototypes = Vec::new();
+    for (name, num_args) in [
+        ("new_string", 3),
+        ("new_array", 3),
+        ("new", 2),
+        ("panic_index_out_of_bounds", 2),
+    ]
+    .iter()
+    {
+        prototypes.extend(data.prototype(name, &vec![i32_type; *num_args], 0));
+    }
+    let prototypes = data.push(&prototypes, 4);
+    let fn_ptrs = data.u32s(&[0; 4]);
+    let (new_string, new_array, new, panic) = (fn_ptrs, fn_ptrs + 4, fn_ptrs + 8, fn_ptrs + 12);
+
+    let wat = format!(
+        r#"
+        (module
+            (type $i32 (func (result i32)))
+            (type $i64 (func (result i64)))
+            (type $unary (func (param i32) (result i32)))
+            (type $new (func (param i32 i32) (result i32)))
+            (type $new_with_length (func (param i32 i32 i32) (result i32)))
+            (type $panic (func (param i32 i32)))
+            (import "env" "__indirect_function_table" (table 5 funcref))
+            (memory (export "memory") 1)
+            (global $allocator_handle (mut i32) (i32.const 0))
+            (elem (i32.const 1) $string_len $array_sum $struct_sum $index)
+            {data}
+
+            (func $string_len (type $i32) (local $object i32)
+                (local.set $object (i32.load (call_indirect (type $new_with_length)
+                    (i32.const {hello}) (i32.const 5) (global.get $allocator_handle)
+                    (i32.load (i32.const {new_string})))))
+                ;; length * 1000 + the first byte
+                (i32.add
+                    (i32.mul (i32.load (local.get $object)) (i32.const 1000))
+                    (i32.load8_u offset=4 (local.get $object))))
+            (func $array_sum (type $i64) (local $object i32)
+                (local.set $object (i32.load (call_indirect (type $new_with_length)
+                    (i32.const {array_type}) (i32.const 3) (global.get $allocator_handle)
+                    (i32.load (i32.const {new_array})))))
+                (i64.store offset=8 (local.get $object) (i64.const 1))
+                (i64.store offset=16 (local.get $object) (i64.const 2))
+                ;; the length, the (zero-initialized) elements and the misalignment of the object
+                (i64.add
+                    (i64.add
+                        (i64.extend_i32_u (i32.load (local.get $object)))
+                        (i64.extend_i32_u (i32.and (local.get $object) (i32.const 7))))
+                    (i64.add
+                        (i64.add
+                            (i64.load offset=8 (local.get $object))
+                            (i64.load offset=16 (local.get $object)))
+                        (i64.load offset=24 (local.get $object)))))
+            (func $struct_sum (type $i32) (local $first i32) (local $second i32)
+                (local.set $first (i32.load (call_indirect (type $new)
+                    (i32.const {pair_type}) (global.get $allocator_handle)
+                    (i32.load (i32.const {new})))))
+                (local.set $second (i32.load (call_indirect (type $new)
+                    (i32.const {pair_type}) (global.get $allocator_handle)
+                    (i32.load (i32.const {new})))))
+                (i32.store (local.get $first) (i32.const 3))
+                (i32.store offset=4 (local.get $first) (i32.const 4))
+                ;; the fields of both (non-overlapping, zero-initialized) objects
+                (i32.add
+                    (i32.add (i32.load (local.get $first)) (i32.load offset=4 (local.get $first)))
+                    (i32.add (i32.load (local.get $second)) (i32.load offset=4 (local.get $second)))))
+            (func $index (type $unary)
+                (if (i32.ge_u (local.get 0) (i32.const 2))
+                    (then (call_indirect (type $panic)
+                        (local.get 0) (i32.const 2) (i32.load (i32.const {panic})))))
+                (local.get 0))
+
+            (func (export "get_version") (result i32)
+                (i32.const {abi_version}))
+            (func (export "set_allocator_handle") (param i32)
+                (global.set $allocator_handle (local.get 0)))
+            (func (export "get_info") (param $result i32)
+                (i32.store offset=0 (local.get $result) (i32.const {path}))
+                (i32.store offset=4 (local.get $result) (i32.const {functions}))
+                (i32.store offset=16 (local.get $result) (i32.const 4))
+                (i32.store offset=28 (local.get $result) (i32.const {prototypes}))
+                (i32.store offset=32 (local.get $result) (i32.const {fn_ptrs}))
+                (i32.store offset=36 (local.get $result) (i32.const 4)))
+        )
+        "#,
+        data = data.wat(),
+        hello = hello,
+        array_type = array_type,
+        pair_type = pair_type,
+        new_string = new_string,
+        new_array = new_array,
+        new = new,
+        panic = panic,
+        abi_version = abi::ABI_VERSION,
+        path = path,
+        functions = functions,
+        prototypes = prototypes,
+        fn_ptrs = fn_ptrs,
+    );
+    wat::parse_str(wat).unwrap()
+}
+
+#[test]
+fn allocate() {
+    let runtime = Runtime::from_bytes(&allocating_assembly()).unwrap();
+
+    assert_eq!(
+        runtime.invoke("string_len", &[]).unwrap(),
+        Some(RuntimeValue::I32(5104))
+    );
+    assert_eq!(
+        runtime.invoke("array_sum", &[]).unwrap(),
+        Some(RuntimeValue::I64(6))
+    );
+    assert_eq!(
+        runtime.invoke("struct_sum", &[]).unwrap(),
+        Some(RuntimeValue::I32(7))
+    );
+}
+
+#[test]
+fn index_out_of_bounds() {
+    let runtime = Runtime::from_bytes(&allocating_assembly()).unwrap();
+
+    assert_eq!(
+        runtime.invoke("index", &[RuntimeValue::I32(1)]).unwrap(),
+        Some(RuntimeValue::I32(1))
+    );
+    assert_eq!(
+        runtime
+            .invoke("index", &[RuntimeValue::I32(2)])
+            .unwrap_err()
+            .to_string(),
+        "failed to invoke `fn index(core::i32):core::i32`: index out of bounds: the len is 2 but the index is 2"
+    );
+}
@@ -77,10 +77,10 @@ pub fn run_test(code: &str, mode: TestMode) {
         .expect("error creating runtime for test assembly");
 
     // Find the main function
-    if runtime.borrow().get_function_definition("main").is_none() {
+    if runtime.read().get_function_definition("main").is_none() {
         panic!("Could not find `main` function");
     }
 
     // Call the main function
-    let _: () = invoke_fn!(runtime.borrow_mut(), "main").expect("error calling main function");
+    let _: () = invoke_fn!(runtime.write(), "main").expect("error calling main function");
 }
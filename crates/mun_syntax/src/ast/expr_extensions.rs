@@ -159,6 +159,60 @@ impl BinExpr {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RangeOp {
+    /// The `..` operator for a range that excludes its end
+    Exclusive,
+    /// The `..=` operator for a range that includes its end
+    Inclusive,
+}
+
+impl ast::RangeExpr {
+    pub fn op_details(&self) -> Option<(SyntaxToken, RangeOp)> {
+        self.syntax()
+            .children_with_tokens()
+            .filter_map(|it| it.into_token())
+            .find_map(|c| {
+                let range_op = match c.kind() {
+                    T![..] => RangeOp::Exclusive,
+                    T![..=] => RangeOp::Inclusive,
+                    _ => return None,
+                };
+                Some((c, range_op))
+            })
+    }
+
+    pub fn op_kind(&self) -> Option<RangeOp> {
+        self.op_details().map(|t| t.1)
+    }
+
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        self.op_details().map(|t| t.0)
+    }
+
+    /// Returns the start of the range, e.g. `a` in `a..b`
+    pub fn start(&self) -> Option<ast::Expr> {
+        children(self).next()
+    }
+
+    /// Returns the end of the range, e.g. `b` in `a..b`
+    pub fn end(&self) -> Option<ast::Expr> {
+        children(self).nth(1)
+    }
+}
+
+impl ast::IndexExpr {
+    /// Returns the expression that is being indexed, e.g. `a` in `a[i]`
+    pub fn base(&self) -> Option<ast::Expr> {
+        children(self).next()
+    }
+
+    /// Returns the index expression, e.g. `i` in `a[i]`
+    pub fn index(&self) -> Option<ast::Expr> {
+        children(self).nth(1)
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub enum FieldKind {
     Name(ast::NameRef),
@@ -207,3 +207,54 @@ impl ast::UseTree {
             .any(|it| it.kind() == T![*])
     }
 }
+
+impl ast::EnumDef {
+    pub fn signature_range(&self) -> TextRange {
+        let enum_kw = self
+            .syntax()
+            .children_with_tokens()
+            .find(|p| p.kind() == T![enum])
+            .map(|kw| kw.text_range());
+        let name = self.name().map(|n| n.syntax.text_range());
+
+        let start = enum_kw
+            .map(|kw| kw.start())
+            .unwrap_or_else(|| self.syntax.text_range().start());
+
+        let end = name
+            .map(|name| name.end())
+            .or_else(|| enum_kw.map(|kw| kw.end()))
+            .unwrap_or_else(|| self.syntax().text_range().end());
+
+        TextRange::new(start, end)
+    }
+}
+
+impl ast::EnumVariant {
+    pub fn kind(&self) -> StructKind {
+        StructKind::from_node(self)
+    }
+}
+
+impl ast::RecordFieldPatList {
+    /// Returns true if the list ends with a `..`, indicating that the remaining fields are ignored.
+    pub fn has_rest(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .any(|it| it.kind() == T![..])
+    }
+}
+
+impl ast::RecordFieldPat {
+    /// Returns the name of the field that is matched. For shorthand fields (e.g. `{ x }`) this is
+    /// the name of the binding.
+    pub fn field_name(&self) -> Option<SmolStr> {
+        if let Some(name_ref) = self.name_ref() {
+            return Some(name_ref.text().clone());
+        }
+        match self.pat()?.kind() {
+            ast::PatKind::BindPat(pat) => pat.name().map(|name| name.text().clone()),
+            _ => None,
+        }
+    }
+}
@@ -43,6 +43,62 @@ impl ArgList {
     }
 }
 
+// ArrayExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrayExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ArrayExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ARRAY_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ArrayExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ArrayExpr {
+    pub fn exprs(&self) -> impl Iterator<Item = Expr> {
+        super::children(self)
+    }
+}
+
+// ArrayType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrayType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ArrayType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ARRAY_TYPE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ArrayType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ArrayType {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+}
+
 // BinExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -217,6 +273,147 @@ impl Condition {
     }
 }
 
+// ConstDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ConstDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, CONST_DEF)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ConstDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for ConstDef {}
+impl ast::VisibilityOwner for ConstDef {}
+impl ast::DocCommentsOwner for ConstDef {}
+impl ast::TypeAscriptionOwner for ConstDef {}
+impl ConstDef {
+    pub fn body(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// ContinueExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContinueExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ContinueExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, CONTINUE_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ContinueExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ContinueExpr {}
+
+// EnumDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ENUM_DEF)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(EnumDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for EnumDef {}
+impl ast::VisibilityOwner for EnumDef {}
+impl ast::DocCommentsOwner for EnumDef {}
+impl EnumDef {
+    pub fn variant_list(&self) -> Option<EnumVariantList> {
+        super::child_opt(self)
+    }
+}
+
+// EnumVariant
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumVariant {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumVariant {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ENUM_VARIANT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(EnumVariant { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for EnumVariant {}
+impl ast::DocCommentsOwner for EnumVariant {}
+impl EnumVariant {}
+
+// EnumVariantList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumVariantList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumVariantList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ENUM_VARIANT_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(EnumVariantList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl EnumVariantList {
+    pub fn variants(&self) -> impl Iterator<Item = EnumVariant> {
+        super::children(self)
+    }
+}
+
 // Expr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -232,14 +429,22 @@ impl AstNode for Expr {
                 | PREFIX_EXPR
                 | PATH_EXPR
                 | BIN_EXPR
+                | RANGE_EXPR
                 | PAREN_EXPR
                 | CALL_EXPR
+                | METHOD_CALL_EXPR
                 | FIELD_EXPR
+                | INDEX_EXPR
+                | ARRAY_EXPR
                 | IF_EXPR
                 | LOOP_EXPR
                 | WHILE_EXPR
+                | FOR_EXPR
                 | RETURN_EXPR
                 | BREAK_EXPR
+                | CONTINUE_EXPR
+                | LAMBDA_EXPR
+                | MATCH_EXPR
                 | BLOCK_EXPR
                 | RECORD_LIT
         )
@@ -261,14 +466,22 @@ pub enum ExprKind {
     PrefixExpr(PrefixExpr),
     PathExpr(PathExpr),
     BinExpr(BinExpr),
+    RangeExpr(RangeExpr),
     ParenExpr(ParenExpr),
     CallExpr(CallExpr),
+    MethodCallExpr(MethodCallExpr),
     FieldExpr(FieldExpr),
+    IndexExpr(IndexExpr),
+    ArrayExpr(ArrayExpr),
     IfExpr(IfExpr),
     LoopExpr(LoopExpr),
     WhileExpr(WhileExpr),
+    ForExpr(ForExpr),
     ReturnExpr(ReturnExpr),
     BreakExpr(BreakExpr),
+    ContinueExpr(ContinueExpr),
+    LambdaExpr(LambdaExpr),
+    MatchExpr(MatchExpr),
     BlockExpr(BlockExpr),
     RecordLit(RecordLit),
 }
@@ -292,6 +505,11 @@ impl From<BinExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<RangeExpr> for Expr {
+    fn from(n: RangeExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<ParenExpr> for Expr {
     fn from(n: ParenExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -302,11 +520,26 @@ impl From<CallExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<MethodCallExpr> for Expr {
+    fn from(n: MethodCallExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<FieldExpr> for Expr {
     fn from(n: FieldExpr) -> Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<IndexExpr> for Expr {
+    fn from(n: IndexExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<ArrayExpr> for Expr {
+    fn from(n: ArrayExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<IfExpr> for Expr {
     fn from(n: IfExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -322,6 +555,11 @@ impl From<WhileExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<ForExpr> for Expr {
+    fn from(n: ForExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<ReturnExpr> for Expr {
     fn from(n: ReturnExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -332,6 +570,21 @@ impl From<BreakExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<ContinueExpr> for Expr {
+    fn from(n: ContinueExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<LambdaExpr> for Expr {
+    fn from(n: LambdaExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<MatchExpr> for Expr {
+    fn from(n: MatchExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<BlockExpr> for Expr {
     fn from(n: BlockExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -350,14 +603,26 @@ impl Expr {
             PREFIX_EXPR => ExprKind::PrefixExpr(PrefixExpr::cast(self.syntax.clone()).unwrap()),
             PATH_EXPR => ExprKind::PathExpr(PathExpr::cast(self.syntax.clone()).unwrap()),
             BIN_EXPR => ExprKind::BinExpr(BinExpr::cast(self.syntax.clone()).unwrap()),
+            RANGE_EXPR => ExprKind::RangeExpr(RangeExpr::cast(self.syntax.clone()).unwrap()),
             PAREN_EXPR => ExprKind::ParenExpr(ParenExpr::cast(self.syntax.clone()).unwrap()),
             CALL_EXPR => ExprKind::CallExpr(CallExpr::cast(self.syntax.clone()).unwrap()),
+            METHOD_CALL_EXPR => {
+                ExprKind::MethodCallExpr(MethodCallExpr::cast(self.syntax.clone()).unwrap())
+            }
             FIELD_EXPR => ExprKind::FieldExpr(FieldExpr::cast(self.syntax.clone()).unwrap()),
+            INDEX_EXPR => ExprKind::IndexExpr(IndexExpr::cast(self.syntax.clone()).unwrap()),
+            ARRAY_EXPR => ExprKind::ArrayExpr(ArrayExpr::cast(self.syntax.clone()).unwrap()),
             IF_EXPR => ExprKind::IfExpr(IfExpr::cast(self.syntax.clone()).unwrap()),
             LOOP_EXPR => ExprKind::LoopExpr(LoopExpr::cast(self.syntax.clone()).unwrap()),
             WHILE_EXPR => ExprKind::WhileExpr(WhileExpr::cast(self.syntax.clone()).unwrap()),
+            FOR_EXPR => ExprKind::ForExpr(ForExpr::cast(self.syntax.clone()).unwrap()),
             RETURN_EXPR => ExprKind::ReturnExpr(ReturnExpr::cast(self.syntax.clone()).unwrap()),
             BREAK_EXPR => ExprKind::BreakExpr(BreakExpr::cast(self.syntax.clone()).unwrap()),
+            CONTINUE_EXPR => {
+                ExprKind::ContinueExpr(ContinueExpr::cast(self.syntax.clone()).unwrap())
+            }
+            LAMBDA_EXPR => ExprKind::LambdaExpr(LambdaExpr::cast(self.syntax.clone()).unwrap()),
+            MATCH_EXPR => ExprKind::MatchExpr(MatchExpr::cast(self.syntax.clone()).unwrap()),
             BLOCK_EXPR => ExprKind::BlockExpr(BlockExpr::cast(self.syntax.clone()).unwrap()),
             RECORD_LIT => ExprKind::RecordLit(RecordLit::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
@@ -427,6 +692,71 @@ impl FieldExpr {
     }
 }
 
+// FnType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FnType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for FnType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, FN_TYPE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(FnType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl FnType {
+    pub fn param_list(&self) -> Option<ParamList> {
+        super::child_opt(self)
+    }
+
+    pub fn ret_type(&self) -> Option<RetType> {
+        super::child_opt(self)
+    }
+}
+
+// ForExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ForExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, FOR_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ForExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::LoopBodyOwner for ForExpr {}
+impl ForExpr {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+
+    pub fn iterable(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
 // FunctionDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -453,6 +783,7 @@ impl ast::NameOwner for FunctionDef {}
 impl ast::VisibilityOwner for FunctionDef {}
 impl ast::DocCommentsOwner for FunctionDef {}
 impl ast::ExternOwner for FunctionDef {}
+impl ast::TypeParamsOwner for FunctionDef {}
 impl FunctionDef {
     pub fn param_list(&self) -> Option<ParamList> {
         super::child_opt(self)
@@ -495,20 +826,20 @@ impl IfExpr {
     }
 }
 
-// LetStmt
+// ImplDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct LetStmt {
+pub struct ImplDef {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for LetStmt {
+impl AstNode for ImplDef {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, LET_STMT)
+        matches!(kind, IMPL_DEF)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(LetStmt { syntax })
+            Some(ImplDef { syntax })
         } else {
             None
         }
@@ -517,31 +848,35 @@ impl AstNode for LetStmt {
         &self.syntax
     }
 }
-impl ast::TypeAscriptionOwner for LetStmt {}
-impl LetStmt {
-    pub fn pat(&self) -> Option<Pat> {
+impl ast::DocCommentsOwner for ImplDef {}
+impl ImplDef {
+    pub fn path(&self) -> Option<Path> {
         super::child_opt(self)
     }
 
-    pub fn initializer(&self) -> Option<Expr> {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+
+    pub fn item_list(&self) -> Option<ItemList> {
         super::child_opt(self)
     }
 }
 
-// Literal
+// IndexExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Literal {
+pub struct IndexExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for Literal {
+impl AstNode for IndexExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, LITERAL)
+        matches!(kind, INDEX_EXPR)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(Literal { syntax })
+            Some(IndexExpr { syntax })
         } else {
             None
         }
@@ -550,22 +885,22 @@ impl AstNode for Literal {
         &self.syntax
     }
 }
-impl Literal {}
+impl IndexExpr {}
 
-// LoopExpr
+// ItemList
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct LoopExpr {
+pub struct ItemList {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for LoopExpr {
+impl AstNode for ItemList {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, LOOP_EXPR)
+        matches!(kind, ITEM_LIST)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(LoopExpr { syntax })
+            Some(ItemList { syntax })
         } else {
             None
         }
@@ -574,23 +909,23 @@ impl AstNode for LoopExpr {
         &self.syntax
     }
 }
-impl ast::LoopBodyOwner for LoopExpr {}
-impl LoopExpr {}
+impl ast::FunctionDefOwner for ItemList {}
+impl ItemList {}
 
-// MemoryTypeSpecifier
+// LambdaExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct MemoryTypeSpecifier {
+pub struct LambdaExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for MemoryTypeSpecifier {
+impl AstNode for LambdaExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, MEMORY_TYPE_SPECIFIER)
+        matches!(kind, LAMBDA_EXPR)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(MemoryTypeSpecifier { syntax })
+            Some(LambdaExpr { syntax })
         } else {
             None
         }
@@ -599,9 +934,252 @@ impl AstNode for MemoryTypeSpecifier {
         &self.syntax
     }
 }
-impl MemoryTypeSpecifier {}
+impl LambdaExpr {
+    pub fn param_list(&self) -> Option<ParamList> {
+        super::child_opt(self)
+    }
 
-// ModuleItem
+    pub fn ret_type(&self) -> Option<RetType> {
+        super::child_opt(self)
+    }
+
+    pub fn body(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// LetStmt
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LetStmt {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for LetStmt {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, LET_STMT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(LetStmt { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::TypeAscriptionOwner for LetStmt {}
+impl LetStmt {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+
+    pub fn initializer(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// Literal
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Literal {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Literal {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, LITERAL)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Literal { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Literal {}
+
+// LoopExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LoopExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for LoopExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, LOOP_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(LoopExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::LoopBodyOwner for LoopExpr {}
+impl LoopExpr {}
+
+// MatchArm
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchArm {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MatchArm {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, MATCH_ARM)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MatchArm { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl MatchArm {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// MatchArmList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchArmList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MatchArmList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, MATCH_ARM_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MatchArmList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl MatchArmList {
+    pub fn arms(&self) -> impl Iterator<Item = MatchArm> {
+        super::children(self)
+    }
+}
+
+// MatchExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MatchExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, MATCH_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MatchExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl MatchExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+
+    pub fn match_arm_list(&self) -> Option<MatchArmList> {
+        super::child_opt(self)
+    }
+}
+
+// MemoryTypeSpecifier
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryTypeSpecifier {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MemoryTypeSpecifier {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, MEMORY_TYPE_SPECIFIER)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MemoryTypeSpecifier { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl MemoryTypeSpecifier {}
+
+// MethodCallExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodCallExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MethodCallExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, METHOD_CALL_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MethodCallExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::ArgListOwner for MethodCallExpr {}
+impl MethodCallExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+}
+
+// ModuleItem
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ModuleItem {
@@ -610,7 +1188,17 @@ pub struct ModuleItem {
 
 impl AstNode for ModuleItem {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, USE | FUNCTION_DEF | STRUCT_DEF | TYPE_ALIAS_DEF)
+        matches!(
+            kind,
+            USE | FUNCTION_DEF
+                | STRUCT_DEF
+                | ENUM_DEF
+                | TYPE_ALIAS_DEF
+                | IMPL_DEF
+                | TRAIT_DEF
+                | CONST_DEF
+                | STATIC_DEF
+        )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
@@ -628,7 +1216,12 @@ pub enum ModuleItemKind {
     Use(Use),
     FunctionDef(FunctionDef),
     StructDef(StructDef),
+    EnumDef(EnumDef),
     TypeAliasDef(TypeAliasDef),
+    ImplDef(ImplDef),
+    TraitDef(TraitDef),
+    ConstDef(ConstDef),
+    StaticDef(StaticDef),
 }
 impl From<Use> for ModuleItem {
     fn from(n: Use) -> ModuleItem {
@@ -645,11 +1238,36 @@ impl From<StructDef> for ModuleItem {
         ModuleItem { syntax: n.syntax }
     }
 }
+impl From<EnumDef> for ModuleItem {
+    fn from(n: EnumDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
 impl From<TypeAliasDef> for ModuleItem {
     fn from(n: TypeAliasDef) -> ModuleItem {
         ModuleItem { syntax: n.syntax }
     }
 }
+impl From<ImplDef> for ModuleItem {
+    fn from(n: ImplDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<TraitDef> for ModuleItem {
+    fn from(n: TraitDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<ConstDef> for ModuleItem {
+    fn from(n: ConstDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<StaticDef> for ModuleItem {
+    fn from(n: StaticDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
 
 impl ModuleItem {
     pub fn kind(&self) -> ModuleItemKind {
@@ -659,9 +1277,14 @@ impl ModuleItem {
                 ModuleItemKind::FunctionDef(FunctionDef::cast(self.syntax.clone()).unwrap())
             }
             STRUCT_DEF => ModuleItemKind::StructDef(StructDef::cast(self.syntax.clone()).unwrap()),
+            ENUM_DEF => ModuleItemKind::EnumDef(EnumDef::cast(self.syntax.clone()).unwrap()),
             TYPE_ALIAS_DEF => {
                 ModuleItemKind::TypeAliasDef(TypeAliasDef::cast(self.syntax.clone()).unwrap())
             }
+            IMPL_DEF => ModuleItemKind::ImplDef(ImplDef::cast(self.syntax.clone()).unwrap()),
+            TRAIT_DEF => ModuleItemKind::TraitDef(TraitDef::cast(self.syntax.clone()).unwrap()),
+            CONST_DEF => ModuleItemKind::ConstDef(ConstDef::cast(self.syntax.clone()).unwrap()),
+            STATIC_DEF => ModuleItemKind::StaticDef(StaticDef::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
@@ -796,6 +1419,10 @@ impl ParamList {
     pub fn params(&self) -> impl Iterator<Item = Param> {
         super::children(self)
     }
+
+    pub fn self_param(&self) -> Option<SelfParam> {
+        super::child_opt(self)
+    }
 }
 
 // ParenExpr
@@ -835,7 +1462,10 @@ pub struct Pat {
 
 impl AstNode for Pat {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, BIND_PAT | PLACEHOLDER_PAT)
+        matches!(
+            kind,
+            BIND_PAT | PLACEHOLDER_PAT | PATH_PAT | TUPLE_STRUCT_PAT | RECORD_PAT
+        )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
@@ -852,6 +1482,9 @@ impl AstNode for Pat {
 pub enum PatKind {
     BindPat(BindPat),
     PlaceholderPat(PlaceholderPat),
+    PathPat(PathPat),
+    TupleStructPat(TupleStructPat),
+    RecordPat(RecordPat),
 }
 impl From<BindPat> for Pat {
     fn from(n: BindPat) -> Pat {
@@ -863,6 +1496,21 @@ impl From<PlaceholderPat> for Pat {
         Pat { syntax: n.syntax }
     }
 }
+impl From<PathPat> for Pat {
+    fn from(n: PathPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<TupleStructPat> for Pat {
+    fn from(n: TupleStructPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<RecordPat> for Pat {
+    fn from(n: RecordPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
 
 impl Pat {
     pub fn kind(&self) -> PatKind {
@@ -871,6 +1519,11 @@ impl Pat {
             PLACEHOLDER_PAT => {
                 PatKind::PlaceholderPat(PlaceholderPat::cast(self.syntax.clone()).unwrap())
             }
+            PATH_PAT => PatKind::PathPat(PathPat::cast(self.syntax.clone()).unwrap()),
+            TUPLE_STRUCT_PAT => {
+                PatKind::TupleStructPat(TupleStructPat::cast(self.syntax.clone()).unwrap())
+            }
+            RECORD_PAT => PatKind::RecordPat(RecordPat::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
@@ -938,6 +1591,34 @@ impl PathExpr {
     }
 }
 
+// PathPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for PathPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, PATH_PAT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(PathPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl PathPat {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+}
+
 // PathSegment
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -964,6 +1645,10 @@ impl PathSegment {
     pub fn name_ref(&self) -> Option<NameRef> {
         super::child_opt(self)
     }
+
+    pub fn type_arg_list(&self) -> Option<TypeArgList> {
+        super::child_opt(self)
+    }
 }
 
 // PathType
@@ -1046,6 +1731,30 @@ impl PrefixExpr {
     }
 }
 
+// RangeExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RangeExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, RANGE_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RangeExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RangeExpr {}
+
 // RecordField
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1106,20 +1815,112 @@ impl ast::DocCommentsOwner for RecordFieldDef {}
 impl ast::TypeAscriptionOwner for RecordFieldDef {}
 impl RecordFieldDef {}
 
-// RecordFieldDefList
+// RecordFieldDefList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordFieldDefList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordFieldDefList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, RECORD_FIELD_DEF_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordFieldDefList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RecordFieldDefList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordFieldDef> {
+        super::children(self)
+    }
+}
+
+// RecordFieldList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordFieldList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordFieldList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, RECORD_FIELD_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordFieldList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RecordFieldList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordField> {
+        super::children(self)
+    }
+
+    pub fn spread(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// RecordFieldPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordFieldPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordFieldPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, RECORD_FIELD_PAT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordFieldPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RecordFieldPat {
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+}
+
+// RecordFieldPatList
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RecordFieldDefList {
+pub struct RecordFieldPatList {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RecordFieldDefList {
+impl AstNode for RecordFieldPatList {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, RECORD_FIELD_DEF_LIST)
+        matches!(kind, RECORD_FIELD_PAT_LIST)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RecordFieldDefList { syntax })
+            Some(RecordFieldPatList { syntax })
         } else {
             None
         }
@@ -1128,26 +1929,26 @@ impl AstNode for RecordFieldDefList {
         &self.syntax
     }
 }
-impl RecordFieldDefList {
-    pub fn fields(&self) -> impl Iterator<Item = RecordFieldDef> {
+impl RecordFieldPatList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordFieldPat> {
         super::children(self)
     }
 }
 
-// RecordFieldList
+// RecordLit
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RecordFieldList {
+pub struct RecordLit {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RecordFieldList {
+impl AstNode for RecordLit {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, RECORD_FIELD_LIST)
+        matches!(kind, RECORD_LIT)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RecordFieldList { syntax })
+            Some(RecordLit { syntax })
         } else {
             None
         }
@@ -1156,30 +1957,30 @@ impl AstNode for RecordFieldList {
         &self.syntax
     }
 }
-impl RecordFieldList {
-    pub fn fields(&self) -> impl Iterator<Item = RecordField> {
-        super::children(self)
+impl RecordLit {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
     }
 
-    pub fn spread(&self) -> Option<Expr> {
+    pub fn record_field_list(&self) -> Option<RecordFieldList> {
         super::child_opt(self)
     }
 }
 
-// RecordLit
+// RecordPat
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RecordLit {
+pub struct RecordPat {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RecordLit {
+impl AstNode for RecordPat {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, RECORD_LIT)
+        matches!(kind, RECORD_PAT)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RecordLit { syntax })
+            Some(RecordPat { syntax })
         } else {
             None
         }
@@ -1188,12 +1989,12 @@ impl AstNode for RecordLit {
         &self.syntax
     }
 }
-impl RecordLit {
-    pub fn type_ref(&self) -> Option<TypeRef> {
+impl RecordPat {
+    pub fn path(&self) -> Option<Path> {
         super::child_opt(self)
     }
 
-    pub fn record_field_list(&self) -> Option<RecordFieldList> {
+    pub fn record_field_pat_list(&self) -> Option<RecordFieldPatList> {
         super::child_opt(self)
     }
 }
@@ -1279,6 +2080,30 @@ impl ReturnExpr {
     }
 }
 
+// SelfParam
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SelfParam {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for SelfParam {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, SELF_PARAM)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(SelfParam { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl SelfParam {}
+
 // SourceFile
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1305,6 +2130,38 @@ impl ast::ModuleItemOwner for SourceFile {}
 impl ast::FunctionDefOwner for SourceFile {}
 impl SourceFile {}
 
+// StaticDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StaticDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for StaticDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, STATIC_DEF)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(StaticDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for StaticDef {}
+impl ast::VisibilityOwner for StaticDef {}
+impl ast::DocCommentsOwner for StaticDef {}
+impl ast::TypeAscriptionOwner for StaticDef {}
+impl StaticDef {
+    pub fn body(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
 // Stmt
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1380,12 +2237,44 @@ impl AstNode for StructDef {
 impl ast::NameOwner for StructDef {}
 impl ast::VisibilityOwner for StructDef {}
 impl ast::DocCommentsOwner for StructDef {}
+impl ast::TypeParamsOwner for StructDef {}
 impl StructDef {
     pub fn memory_type_specifier(&self) -> Option<MemoryTypeSpecifier> {
         super::child_opt(self)
     }
 }
 
+// TraitDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraitDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TraitDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TRAIT_DEF)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TraitDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for TraitDef {}
+impl ast::VisibilityOwner for TraitDef {}
+impl ast::DocCommentsOwner for TraitDef {}
+impl TraitDef {
+    pub fn item_list(&self) -> Option<ItemList> {
+        super::child_opt(self)
+    }
+}
+
 // TupleFieldDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1443,6 +2332,38 @@ impl TupleFieldDefList {
     }
 }
 
+// TupleStructPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleStructPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TupleStructPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TUPLE_STRUCT_PAT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TupleStructPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TupleStructPat {
+    pub fn args(&self) -> impl Iterator<Item = Pat> {
+        super::children(self)
+    }
+
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+}
+
 // TypeAliasDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1474,6 +2395,175 @@ impl TypeAliasDef {
     }
 }
 
+// TypeArg
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeArg {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeArg {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TYPE_ARG)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TypeArg { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TypeArg {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+}
+
+// TypeArgList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeArgList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeArgList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TYPE_ARG_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TypeArgList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TypeArgList {
+    pub fn type_args(&self) -> impl Iterator<Item = TypeArg> {
+        super::children(self)
+    }
+}
+
+// TypeBound
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeBound {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeBound {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TYPE_BOUND)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TypeBound { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TypeBound {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+}
+
+// TypeBoundList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeBoundList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeBoundList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TYPE_BOUND_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TypeBoundList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TypeBoundList {
+    pub fn bounds(&self) -> impl Iterator<Item = TypeBound> {
+        super::children(self)
+    }
+}
+
+// TypeParam
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeParam {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeParam {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TYPE_PARAM)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TypeParam { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for TypeParam {}
+impl TypeParam {
+    pub fn type_bound_list(&self) -> Option<TypeBoundList> {
+        super::child_opt(self)
+    }
+}
+
+// TypeParamList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeParamList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeParamList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TYPE_PARAM_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TypeParamList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TypeParamList {
+    pub fn type_params(&self) -> impl Iterator<Item = TypeParam> {
+        super::children(self)
+    }
+}
+
 // TypeRef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1483,7 +2573,7 @@ pub struct TypeRef {
 
 impl AstNode for TypeRef {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, PATH_TYPE | NEVER_TYPE)
+        matches!(kind, PATH_TYPE | NEVER_TYPE | ARRAY_TYPE | FN_TYPE)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
@@ -1500,6 +2590,8 @@ impl AstNode for TypeRef {
 pub enum TypeRefKind {
     PathType(PathType),
     NeverType(NeverType),
+    ArrayType(ArrayType),
+    FnType(FnType),
 }
 impl From<PathType> for TypeRef {
     fn from(n: PathType) -> TypeRef {
@@ -1511,12 +2603,24 @@ impl From<NeverType> for TypeRef {
         TypeRef { syntax: n.syntax }
     }
 }
+impl From<ArrayType> for TypeRef {
+    fn from(n: ArrayType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<FnType> for TypeRef {
+    fn from(n: FnType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
 
 impl TypeRef {
     pub fn kind(&self) -> TypeRefKind {
         match self.syntax.kind() {
             PATH_TYPE => TypeRefKind::PathType(PathType::cast(self.syntax.clone()).unwrap()),
             NEVER_TYPE => TypeRefKind::NeverType(NeverType::cast(self.syntax.clone()).unwrap()),
+            ARRAY_TYPE => TypeRefKind::ArrayType(ArrayType::cast(self.syntax.clone()).unwrap()),
+            FN_TYPE => TypeRefKind::FnType(FnType::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
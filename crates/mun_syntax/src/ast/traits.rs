@@ -32,6 +32,12 @@ pub trait VisibilityOwner: AstNode {
     }
 }
 
+pub trait TypeParamsOwner: AstNode {
+    fn type_param_list(&self) -> Option<ast::TypeParamList> {
+        child_opt(self)
+    }
+}
+
 pub trait LoopBodyOwner: AstNode {
     fn loop_body(&self) -> Option<ast::BlockExpr> {
         child_opt(self)
@@ -4,6 +4,8 @@ mod expressions;
 mod params;
 mod paths;
 mod patterns;
+mod type_args;
+mod type_params;
 mod types;
 
 use super::{
@@ -1,8 +1,17 @@
 use super::*;
 use crate::{parsing::grammar::paths::is_use_path_start, T};
 
-pub(super) const DECLARATION_RECOVERY_SET: TokenSet =
-    TokenSet::new(&[T![fn], T![pub], T![struct], T![use]]);
+pub(super) const DECLARATION_RECOVERY_SET: TokenSet = TokenSet::new(&[
+    T![fn],
+    T![pub],
+    T![struct],
+    T![enum],
+    T![use],
+    T![impl],
+    T![trait],
+    T![const],
+    T![static],
+]);
 
 pub(super) fn mod_contents(p: &mut Parser) {
     while !p.at(EOF) {
@@ -69,9 +78,24 @@ fn declarations_without_modifiers(p: &mut Parser, m: Marker) -> Result<(), Marke
         T![struct] => {
             adt::struct_def(p, m);
         }
+        T![enum] => {
+            adt::enum_def(p, m);
+        }
         T![type] => {
             adt::type_alias_def(p, m);
         }
+        T![impl] => {
+            impl_def(p, m);
+        }
+        T![trait] => {
+            trait_def(p, m);
+        }
+        T![const] => {
+            const_or_static_def(p, m, T![const], CONST_DEF);
+        }
+        T![static] => {
+            const_or_static_def(p, m, T![static], STATIC_DEF);
+        }
         _ => return Err(m),
     };
     Ok(())
@@ -83,6 +107,8 @@ pub(super) fn fn_def(p: &mut Parser) {
 
     name_recovery(p, DECLARATION_RECOVERY_SET.union(TokenSet::new(&[T![')']])));
 
+    type_params::opt_type_param_list(p);
+
     if p.at(T!['(']) {
         params::param_list(p);
     } else {
@@ -98,7 +124,91 @@ pub(super) fn fn_def(p: &mut Parser) {
     }
 }
 
-fn opt_fn_ret_type(p: &mut Parser) -> bool {
+/// Parses a constant or a global variable, e.g. `const FOO: i32 = 5;` or `static BAR: f32 = 1.0;`.
+fn const_or_static_def(p: &mut Parser, m: Marker, kw: SyntaxKind, kind: SyntaxKind) {
+    assert!(p.at(kw));
+    p.bump(kw);
+    name_recovery(
+        p,
+        DECLARATION_RECOVERY_SET.union(TokenSet::new(&[T![:], T![=]])),
+    );
+    types::ascription(p);
+    if p.eat(T![=]) {
+        expressions::expr(p);
+    } else {
+        p.error("expected `=`");
+    }
+    p.expect(T![;]);
+    m.complete(p, kind);
+}
+
+fn impl_def(p: &mut Parser, m: Marker) {
+    assert!(p.at(T![impl]));
+    p.bump(T![impl]);
+    if paths::is_path_start(p) {
+        // The type could also be the trait of an `impl Trait for Type` block, in which case the
+        // trait is stored as a bare path.
+        let ty = p.start();
+        paths::type_path(p);
+        let ty = ty.complete(p, PATH_TYPE);
+        if p.at(T![for]) {
+            ty.undo_completion(p).abandon(p);
+            p.bump(T![for]);
+            types::type_(p);
+        }
+    } else {
+        types::type_(p);
+    }
+    if p.at(T!['{']) {
+        item_list(p);
+    } else {
+        p.error("expected `{`");
+    }
+    m.complete(p, IMPL_DEF);
+}
+
+/// Parses a trait declaration, e.g. `trait Update { fn update(self, dt: f32); }`.
+fn trait_def(p: &mut Parser, m: Marker) {
+    assert!(p.at(T![trait]));
+    p.bump(T![trait]);
+    name_recovery(p, DECLARATION_RECOVERY_SET.union(TokenSet::new(&[T!['{']])));
+    if p.at(T!['{']) {
+        item_list(p);
+    } else {
+        p.error("expected `{`");
+    }
+    m.complete(p, TRAIT_DEF);
+}
+
+/// Parses the items of an `impl` or `trait` block, e.g. the `{ fn foo(self) {} }` in `impl Foo { ... }`.
+fn item_list(p: &mut Parser) {
+    assert!(p.at(T!['{']));
+    let m = p.start();
+    p.bump(T!['{']);
+    while !p.at(EOF) && !p.at(T!['}']) {
+        if p.at(T!['{']) {
+            error_block(p, "expected an item");
+            continue;
+        }
+        associated_item(p);
+    }
+    p.expect(T!['}']);
+    m.complete(p, ITEM_LIST);
+}
+
+fn associated_item(p: &mut Parser) {
+    let m = p.start();
+    opt_visibility(p);
+    if p.at(T![fn]) {
+        fn_def(p);
+        m.complete(p, FUNCTION_DEF);
+    } else {
+        m.abandon(p);
+        p.error_and_bump("expected a function");
+    }
+}
+
+pub(super) fn opt_fn_ret_type(p: &mut Parser) -> bool {
     if p.at(T![->]) {
         let m = p.start();
         p.bump(T![->]);
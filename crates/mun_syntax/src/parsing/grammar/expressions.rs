@@ -10,11 +10,16 @@ const ATOM_EXPR_FIRST: TokenSet = LITERAL_FIRST.union(PATH_FIRST).union(TokenSet
     IDENT,
     T!['('],
     T!['{'],
+    T!['['],
     T![if],
     T![loop],
     T![return],
     T![break],
+    T![continue],
     T![while],
+    T![for],
+    T![match],
+    T![|],
 ]));
 
 const LHS_FIRST: TokenSet = ATOM_EXPR_FIRST.union(TokenSet::new(&[T![!], T![-]]));
@@ -145,7 +150,14 @@ fn expr_bp(p: &mut Parser, r: Restrictions, bp: u8) -> (Option<CompletedMarker>,
         p.bump(op);
 
         expr_bp(p, r, op_bp + 1);
-        lhs = m.complete(p, BIN_EXPR);
+        lhs = m.complete(
+            p,
+            if matches!(op, T![..] | T![..=]) {
+                RANGE_EXPR
+            } else {
+                BIN_EXPR
+            },
+        );
     }
 
     (Some(lhs), BlockLike::NotBlock)
@@ -182,6 +194,8 @@ fn current_op(p: &Parser) -> (u8, SyntaxKind) {
         T![<] if p.at(T![<<=]) => (1, T![<<=]),
         T![<] if p.at(T![<<]) => (9, T![<<]),
         T![<] => (5, T![<]),
+        T![.] if p.at(T![..=]) => (2, T![..=]),
+        T![.] if p.at(T![..]) => (2, T![..]),
         _ => (0, T![_]),
     }
 }
@@ -211,7 +225,8 @@ fn postfix_expr(
     loop {
         lhs = match p.current() {
             T!['('] => call_expr(p, lhs),
-            T![.] => postfix_dot_expr(p, lhs),
+            T!['['] => index_expr(p, lhs),
+            T![.] if !p.at(T![..]) => postfix_dot_expr(p, lhs),
             INDEX => field_expr(p, lhs),
             _ => break,
         }
@@ -245,16 +260,33 @@ fn arg_list(p: &mut Parser) {
     m.complete(p, ARG_LIST);
 }
 
+fn index_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(T!['[']));
+    let m = lhs.precede(p);
+    p.bump(T!['[']);
+    expr(p);
+    p.expect(T![']']);
+    m.complete(p, INDEX_EXPR)
+}
+
 fn postfix_dot_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
     assert!(p.at(T![.]));
     if p.nth(1) == IDENT && p.nth(2) == T!['('] {
-        // TODO: Implement method calls here
-        //unimplemented!("Method calls are not supported yet.");
+        return method_call_expr(p, lhs);
     }
 
     field_expr(p, lhs)
 }
 
+fn method_call_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(T![.]) && p.nth(1) == IDENT && p.nth(2) == T!['(']);
+    let m = lhs.precede(p);
+    p.bump(T![.]);
+    name_ref(p);
+    arg_list(p);
+    m.complete(p, METHOD_CALL_EXPR)
+}
+
 fn field_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
     assert!(p.at(T![.]) || p.at(INDEX));
     let m = lhs.precede(p);
@@ -285,18 +317,23 @@ fn atom_expr(p: &mut Parser, r: Restrictions) -> Option<(CompletedMarker, BlockL
     let marker = match p.current() {
         T!['('] => paren_expr(p),
         T!['{'] => block_expr(p),
+        T!['['] => array_expr(p),
         T![if] => if_expr(p),
         T![loop] => loop_expr(p),
         T![return] => ret_expr(p),
         T![while] => while_expr(p),
+        T![for] => for_expr(p),
         T![break] => break_expr(p, r),
+        T![continue] => continue_expr(p),
+        T![match] => match_expr(p),
+        T![|] => lambda_expr(p),
         _ => {
             p.error_recover("expected expression", EXPR_RECOVERY_SET);
             return None;
         }
     };
     let blocklike = match marker.kind() {
-        IF_EXPR | WHILE_EXPR | LOOP_EXPR | BLOCK_EXPR => BlockLike::Block,
+        IF_EXPR | WHILE_EXPR | FOR_EXPR | LOOP_EXPR | BLOCK_EXPR | MATCH_EXPR => BlockLike::Block,
         _ => BlockLike::NotBlock,
     };
     Some((marker, blocklike))
@@ -334,6 +371,25 @@ fn paren_expr(p: &mut Parser) -> CompletedMarker {
     m.complete(p, PAREN_EXPR)
 }
 
+fn array_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T!['[']));
+    let m = p.start();
+    p.bump(T!['[']);
+    while !p.at(EOF) && !p.at(T![']']) {
+        if !p.at_ts(EXPR_FIRST) {
+            p.error("expected expression");
+            break;
+        }
+
+        expr(p);
+        if !p.at(T![']']) && !p.expect(T![,]) {
+            break;
+        }
+    }
+    p.expect(T![']']);
+    m.complete(p, ARRAY_EXPR)
+}
+
 fn if_expr(p: &mut Parser) -> CompletedMarker {
     assert!(p.at(T![if]));
     let m = p.start();
@@ -385,6 +441,13 @@ fn break_expr(p: &mut Parser, r: Restrictions) -> CompletedMarker {
     m.complete(p, BREAK_EXPR)
 }
 
+fn continue_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T![continue]));
+    let m = p.start();
+    p.bump(T![continue]);
+    m.complete(p, CONTINUE_EXPR)
+}
+
 fn while_expr(p: &mut Parser) -> CompletedMarker {
     assert!(p.at(T![while]));
     let m = p.start();
@@ -394,6 +457,75 @@ fn while_expr(p: &mut Parser) -> CompletedMarker {
     m.complete(p, WHILE_EXPR)
 }
 
+fn for_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T![for]));
+    let m = p.start();
+    p.bump(T![for]);
+    patterns::pattern(p);
+    p.expect(T![in]);
+    expr_no_struct(p);
+    block(p);
+    m.complete(p, FOR_EXPR)
+}
+
+fn lambda_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T![|]));
+    let m = p.start();
+    params::param_list_lambda(p);
+    if declarations::opt_fn_ret_type(p) {
+        // If a return type is specified, the body must be a block
+        if p.at(T!['{']) {
+            block_expr(p);
+        } else {
+            p.error("expected a block");
+        }
+    } else if p.at_ts(EXPR_FIRST) {
+        expr(p);
+    } else {
+        p.error("expected expression");
+    }
+    m.complete(p, LAMBDA_EXPR)
+}
+
+fn match_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T![match]));
+    let m = p.start();
+    p.bump(T![match]);
+    expr_no_struct(p);
+    if p.at(T!['{']) {
+        match_arm_list(p);
+    } else {
+        p.error("expected a '{'");
+    }
+    m.complete(p, MATCH_EXPR)
+}
+
+fn match_arm_list(p: &mut Parser) {
+    assert!(p.at(T!['{']));
+    let m = p.start();
+    p.bump(T!['{']);
+    while !p.at(EOF) && !p.at(T!['}']) {
+        if p.at(T!['{']) {
+            error_block(p, "expected a match arm");
+            continue;
+        }
+        match_arm(p);
+    }
+    p.expect(T!['}']);
+    m.complete(p, MATCH_ARM_LIST);
+}
+
+fn match_arm(p: &mut Parser) {
+    let m = p.start();
+    patterns::pattern_r(p, TokenSet::new(&[T![=]]));
+    p.expect(T![=>]);
+    let blocklike = expr_stmt(p).1;
+    if !p.eat(T![,]) && !blocklike.is_block() && !p.at(T!['}']) {
+        p.error("expected `,`");
+    }
+    m.complete(p, MATCH_ARM);
+}
+
 fn record_field_list(p: &mut Parser) {
     assert!(p.at(T!['{']));
     let m = p.start();
@@ -1,32 +1,93 @@
 use super::*;
 
 pub(super) fn param_list(p: &mut Parser) {
-    list(p)
+    list(p, Flavor::Function)
 }
 
-fn list(p: &mut Parser) {
-    assert!(p.at(T!['(']));
+/// Parses the parameters of a function type, e.g. `(i32, f64)` in `fn(i32, f64) -> bool`. The
+/// parameters only consist of types.
+pub(super) fn param_list_fn_type(p: &mut Parser) {
+    list(p, Flavor::FnType)
+}
+
+/// Parses the parameters of a lambda expression, e.g. `|a: i32, b|`. The types of the parameters
+/// are optional.
+pub(super) fn param_list_lambda(p: &mut Parser) {
+    list(p, Flavor::Lambda)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    Function,
+    FnType,
+    Lambda,
+}
+
+fn list(p: &mut Parser, flavor: Flavor) {
     let m = p.start();
-    p.bump(T!['(']);
-    while !p.at(EOF) && !p.at(T![')']) {
-        if !p.at_ts(VALUE_PARAMETER_FIRST) {
+    let (bra, ket) = match flavor {
+        Flavor::Lambda => {
+            // A lambda without parameters, e.g. `|| 5`
+            if p.at(T![||]) {
+                p.bump(T![||]);
+                m.complete(p, PARAM_LIST);
+                return;
+            }
+            (T![|], T![|])
+        }
+        Flavor::Function | Flavor::FnType => (T!['('], T![')']),
+    };
+    assert!(p.at(bra));
+    p.bump(bra);
+    if flavor == Flavor::Function {
+        opt_self_param(p);
+    }
+    while !p.at(EOF) && !p.at(ket) {
+        let first = match flavor {
+            Flavor::FnType => types::TYPE_FIRST,
+            Flavor::Function | Flavor::Lambda => VALUE_PARAMETER_FIRST,
+        };
+        if !p.at_ts(first) {
             p.error("expected value parameter");
             break;
         }
-        param(p);
-        if !p.at(T![')']) {
+        param(p, flavor);
+        if !p.at(ket) {
             p.expect(T![,]);
         }
     }
-    p.expect(T![')']);
+    p.expect(ket);
     m.complete(p, PARAM_LIST);
 }
 
 const VALUE_PARAMETER_FIRST: TokenSet = patterns::PATTERN_FIRST;
 
-fn param(p: &mut Parser) {
+/// Parses the `self` parameter of a method, if present.
+fn opt_self_param(p: &mut Parser) {
+    if p.at(T![self]) {
+        let m = p.start();
+        p.bump(T![self]);
+        m.complete(p, SELF_PARAM);
+        if !p.at(T![')']) {
+            p.expect(T![,]);
+        }
+    }
+}
+
+fn param(p: &mut Parser, flavor: Flavor) {
     let m = p.start();
-    patterns::pattern(p);
-    types::ascription(p);
+    match flavor {
+        Flavor::Function => {
+            patterns::pattern(p);
+            types::ascription(p);
+        }
+        Flavor::FnType => types::type_(p),
+        Flavor::Lambda => {
+            patterns::pattern(p);
+            if p.at(T![:]) {
+                types::ascription(p);
+            }
+        }
+    }
     m.complete(p, PARAM);
 }
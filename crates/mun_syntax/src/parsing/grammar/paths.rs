@@ -50,11 +50,14 @@ fn path(p: &mut Parser, mode: Mode, top_level: bool) {
     }
 }
 
-fn path_segment(p: &mut Parser, _mode: Mode, top_level: bool) {
+fn path_segment(p: &mut Parser, mode: Mode, top_level: bool) {
     let m = p.start();
     match p.current() {
         IDENT => {
             name_ref(p);
+            if mode == Mode::Type {
+                type_args::opt_type_arg_list(p);
+            }
         }
         T![super] | T![package] if top_level => p.bump_any(),
         T![self] => p.bump(T![self]),
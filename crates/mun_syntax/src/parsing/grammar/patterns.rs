@@ -14,10 +14,14 @@ pub(super) fn pattern_r(p: &mut Parser, recovery_set: TokenSet) {
 
 fn atom_pat(p: &mut Parser, recovery_set: TokenSet) -> Option<CompletedMarker> {
     let t1 = p.nth(0);
-    if t1 == IDENT {
+    if t1 == IDENT && !(p.nth_at(1, T![::]) || matches!(p.nth(1), T!['('] | T!['{'])) {
         return Some(bind_pat(p));
     }
 
+    if paths::is_path_start(p) {
+        return Some(path_pat(p));
+    }
+
     let m = match t1 {
         T![_] => placeholder_pat(p),
         _ => {
@@ -28,6 +32,81 @@ fn atom_pat(p: &mut Parser, recovery_set: TokenSet) -> Option<CompletedMarker> {
     Some(m)
 }
 
+/// Parses a pattern that starts with a path, e.g.:
+/// ```mun
+/// State::Idle
+/// State::Walking(speed)
+/// State::Attacking { target, .. }
+/// ```
+fn path_pat(p: &mut Parser) -> CompletedMarker {
+    assert!(paths::is_path_start(p));
+    let m = p.start();
+    paths::expr_path(p);
+    let kind = match p.current() {
+        T!['('] => {
+            tuple_pat_fields(p);
+            TUPLE_STRUCT_PAT
+        }
+        T!['{'] => {
+            record_field_pat_list(p);
+            RECORD_PAT
+        }
+        _ => PATH_PAT,
+    };
+    m.complete(p, kind)
+}
+
+fn tuple_pat_fields(p: &mut Parser) {
+    assert!(p.at(T!['(']));
+    p.bump(T!['(']);
+    while !p.at(EOF) && !p.at(T![')']) {
+        if !p.at_ts(PATTERN_FIRST) {
+            p.error("expected a pattern");
+            break;
+        }
+        pattern(p);
+        if !p.at(T![')']) && !p.expect(T![,]) {
+            break;
+        }
+    }
+    p.expect(T![')']);
+}
+
+fn record_field_pat_list(p: &mut Parser) {
+    assert!(p.at(T!['{']));
+    let m = p.start();
+    p.bump(T!['{']);
+    while !p.at(EOF) && !p.at(T!['}']) {
+        match p.current() {
+            T![.] if p.at(T![..]) => {
+                p.bump(T![..]);
+                if !p.at(T!['}']) {
+                    p.error("expected '}' after '..'");
+                }
+            }
+            IDENT if p.nth(1) == T![:] => {
+                let m = p.start();
+                name_ref(p);
+                p.bump(T![:]);
+                pattern(p);
+                m.complete(p, RECORD_FIELD_PAT);
+            }
+            IDENT => {
+                let m = p.start();
+                bind_pat(p);
+                m.complete(p, RECORD_FIELD_PAT);
+            }
+            T!['{'] => error_block(p, "expected a field"),
+            _ => p.error_and_bump("expected an identifier"),
+        }
+        if !p.at(T!['}']) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T!['}']);
+    m.complete(p, RECORD_FIELD_PAT_LIST);
+}
+
 fn placeholder_pat(p: &mut Parser) -> CompletedMarker {
     assert!(p.at(T![_]));
     let m = p.start();
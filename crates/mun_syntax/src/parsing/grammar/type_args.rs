@@ -0,0 +1,34 @@
+use super::*;
+
+/// Parses the generic type arguments of a path segment if present, e.g. the `<i32, f64>` in
+/// `Pair<i32, f64>`.
+pub(super) fn opt_type_arg_list(p: &mut Parser) {
+    if !p.at(T![<]) {
+        return;
+    }
+    type_arg_list(p);
+}
+
+fn type_arg_list(p: &mut Parser) {
+    assert!(p.at(T![<]));
+    let m = p.start();
+    p.bump(T![<]);
+    while !p.at(EOF) && !p.at(T![>]) {
+        if !p.at_ts(types::TYPE_FIRST) {
+            p.error("expected type argument");
+            break;
+        }
+        type_arg(p);
+        if !p.at(T![>]) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T![>]);
+    m.complete(p, TYPE_ARG_LIST);
+}
+
+fn type_arg(p: &mut Parser) {
+    let m = p.start();
+    types::type_(p);
+    m.complete(p, TYPE_ARG);
+}
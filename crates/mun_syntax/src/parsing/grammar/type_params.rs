@@ -0,0 +1,59 @@
+use super::*;
+
+/// Parses the generic type parameters of a declaration if present, e.g. the `<T, U>` in
+/// `fn foo<T, U>(a: T, b: U)`.
+pub(super) fn opt_type_param_list(p: &mut Parser) {
+    if !p.at(T![<]) {
+        return;
+    }
+    type_param_list(p);
+}
+
+fn type_param_list(p: &mut Parser) {
+    assert!(p.at(T![<]));
+    let m = p.start();
+    p.bump(T![<]);
+    while !p.at(EOF) && !p.at(T![>]) {
+        if !p.at(IDENT) {
+            p.error("expected type parameter");
+            break;
+        }
+        type_param(p);
+        if !p.at(T![>]) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T![>]);
+    m.complete(p, TYPE_PARAM_LIST);
+}
+
+fn type_param(p: &mut Parser) {
+    assert!(p.at(IDENT));
+    let m = p.start();
+    name(p);
+    if p.at(T![:]) {
+        type_bound_list(p);
+    }
+    m.complete(p, TYPE_PARAM);
+}
+
+/// Parses the trait bounds of a type parameter, e.g. the `: Update + Draw` in
+/// `fn foo<T: Update + Draw>(a: T)`.
+fn type_bound_list(p: &mut Parser) {
+    assert!(p.at(T![:]));
+    let m = p.start();
+    p.bump(T![:]);
+    loop {
+        if !paths::is_path_start(p) {
+            p.error("expected a trait bound");
+            break;
+        }
+        let bound = p.start();
+        paths::type_path(p);
+        bound.complete(p, TYPE_BOUND);
+        if !p.eat(T![+]) {
+            break;
+        }
+    }
+    m.complete(p, TYPE_BOUND_LIST);
+}
@@ -1,6 +1,7 @@
 use super::*;
 
-pub(super) const TYPE_FIRST: TokenSet = paths::PATH_FIRST.union(TokenSet::new(&[T![never]]));
+pub(super) const TYPE_FIRST: TokenSet =
+    paths::PATH_FIRST.union(TokenSet::new(&[T![never], T!['['], T![fn]]));
 
 pub(super) const TYPE_RECOVERY_SET: TokenSet = TokenSet::new(&[T!['('], T![,], T![pub]]);
 
@@ -12,6 +13,8 @@ pub(super) fn ascription(p: &mut Parser) {
 pub(super) fn type_(p: &mut Parser) {
     match p.current() {
         T![never] => never_type(p),
+        T!['['] => array_type(p),
+        T![fn] => fn_type(p),
         _ if paths::is_path_start(p) => path_type(p),
         _ => {
             p.error_recover("expected type", TYPE_RECOVERY_SET);
@@ -31,3 +34,25 @@ fn never_type(p: &mut Parser) {
     p.bump(T![never]);
     m.complete(p, NEVER_TYPE);
 }
+
+fn array_type(p: &mut Parser) {
+    assert!(p.at(T!['[']));
+    let m = p.start();
+    p.bump(T!['[']);
+    type_(p);
+    p.expect(T![']']);
+    m.complete(p, ARRAY_TYPE);
+}
+
+fn fn_type(p: &mut Parser) {
+    assert!(p.at(T![fn]));
+    let m = p.start();
+    p.bump(T![fn]);
+    if p.at(T!['(']) {
+        params::param_list_fn_type(p);
+    } else {
+        p.error("expected parameters");
+    }
+    declarations::opt_fn_ret_type(p);
+    m.complete(p, FN_TYPE);
+}
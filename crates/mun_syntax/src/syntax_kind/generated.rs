@@ -61,6 +61,7 @@ pub enum SyntaxKind {
     DOTDOTEQ,
     COLONCOLON,
     THIN_ARROW,
+    FAT_ARROW,
     AMPAMP,
     PIPEPIPE,
     SHL,
@@ -80,13 +81,20 @@ pub enum SyntaxKind {
     TRUE_KW,
     WHILE_KW,
     LOOP_KW,
+    CONTINUE_KW,
     LET_KW,
     MUT_KW,
     CLASS_KW,
     STRUCT_KW,
+    ENUM_KW,
+    MATCH_KW,
     NEVER_KW,
     PUB_KW,
     TYPE_KW,
+    IMPL_KW,
+    TRAIT_KW,
+    CONST_KW,
+    STATIC_KW,
     PACKAGE_KW,
     SUPER_KW,
     SELF_KW,
@@ -108,38 +116,70 @@ pub enum SyntaxKind {
     VISIBILITY,
     PARAM_LIST,
     PARAM,
+    SELF_PARAM,
+    TYPE_PARAM_LIST,
+    TYPE_PARAM,
+    TYPE_BOUND_LIST,
+    TYPE_BOUND,
     STRUCT_DEF,
     TYPE_ALIAS_DEF,
+    CONST_DEF,
+    STATIC_DEF,
     MEMORY_TYPE_SPECIFIER,
     RECORD_FIELD_DEF_LIST,
     RECORD_FIELD_DEF,
     TUPLE_FIELD_DEF_LIST,
     TUPLE_FIELD_DEF,
+    ENUM_DEF,
+    ENUM_VARIANT_LIST,
+    ENUM_VARIANT,
+    IMPL_DEF,
+    TRAIT_DEF,
+    ITEM_LIST,
     PATH_TYPE,
     NEVER_TYPE,
+    ARRAY_TYPE,
+    FN_TYPE,
     LET_STMT,
     EXPR_STMT,
     PATH_EXPR,
     PREFIX_EXPR,
     LITERAL,
     BIN_EXPR,
+    RANGE_EXPR,
     PAREN_EXPR,
     CALL_EXPR,
+    METHOD_CALL_EXPR,
     FIELD_EXPR,
+    INDEX_EXPR,
+    ARRAY_EXPR,
     IF_EXPR,
     BLOCK_EXPR,
     RETURN_EXPR,
     WHILE_EXPR,
+    FOR_EXPR,
     LOOP_EXPR,
     BREAK_EXPR,
+    CONTINUE_EXPR,
+    LAMBDA_EXPR,
+    MATCH_EXPR,
+    MATCH_ARM_LIST,
+    MATCH_ARM,
     CONDITION,
     BIND_PAT,
     PLACEHOLDER_PAT,
+    PATH_PAT,
+    TUPLE_STRUCT_PAT,
+    RECORD_PAT,
+    RECORD_FIELD_PAT_LIST,
+    RECORD_FIELD_PAT,
     ARG_LIST,
     NAME,
     NAME_REF,
     PATH,
     PATH_SEGMENT,
+    TYPE_ARG_LIST,
+    TYPE_ARG,
     RECORD_LIT,
     RECORD_FIELD_LIST,
     RECORD_FIELD,
@@ -284,6 +324,9 @@ macro_rules! T {
     (->) => {
         $crate::SyntaxKind::THIN_ARROW
     };
+    (=>) => {
+        $crate::SyntaxKind::FAT_ARROW
+    };
     (&&) => {
         $crate::SyntaxKind::AMPAMP
     };
@@ -341,6 +384,9 @@ macro_rules! T {
     (loop) => {
         $crate::SyntaxKind::LOOP_KW
     };
+    (continue) => {
+        $crate::SyntaxKind::CONTINUE_KW
+    };
     (let) => {
         $crate::SyntaxKind::LET_KW
     };
@@ -353,6 +399,12 @@ macro_rules! T {
     (struct) => {
         $crate::SyntaxKind::STRUCT_KW
     };
+    (enum) => {
+        $crate::SyntaxKind::ENUM_KW
+    };
+    (match) => {
+        $crate::SyntaxKind::MATCH_KW
+    };
     (never) => {
         $crate::SyntaxKind::NEVER_KW
     };
@@ -362,6 +414,18 @@ macro_rules! T {
     (type) => {
         $crate::SyntaxKind::TYPE_KW
     };
+    (impl) => {
+        $crate::SyntaxKind::IMPL_KW
+    };
+    (trait) => {
+        $crate::SyntaxKind::TRAIT_KW
+    };
+    (const) => {
+        $crate::SyntaxKind::CONST_KW
+    };
+    (static) => {
+        $crate::SyntaxKind::STATIC_KW
+    };
     (package) => {
         $crate::SyntaxKind::PACKAGE_KW
     };
@@ -408,13 +472,20 @@ impl SyntaxKind {
         | TRUE_KW
         | WHILE_KW
         | LOOP_KW
+        | CONTINUE_KW
         | LET_KW
         | MUT_KW
         | CLASS_KW
         | STRUCT_KW
+        | ENUM_KW
+        | MATCH_KW
         | NEVER_KW
         | PUB_KW
         | TYPE_KW
+        | IMPL_KW
+        | TRAIT_KW
+        | CONST_KW
+        | STATIC_KW
         | PACKAGE_KW
         | SUPER_KW
         | SELF_KW
@@ -468,6 +539,7 @@ impl SyntaxKind {
         | DOTDOTEQ
         | COLONCOLON
         | THIN_ARROW
+        | FAT_ARROW
         | AMPAMP
         | PIPEPIPE
         | SHL
@@ -530,6 +602,7 @@ impl SyntaxKind {
             DOTDOTEQ => &SyntaxInfo { name: "DOTDOTEQ" },
             COLONCOLON => &SyntaxInfo { name: "COLONCOLON" },
             THIN_ARROW => &SyntaxInfo { name: "THIN_ARROW" },
+            FAT_ARROW => &SyntaxInfo { name: "FAT_ARROW" },
             AMPAMP => &SyntaxInfo { name: "AMPAMP" },
             PIPEPIPE => &SyntaxInfo { name: "PIPEPIPE" },
             SHL => &SyntaxInfo { name: "SHL" },
@@ -549,13 +622,20 @@ impl SyntaxKind {
             TRUE_KW => &SyntaxInfo { name: "TRUE_KW" },
             WHILE_KW => &SyntaxInfo { name: "WHILE_KW" },
             LOOP_KW => &SyntaxInfo { name: "LOOP_KW" },
+            CONTINUE_KW => &SyntaxInfo { name: "CONTINUE_KW" },
             LET_KW => &SyntaxInfo { name: "LET_KW" },
             MUT_KW => &SyntaxInfo { name: "MUT_KW" },
             CLASS_KW => &SyntaxInfo { name: "CLASS_KW" },
             STRUCT_KW => &SyntaxInfo { name: "STRUCT_KW" },
+            ENUM_KW => &SyntaxInfo { name: "ENUM_KW" },
+            MATCH_KW => &SyntaxInfo { name: "MATCH_KW" },
             NEVER_KW => &SyntaxInfo { name: "NEVER_KW" },
             PUB_KW => &SyntaxInfo { name: "PUB_KW" },
             TYPE_KW => &SyntaxInfo { name: "TYPE_KW" },
+            IMPL_KW => &SyntaxInfo { name: "IMPL_KW" },
+            TRAIT_KW => &SyntaxInfo { name: "TRAIT_KW" },
+            CONST_KW => &SyntaxInfo { name: "CONST_KW" },
+            STATIC_KW => &SyntaxInfo { name: "STATIC_KW" },
             PACKAGE_KW => &SyntaxInfo { name: "PACKAGE_KW" },
             SUPER_KW => &SyntaxInfo { name: "SUPER_KW" },
             SELF_KW => &SyntaxInfo { name: "SELF_KW" },
@@ -577,38 +657,70 @@ impl SyntaxKind {
             VISIBILITY => &SyntaxInfo { name: "VISIBILITY" },
             PARAM_LIST => &SyntaxInfo { name: "PARAM_LIST" },
             PARAM => &SyntaxInfo { name: "PARAM" },
+            SELF_PARAM => &SyntaxInfo { name: "SELF_PARAM" },
+            TYPE_PARAM_LIST => &SyntaxInfo { name: "TYPE_PARAM_LIST" },
+            TYPE_PARAM => &SyntaxInfo { name: "TYPE_PARAM" },
+            TYPE_BOUND_LIST => &SyntaxInfo { name: "TYPE_BOUND_LIST" },
+            TYPE_BOUND => &SyntaxInfo { name: "TYPE_BOUND" },
             STRUCT_DEF => &SyntaxInfo { name: "STRUCT_DEF" },
             TYPE_ALIAS_DEF => &SyntaxInfo { name: "TYPE_ALIAS_DEF" },
+            CONST_DEF => &SyntaxInfo { name: "CONST_DEF" },
+            STATIC_DEF => &SyntaxInfo { name: "STATIC_DEF" },
             MEMORY_TYPE_SPECIFIER => &SyntaxInfo { name: "MEMORY_TYPE_SPECIFIER" },
             RECORD_FIELD_DEF_LIST => &SyntaxInfo { name: "RECORD_FIELD_DEF_LIST" },
             RECORD_FIELD_DEF => &SyntaxInfo { name: "RECORD_FIELD_DEF" },
             TUPLE_FIELD_DEF_LIST => &SyntaxInfo { name: "TUPLE_FIELD_DEF_LIST" },
             TUPLE_FIELD_DEF => &SyntaxInfo { name: "TUPLE_FIELD_DEF" },
+            ENUM_DEF => &SyntaxInfo { name: "ENUM_DEF" },
+            ENUM_VARIANT_LIST => &SyntaxInfo { name: "ENUM_VARIANT_LIST" },
+            ENUM_VARIANT => &SyntaxInfo { name: "ENUM_VARIANT" },
+            IMPL_DEF => &SyntaxInfo { name: "IMPL_DEF" },
+            TRAIT_DEF => &SyntaxInfo { name: "TRAIT_DEF" },
+            ITEM_LIST => &SyntaxInfo { name: "ITEM_LIST" },
             PATH_TYPE => &SyntaxInfo { name: "PATH_TYPE" },
             NEVER_TYPE => &SyntaxInfo { name: "NEVER_TYPE" },
+            ARRAY_TYPE => &SyntaxInfo { name: "ARRAY_TYPE" },
+            FN_TYPE => &SyntaxInfo { name: "FN_TYPE" },
             LET_STMT => &SyntaxInfo { name: "LET_STMT" },
             EXPR_STMT => &SyntaxInfo { name: "EXPR_STMT" },
             PATH_EXPR => &SyntaxInfo { name: "PATH_EXPR" },
             PREFIX_EXPR => &SyntaxInfo { name: "PREFIX_EXPR" },
             LITERAL => &SyntaxInfo { name: "LITERAL" },
             BIN_EXPR => &SyntaxInfo { name: "BIN_EXPR" },
+            RANGE_EXPR => &SyntaxInfo { name: "RANGE_EXPR" },
             PAREN_EXPR => &SyntaxInfo { name: "PAREN_EXPR" },
             CALL_EXPR => &SyntaxInfo { name: "CALL_EXPR" },
+            METHOD_CALL_EXPR => &SyntaxInfo { name: "METHOD_CALL_EXPR" },
             FIELD_EXPR => &SyntaxInfo { name: "FIELD_EXPR" },
+            INDEX_EXPR => &SyntaxInfo { name: "INDEX_EXPR" },
+            ARRAY_EXPR => &SyntaxInfo { name: "ARRAY_EXPR" },
             IF_EXPR => &SyntaxInfo { name: "IF_EXPR" },
             BLOCK_EXPR => &SyntaxInfo { name: "BLOCK_EXPR" },
             RETURN_EXPR => &SyntaxInfo { name: "RETURN_EXPR" },
             WHILE_EXPR => &SyntaxInfo { name: "WHILE_EXPR" },
+            FOR_EXPR => &SyntaxInfo { name: "FOR_EXPR" },
             LOOP_EXPR => &SyntaxInfo { name: "LOOP_EXPR" },
             BREAK_EXPR => &SyntaxInfo { name: "BREAK_EXPR" },
+            CONTINUE_EXPR => &SyntaxInfo { name: "CONTINUE_EXPR" },
+            LAMBDA_EXPR => &SyntaxInfo { name: "LAMBDA_EXPR" },
+            MATCH_EXPR => &SyntaxInfo { name: "MATCH_EXPR" },
+            MATCH_ARM_LIST => &SyntaxInfo { name: "MATCH_ARM_LIST" },
+            MATCH_ARM => &SyntaxInfo { name: "MATCH_ARM" },
             CONDITION => &SyntaxInfo { name: "CONDITION" },
             BIND_PAT => &SyntaxInfo { name: "BIND_PAT" },
             PLACEHOLDER_PAT => &SyntaxInfo { name: "PLACEHOLDER_PAT" },
+            PATH_PAT => &SyntaxInfo { name: "PATH_PAT" },
+            TUPLE_STRUCT_PAT => &SyntaxInfo { name: "TUPLE_STRUCT_PAT" },
+            RECORD_PAT => &SyntaxInfo { name: "RECORD_PAT" },
+            RECORD_FIELD_PAT_LIST => &SyntaxInfo { name: "RECORD_FIELD_PAT_LIST" },
+            RECORD_FIELD_PAT => &SyntaxInfo { name: "RECORD_FIELD_PAT" },
             ARG_LIST => &SyntaxInfo { name: "ARG_LIST" },
             NAME => &SyntaxInfo { name: "NAME" },
             NAME_REF => &SyntaxInfo { name: "NAME_REF" },
             PATH => &SyntaxInfo { name: "PATH" },
             PATH_SEGMENT => &SyntaxInfo { name: "PATH_SEGMENT" },
+            TYPE_ARG_LIST => &SyntaxInfo { name: "TYPE_ARG_LIST" },
+            TYPE_ARG => &SyntaxInfo { name: "TYPE_ARG" },
             RECORD_LIT => &SyntaxInfo { name: "RECORD_LIT" },
             RECORD_FIELD_LIST => &SyntaxInfo { name: "RECORD_FIELD_LIST" },
             RECORD_FIELD => &SyntaxInfo { name: "RECORD_FIELD" },
@@ -639,13 +751,20 @@ impl SyntaxKind {
             "true" => TRUE_KW,
             "while" => WHILE_KW,
             "loop" => LOOP_KW,
+            "continue" => CONTINUE_KW,
             "let" => LET_KW,
             "mut" => MUT_KW,
             "class" => CLASS_KW,
             "struct" => STRUCT_KW,
+            "enum" => ENUM_KW,
+            "match" => MATCH_KW,
             "never" => NEVER_KW,
             "pub" => PUB_KW,
             "type" => TYPE_KW,
+            "impl" => IMPL_KW,
+            "trait" => TRAIT_KW,
+            "const" => CONST_KW,
+            "static" => STATIC_KW,
             "package" => PACKAGE_KW,
             "super" => SUPER_KW,
             "self" => SELF_KW,
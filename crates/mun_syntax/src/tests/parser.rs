@@ -284,6 +284,58 @@ fn break_expr() {
     )
 }
 
+#[test]
+fn continue_expr() {
+    snapshot_test(
+        r#"
+    fn foo() {
+        continue;
+        loop { continue; }
+        while true { if false { continue } }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn generic_items() {
+    snapshot_test(
+        r#"
+    struct Pair<A, B> {
+        a: A,
+        b: B,
+    }
+    struct Wrapper<T>(T);
+    fn swap<A, B>(p: Pair<A, B>) -> Pair<B, A> {
+        let w: Wrapper<Pair<A, B>> = Wrapper(p);
+    }
+    "#,
+    )
+}
+
+#[test]
+fn lambda_expr() {
+    snapshot_test(
+        r#"
+    fn foo(f: fn(i32) -> i32, g: fn()) {
+        let a = || 5;
+        let b = |x: i32, y| x + y;
+        let c = |x| -> i32 { x };
+    }
+    "#,
+    )
+}
+
+#[test]
+fn const_and_static_def() {
+    snapshot_test(
+        r#"
+    const FOO: i32 = 5;
+    pub static BAR: f64 = 1.0 + 2.0;
+    "#,
+    )
+}
+
 #[test]
 fn while_expr() {
     snapshot_test(
@@ -296,6 +348,19 @@ fn while_expr() {
     )
 }
 
+#[test]
+fn for_expr() {
+    snapshot_test(
+        r#"
+    fn foo() {
+        for i in 0..10 {};
+        for i in 0..=n { break; };
+        for _ in a+1..b*2 {};
+    }
+    "#,
+    )
+}
+
 #[test]
 fn struct_lit() {
     snapshot_test(
@@ -331,6 +396,82 @@ fn struct_field_index() {
     )
 }
 
+#[test]
+fn arrays() {
+    snapshot_test(
+        r#"
+    fn foo(a: [i32], b: [[f64]]) -> [bool] {
+        let c: [u8] = [];
+        let d = [1, 2, 3,];
+        a[0] = d[1 + 2];
+        b[0][1]
+    }
+    "#,
+    )
+}
+
+#[test]
+fn enum_def() {
+    snapshot_test(
+        r#"
+    enum Foo {}
+    enum Foo { A, B }
+    pub enum Foo {
+        A,
+        B(f64),
+        C { a: f64, b: i32, },
+    }
+    "#,
+    )
+}
+
+#[test]
+fn match_expr() {
+    snapshot_test(
+        r#"
+    fn foo(s: State) -> i32 {
+        match s {
+            State::Idle => 0,
+            State::Walking(_, b) => { b }
+            State::Attacking { target: t, range, .. } => t,
+            _ => 1
+        }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn impl_def() {
+    snapshot_test(
+        r#"
+    impl Foo {
+        fn new() -> Self {
+            Foo::create()
+        }
+        pub fn bar(self, a: i32) -> i32 {
+            self.baz(a).c
+        }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn trait_def() {
+    snapshot_test(
+        r#"
+    trait Update {
+        fn update(self, dt: f32);
+    }
+    impl Update for Foo {
+        fn update(self, dt: f32) {}
+    }
+    fn tick<T: Update + Draw, U>(a: T) {}
+    "#,
+    )
+}
+
 #[test]
 fn memory_type_specifier() {
     snapshot_test(
@@ -1,5 +1,6 @@
 mod apple_base;
 mod linux_base;
+mod wasm_base;
 mod windows_msvc_base;
 use crate::host_triple;
 use thiserror::Error;
@@ -9,6 +10,7 @@ pub enum LinkerFlavor {
     Ld,
     Ld64,
     Msvc,
+    Wasm,
 }
 
 /// Everything Mun knows about a target.
@@ -68,6 +70,10 @@ pub struct TargetOptions {
     /// Whether the target toolchain is like Windows
     pub is_like_windows: bool,
     pub is_like_msvc: bool,
+
+    /// Whether the target is WebAssembly. Assemblies for such a target are wasm modules that are
+    /// executed in a sandbox instead of being loaded as shared libraries.
+    pub is_like_wasm: bool,
 }
 
 impl Default for TargetOptions {
@@ -79,6 +85,7 @@ impl Default for TargetOptions {
             dll_prefix: "lib".to_string(),
             is_like_windows: false,
             is_like_msvc: false,
+            is_like_wasm: false,
         }
     }
 }
@@ -128,6 +135,8 @@ macro_rules! supported_targets {
 }
 
 supported_targets!(
+    ("aarch64-unknown-linux-gnu", aarch64_unknown_linux_gnu),
+    ("wasm32-unknown-unknown", wasm32_unknown_unknown),
     ("x86_64-apple-darwin", x86_64_apple_darwin),
     ("x86_64-pc-windows-msvc", x86_64_pc_windows_msvc),
     ("x86_64-unknown-linux-gnu", x86_64_unknown_linux_gnu),
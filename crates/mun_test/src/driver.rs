@@ -1,9 +1,9 @@
 use compiler::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
+use parking_lot::{RwLock, RwLockReadGuard};
 use runtime::{Runtime, RuntimeBuilder};
 use std::{
-    cell::{Ref, RefCell},
     path::{Path, PathBuf},
-    rc::Rc,
+    sync::Arc,
     thread::sleep,
     time::{Duration, Instant},
 };
@@ -127,7 +127,7 @@ impl std::fmt::Debug for CompileTestDriver {
 /// This allows testing of Mun constructs that depend on hot-reloading.
 pub struct CompileAndRunTestDriver {
     driver: CompileTestDriver,
-    runtime: Rc<RefCell<Runtime>>,
+    runtime: Arc<RwLock<Runtime>>,
 }
 
 impl std::fmt::Debug for CompileAndRunTestDriver {
@@ -173,7 +173,7 @@ impl CompileAndRunTestDriver {
     /// the `Runtime`.
     pub fn update(
         &mut self,
-        runtime: Ref<'_, Runtime>,
+        runtime: RwLockReadGuard<'_, Runtime>,
         path: impl AsRef<paths::RelativePath>,
         text: &str,
     ) {
@@ -181,7 +181,7 @@ impl CompileAndRunTestDriver {
 
         let start_time = Instant::now();
         drop(runtime);
-        while !self.runtime().borrow_mut().update() {
+        while !self.runtime().write().update() {
             let now = Instant::now();
             if now - start_time > Duration::from_secs(10) {
                 panic!("runtime did not update after recompilation within 10 seconds");
@@ -191,8 +191,14 @@ impl CompileAndRunTestDriver {
         }
     }
 
+    /// Updates the text of the Mun source file at `path` and recompiles the library, without
+    /// waiting for the runtime to reload it.
+    pub fn update_source(&mut self, path: impl AsRef<paths::RelativePath>, text: &str) {
+        self.driver.update(path, text);
+    }
+
     /// Returns the `Runtime` used by the driver.
-    pub fn runtime(&self) -> Rc<RefCell<Runtime>> {
+    pub fn runtime(&self) -> Arc<RwLock<Runtime>> {
         self.runtime.clone()
     }
 }